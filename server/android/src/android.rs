@@ -63,6 +63,8 @@ pub mod android {
                 LoggingSettings::new(LogMode::File, service::settings::Level::Info)
                     .with_directory(files_dir.to_string_lossy().to_string()),
             ),
+            cold_chain: None,
+            notifications: None,
         };
 
         logging_init(settings.logging.clone(), None);
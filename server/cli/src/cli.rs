@@ -19,8 +19,12 @@ use service::{
     service_provider::{ServiceContext, ServiceProvider},
     settings::Settings,
     sync::{
-        file_sync_driver::FileSyncDriver, settings::SyncSettings, sync_status::logger::SyncLogger,
-        synchroniser::integrate_and_translate_sync_buffer, synchroniser_driver::SynchroniserDriver,
+        file_sync_driver::FileSyncDriver,
+        settings::SyncSettings,
+        sync_bundle::{export_push_bundle, import_sync_bundle},
+        sync_status::logger::SyncLogger,
+        synchroniser::integrate_and_translate_sync_buffer,
+        synchroniser_driver::SynchroniserDriver,
     },
     token_bucket::TokenBucket,
 };
@@ -85,6 +89,25 @@ enum Action {
         #[clap(short, long, action = ArgAction::SetTrue)]
         enable_sync: bool,
     },
+    /// Export pending sync records as a push bundle file, to be imported on open-mSupply central server (for sites without a network connection)
+    ExportSyncBundle {
+        /// Path of the push bundle file
+        #[clap(short, long)]
+        path: String,
+    },
+    /// Import a sync bundle file. On central server a push bundle is imported and a pull bundle is created for the remote site,
+    /// on remote site a pull bundle is imported and sync cursors are advanced
+    ImportSyncBundle {
+        /// Path of the bundle file
+        #[clap(short, long)]
+        path: String,
+        /// Sync password of the remote site, required for push bundle
+        #[clap(short, long)]
+        site_password: Option<String>,
+        /// Path of the pull bundle file to create, when importing a push bundle
+        #[clap(short, long)]
+        reply_path: Option<String>,
+    },
 
     SignPlugin {
         /// Path to the plugin.
@@ -356,12 +379,57 @@ async fn main() -> anyhow::Result<()> {
 
             info!("Refresh data result: {:#?}", result);
         }
+        Action::ExportSyncBundle { path } => {
+            let service_provider = service_provider(settings)?;
+
+            info!("Exporting sync bundle");
+            let export = export_push_bundle(&service_provider)?;
+            fs::write(&path, export.bundle)?;
+            info!(
+                "Exported {} records to push bundle {}",
+                export.number_of_records, path
+            );
+            if export.has_more {
+                info!("More records are pending, export another bundle once the reply is imported");
+            }
+        }
+        Action::ImportSyncBundle {
+            path,
+            site_password,
+            reply_path,
+        } => {
+            let service_provider = service_provider(settings)?;
+
+            info!("Importing sync bundle {}", path);
+            let result =
+                import_sync_bundle(&service_provider, &fs::read(&path)?, site_password).await?;
+            info!(
+                "Imported {} records from {:?} bundle",
+                result.number_of_records, result.r#type
+            );
+
+            if let Some(reply) = result.reply {
+                let reply_path = reply_path.unwrap_or_else(|| format!("{}.pull", path));
+                fs::write(&reply_path, reply)?;
+                info!("Pull bundle saved in {}", reply_path);
+            }
+        }
         Action::SignPlugin { path, key, cert } => sign_plugin(&path, &key, &cert)?,
     }
 
     Ok(())
 }
 
+fn service_provider(settings: Settings) -> anyhow::Result<ServiceProvider> {
+    let connection_manager = get_storage_connection_manager(&settings.database);
+    let app_data_folder = settings
+        .server
+        .base_dir
+        .ok_or(anyhow!("based dir not set in yaml configurations"))?;
+
+    Ok(ServiceProvider::new(connection_manager, &app_data_folder))
+}
+
 fn export_paths(name: &str) -> (PathBuf, PathBuf, PathBuf) {
     let export_folder = Path::new(DATA_EXPORT_FOLDER).join(name);
     let export_file_path = export_folder.join("export.json");
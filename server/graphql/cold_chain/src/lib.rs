@@ -2,23 +2,34 @@ pub mod mutations;
 pub(crate) mod types;
 
 use async_graphql::*;
+use chrono::{DateTime, Utc};
 use graphql_core::{
     pagination::PaginationInput,
     standard_graphql_error::{validate_auth, StandardGraphqlError},
     ContextExt,
 };
-use mutations::{update_sensor, UpdateSensorInput, UpdateSensorResponse};
+use graphql_types::types::StockLineConnector;
+use mutations::{
+    hold_temperature_breach_stock, update_sensor, HoldTemperatureBreachStockInput,
+    HoldTemperatureBreachStockResponse, UpdateSensorInput, UpdateSensorResponse,
+};
 use repository::{
     temperature_breach::TemperatureBreachFilter, EqualFilter, PaginationOption, SensorFilter,
     TemperatureBreachSortField,
 };
 use repository::{temperature_log::TemperatureLogFilter, TemperatureBreachSort};
-use service::auth::{Resource, ResourceAccessRequest};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    cold_chain::temperature_chart::TemperatureChartInput,
+};
 use types::{
     sensor::{SensorConnector, SensorFilterInput, SensorsResponse},
     temperature_breach::{
         TemperatureBreachConnector, TemperatureBreachFilterInput, TemperatureBreachSortInput,
-        TemperatureBreachesResponse,
+        TemperatureBreachStockResponse, TemperatureBreachesResponse,
+    },
+    temperature_chart::{
+        TemperatureChartFilterInput, TemperatureChartNode, TemperatureChartResponse,
     },
     temperature_log::{
         TemperatureLogConnector, TemperatureLogFilterInput, TemperatureLogSortInput,
@@ -172,6 +183,79 @@ impl ColdChainQueries {
         ))
     }
 
+    /// Stock lines that were in the location of a temperature breach during the breach
+    pub async fn temperature_breach_stock(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        temperature_breach_id: String,
+    ) -> Result<TemperatureBreachStockResponse> {
+        let user = validate_auth(
+            ctx,
+            &ResourceAccessRequest {
+                resource: Resource::QueryTemperatureBreach,
+                store_id: Some(store_id.clone()),
+            },
+        )?;
+
+        let service_provider = ctx.service_provider();
+        let service_context = service_provider.context(store_id.clone(), user.user_id)?;
+
+        let stock_lines = service_provider
+            .cold_chain_service
+            .get_temperature_breach_stock(&service_context, &temperature_breach_id)
+            .map_err(mutations::temperature_breach_stock::map_error)?;
+
+        Ok(TemperatureBreachStockResponse::Response(
+            StockLineConnector::from_vec(stock_lines),
+        ))
+    }
+
+    /// Temperatures of the store's sensors over a period, split into `number_of_data_points`
+    /// intervals. Combines raw temperature logs with the hourly aggregates of logs past the
+    /// retention period
+    pub async fn temperature_chart(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        from_datetime: DateTime<Utc>,
+        to_datetime: DateTime<Utc>,
+        number_of_data_points: u32,
+        filter: Option<TemperatureChartFilterInput>,
+    ) -> Result<TemperatureChartResponse> {
+        let user = validate_auth(
+            ctx,
+            &ResourceAccessRequest {
+                resource: Resource::QueryTemperatureLog,
+                store_id: Some(store_id.clone()),
+            },
+        )?;
+
+        let service_provider = ctx.service_provider();
+        let service_context = service_provider.context(store_id.clone(), user.user_id)?;
+
+        let (sensor_ids, location_ids) = filter
+            .map(|filter| (filter.sensor_ids, filter.location_ids))
+            .unwrap_or_default();
+        let temperature_chart = service_provider
+            .cold_chain_service
+            .get_temperature_chart(
+                &service_context,
+                TemperatureChartInput {
+                    from_datetime: from_datetime.naive_utc(),
+                    to_datetime: to_datetime.naive_utc(),
+                    number_of_data_points,
+                    sensor_ids,
+                    location_ids,
+                },
+            )
+            .map_err(types::temperature_chart::map_error)?;
+
+        Ok(TemperatureChartResponse::Response(TemperatureChartNode {
+            temperature_chart,
+        }))
+    }
+
     /// Query omSupply "sensor" entries
     pub async fn sensors(
         &self,
@@ -239,6 +323,15 @@ impl ColdChainMutations {
     ) -> Result<UpdateSensorResponse> {
         update_sensor(ctx, &store_id, input)
     }
+
+    async fn hold_temperature_breach_stock(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: HoldTemperatureBreachStockInput,
+    ) -> Result<HoldTemperatureBreachStockResponse> {
+        hold_temperature_breach_stock(ctx, &store_id, input)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,63 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::StockLineConnector;
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    cold_chain::temperature_breach_stock::TemperatureBreachStockError as ServiceError,
+};
+
+#[derive(InputObject)]
+pub struct HoldTemperatureBreachStockInput {
+    /// Temperature breach id
+    pub id: String,
+}
+
+#[derive(Union)]
+pub enum HoldTemperatureBreachStockResponse {
+    Response(StockLineConnector),
+}
+
+/// Places all stock lines that were in the location of the breach during the breach on hold
+pub fn hold_temperature_breach_stock(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: HoldTemperatureBreachStockInput,
+) -> Result<HoldTemperatureBreachStockResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateStockLine,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    match service_provider
+        .cold_chain_service
+        .hold_temperature_breach_stock(&service_context, &input.id)
+    {
+        Ok(stock_lines) => Ok(HoldTemperatureBreachStockResponse::Response(
+            StockLineConnector::from_vec(stock_lines),
+        )),
+        Err(error) => Err(map_error(error)),
+    }
+}
+
+pub fn map_error(error: ServiceError) -> async_graphql::Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        ServiceError::TemperatureBreachDoesNotExist
+        | ServiceError::TemperatureBreachDoesNotBelongToCurrentStore
+        | ServiceError::TemperatureBreachHasNoLocation => BadUserInput(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
@@ -1,4 +1,5 @@
 pub(crate) mod sensor;
 pub(crate) mod temperature_breach;
+pub(crate) mod temperature_chart;
 pub(crate) mod temperature_log;
 pub(crate) mod temperature_notification;
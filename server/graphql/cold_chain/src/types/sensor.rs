@@ -71,6 +71,8 @@ pub enum SensorNodeType {
     BlueMaestro,
     Laird,
     Berlinger,
+    LogTag,
+    Csv,
 }
 
 #[Object]
@@ -252,6 +254,8 @@ impl SensorNodeType {
             from::BlueMaestro => to::BlueMaestro,
             from::Laird => to::Laird,
             from::Berlinger => to::Berlinger,
+            from::LogTag => to::LogTag,
+            from::Csv => to::Csv,
         }
     }
 
@@ -263,6 +267,8 @@ impl SensorNodeType {
             from::BlueMaestro => to::BlueMaestro,
             from::Laird => to::Laird,
             from::Berlinger => to::Berlinger,
+            from::LogTag => to::LogTag,
+            from::Csv => to::Csv,
         }
     }
 }
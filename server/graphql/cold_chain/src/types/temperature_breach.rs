@@ -9,7 +9,7 @@ use graphql_core::{
     ContextExt,
 };
 
-use graphql_types::types::{LocationFilterInput, LocationNode};
+use graphql_types::types::{LocationFilterInput, LocationNode, StockLineConnector};
 use repository::{
     temperature_breach::{
         TemperatureBreach, TemperatureBreachFilter, TemperatureBreachSort,
@@ -201,6 +201,11 @@ pub enum TemperatureBreachResponse {
     Response(TemperatureBreachNode),
 }
 
+#[derive(Union)]
+pub enum TemperatureBreachStockResponse {
+    Response(StockLineConnector),
+}
+
 impl TemperatureBreachNode {
     pub fn from_domain(temperature_breach: TemperatureBreach) -> TemperatureBreachNode {
         TemperatureBreachNode { temperature_breach }
@@ -0,0 +1,125 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use dataloader::DataLoader;
+use graphql_core::{
+    loader::SensorByIdLoader, standard_graphql_error::StandardGraphqlError, ContextExt,
+};
+use service::cold_chain::temperature_chart::{
+    SensorTemperaturePoints, TemperatureChart, TemperatureChartError as ServiceError,
+    TemperaturePoint,
+};
+
+use super::sensor::SensorNode;
+
+#[derive(InputObject, Clone)]
+pub struct TemperatureChartFilterInput {
+    pub sensor_ids: Option<Vec<String>>,
+    pub location_ids: Option<Vec<String>>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct TemperatureChartNode {
+    pub temperature_chart: TemperatureChart,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct SensorTemperaturePointsNode {
+    pub sensor_temperature_points: SensorTemperaturePoints,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct TemperaturePointNode {
+    pub temperature_point: TemperaturePoint,
+}
+
+#[Object]
+impl TemperatureChartNode {
+    /// Length of the interval each point covers
+    pub async fn interval_seconds(&self) -> i64 {
+        self.temperature_chart.interval_seconds
+    }
+
+    pub async fn sensors(&self) -> Vec<SensorTemperaturePointsNode> {
+        self.temperature_chart
+            .sensors
+            .iter()
+            .cloned()
+            .map(|sensor_temperature_points| SensorTemperaturePointsNode {
+                sensor_temperature_points,
+            })
+            .collect()
+    }
+}
+
+#[Object]
+impl SensorTemperaturePointsNode {
+    pub async fn sensor_id(&self) -> &str {
+        &self.sensor_temperature_points.sensor_id
+    }
+
+    pub async fn sensor(&self, ctx: &Context<'_>) -> Result<Option<SensorNode>> {
+        let loader = ctx.get_loader::<DataLoader<SensorByIdLoader>>();
+
+        Ok(loader
+            .load_one(self.sensor_temperature_points.sensor_id.clone())
+            .await?
+            .map(SensorNode::from_domain))
+    }
+
+    /// Intervals without temperature logs are omitted
+    pub async fn points(&self) -> Vec<TemperaturePointNode> {
+        self.sensor_temperature_points
+            .points
+            .iter()
+            .cloned()
+            .map(|temperature_point| TemperaturePointNode { temperature_point })
+            .collect()
+    }
+}
+
+#[Object]
+impl TemperaturePointNode {
+    /// Start of the interval
+    pub async fn datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_naive_utc_and_offset(self.temperature_point.datetime, Utc)
+    }
+
+    pub async fn minimum_temperature(&self) -> f64 {
+        self.temperature_point.minimum_temperature
+    }
+
+    pub async fn maximum_temperature(&self) -> f64 {
+        self.temperature_point.maximum_temperature
+    }
+
+    pub async fn mean_temperature(&self) -> f64 {
+        self.temperature_point.mean_temperature
+    }
+
+    pub async fn number_of_logs(&self) -> i32 {
+        self.temperature_point.number_of_logs
+    }
+
+    pub async fn temperature_breach_ids(&self) -> &Vec<String> {
+        &self.temperature_point.temperature_breach_ids
+    }
+}
+
+#[derive(Union)]
+pub enum TemperatureChartResponse {
+    Response(TemperatureChartNode),
+}
+
+pub fn map_error(error: ServiceError) -> async_graphql::Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        ServiceError::ToDatetimeBeforeFromDatetime | ServiceError::NumberOfDataPointsOutOfRange => {
+            BadUserInput(formatted_error)
+        }
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
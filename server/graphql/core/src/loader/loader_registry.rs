@@ -118,6 +118,13 @@ pub async fn get_loaders(
         async_std::task::spawn,
     );
 
+    let stocktake_by_id_loader = DataLoader::new(
+        StocktakeByIdLoader {
+            connection_manager: connection_manager.clone(),
+        },
+        async_std::task::spawn,
+    );
+
     let stocktake_line_loader = DataLoader::new(
         StocktakeLineByStocktakeIdLoader {
             connection_manager: connection_manager.clone(),
@@ -132,6 +139,13 @@ pub async fn get_loaders(
         async_std::task::spawn,
     );
 
+    let requisition_approvals_by_requisition_id_loader = DataLoader::new(
+        RequisitionApprovalsByRequisitionIdLoader {
+            connection_manager: connection_manager.clone(),
+        },
+        async_std::task::spawn,
+    );
+
     let requisition_line_by_requisition_id_loader = DataLoader::new(
         RequisitionLinesByRequisitionIdLoader {
             service_provider: service_provider.clone(),
@@ -258,9 +272,11 @@ pub async fn get_loaders(
     loaders.insert(user_account_loader);
     loaders.insert(location_by_id_loader);
     loaders.insert(requisitions_by_id_loader);
+    loaders.insert(requisition_approvals_by_requisition_id_loader);
     loaders.insert(requisition_line_by_requisition_id_loader);
     loaders.insert(requisition_line_by_linked_requisition_line_id_loader);
     loaders.insert(item_stats_for_item_loader);
+    loaders.insert(stocktake_by_id_loader);
     loaders.insert(stocktake_line_loader);
     loaders.insert(requisition_line_supply_status_loader);
     loaders.insert(requisition_lines_remaining_to_supply_loader);
@@ -371,5 +387,12 @@ pub async fn get_loaders(
         async_std::task::spawn,
     ));
 
+    loaders.insert(DataLoader::new(
+        StockLineHeatExposureLoader {
+            service_provider: service_provider.clone(),
+        },
+        async_std::task::spawn,
+    ));
+
     loaders
 }
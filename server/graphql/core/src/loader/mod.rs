@@ -24,11 +24,13 @@ mod name_row;
 mod patient;
 mod program_enrolment;
 mod requisition;
+mod requisition_approval;
 mod requisition_line;
 mod requisition_supply_status;
 mod return_reason;
 mod sensor;
 mod stock_line;
+mod stocktake;
 mod stocktake_lines;
 mod store;
 mod sync_file_reference;
@@ -67,11 +69,13 @@ pub use name_row::*;
 pub use patient::*;
 pub use program_enrolment::*;
 pub use requisition::*;
+pub use requisition_approval::*;
 pub use requisition_line::*;
 pub use requisition_supply_status::*;
 pub use return_reason::*;
 pub use sensor::*;
 pub use stock_line::*;
+pub use stocktake::*;
 pub use stocktake_lines::*;
 pub use store::*;
 pub use sync_file_reference::*;
@@ -0,0 +1,32 @@
+use async_graphql::dataloader::*;
+use repository::{
+    RepositoryError, RequisitionApprovalRow, RequisitionApprovalRowRepository,
+    StorageConnectionManager,
+};
+use std::collections::HashMap;
+
+pub struct RequisitionApprovalsByRequisitionIdLoader {
+    pub connection_manager: StorageConnectionManager,
+}
+
+impl Loader<String> for RequisitionApprovalsByRequisitionIdLoader {
+    type Value = Vec<RequisitionApprovalRow>;
+    type Error = RepositoryError;
+
+    async fn load(
+        &self,
+        requisition_ids: &[String],
+    ) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let connection = self.connection_manager.connection()?;
+        let repo = RequisitionApprovalRowRepository::new(&connection);
+
+        let mut result: HashMap<String, Self::Value> = HashMap::new();
+        for approval in repo.find_many_by_requisition_ids(requisition_ids)? {
+            result
+                .entry(approval.requisition_id.clone())
+                .or_default()
+                .push(approval);
+        }
+        Ok(result)
+    }
+}
@@ -3,8 +3,10 @@ use repository::{
     RepositoryError, StockLine, StockLineFilter, StockLineRepository, StorageConnectionManager,
 };
 
+use actix_web::web::Data;
 use async_graphql::dataloader::*;
 use async_graphql::*;
+use service::{cold_chain::heat_exposure::HeatExposure, service_provider::ServiceProvider};
 use std::collections::HashMap;
 
 use super::IdPair;
@@ -123,3 +125,24 @@ impl Loader<String> for StockLineByIdLoader {
             .collect())
     }
 }
+
+pub struct StockLineHeatExposureLoader {
+    pub service_provider: Data<ServiceProvider>,
+}
+
+impl Loader<String> for StockLineHeatExposureLoader {
+    type Value = HeatExposure;
+    type Error = async_graphql::Error;
+
+    async fn load(
+        &self,
+        stock_line_ids: &[String],
+    ) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let service_context = self.service_provider.basic_context()?;
+
+        Ok(self
+            .service_provider
+            .cold_chain_service
+            .get_stock_line_heat_exposures(&service_context, stock_line_ids.to_owned())?)
+    }
+}
@@ -0,0 +1,24 @@
+use async_graphql::dataloader::*;
+use async_graphql::*;
+use repository::{RepositoryError, StocktakeRow, StocktakeRowRepository, StorageConnectionManager};
+use std::collections::HashMap;
+
+pub struct StocktakeByIdLoader {
+    pub connection_manager: StorageConnectionManager,
+}
+
+impl Loader<String> for StocktakeByIdLoader {
+    type Value = StocktakeRow;
+    type Error = RepositoryError;
+
+    async fn load(&self, ids: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let connection = self.connection_manager.connection()?;
+        let repo = StocktakeRowRepository::new(&connection);
+
+        Ok(repo
+            .find_many_by_id(ids)?
+            .into_iter()
+            .map(|stocktake| (stocktake.id.clone(), stocktake))
+            .collect())
+    }
+}
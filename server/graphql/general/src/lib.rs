@@ -6,6 +6,7 @@ pub mod types;
 pub use self::queries::sync_status::*;
 use self::queries::*;
 
+use chrono::NaiveDate;
 use graphql_core::pagination::PaginationInput;
 use service::sync::CentralServerConfig;
 
@@ -16,9 +17,17 @@ use graphql_types::types::{
 use mutations::{
     barcode::{insert_barcode, BarcodeInput},
     common::SyncSettingsInput,
+    cycle_count::{
+        generate_cycle_count, upsert_cycle_count_plan, GenerateCycleCountResponse,
+        UpsertCycleCountPlanInput, UpsertCycleCountPlanResponse,
+    },
     display_settings::{
         update_display_settings, DisplaySettingsInput, UpdateDisplaySettingsResponse,
     },
+    expiry_management::{
+        create_redistribution_shipment, CreateRedistributionShipmentInput,
+        CreateRedistributionShipmentResponse,
+    },
     initialise_site::{initialise_site, InitialiseSiteResponse},
     label_printer_settings::{
         update_label_printer_settings, LabelPrinterSettingsInput,
@@ -26,17 +35,42 @@ use mutations::{
     },
     log::{update_log_level, LogLevelInput, UpsertLogLevelResponse},
     manual_sync::manual_sync,
+    notification::{
+        delete_notification_subscription, upsert_notification_subscription,
+        DeleteNotificationSubscriptionResponse, UpsertNotificationSubscriptionInput,
+        UpsertNotificationSubscriptionResponse,
+    },
+    sync_buffer_error::{
+        purge_sync_buffer_rows, reintegrate_sync_buffer_rows, PurgeSyncBufferRowsInput,
+        PurgeSyncBufferRowsResponse, ReintegrateSyncBufferRowsInput,
+        ReintegrateSyncBufferRowsResponse,
+    },
+    sync_bundle::{
+        export_sync_bundle, import_sync_bundle_file, ExportSyncBundleResponse,
+        ImportSyncBundleInput, ImportSyncBundleResponse,
+    },
     sync_settings::{update_sync_settings, UpdateSyncSettingsResponse},
+    update_item_heat_stability::{
+        update_item_heat_stability, UpdateItemHeatStabilityInput, UpdateItemHeatStabilityResponse,
+    },
     update_name_properties::{
         update_name_properties, UpdateNamePropertiesInput, UpdateNamePropertiesResponse,
     },
     update_user,
 };
 use queries::{
+    changelog_consumer::{
+        changelog_consumer_dead_letters, changelog_consumer_statuses,
+        ChangelogConsumerDeadLetterConnector, ChangelogConsumerStatusNode,
+    },
     currency::currencies,
     display_settings::{display_settings, DisplaySettingsHash, DisplaySettingsNode},
     initialisation_status::{initialisation_status, InitialisationStatusNode},
     requisition_line_chart::{ConsumptionOptionsInput, StockEvolutionOptionsInput},
+    sync_buffer_error::{
+        sync_buffer_error_groups, sync_buffer_errors, SyncBufferErrorFilterInput,
+        SyncBufferErrorGroupNode, SyncBufferRowConnector,
+    },
     sync_settings::{sync_settings, SyncSettingsNode},
 };
 
@@ -157,6 +191,19 @@ impl GeneralQueries {
         ledger(ctx, store_id, filter, sort)
     }
 
+    /// Days out of stock per item, reconstructed from the stock ledger. The date range can be at
+    /// most a year (366 days)
+    pub async fn stock_outs(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        from_date: NaiveDate,
+        #[graphql(desc = "Inclusive")] to_date: NaiveDate,
+        #[graphql(desc = "Filter option")] filter: Option<StockOutFilterInput>,
+    ) -> Result<StockOutResponse> {
+        stock_outs(ctx, store_id, from_date, to_date, filter)
+    }
+
     pub async fn invoice_counts(
         &self,
         ctx: &Context<'_>,
@@ -176,6 +223,56 @@ impl GeneralQueries {
         stock_counts(ctx, store_id, timezone_offset, days_till_expired)
     }
 
+    /// Available stock expiring soon (or already expired) with the value at risk of expiring
+    /// before it is used, ordered by expiry date
+    pub async fn near_expiry_stock(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        #[graphql(desc = "Timezone offset")] timezone_offset: Option<i32>,
+        #[graphql(desc = "Filter option")] filter: Option<NearExpiryStockFilterInput>,
+    ) -> Result<NearExpiryStockResponse> {
+        near_expiry_stock(ctx, store_id, timezone_offset, filter)
+    }
+
+    /// Customer stores that could use the surplus of a near expiry stock line
+    pub async fn redistribution_suggestions(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        stock_line_id: String,
+        #[graphql(desc = "Timezone offset")] timezone_offset: Option<i32>,
+    ) -> Result<RedistributionSuggestionsResponse> {
+        redistribution_suggestions(ctx, store_id, stock_line_id, timezone_offset)
+    }
+
+    pub async fn cycle_count_plan(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<Option<CycleCountPlanNode>> {
+        cycle_count_plan(ctx, store_id)
+    }
+
+    /// ABC classification and last counted date of the store's items
+    pub async fn cycle_count_items(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<CycleCountItemsResponse> {
+        cycle_count_items(ctx, store_id)
+    }
+
+    /// Count history of finalised stocktakes, most recent first
+    pub async fn cycle_count_variances(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        item_id: Option<String>,
+    ) -> Result<CycleCountVariancesResponse> {
+        cycle_count_variances(ctx, store_id, item_id)
+    }
+
     pub async fn requisition_line_chart(
         &self,
         ctx: &Context<'_>,
@@ -204,6 +301,20 @@ impl GeneralQueries {
         activity_logs(ctx, page, filter, sort)
     }
 
+    /// Field level changes of the store's invoices, invoice lines, stock lines, stocktake lines
+    /// and requisition lines
+    pub async fn audit_logs(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        #[graphql(desc = "Pagination option (first and offset)")] page: Option<PaginationInput>,
+        #[graphql(desc = "Filter option")] filter: Option<AuditLogFilterInput>,
+        #[graphql(desc = "Sort options (only first sort input is evaluated for this endpoint)")]
+        sort: Option<Vec<AuditLogSortInput>>,
+    ) -> Result<AuditLogResponse> {
+        audit_logs(ctx, store_id, page, filter, sort)
+    }
+
     /// Available without authorisation in operational and initialisation states
     pub async fn initialisation_status(
         &self,
@@ -330,6 +441,42 @@ impl GeneralQueries {
         database_settings(ctx)
     }
 
+    /// Status of the background changelog consumers
+    pub async fn changelog_consumer_statuses(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<ChangelogConsumerStatusNode>> {
+        changelog_consumer_statuses(ctx)
+    }
+
+    /// Changelog records that changelog consumers failed to process, most recent first
+    pub async fn changelog_consumer_dead_letters(
+        &self,
+        ctx: &Context<'_>,
+        consumer_name: Option<String>,
+        #[graphql(desc = "Pagination option (first and offset)")] page: Option<PaginationInput>,
+    ) -> Result<ChangelogConsumerDeadLetterConnector> {
+        changelog_consumer_dead_letters(ctx, consumer_name, page)
+    }
+
+    /// Sync buffer rows that failed integration, grouped by table and error
+    pub async fn sync_buffer_error_groups(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<SyncBufferErrorGroupNode>> {
+        sync_buffer_error_groups(ctx)
+    }
+
+    /// Sync buffer rows that failed integration, most recently received first
+    pub async fn sync_buffer_errors(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<SyncBufferErrorFilterInput>,
+        #[graphql(desc = "Pagination option (first and offset)")] page: Option<PaginationInput>,
+    ) -> Result<SyncBufferRowConnector> {
+        sync_buffer_errors(ctx, filter, page)
+    }
+
     /// Generates new outbound return lines in memory, based on either stock line ids, or an item id.
     /// Optionally includes existing outbound return lines for a specific item in a return.
     /// Provides an friendly shape to edit these lines before calling the insert/update mutations.
@@ -375,6 +522,26 @@ impl GeneralQueries {
     pub async fn name_properties(&self, ctx: &Context<'_>) -> Result<NamePropertyResponse> {
         name_properties(ctx)
     }
+
+    /// Notification subscription rules of the store
+    pub async fn notification_subscriptions(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<NotificationSubscriptionsResponse> {
+        notification_subscriptions(ctx, store_id)
+    }
+
+    /// Queued, sent and failed notifications of the store, most recent first
+    pub async fn notification_outbox(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        #[graphql(desc = "Pagination option (first and offset)")] page: Option<PaginationInput>,
+        #[graphql(desc = "Filter option")] filter: Option<NotificationOutboxFilterInput>,
+    ) -> Result<NotificationOutboxResponse> {
+        notification_outbox(ctx, store_id, page, filter)
+    }
 }
 
 #[derive(Default, Clone)]
@@ -403,6 +570,38 @@ impl GeneralMutations {
         manual_sync(ctx, true)
     }
 
+    /// Export pending sync records as a push bundle file, for sites without a network connection
+    pub async fn export_sync_bundle(&self, ctx: &Context<'_>) -> Result<ExportSyncBundleResponse> {
+        export_sync_bundle(ctx)
+    }
+
+    /// Import a push bundle on central server (replies with a pull bundle) or a pull bundle on remote site
+    pub async fn import_sync_bundle(
+        &self,
+        ctx: &Context<'_>,
+        input: ImportSyncBundleInput,
+    ) -> Result<ImportSyncBundleResponse> {
+        import_sync_bundle_file(ctx, input).await
+    }
+
+    /// Re-run integration of sync buffer rows that failed integration
+    pub async fn reintegrate_sync_buffer_rows(
+        &self,
+        ctx: &Context<'_>,
+        input: ReintegrateSyncBufferRowsInput,
+    ) -> Result<ReintegrateSyncBufferRowsResponse> {
+        reintegrate_sync_buffer_rows(ctx, input)
+    }
+
+    /// Delete failed sync buffer rows that can't be integrated, recording them in the activity log
+    pub async fn purge_sync_buffer_rows(
+        &self,
+        ctx: &Context<'_>,
+        input: PurgeSyncBufferRowsInput,
+    ) -> Result<PurgeSyncBufferRowsResponse> {
+        purge_sync_buffer_rows(ctx, input)
+    }
+
     pub async fn update_display_settings(
         &self,
         ctx: &Context<'_>,
@@ -449,6 +648,54 @@ impl GeneralMutations {
     ) -> Result<UpdateNamePropertiesResponse> {
         update_name_properties(ctx, &store_id, input)
     }
+
+    pub async fn upsert_notification_subscription(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: UpsertNotificationSubscriptionInput,
+    ) -> Result<UpsertNotificationSubscriptionResponse> {
+        upsert_notification_subscription(ctx, &store_id, input)
+    }
+
+    pub async fn delete_notification_subscription(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<DeleteNotificationSubscriptionResponse> {
+        delete_notification_subscription(ctx, &store_id, &id)
+    }
+
+    /// Creates a new outbound shipment with near expiry stock for a customer
+    pub async fn create_redistribution_shipment(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: CreateRedistributionShipmentInput,
+    ) -> Result<CreateRedistributionShipmentResponse> {
+        create_redistribution_shipment(ctx, &store_id, input)
+    }
+
+    pub async fn upsert_cycle_count_plan(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: UpsertCycleCountPlanInput,
+    ) -> Result<UpsertCycleCountPlanResponse> {
+        upsert_cycle_count_plan(ctx, &store_id, input)
+    }
+
+    /// Generates today's cycle count stocktake with the items due for counting, if not already
+    /// generated today
+    pub async fn generate_cycle_count(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        #[graphql(desc = "Timezone offset")] timezone_offset: Option<i32>,
+    ) -> Result<GenerateCycleCountResponse> {
+        generate_cycle_count(ctx, &store_id, timezone_offset)
+    }
 }
 
 /// Auth is not checked during initialisation stage
@@ -521,4 +768,14 @@ impl CentralGeneralMutations {
     ) -> Result<ConfigureNamePropertiesResponse> {
         configure_name_properties(ctx, input)
     }
+
+    /// Heat stability is set on the central server and synced to remote sites with the item
+    pub async fn update_item_heat_stability(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: UpdateItemHeatStabilityInput,
+    ) -> Result<UpdateItemHeatStabilityResponse> {
+        update_item_heat_stability(ctx, &store_id, input)
+    }
 }
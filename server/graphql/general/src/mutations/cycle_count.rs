@@ -0,0 +1,170 @@
+use async_graphql::*;
+use chrono::{NaiveDate, Utc};
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::StocktakeNode;
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    cycle_count::{
+        generate::GenerateCycleCountError,
+        plan::{UpsertCycleCountPlan, UpsertCycleCountPlanError},
+    },
+};
+use util::timezone::offset_to_timezone;
+
+use crate::queries::CycleCountPlanNode;
+
+#[derive(InputObject)]
+pub struct UpsertCycleCountPlanInput {
+    pub id: String,
+    /// Defaults to 80
+    pub class_a_percentage: Option<f64>,
+    /// Defaults to 95
+    pub class_b_percentage: Option<f64>,
+    /// Defaults to 30
+    pub class_a_frequency_days: Option<i32>,
+    /// Defaults to 90
+    pub class_b_frequency_days: Option<i32>,
+    /// Defaults to 180
+    pub class_c_frequency_days: Option<i32>,
+    pub max_items_per_count: Option<i32>,
+    /// Defaults to true
+    pub is_active: Option<bool>,
+}
+
+#[derive(Union)]
+pub enum UpsertCycleCountPlanResponse {
+    Response(CycleCountPlanNode),
+}
+
+#[derive(SimpleObject)]
+pub struct GenerateCycleCountNode {
+    /// Null if today's cycle count was already generated or no items are due for counting
+    pub stocktake: Option<StocktakeNode>,
+}
+
+#[derive(Union)]
+pub enum GenerateCycleCountResponse {
+    Response(GenerateCycleCountNode),
+}
+
+pub fn upsert_cycle_count_plan(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: UpsertCycleCountPlanInput,
+) -> Result<UpsertCycleCountPlanResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateStocktake,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    match service_provider
+        .cycle_count_service
+        .upsert_cycle_count_plan(&service_context, input.to_domain())
+    {
+        Ok(plan) => Ok(UpsertCycleCountPlanResponse::Response(
+            CycleCountPlanNode::from_domain(plan),
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                UpsertCycleCountPlanError::NotThisStorePlan
+                | UpsertCycleCountPlanError::PlanAlreadyExistsForStore
+                | UpsertCycleCountPlanError::InvalidClassPercentages
+                | UpsertCycleCountPlanError::InvalidFrequency
+                | UpsertCycleCountPlanError::InvalidMaxItemsPerCount => {
+                    BadUserInput(formatted_error)
+                }
+                UpsertCycleCountPlanError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn generate_cycle_count(
+    ctx: &Context<'_>,
+    store_id: &str,
+    timezone_offset: Option<i32>,
+) -> Result<GenerateCycleCountResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateStocktake,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let today = today(timezone_offset)?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    match service_provider
+        .cycle_count_service
+        .generate_cycle_count(&service_context, today)
+    {
+        Ok(stocktake) => Ok(GenerateCycleCountResponse::Response(
+            GenerateCycleCountNode {
+                stocktake: stocktake.map(StocktakeNode::from_domain),
+            },
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                GenerateCycleCountError::NoCycleCountPlan
+                | GenerateCycleCountError::PlanIsNotActive
+                | GenerateCycleCountError::InsertStocktakeError(_) => BadUserInput(formatted_error),
+                GenerateCycleCountError::CreatedStocktakeDoesNotExist
+                | GenerateCycleCountError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+fn today(timezone_offset: Option<i32>) -> Result<NaiveDate> {
+    let timezone_offset = offset_to_timezone(&timezone_offset).ok_or(
+        StandardGraphqlError::BadUserInput("Invalid timezone offset".to_string()),
+    )?;
+    Ok(Utc::now().with_timezone(&timezone_offset).date_naive())
+}
+
+impl UpsertCycleCountPlanInput {
+    pub fn to_domain(self) -> UpsertCycleCountPlan {
+        let UpsertCycleCountPlanInput {
+            id,
+            class_a_percentage,
+            class_b_percentage,
+            class_a_frequency_days,
+            class_b_frequency_days,
+            class_c_frequency_days,
+            max_items_per_count,
+            is_active,
+        } = self;
+        let default = UpsertCycleCountPlan::default();
+
+        UpsertCycleCountPlan {
+            id,
+            class_a_percentage: class_a_percentage.unwrap_or(default.class_a_percentage),
+            class_b_percentage: class_b_percentage.unwrap_or(default.class_b_percentage),
+            class_a_frequency_days: class_a_frequency_days
+                .unwrap_or(default.class_a_frequency_days),
+            class_b_frequency_days: class_b_frequency_days
+                .unwrap_or(default.class_b_frequency_days),
+            class_c_frequency_days: class_c_frequency_days
+                .unwrap_or(default.class_c_frequency_days),
+            max_items_per_count,
+            is_active: is_active.unwrap_or(default.is_active),
+        }
+    }
+}
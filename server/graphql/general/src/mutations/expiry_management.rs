@@ -0,0 +1,106 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::InvoiceNode;
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    expiry_management::redistribution::{
+        CreateRedistributionShipment, CreateRedistributionShipmentError, RedistributionShipmentLine,
+    },
+};
+
+#[derive(InputObject)]
+pub struct RedistributionShipmentLineInput {
+    pub id: String,
+    pub stock_line_id: String,
+    pub number_of_packs: f64,
+}
+
+#[derive(InputObject)]
+pub struct CreateRedistributionShipmentInput {
+    pub id: String,
+    /// Customer receiving the near expiry stock
+    pub other_party_id: String,
+    pub comment: Option<String>,
+    pub lines: Vec<RedistributionShipmentLineInput>,
+}
+
+#[derive(Union)]
+pub enum CreateRedistributionShipmentResponse {
+    Response(InvoiceNode),
+}
+
+pub fn create_redistribution_shipment(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: CreateRedistributionShipmentInput,
+) -> Result<CreateRedistributionShipmentResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateOutboundShipment,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    match service_provider
+        .expiry_management_service
+        .create_redistribution_shipment(&service_context, input.to_domain())
+    {
+        Ok(invoice) => Ok(CreateRedistributionShipmentResponse::Response(
+            InvoiceNode::from_domain(invoice),
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                CreateRedistributionShipmentError::NoLines
+                | CreateRedistributionShipmentError::ShipmentError(_)
+                | CreateRedistributionShipmentError::LineError { .. } => {
+                    BadUserInput(formatted_error)
+                }
+                CreateRedistributionShipmentError::CreatedInvoiceDoesNotExist
+                | CreateRedistributionShipmentError::DatabaseError(_) => {
+                    InternalError(formatted_error)
+                }
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+impl CreateRedistributionShipmentInput {
+    pub fn to_domain(self) -> CreateRedistributionShipment {
+        let CreateRedistributionShipmentInput {
+            id,
+            other_party_id,
+            comment,
+            lines,
+        } = self;
+
+        CreateRedistributionShipment {
+            id,
+            other_party_id,
+            comment,
+            lines: lines
+                .into_iter()
+                .map(
+                    |RedistributionShipmentLineInput {
+                         id,
+                         stock_line_id,
+                         number_of_packs,
+                     }| RedistributionShipmentLine {
+                        id,
+                        stock_line_id,
+                        number_of_packs,
+                    },
+                )
+                .collect(),
+        }
+    }
+}
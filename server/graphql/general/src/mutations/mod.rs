@@ -1,10 +1,16 @@
 pub mod barcode;
 pub mod common;
+pub mod cycle_count;
 pub mod display_settings;
+pub mod expiry_management;
 pub mod initialise_site;
 pub mod label_printer_settings;
 pub mod log;
 pub mod manual_sync;
+pub mod notification;
+pub mod sync_buffer_error;
+pub mod sync_bundle;
 pub mod sync_settings;
+pub mod update_item_heat_stability;
 pub mod update_name_properties;
 pub mod update_user;
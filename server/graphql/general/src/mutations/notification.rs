@@ -0,0 +1,147 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::DeleteResponse;
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    notification::subscription::{
+        DeleteNotificationSubscriptionError, UpsertNotificationSubscription,
+        UpsertNotificationSubscriptionError,
+    },
+};
+
+use crate::queries::{
+    NotificationChannelNode, NotificationEventTypeNode, NotificationSubscriptionNode,
+};
+
+#[derive(InputObject)]
+pub struct UpsertNotificationSubscriptionInput {
+    pub id: String,
+    pub event_type: NotificationEventTypeNode,
+    pub channel: NotificationChannelNode,
+    /// Email address or webhook (http or https) url
+    pub recipient: String,
+    /// Tera template, rendered with `event_type`, `store` (id, name) and `record` (fields of the
+    /// notified record)
+    pub subject_template: Option<String>,
+    /// Tera template, same context as the subject template
+    pub body_template: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Union)]
+pub enum UpsertNotificationSubscriptionResponse {
+    Response(NotificationSubscriptionNode),
+}
+
+#[derive(Union)]
+pub enum DeleteNotificationSubscriptionResponse {
+    Response(DeleteResponse),
+}
+
+pub fn upsert_notification_subscription(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: UpsertNotificationSubscriptionInput,
+) -> Result<UpsertNotificationSubscriptionResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    match service_provider
+        .notification_service
+        .upsert_notification_subscription(&service_context, input.to_domain())
+    {
+        Ok(row) => Ok(UpsertNotificationSubscriptionResponse::Response(
+            NotificationSubscriptionNode { row },
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                UpsertNotificationSubscriptionError::NotThisStoreSubscription
+                | UpsertNotificationSubscriptionError::InvalidRecipient
+                | UpsertNotificationSubscriptionError::InvalidTemplate(_) => {
+                    BadUserInput(formatted_error)
+                }
+                UpsertNotificationSubscriptionError::DatabaseError(_) => {
+                    InternalError(formatted_error)
+                }
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn delete_notification_subscription(
+    ctx: &Context<'_>,
+    store_id: &str,
+    id: &str,
+) -> Result<DeleteNotificationSubscriptionResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    match service_provider
+        .notification_service
+        .delete_notification_subscription(&service_context, id)
+    {
+        Ok(id) => Ok(DeleteNotificationSubscriptionResponse::Response(
+            DeleteResponse(id),
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                DeleteNotificationSubscriptionError::SubscriptionDoesNotExist
+                | DeleteNotificationSubscriptionError::NotThisStoreSubscription => {
+                    BadUserInput(formatted_error)
+                }
+                DeleteNotificationSubscriptionError::DatabaseError(_) => {
+                    InternalError(formatted_error)
+                }
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+impl UpsertNotificationSubscriptionInput {
+    pub fn to_domain(self) -> UpsertNotificationSubscription {
+        let UpsertNotificationSubscriptionInput {
+            id,
+            event_type,
+            channel,
+            recipient,
+            subject_template,
+            body_template,
+            is_active,
+        } = self;
+
+        UpsertNotificationSubscription {
+            id,
+            event_type: event_type.into(),
+            channel: channel.into(),
+            recipient,
+            subject_template,
+            body_template,
+            is_active: is_active.unwrap_or(true),
+        }
+    }
+}
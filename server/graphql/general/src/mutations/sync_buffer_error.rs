@@ -0,0 +1,148 @@
+use async_graphql::*;
+
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    sync::sync_buffer_error::{
+        PurgeSyncBufferRows, PurgeSyncBufferRowsError, ReintegrateSyncBufferRows,
+        ReintegrateSyncBufferRowsError, ReintegrateSyncBufferRowsResult,
+    },
+};
+
+#[derive(InputObject)]
+pub struct ReintegrateSyncBufferRowsInput {
+    /// Failed rows to reintegrate
+    pub record_ids: Option<Vec<String>>,
+    /// Reintegrate all failed rows of these tables
+    pub table_names: Option<Vec<String>>,
+}
+
+#[derive(SimpleObject)]
+pub struct ReintegrateSyncBufferRowsNode {
+    pub integrated_count: u32,
+    /// Number of rows that still failed integration
+    pub failed_count: u32,
+}
+
+#[derive(Union)]
+pub enum ReintegrateSyncBufferRowsResponse {
+    Response(ReintegrateSyncBufferRowsNode),
+}
+
+#[derive(InputObject)]
+pub struct PurgeSyncBufferRowsInput {
+    pub record_ids: Vec<String>,
+    /// Recorded in the activity log with each purged row
+    pub reason: Option<String>,
+}
+
+#[derive(SimpleObject)]
+pub struct PurgeSyncBufferRowsNode {
+    pub purged_count: u32,
+}
+
+#[derive(Union)]
+pub enum PurgeSyncBufferRowsResponse {
+    Response(PurgeSyncBufferRowsNode),
+}
+
+pub fn reintegrate_sync_buffer_rows(
+    ctx: &Context<'_>,
+    input: ReintegrateSyncBufferRowsInput,
+) -> Result<ReintegrateSyncBufferRowsResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context("".to_string(), user.user_id)?;
+
+    let ReintegrateSyncBufferRowsResult {
+        integrated_count,
+        failed_count,
+    } = service_provider
+        .sync_buffer_error_service
+        .reintegrate_sync_buffer_rows(
+            &service_context,
+            ReintegrateSyncBufferRows {
+                record_ids: input.record_ids,
+                table_names: input.table_names,
+            },
+        )
+        .map_err(map_reintegrate_error)?;
+
+    Ok(ReintegrateSyncBufferRowsResponse::Response(
+        ReintegrateSyncBufferRowsNode {
+            integrated_count,
+            failed_count,
+        },
+    ))
+}
+
+pub fn purge_sync_buffer_rows(
+    ctx: &Context<'_>,
+    input: PurgeSyncBufferRowsInput,
+) -> Result<PurgeSyncBufferRowsResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context("".to_string(), user.user_id)?;
+
+    let purged = service_provider
+        .sync_buffer_error_service
+        .purge_sync_buffer_rows(
+            &service_context,
+            PurgeSyncBufferRows {
+                record_ids: input.record_ids,
+                reason: input.reason,
+            },
+        )
+        .map_err(map_purge_error)?;
+
+    Ok(PurgeSyncBufferRowsResponse::Response(
+        PurgeSyncBufferRowsNode {
+            purged_count: purged.len() as u32,
+        },
+    ))
+}
+
+fn map_reintegrate_error(error: ReintegrateSyncBufferRowsError) -> async_graphql::Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        ReintegrateSyncBufferRowsError::NoRowsSelected
+        | ReintegrateSyncBufferRowsError::SyncInProgress
+        | ReintegrateSyncBufferRowsError::IntegrationInProgress(_) => BadUserInput(formatted_error),
+        ReintegrateSyncBufferRowsError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
+
+fn map_purge_error(error: PurgeSyncBufferRowsError) -> async_graphql::Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        PurgeSyncBufferRowsError::NoRowsSelected
+        | PurgeSyncBufferRowsError::RowDoesNotExist(_)
+        | PurgeSyncBufferRowsError::RowHasNotFailed(_) => BadUserInput(formatted_error),
+        PurgeSyncBufferRowsError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
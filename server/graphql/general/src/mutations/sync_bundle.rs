@@ -0,0 +1,177 @@
+use async_graphql::*;
+
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    static_files::{StaticFileCategory, StaticFileService},
+    sync::sync_bundle::{
+        export_push_bundle, import_sync_bundle, SyncBundleError, SyncBundleExport,
+        SyncBundleImport, SyncBundleType,
+    },
+};
+
+const PUSH_BUNDLE_FILE_NAME: &str = "push.omsyncbundle";
+const PULL_BUNDLE_FILE_NAME: &str = "pull.omsyncbundle";
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SyncBundleTypeNode {
+    Push,
+    Pull,
+}
+
+#[derive(SimpleObject)]
+pub struct ExportSyncBundleNode {
+    /// Id of the push bundle file, download with /files?id={fileId}
+    pub file_id: String,
+    pub number_of_records: u64,
+    /// More records are pending than fit in one bundle, export another push bundle once the reply
+    /// to this one was imported
+    pub has_more: bool,
+}
+
+#[derive(Union)]
+pub enum ExportSyncBundleResponse {
+    Response(ExportSyncBundleNode),
+}
+
+#[derive(InputObject)]
+pub struct ImportSyncBundleInput {
+    /// Id of the bundle file uploaded with /sync-bundle
+    pub file_id: String,
+    /// Sync password of the site the bundle was exported from, required to import a push bundle on central server
+    pub site_password: Option<String>,
+}
+
+#[derive(SimpleObject)]
+pub struct ImportSyncBundleNode {
+    pub bundle_type: SyncBundleTypeNode,
+    pub number_of_records: u64,
+    /// Id of the pull bundle file to carry back to the remote site, when a push bundle was imported
+    pub reply_file_id: Option<String>,
+}
+
+#[derive(Union)]
+pub enum ImportSyncBundleResponse {
+    Response(ImportSyncBundleNode),
+}
+
+pub fn export_sync_bundle(ctx: &Context<'_>) -> Result<ExportSyncBundleResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ManualSync,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let SyncBundleExport {
+        bundle,
+        number_of_records,
+        has_more,
+    } = export_push_bundle(&service_provider).map_err(map_error)?;
+
+    let file = StaticFileService::new(&ctx.get_settings().server.base_dir)
+        .and_then(|service| {
+            service.store_file(
+                PUSH_BUNDLE_FILE_NAME,
+                StaticFileCategory::Temporary,
+                &bundle,
+            )
+        })
+        .map_err(|error| StandardGraphqlError::InternalError(format!("{:#?}", error)).extend())?;
+
+    Ok(ExportSyncBundleResponse::Response(ExportSyncBundleNode {
+        file_id: file.id,
+        number_of_records,
+        has_more,
+    }))
+}
+
+pub async fn import_sync_bundle_file(
+    ctx: &Context<'_>,
+    input: ImportSyncBundleInput,
+) -> Result<ImportSyncBundleResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ManualSync,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let file_service = StaticFileService::new(&ctx.get_settings().server.base_dir)
+        .map_err(|error| StandardGraphqlError::InternalError(format!("{:#?}", error)).extend())?;
+
+    let file = file_service
+        .find_file(&input.file_id, StaticFileCategory::Temporary)
+        .map_err(|error| StandardGraphqlError::InternalError(format!("{:#?}", error)).extend())?
+        .ok_or(StandardGraphqlError::BadUserInput("Bundle file not found".to_string()).extend())?;
+    let bundle = std::fs::read(file.to_path_buf())
+        .map_err(|error| StandardGraphqlError::InternalError(format!("{:#?}", error)).extend())?;
+
+    let SyncBundleImport {
+        r#type,
+        number_of_records,
+        reply,
+    } = import_sync_bundle(&service_provider, &bundle, input.site_password)
+        .await
+        .map_err(map_error)?;
+
+    let reply_file_id = match reply {
+        Some(reply) => Some(
+            file_service
+                .store_file(PULL_BUNDLE_FILE_NAME, StaticFileCategory::Temporary, &reply)
+                .map_err(|error| {
+                    StandardGraphqlError::InternalError(format!("{:#?}", error)).extend()
+                })?
+                .id,
+        ),
+        None => None,
+    };
+
+    Ok(ImportSyncBundleResponse::Response(ImportSyncBundleNode {
+        bundle_type: SyncBundleTypeNode::from(r#type),
+        number_of_records,
+        reply_file_id,
+    }))
+}
+
+fn map_error(error: SyncBundleError) -> async_graphql::Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        SyncBundleError::NotInitialised
+        | SyncBundleError::NotACentralServer
+        | SyncBundleError::InvalidBundle(_)
+        | SyncBundleError::UnsupportedVersion(_)
+        | SyncBundleError::InvalidSignature
+        | SyncBundleError::SitePasswordRequired
+        | SyncBundleError::WrongSite { .. }
+        | SyncBundleError::PullCursorMismatch { .. }
+        | SyncBundleError::PushAlreadyImported { .. }
+        | SyncBundleError::IntegrationInProgress(_)
+        | SyncBundleError::SyncApiError(_) => BadUserInput(formatted_error),
+        SyncBundleError::SyncSettingsNotSet
+        | SyncBundleError::SiteIdNotSet
+        | SyncBundleError::SyncApiV5CreatingError(_)
+        | SyncBundleError::DatabaseError(_)
+        | SyncBundleError::Other(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
+
+impl From<SyncBundleType> for SyncBundleTypeNode {
+    fn from(from: SyncBundleType) -> Self {
+        match from {
+            SyncBundleType::Push => SyncBundleTypeNode::Push,
+            SyncBundleType::Pull => SyncBundleTypeNode::Pull,
+        }
+    }
+}
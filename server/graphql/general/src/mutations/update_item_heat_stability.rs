@@ -0,0 +1,80 @@
+use async_graphql::*;
+
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::ItemNode;
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    item::{
+        update_item_heat_stability as update, UpdateItemHeatStability,
+        UpdateItemHeatStabilityError as ServiceError,
+    },
+};
+
+pub fn update_item_heat_stability(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: UpdateItemHeatStabilityInput,
+) -> Result<UpdateItemHeatStabilityResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateItems,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    match update(&service_context, input.into()) {
+        Ok(item) => Ok(UpdateItemHeatStabilityResponse::Response(
+            ItemNode::from_domain(item),
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                ServiceError::ItemDoesNotExist
+                | ServiceError::ThresholdAndBudgetRequired
+                | ServiceError::BudgetMustBePositive => BadUserInput(formatted_error),
+                ServiceError::UpdatedRecordNotFound | ServiceError::DatabaseError(_) => {
+                    InternalError(formatted_error)
+                }
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct UpdateItemHeatStabilityInput {
+    pub item_id: String,
+    /// Temperature above which time counts against the heat stability budget
+    pub heat_stability_threshold: Option<f64>,
+    /// Total time stock can spend above the threshold, required if the threshold is set
+    pub heat_stability_budget_minutes: Option<i32>,
+}
+
+impl From<UpdateItemHeatStabilityInput> for UpdateItemHeatStability {
+    fn from(
+        UpdateItemHeatStabilityInput {
+            item_id,
+            heat_stability_threshold,
+            heat_stability_budget_minutes,
+        }: UpdateItemHeatStabilityInput,
+    ) -> Self {
+        UpdateItemHeatStability {
+            item_id,
+            heat_stability_threshold,
+            heat_stability_budget_minutes,
+        }
+    }
+}
+
+#[derive(Union)]
+pub enum UpdateItemHeatStabilityResponse {
+    Response(ItemNode),
+}
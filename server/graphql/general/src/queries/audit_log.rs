@@ -0,0 +1,119 @@
+use async_graphql::*;
+use graphql_core::{
+    generic_filters::{DatetimeFilterInput, EqualFilterStringInput},
+    map_filter,
+    pagination::PaginationInput,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{AuditLogConnector, AuditLogRecordTypeNode};
+use repository::{
+    AuditLogFilter, AuditLogSort, AuditLogSortField, DatetimeFilter, EqualFilter, PaginationOption,
+};
+use service::{
+    audit_log::get_audit_logs,
+    auth::{Resource, ResourceAccessRequest},
+};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+#[graphql(rename_items = "camelCase")]
+pub enum AuditLogSortFieldInput {
+    Datetime,
+}
+
+#[derive(InputObject)]
+pub struct AuditLogSortInput {
+    /// Sort query result by `key`
+    key: AuditLogSortFieldInput,
+    /// Sort query result is sorted descending or ascending (if not provided the default is
+    /// ascending)
+    desc: Option<bool>,
+}
+
+#[derive(InputObject, Clone)]
+pub struct EqualFilterAuditLogRecordTypeInput {
+    pub equal_to: Option<AuditLogRecordTypeNode>,
+    pub equal_any: Option<Vec<AuditLogRecordTypeNode>>,
+    pub not_equal_to: Option<AuditLogRecordTypeNode>,
+}
+
+#[derive(InputObject, Clone)]
+pub struct AuditLogFilterInput {
+    pub record_type: Option<EqualFilterAuditLogRecordTypeInput>,
+    pub record_id: Option<EqualFilterStringInput>,
+    pub user_id: Option<EqualFilterStringInput>,
+    pub datetime: Option<DatetimeFilterInput>,
+}
+
+#[derive(Union)]
+pub enum AuditLogResponse {
+    Response(AuditLogConnector),
+}
+
+pub fn audit_logs(
+    ctx: &Context<'_>,
+    store_id: String,
+    page: Option<PaginationInput>,
+    filter: Option<AuditLogFilterInput>,
+    sort: Option<Vec<AuditLogSortInput>>,
+) -> Result<AuditLogResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryLog,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let filter = filter
+        .map(|filter| filter.to_domain())
+        .unwrap_or_default()
+        .store_id(EqualFilter::equal_to(&store_id));
+
+    let connection_manager = ctx.get_connection_manager();
+    let items = get_audit_logs(
+        connection_manager,
+        page.map(PaginationOption::from),
+        Some(filter),
+        // Currently only one sort option is supported, use the first from the list.
+        sort.and_then(|mut sort_list| sort_list.pop())
+            .map(|sort| sort.to_domain()),
+    )
+    .map_err(StandardGraphqlError::from_list_error)?;
+
+    Ok(AuditLogResponse::Response(AuditLogConnector::from_domain(
+        items,
+    )))
+}
+
+impl AuditLogFilterInput {
+    pub fn to_domain(self) -> AuditLogFilter {
+        let AuditLogFilterInput {
+            record_type,
+            record_id,
+            user_id,
+            datetime,
+        } = self;
+
+        AuditLogFilter {
+            record_type: record_type.map(|t| map_filter!(t, AuditLogRecordTypeNode::to_domain)),
+            record_id: record_id.map(EqualFilter::from),
+            user_id: user_id.map(EqualFilter::from),
+            datetime: datetime.map(DatetimeFilter::from),
+            ..Default::default()
+        }
+    }
+}
+
+impl AuditLogSortInput {
+    pub fn to_domain(&self) -> AuditLogSort {
+        let key = match self.key {
+            AuditLogSortFieldInput::Datetime => AuditLogSortField::Datetime,
+        };
+
+        AuditLogSort {
+            key,
+            desc: self.desc,
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    pagination::PaginationInput,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use repository::{
+    ChangelogConsumerDeadLetterFilter, ChangelogConsumerDeadLetterRow, EqualFilter,
+    PaginationOption,
+};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    processors::changelog_consumer::status::ChangelogConsumerStatus,
+};
+
+pub struct ChangelogConsumerStatusNode {
+    status: ChangelogConsumerStatus,
+}
+
+#[Object]
+impl ChangelogConsumerStatusNode {
+    pub async fn name(&self) -> &str {
+        &self.status.name
+    }
+
+    /// Changelog tables processed by the consumer
+    pub async fn table_names(&self) -> Vec<String> {
+        self.status
+            .table_names
+            .iter()
+            .map(|table_name| format!("{:?}", table_name))
+            .collect()
+    }
+
+    /// Next changelog cursor to be processed
+    pub async fn cursor(&self) -> u64 {
+        self.status.cursor
+    }
+
+    /// Number of changelog records still to be processed
+    pub async fn pending_count(&self) -> u64 {
+        self.status.pending_count
+    }
+
+    /// Number of failed attempts of the record that is waiting to be retried
+    pub async fn retry_count(&self) -> u32 {
+        self.status.retry_count
+    }
+
+    pub async fn next_retry_datetime(&self) -> Option<DateTime<Utc>> {
+        self.status
+            .next_retry_datetime
+            .map(|datetime| DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc))
+    }
+
+    pub async fn last_error(&self) -> &Option<String> {
+        &self.status.last_error
+    }
+
+    pub async fn last_processed_datetime(&self) -> Option<DateTime<Utc>> {
+        self.status
+            .last_processed_datetime
+            .map(|datetime| DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc))
+    }
+
+    pub async fn dead_letter_count(&self) -> u32 {
+        self.status.dead_letter_count
+    }
+}
+
+pub struct ChangelogConsumerDeadLetterNode {
+    row: ChangelogConsumerDeadLetterRow,
+}
+
+#[Object]
+impl ChangelogConsumerDeadLetterNode {
+    pub async fn id(&self) -> &str {
+        &self.row.id
+    }
+
+    pub async fn consumer_name(&self) -> &str {
+        &self.row.consumer_id
+    }
+
+    pub async fn changelog_cursor(&self) -> i64 {
+        self.row.changelog_cursor
+    }
+
+    pub async fn table_name(&self) -> String {
+        format!("{:?}", self.row.table_name)
+    }
+
+    pub async fn record_id(&self) -> &str {
+        &self.row.record_id
+    }
+
+    pub async fn error(&self) -> &str {
+        &self.row.error
+    }
+
+    pub async fn attempts(&self) -> i32 {
+        self.row.attempts
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_naive_utc_and_offset(self.row.created_datetime, Utc)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ChangelogConsumerDeadLetterConnector {
+    total_count: u32,
+    nodes: Vec<ChangelogConsumerDeadLetterNode>,
+}
+
+pub fn changelog_consumer_statuses(ctx: &Context<'_>) -> Result<Vec<ChangelogConsumerStatusNode>> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+    let statuses = service_provider
+        .changelog_consumer_service
+        .get_changelog_consumer_statuses(&service_context)?;
+
+    Ok(statuses
+        .into_iter()
+        .map(|status| ChangelogConsumerStatusNode { status })
+        .collect())
+}
+
+pub fn changelog_consumer_dead_letters(
+    ctx: &Context<'_>,
+    consumer_name: Option<String>,
+    page: Option<PaginationInput>,
+) -> Result<ChangelogConsumerDeadLetterConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let filter = consumer_name.map(|consumer_name| {
+        ChangelogConsumerDeadLetterFilter::new().consumer_id(EqualFilter::equal_to(&consumer_name))
+    });
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+    let result = service_provider
+        .changelog_consumer_service
+        .get_changelog_consumer_dead_letters(
+            &service_context,
+            page.map(PaginationOption::from),
+            filter,
+        )
+        .map_err(StandardGraphqlError::from_list_error)?;
+
+    Ok(ChangelogConsumerDeadLetterConnector {
+        total_count: result.count,
+        nodes: result
+            .rows
+            .into_iter()
+            .map(|row| ChangelogConsumerDeadLetterNode { row })
+            .collect(),
+    })
+}
@@ -0,0 +1,278 @@
+use async_graphql::*;
+use chrono::NaiveDate;
+use dataloader::DataLoader;
+use graphql_core::{
+    loader::ItemLoader,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::ItemNode;
+use repository::{CycleCountClass, CycleCountItemRow, CycleCountPlanRow, CycleCountVarianceRow};
+use service::auth::{Resource, ResourceAccessRequest};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+pub enum CycleCountClassNode {
+    A,
+    B,
+    C,
+}
+
+pub struct CycleCountPlanNode {
+    plan: CycleCountPlanRow,
+}
+
+#[Object]
+impl CycleCountPlanNode {
+    pub async fn id(&self) -> &str {
+        &self.plan.id
+    }
+
+    /// Items making up this percentage of the store's consumption value are class A
+    pub async fn class_a_percentage(&self) -> f64 {
+        self.plan.class_a_percentage
+    }
+
+    /// Items making up the consumption value up to this percentage (after class A) are class B
+    pub async fn class_b_percentage(&self) -> f64 {
+        self.plan.class_b_percentage
+    }
+
+    pub async fn class_a_frequency_days(&self) -> i32 {
+        self.plan.class_a_frequency_days
+    }
+
+    pub async fn class_b_frequency_days(&self) -> i32 {
+        self.plan.class_b_frequency_days
+    }
+
+    pub async fn class_c_frequency_days(&self) -> i32 {
+        self.plan.class_c_frequency_days
+    }
+
+    pub async fn max_items_per_count(&self) -> Option<i32> {
+        self.plan.max_items_per_count
+    }
+
+    pub async fn last_generated_date(&self) -> Option<NaiveDate> {
+        self.plan.last_generated_date
+    }
+
+    pub async fn is_active(&self) -> bool {
+        self.plan.is_active
+    }
+}
+
+pub struct CycleCountItemNode {
+    item: CycleCountItemRow,
+}
+
+#[Object]
+impl CycleCountItemNode {
+    pub async fn id(&self) -> &str {
+        &self.item.id
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.item.item_link_id
+    }
+
+    pub async fn item(&self, ctx: &Context<'_>) -> Result<ItemNode> {
+        load_item(ctx, &self.item.item_link_id).await
+    }
+
+    /// Null until the item has been classified
+    pub async fn abc_class(&self) -> Option<CycleCountClassNode> {
+        self.item.abc_class.map(CycleCountClassNode::from)
+    }
+
+    /// Cost of a year's consumption of the item
+    pub async fn consumption_value(&self) -> f64 {
+        self.item.consumption_value
+    }
+
+    pub async fn last_counted_date(&self) -> Option<NaiveDate> {
+        self.item.last_counted_date
+    }
+}
+
+pub struct CycleCountVarianceNode {
+    variance: CycleCountVarianceRow,
+}
+
+#[Object]
+impl CycleCountVarianceNode {
+    pub async fn id(&self) -> &str {
+        &self.variance.id
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.variance.item_link_id
+    }
+
+    pub async fn item(&self, ctx: &Context<'_>) -> Result<ItemNode> {
+        load_item(ctx, &self.variance.item_link_id).await
+    }
+
+    pub async fn stocktake_id(&self) -> &str {
+        &self.variance.stocktake_id
+    }
+
+    pub async fn counted_date(&self) -> NaiveDate {
+        self.variance.counted_date
+    }
+
+    /// Units expected at the time of the count
+    pub async fn snapshot_quantity(&self) -> f64 {
+        self.variance.snapshot_quantity
+    }
+
+    /// Units counted
+    pub async fn counted_quantity(&self) -> f64 {
+        self.variance.counted_quantity
+    }
+
+    pub async fn variance(&self) -> f64 {
+        self.variance.counted_quantity - self.variance.snapshot_quantity
+    }
+
+    /// Percentage accuracy of the snapshot quantity, 100 when the count matched
+    pub async fn accuracy_percentage(&self) -> f64 {
+        let CycleCountVarianceRow {
+            snapshot_quantity,
+            counted_quantity,
+            ..
+        } = self.variance;
+        if counted_quantity == snapshot_quantity {
+            return 100.0;
+        }
+        let largest = counted_quantity.abs().max(snapshot_quantity.abs());
+        (100.0 - (counted_quantity - snapshot_quantity).abs() / largest * 100.0).max(0.0)
+    }
+}
+
+async fn load_item(ctx: &Context<'_>, item_id: &str) -> Result<ItemNode> {
+    let loader = ctx.get_loader::<DataLoader<ItemLoader>>();
+    let item_option = loader.load_one(item_id.to_string()).await?;
+
+    let item = item_option.ok_or(
+        StandardGraphqlError::InternalError(format!("Cannot find item_id {}", item_id)).extend(),
+    )?;
+
+    Ok(ItemNode::from_domain(item))
+}
+
+#[derive(SimpleObject)]
+pub struct CycleCountItemConnector {
+    total_count: u32,
+    nodes: Vec<CycleCountItemNode>,
+}
+
+#[derive(SimpleObject)]
+pub struct CycleCountVarianceConnector {
+    total_count: u32,
+    nodes: Vec<CycleCountVarianceNode>,
+}
+
+#[derive(Union)]
+pub enum CycleCountItemsResponse {
+    Response(CycleCountItemConnector),
+}
+
+#[derive(Union)]
+pub enum CycleCountVariancesResponse {
+    Response(CycleCountVarianceConnector),
+}
+
+pub fn cycle_count_plan(ctx: &Context<'_>, store_id: String) -> Result<Option<CycleCountPlanNode>> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStocktake,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.clone(), user.user_id)?;
+
+    let plan = service_provider
+        .cycle_count_service
+        .get_cycle_count_plan(&service_context, &store_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(plan.map(|plan| CycleCountPlanNode { plan }))
+}
+
+pub fn cycle_count_items(ctx: &Context<'_>, store_id: String) -> Result<CycleCountItemsResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStocktake,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.clone(), user.user_id)?;
+
+    let items = service_provider
+        .cycle_count_service
+        .get_cycle_count_items(&service_context, &store_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(CycleCountItemsResponse::Response(CycleCountItemConnector {
+        total_count: items.len() as u32,
+        nodes: items
+            .into_iter()
+            .map(|item| CycleCountItemNode { item })
+            .collect(),
+    }))
+}
+
+pub fn cycle_count_variances(
+    ctx: &Context<'_>,
+    store_id: String,
+    item_id: Option<String>,
+) -> Result<CycleCountVariancesResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStocktake,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.clone(), user.user_id)?;
+
+    let variances = service_provider
+        .cycle_count_service
+        .get_cycle_count_variances(&service_context, &store_id, item_id.as_deref())
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(CycleCountVariancesResponse::Response(
+        CycleCountVarianceConnector {
+            total_count: variances.len() as u32,
+            nodes: variances
+                .into_iter()
+                .map(|variance| CycleCountVarianceNode { variance })
+                .collect(),
+        },
+    ))
+}
+
+impl CycleCountPlanNode {
+    pub fn from_domain(plan: CycleCountPlanRow) -> CycleCountPlanNode {
+        CycleCountPlanNode { plan }
+    }
+}
+
+impl From<CycleCountClass> for CycleCountClassNode {
+    fn from(class: CycleCountClass) -> Self {
+        match class {
+            CycleCountClass::A => CycleCountClassNode::A,
+            CycleCountClass::B => CycleCountClassNode::B,
+            CycleCountClass::C => CycleCountClassNode::C,
+        }
+    }
+}
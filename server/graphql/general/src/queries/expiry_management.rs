@@ -0,0 +1,221 @@
+use async_graphql::*;
+use chrono::{NaiveDate, Utc};
+use graphql_core::{
+    generic_filters::EqualFilterStringInput,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{NameNode, StockLineNode};
+use repository::EqualFilter;
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    expiry_management::{
+        near_expiry::{NearExpiryStock, NearExpiryStockFilter},
+        redistribution::{GetRedistributionSuggestionsError, RedistributionSuggestion},
+    },
+};
+use util::timezone::offset_to_timezone;
+
+pub struct NearExpiryStockNode {
+    near_expiry_stock: NearExpiryStock,
+}
+
+#[Object]
+impl NearExpiryStockNode {
+    pub async fn stock_line(&self) -> StockLineNode {
+        StockLineNode::from_domain(self.near_expiry_stock.stock_line.clone())
+    }
+
+    /// Zero or negative when already expired
+    pub async fn days_until_expiry(&self) -> i64 {
+        self.near_expiry_stock.days_until_expiry
+    }
+
+    /// Average monthly consumption of the item in the store (in units)
+    pub async fn average_monthly_consumption(&self) -> f64 {
+        self.near_expiry_stock.average_monthly_consumption
+    }
+
+    /// Packs expected to be used before expiry
+    pub async fn expected_consumed_number_of_packs(&self) -> f64 {
+        self.near_expiry_stock.expected_consumed_number_of_packs
+    }
+
+    /// Packs expected to remain unused at expiry
+    pub async fn surplus_number_of_packs(&self) -> f64 {
+        self.near_expiry_stock.surplus_number_of_packs
+    }
+
+    /// Cost of the available packs
+    pub async fn total_value(&self) -> f64 {
+        self.near_expiry_stock.total_value
+    }
+
+    /// Cost of the surplus packs
+    pub async fn value_at_risk(&self) -> f64 {
+        self.near_expiry_stock.value_at_risk
+    }
+}
+
+pub struct RedistributionSuggestionNode {
+    suggestion: RedistributionSuggestion,
+}
+
+#[Object]
+impl RedistributionSuggestionNode {
+    pub async fn customer(&self) -> NameNode {
+        NameNode::from_domain(self.suggestion.customer.clone())
+    }
+
+    /// Null if the customer's stock on hand is not known on this site
+    pub async fn available_stock_on_hand(&self) -> Option<f64> {
+        self.suggestion.available_stock_on_hand
+    }
+
+    /// Null if the customer's consumption is not known on this site
+    pub async fn average_monthly_consumption(&self) -> Option<f64> {
+        self.suggestion.average_monthly_consumption
+    }
+
+    pub async fn suggested_number_of_packs(&self) -> Option<f64> {
+        self.suggestion.suggested_number_of_packs
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct NearExpiryStockConnector {
+    total_count: u32,
+    nodes: Vec<NearExpiryStockNode>,
+}
+
+#[derive(SimpleObject)]
+pub struct RedistributionSuggestionConnector {
+    total_count: u32,
+    nodes: Vec<RedistributionSuggestionNode>,
+}
+
+#[derive(Union)]
+pub enum NearExpiryStockResponse {
+    Response(NearExpiryStockConnector),
+}
+
+#[derive(Union)]
+pub enum RedistributionSuggestionsResponse {
+    Response(RedistributionSuggestionConnector),
+}
+
+#[derive(InputObject, Clone)]
+pub struct NearExpiryStockFilterInput {
+    pub item_id: Option<EqualFilterStringInput>,
+    /// Defaults to 90 days
+    pub expiring_within_days: Option<u32>,
+}
+
+fn today(timezone_offset: Option<i32>) -> Result<NaiveDate> {
+    let timezone_offset = offset_to_timezone(&timezone_offset).ok_or(
+        StandardGraphqlError::BadUserInput("Invalid timezone offset".to_string()),
+    )?;
+    Ok(Utc::now().with_timezone(&timezone_offset).date_naive())
+}
+
+pub fn near_expiry_stock(
+    ctx: &Context<'_>,
+    store_id: String,
+    timezone_offset: Option<i32>,
+    filter: Option<NearExpiryStockFilterInput>,
+) -> Result<NearExpiryStockResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStockLine,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let today = today(timezone_offset)?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.clone(), user.user_id)?;
+
+    let near_expiry_stock = service_provider
+        .expiry_management_service
+        .get_near_expiry_stock(
+            &service_context,
+            &store_id,
+            filter.map(|filter| filter.to_domain()),
+            today,
+        )
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(NearExpiryStockResponse::Response(
+        NearExpiryStockConnector {
+            total_count: near_expiry_stock.len() as u32,
+            nodes: near_expiry_stock
+                .into_iter()
+                .map(|near_expiry_stock| NearExpiryStockNode { near_expiry_stock })
+                .collect(),
+        },
+    ))
+}
+
+pub fn redistribution_suggestions(
+    ctx: &Context<'_>,
+    store_id: String,
+    stock_line_id: String,
+    timezone_offset: Option<i32>,
+) -> Result<RedistributionSuggestionsResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStockLine,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let today = today(timezone_offset)?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.clone(), user.user_id)?;
+
+    match service_provider
+        .expiry_management_service
+        .get_redistribution_suggestions(&service_context, &store_id, &stock_line_id, today)
+    {
+        Ok(suggestions) => Ok(RedistributionSuggestionsResponse::Response(
+            RedistributionSuggestionConnector {
+                total_count: suggestions.len() as u32,
+                nodes: suggestions
+                    .into_iter()
+                    .map(|suggestion| RedistributionSuggestionNode { suggestion })
+                    .collect(),
+            },
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                GetRedistributionSuggestionsError::StockLineDoesNotExist
+                | GetRedistributionSuggestionsError::NotThisStoreStockLine
+                | GetRedistributionSuggestionsError::StockLineHasNoExpiryDate => {
+                    BadUserInput(formatted_error)
+                }
+                GetRedistributionSuggestionsError::DatabaseError(_) => {
+                    InternalError(formatted_error)
+                }
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+impl NearExpiryStockFilterInput {
+    pub fn to_domain(self) -> NearExpiryStockFilter {
+        let NearExpiryStockFilterInput {
+            item_id,
+            expiring_within_days,
+        } = self;
+
+        NearExpiryStockFilter {
+            item_id: item_id.map(EqualFilter::from),
+            expiring_within_days,
+        }
+    }
+}
@@ -142,6 +142,7 @@ impl LedgerFilterInput {
 
         LedgerFilter {
             stock_line_id: stock_line_id.map(EqualFilter::from),
+            ..Default::default()
         }
     }
 }
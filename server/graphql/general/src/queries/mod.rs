@@ -6,6 +6,8 @@ pub mod logout;
 pub use self::logout::*;
 pub mod ledger;
 pub use self::ledger::*;
+pub mod stock_outs;
+pub use self::stock_outs::*;
 pub mod me;
 pub use self::me::*;
 pub mod refresh_token;
@@ -26,12 +28,20 @@ pub mod store;
 pub use self::store::*;
 pub mod activity_log;
 pub use self::activity_log::*;
+pub mod audit_log;
+pub use self::audit_log::*;
+pub mod cycle_count;
+pub use self::cycle_count::*;
 pub mod database_settings;
 pub use self::database_settings::*;
 pub mod display_settings;
+pub mod expiry_management;
+pub use self::expiry_management::*;
 pub mod initialisation_status;
 pub mod name_property;
 pub use self::name_property::*;
+pub mod notification;
+pub use self::notification::*;
 pub mod requisition_line_chart;
 pub mod response_requisition_line_stats;
 pub mod sync_settings;
@@ -42,8 +52,10 @@ pub use self::inventory_adjustment_reason::*;
 pub mod item_counts;
 pub use self::item_counts::*;
 pub mod barcode;
+pub mod changelog_consumer;
 pub mod requisition_counts;
 pub mod store_preference;
+pub mod sync_buffer_error;
 pub use self::barcode::*;
 pub use self::requisition_counts::*;
 pub mod log;
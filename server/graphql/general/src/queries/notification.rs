@@ -0,0 +1,260 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    pagination::PaginationInput,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use repository::{
+    EqualFilter, NotificationEventType, NotificationOutboxFilter, NotificationOutboxRow,
+    NotificationOutboxStatus, NotificationSubscriptionRow, PaginationOption,
+};
+use service::auth::{Resource, ResourceAccessRequest};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "repository::NotificationEventType")]
+pub enum NotificationEventTypeNode {
+    TemperatureBreach,
+    RequisitionReceived,
+    ShipmentArrived,
+    StockExpiring,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "repository::NotificationChannel")]
+pub enum NotificationChannelNode {
+    Email,
+    Webhook,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "repository::NotificationOutboxStatus")]
+pub enum NotificationOutboxStatusNode {
+    Pending,
+    Sent,
+    Failed,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct NotificationSubscriptionNode {
+    pub row: NotificationSubscriptionRow,
+}
+
+#[Object]
+impl NotificationSubscriptionNode {
+    pub async fn id(&self) -> &str {
+        &self.row.id
+    }
+
+    pub async fn event_type(&self) -> NotificationEventTypeNode {
+        NotificationEventTypeNode::from(self.row.event_type)
+    }
+
+    pub async fn channel(&self) -> NotificationChannelNode {
+        NotificationChannelNode::from(self.row.channel)
+    }
+
+    /// Email address or webhook url
+    pub async fn recipient(&self) -> &str {
+        &self.row.recipient
+    }
+
+    /// Tera template, the default template of the event type is used if not set
+    pub async fn subject_template(&self) -> &Option<String> {
+        &self.row.subject_template
+    }
+
+    /// Tera template, the default template of the event type is used if not set
+    pub async fn body_template(&self) -> &Option<String> {
+        &self.row.body_template
+    }
+
+    pub async fn is_active(&self) -> bool {
+        self.row.is_active
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_naive_utc_and_offset(self.row.created_datetime, Utc)
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct NotificationOutboxNode {
+    row: NotificationOutboxRow,
+}
+
+#[Object]
+impl NotificationOutboxNode {
+    pub async fn id(&self) -> &str {
+        &self.row.id
+    }
+
+    pub async fn subscription_id(&self) -> &str {
+        &self.row.subscription_id
+    }
+
+    pub async fn event_type(&self) -> NotificationEventTypeNode {
+        NotificationEventTypeNode::from(self.row.event_type)
+    }
+
+    /// Id of the record the notification is about, e.g. temperature breach id
+    pub async fn record_id(&self) -> &str {
+        &self.row.record_id
+    }
+
+    pub async fn channel(&self) -> NotificationChannelNode {
+        NotificationChannelNode::from(self.row.channel)
+    }
+
+    pub async fn recipient(&self) -> &str {
+        &self.row.recipient
+    }
+
+    pub async fn subject(&self) -> &str {
+        &self.row.subject
+    }
+
+    pub async fn body(&self) -> &str {
+        &self.row.body
+    }
+
+    pub async fn status(&self) -> NotificationOutboxStatusNode {
+        NotificationOutboxStatusNode::from(self.row.status)
+    }
+
+    /// Number of failed delivery attempts
+    pub async fn attempts(&self) -> i32 {
+        self.row.attempts
+    }
+
+    pub async fn next_attempt_datetime(&self) -> Option<DateTime<Utc>> {
+        self.row
+            .next_attempt_datetime
+            .map(|datetime| DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc))
+    }
+
+    pub async fn last_error(&self) -> &Option<String> {
+        &self.row.last_error
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_naive_utc_and_offset(self.row.created_datetime, Utc)
+    }
+
+    pub async fn sent_datetime(&self) -> Option<DateTime<Utc>> {
+        self.row
+            .sent_datetime
+            .map(|datetime| DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc))
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct NotificationSubscriptionConnector {
+    total_count: u32,
+    nodes: Vec<NotificationSubscriptionNode>,
+}
+
+#[derive(SimpleObject)]
+pub struct NotificationOutboxConnector {
+    total_count: u32,
+    nodes: Vec<NotificationOutboxNode>,
+}
+
+#[derive(Union)]
+pub enum NotificationSubscriptionsResponse {
+    Response(NotificationSubscriptionConnector),
+}
+
+#[derive(Union)]
+pub enum NotificationOutboxResponse {
+    Response(NotificationOutboxConnector),
+}
+
+#[derive(InputObject, Clone)]
+pub struct NotificationOutboxFilterInput {
+    pub subscription_id: Option<String>,
+    pub event_type: Option<NotificationEventTypeNode>,
+    pub status: Option<NotificationOutboxStatusNode>,
+}
+
+pub fn notification_subscriptions(
+    ctx: &Context<'_>,
+    store_id: String,
+) -> Result<NotificationSubscriptionsResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.clone(), user.user_id)?;
+    let rows = service_provider
+        .notification_service
+        .get_notification_subscriptions(&service_context, &store_id)?;
+
+    Ok(NotificationSubscriptionsResponse::Response(
+        NotificationSubscriptionConnector {
+            total_count: rows.len() as u32,
+            nodes: rows
+                .into_iter()
+                .map(|row| NotificationSubscriptionNode { row })
+                .collect(),
+        },
+    ))
+}
+
+pub fn notification_outbox(
+    ctx: &Context<'_>,
+    store_id: String,
+    page: Option<PaginationInput>,
+    filter: Option<NotificationOutboxFilterInput>,
+) -> Result<NotificationOutboxResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let mut outbox_filter =
+        NotificationOutboxFilter::new().store_id(EqualFilter::equal_to(&store_id));
+    if let Some(filter) = filter {
+        if let Some(subscription_id) = filter.subscription_id {
+            outbox_filter = outbox_filter.subscription_id(EqualFilter::equal_to(&subscription_id));
+        }
+        if let Some(event_type) = filter.event_type {
+            outbox_filter =
+                outbox_filter.event_type(NotificationEventType::from(event_type).equal_to());
+        }
+        if let Some(status) = filter.status {
+            outbox_filter = outbox_filter.status(NotificationOutboxStatus::from(status).equal_to());
+        }
+    }
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.clone(), user.user_id)?;
+    let result = service_provider
+        .notification_service
+        .get_notification_outbox(
+            &service_context,
+            page.map(PaginationOption::from),
+            Some(outbox_filter),
+            None,
+        )
+        .map_err(StandardGraphqlError::from_list_error)?;
+
+    Ok(NotificationOutboxResponse::Response(
+        NotificationOutboxConnector {
+            total_count: result.count,
+            nodes: result
+                .rows
+                .into_iter()
+                .map(|row| NotificationOutboxNode { row })
+                .collect(),
+        },
+    ))
+}
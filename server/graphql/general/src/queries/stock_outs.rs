@@ -0,0 +1,194 @@
+use async_graphql::*;
+use chrono::NaiveDate;
+use dataloader::DataLoader;
+use graphql_core::{
+    generic_filters::EqualFilterStringInput,
+    loader::ItemLoader,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::ItemNode;
+use repository::EqualFilter;
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    stock_out::{DailyStockOnHand, ItemStockOut, StockOutError, StockOutFilter, StockOutPeriod},
+};
+
+#[derive(InputObject, Clone)]
+pub struct StockOutFilterInput {
+    pub item_id: Option<EqualFilterStringInput>,
+}
+
+#[derive(SimpleObject)]
+pub struct StockOutPeriodNode {
+    pub from: NaiveDate,
+    /// Inclusive
+    pub to: NaiveDate,
+}
+
+#[derive(SimpleObject)]
+pub struct DailyStockOnHandNode {
+    pub date: NaiveDate,
+    /// Stock on hand at the end of the day
+    pub stock_on_hand: f64,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct ItemStockOutNode {
+    stock_out: ItemStockOut,
+}
+
+#[Object]
+impl ItemStockOutNode {
+    pub async fn item_id(&self) -> &String {
+        &self.stock_out.item_id
+    }
+
+    pub async fn store_id(&self) -> &String {
+        &self.stock_out.store_id
+    }
+
+    pub async fn item(&self, ctx: &Context<'_>) -> Result<ItemNode> {
+        let loader = ctx.get_loader::<DataLoader<ItemLoader>>();
+        let item_option = loader.load_one(self.stock_out.item_id.clone()).await?;
+
+        let item = item_option.ok_or(
+            StandardGraphqlError::InternalError(format!(
+                "Cannot find item_id {} for stock out",
+                self.stock_out.item_id
+            ))
+            .extend(),
+        )?;
+
+        Ok(ItemNode::from_domain(item))
+    }
+
+    pub async fn total_days(&self) -> u32 {
+        self.stock_out.total_days
+    }
+
+    pub async fn days_out_of_stock(&self) -> u32 {
+        self.stock_out.days_out_of_stock
+    }
+
+    pub async fn days_in_stock(&self) -> u32 {
+        self.stock_out.days_in_stock()
+    }
+
+    pub async fn consumption(&self) -> f64 {
+        self.stock_out.consumption
+    }
+
+    /// Consumption adjusted for days out of stock
+    pub async fn adjusted_consumption(&self) -> f64 {
+        self.stock_out.adjusted_consumption
+    }
+
+    pub async fn stock_out_periods(&self) -> Vec<StockOutPeriodNode> {
+        self.stock_out
+            .stock_out_periods
+            .iter()
+            .map(|StockOutPeriod { from, to }| StockOutPeriodNode {
+                from: *from,
+                to: *to,
+            })
+            .collect()
+    }
+
+    pub async fn daily_stock_on_hand(&self) -> Vec<DailyStockOnHandNode> {
+        self.stock_out
+            .daily_stock_on_hand
+            .iter()
+            .map(
+                |DailyStockOnHand {
+                     date,
+                     stock_on_hand,
+                 }| DailyStockOnHandNode {
+                    date: *date,
+                    stock_on_hand: *stock_on_hand,
+                },
+            )
+            .collect()
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct StockOutConnector {
+    total_count: u32,
+    nodes: Vec<ItemStockOutNode>,
+}
+
+#[derive(Union)]
+pub enum StockOutResponse {
+    Response(StockOutConnector),
+}
+
+pub fn stock_outs(
+    ctx: &Context<'_>,
+    store_id: String,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+    filter: Option<StockOutFilterInput>,
+) -> Result<StockOutResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryItems,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.clone(), user.user_id)?;
+
+    let stock_outs = service_provider
+        .stock_out_service
+        .get_stock_outs(
+            &service_context,
+            &store_id,
+            from_date,
+            to_date,
+            filter.map(|filter| filter.to_domain()),
+        )
+        .map_err(map_error)?;
+
+    Ok(StockOutResponse::Response(StockOutConnector::from_domain(
+        stock_outs,
+    )))
+}
+
+fn map_error(error: StockOutError) -> Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        StockOutError::InvalidDateRange | StockOutError::DateRangeTooLong => {
+            BadUserInput(formatted_error)
+        }
+        StockOutError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
+
+impl StockOutConnector {
+    pub fn from_domain(stock_outs: Vec<ItemStockOut>) -> StockOutConnector {
+        StockOutConnector {
+            total_count: stock_outs.len() as u32,
+            nodes: stock_outs
+                .into_iter()
+                .map(|stock_out| ItemStockOutNode { stock_out })
+                .collect(),
+        }
+    }
+}
+
+impl StockOutFilterInput {
+    pub fn to_domain(self) -> StockOutFilter {
+        let StockOutFilterInput { item_id } = self;
+
+        StockOutFilter {
+            item_id: item_id.map(EqualFilter::from),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    generic_filters::EqualFilterStringInput,
+    pagination::PaginationInput,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use repository::{
+    EqualFilter, PaginationOption, SyncBufferErrorGroup, SyncBufferFilter, SyncBufferRow,
+};
+use service::auth::{Resource, ResourceAccessRequest};
+
+pub struct SyncBufferErrorGroupNode {
+    group: SyncBufferErrorGroup,
+}
+
+#[Object]
+impl SyncBufferErrorGroupNode {
+    pub async fn table_name(&self) -> &str {
+        &self.group.table_name
+    }
+
+    pub async fn integration_error(&self) -> &str {
+        &self.group.integration_error
+    }
+
+    /// Number of failed rows with this table name and error
+    pub async fn count(&self) -> i64 {
+        self.group.count
+    }
+
+    pub async fn latest_integration_datetime(&self) -> Option<DateTime<Utc>> {
+        self.group
+            .latest_integration_datetime
+            .map(|datetime| DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc))
+    }
+}
+
+pub struct SyncBufferRowNode {
+    pub row: SyncBufferRow,
+}
+
+#[Object]
+impl SyncBufferRowNode {
+    pub async fn record_id(&self) -> &str {
+        &self.row.record_id
+    }
+
+    pub async fn table_name(&self) -> &str {
+        &self.row.table_name
+    }
+
+    pub async fn action(&self) -> String {
+        format!("{:?}", self.row.action)
+    }
+
+    /// Sync record payload (JSON)
+    pub async fn data(&self) -> &str {
+        &self.row.data
+    }
+
+    pub async fn integration_error(&self) -> &Option<String> {
+        &self.row.integration_error
+    }
+
+    pub async fn received_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_naive_utc_and_offset(self.row.received_datetime, Utc)
+    }
+
+    pub async fn integration_datetime(&self) -> Option<DateTime<Utc>> {
+        self.row
+            .integration_datetime
+            .map(|datetime| DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc))
+    }
+
+    /// Site the record was received from (on central server)
+    pub async fn source_site_id(&self) -> Option<i32> {
+        self.row.source_site_id
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct SyncBufferRowConnector {
+    total_count: u32,
+    nodes: Vec<SyncBufferRowNode>,
+}
+
+#[derive(InputObject, Clone)]
+pub struct SyncBufferErrorFilterInput {
+    pub record_id: Option<EqualFilterStringInput>,
+    pub table_name: Option<EqualFilterStringInput>,
+    pub integration_error: Option<EqualFilterStringInput>,
+}
+
+pub fn sync_buffer_error_groups(ctx: &Context<'_>) -> Result<Vec<SyncBufferErrorGroupNode>> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+    let groups = service_provider
+        .sync_buffer_error_service
+        .get_sync_buffer_error_groups(&service_context)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(groups
+        .into_iter()
+        .map(|group| SyncBufferErrorGroupNode { group })
+        .collect())
+}
+
+pub fn sync_buffer_errors(
+    ctx: &Context<'_>,
+    filter: Option<SyncBufferErrorFilterInput>,
+    page: Option<PaginationInput>,
+) -> Result<SyncBufferRowConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+    let result = service_provider
+        .sync_buffer_error_service
+        .get_failed_sync_buffer_rows(
+            &service_context,
+            page.map(PaginationOption::from),
+            filter.map(|filter| filter.to_domain()),
+        )
+        .map_err(StandardGraphqlError::from_list_error)?;
+
+    Ok(SyncBufferRowConnector {
+        total_count: result.count,
+        nodes: result
+            .rows
+            .into_iter()
+            .map(|row| SyncBufferRowNode { row })
+            .collect(),
+    })
+}
+
+impl SyncBufferErrorFilterInput {
+    pub fn to_domain(self) -> SyncBufferFilter {
+        let SyncBufferErrorFilterInput {
+            record_id,
+            table_name,
+            integration_error,
+        } = self;
+
+        SyncBufferFilter {
+            record_id: record_id.map(EqualFilter::from),
+            table_name: table_name.map(EqualFilter::from),
+            integration_error: integration_error.map(EqualFilter::from),
+            ..Default::default()
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use async_graphql::*;
 use chrono::NaiveDate;
+use graphql_types::types::ConsumptionForecastMethodNode;
 use service::requisition_line::chart::{
     ConsumptionHistory, ItemChart, StockEvolution, SuggestedQuantityCalculation,
 };
@@ -86,6 +87,14 @@ impl SuggestedQuantityCalculationNode {
     pub async fn suggested_quantity(&self) -> u32 {
         self.suggested_quantity_calculation.suggested
     }
+
+    pub async fn consumption_forecast_method(&self) -> ConsumptionForecastMethodNode {
+        ConsumptionForecastMethodNode::from_domain(
+            &self
+                .suggested_quantity_calculation
+                .consumption_forecast_method,
+        )
+    }
 }
 
 #[Object]
@@ -175,7 +184,7 @@ impl ItemChartNode {
 mod test {
     use async_graphql::Object;
     use graphql_core::{assert_graphql_query, test_helpers::setup_graphql_test};
-    use repository::mock::MockDataInserts;
+    use repository::{mock::MockDataInserts, ConsumptionForecastMethod};
     use serde_json::json;
 
     use super::*;
@@ -226,6 +235,7 @@ mod test {
                         minimum_stock_on_hand: 100.0,
                         maximum_stock_on_hand: 200.0,
                         suggested: 150,
+                        consumption_forecast_method: ConsumptionForecastMethod::StockOutAdjusted,
                     },
                 })
             }
@@ -259,7 +269,7 @@ mod test {
                     minimumStockOnHand
                     maximumStockOnHand
                     suggestedQuantity
-
+                    consumptionForecastMethod
                 }
                calculationDate
             }
@@ -312,7 +322,8 @@ mod test {
                 "averageMonthlyConsumption": 10,
                 "maximumStockOnHand": 200,
                 "minimumStockOnHand": 100,
-                "suggestedQuantity": 150
+                "suggestedQuantity": 150,
+                "consumptionForecastMethod": "STOCK_OUT_ADJUSTED"
               }
             }
           }
@@ -138,6 +138,16 @@ impl InvoiceMutations {
         outbound_shipment::add_from_master_list(ctx, &store_id, input)
     }
 
+    /// Allocate stock to all unallocated lines of an outbound shipment
+    async fn allocate_outbound_shipment(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        invoice_id: String,
+    ) -> Result<outbound_shipment::allocate::AllocateResponse> {
+        outbound_shipment::allocate::allocate(ctx, &store_id, invoice_id)
+    }
+
     async fn add_to_inbound_shipment_from_master_list(
         &self,
         ctx: &Context<'_>,
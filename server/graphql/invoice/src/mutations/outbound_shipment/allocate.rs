@@ -0,0 +1,153 @@
+use async_graphql::*;
+use graphql_core::{
+    simple_generic_errors::{CannotEditInvoice, RecordNotFound},
+    standard_graphql_error::validate_auth,
+    standard_graphql_error::StandardGraphqlError,
+    ContextExt,
+};
+use graphql_types::types::{
+    AllocationStrategyNode, DeleteResponse, InvoiceLineConnector, StockLineConnector,
+};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    invoice::outbound_shipment::{
+        AllocateOutboundShipmentError as ServiceError,
+        AllocateOutboundShipmentResult as ServiceResult, AllocationShortfall,
+    },
+};
+
+#[derive(Interface)]
+#[graphql(name = "AllocateOutboundShipmentErrorInterface")]
+#[graphql(field(name = "description", ty = "String"))]
+pub enum AllocateErrorInterface {
+    RecordNotFound(RecordNotFound),
+    CannotEditInvoice(CannotEditInvoice),
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "AllocateOutboundShipmentError")]
+pub struct AllocateError {
+    pub error: AllocateErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "AllocateOutboundShipmentResponse")]
+pub enum AllocateResponse {
+    Error(AllocateError),
+    Response(AllocateResponseNode),
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "AllocationShortfallNode")]
+pub struct ShortfallNode {
+    item_id: String,
+    item_name: String,
+    requested_quantity: f64,
+    /// Quantity left on the unallocated line
+    remaining_quantity: f64,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "AllocateOutboundShipmentNode")]
+pub struct AllocateResponseNode {
+    updates: InvoiceLineConnector,
+    inserts: InvoiceLineConnector,
+    deletes: Vec<DeleteResponse>,
+    skipped_expired_stock_lines: StockLineConnector,
+    skipped_on_hold_stock_lines: StockLineConnector,
+    issued_expiring_soon_stock_lines: StockLineConnector,
+    /// Items that could not be fully allocated
+    shortfalls: Vec<ShortfallNode>,
+    allocation_strategy: AllocationStrategyNode,
+}
+
+pub fn allocate(ctx: &Context<'_>, store_id: &str, invoice_id: String) -> Result<AllocateResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateOutboundShipment,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    let response = match service_provider
+        .invoice_service
+        .allocate_outbound_shipment(&service_context, &invoice_id)
+    {
+        Ok(result) => AllocateResponse::Response(AllocateResponseNode::from_domain(result)),
+        Err(error) => AllocateResponse::Error(AllocateError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(response)
+}
+
+fn map_error(error: ServiceError) -> Result<AllocateErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::ShipmentDoesNotExist => {
+            return Ok(AllocateErrorInterface::RecordNotFound(RecordNotFound {}))
+        }
+        ServiceError::CannotEditShipment => {
+            return Ok(AllocateErrorInterface::CannotEditInvoice(
+                CannotEditInvoice {},
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::NotThisStoreShipment => BadUserInput(formatted_error),
+        ServiceError::NotAnOutboundShipment => BadUserInput(formatted_error),
+        ServiceError::AllocateLine { .. } => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
+
+impl AllocateResponseNode {
+    pub fn from_domain(from: ServiceResult) -> AllocateResponseNode {
+        let ServiceResult {
+            inserts,
+            deletes,
+            updates,
+            skipped_expired_stock_lines,
+            skipped_on_hold_stock_lines,
+            issued_expiring_soon_stock_lines,
+            shortfalls,
+            allocation_strategy,
+        } = from;
+        AllocateResponseNode {
+            updates: InvoiceLineConnector::from_vec(updates),
+            deletes: deletes.into_iter().map(DeleteResponse).collect(),
+            inserts: InvoiceLineConnector::from_vec(inserts),
+            skipped_expired_stock_lines: StockLineConnector::from_vec(skipped_expired_stock_lines),
+            skipped_on_hold_stock_lines: StockLineConnector::from_vec(skipped_on_hold_stock_lines),
+            issued_expiring_soon_stock_lines: StockLineConnector::from_vec(
+                issued_expiring_soon_stock_lines,
+            ),
+            shortfalls: shortfalls
+                .into_iter()
+                .map(
+                    |AllocationShortfall {
+                         item_id,
+                         item_name,
+                         requested_quantity,
+                         remaining_quantity,
+                     }| ShortfallNode {
+                        item_id,
+                        item_name,
+                        requested_quantity,
+                        remaining_quantity,
+                    },
+                )
+                .collect(),
+            allocation_strategy: AllocationStrategyNode::from_domain(&allocation_strategy),
+        }
+    }
+}
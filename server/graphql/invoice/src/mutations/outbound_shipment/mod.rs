@@ -1,3 +1,4 @@
+pub mod allocate;
 pub mod delete;
 pub mod error;
 pub mod insert;
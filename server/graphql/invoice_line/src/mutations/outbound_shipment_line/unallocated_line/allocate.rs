@@ -3,7 +3,9 @@ use graphql_core::{
     simple_generic_errors::RecordNotFound, standard_graphql_error::validate_auth,
     standard_graphql_error::StandardGraphqlError, ContextExt,
 };
-use graphql_types::types::{DeleteResponse, InvoiceLineConnector, StockLineConnector};
+use graphql_types::types::{
+    AllocationStrategyNode, DeleteResponse, InvoiceLineConnector, StockLineConnector,
+};
 use service::{
     auth::{Resource, ResourceAccessRequest},
     invoice_line::outbound_shipment_unallocated_line::{
@@ -40,6 +42,8 @@ pub struct ResponseNode {
     skipped_expired_stock_lines: StockLineConnector,
     skipped_on_hold_stock_lines: StockLineConnector,
     issued_expiring_soon_stock_lines: StockLineConnector,
+    /// Store allocation strategy used to choose stock lines
+    allocation_strategy: AllocationStrategyNode,
 }
 
 pub fn allocate(ctx: &Context<'_>, store_id: &str, line_id: String) -> Result<AllocateResponse> {
@@ -102,6 +106,7 @@ impl ResponseNode {
             skipped_expired_stock_lines,
             skipped_on_hold_stock_lines,
             issued_expiring_soon_stock_lines,
+            allocation_strategy,
         } = from;
         ResponseNode {
             updates: InvoiceLineConnector::from_vec(updates),
@@ -112,6 +117,7 @@ impl ResponseNode {
             issued_expiring_soon_stock_lines: StockLineConnector::from_vec(
                 issued_expiring_soon_stock_lines,
             ),
+            allocation_strategy: AllocationStrategyNode::from_domain(&allocation_strategy),
         }
     }
 }
@@ -123,7 +129,8 @@ mod graphql {
         assert_graphql_query, assert_standard_graphql_error, test_helpers::setup_graphql_test,
     };
     use repository::{
-        mock::MockDataInserts, InvoiceLine, InvoiceLineRow, StockLine, StorageConnectionManager,
+        mock::MockDataInserts, AllocationStrategy, InvoiceLine, InvoiceLineRow, StockLine,
+        StorageConnectionManager,
     };
     use serde_json::json;
 
@@ -288,6 +295,7 @@ mod graphql {
                             id
                         }
                     }
+                    allocationStrategy
                 }
             }
           }
@@ -321,6 +329,7 @@ mod graphql {
                 issued_expiring_soon_stock_lines: vec![inline_init(|r: &mut StockLine| {
                     r.stock_line_row.id = "expiring_soon".to_string();
                 })],
+                allocation_strategy: AllocationStrategy::Location,
             })
         }));
 
@@ -355,7 +364,8 @@ mod graphql {
                     "nodes": [{
                         "id": "expiring_soon"
                     }]
-                }
+                },
+                "allocationStrategy": "LOCATION"
             }
           }
         );
@@ -38,7 +38,7 @@ use graphql_pack_variant::{PackVariantMutations, PackVariantQueries};
 use graphql_plugin::{PluginMutations, PluginQueries};
 use graphql_programs::{CentralProgramsMutations, ProgramsMutations, ProgramsQueries};
 use graphql_repack::{RepackMutations, RepackQueries};
-use graphql_reports::ReportQueries;
+use graphql_reports::{ReportMutations, ReportQueries};
 use graphql_requisition::{RequisitionMutations, RequisitionQueries};
 use graphql_requisition_line::RequisitionLineMutations;
 use graphql_stock_line::{StockLineMutations, StockLineQueries};
@@ -180,6 +180,7 @@ pub struct Mutations(
     pub AssetMutations,
     pub AssetLogMutations,
     pub InventoryAdjustmentMutations,
+    pub ReportMutations,
 );
 
 impl Mutations {
@@ -204,6 +205,7 @@ impl Mutations {
             AssetMutations,
             AssetLogMutations,
             InventoryAdjustmentMutations,
+            ReportMutations,
         )
     }
 }
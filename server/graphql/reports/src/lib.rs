@@ -2,9 +2,15 @@ use async_graphql::*;
 use graphql_core::{generic_inputs::PrintReportSortInput, pagination::PaginationInput};
 use printing::{print_report, print_report_definition, PrintReportResponse};
 use reports::{reports, ReportFilterInput, ReportSortInput, ReportsResponse};
+use schedule::{
+    delete_report_schedule, report_schedule_runs, report_schedules, upsert_report_schedule,
+    DeleteReportScheduleResponse, ReportScheduleRunsResponse, ReportSchedulesResponse,
+    UpsertReportScheduleInput, UpsertReportScheduleResponse,
+};
 
 mod printing;
 mod reports;
+mod schedule;
 
 #[derive(Default, Clone)]
 pub struct ReportQueries;
@@ -13,6 +19,8 @@ pub struct ReportQueries;
 pub enum PrintFormat {
     Pdf,
     Html,
+    Xlsx,
+    Csv,
 }
 
 #[Object]
@@ -48,12 +56,13 @@ impl ReportQueries {
         format: Option<PrintFormat>,
         sort: Option<PrintReportSortInput>,
     ) -> Result<PrintReportResponse> {
-        let report_format = match format {
-            Some(PrintFormat::Html) => Some(service::report::report_service::PrintFormat::Html),
-            Some(PrintFormat::Pdf) | None => {
-                Some(service::report::report_service::PrintFormat::Pdf)
-            }
-        };
+        // Without format the report is printed in the output format of its definition
+        let report_format = format.map(|format| match format {
+            PrintFormat::Html => service::report::report_service::PrintFormat::Html,
+            PrintFormat::Pdf => service::report::report_service::PrintFormat::Pdf,
+            PrintFormat::Xlsx => service::report::report_service::PrintFormat::Xlsx,
+            PrintFormat::Csv => service::report::report_service::PrintFormat::Csv,
+        });
         print_report(
             ctx,
             store_id,
@@ -79,4 +88,53 @@ impl ReportQueries {
     ) -> Result<PrintReportResponse> {
         print_report_definition(ctx, store_id, name, report, data_id, arguments).await
     }
+
+    /// Report schedules of the store, i.e. reports that are generated automatically
+    pub async fn report_schedules(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<ReportSchedulesResponse> {
+        report_schedules(ctx, store_id)
+    }
+
+    /// History of generated scheduled reports, most recent first.
+    /// The generated files can be retrieved from the `/files` endpoint using the file id.
+    pub async fn report_schedule_runs(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        report_schedule_id: Option<String>,
+        page: Option<PaginationInput>,
+    ) -> Result<ReportScheduleRunsResponse> {
+        report_schedule_runs(ctx, store_id, report_schedule_id, page)
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct ReportMutations;
+
+#[Object]
+impl ReportMutations {
+    /// Creates or updates a report schedule, reports are generated in the background on schedule.
+    /// Only reports that fetch their data with SQL queries can be scheduled: GraphQL queries are
+    /// run on behalf of a logged in user, reports using them are rejected as not schedulable.
+    pub async fn upsert_report_schedule(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: UpsertReportScheduleInput,
+    ) -> Result<UpsertReportScheduleResponse> {
+        upsert_report_schedule(ctx, &store_id, input)
+    }
+
+    /// Deletes the report schedule and its run history
+    pub async fn delete_report_schedule(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<DeleteReportScheduleResponse> {
+        delete_report_schedule(ctx, &store_id, &id)
+    }
 }
@@ -22,10 +22,25 @@ impl FailedToFetchReportData {
     }
 }
 
+pub struct InvalidReportArguments {
+    errors: Vec<String>,
+}
+#[Object]
+impl InvalidReportArguments {
+    pub async fn description(&self) -> &str {
+        "Report arguments don't match the argument schema of the report"
+    }
+
+    pub async fn errors(&self) -> &Vec<String> {
+        &self.errors
+    }
+}
+
 #[derive(Interface)]
 #[graphql(field(name = "description", ty = "String"))]
 pub enum PrintReportErrorInterface {
     FailedToFetchReportData(FailedToFetchReportData),
+    InvalidReportArguments(InvalidReportArguments),
 }
 
 #[derive(SimpleObject)]
@@ -85,6 +100,13 @@ pub async fn print_report(
         }
     };
 
+    // validate arguments before running any queries
+    if let Err(err) = service.validate_report_arguments(&resolved_report, &arguments) {
+        return Ok(PrintReportResponse::Error(PrintReportError {
+            error: map_error(err)?,
+        }));
+    }
+
     // fetch data required for the report
     let result = fetch_data(
         ctx,
@@ -162,6 +184,13 @@ pub async fn print_report_definition(
         }
     };
 
+    // validate arguments before running any queries
+    if let Err(err) = service.validate_report_arguments(&resolved_report, &arguments) {
+        return Ok(PrintReportResponse::Error(PrintReportError {
+            error: map_error(err)?,
+        }));
+    }
+
     // fetch data required for the report
     let result = fetch_data(
         ctx,
@@ -353,6 +382,14 @@ fn map_error(error: ReportError) -> Result<PrintReportErrorInterface> {
     let formatted_error = format!("{:#?}", error);
 
     let graphql_error = match error {
+        // Structured Errors
+        ReportError::InvalidArguments(errors) => {
+            return Ok(PrintReportErrorInterface::InvalidReportArguments(
+                InvalidReportArguments { errors },
+            ))
+        }
+
+        // Standard Graphql Errors
         ReportError::RepositoryError(_) => StandardGraphqlError::InternalError(formatted_error),
         ReportError::ReportDefinitionNotFound {
             report_id: _,
@@ -366,6 +403,9 @@ fn map_error(error: ReportError) -> Result<PrintReportErrorInterface> {
         ReportError::QueryError(_) => StandardGraphqlError::InternalError(formatted_error),
         ReportError::DocGenerationError(_) => StandardGraphqlError::InternalError(formatted_error),
         ReportError::HTMLToPDFError(_) => StandardGraphqlError::InternalError(formatted_error),
+        ReportError::InvalidArgumentSchema(_) => {
+            StandardGraphqlError::InternalError(formatted_error)
+        }
         ReportError::MultipleGraphqlQueriesNotAllowed => {
             StandardGraphqlError::BadUserInput(formatted_error)
         }
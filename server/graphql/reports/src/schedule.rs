@@ -0,0 +1,318 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    pagination::PaginationInput,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::DeleteResponse;
+use repository::{
+    PaginationOption, ReportScheduleRow, ReportScheduleRunFilter, ReportScheduleRunRow,
+};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    report::report_schedule::{
+        DeleteReportScheduleError, UpsertReportSchedule, UpsertReportScheduleError,
+    },
+};
+
+#[derive(PartialEq, Debug)]
+pub struct ReportScheduleNode {
+    row: ReportScheduleRow,
+}
+
+#[Object]
+impl ReportScheduleNode {
+    pub async fn id(&self) -> &str {
+        &self.row.id
+    }
+
+    pub async fn report_id(&self) -> &str {
+        &self.row.report_id
+    }
+
+    pub async fn name(&self) -> &str {
+        &self.row.name
+    }
+
+    /// Cron expression, e.g. "0 6 1 * *" or "@monthly"
+    pub async fn schedule(&self) -> &str {
+        &self.row.schedule
+    }
+
+    pub async fn arguments(&self) -> Option<serde_json::Value> {
+        self.row
+            .arguments
+            .as_ref()
+            .and_then(|arguments| serde_json::from_str(arguments).ok())
+    }
+
+    pub async fn is_active(&self) -> bool {
+        self.row.is_active
+    }
+
+    pub async fn last_run_datetime(&self) -> Option<DateTime<Utc>> {
+        self.row
+            .last_run_datetime
+            .map(|datetime| DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc))
+    }
+
+    pub async fn next_run_datetime(&self) -> Option<DateTime<Utc>> {
+        self.row
+            .next_run_datetime
+            .map(|datetime| DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc))
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct ReportScheduleRunNode {
+    row: ReportScheduleRunRow,
+}
+
+#[Object]
+impl ReportScheduleRunNode {
+    pub async fn id(&self) -> &str {
+        &self.row.id
+    }
+
+    pub async fn report_schedule_id(&self) -> &str {
+        &self.row.report_schedule_id
+    }
+
+    pub async fn report_id(&self) -> &str {
+        &self.row.report_id
+    }
+
+    pub async fn run_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_naive_utc_and_offset(self.row.run_datetime, Utc)
+    }
+
+    /// The generated file can be fetched using the /files?id={id} endpoint
+    pub async fn file_id(&self) -> &Option<String> {
+        &self.row.file_id
+    }
+
+    pub async fn file_name(&self) -> &Option<String> {
+        &self.row.file_name
+    }
+
+    /// Set if the report failed to generate
+    pub async fn error(&self) -> &Option<String> {
+        &self.row.error
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ReportScheduleConnector {
+    total_count: u32,
+    nodes: Vec<ReportScheduleNode>,
+}
+
+#[derive(SimpleObject)]
+pub struct ReportScheduleRunConnector {
+    total_count: u32,
+    nodes: Vec<ReportScheduleRunNode>,
+}
+
+#[derive(Union)]
+pub enum ReportSchedulesResponse {
+    Response(ReportScheduleConnector),
+}
+
+#[derive(Union)]
+pub enum ReportScheduleRunsResponse {
+    Response(ReportScheduleRunConnector),
+}
+
+#[derive(Union)]
+pub enum UpsertReportScheduleResponse {
+    Response(ReportScheduleNode),
+}
+
+#[derive(Union)]
+pub enum DeleteReportScheduleResponse {
+    Response(DeleteResponse),
+}
+
+#[derive(InputObject)]
+pub struct UpsertReportScheduleInput {
+    pub id: String,
+    pub report_id: String,
+    pub name: String,
+    /// Cron expression (minute hour day-of-month month day-of-week), e.g. "0 6 1 * *" for 6am on
+    /// the 1st of every month, or one of @daily, @weekly, @monthly, @yearly
+    pub schedule: String,
+    /// Report arguments. String values can be relative dates which are resolved when the report is
+    /// generated: $today, $yesterday, $currentMonthStart, $previousMonthStart, $previousMonthEnd,
+    /// $previousWeekStart, $previousWeekEnd, $previousYearStart, $previousYearEnd
+    pub arguments: Option<serde_json::Value>,
+    pub is_active: Option<bool>,
+}
+
+pub fn report_schedules(ctx: &Context<'_>, store_id: String) -> Result<ReportSchedulesResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::Report,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.clone(), user.user_id)?;
+    let rows = service_provider
+        .report_schedule_service
+        .get_report_schedules(&service_context, &store_id)?;
+
+    Ok(ReportSchedulesResponse::Response(ReportScheduleConnector {
+        total_count: rows.len() as u32,
+        nodes: rows
+            .into_iter()
+            .map(|row| ReportScheduleNode { row })
+            .collect(),
+    }))
+}
+
+pub fn report_schedule_runs(
+    ctx: &Context<'_>,
+    store_id: String,
+    report_schedule_id: Option<String>,
+    page: Option<PaginationInput>,
+) -> Result<ReportScheduleRunsResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::Report,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let filter = ReportScheduleRunFilter::new().store_id(EqualFilter::equal_to(&store_id));
+    let filter = match report_schedule_id {
+        Some(id) => filter.report_schedule_id(EqualFilter::equal_to(&id)),
+        None => filter,
+    };
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.clone(), user.user_id)?;
+    let result = service_provider
+        .report_schedule_service
+        .get_report_schedule_runs(
+            &service_context,
+            page.map(PaginationOption::from),
+            Some(filter),
+        )
+        .map_err(StandardGraphqlError::from_list_error)?;
+
+    Ok(ReportScheduleRunsResponse::Response(
+        ReportScheduleRunConnector {
+            total_count: result.count,
+            nodes: result
+                .rows
+                .into_iter()
+                .map(|row| ReportScheduleRunNode { row })
+                .collect(),
+        },
+    ))
+}
+
+pub fn upsert_report_schedule(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: UpsertReportScheduleInput,
+) -> Result<UpsertReportScheduleResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateReportSchedule,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    match service_provider
+        .report_schedule_service
+        .upsert_report_schedule(&service_context, input.to_domain())
+    {
+        Ok(row) => Ok(UpsertReportScheduleResponse::Response(ReportScheduleNode {
+            row,
+        })),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                UpsertReportScheduleError::NotThisStoreReportSchedule
+                | UpsertReportScheduleError::ReportDoesNotExist
+                | UpsertReportScheduleError::InvalidReport(_)
+                | UpsertReportScheduleError::ReportNotSchedulable
+                | UpsertReportScheduleError::InvalidSchedule(_)
+                | UpsertReportScheduleError::InvalidArguments
+                | UpsertReportScheduleError::ArgumentsDoNotMatchSchema(_) => {
+                    BadUserInput(formatted_error)
+                }
+                UpsertReportScheduleError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn delete_report_schedule(
+    ctx: &Context<'_>,
+    store_id: &str,
+    id: &str,
+) -> Result<DeleteReportScheduleResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateReportSchedule,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    match service_provider
+        .report_schedule_service
+        .delete_report_schedule(&service_context, id)
+    {
+        Ok(id) => Ok(DeleteReportScheduleResponse::Response(DeleteResponse(id))),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                DeleteReportScheduleError::ReportScheduleDoesNotExist
+                | DeleteReportScheduleError::NotThisStoreReportSchedule => {
+                    BadUserInput(formatted_error)
+                }
+                DeleteReportScheduleError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+impl UpsertReportScheduleInput {
+    pub fn to_domain(self) -> UpsertReportSchedule {
+        let UpsertReportScheduleInput {
+            id,
+            report_id,
+            name,
+            schedule,
+            arguments,
+            is_active,
+        } = self;
+
+        UpsertReportSchedule {
+            id,
+            report_id,
+            name,
+            schedule,
+            arguments,
+            is_active: is_active.unwrap_or(true),
+        }
+    }
+}
@@ -3,10 +3,10 @@ mod program_settings;
 mod requisition_queries;
 use async_graphql::*;
 use graphql_core::pagination::PaginationInput;
-use graphql_types::types::RequisitionNodeType;
+use graphql_types::types::{RequisitionApprovalLevelNode, RequisitionNodeType};
 use program_settings::{get_program_requisition_settings, ProgramRequisitionSettingNode};
 
-use self::mutations::{request_requisition, response_requisition};
+use self::mutations::{approval_level, request_requisition, response_requisition};
 use self::requisition_queries::*;
 #[derive(Default, Clone)]
 pub struct RequisitionQueries;
@@ -50,6 +50,15 @@ impl RequisitionQueries {
     ) -> Result<Vec<ProgramRequisitionSettingNode>> {
         get_program_requisition_settings(ctx, &store_id)
     }
+
+    /// Approval chain levels of response requisitions in the store
+    pub async fn requisition_approval_levels(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<Vec<RequisitionApprovalLevelNode>> {
+        approval_level::get_requisition_approval_levels(ctx, &store_id)
+    }
 }
 
 #[derive(Default, Clone)]
@@ -149,6 +158,34 @@ impl RequisitionMutations {
             ctx, &store_id, input,
         )
     }
+
+    /// Approve or deny the current level of a response requisition pending approval
+    async fn approve_requisition(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: response_requisition::approve::ApproveRequisitionInput,
+    ) -> Result<response_requisition::approve::ApproveRequisitionResponse> {
+        response_requisition::approve::approve_requisition(ctx, &store_id, input)
+    }
+
+    async fn upsert_requisition_approval_level(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: approval_level::UpsertRequisitionApprovalLevelInput,
+    ) -> Result<approval_level::UpsertRequisitionApprovalLevelResponse> {
+        approval_level::upsert_requisition_approval_level(ctx, &store_id, input)
+    }
+
+    async fn delete_requisition_approval_level(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<approval_level::DeleteRequisitionApprovalLevelResponse> {
+        approval_level::delete_requisition_approval_level(ctx, &store_id, &id)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,166 @@
+use async_graphql::*;
+
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{DeleteResponse as GenericDeleteResponse, RequisitionApprovalLevelNode};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    requisition::approval::chain::{
+        DeleteRequisitionApprovalLevelError, UpsertRequisitionApprovalLevel,
+        UpsertRequisitionApprovalLevelError,
+    },
+};
+
+#[derive(InputObject)]
+pub struct UpsertRequisitionApprovalLevelInput {
+    pub id: String,
+    /// Approval chain for response requisitions of this program, applies to
+    /// requisitions without a program if not set
+    pub program_id: Option<String>,
+    pub level: i32,
+    pub name: String,
+    /// Any user with the approve requisition permission can approve this level if not set
+    pub approver_user_id: Option<String>,
+}
+
+#[derive(Union)]
+pub enum UpsertRequisitionApprovalLevelResponse {
+    Response(RequisitionApprovalLevelNode),
+}
+
+#[derive(Union)]
+pub enum DeleteRequisitionApprovalLevelResponse {
+    Response(GenericDeleteResponse),
+}
+
+pub fn upsert_requisition_approval_level(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: UpsertRequisitionApprovalLevelInput,
+) -> Result<UpsertRequisitionApprovalLevelResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateRequisitionApprovalChain,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    match service_provider
+        .requisition_service
+        .upsert_requisition_approval_level(&service_context, input.to_domain())
+    {
+        Ok(level) => Ok(UpsertRequisitionApprovalLevelResponse::Response(
+            RequisitionApprovalLevelNode::from_domain(level),
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                UpsertRequisitionApprovalLevelError::NotThisStoreApprovalLevel
+                | UpsertRequisitionApprovalLevelError::LevelMustBePositive
+                | UpsertRequisitionApprovalLevelError::LevelAlreadyExists
+                | UpsertRequisitionApprovalLevelError::ProgramDoesNotExist
+                | UpsertRequisitionApprovalLevelError::ApproverDoesNotExist => {
+                    BadUserInput(formatted_error)
+                }
+                UpsertRequisitionApprovalLevelError::DatabaseError(_) => {
+                    InternalError(formatted_error)
+                }
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn delete_requisition_approval_level(
+    ctx: &Context<'_>,
+    store_id: &str,
+    id: &str,
+) -> Result<DeleteRequisitionApprovalLevelResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateRequisitionApprovalChain,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    match service_provider
+        .requisition_service
+        .delete_requisition_approval_level(&service_context, id)
+    {
+        Ok(id) => Ok(DeleteRequisitionApprovalLevelResponse::Response(
+            GenericDeleteResponse(id),
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                DeleteRequisitionApprovalLevelError::ApprovalLevelDoesNotExist
+                | DeleteRequisitionApprovalLevelError::NotThisStoreApprovalLevel
+                | DeleteRequisitionApprovalLevelError::RequisitionsPendingApproval => {
+                    BadUserInput(formatted_error)
+                }
+                DeleteRequisitionApprovalLevelError::DatabaseError(_) => {
+                    InternalError(formatted_error)
+                }
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn get_requisition_approval_levels(
+    ctx: &Context<'_>,
+    store_id: &str,
+) -> Result<Vec<RequisitionApprovalLevelNode>> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    let levels = service_provider
+        .requisition_service
+        .get_requisition_approval_levels(&service_context, store_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(levels
+        .into_iter()
+        .map(RequisitionApprovalLevelNode::from_domain)
+        .collect())
+}
+
+impl UpsertRequisitionApprovalLevelInput {
+    pub fn to_domain(self) -> UpsertRequisitionApprovalLevel {
+        let UpsertRequisitionApprovalLevelInput {
+            id,
+            program_id,
+            level,
+            name,
+            approver_user_id,
+        } = self;
+
+        UpsertRequisitionApprovalLevel {
+            id,
+            program_id,
+            level,
+            name,
+            approver_user_id,
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use async_graphql::*;
+
+use graphql_core::{
+    simple_generic_errors::{CannotEditRequisition, RecordNotFound},
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::RequisitionNode;
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    requisition::approval::approve::{
+        ApproveRequisition as ServiceInput, ApproveRequisitionError as ServiceError,
+        ApproveRequisitionStatus,
+    },
+};
+
+#[derive(InputObject)]
+pub struct ApproveRequisitionInput {
+    pub id: String,
+    pub status: ApproveRequisitionStatusInput,
+    /// Required when denying
+    pub comment: Option<String>,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ApproveRequisitionStatusInput {
+    Approved,
+    Denied,
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", ty = "String"))]
+pub enum ApproveRequisitionErrorInterface {
+    RecordNotFound(RecordNotFound),
+    CannotEditRequisition(CannotEditRequisition),
+}
+
+#[derive(SimpleObject)]
+pub struct ApproveRequisitionError {
+    pub error: ApproveRequisitionErrorInterface,
+}
+
+#[derive(Union)]
+pub enum ApproveRequisitionResponse {
+    Error(ApproveRequisitionError),
+    Response(RequisitionNode),
+}
+
+pub fn approve_requisition(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: ApproveRequisitionInput,
+) -> Result<ApproveRequisitionResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ApproveRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    let response = match service_provider
+        .requisition_service
+        .approve_requisition(&service_context, input.to_domain())
+    {
+        Ok(requisition) => {
+            ApproveRequisitionResponse::Response(RequisitionNode::from_domain(requisition))
+        }
+        Err(error) => ApproveRequisitionResponse::Error(ApproveRequisitionError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(response)
+}
+
+impl ApproveRequisitionInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let ApproveRequisitionInput {
+            id,
+            status,
+            comment,
+        } = self;
+
+        ServiceInput {
+            id,
+            status: match status {
+                ApproveRequisitionStatusInput::Approved => ApproveRequisitionStatus::Approved,
+                ApproveRequisitionStatusInput::Denied => ApproveRequisitionStatus::Denied,
+            },
+            comment,
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> Result<ApproveRequisitionErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::RequisitionDoesNotExist => {
+            return Ok(ApproveRequisitionErrorInterface::RecordNotFound(
+                RecordNotFound {},
+            ))
+        }
+        ServiceError::CannotEditRequisition => {
+            return Ok(ApproveRequisitionErrorInterface::CannotEditRequisition(
+                CannotEditRequisition {},
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::NotThisStoreRequisition
+        | ServiceError::NotAResponseRequisition
+        | ServiceError::NotPendingApproval
+        | ServiceError::UserCannotApproveLevel
+        | ServiceError::CommentRequiredToDeny => BadUserInput(formatted_error),
+        ServiceError::UpdatedRequisitionDoesNotExist | ServiceError::DatabaseError(_) => {
+            InternalError(formatted_error)
+        }
+    };
+
+    Err(graphql_error.extend())
+}
@@ -1,3 +1,4 @@
+pub(crate) mod approve;
 pub(crate) mod create_requisition_shipment;
 pub(crate) mod supply_requested_quantity;
 pub(crate) mod update;
@@ -12,6 +12,9 @@ pub struct ProgramRequisitionOrderTypeNode {
     pub name: String,
     pub id: String,
     pub available_periods: Vec<PeriodNode>,
+    pub is_emergency: bool,
+    /// Maximum number of items in an emergency order, 0 for no limit
+    pub max_items_in_emergency_order: i32,
 }
 
 #[derive(SimpleObject)]
@@ -72,6 +75,8 @@ pub fn get_program_requisition_settings(
                                 .into_iter()
                                 .map(PeriodNode::from_domain)
                                 .collect(),
+                            is_emergency: order_type.is_emergency,
+                            max_items_in_emergency_order: order_type.max_items_in_emergency_order,
                         },
                     )
                     .collect(),
@@ -44,4 +44,14 @@ impl RequisitionLineMutations {
     ) -> Result<response_requisition_line::UpdateResponse> {
         response_requisition_line::update(ctx, &store_id, input)
     }
+
+    /// Set the approved quantity of a line in a response requisition pending approval
+    async fn update_response_requisition_line_approval(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: response_requisition_line::UpdateResponseRequisitionLineApprovalInput,
+    ) -> Result<response_requisition_line::UpdateResponseRequisitionLineApprovalResponse> {
+        response_requisition_line::update_approval(ctx, &store_id, input)
+    }
 }
@@ -20,6 +20,12 @@ pub struct UpdateInput {
     pub id: String,
     pub requested_quantity: Option<f64>,
     pub comment: Option<String>,
+    pub opening_balance: Option<f64>,
+    pub quantity_received: Option<f64>,
+    pub quantity_dispensed: Option<f64>,
+    pub losses_and_adjustments: Option<f64>,
+    pub closing_balance: Option<f64>,
+    pub days_out_of_stock: Option<i32>,
 }
 
 #[derive(Interface)]
@@ -81,12 +87,24 @@ impl UpdateInput {
             id,
             requested_quantity,
             comment,
+            opening_balance,
+            quantity_received,
+            quantity_dispensed,
+            losses_and_adjustments,
+            closing_balance,
+            days_out_of_stock,
         } = self;
 
         ServiceInput {
             id,
             requested_quantity,
             comment,
+            opening_balance,
+            quantity_received,
+            quantity_dispensed,
+            losses_and_adjustments,
+            closing_balance,
+            days_out_of_stock,
         }
     }
 }
@@ -113,6 +131,9 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
         // Standard Graphql Errors
         ServiceError::NotThisStoreRequisition => BadUserInput(formatted_error),
         ServiceError::NotARequestRequisition => BadUserInput(formatted_error),
+        ServiceError::LmisBalanceMismatch => BadUserInput(formatted_error),
+        ServiceError::DaysOutOfStockNegative => BadUserInput(formatted_error),
+        ServiceError::RequestedQuantityAboveEmergencyMaximum => BadUserInput(formatted_error),
         ServiceError::UpdatedRequisitionLineDoesNotExist => InternalError(formatted_error),
         ServiceError::DatabaseError(_) => InternalError(formatted_error),
     };
@@ -328,7 +349,8 @@ mod test {
                 ServiceInput {
                     id: "update line id input".to_string(),
                     requested_quantity: Some(1.0),
-                    comment: Some("comment".to_string())
+                    comment: Some("comment".to_string()),
+                    ..Default::default()
                 }
             );
             Ok(RequisitionLine {
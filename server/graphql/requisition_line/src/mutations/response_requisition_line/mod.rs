@@ -1,2 +1,4 @@
 mod update;
 pub use update::*;
+mod update_approval;
+pub use update_approval::*;
@@ -0,0 +1,133 @@
+use async_graphql::*;
+
+use graphql_core::{
+    simple_generic_errors::{CannotEditRequisition, ForeignKey, ForeignKeyError, RecordNotFound},
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::RequisitionLineNode;
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    requisition_line::response_requisition_line::{
+        UpdateResponseRequisitionLineApproval as ServiceInput,
+        UpdateResponseRequisitionLineApprovalError as ServiceError,
+    },
+};
+
+#[derive(InputObject)]
+pub struct UpdateResponseRequisitionLineApprovalInput {
+    pub id: String,
+    pub approved_quantity: f64,
+    /// Required when approved quantity is below requested quantity
+    pub approval_comment: Option<String>,
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", ty = "String"))]
+pub enum UpdateResponseRequisitionLineApprovalErrorInterface {
+    RecordNotFound(RecordNotFound),
+    RequisitionDoesNotExist(ForeignKeyError),
+    CannotEditRequisition(CannotEditRequisition),
+}
+
+#[derive(SimpleObject)]
+pub struct UpdateResponseRequisitionLineApprovalError {
+    pub error: UpdateResponseRequisitionLineApprovalErrorInterface,
+}
+
+#[derive(Union)]
+pub enum UpdateResponseRequisitionLineApprovalResponse {
+    Error(UpdateResponseRequisitionLineApprovalError),
+    Response(RequisitionLineNode),
+}
+
+pub fn update_approval(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: UpdateResponseRequisitionLineApprovalInput,
+) -> Result<UpdateResponseRequisitionLineApprovalResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ApproveRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    let response = match service_provider
+        .requisition_line_service
+        .update_response_requisition_line_approval(&service_context, input.to_domain())
+    {
+        Ok(requisition_line) => UpdateResponseRequisitionLineApprovalResponse::Response(
+            RequisitionLineNode::from_domain(requisition_line),
+        ),
+        Err(error) => UpdateResponseRequisitionLineApprovalResponse::Error(
+            UpdateResponseRequisitionLineApprovalError {
+                error: map_error(error)?,
+            },
+        ),
+    };
+
+    Ok(response)
+}
+
+impl UpdateResponseRequisitionLineApprovalInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let UpdateResponseRequisitionLineApprovalInput {
+            id,
+            approved_quantity,
+            approval_comment,
+        } = self;
+
+        ServiceInput {
+            id,
+            approved_quantity,
+            approval_comment,
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> Result<UpdateResponseRequisitionLineApprovalErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::RequisitionLineDoesNotExist => {
+            return Ok(
+                UpdateResponseRequisitionLineApprovalErrorInterface::RecordNotFound(
+                    RecordNotFound {},
+                ),
+            )
+        }
+        ServiceError::RequisitionDoesNotExist => {
+            return Ok(
+                UpdateResponseRequisitionLineApprovalErrorInterface::RequisitionDoesNotExist(
+                    ForeignKeyError(ForeignKey::RequisitionId),
+                ),
+            )
+        }
+        ServiceError::CannotEditRequisition => {
+            return Ok(
+                UpdateResponseRequisitionLineApprovalErrorInterface::CannotEditRequisition(
+                    CannotEditRequisition {},
+                ),
+            )
+        }
+        // Standard Graphql Errors
+        ServiceError::NotThisStoreRequisition
+        | ServiceError::NotAResponseRequisition
+        | ServiceError::NotPendingApproval
+        | ServiceError::UserCannotApproveLevel
+        | ServiceError::ApprovedQuantityBelowZero
+        | ServiceError::CommentRequiredForReduction => BadUserInput(formatted_error),
+        ServiceError::UpdatedRequisitionLineDoesNotExist | ServiceError::DatabaseError(_) => {
+            InternalError(formatted_error)
+        }
+    };
+
+    Err(graphql_error.extend())
+}
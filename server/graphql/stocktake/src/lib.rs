@@ -3,7 +3,7 @@ mod stocktake_queries;
 use self::stocktake_queries::*;
 use async_graphql::*;
 use graphql_core::pagination::PaginationInput;
-use mutations::{delete::*, insert::*, update::*};
+use mutations::{blind_count::*, delete::*, insert::*, update::*};
 
 #[derive(Default, Clone)]
 pub struct StocktakeQueries;
@@ -71,4 +71,24 @@ impl StocktakeMutations {
     ) -> Result<DeleteResponse> {
         delete(ctx, &store_id, input)
     }
+
+    /// Ends the counting of a blind count stocktake, revealing the snapshot quantities
+    async fn submit_stocktake_count(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: SubmitStocktakeCountInput,
+    ) -> Result<SubmitStocktakeCountResponse> {
+        submit_stocktake_count(ctx, &store_id, input)
+    }
+
+    /// Approves the variance of a submitted blind count stocktake so it can be finalised
+    async fn approve_stocktake_variance(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: ApproveStocktakeVarianceInput,
+    ) -> Result<ApproveStocktakeVarianceResponse> {
+        approve_stocktake_variance(ctx, &store_id, input)
+    }
 }
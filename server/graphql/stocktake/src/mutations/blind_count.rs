@@ -0,0 +1,199 @@
+use async_graphql::*;
+
+use graphql_core::simple_generic_errors::CannotEditStocktake;
+use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
+use graphql_core::ContextExt;
+use graphql_types::types::{StocktakeLineConnector, StocktakeNode};
+use repository::Stocktake;
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    stocktake::{
+        ApproveStocktakeVarianceError as ApproveServiceError,
+        SubmitStocktakeCountError as SubmitServiceError,
+    },
+};
+
+#[derive(InputObject)]
+pub struct SubmitStocktakeCountInput {
+    pub id: String,
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", ty = "String"))]
+pub enum SubmitStocktakeCountErrorInterface {
+    CannotEditStocktake(CannotEditStocktake),
+}
+
+#[derive(SimpleObject)]
+pub struct SubmitStocktakeCountError {
+    pub error: SubmitStocktakeCountErrorInterface,
+}
+
+#[derive(Union)]
+pub enum SubmitStocktakeCountResponse {
+    Error(SubmitStocktakeCountError),
+    Response(StocktakeNode),
+}
+
+#[derive(InputObject)]
+pub struct ApproveStocktakeVarianceInput {
+    pub id: String,
+}
+
+pub struct SecondCountsMissing(StocktakeLineConnector);
+#[Object]
+impl SecondCountsMissing {
+    pub async fn description(&self) -> &str {
+        "Lines with a variance above the threshold need a second count"
+    }
+
+    pub async fn lines(&self) -> &StocktakeLineConnector {
+        &self.0
+    }
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", ty = "String"))]
+pub enum ApproveStocktakeVarianceErrorInterface {
+    CannotEditStocktake(CannotEditStocktake),
+    SecondCountsMissing(SecondCountsMissing),
+}
+
+#[derive(SimpleObject)]
+pub struct ApproveStocktakeVarianceError {
+    pub error: ApproveStocktakeVarianceErrorInterface,
+}
+
+#[derive(Union)]
+pub enum ApproveStocktakeVarianceResponse {
+    Error(ApproveStocktakeVarianceError),
+    Response(StocktakeNode),
+}
+
+pub fn submit_stocktake_count(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: SubmitStocktakeCountInput,
+) -> Result<SubmitStocktakeCountResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateStocktake,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+    map_submit_response(
+        service_provider
+            .stocktake_service
+            .submit_stocktake_count(&service_context, input.id),
+    )
+}
+
+pub fn approve_stocktake_variance(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: ApproveStocktakeVarianceInput,
+) -> Result<ApproveStocktakeVarianceResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ApproveStocktakeVariance,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+    map_approve_response(
+        service_provider
+            .stocktake_service
+            .approve_stocktake_variance(&service_context, input.id),
+    )
+}
+
+fn map_submit_response(
+    from: Result<Stocktake, SubmitServiceError>,
+) -> Result<SubmitStocktakeCountResponse> {
+    use StandardGraphqlError::*;
+    let error = match from {
+        Ok(stocktake) => {
+            return Ok(SubmitStocktakeCountResponse::Response(
+                StocktakeNode::from_domain(stocktake),
+            ))
+        }
+        Err(error) => error,
+    };
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        SubmitServiceError::CannotEditFinalised => {
+            return Ok(SubmitStocktakeCountResponse::Error(
+                SubmitStocktakeCountError {
+                    error: SubmitStocktakeCountErrorInterface::CannotEditStocktake(
+                        CannotEditStocktake {},
+                    ),
+                },
+            ))
+        }
+        // Standard Graphql Errors
+        SubmitServiceError::InvalidStore
+        | SubmitServiceError::StocktakeDoesNotExist
+        | SubmitServiceError::NotABlindCount
+        | SubmitServiceError::CountAlreadySubmitted => BadUserInput(formatted_error),
+        SubmitServiceError::InternalError(err) => InternalError(err),
+        SubmitServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
+
+fn map_approve_response(
+    from: Result<Stocktake, ApproveServiceError>,
+) -> Result<ApproveStocktakeVarianceResponse> {
+    use StandardGraphqlError::*;
+    let error = match from {
+        Ok(stocktake) => {
+            return Ok(ApproveStocktakeVarianceResponse::Response(
+                StocktakeNode::from_domain(stocktake),
+            ))
+        }
+        Err(error) => error,
+    };
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ApproveServiceError::CannotEditFinalised => {
+            return Ok(ApproveStocktakeVarianceResponse::Error(
+                ApproveStocktakeVarianceError {
+                    error: ApproveStocktakeVarianceErrorInterface::CannotEditStocktake(
+                        CannotEditStocktake {},
+                    ),
+                },
+            ))
+        }
+        ApproveServiceError::SecondCountsMissing(lines) => {
+            return Ok(ApproveStocktakeVarianceResponse::Error(
+                ApproveStocktakeVarianceError {
+                    error: ApproveStocktakeVarianceErrorInterface::SecondCountsMissing(
+                        SecondCountsMissing(StocktakeLineConnector::from_domain_vec(lines)),
+                    ),
+                },
+            ))
+        }
+        // Standard Graphql Errors
+        ApproveServiceError::InvalidStore
+        | ApproveServiceError::StocktakeDoesNotExist
+        | ApproveServiceError::NotABlindCount
+        | ApproveServiceError::CountNotSubmitted
+        | ApproveServiceError::VarianceAlreadyApproved => BadUserInput(formatted_error),
+        ApproveServiceError::InternalError(err) => InternalError(err),
+        ApproveServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
@@ -25,6 +25,11 @@ pub struct InsertInput {
     pub location: Option<NullableUpdateInput<String>>,
     pub items_have_stock: Option<bool>,
     pub expires_before: Option<NaiveDate>,
+    /// Hides snapshot quantities until the count is submitted and requires variance approval
+    /// before finalising
+    pub is_blind_count: Option<bool>,
+    /// Blind count lines with a larger variance need a second count
+    pub variance_threshold_percentage: Option<f64>,
 }
 
 #[derive(Union)]
@@ -100,6 +105,8 @@ impl InsertInput {
             master_list_id,
             items_have_stock,
             expires_before,
+            is_blind_count,
+            variance_threshold_percentage,
         } = self;
 
         ServiceInput {
@@ -114,6 +121,8 @@ impl InsertInput {
             master_list_id,
             items_have_stock,
             expires_before,
+            is_blind_count,
+            variance_threshold_percentage,
         }
     }
 }
@@ -191,7 +200,9 @@ mod test {
                     location: None,
                     master_list_id: None,
                     items_have_stock: None,
-                    expires_before: None
+                    expires_before: None,
+                    is_blind_count: None,
+                    variance_threshold_percentage: None,
                 }
             );
             // StocktakeNode result is checked in queries
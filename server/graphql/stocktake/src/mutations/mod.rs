@@ -1,3 +1,4 @@
+pub mod blind_count;
 pub mod delete;
 pub mod insert;
 pub mod update;
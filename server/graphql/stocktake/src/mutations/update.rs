@@ -140,6 +140,8 @@ fn map_error(err: ServiceError) -> Result<UpdateErrorInterface> {
         ServiceError::InvalidStore => BadUserInput(formatted_error),
         ServiceError::StocktakeDoesNotExist => BadUserInput(formatted_error),
         ServiceError::NoLines => BadUserInput(formatted_error),
+        ServiceError::BlindCountNotSubmitted => BadUserInput(formatted_error),
+        ServiceError::VarianceNotApproved => BadUserInput(formatted_error),
         ServiceError::InternalError(err) => InternalError(err),
         ServiceError::InsertStockInLineError { .. }
         | ServiceError::InsertStockOutLineError { .. }
@@ -79,6 +79,7 @@ fn map_error(error: ServiceError) -> Result<DeleteErrorInterface> {
         // Standard Graphql Errors
         // TODO some are structured errors (where can be changed concurrently)
         ServiceError::StocktakeIsLocked => BadUserInput(formatted_error),
+        ServiceError::CountAlreadySubmitted => BadUserInput(formatted_error),
         ServiceError::StocktakeLineDoesNotExist => BadUserInput(formatted_error),
         ServiceError::InvalidStore => BadUserInput(formatted_error),
         ServiceError::DatabaseError(_) => InternalError(formatted_error),
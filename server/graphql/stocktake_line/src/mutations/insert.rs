@@ -124,6 +124,7 @@ fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
         ServiceError::StockLineAlreadyExistsInStocktake => BadUserInput(formatted_error),
         ServiceError::LocationDoesNotExist => BadUserInput(formatted_error),
         ServiceError::StocktakeIsLocked => BadUserInput(formatted_error),
+        ServiceError::CountAlreadySubmitted => BadUserInput(formatted_error),
         ServiceError::StockLineXOrItem => BadUserInput(format!(
             "Either a stock line id or item id must be set (not both), {}",
             formatted_error
@@ -292,6 +293,7 @@ mod test {
                     sell_price_per_pack: Some(12.0),
                     note: Some("note".to_string()),
                     inventory_adjustment_reason_id: None,
+                    second_counted_number_of_packs: None,
                 },
                 stock_line: Some(mock_stock_line_a()),
                 location: Some(mock_location_1()),
@@ -28,6 +28,8 @@ pub struct UpdateInput {
     pub comment: Option<String>,
     pub snapshot_number_of_packs: Option<f64>,
     pub counted_number_of_packs: Option<f64>,
+    /// Recount of a blind count line with a variance above the stocktake's threshold
+    pub second_counted_number_of_packs: Option<f64>,
     pub batch: Option<String>,
     pub expiry_date: Option<NaiveDate>,
     pub pack_size: Option<f64>,
@@ -97,6 +99,7 @@ impl UpdateInput {
             comment,
             snapshot_number_of_packs,
             counted_number_of_packs,
+            second_counted_number_of_packs,
             batch,
             expiry_date,
             pack_size,
@@ -114,6 +117,7 @@ impl UpdateInput {
             comment,
             snapshot_number_of_packs,
             counted_number_of_packs,
+            second_counted_number_of_packs,
             batch,
             expiry_date,
             pack_size,
@@ -158,6 +162,9 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
         ServiceError::StockLineDoesNotExist => BadUserInput(formatted_error),
         ServiceError::LocationDoesNotExist => BadUserInput(formatted_error),
         ServiceError::StocktakeIsLocked => BadUserInput(formatted_error),
+        ServiceError::CountAlreadySubmitted => BadUserInput(formatted_error),
+        ServiceError::SecondCountNotRequired => BadUserInput(formatted_error),
+        ServiceError::VarianceAlreadyApproved => BadUserInput(formatted_error),
         ServiceError::DatabaseError(_) => InternalError(formatted_error),
         ServiceError::InternalError(err) => InternalError(err),
     };
@@ -278,6 +285,7 @@ mod test {
                     sell_price_per_pack: Some(12.0),
                     note: Some("note".to_string()),
                     inventory_adjustment_reason_id: None,
+                    second_counted_number_of_packs: None,
                 },
                 stock_line: Some(mock_stock_line_a()),
                 location: Some(mock_location_1()),
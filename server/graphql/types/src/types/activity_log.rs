@@ -68,6 +68,8 @@ pub enum ActivityLogNodeType {
     ProgramCreated,
     ProgramUpdated,
     VaccineCourseUpdated,
+    TemperatureBreachStockOnHold,
+    SyncBufferRowPurged,
 }
 
 #[Object]
@@ -191,6 +193,8 @@ impl ActivityLogNodeType {
             from::VaccineCourseUpdated => to::VaccineCourseUpdated,
             from::ProgramCreated => to::ProgramCreated,
             from::ProgramUpdated => to::ProgramUpdated,
+            from::TemperatureBreachStockOnHold => to::TemperatureBreachStockOnHold,
+            from::SyncBufferRowPurged => to::SyncBufferRowPurged,
         }
     }
 
@@ -244,6 +248,8 @@ impl ActivityLogNodeType {
             from::VaccineCourseUpdated => to::VaccineCourseUpdated,
             from::ProgramCreated => to::ProgramCreated,
             from::ProgramUpdated => to::ProgramUpdated,
+            from::TemperatureBreachStockOnHold => to::TemperatureBreachStockOnHold,
+            from::SyncBufferRowPurged => to::SyncBufferRowPurged,
         }
     }
 }
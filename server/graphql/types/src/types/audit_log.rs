@@ -0,0 +1,158 @@
+use async_graphql::{dataloader::DataLoader, *};
+use chrono::{DateTime, Utc};
+use graphql_core::{loader::UserLoader, ContextExt};
+use repository::{AuditLog, AuditLogRecordType, AuditLogRow};
+use serde_json::Value;
+use service::{
+    audit_log::{audit_log_field_changes, AuditLogFieldChange},
+    ListResult,
+};
+
+use super::UserNode;
+
+#[derive(PartialEq, Debug)]
+pub struct AuditLogNode {
+    audit_log: AuditLog,
+}
+
+#[derive(SimpleObject)]
+pub struct AuditLogConnector {
+    total_count: u32,
+    nodes: Vec<AuditLogNode>,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+pub enum AuditLogRecordTypeNode {
+    Invoice,
+    InvoiceLine,
+    StockLine,
+    StocktakeLine,
+    RequisitionLine,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct AuditLogFieldChangeNode {
+    change: AuditLogFieldChange,
+}
+
+#[Object]
+impl AuditLogNode {
+    pub async fn id(&self) -> &str {
+        &self.row().id
+    }
+
+    pub async fn record_type(&self) -> AuditLogRecordTypeNode {
+        AuditLogRecordTypeNode::from_domain(&self.row().record_type)
+    }
+
+    pub async fn record_id(&self) -> &str {
+        &self.row().record_id
+    }
+
+    pub async fn store_id(&self) -> &str {
+        &self.row().store_id
+    }
+
+    pub async fn datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_naive_utc_and_offset(self.row().datetime, Utc)
+    }
+
+    /// Changed fields, sorted by field name
+    pub async fn changes(&self) -> Vec<AuditLogFieldChangeNode> {
+        audit_log_field_changes(self.row())
+            .into_iter()
+            .map(|change| AuditLogFieldChangeNode { change })
+            .collect()
+    }
+
+    pub async fn user(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        let loader = ctx.get_loader::<DataLoader<UserLoader>>();
+
+        let user_id = match &self.row().user_id {
+            Some(user_id) => user_id,
+            None => return Ok(None),
+        };
+
+        let result = loader
+            .load_one(user_id.clone())
+            .await?
+            .map(UserNode::from_domain);
+
+        Ok(result)
+    }
+}
+
+#[Object]
+impl AuditLogFieldChangeNode {
+    /// Field name of the database row, e.g. `sell_price_per_pack`
+    pub async fn field(&self) -> &str {
+        &self.change.field
+    }
+
+    pub async fn from(&self) -> Option<String> {
+        value_to_string(&self.change.from)
+    }
+
+    pub async fn to(&self) -> Option<String> {
+        value_to_string(&self.change.to)
+    }
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(value) => Some(value.clone()),
+        value => Some(value.to_string()),
+    }
+}
+
+impl AuditLogNode {
+    pub fn from_domain(audit_log: AuditLog) -> Self {
+        AuditLogNode { audit_log }
+    }
+
+    pub fn row(&self) -> &AuditLogRow {
+        &self.audit_log.audit_log_row
+    }
+}
+
+impl AuditLogRecordTypeNode {
+    pub fn from_domain(from: &AuditLogRecordType) -> AuditLogRecordTypeNode {
+        use AuditLogRecordType as from;
+        use AuditLogRecordTypeNode as to;
+
+        match from {
+            from::Invoice => to::Invoice,
+            from::InvoiceLine => to::InvoiceLine,
+            from::StockLine => to::StockLine,
+            from::StocktakeLine => to::StocktakeLine,
+            from::RequisitionLine => to::RequisitionLine,
+        }
+    }
+
+    pub fn to_domain(self) -> AuditLogRecordType {
+        use AuditLogRecordType as to;
+        use AuditLogRecordTypeNode as from;
+
+        match self {
+            from::Invoice => to::Invoice,
+            from::InvoiceLine => to::InvoiceLine,
+            from::StockLine => to::StockLine,
+            from::StocktakeLine => to::StocktakeLine,
+            from::RequisitionLine => to::RequisitionLine,
+        }
+    }
+}
+
+impl AuditLogConnector {
+    pub fn from_domain(audit_logs: ListResult<AuditLog>) -> AuditLogConnector {
+        AuditLogConnector {
+            total_count: audit_logs.count,
+            nodes: audit_logs
+                .rows
+                .into_iter()
+                .map(AuditLogNode::from_domain)
+                .collect(),
+        }
+    }
+}
@@ -135,6 +135,16 @@ impl ItemNode {
         self.row().default_pack_size
     }
 
+    /// Temperature above which time counts against the heat stability budget
+    pub async fn heat_stability_threshold(&self) -> Option<f64> {
+        self.row().heat_stability_threshold
+    }
+
+    /// Total time stock of the item can spend above the heat stability threshold
+    pub async fn heat_stability_budget_minutes(&self) -> Option<i32> {
+        self.row().heat_stability_budget_minutes
+    }
+
     pub async fn outer_pack_size(&self) -> i64 {
         self.legacy_i64("outer_pack_size")
     }
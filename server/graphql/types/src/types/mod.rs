@@ -10,6 +10,9 @@ pub use self::item_stats::*;
 pub mod requisition;
 pub use self::requisition::*;
 
+pub mod requisition_approval;
+pub use self::requisition_approval::*;
+
 pub mod requisition_line;
 pub use self::requisition_line::*;
 
@@ -43,6 +46,9 @@ pub use self::user::*;
 pub mod activity_log;
 pub use self::activity_log::*;
 
+pub mod audit_log;
+pub use self::audit_log::*;
+
 pub mod period;
 pub use self::period::*;
 
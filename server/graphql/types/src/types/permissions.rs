@@ -26,10 +26,13 @@ pub enum UserPermission {
     CreateRepack,
     StocktakeQuery,
     StocktakeMutate,
+    StocktakeVarianceApprove,
     InventoryAdjustmentMutate,
     RequisitionQuery,
     RequisitionMutate,
     RequisitionSend,
+    RequisitionApprove,
+    RequisitionApprovalChainMutate,
     OutboundShipmentQuery,
     OutboundShipmentMutate,
     InboundShipmentQuery,
@@ -41,6 +44,7 @@ pub enum UserPermission {
     PrescriptionQuery,
     PrescriptionMutate,
     Report,
+    ReportScheduleMutate,
     LogQuery,
     StockLineMutate,
     ItemMutate,
@@ -108,10 +112,15 @@ impl UserPermission {
             PermissionType::CreateRepack => UserPermission::CreateRepack,
             PermissionType::StocktakeQuery => UserPermission::StocktakeQuery,
             PermissionType::StocktakeMutate => UserPermission::StocktakeMutate,
+            PermissionType::StocktakeVarianceApprove => UserPermission::StocktakeVarianceApprove,
             PermissionType::InventoryAdjustmentMutate => UserPermission::InventoryAdjustmentMutate,
             PermissionType::RequisitionQuery => UserPermission::RequisitionQuery,
             PermissionType::RequisitionMutate => UserPermission::RequisitionMutate,
             PermissionType::RequisitionSend => UserPermission::RequisitionSend,
+            PermissionType::RequisitionApprove => UserPermission::RequisitionApprove,
+            PermissionType::RequisitionApprovalChainMutate => {
+                UserPermission::RequisitionApprovalChainMutate
+            }
             PermissionType::OutboundShipmentQuery => UserPermission::OutboundShipmentQuery,
             PermissionType::OutboundShipmentMutate => UserPermission::OutboundShipmentMutate,
             PermissionType::InboundShipmentQuery => UserPermission::InboundShipmentQuery,
@@ -123,6 +132,7 @@ impl UserPermission {
             PermissionType::PrescriptionQuery => UserPermission::PrescriptionQuery,
             PermissionType::PrescriptionMutate => UserPermission::PrescriptionMutate,
             PermissionType::Report => UserPermission::Report,
+            PermissionType::ReportScheduleMutate => UserPermission::ReportScheduleMutate,
             PermissionType::LogQuery => UserPermission::LogQuery,
             PermissionType::StockLineMutate => UserPermission::StockLineMutate,
             PermissionType::ItemMutate => UserPermission::ItemMutate,
@@ -156,10 +166,15 @@ impl UserPermission {
             UserPermission::CreateRepack => PermissionType::CreateRepack,
             UserPermission::StocktakeQuery => PermissionType::StocktakeQuery,
             UserPermission::StocktakeMutate => PermissionType::StocktakeMutate,
+            UserPermission::StocktakeVarianceApprove => PermissionType::StocktakeVarianceApprove,
             UserPermission::InventoryAdjustmentMutate => PermissionType::InventoryAdjustmentMutate,
             UserPermission::RequisitionQuery => PermissionType::RequisitionQuery,
             UserPermission::RequisitionMutate => PermissionType::RequisitionMutate,
             UserPermission::RequisitionSend => PermissionType::RequisitionSend,
+            UserPermission::RequisitionApprove => PermissionType::RequisitionApprove,
+            UserPermission::RequisitionApprovalChainMutate => {
+                PermissionType::RequisitionApprovalChainMutate
+            }
             UserPermission::OutboundShipmentQuery => PermissionType::OutboundShipmentQuery,
             UserPermission::OutboundShipmentMutate => PermissionType::OutboundShipmentMutate,
             UserPermission::InboundShipmentQuery => PermissionType::InboundShipmentQuery,
@@ -171,6 +186,7 @@ impl UserPermission {
             UserPermission::PrescriptionQuery => PermissionType::PrescriptionQuery,
             UserPermission::PrescriptionMutate => PermissionType::PrescriptionMutate,
             UserPermission::Report => PermissionType::Report,
+            UserPermission::ReportScheduleMutate => PermissionType::ReportScheduleMutate,
             UserPermission::LogQuery => PermissionType::LogQuery,
             UserPermission::StockLineMutate => PermissionType::StockLineMutate,
             UserPermission::ItemMutate => PermissionType::ItemMutate,
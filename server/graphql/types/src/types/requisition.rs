@@ -4,8 +4,8 @@ use chrono::{DateTime, NaiveDate, Utc};
 use graphql_core::{
     loader::{
         InvoiceByRequisitionIdLoader, NameByIdLoader, NameByIdLoaderInput,
-        RequisitionLinesByRequisitionIdLoader, RequisitionLinesRemainingToSupplyLoader,
-        RequisitionsByIdLoader, UserLoader,
+        RequisitionApprovalsByRequisitionIdLoader, RequisitionLinesByRequisitionIdLoader,
+        RequisitionLinesRemainingToSupplyLoader, RequisitionsByIdLoader, UserLoader,
     },
     standard_graphql_error::StandardGraphqlError,
     ContextExt,
@@ -16,7 +16,10 @@ use repository::{
 };
 use service::ListResult;
 
-use super::{InvoiceConnector, NameNode, PeriodNode, RequisitionLineConnector, UserNode};
+use super::{
+    InvoiceConnector, NameNode, PeriodNode, RequisitionApprovalNode, RequisitionLineConnector,
+    UserNode,
+};
 
 #[derive(Enum, Copy, Clone, PartialEq, Eq)]
 pub enum RequisitionNodeType {
@@ -93,6 +96,20 @@ impl RequisitionNode {
             .unwrap_or(RequisitionNodeApprovalStatus::None)
     }
 
+    /// Approval chain decisions, oldest first
+    pub async fn approvals(&self, ctx: &Context<'_>) -> Result<Vec<RequisitionApprovalNode>> {
+        let loader = ctx.get_loader::<DataLoader<RequisitionApprovalsByRequisitionIdLoader>>();
+        let result = loader
+            .load_one(self.row().id.clone())
+            .await?
+            .unwrap_or_default();
+
+        Ok(result
+            .into_iter()
+            .map(RequisitionApprovalNode::from_domain)
+            .collect())
+    }
+
     /// User that last edited requisition, if user is not found in system default unknown user is returned
     /// Null is returned for transfers, where response requisition has not been edited yet
     pub async fn user(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
@@ -236,6 +253,11 @@ impl RequisitionNode {
         &self.row().order_type
     }
 
+    /// Emergency order, placed outside of the regular orders for the period
+    pub async fn is_emergency(&self) -> bool {
+        self.row().is_emergency
+    }
+
     pub async fn period(&self) -> Option<PeriodNode> {
         self.requisition
             .period
@@ -298,7 +320,7 @@ impl RequisitionNodeType {
 }
 
 impl RequisitionNodeApprovalStatus {
-    fn from_domain(status: &ApprovalStatusType) -> Self {
+    pub fn from_domain(status: &ApprovalStatusType) -> Self {
         use ApprovalStatusType::*;
         match status {
             None => Self::None,
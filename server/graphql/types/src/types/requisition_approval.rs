@@ -0,0 +1,91 @@
+use async_graphql::dataloader::DataLoader;
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use graphql_core::{loader::UserLoader, ContextExt};
+use repository::{RequisitionApprovalLevelRow, RequisitionApprovalRow};
+
+use super::{RequisitionNodeApprovalStatus, UserNode};
+
+pub struct RequisitionApprovalNode {
+    approval: RequisitionApprovalRow,
+}
+
+#[Object]
+impl RequisitionApprovalNode {
+    pub async fn id(&self) -> &str {
+        &self.approval.id
+    }
+
+    /// Approval chain level that was approved or denied
+    pub async fn level(&self) -> i32 {
+        self.approval.level
+    }
+
+    pub async fn user_id(&self) -> &str {
+        &self.approval.user_id
+    }
+
+    pub async fn user(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        let loader = ctx.get_loader::<DataLoader<UserLoader>>();
+
+        Ok(loader
+            .load_one(self.approval.user_id.clone())
+            .await?
+            .map(UserNode::from_domain))
+    }
+
+    /// Approved or Denied
+    pub async fn status(&self) -> RequisitionNodeApprovalStatus {
+        RequisitionNodeApprovalStatus::from_domain(&self.approval.status)
+    }
+
+    pub async fn comment(&self) -> &Option<String> {
+        &self.approval.comment
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_naive_utc_and_offset(self.approval.created_datetime, Utc)
+    }
+}
+
+impl RequisitionApprovalNode {
+    pub fn from_domain(approval: RequisitionApprovalRow) -> RequisitionApprovalNode {
+        RequisitionApprovalNode { approval }
+    }
+}
+
+pub struct RequisitionApprovalLevelNode {
+    level: RequisitionApprovalLevelRow,
+}
+
+#[Object]
+impl RequisitionApprovalLevelNode {
+    pub async fn id(&self) -> &str {
+        &self.level.id
+    }
+
+    /// Approval chain of response requisitions of this program, or without a program if null
+    pub async fn program_id(&self) -> &Option<String> {
+        &self.level.program_id
+    }
+
+    /// Levels are approved in ascending order
+    pub async fn level(&self) -> i32 {
+        self.level.level
+    }
+
+    pub async fn name(&self) -> &str {
+        &self.level.name
+    }
+
+    /// Only this user can approve the level, any user with the approve permission if null
+    pub async fn approver_user_id(&self) -> &Option<String> {
+        &self.level.approver_user_id
+    }
+}
+
+impl RequisitionApprovalLevelNode {
+    pub fn from_domain(level: RequisitionApprovalLevelRow) -> RequisitionApprovalLevelNode {
+        RequisitionApprovalLevelNode { level }
+    }
+}
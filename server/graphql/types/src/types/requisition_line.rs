@@ -84,6 +84,32 @@ impl RequisitionLineNode {
         &self.row().approval_comment
     }
 
+    /// R&R stock on hand at the start of the requisition period, only set for program requisitions
+    pub async fn opening_balance(&self) -> &Option<f64> {
+        &self.row().opening_balance
+    }
+
+    pub async fn quantity_received(&self) -> &Option<f64> {
+        &self.row().quantity_received
+    }
+
+    pub async fn quantity_dispensed(&self) -> &Option<f64> {
+        &self.row().quantity_dispensed
+    }
+
+    /// Net losses (negative) and adjustments (positive)
+    pub async fn losses_and_adjustments(&self) -> &Option<f64> {
+        &self.row().losses_and_adjustments
+    }
+
+    pub async fn closing_balance(&self) -> &Option<f64> {
+        &self.row().closing_balance
+    }
+
+    pub async fn days_out_of_stock(&self) -> &Option<i32> {
+        &self.row().days_out_of_stock
+    }
+
     /// OutboundShipment lines linked to requisitions line
     pub async fn outbound_shipment_lines(&self, ctx: &Context<'_>) -> Result<InvoiceLineConnector> {
         // Outbound shipments link to response requisition, so for request requisition
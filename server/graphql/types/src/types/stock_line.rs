@@ -3,7 +3,7 @@ use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
 use chrono::NaiveDate;
 use graphql_core::{
-    loader::{ItemLoader, LocationByIdLoader},
+    loader::{ItemLoader, LocationByIdLoader, StockLineHeatExposureLoader},
     simple_generic_errors::NodeError,
     standard_graphql_error::StandardGraphqlError,
     ContextExt,
@@ -99,6 +99,24 @@ impl StockLineNode {
     pub async fn barcode(&self) -> Option<&str> {
         self.stock_line.barcode()
     }
+
+    /// Time the stock line spent above the heat stability threshold of the item, null if the item
+    /// has no heat stability budget
+    pub async fn heat_exposure_minutes(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        let loader = ctx.get_loader::<DataLoader<StockLineHeatExposureLoader>>();
+        let result = loader.load_one(self.row().id.clone()).await?;
+
+        Ok(result.map(|heat_exposure| heat_exposure.exposure_minutes))
+    }
+
+    /// Heat stability budget of the item less the heat exposure of the stock line, null if the
+    /// item has no heat stability budget
+    pub async fn remaining_heat_stability_minutes(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        let loader = ctx.get_loader::<DataLoader<StockLineHeatExposureLoader>>();
+        let result = loader.load_one(self.row().id.clone()).await?;
+
+        Ok(result.map(|heat_exposure| heat_exposure.remaining_budget_minutes))
+    }
 }
 
 #[derive(Union)]
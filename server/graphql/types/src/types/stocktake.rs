@@ -77,6 +77,31 @@ impl StocktakeNode {
             .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
     }
 
+    /// Snapshot quantities are hidden until the count is submitted
+    pub async fn is_blind_count(&self) -> bool {
+        self.stocktake.is_blind_count
+    }
+
+    pub async fn variance_threshold_percentage(&self) -> Option<f64> {
+        self.stocktake.variance_threshold_percentage
+    }
+
+    pub async fn count_submitted_datetime(&self) -> Option<DateTime<Utc>> {
+        self.stocktake
+            .count_submitted_datetime
+            .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
+    }
+
+    pub async fn variance_approved_datetime(&self) -> Option<DateTime<Utc>> {
+        self.stocktake
+            .variance_approved_datetime
+            .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
+    }
+
+    pub async fn variance_approved_by_user_id(&self) -> &Option<String> {
+        &self.stocktake.variance_approved_by_user_id
+    }
+
     pub async fn inventory_addition_id(&self) -> &Option<String> {
         &self.stocktake.inventory_addition_id
     }
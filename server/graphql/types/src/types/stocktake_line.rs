@@ -1,12 +1,13 @@
 use async_graphql::*;
 use chrono::NaiveDate;
 use dataloader::DataLoader;
-use repository::StocktakeLine;
-use service::usize_to_u32;
+use repository::{StocktakeLine, StocktakeRow};
+use service::{stocktake::requires_second_count, usize_to_u32};
 
 use graphql_core::{
     loader::{
         InventoryAdjustmentReasonByIdLoader, ItemLoader, LocationByIdLoader, StockLineByIdLoader,
+        StocktakeByIdLoader,
     },
     standard_graphql_error::StandardGraphqlError,
     ContextExt,
@@ -28,7 +29,11 @@ impl StocktakeLineNode {
         &self.line.line.stocktake_id
     }
 
+    /// Null while the count of a blind count stocktake is not submitted
     pub async fn stock_line(&self, ctx: &Context<'_>) -> Result<Option<StockLineNode>> {
+        if self.is_snapshot_hidden(ctx).await? {
+            return Ok(None);
+        }
         if let Some(ref stock_line) = self.line.stock_line {
             let loader = ctx.get_loader::<DataLoader<StockLineByIdLoader>>();
             let stock_line = loader.load_one(stock_line.id.clone()).await?.ok_or(
@@ -57,14 +62,28 @@ impl StocktakeLineNode {
         self.line.line.comment.clone()
     }
 
-    pub async fn snapshot_number_of_packs(&self) -> f64 {
-        self.line.line.snapshot_number_of_packs
+    /// Null while the count of a blind count stocktake is not submitted
+    pub async fn snapshot_number_of_packs(&self, ctx: &Context<'_>) -> Result<Option<f64>> {
+        if self.is_snapshot_hidden(ctx).await? {
+            return Ok(None);
+        }
+        Ok(Some(self.line.line.snapshot_number_of_packs))
     }
 
     pub async fn counted_number_of_packs(&self) -> Option<f64> {
         self.line.line.counted_number_of_packs
     }
 
+    pub async fn second_counted_number_of_packs(&self) -> Option<f64> {
+        self.line.line.second_counted_number_of_packs
+    }
+
+    /// True when the variance of a submitted blind count exceeds the stocktake's threshold
+    pub async fn requires_second_count(&self, ctx: &Context<'_>) -> Result<bool> {
+        let stocktake = self.stocktake(ctx).await?;
+        Ok(requires_second_count(&stocktake, &self.line.line))
+    }
+
     pub async fn item_id(&self) -> &str {
         &self.line.item.id
     }
@@ -161,4 +180,21 @@ impl StocktakeLineNode {
     pub fn from_domain(line: StocktakeLine) -> StocktakeLineNode {
         StocktakeLineNode { line }
     }
+
+    async fn stocktake(&self, ctx: &Context<'_>) -> Result<StocktakeRow> {
+        let loader = ctx.get_loader::<DataLoader<StocktakeByIdLoader>>();
+        let stocktake_id = &self.line.line.stocktake_id;
+        loader.load_one(stocktake_id.clone()).await?.ok_or(
+            StandardGraphqlError::InternalError(format!(
+                "Cannot find stocktake {} for stocktake line id {}",
+                stocktake_id, self.line.line.id
+            ))
+            .extend(),
+        )
+    }
+
+    async fn is_snapshot_hidden(&self, ctx: &Context<'_>) -> Result<bool> {
+        let stocktake = self.stocktake(ctx).await?;
+        Ok(stocktake.is_blind_count && stocktake.count_submitted_datetime.is_none())
+    }
 }
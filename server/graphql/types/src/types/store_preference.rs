@@ -1,5 +1,48 @@
 use async_graphql::*;
-use repository::StorePreferenceRow;
+use repository::{AllocationStrategy, ConsumptionForecastMethod, StorePreferenceRow};
+use serde::Serialize;
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")] // only needed to be comparable in tests
+pub enum ConsumptionForecastMethodNode {
+    SimpleAverage,
+    StockOutAdjusted,
+    ExponentialSmoothing,
+    SeasonalMovingAverage,
+}
+
+impl ConsumptionForecastMethodNode {
+    pub fn from_domain(method: &ConsumptionForecastMethod) -> Self {
+        use ConsumptionForecastMethodNode::*;
+        match method {
+            ConsumptionForecastMethod::SimpleAverage => SimpleAverage,
+            ConsumptionForecastMethod::StockOutAdjusted => StockOutAdjusted,
+            ConsumptionForecastMethod::ExponentialSmoothing => ExponentialSmoothing,
+            ConsumptionForecastMethod::SeasonalMovingAverage => SeasonalMovingAverage,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")] // only needed to be comparable in tests
+pub enum AllocationStrategyNode {
+    Fefo,
+    Fifo,
+    Location,
+    PackVariant,
+}
+
+impl AllocationStrategyNode {
+    pub fn from_domain(strategy: &AllocationStrategy) -> Self {
+        use AllocationStrategyNode::*;
+        match strategy {
+            AllocationStrategy::Fefo => Fefo,
+            AllocationStrategy::Fifo => Fifo,
+            AllocationStrategy::Location => Location,
+            AllocationStrategy::PackVariant => PackVariant,
+        }
+    }
+}
 
 // #[derive(Clone)]
 #[derive(PartialEq, Debug)]
@@ -38,6 +81,16 @@ impl StorePreferenceNode {
     pub async fn issue_in_foreign_currency(&self) -> &bool {
         &self.store_preference.issue_in_foreign_currency
     }
+
+    pub async fn consumption_forecast_method(&self) -> ConsumptionForecastMethodNode {
+        ConsumptionForecastMethodNode::from_domain(
+            &self.store_preference.consumption_forecast_method,
+        )
+    }
+
+    pub async fn allocation_strategy(&self) -> AllocationStrategyNode {
+        AllocationStrategyNode::from_domain(&self.store_preference.allocation_strategy)
+    }
 }
 
 impl StorePreferenceNode {
@@ -1,7 +1,8 @@
 use anyhow::Result;
 use service::report::definition::{
-    DefaultQuery, GraphQlQuery, ReportDefinition, ReportDefinitionEntry, ReportDefinitionIndex,
-    ReportOutputType, SQLQuery, TeraTemplate,
+    DefaultQuery, GraphQlQuery, PageMargins, PageOrientation, PageSettings, PageSize,
+    ReportDefinition, ReportDefinitionEntry, ReportDefinitionIndex, ReportOutputType, SQLQuery,
+    TeraTemplate,
 };
 use std::{
     collections::HashMap,
@@ -43,6 +44,60 @@ fn parse_default_query(input: &str) -> anyhow::Result<DefaultQuery> {
     Ok(query)
 }
 
+fn parse_page_size(input: &str) -> anyhow::Result<PageSize> {
+    let size = match input.to_lowercase().as_str() {
+        "a3" => PageSize::A3,
+        "a4" => PageSize::A4,
+        "a5" => PageSize::A5,
+        "letter" => PageSize::Letter,
+        "legal" => PageSize::Legal,
+        custom => {
+            let dimensions = custom
+                .split_once('x')
+                .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)));
+            let Some((width_mm, height_mm)) = dimensions else {
+                return Err(anyhow::Error::msg(format!("Invalid page size: {}", input)));
+            };
+            PageSize::Custom {
+                width_mm,
+                height_mm,
+            }
+        }
+    };
+    Ok(size)
+}
+
+/// Page settings are only set if any page option is given, otherwise the css page size and
+/// margins of the template are used
+fn page_settings(args: &BuildArgs) -> anyhow::Result<Option<PageSettings>> {
+    if args.page_size.is_none() && !args.landscape && args.margin.is_none() {
+        return Ok(None);
+    }
+    let size = match &args.page_size {
+        Some(page_size) => parse_page_size(page_size)?,
+        None => PageSize::default(),
+    };
+    let orientation = if args.landscape {
+        PageOrientation::Landscape
+    } else {
+        PageOrientation::Portrait
+    };
+    let margins = match args.margin {
+        Some(margin) => PageMargins {
+            top: margin,
+            bottom: margin,
+            left: margin,
+            right: margin,
+        },
+        None => PageMargins::default(),
+    };
+    Ok(Some(PageSettings {
+        size,
+        orientation,
+        margins,
+    }))
+}
+
 /// Returns query name and SQLQuery
 fn extract_sql_entry(
     args: &BuildArgs,
@@ -99,6 +154,7 @@ fn make_report(args: &BuildArgs, mut files: HashMap<String, PathBuf>) -> Result<
         header: None,
         footer: None,
         query: vec![],
+        page: page_settings(args)?,
     };
     let mut entries: HashMap<String, ReportDefinitionEntry> = HashMap::new();
 
@@ -111,7 +167,11 @@ fn make_report(args: &BuildArgs, mut files: HashMap<String, PathBuf>) -> Result<
     entries.insert(
         args.template.clone(),
         ReportDefinitionEntry::TeraTemplate(TeraTemplate {
-            output: ReportOutputType::Html,
+            output: if args.native_pdf {
+                ReportOutputType::NativePdf
+            } else {
+                ReportOutputType::Html
+            },
             template: data,
         }),
     );
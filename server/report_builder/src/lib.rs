@@ -49,6 +49,18 @@ pub struct BuildArgs {
     /// GraphQL query since otherwise data from the GraphQL query might get overwritten.
     #[clap(long, value_parser, value_delimiter = ' ')]
     pub query_sql: Option<Vec<String>>,
+
+    /// Print the report to PDF using the built-in renderer instead of headless Chrome
+    #[clap(long)]
+    pub native_pdf: bool,
+    /// Page size, one of: "A3" | "A4" | "A5" | "Letter" | "Legal" or "{width}x{height}" in mm
+    #[clap(long)]
+    pub page_size: Option<String>,
+    #[clap(long)]
+    pub landscape: bool,
+    /// Page margin in mm (all sides)
+    #[clap(long)]
+    pub margin: Option<f64>,
 }
 
 #[derive(clap::Args)]
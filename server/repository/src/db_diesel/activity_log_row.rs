@@ -73,6 +73,8 @@ pub enum ActivityLogType {
     ProgramCreated,
     ProgramUpdated,
     VaccineCourseUpdated,
+    TemperatureBreachStockOnHold,
+    SyncBufferRowPurged,
 }
 
 #[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
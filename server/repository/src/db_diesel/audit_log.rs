@@ -0,0 +1,150 @@
+use super::{
+    audit_log_row::{audit_log, audit_log::dsl as audit_log_dsl},
+    AuditLogRow, DBType, StorageConnection,
+};
+use diesel::prelude::*;
+
+use crate::{
+    diesel_macros::{apply_date_time_filter, apply_equal_filter, apply_sort},
+    repository_error::RepositoryError,
+    AuditLogRecordType, DatetimeFilter,
+};
+
+use crate::{EqualFilter, Pagination, Sort};
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct AuditLog {
+    pub audit_log_row: AuditLogRow,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct AuditLogFilter {
+    pub id: Option<EqualFilter<String>>,
+    pub record_type: Option<EqualFilter<AuditLogRecordType>>,
+    pub record_id: Option<EqualFilter<String>>,
+    pub user_id: Option<EqualFilter<String>>,
+    pub store_id: Option<EqualFilter<String>>,
+    pub datetime: Option<DatetimeFilter>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum AuditLogSortField {
+    Datetime,
+}
+
+pub type AuditLogSort = Sort<AuditLogSortField>;
+
+pub struct AuditLogRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> AuditLogRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        AuditLogRepository { connection }
+    }
+
+    pub fn count(&self, filter: Option<AuditLogFilter>) -> Result<i64, RepositoryError> {
+        let query = create_filtered_query(filter);
+        Ok(query
+            .count()
+            .get_result(self.connection.lock().connection())?)
+    }
+
+    pub fn query_by_filter(
+        &self,
+        filter: AuditLogFilter,
+    ) -> Result<Vec<AuditLog>, RepositoryError> {
+        self.query(Pagination::all(), Some(filter), None)
+    }
+
+    pub fn query(
+        &self,
+        pagination: Pagination,
+        filter: Option<AuditLogFilter>,
+        sort: Option<AuditLogSort>,
+    ) -> Result<Vec<AuditLog>, RepositoryError> {
+        let mut query = create_filtered_query(filter);
+        if let Some(sort) = sort {
+            match sort.key {
+                AuditLogSortField::Datetime => {
+                    apply_sort!(query, sort, audit_log_dsl::datetime)
+                }
+            }
+        } else {
+            query = query.order(audit_log_dsl::datetime.asc())
+        }
+
+        let result = query
+            .offset(pagination.offset as i64)
+            .limit(pagination.limit as i64)
+            .load::<AuditLogRow>(self.connection.lock().connection())?;
+
+        Ok(result.into_iter().map(to_domain).collect())
+    }
+}
+
+type BoxedAuditLogQuery = audit_log::BoxedQuery<'static, DBType>;
+
+fn create_filtered_query(filter: Option<AuditLogFilter>) -> BoxedAuditLogQuery {
+    let mut query = audit_log::table.into_boxed();
+
+    if let Some(filter) = filter {
+        apply_equal_filter!(query, filter.id, audit_log_dsl::id);
+        apply_equal_filter!(query, filter.record_type, audit_log_dsl::record_type);
+        apply_equal_filter!(query, filter.record_id, audit_log_dsl::record_id);
+        apply_equal_filter!(query, filter.user_id, audit_log_dsl::user_id);
+        apply_equal_filter!(query, filter.store_id, audit_log_dsl::store_id);
+        apply_date_time_filter!(query, filter.datetime, audit_log_dsl::datetime);
+    }
+
+    query
+}
+
+fn to_domain(audit_log_row: AuditLogRow) -> AuditLog {
+    AuditLog { audit_log_row }
+}
+
+impl AuditLogFilter {
+    pub fn new() -> AuditLogFilter {
+        Self::default()
+    }
+
+    pub fn id(mut self, filter: EqualFilter<String>) -> Self {
+        self.id = Some(filter);
+        self
+    }
+
+    pub fn record_type(mut self, filter: EqualFilter<AuditLogRecordType>) -> Self {
+        self.record_type = Some(filter);
+        self
+    }
+
+    pub fn record_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.record_id = Some(filter);
+        self
+    }
+
+    pub fn user_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.user_id = Some(filter);
+        self
+    }
+
+    pub fn store_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.store_id = Some(filter);
+        self
+    }
+
+    pub fn datetime(mut self, filter: DatetimeFilter) -> Self {
+        self.datetime = Some(filter);
+        self
+    }
+}
+
+impl AuditLogRecordType {
+    pub fn equal_to(&self) -> EqualFilter<Self> {
+        EqualFilter {
+            equal_to: Some(self.clone()),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use super::{audit_log_row::audit_log::dsl as audit_log_dsl, StorageConnection};
+
+use crate::{
+    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RepositoryError, RowActionType,
+    Upsert,
+};
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use util::uuid::uuid;
+
+table! {
+    audit_log (id) {
+        id -> Text,
+        record_type -> crate::db_diesel::audit_log_row::AuditLogRecordTypeMapping,
+        record_id -> Text,
+        user_id -> Nullable<Text>,
+        store_id -> Text,
+        datetime -> Timestamp,
+        changes -> Text,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum AuditLogRecordType {
+    #[default]
+    Invoice,
+    InvoiceLine,
+    StockLine,
+    StocktakeLine,
+    RequisitionLine,
+}
+
+/// Field level changes of a record, `changes` is a JSON object of changed fields, i.e.
+/// `{"field": {"from": <value>, "to": <value>}}`
+#[derive(
+    Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default, Serialize, Deserialize,
+)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(table_name = audit_log)]
+pub struct AuditLogRow {
+    pub id: String,
+    pub record_type: AuditLogRecordType,
+    pub record_id: String,
+    pub user_id: Option<String>,
+    pub store_id: String,
+    pub datetime: NaiveDateTime,
+    pub changes: String,
+}
+
+/// Store and user that changes made through a connection are attributed to, see
+/// `StorageConnection::set_audit_context`
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditContext {
+    pub store_id: String,
+    pub user_id: Option<String>,
+}
+
+pub struct AuditLogRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> AuditLogRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        AuditLogRowRepository { connection }
+    }
+
+    fn _upsert_one(&self, row: &AuditLogRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(audit_log_dsl::audit_log)
+            .values(row)
+            .on_conflict(audit_log_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    /// Returns the changelog cursor
+    pub fn upsert_one(&self, row: &AuditLogRow) -> Result<i64, RepositoryError> {
+        self._upsert_one(row)?;
+
+        ChangelogRepository::new(self.connection).insert(&ChangeLogInsertRow {
+            table_name: ChangelogTableName::AuditLog,
+            record_id: row.id.clone(),
+            row_action: RowActionType::Upsert,
+            store_id: Some(row.store_id.clone()),
+            ..Default::default()
+        })
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<AuditLogRow>, RepositoryError> {
+        let result = audit_log_dsl::audit_log
+            .filter(audit_log_dsl::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Loads the state of a record before it's changed, only done if the connection is audited
+    pub fn find_before<T>(
+        &self,
+        find: impl FnOnce() -> Result<Option<T>, RepositoryError>,
+    ) -> Result<Option<T>, RepositoryError> {
+        match self.connection.audit_context() {
+            Some(_) => find(),
+            None => Ok(None),
+        }
+    }
+
+    /// Records the fields that differ between `before` and `after` (`None` if the record didn't
+    /// exist or was deleted). Nothing is recorded if the connection isn't audited or if the record
+    /// is unchanged.
+    pub fn record_changes<T: Serialize>(
+        &self,
+        record_type: AuditLogRecordType,
+        record_id: &str,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> Result<(), RepositoryError> {
+        let Some(audit_context) = self.connection.audit_context() else {
+            return Ok(());
+        };
+        let changes = field_changes(before, after);
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let row = AuditLogRow {
+            id: uuid(),
+            record_type,
+            record_id: record_id.to_string(),
+            user_id: audit_context.user_id.clone(),
+            store_id: audit_context.store_id.clone(),
+            datetime: Utc::now().naive_utc(),
+            changes: Value::Object(changes).to_string(),
+        };
+        self.upsert_one(&row)?;
+        Ok(())
+    }
+}
+
+/// Changed fields keyed by field name, i.e. `{"field": {"from": <value>, "to": <value>}}`
+pub fn field_changes<T: Serialize>(before: Option<&T>, after: Option<&T>) -> Map<String, Value> {
+    let fields = |record: Option<&T>| match record.map(serde_json::to_value) {
+        Some(Ok(Value::Object(fields))) => fields,
+        _ => Map::new(),
+    };
+    let before = fields(before);
+    let mut after = fields(after);
+    // Fields of deleted records change to null
+    for field in before.keys() {
+        after.entry(field.clone()).or_insert(Value::Null);
+    }
+
+    after
+        .into_iter()
+        .filter_map(|(field, to)| {
+            let from = before.get(&field).cloned().unwrap_or_default();
+            (from != to).then(|| (field, json!({ "from": from, "to": to })))
+        })
+        .collect()
+}
+
+impl Upsert for AuditLogRow {
+    fn upsert_sync(&self, con: &StorageConnection) -> Result<(), RepositoryError> {
+        let _change_log_id = AuditLogRowRepository::new(con).upsert_one(self)?;
+        Ok(())
+    }
+
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = AuditLogRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            AuditLogRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
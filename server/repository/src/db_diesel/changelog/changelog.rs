@@ -98,6 +98,10 @@ pub enum ChangelogTableName {
     Property,
     NameProperty,
     NameOmsFields,
+    AuditLog,
+    ItemOmsFields,
+    RequisitionApprovalLevel,
+    RequisitionApproval,
 }
 
 pub(crate) enum ChangeLogSyncStyle {
@@ -151,6 +155,10 @@ impl ChangelogTableName {
             ChangelogTableName::Property => ChangeLogSyncStyle::Central,
             ChangelogTableName::NameProperty => ChangeLogSyncStyle::Central,
             ChangelogTableName::NameOmsFields => ChangeLogSyncStyle::Central,
+            ChangelogTableName::AuditLog => ChangeLogSyncStyle::Remote,
+            ChangelogTableName::ItemOmsFields => ChangeLogSyncStyle::Central,
+            ChangelogTableName::RequisitionApprovalLevel => ChangeLogSyncStyle::Remote,
+            ChangelogTableName::RequisitionApproval => ChangeLogSyncStyle::Remote,
         }
     }
 }
@@ -0,0 +1,101 @@
+use super::{
+    changelog_consumer_dead_letter_row::{
+        changelog_consumer_dead_letter, changelog_consumer_dead_letter::dsl as dead_letter_dsl,
+    },
+    DBType, StorageConnection,
+};
+use diesel::prelude::*;
+
+use crate::{
+    diesel_macros::apply_equal_filter, repository_error::RepositoryError,
+    ChangelogConsumerDeadLetterRow,
+};
+
+use crate::{EqualFilter, Pagination};
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ChangelogConsumerDeadLetterFilter {
+    pub id: Option<EqualFilter<String>>,
+    pub consumer_id: Option<EqualFilter<String>>,
+    pub record_id: Option<EqualFilter<String>>,
+}
+
+pub struct ChangelogConsumerDeadLetterRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ChangelogConsumerDeadLetterRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ChangelogConsumerDeadLetterRepository { connection }
+    }
+
+    pub fn count(
+        &self,
+        filter: Option<ChangelogConsumerDeadLetterFilter>,
+    ) -> Result<i64, RepositoryError> {
+        let query = create_filtered_query(filter);
+        Ok(query
+            .count()
+            .get_result(self.connection.lock().connection())?)
+    }
+
+    pub fn query_by_filter(
+        &self,
+        filter: ChangelogConsumerDeadLetterFilter,
+    ) -> Result<Vec<ChangelogConsumerDeadLetterRow>, RepositoryError> {
+        self.query(Pagination::all(), Some(filter))
+    }
+
+    /// Most recent dead letters first
+    pub fn query(
+        &self,
+        pagination: Pagination,
+        filter: Option<ChangelogConsumerDeadLetterFilter>,
+    ) -> Result<Vec<ChangelogConsumerDeadLetterRow>, RepositoryError> {
+        let result = create_filtered_query(filter)
+            .order(dead_letter_dsl::changelog_cursor.desc())
+            .offset(pagination.offset as i64)
+            .limit(pagination.limit as i64)
+            .load::<ChangelogConsumerDeadLetterRow>(self.connection.lock().connection())?;
+
+        Ok(result)
+    }
+}
+
+type BoxedChangelogConsumerDeadLetterQuery =
+    changelog_consumer_dead_letter::BoxedQuery<'static, DBType>;
+
+fn create_filtered_query(
+    filter: Option<ChangelogConsumerDeadLetterFilter>,
+) -> BoxedChangelogConsumerDeadLetterQuery {
+    let mut query = changelog_consumer_dead_letter::table.into_boxed();
+
+    if let Some(filter) = filter {
+        apply_equal_filter!(query, filter.id, dead_letter_dsl::id);
+        apply_equal_filter!(query, filter.consumer_id, dead_letter_dsl::consumer_id);
+        apply_equal_filter!(query, filter.record_id, dead_letter_dsl::record_id);
+    }
+
+    query
+}
+
+impl ChangelogConsumerDeadLetterFilter {
+    pub fn new() -> ChangelogConsumerDeadLetterFilter {
+        Default::default()
+    }
+
+    pub fn id(mut self, filter: EqualFilter<String>) -> Self {
+        self.id = Some(filter);
+        self
+    }
+
+    pub fn consumer_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.consumer_id = Some(filter);
+        self
+    }
+
+    pub fn record_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.record_id = Some(filter);
+        self
+    }
+}
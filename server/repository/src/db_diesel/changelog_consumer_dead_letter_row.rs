@@ -0,0 +1,75 @@
+use super::{
+    changelog_consumer_dead_letter_row::changelog_consumer_dead_letter::dsl as dead_letter_dsl,
+    ChangelogTableName, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+table! {
+    changelog_consumer_dead_letter (id) {
+        id -> Text,
+        consumer_id -> Text,
+        changelog_cursor -> BigInt,
+        table_name -> crate::db_diesel::changelog::ChangelogTableNameMapping,
+        record_id -> Text,
+        error -> Text,
+        attempts -> Integer,
+        created_datetime -> Timestamp,
+    }
+}
+
+/// Changelog record that a consumer failed to process and has given up on
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = changelog_consumer_dead_letter)]
+#[diesel(treat_none_as_null = true)]
+pub struct ChangelogConsumerDeadLetterRow {
+    pub id: String,
+    pub consumer_id: String,
+    pub changelog_cursor: i64,
+    pub table_name: ChangelogTableName,
+    pub record_id: String,
+    pub error: String,
+    pub attempts: i32,
+    pub created_datetime: NaiveDateTime,
+}
+
+pub struct ChangelogConsumerDeadLetterRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ChangelogConsumerDeadLetterRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ChangelogConsumerDeadLetterRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &ChangelogConsumerDeadLetterRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(dead_letter_dsl::changelog_consumer_dead_letter)
+            .values(row)
+            .on_conflict(dead_letter_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        id: &str,
+    ) -> Result<Option<ChangelogConsumerDeadLetterRow>, RepositoryError> {
+        let result = dead_letter_dsl::changelog_consumer_dead_letter
+            .filter(dead_letter_dsl::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), RepositoryError> {
+        diesel::delete(dead_letter_dsl::changelog_consumer_dead_letter)
+            .filter(dead_letter_dsl::id.eq(id))
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use super::{
+    changelog_consumer_row::changelog_consumer::dsl as changelog_consumer_dsl, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+table! {
+    changelog_consumer (id) {
+        id -> Text,
+        cursor -> BigInt,
+        retry_cursor -> Nullable<BigInt>,
+        retry_count -> Integer,
+        next_retry_datetime -> Nullable<Timestamp>,
+        last_error -> Nullable<Text>,
+        last_processed_datetime -> Nullable<Timestamp>,
+    }
+}
+
+/// Processing state of a changelog consumer, id is the name of the consumer
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = changelog_consumer)]
+#[diesel(treat_none_as_null = true)]
+pub struct ChangelogConsumerRow {
+    pub id: String,
+    /// Next changelog cursor to be processed
+    pub cursor: i64,
+    /// Changelog cursor of the record that is currently failing and waiting to be retried
+    pub retry_cursor: Option<i64>,
+    /// Number of failed attempts for the record at retry_cursor
+    pub retry_count: i32,
+    pub next_retry_datetime: Option<NaiveDateTime>,
+    pub last_error: Option<String>,
+    pub last_processed_datetime: Option<NaiveDateTime>,
+}
+
+pub struct ChangelogConsumerRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ChangelogConsumerRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ChangelogConsumerRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &ChangelogConsumerRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(changelog_consumer_dsl::changelog_consumer)
+            .values(row)
+            .on_conflict(changelog_consumer_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        id: &str,
+    ) -> Result<Option<ChangelogConsumerRow>, RepositoryError> {
+        let result = changelog_consumer_dsl::changelog_consumer
+            .filter(changelog_consumer_dsl::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_all(&self) -> Result<Vec<ChangelogConsumerRow>, RepositoryError> {
+        let result = changelog_consumer_dsl::changelog_consumer
+            .order(changelog_consumer_dsl::id.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
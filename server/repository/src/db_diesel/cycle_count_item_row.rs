@@ -0,0 +1,87 @@
+use super::{
+    cycle_count_item_row::cycle_count_item::dsl as cycle_count_item_dsl, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+
+table! {
+    cycle_count_item (id) {
+        id -> Text,
+        store_id -> Text,
+        item_link_id -> Text,
+        abc_class -> Nullable<crate::db_diesel::cycle_count_item_row::CycleCountClassMapping>,
+        consumption_value -> Double,
+        last_counted_date -> Nullable<Date>,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum CycleCountClass {
+    A,
+    B,
+    C,
+}
+
+/// Cycle count status of an item in a store
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = cycle_count_item)]
+#[diesel(treat_none_as_null = true)]
+pub struct CycleCountItemRow {
+    pub id: String,
+    pub store_id: String,
+    pub item_link_id: String,
+    /// Not set until the item is classified
+    pub abc_class: Option<CycleCountClass>,
+    /// Cost of a year's consumption at the last classification
+    pub consumption_value: f64,
+    /// Date the item was last counted in a finalised stocktake
+    pub last_counted_date: Option<NaiveDate>,
+}
+
+pub struct CycleCountItemRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> CycleCountItemRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        CycleCountItemRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &CycleCountItemRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(cycle_count_item_dsl::cycle_count_item)
+            .values(row)
+            .on_conflict(cycle_count_item_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_store_and_item(
+        &self,
+        store_id: &str,
+        item_link_id: &str,
+    ) -> Result<Option<CycleCountItemRow>, RepositoryError> {
+        let result = cycle_count_item_dsl::cycle_count_item
+            .filter(cycle_count_item_dsl::store_id.eq(store_id))
+            .filter(cycle_count_item_dsl::item_link_id.eq(item_link_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_store_id(
+        &self,
+        store_id: &str,
+    ) -> Result<Vec<CycleCountItemRow>, RepositoryError> {
+        let result = cycle_count_item_dsl::cycle_count_item
+            .filter(cycle_count_item_dsl::store_id.eq(store_id))
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,92 @@
+use super::{
+    cycle_count_plan_row::cycle_count_plan::dsl as cycle_count_plan_dsl, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDate;
+use diesel::prelude::*;
+
+table! {
+    cycle_count_plan (id) {
+        id -> Text,
+        store_id -> Text,
+        class_a_percentage -> Double,
+        class_b_percentage -> Double,
+        class_a_frequency_days -> Integer,
+        class_b_frequency_days -> Integer,
+        class_c_frequency_days -> Integer,
+        max_items_per_count -> Nullable<Integer>,
+        last_generated_date -> Nullable<Date>,
+        is_active -> Bool,
+    }
+}
+
+/// Cycle count plan of a store, items are classified by consumption value (ABC classification)
+/// and each class is counted at its own frequency
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = cycle_count_plan)]
+#[diesel(treat_none_as_null = true)]
+pub struct CycleCountPlanRow {
+    pub id: String,
+    pub store_id: String,
+    /// Items making up this (cumulative) percentage of the store's consumption value are class A
+    pub class_a_percentage: f64,
+    /// Items making up this (cumulative) percentage of the store's consumption value, that are
+    /// not class A, are class B. Remaining items are class C
+    pub class_b_percentage: f64,
+    pub class_a_frequency_days: i32,
+    pub class_b_frequency_days: i32,
+    pub class_c_frequency_days: i32,
+    /// Maximum number of items in a generated stocktake, all due items if not set
+    pub max_items_per_count: Option<i32>,
+    /// Date a cycle count stocktake was last generated for
+    pub last_generated_date: Option<NaiveDate>,
+    pub is_active: bool,
+}
+
+pub struct CycleCountPlanRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> CycleCountPlanRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        CycleCountPlanRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &CycleCountPlanRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(cycle_count_plan_dsl::cycle_count_plan)
+            .values(row)
+            .on_conflict(cycle_count_plan_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<CycleCountPlanRow>, RepositoryError> {
+        let result = cycle_count_plan_dsl::cycle_count_plan
+            .filter(cycle_count_plan_dsl::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_one_by_store_id(
+        &self,
+        store_id: &str,
+    ) -> Result<Option<CycleCountPlanRow>, RepositoryError> {
+        let result = cycle_count_plan_dsl::cycle_count_plan
+            .filter(cycle_count_plan_dsl::store_id.eq(store_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_active(&self) -> Result<Vec<CycleCountPlanRow>, RepositoryError> {
+        let result = cycle_count_plan_dsl::cycle_count_plan
+            .filter(cycle_count_plan_dsl::is_active.eq(true))
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
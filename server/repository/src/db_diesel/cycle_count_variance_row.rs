@@ -0,0 +1,74 @@
+use super::{
+    cycle_count_variance_row::cycle_count_variance::dsl as cycle_count_variance_dsl,
+    StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDate;
+use diesel::prelude::*;
+
+table! {
+    cycle_count_variance (id) {
+        id -> Text,
+        store_id -> Text,
+        item_link_id -> Text,
+        stocktake_id -> Text,
+        counted_date -> Date,
+        snapshot_quantity -> Double,
+        counted_quantity -> Double,
+    }
+}
+
+/// Count of an item in a finalised stocktake, used for count accuracy history
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = cycle_count_variance)]
+pub struct CycleCountVarianceRow {
+    pub id: String,
+    pub store_id: String,
+    pub item_link_id: String,
+    pub stocktake_id: String,
+    pub counted_date: NaiveDate,
+    /// Expected quantity (in units) when the stocktake was created
+    pub snapshot_quantity: f64,
+    /// Counted quantity (in units)
+    pub counted_quantity: f64,
+}
+
+pub struct CycleCountVarianceRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> CycleCountVarianceRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        CycleCountVarianceRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &CycleCountVarianceRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(cycle_count_variance_dsl::cycle_count_variance)
+            .values(row)
+            .on_conflict(cycle_count_variance_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    /// Most recent counts first
+    pub fn find_many_by_store_id(
+        &self,
+        store_id: &str,
+        item_link_id: Option<&str>,
+    ) -> Result<Vec<CycleCountVarianceRow>, RepositoryError> {
+        let mut query = cycle_count_variance_dsl::cycle_count_variance
+            .filter(cycle_count_variance_dsl::store_id.eq(store_id))
+            .into_boxed();
+        if let Some(item_link_id) = item_link_id {
+            query = query.filter(cycle_count_variance_dsl::item_link_id.eq(item_link_id));
+        }
+        let result = query
+            .order(cycle_count_variance_dsl::counted_date.desc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
@@ -6,12 +6,13 @@ use super::{
 };
 
 use crate::repository_error::RepositoryError;
-use crate::{Delete, Upsert};
+use crate::{AuditLogRecordType, AuditLogRowRepository, Delete, Upsert};
 
 use diesel::prelude::*;
 
 use chrono::NaiveDate;
 use diesel_derive_enum::DbEnum;
+use serde::Serialize;
 
 table! {
     invoice_line (id) {
@@ -48,7 +49,8 @@ joinable!(invoice_line -> return_reason (return_reason_id));
 allow_tables_to_appear_in_same_query!(invoice_line, item_link);
 allow_tables_to_appear_in_same_query!(invoice_line, name_link);
 
-#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Default)]
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
 pub enum InvoiceLineType {
     #[default]
@@ -58,7 +60,7 @@ pub enum InvoiceLineType {
     Service,
 }
 
-#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default, Serialize)]
 #[diesel(treat_none_as_null = true)]
 #[diesel(table_name = invoice_line)]
 pub struct InvoiceLineRow {
@@ -98,13 +100,42 @@ impl<'a> InvoiceLineRowRepository<'a> {
     }
 
     pub fn upsert_one(&self, row: &InvoiceLineRow) -> Result<(), RepositoryError> {
+        let audit = AuditLogRowRepository::new(self.connection);
+        let before = audit.find_before(|| self.find_one_by_id(&row.id))?;
         diesel::insert_into(invoice_line)
             .values(row)
             .on_conflict(id)
             .do_update()
             .set(row)
             .execute(self.connection.lock().connection())?;
-        Ok(())
+        audit.record_changes(
+            AuditLogRecordType::InvoiceLine,
+            &row.id,
+            before.as_ref(),
+            Some(row),
+        )
+    }
+
+    /// Audits a partial update of a record, `update` is only called once the previous state of the
+    /// record is loaded
+    fn audited_update(
+        &self,
+        record_id: &str,
+        update: impl FnOnce() -> Result<usize, diesel::result::Error>,
+    ) -> Result<(), RepositoryError> {
+        let audit = AuditLogRowRepository::new(self.connection);
+        let before = audit.find_before(|| self.find_one_by_id(record_id))?;
+        update()?;
+        let after = match before {
+            Some(_) => self.find_one_by_id(record_id)?,
+            None => None,
+        };
+        audit.record_changes(
+            AuditLogRecordType::InvoiceLine,
+            record_id,
+            before.as_ref(),
+            after.as_ref(),
+        )
     }
 
     pub fn update_inventory_adjustment_reason_id(
@@ -112,11 +143,12 @@ impl<'a> InvoiceLineRowRepository<'a> {
         record_id: &str,
         reason_id: Option<String>,
     ) -> Result<(), RepositoryError> {
-        diesel::update(invoice_line)
-            .filter(id.eq(record_id))
-            .set(inventory_adjustment_reason_id.eq(reason_id))
-            .execute(self.connection.lock().connection())?;
-        Ok(())
+        self.audited_update(record_id, || {
+            diesel::update(invoice_line)
+                .filter(id.eq(record_id))
+                .set(inventory_adjustment_reason_id.eq(reason_id))
+                .execute(self.connection.lock().connection())
+        })
     }
 
     pub fn update_return_reason_id(
@@ -124,11 +156,12 @@ impl<'a> InvoiceLineRowRepository<'a> {
         record_id: &str,
         reason_id: Option<String>,
     ) -> Result<(), RepositoryError> {
-        diesel::update(invoice_line)
-            .filter(id.eq(record_id))
-            .set(return_reason_id.eq(reason_id))
-            .execute(self.connection.lock().connection())?;
-        Ok(())
+        self.audited_update(record_id, || {
+            diesel::update(invoice_line)
+                .filter(id.eq(record_id))
+                .set(return_reason_id.eq(reason_id))
+                .execute(self.connection.lock().connection())
+        })
     }
 
     pub fn update_tax(
@@ -137,14 +170,15 @@ impl<'a> InvoiceLineRowRepository<'a> {
         tax_input: Option<f64>,
         total_after_tax_calculation: f64,
     ) -> Result<(), RepositoryError> {
-        diesel::update(invoice_line)
-            .filter(id.eq(record_id))
-            .set((
-                tax_percentage.eq(tax_input),
-                total_after_tax.eq(total_after_tax_calculation),
-            ))
-            .execute(self.connection.lock().connection())?;
-        Ok(())
+        self.audited_update(record_id, || {
+            diesel::update(invoice_line)
+                .filter(id.eq(record_id))
+                .set((
+                    tax_percentage.eq(tax_input),
+                    total_after_tax.eq(total_after_tax_calculation),
+                ))
+                .execute(self.connection.lock().connection())
+        })
     }
 
     pub fn update_currency(
@@ -152,19 +186,28 @@ impl<'a> InvoiceLineRowRepository<'a> {
         record_id: &str,
         foreign_currency_price_before_tax_calculation: Option<f64>,
     ) -> Result<(), RepositoryError> {
-        diesel::update(invoice_line)
-            .filter(id.eq(record_id))
-            .set(
-                foreign_currency_price_before_tax.eq(foreign_currency_price_before_tax_calculation),
-            )
-            .execute(self.connection.lock().connection())?;
-        Ok(())
+        self.audited_update(record_id, || {
+            diesel::update(invoice_line)
+                .filter(id.eq(record_id))
+                .set(
+                    foreign_currency_price_before_tax
+                        .eq(foreign_currency_price_before_tax_calculation),
+                )
+                .execute(self.connection.lock().connection())
+        })
     }
 
     pub fn delete(&self, invoice_line_id: &str) -> Result<(), RepositoryError> {
+        let audit = AuditLogRowRepository::new(self.connection);
+        let before = audit.find_before(|| self.find_one_by_id(invoice_line_id))?;
         diesel::delete(invoice_line.filter(id.eq(invoice_line_id)))
             .execute(self.connection.lock().connection())?;
-        Ok(())
+        audit.record_changes(
+            AuditLogRecordType::InvoiceLine,
+            invoice_line_id,
+            before.as_ref(),
+            None,
+        )
     }
 
     pub fn find_one_by_id(
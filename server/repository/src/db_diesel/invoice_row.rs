@@ -6,7 +6,9 @@ use super::{
     StorageConnection,
 };
 
-use crate::{repository_error::RepositoryError, Delete, Upsert};
+use crate::{
+    repository_error::RepositoryError, AuditLogRecordType, AuditLogRowRepository, Delete, Upsert,
+};
 
 use diesel::{dsl::max, prelude::*};
 
@@ -83,7 +85,7 @@ pub enum InvoiceStatus {
     Verified,
 }
 
-#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Serialize)]
 #[diesel(treat_none_as_null = true)]
 #[diesel(table_name = invoice)]
 pub struct InvoiceRow {
@@ -160,19 +162,33 @@ impl<'a> InvoiceRowRepository<'a> {
     }
 
     pub fn upsert_one(&self, row: &InvoiceRow) -> Result<(), RepositoryError> {
+        let audit = AuditLogRowRepository::new(self.connection);
+        let before = audit.find_before(|| self.find_one_by_id(&row.id))?;
         diesel::insert_into(invoice)
             .values(row)
             .on_conflict(id)
             .do_update()
             .set(row)
             .execute(self.connection.lock().connection())?;
-        Ok(())
+        audit.record_changes(
+            AuditLogRecordType::Invoice,
+            &row.id,
+            before.as_ref(),
+            Some(row),
+        )
     }
 
     pub fn delete(&self, invoice_id: &str) -> Result<(), RepositoryError> {
+        let audit = AuditLogRowRepository::new(self.connection);
+        let before = audit.find_before(|| self.find_one_by_id(invoice_id))?;
         diesel::delete(invoice.filter(id.eq(invoice_id)))
             .execute(self.connection.lock().connection())?;
-        Ok(())
+        audit.record_changes(
+            AuditLogRecordType::Invoice,
+            invoice_id,
+            before.as_ref(),
+            None,
+        )
     }
 
     pub fn find_one_by_id(&self, invoice_id: &str) -> Result<Option<InvoiceRow>, RepositoryError> {
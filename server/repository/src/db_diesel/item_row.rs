@@ -1,12 +1,17 @@
+use crate::{ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType};
 use crate::{Delete, Upsert};
 
 use super::{
-    item_link_row::item_link, item_row::item::dsl::*, name_link_row::name_link, unit_row::unit,
+    item_link_row::item_link,
+    item_row::{item::dsl::*, item_oms_fields::dsl as item_oms_fields_dsl},
+    name_link_row::name_link,
+    unit_row::unit,
     ItemLinkRow, ItemLinkRowRepository, RepositoryError, StorageConnection,
 };
 
 use diesel::prelude::*;
 use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
 
 table! {
     item (id) {
@@ -20,6 +25,8 @@ table! {
         legacy_record -> Text,
         is_active -> Bool,
         is_vaccine -> Bool,
+        heat_stability_threshold -> Nullable<Double>,
+        heat_stability_budget_minutes -> Nullable<Integer>,
     }
 }
 
@@ -30,8 +37,19 @@ table! {
     }
 }
 
+table! {
+    #[sql_name = "item"]
+    item_oms_fields (id) {
+        id -> Text,
+        heat_stability_threshold -> Nullable<Double>,
+        heat_stability_budget_minutes -> Nullable<Integer>,
+    }
+}
+
 joinable!(item -> unit (unit_id));
 joinable!(item_is_visible -> item (id));
+joinable!(item_oms_fields -> item (id));
+allow_tables_to_appear_in_same_query!(item, item_oms_fields);
 allow_tables_to_appear_in_same_query!(item, item_link);
 allow_tables_to_appear_in_same_query!(item, name_link);
 
@@ -57,6 +75,24 @@ pub struct ItemRow {
     pub legacy_record: String,
     pub is_active: bool,
     pub is_vaccine: bool,
+    /// Temperature above which time counts against the heat stability budget, set on open mSupply
+    /// central via `ItemOmsFieldsRow`
+    pub heat_stability_threshold: Option<f64>,
+    /// Total time stock can spend above the heat stability threshold, set on open mSupply central
+    /// via `ItemOmsFieldsRow`
+    pub heat_stability_budget_minutes: Option<i32>,
+}
+
+/// Item fields that are managed by open mSupply central rather than legacy mSupply
+#[derive(
+    Clone, Queryable, Insertable, Debug, PartialEq, AsChangeset, Default, Serialize, Deserialize,
+)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(table_name = item_oms_fields)]
+pub struct ItemOmsFieldsRow {
+    pub id: String,
+    pub heat_stability_threshold: Option<f64>,
+    pub heat_stability_budget_minutes: Option<i32>,
 }
 
 impl Default for ItemRow {
@@ -71,6 +107,8 @@ impl Default for ItemRow {
             legacy_record: Default::default(),
             is_active: true,
             is_vaccine: false,
+            heat_stability_threshold: None,
+            heat_stability_budget_minutes: None,
         }
     }
 }
@@ -150,6 +188,31 @@ impl<'a> ItemRowRepository<'a> {
             .execute(self.connection.lock().connection())?;
         Ok(())
     }
+
+    pub fn find_one_oms_fields_by_id(
+        &self,
+        item_id: &str,
+    ) -> Result<Option<ItemOmsFieldsRow>, RepositoryError> {
+        let result = item_oms_fields_dsl::item_oms_fields
+            .filter(item_oms_fields_dsl::id.eq(item_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn update_oms_fields(&self, row: &ItemOmsFieldsRow) -> Result<i64, RepositoryError> {
+        diesel::update(item_oms_fields::table.find(&row.id))
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+
+        let changelog = ChangeLogInsertRow {
+            table_name: ChangelogTableName::ItemOmsFields,
+            record_id: row.id.clone(),
+            row_action: RowActionType::Upsert,
+            ..Default::default()
+        };
+        ChangelogRepository::new(self.connection).insert(&changelog)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -183,3 +246,23 @@ impl Upsert for ItemRow {
         )
     }
 }
+
+impl Upsert for ItemOmsFieldsRow {
+    fn upsert_sync(&self, con: &StorageConnection) -> Result<(), RepositoryError> {
+        let _changelog_id = ItemRowRepository::new(con).update_oms_fields(self)?;
+        Ok(())
+    }
+
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = ItemRowRepository::new(con).update_oms_fields(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            ItemRowRepository::new(con).find_one_oms_fields_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
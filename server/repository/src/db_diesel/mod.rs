@@ -3,9 +3,14 @@ use crate::repository_error::RepositoryError;
 pub mod activity_log;
 mod activity_log_row;
 pub mod assets;
+pub mod audit_log;
+mod audit_log_row;
 pub mod barcode;
 mod barcode_row;
 pub mod changelog;
+pub mod changelog_consumer_dead_letter;
+mod changelog_consumer_dead_letter_row;
+mod changelog_consumer_row;
 pub mod clinician;
 mod clinician_row;
 mod clinician_store_join_row;
@@ -15,6 +20,9 @@ pub mod contact_trace_row;
 mod context_row;
 pub mod currency;
 mod currency_row;
+mod cycle_count_item_row;
+mod cycle_count_plan_row;
+mod cycle_count_variance_row;
 pub mod demographic_indicator;
 pub mod demographic_indicator_row;
 pub mod demographic_projection;
@@ -64,6 +72,9 @@ pub mod name_store_join;
 pub mod name_tag;
 pub mod name_tag_join;
 mod name_tag_row;
+pub mod notification_outbox;
+mod notification_outbox_row;
+mod notification_subscription_row;
 mod number_row;
 pub mod pack_variant;
 mod pack_variant_row;
@@ -80,6 +91,9 @@ pub mod property;
 pub mod property_row;
 pub mod report;
 mod report_row;
+pub mod report_schedule_run;
+mod report_schedule_run_row;
+mod report_schedule_row;
 pub mod requisition;
 pub mod requisition_line;
 pub mod return_reason;
@@ -99,6 +113,7 @@ pub mod store;
 mod store_preference_row;
 mod store_row;
 pub mod sync_buffer;
+mod sync_bundle_site_row;
 pub mod sync_log;
 mod sync_log_row;
 pub mod temperature_breach;
@@ -107,6 +122,8 @@ mod temperature_breach_config_row;
 mod temperature_breach_row;
 mod temperature_excursion;
 pub mod temperature_log;
+pub mod temperature_log_aggregate;
+mod temperature_log_aggregate_row;
 mod temperature_log_row;
 mod unit_row;
 mod user;
@@ -118,8 +135,13 @@ pub mod vaccine_course;
 
 pub use activity_log_row::*;
 pub use assets::*;
+pub use audit_log::*;
+pub use audit_log_row::*;
 pub use barcode_row::*;
 pub use changelog::*;
+pub use changelog_consumer_dead_letter::*;
+pub use changelog_consumer_dead_letter_row::*;
+pub use changelog_consumer_row::*;
 pub use clinician::*;
 pub use clinician_link_row::*;
 pub use clinician_row::*;
@@ -128,6 +150,9 @@ pub use consumption::*;
 pub use context_row::*;
 pub use currency::*;
 pub use currency_row::*;
+pub use cycle_count_item_row::*;
+pub use cycle_count_plan_row::*;
+pub use cycle_count_variance_row::*;
 pub use demographic_indicator::*;
 pub use demographic_indicator_row::*;
 pub use demographic_projection_row::*;
@@ -165,6 +190,9 @@ pub use name_store_join::*;
 pub use name_tag::*;
 pub use name_tag_join::*;
 pub use name_tag_row::*;
+pub use notification_outbox::*;
+pub use notification_outbox_row::*;
+pub use notification_subscription_row::*;
 pub use number_row::*;
 pub use pack_variant::*;
 pub use pack_variant_row::*;
@@ -181,6 +209,9 @@ pub use property_row::*;
 pub use report::*;
 pub use report_query::*;
 pub use report_row::*;
+pub use report_schedule_run::*;
+pub use report_schedule_run_row::*;
+pub use report_schedule_row::*;
 pub use requisition::*;
 pub use requisition_line::*;
 pub use return_reason_row::*;
@@ -199,6 +230,7 @@ pub use store::*;
 pub use store_preference_row::*;
 pub use store_row::*;
 pub use sync_buffer::*;
+pub use sync_bundle_site_row::*;
 pub use sync_file_reference::*;
 pub use sync_file_reference_row::*;
 pub use sync_log::*;
@@ -209,6 +241,8 @@ pub use temperature_breach_config_row::*;
 pub use temperature_breach_row::*;
 pub use temperature_excursion::*;
 pub use temperature_log::*;
+pub use temperature_log_aggregate::*;
+pub use temperature_log_aggregate_row::*;
 pub use temperature_log_row::*;
 pub use unit_row::*;
 pub use user::*;
@@ -0,0 +1,143 @@
+use super::{
+    notification_outbox_row::{notification_outbox, notification_outbox::dsl as outbox_dsl},
+    DBType, NotificationOutboxRow, NotificationOutboxStatus, StorageConnection,
+};
+use diesel::prelude::*;
+
+use crate::{
+    diesel_macros::{apply_equal_filter, apply_sort},
+    repository_error::RepositoryError,
+    NotificationEventType,
+};
+
+use crate::{EqualFilter, Pagination, Sort};
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct NotificationOutboxFilter {
+    pub id: Option<EqualFilter<String>>,
+    pub store_id: Option<EqualFilter<String>>,
+    pub subscription_id: Option<EqualFilter<String>>,
+    pub event_type: Option<EqualFilter<NotificationEventType>>,
+    pub status: Option<EqualFilter<NotificationOutboxStatus>>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum NotificationOutboxSortField {
+    CreatedDatetime,
+}
+
+pub type NotificationOutboxSort = Sort<NotificationOutboxSortField>;
+
+pub struct NotificationOutboxRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> NotificationOutboxRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        NotificationOutboxRepository { connection }
+    }
+
+    pub fn count(&self, filter: Option<NotificationOutboxFilter>) -> Result<i64, RepositoryError> {
+        let query = create_filtered_query(filter);
+        Ok(query
+            .count()
+            .get_result(self.connection.lock().connection())?)
+    }
+
+    pub fn query_by_filter(
+        &self,
+        filter: NotificationOutboxFilter,
+    ) -> Result<Vec<NotificationOutboxRow>, RepositoryError> {
+        self.query(Pagination::all(), Some(filter), None)
+    }
+
+    pub fn query(
+        &self,
+        pagination: Pagination,
+        filter: Option<NotificationOutboxFilter>,
+        sort: Option<NotificationOutboxSort>,
+    ) -> Result<Vec<NotificationOutboxRow>, RepositoryError> {
+        let mut query = create_filtered_query(filter);
+        if let Some(sort) = sort {
+            match sort.key {
+                NotificationOutboxSortField::CreatedDatetime => {
+                    apply_sort!(query, sort, outbox_dsl::created_datetime)
+                }
+            }
+        } else {
+            query = query.order(outbox_dsl::created_datetime.desc())
+        }
+
+        let result = query
+            .offset(pagination.offset as i64)
+            .limit(pagination.limit as i64)
+            .load::<NotificationOutboxRow>(self.connection.lock().connection())?;
+
+        Ok(result)
+    }
+}
+
+type BoxedNotificationOutboxQuery = notification_outbox::BoxedQuery<'static, DBType>;
+
+fn create_filtered_query(filter: Option<NotificationOutboxFilter>) -> BoxedNotificationOutboxQuery {
+    let mut query = notification_outbox::table.into_boxed();
+
+    if let Some(filter) = filter {
+        apply_equal_filter!(query, filter.id, outbox_dsl::id);
+        apply_equal_filter!(query, filter.store_id, outbox_dsl::store_id);
+        apply_equal_filter!(query, filter.subscription_id, outbox_dsl::subscription_id);
+        apply_equal_filter!(query, filter.event_type, outbox_dsl::event_type);
+        apply_equal_filter!(query, filter.status, outbox_dsl::status);
+    }
+
+    query
+}
+
+impl NotificationOutboxFilter {
+    pub fn new() -> NotificationOutboxFilter {
+        Self::default()
+    }
+
+    pub fn id(mut self, filter: EqualFilter<String>) -> Self {
+        self.id = Some(filter);
+        self
+    }
+
+    pub fn store_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.store_id = Some(filter);
+        self
+    }
+
+    pub fn subscription_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.subscription_id = Some(filter);
+        self
+    }
+
+    pub fn event_type(mut self, filter: EqualFilter<NotificationEventType>) -> Self {
+        self.event_type = Some(filter);
+        self
+    }
+
+    pub fn status(mut self, filter: EqualFilter<NotificationOutboxStatus>) -> Self {
+        self.status = Some(filter);
+        self
+    }
+}
+
+impl NotificationOutboxStatus {
+    pub fn equal_to(&self) -> EqualFilter<Self> {
+        EqualFilter {
+            equal_to: Some(*self),
+            ..Default::default()
+        }
+    }
+}
+
+impl NotificationEventType {
+    pub fn equal_to(&self) -> EqualFilter<Self> {
+        EqualFilter {
+            equal_to: Some(*self),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use super::{
+    notification_outbox_row::notification_outbox::dsl as notification_outbox_dsl,
+    NotificationChannel, NotificationEventType, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+
+table! {
+    notification_outbox (id) {
+        id -> Text,
+        subscription_id -> Text,
+        store_id -> Text,
+        event_type -> crate::db_diesel::notification_subscription_row::NotificationEventTypeMapping,
+        record_id -> Text,
+        channel -> crate::db_diesel::notification_subscription_row::NotificationChannelMapping,
+        recipient -> Text,
+        subject -> Text,
+        body -> Text,
+        status -> crate::db_diesel::notification_outbox_row::NotificationOutboxStatusMapping,
+        attempts -> Integer,
+        next_attempt_datetime -> Nullable<Timestamp>,
+        last_error -> Nullable<Text>,
+        created_datetime -> Timestamp,
+        sent_datetime -> Nullable<Timestamp>,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum NotificationOutboxStatus {
+    #[default]
+    Pending,
+    Sent,
+    /// Delivery failed too many times, won't be retried
+    Failed,
+}
+
+/// A rendered notification waiting to be delivered (or delivered), one per subscription and
+/// notified record
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = notification_outbox)]
+#[diesel(treat_none_as_null = true)]
+pub struct NotificationOutboxRow {
+    pub id: String,
+    pub subscription_id: String,
+    pub store_id: String,
+    pub event_type: NotificationEventType,
+    /// Id of the record the notification is about, e.g. temperature breach id
+    pub record_id: String,
+    pub channel: NotificationChannel,
+    pub recipient: String,
+    pub subject: String,
+    pub body: String,
+    pub status: NotificationOutboxStatus,
+    /// Number of failed delivery attempts
+    pub attempts: i32,
+    /// Pending notifications are not delivered before this datetime (set after a failed attempt)
+    pub next_attempt_datetime: Option<NaiveDateTime>,
+    pub last_error: Option<String>,
+    pub created_datetime: NaiveDateTime,
+    pub sent_datetime: Option<NaiveDateTime>,
+}
+
+pub struct NotificationOutboxRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> NotificationOutboxRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        NotificationOutboxRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &NotificationOutboxRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(notification_outbox_dsl::notification_outbox)
+            .values(row)
+            .on_conflict(notification_outbox_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        id: &str,
+    ) -> Result<Option<NotificationOutboxRow>, RepositoryError> {
+        let result = notification_outbox_dsl::notification_outbox
+            .filter(notification_outbox_dsl::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_one_by_subscription_and_record(
+        &self,
+        subscription_id: &str,
+        record_id: &str,
+    ) -> Result<Option<NotificationOutboxRow>, RepositoryError> {
+        let result = notification_outbox_dsl::notification_outbox
+            .filter(notification_outbox_dsl::subscription_id.eq(subscription_id))
+            .filter(notification_outbox_dsl::record_id.eq(record_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Pending notifications that are due for a delivery attempt at `datetime`, oldest first
+    pub fn find_due(
+        &self,
+        datetime: NaiveDateTime,
+        limit: i64,
+    ) -> Result<Vec<NotificationOutboxRow>, RepositoryError> {
+        let result = notification_outbox_dsl::notification_outbox
+            .filter(notification_outbox_dsl::status.eq(NotificationOutboxStatus::Pending))
+            .filter(
+                notification_outbox_dsl::next_attempt_datetime
+                    .is_null()
+                    .or(notification_outbox_dsl::next_attempt_datetime.le(datetime)),
+            )
+            .order(notification_outbox_dsl::created_datetime.asc())
+            .limit(limit)
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
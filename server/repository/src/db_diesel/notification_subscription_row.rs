@@ -0,0 +1,141 @@
+use super::{
+    notification_subscription_row::notification_subscription::dsl as notification_subscription_dsl,
+    StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+
+table! {
+    notification_subscription (id) {
+        id -> Text,
+        store_id -> Text,
+        event_type -> crate::db_diesel::notification_subscription_row::NotificationEventTypeMapping,
+        channel -> crate::db_diesel::notification_subscription_row::NotificationChannelMapping,
+        recipient -> Text,
+        subject_template -> Nullable<Text>,
+        body_template -> Nullable<Text>,
+        is_active -> Bool,
+        created_datetime -> Timestamp,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum NotificationEventType {
+    /// A temperature breach was detected
+    #[default]
+    TemperatureBreach,
+    /// A customer's request requisition was received (response requisition created by transfer)
+    RequisitionReceived,
+    /// An inbound shipment was delivered
+    ShipmentArrived,
+    /// Stock on hand will expire soon
+    StockExpiring,
+}
+
+#[derive(DbEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum NotificationChannel {
+    #[default]
+    Email,
+    Webhook,
+}
+
+/// Rule to notify `recipient` (an email address or a webhook url) of events of `event_type` in
+/// the store. Only events that happen after the subscription was created are notified.
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = notification_subscription)]
+#[diesel(treat_none_as_null = true)]
+pub struct NotificationSubscriptionRow {
+    pub id: String,
+    pub store_id: String,
+    pub event_type: NotificationEventType,
+    pub channel: NotificationChannel,
+    pub recipient: String,
+    /// Tera template, the default template of the event type is used if not set
+    pub subject_template: Option<String>,
+    /// Tera template, the default template of the event type is used if not set
+    pub body_template: Option<String>,
+    pub is_active: bool,
+    pub created_datetime: NaiveDateTime,
+}
+
+pub struct NotificationSubscriptionRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> NotificationSubscriptionRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        NotificationSubscriptionRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &NotificationSubscriptionRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(notification_subscription_dsl::notification_subscription)
+            .values(row)
+            .on_conflict(notification_subscription_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        id: &str,
+    ) -> Result<Option<NotificationSubscriptionRow>, RepositoryError> {
+        let result = notification_subscription_dsl::notification_subscription
+            .filter(notification_subscription_dsl::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_store_id(
+        &self,
+        store_id: &str,
+    ) -> Result<Vec<NotificationSubscriptionRow>, RepositoryError> {
+        let result = notification_subscription_dsl::notification_subscription
+            .filter(notification_subscription_dsl::store_id.eq(store_id))
+            .order(notification_subscription_dsl::created_datetime.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn find_active(
+        &self,
+        store_id: &str,
+        event_type: NotificationEventType,
+    ) -> Result<Vec<NotificationSubscriptionRow>, RepositoryError> {
+        let result = notification_subscription_dsl::notification_subscription
+            .filter(notification_subscription_dsl::store_id.eq(store_id))
+            .filter(notification_subscription_dsl::event_type.eq(event_type))
+            .filter(notification_subscription_dsl::is_active.eq(true))
+            .order(notification_subscription_dsl::created_datetime.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Active subscriptions of all stores
+    pub fn find_active_by_event_type(
+        &self,
+        event_type: NotificationEventType,
+    ) -> Result<Vec<NotificationSubscriptionRow>, RepositoryError> {
+        let result = notification_subscription_dsl::notification_subscription
+            .filter(notification_subscription_dsl::event_type.eq(event_type))
+            .filter(notification_subscription_dsl::is_active.eq(true))
+            .order(notification_subscription_dsl::created_datetime.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), RepositoryError> {
+        diesel::delete(notification_subscription_dsl::notification_subscription)
+            .filter(notification_subscription_dsl::id.eq(id))
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+}
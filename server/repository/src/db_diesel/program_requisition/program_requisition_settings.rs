@@ -157,6 +157,7 @@ mod test {
             program_id: program.id.clone(),
             name_tag_id: name_tag1.id.clone(),
             period_schedule_id: mock_period_schedule_1().id,
+            consumption_forecast_method: None,
         };
         let (_, connection, _, _) = setup_all_with_data(
             "program_requisition_settings_repository",
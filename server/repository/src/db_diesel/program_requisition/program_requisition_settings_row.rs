@@ -5,7 +5,7 @@ use super::{
 
 use crate::{
     db_diesel::name_tag_row::name_tag, period_schedule_row::period_schedule,
-    repository_error::RepositoryError, StorageConnection,
+    repository_error::RepositoryError, ConsumptionForecastMethod, StorageConnection,
 };
 use crate::{Delete, Upsert};
 use diesel::prelude::*;
@@ -16,6 +16,7 @@ table! {
         name_tag_id -> Text,
         program_id -> Text,
         period_schedule_id -> Text,
+        consumption_forecast_method -> Nullable<crate::db_diesel::store_preference_row::ConsumptionForecastMethodMapping>,
     }
 }
 
@@ -25,11 +26,14 @@ joinable!(program_requisition_settings -> period_schedule(period_schedule_id));
 
 #[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
 #[diesel(table_name = program_requisition_settings)]
+#[diesel(treat_none_as_null = true)]
 pub struct ProgramRequisitionSettingsRow {
     pub id: String,
     pub name_tag_id: String,
     pub program_id: String,
     pub period_schedule_id: String,
+    /// Overrides store preference consumption_forecast_method for this program
+    pub consumption_forecast_method: Option<ConsumptionForecastMethod>,
 }
 
 pub struct ProgramRequisitionSettingsRowRepository<'a> {
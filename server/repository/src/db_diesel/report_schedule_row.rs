@@ -0,0 +1,101 @@
+use super::{report_schedule_row::report_schedule::dsl as report_schedule_dsl, StorageConnection};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+table! {
+    report_schedule (id) {
+        id -> Text,
+        report_id -> Text,
+        store_id -> Text,
+        user_id -> Text,
+        name -> Text,
+        schedule -> Text,
+        arguments -> Nullable<Text>,
+        is_active -> Bool,
+        created_datetime -> Timestamp,
+        last_run_datetime -> Nullable<Timestamp>,
+        next_run_datetime -> Nullable<Timestamp>,
+    }
+}
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = report_schedule)]
+#[diesel(treat_none_as_null = true)]
+pub struct ReportScheduleRow {
+    pub id: String,
+    pub report_id: String,
+    pub store_id: String,
+    /// User that created or last updated the schedule
+    pub user_id: String,
+    pub name: String,
+    /// Cron expression, e.g. "0 6 1 * *" (6am on the 1st of every month)
+    pub schedule: String,
+    /// Report arguments (json), may contain relative dates like "$previousMonthStart"
+    pub arguments: Option<String>,
+    pub is_active: bool,
+    pub created_datetime: NaiveDateTime,
+    pub last_run_datetime: Option<NaiveDateTime>,
+    pub next_run_datetime: Option<NaiveDateTime>,
+}
+
+pub struct ReportScheduleRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ReportScheduleRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ReportScheduleRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &ReportScheduleRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(report_schedule_dsl::report_schedule)
+            .values(row)
+            .on_conflict(report_schedule_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<ReportScheduleRow>, RepositoryError> {
+        let result = report_schedule_dsl::report_schedule
+            .filter(report_schedule_dsl::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_store_id(
+        &self,
+        store_id: &str,
+    ) -> Result<Vec<ReportScheduleRow>, RepositoryError> {
+        let result = report_schedule_dsl::report_schedule
+            .filter(report_schedule_dsl::store_id.eq(store_id))
+            .order(report_schedule_dsl::name.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Active schedules with a next run at or before `datetime`
+    pub fn find_due(
+        &self,
+        datetime: NaiveDateTime,
+    ) -> Result<Vec<ReportScheduleRow>, RepositoryError> {
+        let result = report_schedule_dsl::report_schedule
+            .filter(report_schedule_dsl::is_active.eq(true))
+            .filter(report_schedule_dsl::next_run_datetime.le(datetime))
+            .order(report_schedule_dsl::next_run_datetime.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), RepositoryError> {
+        diesel::delete(report_schedule_dsl::report_schedule)
+            .filter(report_schedule_dsl::id.eq(id))
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+}
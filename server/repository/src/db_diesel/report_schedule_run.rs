@@ -0,0 +1,96 @@
+use super::{
+    report_schedule_run_row::{report_schedule_run, report_schedule_run::dsl as run_dsl},
+    DBType, StorageConnection,
+};
+use diesel::prelude::*;
+
+use crate::{
+    diesel_macros::apply_equal_filter, repository_error::RepositoryError, ReportScheduleRunRow,
+};
+
+use crate::{EqualFilter, Pagination};
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ReportScheduleRunFilter {
+    pub id: Option<EqualFilter<String>>,
+    pub store_id: Option<EqualFilter<String>>,
+    pub report_schedule_id: Option<EqualFilter<String>>,
+}
+
+pub struct ReportScheduleRunRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ReportScheduleRunRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ReportScheduleRunRepository { connection }
+    }
+
+    pub fn count(&self, filter: Option<ReportScheduleRunFilter>) -> Result<i64, RepositoryError> {
+        let query = create_filtered_query(filter);
+        Ok(query
+            .count()
+            .get_result(self.connection.lock().connection())?)
+    }
+
+    pub fn query_by_filter(
+        &self,
+        filter: ReportScheduleRunFilter,
+    ) -> Result<Vec<ReportScheduleRunRow>, RepositoryError> {
+        self.query(Pagination::all(), Some(filter))
+    }
+
+    /// Most recent runs first
+    pub fn query(
+        &self,
+        pagination: Pagination,
+        filter: Option<ReportScheduleRunFilter>,
+    ) -> Result<Vec<ReportScheduleRunRow>, RepositoryError> {
+        let result = create_filtered_query(filter)
+            .order(run_dsl::run_datetime.desc())
+            .offset(pagination.offset as i64)
+            .limit(pagination.limit as i64)
+            .load::<ReportScheduleRunRow>(self.connection.lock().connection())?;
+
+        Ok(result)
+    }
+}
+
+type BoxedReportScheduleRunQuery = report_schedule_run::BoxedQuery<'static, DBType>;
+
+fn create_filtered_query(filter: Option<ReportScheduleRunFilter>) -> BoxedReportScheduleRunQuery {
+    let mut query = report_schedule_run::table.into_boxed();
+
+    if let Some(filter) = filter {
+        apply_equal_filter!(query, filter.id, run_dsl::id);
+        apply_equal_filter!(query, filter.store_id, run_dsl::store_id);
+        apply_equal_filter!(
+            query,
+            filter.report_schedule_id,
+            run_dsl::report_schedule_id
+        );
+    }
+
+    query
+}
+
+impl ReportScheduleRunFilter {
+    pub fn new() -> ReportScheduleRunFilter {
+        Default::default()
+    }
+
+    pub fn id(mut self, filter: EqualFilter<String>) -> Self {
+        self.id = Some(filter);
+        self
+    }
+
+    pub fn store_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.store_id = Some(filter);
+        self
+    }
+
+    pub fn report_schedule_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.report_schedule_id = Some(filter);
+        self
+    }
+}
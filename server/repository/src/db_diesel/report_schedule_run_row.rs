@@ -0,0 +1,78 @@
+use super::{
+    report_schedule_run_row::report_schedule_run::dsl as report_schedule_run_dsl, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+table! {
+    report_schedule_run (id) {
+        id -> Text,
+        report_schedule_id -> Text,
+        report_id -> Text,
+        store_id -> Text,
+        run_datetime -> Timestamp,
+        file_id -> Nullable<Text>,
+        file_name -> Nullable<Text>,
+        error -> Nullable<Text>,
+    }
+}
+
+/// Result of a scheduled report run, either a file or an error is set
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = report_schedule_run)]
+#[diesel(treat_none_as_null = true)]
+pub struct ReportScheduleRunRow {
+    pub id: String,
+    pub report_schedule_id: String,
+    pub report_id: String,
+    pub store_id: String,
+    pub run_datetime: NaiveDateTime,
+    /// Id of the archived file (StaticFileService)
+    pub file_id: Option<String>,
+    pub file_name: Option<String>,
+    pub error: Option<String>,
+}
+
+pub struct ReportScheduleRunRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ReportScheduleRunRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ReportScheduleRunRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &ReportScheduleRunRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(report_schedule_run_dsl::report_schedule_run)
+            .values(row)
+            .on_conflict(report_schedule_run_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        id: &str,
+    ) -> Result<Option<ReportScheduleRunRow>, RepositoryError> {
+        let result = report_schedule_run_dsl::report_schedule_run
+            .filter(report_schedule_run_dsl::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn delete_by_report_schedule_id(
+        &self,
+        report_schedule_id: &str,
+    ) -> Result<(), RepositoryError> {
+        diesel::delete(report_schedule_run_dsl::report_schedule_run)
+            .filter(report_schedule_run_dsl::report_schedule_id.eq(report_schedule_id))
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+}
@@ -3,9 +3,13 @@ use util::inline_init;
 use crate::{DateFilter, DatetimeFilter, EqualFilter, Sort, StringFilter};
 
 pub mod requisition;
+pub mod requisition_approval_level_row;
+pub mod requisition_approval_row;
 pub mod requisition_row;
 
 pub use self::requisition::*;
+pub use self::requisition_approval_level_row::*;
+pub use self::requisition_approval_row::*;
 pub use self::requisition_row::*;
 
 #[derive(Clone, Debug, PartialEq, Default)]
@@ -27,6 +31,7 @@ pub struct RequisitionFilter {
     pub store_id: Option<EqualFilter<String>>,
     pub linked_requisition_id: Option<EqualFilter<String>>,
     pub order_type: Option<EqualFilter<String>>,
+    pub is_emergency: Option<bool>,
 }
 
 #[derive(PartialEq, Debug)]
@@ -145,6 +150,11 @@ impl RequisitionFilter {
         self.order_type = Some(filter);
         self
     }
+
+    pub fn is_emergency(mut self, filter: bool) -> Self {
+        self.is_emergency = Some(filter);
+        self
+    }
 }
 
 impl RequisitionStatus {
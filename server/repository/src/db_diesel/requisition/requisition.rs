@@ -179,6 +179,7 @@ fn create_filtered_query(
         store_id,
         linked_requisition_id,
         order_type,
+        is_emergency,
     }) = filter
     {
         apply_equal_filter!(query, id, requisition_dsl::id);
@@ -216,7 +217,11 @@ fn create_filtered_query(
         apply_string_filter!(query, comment, requisition_dsl::comment);
 
         apply_equal_filter!(query, store_id, requisition_dsl::store_id);
-        apply_equal_filter!(query, order_type, requisition_dsl::order_type)
+        apply_equal_filter!(query, order_type, requisition_dsl::order_type);
+
+        if let Some(is_emergency) = is_emergency {
+            query = query.filter(requisition_dsl::is_emergency.eq(is_emergency));
+        }
     }
 
     Ok(query)
@@ -0,0 +1,174 @@
+use super::requisition_approval_level_row::requisition_approval_level::dsl as requisition_approval_level_dsl;
+
+use crate::{
+    repository_error::RepositoryError, ChangeLogInsertRow, ChangelogRepository, ChangelogTableName,
+    Delete, RowActionType, StorageConnection, Upsert,
+};
+
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+table! {
+    requisition_approval_level (id) {
+        id -> Text,
+        store_id -> Text,
+        program_id -> Nullable<Text>,
+        level -> Integer,
+        name -> Text,
+        approver_user_id -> Nullable<Text>,
+    }
+}
+
+/// Level of a response requisition approval chain, levels are approved in ascending order.
+/// The chain with a `program_id` applies to response requisitions of that program, the chain
+/// without a `program_id` applies to response requisitions without a program
+#[derive(
+    Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default, Serialize, Deserialize,
+)]
+#[diesel(table_name = requisition_approval_level)]
+#[diesel(treat_none_as_null = true)]
+pub struct RequisitionApprovalLevelRow {
+    pub id: String,
+    pub store_id: String,
+    pub program_id: Option<String>,
+    pub level: i32,
+    pub name: String,
+    /// Only this user can approve the level, any user with the approve permission if not set
+    pub approver_user_id: Option<String>,
+}
+
+pub struct RequisitionApprovalLevelRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> RequisitionApprovalLevelRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        RequisitionApprovalLevelRowRepository { connection }
+    }
+
+    fn _upsert_one(&self, row: &RequisitionApprovalLevelRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(requisition_approval_level_dsl::requisition_approval_level)
+            .values(row)
+            .on_conflict(requisition_approval_level_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    fn insert_changelog(
+        &self,
+        record_id: String,
+        row_action: RowActionType,
+        store_id: String,
+    ) -> Result<i64, RepositoryError> {
+        ChangelogRepository::new(self.connection).insert(&ChangeLogInsertRow {
+            table_name: ChangelogTableName::RequisitionApprovalLevel,
+            record_id,
+            row_action,
+            store_id: Some(store_id),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the changelog cursor
+    pub fn upsert_one(&self, row: &RequisitionApprovalLevelRow) -> Result<i64, RepositoryError> {
+        self._upsert_one(row)?;
+        self.insert_changelog(row.id.clone(), RowActionType::Upsert, row.store_id.clone())
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        id: &str,
+    ) -> Result<Option<RequisitionApprovalLevelRow>, RepositoryError> {
+        let result = requisition_approval_level_dsl::requisition_approval_level
+            .filter(requisition_approval_level_dsl::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Levels of all the approval chains of a store
+    pub fn find_many_by_store_id(
+        &self,
+        store_id: &str,
+    ) -> Result<Vec<RequisitionApprovalLevelRow>, RepositoryError> {
+        let result = requisition_approval_level_dsl::requisition_approval_level
+            .filter(requisition_approval_level_dsl::store_id.eq(store_id))
+            .order((
+                requisition_approval_level_dsl::program_id.asc(),
+                requisition_approval_level_dsl::level.asc(),
+            ))
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Levels of the approval chain of a store for a program, in approval order
+    pub fn find_chain(
+        &self,
+        store_id: &str,
+        program_id: Option<&str>,
+    ) -> Result<Vec<RequisitionApprovalLevelRow>, RepositoryError> {
+        let mut query = requisition_approval_level_dsl::requisition_approval_level
+            .filter(requisition_approval_level_dsl::store_id.eq(store_id))
+            .into_boxed();
+        query = match program_id {
+            Some(program_id) => {
+                query.filter(requisition_approval_level_dsl::program_id.eq(program_id))
+            }
+            None => query.filter(requisition_approval_level_dsl::program_id.is_null()),
+        };
+        let result = query
+            .order(requisition_approval_level_dsl::level.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), RepositoryError> {
+        let Some(row) = self.find_one_by_id(id)? else {
+            return Ok(());
+        };
+        diesel::delete(
+            requisition_approval_level_dsl::requisition_approval_level
+                .filter(requisition_approval_level_dsl::id.eq(id)),
+        )
+        .execute(self.connection.lock().connection())?;
+        self.insert_changelog(row.id, RowActionType::Delete, row.store_id)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RequisitionApprovalLevelRowDelete(pub String);
+impl Delete for RequisitionApprovalLevelRowDelete {
+    fn delete(&self, con: &StorageConnection) -> Result<(), RepositoryError> {
+        RequisitionApprovalLevelRowRepository::new(con).delete(&self.0)
+    }
+    // Test only
+    fn assert_deleted(&self, con: &StorageConnection) {
+        assert_eq!(
+            RequisitionApprovalLevelRowRepository::new(con).find_one_by_id(&self.0),
+            Ok(None)
+        )
+    }
+}
+
+impl Upsert for RequisitionApprovalLevelRow {
+    fn upsert_sync(&self, con: &StorageConnection) -> Result<(), RepositoryError> {
+        let _change_log_id = RequisitionApprovalLevelRowRepository::new(con).upsert_one(self)?;
+        Ok(())
+    }
+
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = RequisitionApprovalLevelRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            RequisitionApprovalLevelRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
@@ -0,0 +1,128 @@
+use super::{
+    requisition_approval_row::requisition_approval::dsl as requisition_approval_dsl,
+    requisition_row::requisition::dsl as requisition_dsl, ApprovalStatusType,
+};
+
+use crate::{
+    repository_error::RepositoryError, ChangeLogInsertRow, ChangelogRepository, ChangelogTableName,
+    RowActionType, StorageConnection, Upsert,
+};
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+table! {
+    requisition_approval (id) {
+        id -> Text,
+        requisition_id -> Text,
+        level -> Integer,
+        user_id -> Text,
+        status -> crate::db_diesel::requisition::requisition_row::ApprovalStatusTypeMapping,
+        comment -> Nullable<Text>,
+        created_datetime -> Timestamp,
+    }
+}
+
+/// Approval history of a response requisition, one row per approval chain level decision
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Serialize, Deserialize)]
+#[diesel(table_name = requisition_approval)]
+#[diesel(treat_none_as_null = true)]
+pub struct RequisitionApprovalRow {
+    pub id: String,
+    pub requisition_id: String,
+    pub level: i32,
+    pub user_id: String,
+    /// Approved or Denied
+    pub status: ApprovalStatusType,
+    pub comment: Option<String>,
+    pub created_datetime: NaiveDateTime,
+}
+
+pub struct RequisitionApprovalRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> RequisitionApprovalRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        RequisitionApprovalRowRepository { connection }
+    }
+
+    fn _upsert_one(&self, row: &RequisitionApprovalRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(requisition_approval_dsl::requisition_approval)
+            .values(row)
+            .on_conflict(requisition_approval_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    /// Returns the changelog cursor, the changelog has the store of the requisition
+    pub fn upsert_one(&self, row: &RequisitionApprovalRow) -> Result<i64, RepositoryError> {
+        self._upsert_one(row)?;
+
+        let store_id: Option<String> = requisition_dsl::requisition
+            .filter(requisition_dsl::id.eq(&row.requisition_id))
+            .select(requisition_dsl::store_id)
+            .first(self.connection.lock().connection())
+            .optional()?;
+        ChangelogRepository::new(self.connection).insert(&ChangeLogInsertRow {
+            table_name: ChangelogTableName::RequisitionApproval,
+            record_id: row.id.clone(),
+            row_action: RowActionType::Upsert,
+            store_id,
+            ..Default::default()
+        })
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        id: &str,
+    ) -> Result<Option<RequisitionApprovalRow>, RepositoryError> {
+        let result = requisition_approval_dsl::requisition_approval
+            .filter(requisition_approval_dsl::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Oldest decisions first
+    pub fn find_many_by_requisition_ids(
+        &self,
+        requisition_ids: &[String],
+    ) -> Result<Vec<RequisitionApprovalRow>, RepositoryError> {
+        let result = requisition_approval_dsl::requisition_approval
+            .filter(requisition_approval_dsl::requisition_id.eq_any(requisition_ids))
+            .order(requisition_approval_dsl::created_datetime.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_requisition_id(
+        &self,
+        requisition_id: &str,
+    ) -> Result<Vec<RequisitionApprovalRow>, RepositoryError> {
+        self.find_many_by_requisition_ids(&[requisition_id.to_string()])
+    }
+}
+
+impl Upsert for RequisitionApprovalRow {
+    fn upsert_sync(&self, con: &StorageConnection) -> Result<(), RepositoryError> {
+        let _change_log_id = RequisitionApprovalRowRepository::new(con).upsert_one(self)?;
+        Ok(())
+    }
+
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = RequisitionApprovalRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            RequisitionApprovalRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
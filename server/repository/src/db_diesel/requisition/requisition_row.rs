@@ -38,6 +38,7 @@ table! {
         program_id -> Nullable<Text>,
         period_id -> Nullable<Text>,
         order_type -> Nullable<Text>,
+        is_emergency -> Bool,
     }
 }
 
@@ -72,8 +73,9 @@ pub enum RequisitionStatus {
     Sent,
     Finalised,
 }
-#[derive(DbEnum, Debug, Clone, PartialEq, Eq)]
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(test, derive(strum::EnumIter))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
 pub enum ApprovalStatusType {
     None,
@@ -111,6 +113,7 @@ pub struct RequisitionRow {
     pub program_id: Option<String>,
     pub period_id: Option<String>,
     pub order_type: Option<String>,
+    pub is_emergency: bool,
 }
 
 impl Default for RequisitionRow {
@@ -138,6 +141,7 @@ impl Default for RequisitionRow {
             program_id: None,
             period_id: None,
             order_type: None,
+            is_emergency: false,
         }
     }
 }
@@ -5,9 +5,10 @@ use crate::repository_error::RepositoryError;
 use crate::StorageConnection;
 use diesel::prelude::*;
 
-use crate::{Delete, Upsert};
+use crate::{AuditLogRecordType, AuditLogRowRepository, Delete, Upsert};
 
 use chrono::NaiveDateTime;
+use serde::Serialize;
 
 table! {
     requisition_line (id) {
@@ -24,6 +25,12 @@ table! {
         approved_quantity -> Double,
         approval_comment -> Nullable<Text>,
         comment -> Nullable<Text>,
+        opening_balance -> Nullable<Double>,
+        quantity_received -> Nullable<Double>,
+        quantity_dispensed -> Nullable<Double>,
+        losses_and_adjustments -> Nullable<Double>,
+        closing_balance -> Nullable<Double>,
+        days_out_of_stock -> Nullable<Integer>,
     }
 }
 
@@ -39,7 +46,7 @@ joinable!(requisition_line -> item_link (item_link_id));
 joinable!(requisition_line -> requisition (requisition_id));
 allow_tables_to_appear_in_same_query!(requisition_line, item_link);
 
-#[derive(Clone, Queryable, AsChangeset, Insertable, Debug, PartialEq, Default)]
+#[derive(Clone, Queryable, AsChangeset, Insertable, Debug, PartialEq, Default, Serialize)]
 #[diesel(table_name = requisition_line)]
 pub struct RequisitionLineRow {
     pub id: String,
@@ -55,6 +62,14 @@ pub struct RequisitionLineRow {
     pub approved_quantity: f64,
     pub approval_comment: Option<String>,
     pub comment: Option<String>,
+    // Report and requisition (R&R) fields of program requisitions, for the requisition's period
+    pub opening_balance: Option<f64>,
+    pub quantity_received: Option<f64>,
+    pub quantity_dispensed: Option<f64>,
+    /// Net losses (negative) and adjustments (positive)
+    pub losses_and_adjustments: Option<f64>,
+    pub closing_balance: Option<f64>,
+    pub days_out_of_stock: Option<i32>,
 }
 
 pub struct RequisitionLineRowRepository<'a> {
@@ -85,18 +100,32 @@ impl<'a> RequisitionLineRowRepository<'a> {
     }
 
     pub fn upsert_one(&self, row: &RequisitionLineRow) -> Result<(), RepositoryError> {
+        let audit = AuditLogRowRepository::new(self.connection);
+        let before = audit.find_before(|| self.find_one_by_id(&row.id))?;
         self._upsert_one(row)?;
         self.toggle_is_sync_update(&row.id, false)?;
-        Ok(())
+        audit.record_changes(
+            AuditLogRecordType::RequisitionLine,
+            &row.id,
+            before.as_ref(),
+            Some(row),
+        )
     }
 
     pub fn delete(&self, requisition_line_id: &str) -> Result<(), RepositoryError> {
+        let audit = AuditLogRowRepository::new(self.connection);
+        let before = audit.find_before(|| self.find_one_by_id(requisition_line_id))?;
         diesel::delete(
             requisition_line_dsl::requisition_line
                 .filter(requisition_line_dsl::id.eq(requisition_line_id)),
         )
         .execute(self.connection.lock().connection())?;
-        Ok(())
+        audit.record_changes(
+            AuditLogRecordType::RequisitionLine,
+            requisition_line_id,
+            before.as_ref(),
+            None,
+        )
     }
 
     pub fn find_one_by_id(&self, id: &str) -> Result<Option<RequisitionLineRow>, RepositoryError> {
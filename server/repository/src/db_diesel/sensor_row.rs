@@ -34,6 +34,9 @@ pub enum SensorType {
     BlueMaestro,
     Laird,
     Berlinger,
+    LogTag,
+    /// Logger exporting a generic timestamp/temperature CSV
+    Csv,
 }
 
 // TODO put this somewhere more sensible
@@ -43,6 +46,8 @@ pub fn get_sensor_type(serial: &str) -> SensorType {
         Some("BLUE_MAESTRO") => SensorType::BlueMaestro,
         Some("LAIRD") => SensorType::Laird,
         Some("BERLINGER") => SensorType::Berlinger,
+        Some("LOG_TAG") => SensorType::LogTag,
+        Some("CSV") => SensorType::Csv,
         _ => SensorType::BlueMaestro,
     }
 }
@@ -3,11 +3,15 @@ use super::{
     stock_line_row::stock_line::dsl as stock_line_dsl, store_row::store, StorageConnection,
 };
 
-use crate::{db_diesel::barcode_row::barcode, repository_error::RepositoryError, Delete, Upsert};
+use crate::{
+    db_diesel::barcode_row::barcode, repository_error::RepositoryError, AuditLogRecordType,
+    AuditLogRowRepository, Delete, Upsert,
+};
 
 use diesel::prelude::*;
 
 use chrono::NaiveDate;
+use serde::Serialize;
 
 table! {
     stock_line (id) {
@@ -37,7 +41,7 @@ joinable!(stock_line -> barcode (barcode_id));
 allow_tables_to_appear_in_same_query!(stock_line, item_link);
 allow_tables_to_appear_in_same_query!(stock_line, name_link);
 
-#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default, Serialize)]
 #[diesel(treat_none_as_null = true)]
 #[diesel(table_name = stock_line)]
 pub struct StockLineRow {
@@ -68,19 +72,28 @@ impl<'a> StockLineRowRepository<'a> {
     }
 
     pub fn upsert_one(&self, row: &StockLineRow) -> Result<(), RepositoryError> {
+        let audit = AuditLogRowRepository::new(self.connection);
+        let before = audit.find_before(|| self.find_one_by_id(&row.id))?;
         diesel::insert_into(stock_line_dsl::stock_line)
             .values(row)
             .on_conflict(stock_line_dsl::id)
             .do_update()
             .set(row)
             .execute(self.connection.lock().connection())?;
-        Ok(())
+        audit.record_changes(
+            AuditLogRecordType::StockLine,
+            &row.id,
+            before.as_ref(),
+            Some(row),
+        )
     }
 
     pub fn delete(&self, id: &str) -> Result<(), RepositoryError> {
+        let audit = AuditLogRowRepository::new(self.connection);
+        let before = audit.find_before(|| self.find_one_by_id(id))?;
         diesel::delete(stock_line_dsl::stock_line.filter(stock_line_dsl::id.eq(id)))
             .execute(self.connection.lock().connection())?;
-        Ok(())
+        audit.record_changes(AuditLogRecordType::StockLine, id, before.as_ref(), None)
     }
 
     pub fn find_one_by_id(&self, id: &str) -> Result<Option<StockLineRow>, RepositoryError> {
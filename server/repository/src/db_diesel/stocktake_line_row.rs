@@ -5,11 +5,14 @@ use super::{
     StorageConnection,
 };
 
-use crate::{repository_error::RepositoryError, Delete, Upsert};
+use crate::{
+    repository_error::RepositoryError, AuditLogRecordType, AuditLogRowRepository, Delete, Upsert,
+};
 
 use diesel::prelude::*;
 
 use chrono::NaiveDate;
+use serde::Serialize;
 
 table! {
     stocktake_line (id) {
@@ -31,6 +34,7 @@ table! {
         sell_price_per_pack -> Nullable<Double>,
         note -> Nullable<Text>,
         inventory_adjustment_reason_id -> Nullable<Text>,
+        second_counted_number_of_packs -> Nullable<Double>,
     }
 }
 
@@ -41,7 +45,7 @@ joinable!(stocktake_line -> stock_line (stock_line_id));
 joinable!(stocktake_line -> inventory_adjustment_reason (inventory_adjustment_reason_id));
 allow_tables_to_appear_in_same_query!(stocktake_line, item_link);
 
-#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default, Serialize)]
 #[diesel(treat_none_as_null = true)]
 #[diesel(table_name = stocktake_line)]
 pub struct StocktakeLineRow {
@@ -65,6 +69,16 @@ pub struct StocktakeLineRow {
     pub sell_price_per_pack: Option<f64>,
     pub note: Option<String>,
     pub inventory_adjustment_reason_id: Option<String>,
+    /// Recount of blind count lines exceeding the variance threshold, replaces the first count
+    pub second_counted_number_of_packs: Option<f64>,
+}
+
+impl StocktakeLineRow {
+    /// Count used for adjustments, the second count if the line was recounted
+    pub fn final_counted_number_of_packs(&self) -> Option<f64> {
+        self.second_counted_number_of_packs
+            .or(self.counted_number_of_packs)
+    }
 }
 
 pub struct StocktakeLineRowRepository<'a> {
@@ -77,19 +91,28 @@ impl<'a> StocktakeLineRowRepository<'a> {
     }
 
     pub fn upsert_one(&self, row: &StocktakeLineRow) -> Result<(), RepositoryError> {
+        let audit = AuditLogRowRepository::new(self.connection);
+        let before = audit.find_before(|| self.find_one_by_id(&row.id))?;
         diesel::insert_into(stocktake_line_dsl::stocktake_line)
             .values(row)
             .on_conflict(stocktake_line_dsl::id)
             .do_update()
             .set(row)
             .execute(self.connection.lock().connection())?;
-        Ok(())
+        audit.record_changes(
+            AuditLogRecordType::StocktakeLine,
+            &row.id,
+            before.as_ref(),
+            Some(row),
+        )
     }
 
     pub fn delete(&self, id: &str) -> Result<(), RepositoryError> {
+        let audit = AuditLogRowRepository::new(self.connection);
+        let before = audit.find_before(|| self.find_one_by_id(id))?;
         diesel::delete(stocktake_line_dsl::stocktake_line.filter(stocktake_line_dsl::id.eq(id)))
             .execute(self.connection.lock().connection())?;
-        Ok(())
+        audit.record_changes(AuditLogRecordType::StocktakeLine, id, before.as_ref(), None)
     }
 
     pub fn find_one_by_id(&self, id: &str) -> Result<Option<StocktakeLineRow>, RepositoryError> {
@@ -25,6 +25,11 @@ table! {
         inventory_addition_id -> Nullable<Text>,
         inventory_reduction_id -> Nullable<Text>,
         is_locked -> Bool,
+        is_blind_count -> Bool,
+        variance_threshold_percentage -> Nullable<Double>,
+        count_submitted_datetime -> Nullable<Timestamp>,
+        variance_approved_datetime -> Nullable<Timestamp>,
+        variance_approved_by_user_id -> Nullable<Text>,
     }
 }
 
@@ -37,7 +42,7 @@ pub enum StocktakeStatus {
     Finalised,
 }
 
-#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Eq)]
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
 #[diesel(table_name = stocktake)]
 pub struct StocktakeRow {
     pub id: String,
@@ -54,6 +59,14 @@ pub struct StocktakeRow {
     pub inventory_addition_id: Option<String>,
     pub inventory_reduction_id: Option<String>,
     pub is_locked: bool,
+    /// Snapshot quantities are hidden from counters until the count is submitted, adjustments
+    /// need to be approved before finalising
+    pub is_blind_count: bool,
+    /// Blind count lines with a larger variance (relative to the snapshot) need a second count
+    pub variance_threshold_percentage: Option<f64>,
+    pub count_submitted_datetime: Option<NaiveDateTime>,
+    pub variance_approved_datetime: Option<NaiveDateTime>,
+    pub variance_approved_by_user_id: Option<String>,
 }
 
 impl Default for StocktakeStatus {
@@ -79,6 +92,11 @@ impl Default for StocktakeRow {
             inventory_addition_id: Default::default(),
             inventory_reduction_id: Default::default(),
             is_locked: Default::default(),
+            is_blind_count: Default::default(),
+            variance_threshold_percentage: Default::default(),
+            count_submitted_datetime: Default::default(),
+            variance_approved_datetime: Default::default(),
+            variance_approved_by_user_id: Default::default(),
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::sync::{Mutex, MutexGuard};
 
-use super::{get_connection, DBBackendConnection, DBConnection};
+use super::{get_connection, AuditContext, DBBackendConnection, DBConnection};
 
 use crate::repository_error::RepositoryError;
 
@@ -59,6 +59,7 @@ impl<'a> LockedConnection<'a> {
 
 pub struct StorageConnection {
     raw_connection: Mutex<DBConnection>,
+    audit_context: Option<AuditContext>,
 }
 
 impl StorageConnection {
@@ -109,9 +110,20 @@ impl StorageConnection {
     pub fn new(connection: DBConnection) -> StorageConnection {
         StorageConnection {
             raw_connection: Mutex::new(connection),
+            audit_context: None,
         }
     }
 
+    /// Changes to audited records made through this connection are recorded in the audit log and
+    /// attributed to the given store and user
+    pub fn set_audit_context(&mut self, audit_context: AuditContext) {
+        self.audit_context = Some(audit_context);
+    }
+
+    pub fn audit_context(&self) -> Option<&AuditContext> {
+        self.audit_context.as_ref()
+    }
+
     /// Executes operations in transaction. A new transaction is only started if not already in a
     /// transaction.
     pub fn transaction_sync<T, E, F>(&self, f: F) -> Result<T, TransactionError<E>>
@@ -20,6 +20,8 @@ table! {
         om_program_module -> Bool,
         vaccine_module -> Bool,
         issue_in_foreign_currency -> Bool,
+        consumption_forecast_method -> crate::db_diesel::store_preference_row::ConsumptionForecastMethodMapping,
+        allocation_strategy -> crate::db_diesel::store_preference_row::AllocationStrategyMapping,
     }
 }
 
@@ -36,6 +38,36 @@ pub enum StorePreferenceType {
     StorePreferences,
 }
 
+/// Method used to forecast average monthly consumption (AMC), see service::consumption_forecast
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum ConsumptionForecastMethod {
+    /// Total consumption in the lookback period divided by number of months
+    #[default]
+    SimpleAverage,
+    /// Simple average, excluding days on which the store had no stock
+    StockOutAdjusted,
+    /// Exponentially weighted average of monthly consumption
+    ExponentialSmoothing,
+    /// Recent average adjusted by the seasonal trend observed a year ago
+    SeasonalMovingAverage,
+}
+
+/// Order in which stock lines are allocated to outbound shipment lines
+#[derive(DbEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum AllocationStrategy {
+    /// First expiry first out, stock lines without expiry date last
+    #[default]
+    Fefo,
+    /// First in first out, by the date stock line was received
+    Fifo,
+    /// By location code, to follow the pick path through the store
+    Location,
+    /// Stock lines with pack size matching an item pack variant first
+    PackVariant,
+}
+
 #[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Eq, Default)]
 #[diesel(table_name = store_preference)]
 pub struct StorePreferenceRow {
@@ -48,6 +80,8 @@ pub struct StorePreferenceRow {
     pub om_program_module: bool,
     pub vaccine_module: bool,
     pub issue_in_foreign_currency: bool,
+    pub consumption_forecast_method: ConsumptionForecastMethod,
+    pub allocation_strategy: AllocationStrategy,
 }
 
 pub struct StorePreferenceRowRepository<'a> {
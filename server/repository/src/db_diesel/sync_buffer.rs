@@ -2,10 +2,13 @@ use super::StorageConnection;
 use crate::{
     diesel_macros::{apply_date_time_filter, apply_equal_filter},
     repository_error::RepositoryError,
-    DBType, DatetimeFilter, EqualFilter,
+    DBType, DatetimeFilter, EqualFilter, Pagination,
 };
 use chrono::NaiveDateTime;
-use diesel::{dsl::IntoBoxed, prelude::*};
+use diesel::{
+    dsl::{count_star, max, IntoBoxed},
+    prelude::*,
+};
 use diesel_derive_enum::DbEnum;
 
 #[derive(DbEnum, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -108,6 +111,14 @@ impl<'a> SyncBufferRowRepository<'a> {
             .optional()?;
         Ok(result)
     }
+
+    pub fn delete(&self, record_id: &str) -> Result<(), RepositoryError> {
+        diesel::delete(
+            sync_buffer_dsl::sync_buffer.filter(sync_buffer_dsl::record_id.eq(record_id)),
+        )
+        .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
 }
 
 #[derive(Clone, Default)]
@@ -164,6 +175,14 @@ impl SyncAction {
 
 type SyncBuffer = SyncBufferRow;
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct SyncBufferErrorGroup {
+    pub table_name: String,
+    pub integration_error: String,
+    pub count: i64,
+    pub latest_integration_datetime: Option<NaiveDateTime>,
+}
+
 pub struct SyncBufferRepository<'a> {
     connection: &'a StorageConnection,
 }
@@ -190,6 +209,73 @@ impl<'a> SyncBufferRepository<'a> {
 
         Ok(result)
     }
+
+    /// Most recently received first
+    pub fn query_paginated(
+        &self,
+        pagination: Pagination,
+        filter: Option<SyncBufferFilter>,
+    ) -> Result<Vec<SyncBuffer>, RepositoryError> {
+        let query = create_filtered_query(filter)
+            .order((
+                sync_buffer_dsl::received_datetime.desc(),
+                sync_buffer_dsl::record_id.asc(),
+            ))
+            .offset(pagination.offset as i64)
+            .limit(pagination.limit as i64);
+
+        let result = query.load::<SyncBuffer>(self.connection.lock().connection())?;
+
+        Ok(result)
+    }
+
+    pub fn count(&self, filter: Option<SyncBufferFilter>) -> Result<i64, RepositoryError> {
+        let query = create_filtered_query(filter);
+
+        Ok(query
+            .count()
+            .get_result(self.connection.lock().connection())?)
+    }
+
+    /// Rows that failed integration grouped by table name and integration error, largest groups first
+    pub fn error_groups(&self) -> Result<Vec<SyncBufferErrorGroup>, RepositoryError> {
+        let groups = sync_buffer_dsl::sync_buffer
+            .filter(sync_buffer_dsl::integration_error.is_not_null())
+            .group_by((
+                sync_buffer_dsl::table_name,
+                sync_buffer_dsl::integration_error,
+            ))
+            .select((
+                sync_buffer_dsl::table_name,
+                sync_buffer_dsl::integration_error,
+                count_star(),
+                max(sync_buffer_dsl::integration_datetime),
+            ))
+            .load::<(String, Option<String>, i64, Option<NaiveDateTime>)>(
+                self.connection.lock().connection(),
+            )?;
+
+        let mut result: Vec<SyncBufferErrorGroup> = groups
+            .into_iter()
+            .map(
+                |(table_name, integration_error, count, latest_integration_datetime)| {
+                    SyncBufferErrorGroup {
+                        table_name,
+                        integration_error: integration_error.unwrap_or_default(),
+                        count,
+                        latest_integration_datetime,
+                    }
+                },
+            )
+            .collect();
+        result.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.table_name.cmp(&b.table_name))
+        });
+
+        Ok(result)
+    }
 }
 
 type BoxedSyncBufferQuery = IntoBoxed<'static, sync_buffer::table, DBType>;
@@ -315,4 +401,56 @@ mod test {
             vec![new_a]
         );
     }
+
+    #[actix_rt::test]
+    async fn test_sync_buffer_error_groups() {
+        let failed_row = |record_id: &str, table_name: &str, error: &str| {
+            inline_init(|r: &mut SyncBufferRow| {
+                r.record_id = record_id.to_string();
+                r.table_name = table_name.to_string();
+                r.integration_datetime = Some(Defaults::naive_date_time());
+                r.integration_error = Some(error.to_string());
+            })
+        };
+
+        let (_, connection, _, _) = test_db::setup_all_with_data(
+            "test_sync_buffer_error_groups",
+            MockDataInserts::none(),
+            inline_init(|r: &mut MockData| {
+                r.sync_buffer_rows = vec![
+                    row_a(),
+                    row_c(),
+                    failed_row("1", "item", "error 1"),
+                    failed_row("2", "unit", "error 1"),
+                    failed_row("3", "unit", "error 1"),
+                    failed_row("4", "unit", "error 2"),
+                ];
+            }),
+        )
+        .await;
+        let repo = SyncBufferRepository::new(&connection);
+
+        let groups: Vec<(String, String, i64)> = repo
+            .error_groups()
+            .unwrap()
+            .into_iter()
+            .map(|group| (group.table_name, group.integration_error, group.count))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("unit".to_string(), "error 1".to_string(), 2),
+                ("item".to_string(), "error 1".to_string(), 1),
+                ("unit".to_string(), "error 2".to_string(), 1),
+            ]
+        );
+
+        let filter = SyncBufferFilter::new().integration_error(EqualFilter::is_null(false));
+        assert_eq!(repo.count(Some(filter.clone())).unwrap(), 4);
+
+        SyncBufferRowRepository::new(&connection)
+            .delete("4")
+            .unwrap();
+        assert_eq!(repo.count(Some(filter)).unwrap(), 3);
+    }
 }
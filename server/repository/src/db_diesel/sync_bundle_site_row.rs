@@ -0,0 +1,63 @@
+use super::{
+    sync_bundle_site_row::sync_bundle_site::dsl as sync_bundle_site_dsl, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use diesel::prelude::*;
+
+table! {
+    sync_bundle_site (site_id) {
+        site_id -> Integer,
+        acknowledged_push_cursor -> Nullable<BigInt>,
+        reply_pull_cursor -> Nullable<BigInt>,
+        pending_legacy_records -> Nullable<Text>,
+    }
+}
+
+/// Central server state of a remote site syncing with sync bundles
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = sync_bundle_site)]
+#[diesel(treat_none_as_null = true)]
+pub struct SyncBundleSiteRow {
+    pub site_id: i32,
+    /// End cursor of the last push bundle that was integrated, older bundles are rejected
+    pub acknowledged_push_cursor: Option<i64>,
+    /// Pull cursor of the remote site once it imported the last pull bundle reply
+    pub reply_pull_cursor: Option<i64>,
+    /// JSON of the records pulled from legacy mSupply central server for the site (and
+    /// acknowledged there) that were sent in the last pull bundle reply, they are sent again until
+    /// the remote site imported the reply
+    pub pending_legacy_records: Option<String>,
+}
+
+pub struct SyncBundleSiteRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> SyncBundleSiteRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        SyncBundleSiteRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &SyncBundleSiteRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(sync_bundle_site_dsl::sync_bundle_site)
+            .values(row)
+            .on_conflict(sync_bundle_site_dsl::site_id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_site_id(
+        &self,
+        site_id: i32,
+    ) -> Result<Option<SyncBundleSiteRow>, RepositoryError> {
+        let result = sync_bundle_site_dsl::sync_bundle_site
+            .filter(sync_bundle_site_dsl::site_id.eq(site_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,131 @@
+use super::{
+    temperature_log_aggregate_row::{
+        temperature_log_aggregate, temperature_log_aggregate::dsl as temperature_log_aggregate_dsl,
+    },
+    DBType, StorageConnection,
+};
+use diesel::prelude::*;
+
+use crate::{
+    diesel_macros::{apply_date_time_filter, apply_equal_filter},
+    repository_error::RepositoryError,
+    TemperatureLogAggregateRow,
+};
+
+use crate::{DatetimeFilter, EqualFilter, Pagination};
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct TemperatureLogAggregateFilter {
+    pub id: Option<EqualFilter<String>>,
+    pub store_id: Option<EqualFilter<String>>,
+    pub sensor_id: Option<EqualFilter<String>>,
+    pub location_id: Option<EqualFilter<String>>,
+    pub datetime: Option<DatetimeFilter>,
+}
+
+pub struct TemperatureLogAggregateRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> TemperatureLogAggregateRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        TemperatureLogAggregateRepository { connection }
+    }
+
+    pub fn count(
+        &self,
+        filter: Option<TemperatureLogAggregateFilter>,
+    ) -> Result<i64, RepositoryError> {
+        let query = create_filtered_query(filter);
+        Ok(query
+            .count()
+            .get_result(self.connection.lock().connection())?)
+    }
+
+    pub fn query_by_filter(
+        &self,
+        filter: TemperatureLogAggregateFilter,
+    ) -> Result<Vec<TemperatureLogAggregateRow>, RepositoryError> {
+        self.query(Pagination::all(), Some(filter))
+    }
+
+    /// Sorted by datetime (oldest first)
+    pub fn query(
+        &self,
+        pagination: Pagination,
+        filter: Option<TemperatureLogAggregateFilter>,
+    ) -> Result<Vec<TemperatureLogAggregateRow>, RepositoryError> {
+        let result = create_filtered_query(filter)
+            .order(temperature_log_aggregate_dsl::datetime.asc())
+            .offset(pagination.offset as i64)
+            .limit(pagination.limit as i64)
+            .load::<TemperatureLogAggregateRow>(self.connection.lock().connection())?;
+
+        Ok(result)
+    }
+}
+
+type BoxedTemperatureLogAggregateQuery = temperature_log_aggregate::BoxedQuery<'static, DBType>;
+
+fn create_filtered_query(
+    filter: Option<TemperatureLogAggregateFilter>,
+) -> BoxedTemperatureLogAggregateQuery {
+    let mut query = temperature_log_aggregate::table.into_boxed();
+
+    if let Some(filter) = filter {
+        apply_equal_filter!(query, filter.id, temperature_log_aggregate_dsl::id);
+        apply_equal_filter!(
+            query,
+            filter.store_id,
+            temperature_log_aggregate_dsl::store_id
+        );
+        apply_equal_filter!(
+            query,
+            filter.sensor_id,
+            temperature_log_aggregate_dsl::sensor_id
+        );
+        apply_equal_filter!(
+            query,
+            filter.location_id,
+            temperature_log_aggregate_dsl::location_id
+        );
+        apply_date_time_filter!(
+            query,
+            filter.datetime,
+            temperature_log_aggregate_dsl::datetime
+        );
+    }
+
+    query
+}
+
+impl TemperatureLogAggregateFilter {
+    pub fn new() -> TemperatureLogAggregateFilter {
+        Default::default()
+    }
+
+    pub fn id(mut self, filter: EqualFilter<String>) -> Self {
+        self.id = Some(filter);
+        self
+    }
+
+    pub fn store_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.store_id = Some(filter);
+        self
+    }
+
+    pub fn sensor_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.sensor_id = Some(filter);
+        self
+    }
+
+    pub fn location_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.location_id = Some(filter);
+        self
+    }
+
+    pub fn datetime(mut self, filter: DatetimeFilter) -> Self {
+        self.datetime = Some(filter);
+        self
+    }
+}
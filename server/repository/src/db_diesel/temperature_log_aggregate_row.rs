@@ -0,0 +1,72 @@
+use super::{
+    temperature_log_aggregate_row::temperature_log_aggregate::dsl as temperature_log_aggregate_dsl,
+    StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+table! {
+    temperature_log_aggregate (id) {
+        id -> Text,
+        sensor_id -> Text,
+        location_id -> Nullable<Text>,
+        store_id -> Text,
+        datetime -> Timestamp,
+        minimum_temperature -> Double,
+        maximum_temperature -> Double,
+        mean_temperature -> Double,
+        number_of_logs -> Integer,
+    }
+}
+
+/// Temperature logs of a sensor (and location) during an hour, aggregated by the temperature log
+/// retention. Not synced, aggregates are local to the site that aggregated the logs.
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = temperature_log_aggregate)]
+#[diesel(treat_none_as_null = true)]
+pub struct TemperatureLogAggregateRow {
+    pub id: String,
+    pub sensor_id: String,
+    pub location_id: Option<String>,
+    pub store_id: String,
+    /// Start of the hour
+    pub datetime: NaiveDateTime,
+    pub minimum_temperature: f64,
+    pub maximum_temperature: f64,
+    pub mean_temperature: f64,
+    pub number_of_logs: i32,
+}
+
+pub struct TemperatureLogAggregateRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> TemperatureLogAggregateRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        TemperatureLogAggregateRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &TemperatureLogAggregateRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(temperature_log_aggregate_dsl::temperature_log_aggregate)
+            .values(row)
+            .on_conflict(temperature_log_aggregate_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        id: &str,
+    ) -> Result<Option<TemperatureLogAggregateRow>, RepositoryError> {
+        let result = temperature_log_aggregate_dsl::temperature_log_aggregate
+            .filter(temperature_log_aggregate_dsl::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+}
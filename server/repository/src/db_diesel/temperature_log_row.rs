@@ -90,6 +90,13 @@ impl<'a> TemperatureLogRowRepository<'a> {
             .filter(temperature_log_dsl::id.eq_any(ids))
             .load(self.connection.lock().connection())?)
     }
+
+    pub fn delete_many(&self, ids: &[String]) -> Result<(), RepositoryError> {
+        diesel::delete(temperature_log_dsl::temperature_log)
+            .filter(temperature_log_dsl::id.eq_any(ids))
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
 }
 
 impl Upsert for TemperatureLogRow {
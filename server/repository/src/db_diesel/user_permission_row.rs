@@ -41,12 +41,15 @@ pub enum PermissionType {
     // stocktake
     StocktakeQuery,
     StocktakeMutate,
+    StocktakeVarianceApprove,
     // inventory adjustment
     InventoryAdjustmentMutate,
     // requisition
     RequisitionQuery,
     RequisitionMutate,
     RequisitionSend,
+    RequisitionApprove,
+    RequisitionApprovalChainMutate,
     // outbound shipment
     OutboundShipmentQuery,
     OutboundShipmentMutate,
@@ -64,6 +67,7 @@ pub enum PermissionType {
     PrescriptionMutate,
     // reporting
     Report,
+    ReportScheduleMutate,
     // log
     LogQuery,
     // items
@@ -11,6 +11,8 @@ pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
                 ALTER TYPE activity_log_type ADD VALUE 'VACCINE_COURSE_UPDATED';
                 ALTER TYPE activity_log_type ADD VALUE 'PROGRAM_CREATED';
                 ALTER TYPE activity_log_type ADD VALUE 'PROGRAM_UPDATED';
+                ALTER TYPE activity_log_type ADD VALUE 'TEMPERATURE_BREACH_STOCK_ON_HOLD';
+                ALTER TYPE activity_log_type ADD VALUE 'SYNC_BUFFER_ROW_PURGED';
             "#
         )?;
     }
@@ -0,0 +1,38 @@
+use crate::{migrations::sql, StorageConnection};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    #[cfg(feature = "postgres")]
+    sql!(
+        connection,
+        r#"
+            CREATE TYPE allocation_strategy AS ENUM (
+                'FEFO',
+                'FIFO',
+                'LOCATION',
+                'PACK_VARIANT'
+            );
+        "#
+    )?;
+    const ALLOCATION_STRATEGY_TYPE: &str = if cfg!(feature = "postgres") {
+        "allocation_strategy"
+    } else {
+        "TEXT"
+    };
+
+    sql!(
+        connection,
+        r#"
+            ALTER TABLE store_preference ADD COLUMN allocation_strategy {ALLOCATION_STRATEGY_TYPE} NOT NULL DEFAULT 'FEFO';
+        "#
+    )?;
+
+    // Re-translate store preferences on the next sync
+    sql!(
+        connection,
+        r#"
+            UPDATE sync_buffer SET integration_datetime = NULL WHERE table_name = 'pref';
+        "#
+    )?;
+
+    Ok(())
+}
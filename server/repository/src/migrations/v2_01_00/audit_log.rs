@@ -0,0 +1,54 @@
+use crate::{
+    migrations::{sql, DATETIME},
+    StorageConnection,
+};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    #[cfg(feature = "postgres")]
+    sql!(
+        connection,
+        r#"
+            CREATE TYPE audit_log_record_type AS ENUM (
+                'INVOICE',
+                'INVOICE_LINE',
+                'STOCK_LINE',
+                'STOCKTAKE_LINE',
+                'REQUISITION_LINE'
+            );
+        "#
+    )?;
+
+    const AUDIT_LOG_RECORD_TYPE_ENUM_TYPE: &str = if cfg!(feature = "postgres") {
+        "audit_log_record_type"
+    } else {
+        "TEXT"
+    };
+
+    sql!(
+        connection,
+        r#"
+            CREATE TABLE audit_log (
+                id TEXT NOT NULL PRIMARY KEY,
+                record_type {AUDIT_LOG_RECORD_TYPE_ENUM_TYPE} NOT NULL,
+                record_id TEXT NOT NULL,
+                user_id TEXT,
+                store_id TEXT NOT NULL,
+                datetime {DATETIME} NOT NULL,
+                changes TEXT NOT NULL
+            );
+
+            CREATE INDEX index_audit_log_record_id ON audit_log (record_id);
+        "#
+    )?;
+
+    if cfg!(feature = "postgres") {
+        sql!(
+            connection,
+            r#"
+                ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'audit_log';
+            "#
+        )?;
+    }
+
+    Ok(())
+}
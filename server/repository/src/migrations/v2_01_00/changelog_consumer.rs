@@ -0,0 +1,42 @@
+use crate::{
+    migrations::{sql, DATETIME},
+    StorageConnection,
+};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    const CHANGELOG_TABLE_NAME_TYPE: &str = if cfg!(feature = "postgres") {
+        "changelog_table_name"
+    } else {
+        "TEXT"
+    };
+
+    sql!(
+        connection,
+        r#"
+            CREATE TABLE changelog_consumer (
+                id TEXT NOT NULL PRIMARY KEY,
+                cursor BIGINT NOT NULL,
+                retry_cursor BIGINT,
+                retry_count INTEGER NOT NULL DEFAULT 0,
+                next_retry_datetime {DATETIME},
+                last_error TEXT,
+                last_processed_datetime {DATETIME}
+            );
+
+            CREATE TABLE changelog_consumer_dead_letter (
+                id TEXT NOT NULL PRIMARY KEY,
+                consumer_id TEXT NOT NULL,
+                changelog_cursor BIGINT NOT NULL,
+                table_name {CHANGELOG_TABLE_NAME_TYPE} NOT NULL,
+                record_id TEXT NOT NULL,
+                error TEXT NOT NULL,
+                attempts INTEGER NOT NULL,
+                created_datetime {DATETIME} NOT NULL
+            );
+
+            CREATE INDEX index_changelog_consumer_dead_letter_consumer_id ON changelog_consumer_dead_letter (consumer_id);
+        "#
+    )?;
+
+    Ok(())
+}
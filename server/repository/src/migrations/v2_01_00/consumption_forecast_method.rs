@@ -0,0 +1,39 @@
+use crate::{migrations::sql, StorageConnection};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    #[cfg(feature = "postgres")]
+    sql!(
+        connection,
+        r#"
+            CREATE TYPE consumption_forecast_method AS ENUM (
+                'SIMPLE_AVERAGE',
+                'STOCK_OUT_ADJUSTED',
+                'EXPONENTIAL_SMOOTHING',
+                'SEASONAL_MOVING_AVERAGE'
+            );
+        "#
+    )?;
+    const CONSUMPTION_FORECAST_METHOD_TYPE: &str = if cfg!(feature = "postgres") {
+        "consumption_forecast_method"
+    } else {
+        "TEXT"
+    };
+
+    sql!(
+        connection,
+        r#"
+            ALTER TABLE store_preference ADD COLUMN consumption_forecast_method {CONSUMPTION_FORECAST_METHOD_TYPE} NOT NULL DEFAULT 'SIMPLE_AVERAGE';
+            ALTER TABLE program_requisition_settings ADD COLUMN consumption_forecast_method {CONSUMPTION_FORECAST_METHOD_TYPE};
+        "#
+    )?;
+
+    // Re-translate store preferences and program settings on the next sync
+    sql!(
+        connection,
+        r#"
+            UPDATE sync_buffer SET integration_datetime = NULL WHERE table_name IN ('pref', 'list_master');
+        "#
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::{
+    migrations::{sql, DATE, DOUBLE},
+    StorageConnection,
+};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    #[cfg(feature = "postgres")]
+    sql!(
+        connection,
+        r#"
+            CREATE TYPE cycle_count_class AS ENUM (
+                'A',
+                'B',
+                'C'
+            );
+        "#
+    )?;
+
+    const CLASS_ENUM_TYPE: &str = if cfg!(feature = "postgres") {
+        "cycle_count_class"
+    } else {
+        "TEXT"
+    };
+
+    sql!(
+        connection,
+        r#"
+            CREATE TABLE cycle_count_plan (
+                id TEXT NOT NULL PRIMARY KEY,
+                store_id TEXT NOT NULL REFERENCES store(id),
+                class_a_percentage {DOUBLE} NOT NULL,
+                class_b_percentage {DOUBLE} NOT NULL,
+                class_a_frequency_days INTEGER NOT NULL,
+                class_b_frequency_days INTEGER NOT NULL,
+                class_c_frequency_days INTEGER NOT NULL,
+                max_items_per_count INTEGER,
+                last_generated_date {DATE},
+                is_active BOOLEAN NOT NULL
+            );
+
+            CREATE TABLE cycle_count_item (
+                id TEXT NOT NULL PRIMARY KEY,
+                store_id TEXT NOT NULL REFERENCES store(id),
+                item_link_id TEXT NOT NULL REFERENCES item_link(id),
+                abc_class {CLASS_ENUM_TYPE},
+                consumption_value {DOUBLE} NOT NULL,
+                last_counted_date {DATE}
+            );
+
+            CREATE TABLE cycle_count_variance (
+                id TEXT NOT NULL PRIMARY KEY,
+                store_id TEXT NOT NULL REFERENCES store(id),
+                item_link_id TEXT NOT NULL REFERENCES item_link(id),
+                stocktake_id TEXT NOT NULL,
+                counted_date {DATE} NOT NULL,
+                snapshot_quantity {DOUBLE} NOT NULL,
+                counted_quantity {DOUBLE} NOT NULL
+            );
+
+            CREATE UNIQUE INDEX index_cycle_count_item_store_id_item_link_id ON cycle_count_item (store_id, item_link_id);
+            CREATE INDEX index_cycle_count_variance_store_id_item_link_id ON cycle_count_variance (store_id, item_link_id);
+        "#
+    )?;
+
+    Ok(())
+}
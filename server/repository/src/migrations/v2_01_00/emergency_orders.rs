@@ -0,0 +1,14 @@
+use crate::{migrations::sql, StorageConnection};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    sql!(
+        connection,
+        r#"
+            ALTER TABLE program_requisition_order_type ADD COLUMN is_emergency BOOLEAN NOT NULL DEFAULT FALSE;
+            ALTER TABLE program_requisition_order_type ADD COLUMN max_items_in_emergency_order INTEGER NOT NULL DEFAULT 0;
+            ALTER TABLE requisition ADD COLUMN is_emergency BOOLEAN NOT NULL DEFAULT FALSE;
+        "#,
+    )?;
+
+    Ok(())
+}
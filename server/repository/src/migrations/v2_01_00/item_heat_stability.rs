@@ -0,0 +1,25 @@
+use crate::{
+    migrations::{sql, DOUBLE},
+    StorageConnection,
+};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    sql!(
+        connection,
+        r#"
+            ALTER TABLE item ADD COLUMN heat_stability_threshold {DOUBLE};
+            ALTER TABLE item ADD COLUMN heat_stability_budget_minutes INTEGER;
+        "#,
+    )?;
+
+    if cfg!(feature = "postgres") {
+        sql!(
+            connection,
+            r#"
+                ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'item_oms_fields';
+            "#
+        )?;
+    }
+
+    Ok(())
+}
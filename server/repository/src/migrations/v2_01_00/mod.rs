@@ -3,17 +3,32 @@ use super::{version::Version, Migration};
 use crate::StorageConnection;
 
 mod activity_log;
+mod allocation_strategy;
 mod assets;
+mod audit_log;
+mod changelog_consumer;
+mod consumption_forecast_method;
+mod cycle_count;
 mod decimal_pack_size;
 mod decimal_requisition_quantities;
 mod demographics;
+mod emergency_orders;
 mod item_add_is_vaccine;
+mod item_heat_stability;
 mod ledger;
 mod name_property;
+mod notification;
 mod pg_enums;
 mod program;
 mod property;
+mod report_schedule;
+mod requisition_approval;
+mod requisition_line_lmis;
+mod sensor_type;
+mod stocktake_blind_count;
 mod store_add_name_link_id;
+mod sync_bundle_site;
+mod temperature_log_aggregate;
 mod v6_sync_api_error_code;
 mod vaccine_course;
 
@@ -43,6 +58,21 @@ impl Migration for V2_01_00 {
         vaccine_course::migrate(connection)?;
         program::migrate(connection)?;
         item_add_is_vaccine::migrate(connection)?;
+        consumption_forecast_method::migrate(connection)?;
+        allocation_strategy::migrate(connection)?;
+        report_schedule::migrate(connection)?;
+        changelog_consumer::migrate(connection)?;
+        item_heat_stability::migrate(connection)?;
+        sensor_type::migrate(connection)?;
+        temperature_log_aggregate::migrate(connection)?;
+        audit_log::migrate(connection)?;
+        notification::migrate(connection)?;
+        cycle_count::migrate(connection)?;
+        stocktake_blind_count::migrate(connection)?;
+        requisition_line_lmis::migrate(connection)?;
+        requisition_approval::migrate(connection)?;
+        emergency_orders::migrate(connection)?;
+        sync_bundle_site::migrate(connection)?;
         Ok(())
     }
 }
@@ -0,0 +1,84 @@
+use crate::{
+    migrations::{sql, DATETIME},
+    StorageConnection,
+};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    #[cfg(feature = "postgres")]
+    sql!(
+        connection,
+        r#"
+            CREATE TYPE notification_event_type AS ENUM (
+                'TEMPERATURE_BREACH',
+                'REQUISITION_RECEIVED',
+                'SHIPMENT_ARRIVED',
+                'STOCK_EXPIRING'
+            );
+            CREATE TYPE notification_channel AS ENUM (
+                'EMAIL',
+                'WEBHOOK'
+            );
+            CREATE TYPE notification_outbox_status AS ENUM (
+                'PENDING',
+                'SENT',
+                'FAILED'
+            );
+        "#
+    )?;
+
+    const EVENT_TYPE_ENUM_TYPE: &str = if cfg!(feature = "postgres") {
+        "notification_event_type"
+    } else {
+        "TEXT"
+    };
+    const CHANNEL_ENUM_TYPE: &str = if cfg!(feature = "postgres") {
+        "notification_channel"
+    } else {
+        "TEXT"
+    };
+    const STATUS_ENUM_TYPE: &str = if cfg!(feature = "postgres") {
+        "notification_outbox_status"
+    } else {
+        "TEXT"
+    };
+
+    sql!(
+        connection,
+        r#"
+            CREATE TABLE notification_subscription (
+                id TEXT NOT NULL PRIMARY KEY,
+                store_id TEXT NOT NULL REFERENCES store(id),
+                event_type {EVENT_TYPE_ENUM_TYPE} NOT NULL,
+                channel {CHANNEL_ENUM_TYPE} NOT NULL,
+                recipient TEXT NOT NULL,
+                subject_template TEXT,
+                body_template TEXT,
+                is_active BOOLEAN NOT NULL,
+                created_datetime {DATETIME} NOT NULL
+            );
+
+            CREATE TABLE notification_outbox (
+                id TEXT NOT NULL PRIMARY KEY,
+                subscription_id TEXT NOT NULL,
+                store_id TEXT NOT NULL,
+                event_type {EVENT_TYPE_ENUM_TYPE} NOT NULL,
+                record_id TEXT NOT NULL,
+                channel {CHANNEL_ENUM_TYPE} NOT NULL,
+                recipient TEXT NOT NULL,
+                subject TEXT NOT NULL,
+                body TEXT NOT NULL,
+                status {STATUS_ENUM_TYPE} NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                next_attempt_datetime {DATETIME},
+                last_error TEXT,
+                created_datetime {DATETIME} NOT NULL,
+                sent_datetime {DATETIME}
+            );
+
+            CREATE INDEX index_notification_outbox_subscription_id_record_id ON notification_outbox (subscription_id, record_id);
+            CREATE INDEX index_notification_outbox_status ON notification_outbox (status);
+        "#
+    )?;
+
+    Ok(())
+}
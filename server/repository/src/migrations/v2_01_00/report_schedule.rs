@@ -0,0 +1,47 @@
+use crate::{
+    migrations::{sql, DATETIME},
+    StorageConnection,
+};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    if cfg!(feature = "postgres") {
+        sql!(
+            connection,
+            r#"
+            ALTER TYPE permission_type ADD VALUE 'REPORT_SCHEDULE_MUTATE';
+            "#
+        )?;
+    }
+
+    sql!(
+        connection,
+        r#"
+            CREATE TABLE report_schedule (
+                id TEXT NOT NULL PRIMARY KEY,
+                report_id TEXT NOT NULL REFERENCES report(id),
+                store_id TEXT NOT NULL REFERENCES store(id),
+                user_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                schedule TEXT NOT NULL,
+                arguments TEXT,
+                is_active BOOLEAN NOT NULL,
+                created_datetime {DATETIME} NOT NULL,
+                last_run_datetime {DATETIME},
+                next_run_datetime {DATETIME}
+            );
+
+            CREATE TABLE report_schedule_run (
+                id TEXT NOT NULL PRIMARY KEY,
+                report_schedule_id TEXT NOT NULL REFERENCES report_schedule(id),
+                report_id TEXT NOT NULL,
+                store_id TEXT NOT NULL,
+                run_datetime {DATETIME} NOT NULL,
+                file_id TEXT,
+                file_name TEXT,
+                error TEXT
+            );
+        "#
+    )?;
+
+    Ok(())
+}
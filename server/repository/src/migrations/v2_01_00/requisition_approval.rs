@@ -0,0 +1,53 @@
+use crate::{
+    migrations::{sql, DATETIME},
+    StorageConnection,
+};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    if cfg!(feature = "postgres") {
+        sql!(
+            connection,
+            r#"
+            ALTER TYPE permission_type ADD VALUE 'REQUISITION_APPROVE';
+            ALTER TYPE permission_type ADD VALUE 'REQUISITION_APPROVAL_CHAIN_MUTATE';
+            ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'requisition_approval_level';
+            ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'requisition_approval';
+            "#
+        )?;
+    }
+
+    const APPROVAL_STATUS_TYPE: &str = if cfg!(feature = "postgres") {
+        "approval_status_type"
+    } else {
+        "TEXT"
+    };
+
+    sql!(
+        connection,
+        r#"
+            CREATE TABLE requisition_approval_level (
+                id TEXT NOT NULL PRIMARY KEY,
+                store_id TEXT NOT NULL REFERENCES store(id),
+                program_id TEXT REFERENCES program(id),
+                level INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                approver_user_id TEXT
+            );
+
+            CREATE TABLE requisition_approval (
+                id TEXT NOT NULL PRIMARY KEY,
+                requisition_id TEXT NOT NULL REFERENCES requisition(id),
+                level INTEGER NOT NULL,
+                user_id TEXT NOT NULL,
+                status {APPROVAL_STATUS_TYPE} NOT NULL,
+                comment TEXT,
+                created_datetime {DATETIME} NOT NULL
+            );
+
+            CREATE INDEX index_requisition_approval_level_store_id ON requisition_approval_level (store_id);
+            CREATE INDEX index_requisition_approval_requisition_id ON requisition_approval (requisition_id);
+        "#
+    )?;
+
+    Ok(())
+}
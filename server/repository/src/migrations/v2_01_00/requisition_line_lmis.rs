@@ -0,0 +1,20 @@
+use crate::{
+    migrations::{sql, DOUBLE},
+    StorageConnection,
+};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    sql!(
+        connection,
+        r#"
+            ALTER TABLE requisition_line ADD COLUMN opening_balance {DOUBLE};
+            ALTER TABLE requisition_line ADD COLUMN quantity_received {DOUBLE};
+            ALTER TABLE requisition_line ADD COLUMN quantity_dispensed {DOUBLE};
+            ALTER TABLE requisition_line ADD COLUMN losses_and_adjustments {DOUBLE};
+            ALTER TABLE requisition_line ADD COLUMN closing_balance {DOUBLE};
+            ALTER TABLE requisition_line ADD COLUMN days_out_of_stock INTEGER;
+        "#,
+    )?;
+
+    Ok(())
+}
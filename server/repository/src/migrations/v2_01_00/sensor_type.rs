@@ -0,0 +1,15 @@
+use crate::{migrations::sql, StorageConnection};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    if cfg!(feature = "postgres") {
+        sql!(
+            connection,
+            r#"
+                ALTER TYPE sensor_type ADD VALUE 'LOG_TAG';
+                ALTER TYPE sensor_type ADD VALUE 'CSV';
+            "#
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::{
+    migrations::{sql, DATETIME, DOUBLE},
+    StorageConnection,
+};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    if cfg!(feature = "postgres") {
+        sql!(
+            connection,
+            r#"
+            ALTER TYPE permission_type ADD VALUE 'STOCKTAKE_VARIANCE_APPROVE';
+            "#
+        )?;
+    }
+
+    sql!(
+        connection,
+        r#"
+            ALTER TABLE stocktake ADD COLUMN is_blind_count BOOLEAN NOT NULL DEFAULT FALSE;
+            ALTER TABLE stocktake ADD COLUMN variance_threshold_percentage {DOUBLE};
+            ALTER TABLE stocktake ADD COLUMN count_submitted_datetime {DATETIME};
+            ALTER TABLE stocktake ADD COLUMN variance_approved_datetime {DATETIME};
+            ALTER TABLE stocktake ADD COLUMN variance_approved_by_user_id TEXT;
+            ALTER TABLE stocktake_line ADD COLUMN second_counted_number_of_packs {DOUBLE};
+        "#,
+    )?;
+
+    Ok(())
+}
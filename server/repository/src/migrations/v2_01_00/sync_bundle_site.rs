@@ -0,0 +1,17 @@
+use crate::{migrations::sql, StorageConnection};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    sql!(
+        connection,
+        r#"
+            CREATE TABLE sync_bundle_site (
+                site_id INTEGER NOT NULL PRIMARY KEY,
+                acknowledged_push_cursor BIGINT,
+                reply_pull_cursor BIGINT,
+                pending_legacy_records TEXT
+            );
+        "#
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::{
+    migrations::{sql, DATETIME, DOUBLE},
+    StorageConnection,
+};
+
+pub(crate) fn migrate(connection: &StorageConnection) -> anyhow::Result<()> {
+    sql!(
+        connection,
+        r#"
+            CREATE TABLE temperature_log_aggregate (
+                id TEXT NOT NULL PRIMARY KEY,
+                sensor_id TEXT NOT NULL REFERENCES sensor(id),
+                location_id TEXT REFERENCES location(id),
+                store_id TEXT NOT NULL REFERENCES store(id),
+                datetime {DATETIME} NOT NULL,
+                minimum_temperature {DOUBLE} NOT NULL,
+                maximum_temperature {DOUBLE} NOT NULL,
+                mean_temperature {DOUBLE} NOT NULL,
+                number_of_logs INTEGER NOT NULL
+            );
+
+            CREATE INDEX index_temperature_log_aggregate_sensor_id_datetime
+                ON temperature_log_aggregate (sensor_id, datetime);
+        "#
+    )?;
+
+    Ok(())
+}
@@ -8,6 +8,7 @@ pub fn mock_program_requisition_setting_a() -> ProgramRequisitionSettingsRow {
         name_tag_id: mock_name_tag_1().id,
         program_id: mock_program_a().id,
         period_schedule_id: mock_period_schedule_1().id,
+        consumption_forecast_method: None,
     }
 }
 
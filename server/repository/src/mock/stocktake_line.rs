@@ -20,6 +20,7 @@ pub fn mock_stocktake_line_a() -> StocktakeLineRow {
         sell_price_per_pack: None,
         note: None,
         inventory_adjustment_reason_id: None,
+        second_counted_number_of_packs: None,
     }
 }
 
@@ -41,6 +42,7 @@ pub fn mock_stocktake_line_b() -> StocktakeLineRow {
         sell_price_per_pack: None,
         note: None,
         inventory_adjustment_reason_id: None,
+        second_counted_number_of_packs: None,
     }
 }
 
@@ -6,7 +6,7 @@ use crate::{
     cors::cors_policy, middleware::central_server_only, print::config_print,
     serve_frontend::config_serve_frontend, static_files::config_static_files,
     support::config_support, sync_on_central::config_sync_on_central,
-    upload_fridge_tag::config_upload_fridge_tag,
+    upload_fridge_tag::config_upload_fridge_tag, upload_sync_bundle::config_upload_sync_bundle,
 };
 
 use self::middleware::{compress as compress_middleware, logger as logger_middleware};
@@ -22,8 +22,12 @@ use repository::{get_storage_connection_manager, migrations::migrate};
 
 use service::{
     auth_data::AuthData,
+    cold_chain::temperature_log_retention_driver::TemperatureLogRetentionDriver,
+    cycle_count::cycle_count_driver::CycleCountDriver,
+    notification::notification_driver::NotificationDriver,
     plugin::validation::ValidatedPluginBucket,
     processors::Processors,
+    report::report_schedule_driver::ReportScheduleDriver,
     service_provider::ServiceProvider,
     settings::{is_develop, ServerSettings, Settings},
     sync::{
@@ -48,6 +52,7 @@ mod serve_frontend;
 pub mod static_files;
 pub mod support;
 mod upload_fridge_tag;
+mod upload_sync_bundle;
 pub use self::logging::*;
 
 pub mod print;
@@ -92,6 +97,10 @@ pub async fn start_server(
     info!("Initialising server context..");
     let (processors_trigger, processors) = Processors::init();
     let (file_sync_trigger, file_sync_driver) = FileSyncDriver::init(&settings);
+    let report_schedule_driver = ReportScheduleDriver::init(&settings);
+    let temperature_log_retention_driver = TemperatureLogRetentionDriver::init(&settings);
+    let notification_driver = NotificationDriver::init(&settings);
+    let cycle_count_driver = CycleCountDriver::init();
     let (sync_trigger, synchroniser_driver) = SynchroniserDriver::init(file_sync_trigger.clone()); // Cloning as we want to expose this for stop messages
     let (site_is_initialise_trigger, site_is_initialised_callback) =
         SiteIsInitialisedCallback::init();
@@ -285,6 +294,11 @@ pub async fn start_server(
         force_trigger_sync_on_startup,
     );
     let file_sync_task = file_sync_driver.run(service_provider.clone().into_inner());
+    let report_schedule_task = report_schedule_driver.run(service_provider.clone().into_inner());
+    let temperature_log_retention_task =
+        temperature_log_retention_driver.run(service_provider.clone().into_inner());
+    let notification_task = notification_driver.run(service_provider.clone().into_inner());
+    let cycle_count_task = cycle_count_driver.run(service_provider.clone().into_inner());
 
     let closure_settings = settings.clone();
     let mut http_server = HttpServer::new(move || {
@@ -303,6 +317,7 @@ pub async fn start_server(
             .configure(config_static_files)
             .configure(config_cold_chain)
             .configure(config_upload_fridge_tag)
+            .configure(config_upload_sync_bundle)
             .configure(config_sync_on_central)
             .configure(config_support)
             .configure(config_print)
@@ -334,6 +349,10 @@ pub async fn start_server(
         Some(_) = off_switch.recv() => {},
         _ = synchroniser_task => unreachable!("Synchroniser unexpectedly stopped"),
         _ = file_sync_task => unreachable!("File sync unexpectedly stopped"),
+        _ = report_schedule_task => unreachable!("Report scheduler unexpectedly stopped"),
+        _ = temperature_log_retention_task => unreachable!("Temperature log retention unexpectedly stopped"),
+        _ = notification_task => unreachable!("Notifications unexpectedly stopped"),
+        _ = cycle_count_task => unreachable!("Cycle counting unexpectedly stopped"),
         result = processors_task => unreachable!("Processor terminated ({:?})", result)
     };
 
@@ -12,7 +12,11 @@ use serde::Deserialize;
 
 use service::{
     auth_data::AuthData,
-    sensor::berlinger::{read_sensor, ReadSensor},
+    sensor::{
+        berlinger::BerlingerImporter,
+        csv::{CsvImportConfig, CsvImportConfigOverrides, CsvImporter},
+        import::{import_sensor_file, ReadSensor, SensorFileImporter},
+    },
     service_provider::ServiceProvider,
     settings::Settings,
     static_files::{StaticFileCategory, StaticFileService},
@@ -25,10 +29,24 @@ pub fn config_upload_fridge_tag(cfg: &mut web::ServiceConfig) {
     cfg.service(upload);
 }
 
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+enum SensorFileFormat {
+    #[default]
+    Berlinger,
+    LogTag,
+    Csv,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "kebab-case")]
 struct UrlParams {
     store_id: String,
+    #[serde(default)]
+    format: SensorFileFormat,
+    /// JSON `CsvImportConfigOverrides` for CSV formats, set fields replace the ones of the config of
+    /// the format. The serial has to be set for files that don't include the serial of the sensor
+    csv_config: Option<String>,
 }
 
 #[post("/fridge-tag")]
@@ -69,11 +87,35 @@ fn upload_fridge_tag(
     let file_service = StaticFileService::new(&settings.server.base_dir)?;
 
     let static_file = file_service.move_temp_file(file, &StaticFileCategory::Temporary, None)?;
+    let importer = get_importer(&url_params)?;
+
+    let result = import_sensor_file(
+        &ctx,
+        &url_params.store_id,
+        importer.as_ref(),
+        &static_file.to_path_buf(),
+    )
+    .context("Error while integrating sensor data")?;
+
+    Ok(result)
+}
+
+fn get_importer(url_params: &UrlParams) -> anyhow::Result<Box<dyn SensorFileImporter>> {
+    let overrides = match &url_params.csv_config {
+        Some(csv_config) => serde_json::from_str::<CsvImportConfigOverrides>(csv_config)
+            .context("Invalid CSV import config")?,
+        None => CsvImportConfigOverrides::default(),
+    };
+
+    let importer: Box<dyn SensorFileImporter> = match url_params.format {
+        SensorFileFormat::Berlinger => Box::new(BerlingerImporter),
+        SensorFileFormat::LogTag => Box::new(CsvImporter {
+            config: CsvImportConfig::log_tag().with_overrides(overrides),
+        }),
+        SensorFileFormat::Csv => Box::new(CsvImporter {
+            config: CsvImportConfig::default().with_overrides(overrides),
+        }),
+    };
 
-    ctx.connection
-        .transaction_sync(|con| {
-            read_sensor(con, &url_params.store_id, static_file.to_path_buf())
-                .context("Error while integrating sensor data")
-        })
-        .map_err(|error| error.to_inner_error())
+    Ok(importer)
 }
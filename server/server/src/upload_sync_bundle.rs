@@ -0,0 +1,55 @@
+use std::ops::Deref;
+
+use actix_multipart::form::MultipartForm;
+use actix_web::{
+    post,
+    web::{self, Data},
+    HttpRequest, HttpResponse,
+};
+use serde::Serialize;
+
+use service::{
+    auth_data::AuthData,
+    settings::Settings,
+    static_files::{StaticFileCategory, StaticFileService},
+};
+use util::format_error;
+
+use crate::{authentication::validate_cookie_auth, static_files::UploadForm};
+
+pub fn config_upload_sync_bundle(cfg: &mut web::ServiceConfig) {
+    cfg.service(upload);
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadSyncBundleResponse {
+    /// To be used in importSyncBundle mutation
+    file_id: String,
+}
+
+/// Stores an uploaded sync bundle file, bundle is imported with importSyncBundle mutation
+#[post("/sync-bundle")]
+async fn upload(
+    MultipartForm(UploadForm { file }): MultipartForm<UploadForm>,
+    settings: Data<Settings>,
+    auth_data: Data<AuthData>,
+    request: HttpRequest,
+) -> HttpResponse {
+    if validate_cookie_auth(request.clone(), &auth_data).is_err() {
+        return HttpResponse::InternalServerError().body("You need to be logged in");
+    };
+
+    let result = StaticFileService::new(&settings.server.base_dir)
+        .and_then(|service| service.move_temp_file(file, &StaticFileCategory::Temporary, None));
+
+    match result {
+        Ok(static_file) => HttpResponse::Ok().json(UploadSyncBundleResponse {
+            file_id: static_file.id,
+        }),
+        Err(error) => {
+            log::error!("{}", format_error(&error.deref()));
+            HttpResponse::InternalServerError().body("Error uploading sync bundle")
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use repository::{
+    AuditLog, AuditLogFilter, AuditLogRepository, AuditLogRow, AuditLogSort, PaginationOption,
+    StorageConnectionManager,
+};
+use serde_json::Value;
+
+use super::{get_default_pagination, i64_to_u32, ListError, ListResult};
+
+pub const MAX_LIMIT: u32 = 1000;
+pub const MIN_LIMIT: u32 = 1;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AuditLogFieldChange {
+    pub field: String,
+    pub from: Value,
+    pub to: Value,
+}
+
+pub fn get_audit_logs(
+    connection_manager: &StorageConnectionManager,
+    pagination: Option<PaginationOption>,
+    filter: Option<AuditLogFilter>,
+    sort: Option<AuditLogSort>,
+) -> Result<ListResult<AuditLog>, ListError> {
+    let pagination = get_default_pagination(pagination, MAX_LIMIT, MIN_LIMIT)?;
+    let connection = connection_manager.connection()?;
+    let repository = AuditLogRepository::new(&connection);
+
+    Ok(ListResult {
+        rows: repository.query(pagination, filter.clone(), sort)?,
+        count: i64_to_u32(repository.count(filter)?),
+    })
+}
+
+/// Parses `AuditLogRow::changes`, sorted by field name
+pub fn audit_log_field_changes(row: &AuditLogRow) -> Vec<AuditLogFieldChange> {
+    let Ok(Value::Object(changes)) = serde_json::from_str::<Value>(&row.changes) else {
+        return Vec::new();
+    };
+
+    let mut result: Vec<AuditLogFieldChange> = changes
+        .into_iter()
+        .map(|(field, mut change)| AuditLogFieldChange {
+            field,
+            from: change["from"].take(),
+            to: change["to"].take(),
+        })
+        .collect();
+    result.sort_by(|a, b| a.field.cmp(&b.field));
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_item_a, mock_outbound_shipment_c, mock_stock_line_si_d,
+            mock_stock_line_stocktake_surplus, mock_stocktake_stock_surplus, mock_store_a,
+            mock_store_c, MockData, MockDataInserts,
+        },
+        AuditLogFilter, AuditLogRecordType, EqualFilter, StockLineRow, StorageConnectionManager,
+    };
+    use serde_json::json;
+    use util::inline_init;
+
+    use crate::{
+        invoice::outbound_shipment::update::{
+            UpdateOutboundShipment, UpdateOutboundShipmentStatus,
+        },
+        invoice_line::stock_out_line::{InsertStockOutLine, StockOutType},
+        stock_line::UpdateStockLine,
+        stocktake::{UpdateStocktake, UpdateStocktakeStatus},
+        test_helpers::{
+            setup_all_and_service_provider, setup_all_with_data_and_service_provider,
+            ServiceTestContext,
+        },
+    };
+
+    use super::{audit_log_field_changes, get_audit_logs, AuditLogFieldChange};
+
+    /// All recorded changes of a field of a record
+    fn recorded_changes(
+        connection_manager: &StorageConnectionManager,
+        record_type: AuditLogRecordType,
+        record_id: &str,
+        field: &str,
+    ) -> Vec<AuditLogFieldChange> {
+        let filter = AuditLogFilter::new()
+            .record_type(record_type.equal_to())
+            .record_id(EqualFilter::equal_to(record_id));
+        get_audit_logs(connection_manager, None, Some(filter), None)
+            .unwrap()
+            .rows
+            .iter()
+            .inspect(|log| assert_eq!(log.audit_log_row.user_id, Some("auditor".to_string())))
+            .flat_map(|log| audit_log_field_changes(&log.audit_log_row))
+            .filter(|change| change.field == field)
+            .collect()
+    }
+
+    #[actix_rt::test]
+    async fn stock_line_audit_log() {
+        let ServiceTestContext {
+            service_provider,
+            connection_manager,
+            ..
+        } = setup_all_with_data_and_service_provider(
+            "stock_line_audit_log",
+            MockDataInserts::none().names().stores().units().items(),
+            inline_init(|r: &mut MockData| {
+                r.stock_lines = vec![inline_init(|r: &mut StockLineRow| {
+                    r.id = "audited_stock_line".to_string();
+                    r.item_link_id = mock_item_a().id;
+                    r.store_id = mock_store_a().id;
+                    r.pack_size = 1.0;
+                    r.cost_price_per_pack = 1.0;
+                    r.sell_price_per_pack = 2.0;
+                })]
+            }),
+        )
+        .await;
+
+        let ctx = service_provider
+            .context(mock_store_a().id, "auditor".to_string())
+            .unwrap();
+        let filter = AuditLogFilter::new()
+            .record_type(AuditLogRecordType::StockLine.equal_to())
+            .record_id(EqualFilter::equal_to("audited_stock_line"));
+
+        let update = inline_init(|r: &mut UpdateStockLine| {
+            r.id = "audited_stock_line".to_string();
+            r.sell_price_per_pack = Some(3.0);
+            r.batch = Some("B1".to_string());
+        });
+        service_provider
+            .stock_line_service
+            .update_stock_line(&ctx, update.clone())
+            .unwrap();
+
+        let logs = get_audit_logs(&connection_manager, None, Some(filter.clone()), None).unwrap();
+        assert_eq!(logs.count, 1);
+        let row = &logs.rows[0].audit_log_row;
+        assert_eq!(row.user_id, Some("auditor".to_string()));
+        assert_eq!(row.store_id, mock_store_a().id);
+
+        let changes = audit_log_field_changes(row);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].field, "batch");
+        assert_eq!(changes[0].from, json!(null));
+        assert_eq!(changes[0].to, json!("B1"));
+        assert_eq!(changes[1].field, "sell_price_per_pack");
+        assert_eq!(changes[1].from, json!(2.0));
+        assert_eq!(changes[1].to, json!(3.0));
+
+        // Unchanged records are not logged
+        service_provider
+            .stock_line_service
+            .update_stock_line(&ctx, update)
+            .unwrap();
+        let logs = get_audit_logs(&connection_manager, None, Some(filter), None).unwrap();
+        assert_eq!(logs.count, 1);
+    }
+
+    #[actix_rt::test]
+    async fn outbound_shipment_picking_audit_log() {
+        let ServiceTestContext {
+            service_provider,
+            connection_manager,
+            ..
+        } = setup_all_and_service_provider(
+            "outbound_shipment_picking_audit_log",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let ctx = service_provider
+            .context(mock_store_c().id, "auditor".to_string())
+            .unwrap();
+        let stock_line = mock_stock_line_si_d()[0].clone();
+
+        service_provider
+            .invoice_line_service
+            .insert_stock_out_line(
+                &ctx,
+                inline_init(|r: &mut InsertStockOutLine| {
+                    r.id = "audited_outbound_line".to_string();
+                    r.r#type = StockOutType::OutboundShipment;
+                    r.invoice_id = mock_outbound_shipment_c().id;
+                    r.stock_line_id.clone_from(&stock_line.id);
+                    r.number_of_packs = 2.0;
+                }),
+            )
+            .unwrap();
+        service_provider
+            .invoice_service
+            .update_outbound_shipment(
+                &ctx,
+                inline_init(|r: &mut UpdateOutboundShipment| {
+                    r.id = mock_outbound_shipment_c().id;
+                    r.status = Some(UpdateOutboundShipmentStatus::Picked);
+                }),
+            )
+            .unwrap();
+
+        // Inserted line
+        let changes = recorded_changes(
+            &connection_manager,
+            AuditLogRecordType::InvoiceLine,
+            "audited_outbound_line",
+            "number_of_packs",
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].from, json!(null));
+        assert_eq!(changes[0].to, json!(2.0));
+
+        // Allocation reduces the available stock and picking the total stock
+        let changes = recorded_changes(
+            &connection_manager,
+            AuditLogRecordType::StockLine,
+            &stock_line.id,
+            "available_number_of_packs",
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].from, json!(stock_line.available_number_of_packs));
+        assert_eq!(
+            changes[0].to,
+            json!(stock_line.available_number_of_packs - 2.0)
+        );
+        let changes = recorded_changes(
+            &connection_manager,
+            AuditLogRecordType::StockLine,
+            &stock_line.id,
+            "total_number_of_packs",
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].from, json!(stock_line.total_number_of_packs));
+        assert_eq!(changes[0].to, json!(stock_line.total_number_of_packs - 2.0));
+
+        let changes = recorded_changes(
+            &connection_manager,
+            AuditLogRecordType::Invoice,
+            &mock_outbound_shipment_c().id,
+            "status",
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].from, json!("NEW"));
+        assert_eq!(changes[0].to, json!("PICKED"));
+    }
+
+    #[actix_rt::test]
+    async fn stocktake_finalise_audit_log() {
+        let ServiceTestContext {
+            service_provider,
+            connection_manager,
+            ..
+        } = setup_all_and_service_provider("stocktake_finalise_audit_log", MockDataInserts::all())
+            .await;
+
+        let ctx = service_provider
+            .context(mock_store_a().id, "auditor".to_string())
+            .unwrap();
+        let stocktake = service_provider
+            .stocktake_service
+            .update_stocktake(
+                &ctx,
+                inline_init(|r: &mut UpdateStocktake| {
+                    r.id = mock_stocktake_stock_surplus().id;
+                    r.status = Some(UpdateStocktakeStatus::Finalised);
+                }),
+            )
+            .unwrap();
+
+        // Counted surplus of 10 packs is added to the stock line
+        let stock_line = mock_stock_line_stocktake_surplus();
+        let changes = recorded_changes(
+            &connection_manager,
+            AuditLogRecordType::StockLine,
+            &stock_line.id,
+            "total_number_of_packs",
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].from, json!(stock_line.total_number_of_packs));
+        assert_eq!(
+            changes[0].to,
+            json!(stock_line.total_number_of_packs + 10.0)
+        );
+
+        // Inventory addition line is logged as inserted
+        let inventory_addition_id = stocktake.inventory_addition_id.unwrap();
+        let changes = recorded_changes(
+            &connection_manager,
+            AuditLogRecordType::Invoice,
+            &inventory_addition_id,
+            "id",
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].from, json!(null));
+        assert_eq!(changes[0].to, json!(inventory_addition_id));
+    }
+}
@@ -59,6 +59,7 @@ pub enum Resource {
     // stocktake
     QueryStocktake,
     MutateStocktake,
+    ApproveStocktakeVariance,
     // inventory adjustment
     MutateInventoryAdjustment,
     // requisition
@@ -67,6 +68,8 @@ pub enum Resource {
     RequisitionChart,
     RequisitionStats,
     RequisitionSend,
+    ApproveRequisition,
+    MutateRequisitionApprovalChain,
     // stock take line
     InsertStocktakeLine,
     UpdateStocktakeLine,
@@ -87,6 +90,7 @@ pub enum Resource {
     // reporting
     Report,
     ReportDev,
+    MutateReportSchedule,
     QueryLog,
     // view/edit server setting
     ServerAdmin,
@@ -270,6 +274,13 @@ fn all_permissions() -> HashMap<Resource, PermissionDSL> {
             PermissionDSL::HasPermission(PermissionType::StocktakeMutate),
         ]),
     );
+    map.insert(
+        Resource::ApproveStocktakeVariance,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(PermissionType::StocktakeVarianceApprove),
+        ]),
+    );
     // stock take line
     map.insert(
         Resource::InsertStocktakeLine,
@@ -328,6 +339,20 @@ fn all_permissions() -> HashMap<Resource, PermissionDSL> {
             PermissionDSL::HasPermission(PermissionType::RequisitionSend),
         ]),
     );
+    map.insert(
+        Resource::ApproveRequisition,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(PermissionType::RequisitionApprove),
+        ]),
+    );
+    map.insert(
+        Resource::MutateRequisitionApprovalChain,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(PermissionType::RequisitionApprovalChainMutate),
+        ]),
+    );
     // invoice
     map.insert(
         Resource::QueryInvoice,
@@ -397,6 +422,14 @@ fn all_permissions() -> HashMap<Resource, PermissionDSL> {
             PermissionDSL::HasPermission(PermissionType::Report),
         ]),
     );
+    // report schedules generate reports in the background, i.e. need more than view permission
+    map.insert(
+        Resource::MutateReportSchedule,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(PermissionType::ReportScheduleMutate),
+        ]),
+    );
     // report development
     map.insert(
         Resource::ReportDev,
@@ -0,0 +1,394 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use repository::{
+    ChangelogRow, ChangelogTableName, DatetimeFilter, EqualFilter, RepositoryError, SensorFilter,
+    SensorRowRepository, SensorType, StorageConnection, TemperatureBreachConfigFilter,
+    TemperatureBreachConfigRepository, TemperatureBreachConfigRow, TemperatureBreachFilter,
+    TemperatureBreachRepository, TemperatureBreachRow, TemperatureBreachRowRepository,
+    TemperatureBreachType, TemperatureLogFilter, TemperatureLogRepository, TemperatureLogRow,
+    TemperatureLogRowRepository, TemperatureLogSort, TemperatureLogSortField,
+};
+use util::uuid::uuid;
+
+use crate::{
+    processors::changelog_consumer::{ChangelogConsumer, ChangelogConsumerError},
+    service_provider::{ServiceContext, ServiceProvider},
+    sync::ActiveStoresOnSite,
+};
+
+/// A breach found in the temperature logs of a sensor
+#[derive(Debug, PartialEq)]
+struct DetectedBreach {
+    start_datetime: NaiveDateTime,
+    /// None if the sensor is still breaching at the last log
+    end_datetime: Option<NaiveDateTime>,
+    duration: Duration,
+    temperature_log_ids: Vec<String>,
+}
+
+/// Detects hot/cold consecutive and cumulative breaches from the temperature logs of a sensor
+/// against the active breach configs of the sensor's store, and creates or updates the
+/// matching `TemperatureBreachRow`s. Logs in a breach that are not linked to a breach yet are
+/// linked to it, consecutive breaches take priority.
+///
+/// * Consecutive: temperature is out of range for at least the config duration without a reading
+///   in range. The breach is ongoing (no end datetime) until a reading is back in range.
+/// * Cumulative: total time out of range during a day (UTC) is at least the config duration.
+///
+/// Logs from `from` (and any ongoing breach or out of range period leading up to it) are
+/// re-evaluated, re-running the detection for the same logs doesn't change anything.
+/// Berlinger sensors are skipped, their breaches are calculated by the fridge-tag.
+///
+/// Returns breaches that were created or updated
+pub fn detect_temperature_breaches(
+    connection: &StorageConnection,
+    sensor_id: &str,
+    from: NaiveDateTime,
+) -> Result<Vec<TemperatureBreachRow>, RepositoryError> {
+    let Some(sensor) = SensorRowRepository::new(connection).find_one_by_id(sensor_id)? else {
+        return Ok(Vec::new());
+    };
+    if sensor.r#type == SensorType::Berlinger {
+        return Ok(Vec::new());
+    }
+
+    let mut configs: Vec<TemperatureBreachConfigRow> =
+        TemperatureBreachConfigRepository::new(connection)
+            .query_by_filter(
+                TemperatureBreachConfigFilter::new()
+                    .store_id(EqualFilter::equal_to(&sensor.store_id))
+                    .is_active(true),
+            )?
+            .into_iter()
+            .map(|config| config.temperature_breach_config_row)
+            .filter(|config| config.r#type != TemperatureBreachType::Excursion)
+            .collect();
+    if configs.is_empty() {
+        return Ok(Vec::new());
+    }
+    // Logs are linked to consecutive breaches before cumulative breaches
+    configs.sort_by_key(|config| is_cumulative(&config.r#type));
+
+    let existing_breaches: Vec<TemperatureBreachRow> = TemperatureBreachRepository::new(connection)
+        .query_by_filter(
+            TemperatureBreachFilter::new()
+                .sensor(SensorFilter::new().id(EqualFilter::equal_to(&sensor.id))),
+        )?
+        .into_iter()
+        .map(|breach| breach.temperature_breach_row)
+        .collect();
+
+    let window_start = evaluation_window_start(from, &configs, &existing_breaches);
+    let logs: Vec<TemperatureLogRow> = TemperatureLogRepository::new(connection)
+        .query(
+            repository::Pagination::all(),
+            Some(
+                TemperatureLogFilter::new()
+                    .sensor(SensorFilter::new().id(EqualFilter::equal_to(&sensor.id)))
+                    .datetime(DatetimeFilter::after_or_equal_to(window_start)),
+            ),
+            Some(TemperatureLogSort {
+                key: TemperatureLogSortField::Datetime,
+                desc: Some(false),
+            }),
+        )?
+        .into_iter()
+        .map(|log| log.temperature_log_row)
+        .collect();
+
+    let breach_repo = TemperatureBreachRowRepository::new(connection);
+    let log_repo = TemperatureLogRowRepository::new(connection);
+    let mut matched_breach_ids = HashSet::new();
+    let mut linked_log_ids: HashSet<String> = logs
+        .iter()
+        .filter(|log| log.temperature_breach_id.is_some())
+        .map(|log| log.id.clone())
+        .collect();
+    let mut result = Vec::new();
+
+    for config in configs {
+        let is_breaching = |temperature: f64| match config.r#type {
+            TemperatureBreachType::HotConsecutive | TemperatureBreachType::HotCumulative => {
+                temperature > config.maximum_temperature
+            }
+            _ => temperature < config.minimum_temperature,
+        };
+        let threshold = Duration::milliseconds(config.duration_milliseconds as i64);
+
+        let detected_breaches = if is_cumulative(&config.r#type) {
+            detect_cumulative(&logs, is_breaching, threshold, window_start)
+        } else {
+            detect_consecutive(&logs, is_breaching, threshold)
+        };
+
+        for detected in detected_breaches {
+            let existing = existing_breaches.iter().find(|existing| {
+                existing.r#type == config.r#type
+                    && !matched_breach_ids.contains(&existing.id)
+                    && is_same_breach(&config.r#type, existing, &detected)
+            });
+
+            let breach = match existing {
+                Some(existing) => TemperatureBreachRow {
+                    start_datetime: detected.start_datetime,
+                    end_datetime: detected.end_datetime,
+                    duration_milliseconds: to_milliseconds(detected.duration),
+                    ..existing.clone()
+                },
+                None => TemperatureBreachRow {
+                    id: uuid(),
+                    duration_milliseconds: to_milliseconds(detected.duration),
+                    r#type: config.r#type.clone(),
+                    sensor_id: sensor.id.clone(),
+                    location_id: sensor.location_id.clone(),
+                    store_id: sensor.store_id.clone(),
+                    start_datetime: detected.start_datetime,
+                    end_datetime: detected.end_datetime,
+                    unacknowledged: true,
+                    threshold_minimum: config.minimum_temperature,
+                    threshold_maximum: config.maximum_temperature,
+                    threshold_duration_milliseconds: config.duration_milliseconds,
+                    comment: None,
+                },
+            };
+            matched_breach_ids.insert(breach.id.clone());
+
+            if existing != Some(&breach) {
+                breach_repo.upsert_one(&breach)?;
+                result.push(breach.clone());
+            }
+
+            let log_ids: Vec<String> = detected
+                .temperature_log_ids
+                .into_iter()
+                .filter(|id| !linked_log_ids.contains(id))
+                .collect();
+            if !log_ids.is_empty() {
+                log_repo.update_breach_id(&breach.id, &log_ids)?;
+                linked_log_ids.extend(log_ids);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+fn is_cumulative(r#type: &TemperatureBreachType) -> bool {
+    matches!(
+        r#type,
+        TemperatureBreachType::HotCumulative | TemperatureBreachType::ColdCumulative
+    )
+}
+
+fn start_of_day(date: NaiveDate) -> NaiveDateTime {
+    date.and_time(NaiveTime::MIN)
+}
+
+fn to_milliseconds(duration: Duration) -> i32 {
+    duration.num_milliseconds().clamp(0, i32::MAX as i64) as i32
+}
+
+/// Earliest log datetime that needs to be evaluated when logs from `from` have changed
+fn evaluation_window_start(
+    from: NaiveDateTime,
+    configs: &[TemperatureBreachConfigRow],
+    existing_breaches: &[TemperatureBreachRow],
+) -> NaiveDateTime {
+    // An out of range period that hasn't reached the config duration yet can't have started
+    // more than the duration ago, the margin allows for gaps in the logs
+    let max_duration = configs
+        .iter()
+        .map(|config| Duration::milliseconds(config.duration_milliseconds as i64))
+        .max()
+        .unwrap_or_else(Duration::zero);
+    let mut window_start = start_of_day(from.date()).min(from - max_duration * 2);
+
+    // Breaches that are still ongoing or overlap the window are re-evaluated from their start
+    for breach in existing_breaches {
+        let overlaps = match breach.end_datetime {
+            Some(end_datetime) => end_datetime >= window_start,
+            None => true,
+        };
+        if overlaps && breach.start_datetime < window_start {
+            window_start = breach.start_datetime;
+        }
+    }
+
+    window_start
+}
+
+/// Two breaches are the same if they overlap (consecutive) or are on the same day (cumulative)
+fn is_same_breach(
+    r#type: &TemperatureBreachType,
+    existing: &TemperatureBreachRow,
+    detected: &DetectedBreach,
+) -> bool {
+    if is_cumulative(r#type) {
+        return existing.start_datetime.date() == detected.start_datetime.date();
+    }
+
+    let starts_before_detected_end = match detected.end_datetime {
+        Some(end_datetime) => existing.start_datetime <= end_datetime,
+        None => true,
+    };
+    let ends_after_detected_start = match existing.end_datetime {
+        Some(end_datetime) => end_datetime >= detected.start_datetime,
+        None => true,
+    };
+    starts_before_detected_end && ends_after_detected_start
+}
+
+/// `logs` must be sorted by datetime
+fn detect_consecutive(
+    logs: &[TemperatureLogRow],
+    is_breaching: impl Fn(f64) -> bool,
+    threshold: Duration,
+) -> Vec<DetectedBreach> {
+    let mut result = Vec::new();
+    let mut current: Option<(NaiveDateTime, Vec<String>)> = None;
+
+    for log in logs {
+        if is_breaching(log.temperature) {
+            current
+                .get_or_insert_with(|| (log.datetime, Vec::new()))
+                .1
+                .push(log.id.clone());
+            continue;
+        }
+
+        if let Some((start_datetime, temperature_log_ids)) = current.take() {
+            let duration = log.datetime - start_datetime;
+            if duration >= threshold {
+                result.push(DetectedBreach {
+                    start_datetime,
+                    end_datetime: Some(log.datetime),
+                    duration,
+                    temperature_log_ids,
+                });
+            }
+        }
+    }
+
+    // Still breaching at the last log
+    if let (Some((start_datetime, temperature_log_ids)), Some(last_log)) = (current, logs.last()) {
+        let duration = last_log.datetime - start_datetime;
+        if duration >= threshold {
+            result.push(DetectedBreach {
+                start_datetime,
+                end_datetime: None,
+                duration,
+                temperature_log_ids,
+            });
+        }
+    }
+
+    result
+}
+
+/// `logs` must be sorted by datetime. A log's temperature is assumed to last until the next log.
+/// Days starting before `window_start` are skipped since not all of their logs are included.
+fn detect_cumulative(
+    logs: &[TemperatureLogRow],
+    is_breaching: impl Fn(f64) -> bool,
+    threshold: Duration,
+    window_start: NaiveDateTime,
+) -> Vec<DetectedBreach> {
+    let mut days: BTreeMap<NaiveDate, DetectedBreach> = BTreeMap::new();
+
+    for (index, log) in logs.iter().enumerate() {
+        let day = log.datetime.date();
+        if !is_breaching(log.temperature) || start_of_day(day) < window_start {
+            continue;
+        }
+
+        let next_datetime = logs.get(index + 1).map(|next| next.datetime);
+        let breach = days.entry(day).or_insert_with(|| DetectedBreach {
+            start_datetime: log.datetime,
+            end_datetime: None,
+            duration: Duration::zero(),
+            temperature_log_ids: Vec::new(),
+        });
+        breach.end_datetime = next_datetime;
+        breach.duration = breach.duration
+            + next_datetime
+                .map(|next_datetime| next_datetime - log.datetime)
+                .unwrap_or_else(Duration::zero);
+        breach.temperature_log_ids.push(log.id.clone());
+    }
+
+    days.into_values()
+        .filter(|breach| breach.duration >= threshold)
+        .collect()
+}
+
+/// Runs breach detection for new or updated temperature logs of sensors in stores on this site.
+/// Logs are handled in batches, each sensor is evaluated once from its earliest changed log.
+pub(crate) struct TemperatureBreachDetectionConsumer;
+
+impl ChangelogConsumer for TemperatureBreachDetectionConsumer {
+    fn name(&self) -> &'static str {
+        "temperature_breach_detection"
+    }
+
+    fn table_names(&self) -> Vec<ChangelogTableName> {
+        vec![ChangelogTableName::TemperatureLog]
+    }
+
+    fn handle(
+        &self,
+        _: &ServiceProvider,
+        ctx: &ServiceContext,
+        changelog: &ChangelogRow,
+    ) -> Result<(), ChangelogConsumerError> {
+        let Some(log) = TemperatureLogRowRepository::new(&ctx.connection)
+            .find_one_by_id(&changelog.record_id)?
+        else {
+            // Deleted
+            return Ok(());
+        };
+
+        let active_stores = ActiveStoresOnSite::get(&ctx.connection)
+            .map_err(|error| ChangelogConsumerError::Retryable(error.to_string()))?;
+        if !active_stores.store_ids().contains(&log.store_id) {
+            return Ok(());
+        }
+
+        detect_temperature_breaches(&ctx.connection, &log.sensor_id, log.datetime)?;
+        Ok(())
+    }
+
+    fn handle_batch(
+        &self,
+        _: &ServiceProvider,
+        ctx: &ServiceContext,
+        changelogs: &[ChangelogRow],
+    ) -> Result<bool, ChangelogConsumerError> {
+        let log_ids: Vec<String> = changelogs
+            .iter()
+            .map(|changelog| changelog.record_id.clone())
+            .collect();
+        // Deleted logs are not returned
+        let logs = TemperatureLogRowRepository::new(&ctx.connection).find_many_by_id(&log_ids)?;
+
+        let active_stores = ActiveStoresOnSite::get(&ctx.connection)
+            .map_err(|error| ChangelogConsumerError::Retryable(error.to_string()))?;
+        let store_ids = active_stores.store_ids();
+
+        let mut sensors: BTreeMap<String, NaiveDateTime> = BTreeMap::new();
+        for log in logs {
+            if !store_ids.contains(&log.store_id) {
+                continue;
+            }
+            sensors
+                .entry(log.sensor_id)
+                .and_modify(|from| *from = (*from).min(log.datetime))
+                .or_insert(log.datetime);
+        }
+
+        for (sensor_id, from) in sensors {
+            detect_temperature_breaches(&ctx.connection, &sensor_id, from)?;
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use repository::{
+    location::LocationFilter,
+    location_movement::{LocationMovementFilter, LocationMovementRepository},
+    DatetimeFilter, EqualFilter, Pagination, RepositoryError, StockLine, StockLineFilter,
+    StockLineRepository, StorageConnection, TemperatureLogFilter, TemperatureLogRepository,
+    TemperatureLogRow, TemperatureLogSort, TemperatureLogSortField,
+};
+
+/// A temperature log is assumed to last until the next log in the location, but no longer than
+/// this (sensor could have stopped logging)
+fn max_log_duration() -> Duration {
+    Duration::hours(1)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct HeatExposure {
+    /// Time spent above the heat stability threshold of the item
+    pub exposure_minutes: i32,
+    /// Heat stability budget of the item less the exposure, negative if the budget is exceeded
+    pub remaining_budget_minutes: i32,
+}
+
+/// Current heat exposure of stock lines, see `get_heat_exposures`
+pub fn get_stock_line_heat_exposures(
+    connection: &StorageConnection,
+    stock_line_ids: Vec<String>,
+) -> Result<HashMap<String, HeatExposure>, RepositoryError> {
+    let stock_lines = StockLineRepository::new(connection).query_by_filter(
+        StockLineFilter::new().id(EqualFilter::equal_any(stock_line_ids)),
+        None,
+    )?;
+    get_heat_exposures(connection, &stock_lines, Utc::now().naive_utc())
+}
+
+/// Heat exposure of stock lines, keyed by stock line id. Only stock lines of items with a heat
+/// stability threshold and budget are included.
+///
+/// Exposure is the time the temperature logs of the locations a stock line was in (as per
+/// location movements) were above the threshold, while the stock line was in the location.
+/// Stock lines without location movements have no recorded exposure.
+pub fn get_heat_exposures(
+    connection: &StorageConnection,
+    stock_lines: &[StockLine],
+    now: NaiveDateTime,
+) -> Result<HashMap<String, HeatExposure>, RepositoryError> {
+    let tracked_stock_lines: Vec<(&StockLine, f64, i32)> = stock_lines
+        .iter()
+        .filter_map(|stock_line| {
+            let item = &stock_line.item_row;
+            Some((
+                stock_line,
+                item.heat_stability_threshold?,
+                item.heat_stability_budget_minutes?,
+            ))
+        })
+        .collect();
+    if tracked_stock_lines.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let stock_line_ids = tracked_stock_lines
+        .iter()
+        .map(|(stock_line, _, _)| stock_line.stock_line_row.id.clone())
+        .collect();
+    let movements = LocationMovementRepository::new(connection).query(
+        Pagination::all(),
+        Some(LocationMovementFilter::new().stock_line_id(EqualFilter::equal_any(stock_line_ids))),
+        None,
+    )?;
+
+    // Periods (location id, from, to) a stock line was in a location
+    let mut periods: HashMap<String, Vec<(String, NaiveDateTime, NaiveDateTime)>> = HashMap::new();
+    for movement in movements {
+        let movement = movement.location_movement_row;
+        let (Some(location_id), Some(enter_datetime)) =
+            (movement.location_id, movement.enter_datetime)
+        else {
+            continue;
+        };
+        periods.entry(movement.stock_line_id).or_default().push((
+            location_id,
+            enter_datetime,
+            movement.exit_datetime.unwrap_or(now),
+        ));
+    }
+
+    let logs_by_location = get_logs_by_location(connection, &periods)?;
+
+    let mut result = HashMap::new();
+    for (stock_line, threshold, budget_minutes) in tracked_stock_lines {
+        let stock_line_id = &stock_line.stock_line_row.id;
+        let exposure = periods
+            .get(stock_line_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|(location_id, from, to)| {
+                let logs = logs_by_location.get(location_id)?;
+                Some(exposure_in_period(logs, threshold, *from, *to))
+            })
+            .fold(Duration::zero(), |total, exposure| total + exposure);
+        let exposure_minutes = exposure.num_minutes().clamp(0, i32::MAX as i64) as i32;
+
+        result.insert(
+            stock_line_id.clone(),
+            HeatExposure {
+                exposure_minutes,
+                remaining_budget_minutes: budget_minutes.saturating_sub(exposure_minutes),
+            },
+        );
+    }
+
+    Ok(result)
+}
+
+/// Temperature logs (sorted by datetime) of the locations in `periods`, keyed by location id.
+/// Only logs that can overlap with a period are loaded, i.e. logs of the time windows stock
+/// lines were in the location (merged where they overlap).
+fn get_logs_by_location(
+    connection: &StorageConnection,
+    periods: &HashMap<String, Vec<(String, NaiveDateTime, NaiveDateTime)>>,
+) -> Result<HashMap<String, Vec<TemperatureLogRow>>, RepositoryError> {
+    let mut windows_by_location: HashMap<String, Vec<(NaiveDateTime, NaiveDateTime)>> =
+        HashMap::new();
+    for (location_id, from, to) in periods.values().flatten() {
+        windows_by_location
+            .entry(location_id.clone())
+            .or_default()
+            .push((*from - max_log_duration(), *to));
+    }
+
+    let repository = TemperatureLogRepository::new(connection);
+    let mut logs_by_location: HashMap<String, Vec<TemperatureLogRow>> = HashMap::new();
+    for (location_id, windows) in windows_by_location {
+        let mut logs = Vec::new();
+        for (from, to) in merge_windows(windows) {
+            let window_logs = repository.query(
+                Pagination::all(),
+                Some(
+                    TemperatureLogFilter::new()
+                        .location(LocationFilter::new().id(EqualFilter::equal_to(&location_id)))
+                        .datetime(DatetimeFilter::date_range(from, to)),
+                ),
+                Some(TemperatureLogSort {
+                    key: TemperatureLogSortField::Datetime,
+                    desc: Some(false),
+                }),
+            )?;
+            logs.extend(window_logs.into_iter().map(|log| log.temperature_log_row));
+        }
+        logs_by_location.insert(location_id, logs);
+    }
+
+    Ok(logs_by_location)
+}
+
+/// Sorted, non overlapping windows covering `windows`
+fn merge_windows(
+    mut windows: Vec<(NaiveDateTime, NaiveDateTime)>,
+) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+    windows.sort();
+    let mut merged: Vec<(NaiveDateTime, NaiveDateTime)> = Vec::new();
+    for (from, to) in windows {
+        match merged.last_mut() {
+            Some((_, last_to)) if from <= *last_to => *last_to = (*last_to).max(to),
+            _ => merged.push((from, to)),
+        }
+    }
+    merged
+}
+
+/// `logs` must be sorted by datetime
+fn exposure_in_period(
+    logs: &[TemperatureLogRow],
+    threshold: f64,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+) -> Duration {
+    let mut exposure = Duration::zero();
+
+    for (index, log) in logs.iter().enumerate() {
+        if log.temperature <= threshold {
+            continue;
+        }
+        let max_end = log.datetime + max_log_duration();
+        let log_end = logs
+            .get(index + 1)
+            .map_or(max_end, |next| next.datetime.min(max_end));
+
+        let start = log.datetime.max(from);
+        let end = log_end.min(to);
+        if end > start {
+            exposure = exposure + (end - start);
+        }
+    }
+
+    exposure
+}
@@ -38,6 +38,8 @@ pub fn insert_temperature_log(
                 .map_err(InsertTemperatureLogError::from)
         })
         .map_err(|error| error.to_inner_error())?;
+    // Breach detection
+    ctx.processors_trigger.trigger_changelog_consumers();
     Ok(temperature_log)
 }
 
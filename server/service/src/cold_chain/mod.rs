@@ -1,3 +1,5 @@
+use self::breach_detection::detect_temperature_breaches;
+use self::heat_exposure::{get_stock_line_heat_exposures, HeatExposure};
 use self::insert_temperature_log::{
     insert_temperature_log, InsertTemperatureLog, InsertTemperatureLogError,
 };
@@ -10,22 +12,39 @@ use self::insert_temperature_breach::{
     insert_temperature_breach, InsertTemperatureBreach, InsertTemperatureBreachError,
 };
 use self::query_temperature_breach::{get_temperature_breach, temperature_breaches};
+use self::temperature_breach_stock::{
+    get_temperature_breach_stock, hold_temperature_breach_stock, TemperatureBreachStockError,
+};
+use self::temperature_chart::{
+    get_temperature_chart, TemperatureChart, TemperatureChartError, TemperatureChartInput,
+};
+use self::temperature_log_retention::aggregate_temperature_logs;
 use self::update_temperature_breach::{
     update_temperature_breach, update_temperature_breach_acknowledgement, UpdateTemperatureBreach,
     UpdateTemperatureBreachAcknowledgement, UpdateTemperatureBreachError,
 };
 use super::{ListError, ListResult};
 use crate::{service_provider::ServiceContext, SingleRecordError};
+use chrono::NaiveDateTime;
 use repository::temperature_breach::{
     TemperatureBreach, TemperatureBreachFilter, TemperatureBreachSort,
 };
 use repository::temperature_log::{TemperatureLog, TemperatureLogFilter, TemperatureLogSort};
-use repository::{PaginationOption, StorageConnection};
+use repository::{
+    PaginationOption, RepositoryError, StockLine, StorageConnection, TemperatureBreachRow,
+};
+use std::collections::HashMap;
 
+pub mod breach_detection;
+pub mod heat_exposure;
 pub mod insert_temperature_breach;
 pub mod insert_temperature_log;
 pub mod query_temperature_breach;
 pub mod query_temperature_log;
+pub mod temperature_breach_stock;
+pub mod temperature_chart;
+pub mod temperature_log_retention;
+pub mod temperature_log_retention_driver;
 pub mod update_temperature_breach;
 pub mod update_temperature_log;
 mod validate;
@@ -105,6 +124,64 @@ pub trait ColdChainServiceTrait: Sync + Send {
     ) -> Result<TemperatureBreach, UpdateTemperatureBreachError> {
         update_temperature_breach_acknowledgement(ctx, input)
     }
+
+    /// Detects temperature breaches from the temperature logs of a sensor, see
+    /// `breach_detection::detect_temperature_breaches`
+    fn detect_temperature_breaches(
+        &self,
+        ctx: &ServiceContext,
+        sensor_id: &str,
+        from: NaiveDateTime,
+    ) -> Result<Vec<TemperatureBreachRow>, RepositoryError> {
+        ctx.connection
+            .transaction_sync(|connection| detect_temperature_breaches(connection, sensor_id, from))
+            .map_err(|error| error.to_inner_error())
+    }
+
+    fn get_temperature_breach_stock(
+        &self,
+        ctx: &ServiceContext,
+        temperature_breach_id: &str,
+    ) -> Result<Vec<StockLine>, TemperatureBreachStockError> {
+        get_temperature_breach_stock(ctx, temperature_breach_id)
+    }
+
+    fn hold_temperature_breach_stock(
+        &self,
+        ctx: &ServiceContext,
+        temperature_breach_id: &str,
+    ) -> Result<Vec<StockLine>, TemperatureBreachStockError> {
+        hold_temperature_breach_stock(ctx, temperature_breach_id)
+    }
+
+    /// Heat exposure of stock lines, keyed by stock line id, see
+    /// `heat_exposure::get_heat_exposures`
+    fn get_stock_line_heat_exposures(
+        &self,
+        ctx: &ServiceContext,
+        stock_line_ids: Vec<String>,
+    ) -> Result<HashMap<String, HeatExposure>, RepositoryError> {
+        get_stock_line_heat_exposures(&ctx.connection, stock_line_ids)
+    }
+
+    /// Downsamples temperature logs older than `retention_days` into hourly aggregates, see
+    /// `temperature_log_retention::aggregate_temperature_logs`
+    fn aggregate_temperature_logs(
+        &self,
+        connection: &StorageConnection,
+        retention_days: u32,
+        now: NaiveDateTime,
+    ) -> Result<usize, RepositoryError> {
+        aggregate_temperature_logs(connection, retention_days, now)
+    }
+
+    fn get_temperature_chart(
+        &self,
+        ctx: &ServiceContext,
+        input: TemperatureChartInput,
+    ) -> Result<TemperatureChart, TemperatureChartError> {
+        get_temperature_chart(ctx, input)
+    }
 }
 
 pub struct ColdChainService {}
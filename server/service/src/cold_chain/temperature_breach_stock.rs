@@ -0,0 +1,189 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{NaiveDateTime, Utc};
+use repository::{
+    location_movement::{LocationMovementFilter, LocationMovementRepository},
+    ActivityLogType, EqualFilter, LedgerFilter, LedgerRepository, Pagination, RepositoryError,
+    StockLine, StockLineFilter, StockLineRepository, StockLineRow, StockLineRowRepository,
+    StorageConnection, TemperatureBreachRow,
+};
+
+use super::validate::check_temperature_breach_exists;
+use crate::{activity_log::activity_log_entry, service_provider::ServiceContext};
+
+#[derive(Debug, PartialEq)]
+pub enum TemperatureBreachStockError {
+    TemperatureBreachDoesNotExist,
+    TemperatureBreachDoesNotBelongToCurrentStore,
+    TemperatureBreachHasNoLocation,
+    DatabaseError(RepositoryError),
+}
+
+/// Stock lines still in store that were in the location of the breach at any time between the
+/// start and end (or now, for an ongoing breach) of the breach
+pub fn get_temperature_breach_stock(
+    ctx: &ServiceContext,
+    temperature_breach_id: &str,
+) -> Result<Vec<StockLine>, TemperatureBreachStockError> {
+    let breach = validate(&ctx.connection, &ctx.store_id, temperature_breach_id)?;
+    Ok(stock_in_breach_location(&ctx.connection, &breach)?)
+}
+
+/// Places all stock lines impacted by the breach on hold, returns the impacted stock lines
+pub fn hold_temperature_breach_stock(
+    ctx: &ServiceContext,
+    temperature_breach_id: &str,
+) -> Result<Vec<StockLine>, TemperatureBreachStockError> {
+    let result = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let breach = validate(connection, &ctx.store_id, temperature_breach_id)?;
+            let stock_line_repo = StockLineRowRepository::new(connection);
+
+            for stock_line in stock_in_breach_location(connection, &breach)? {
+                if stock_line.stock_line_row.on_hold {
+                    continue;
+                }
+                stock_line_repo.upsert_one(&StockLineRow {
+                    on_hold: true,
+                    ..stock_line.stock_line_row.clone()
+                })?;
+                activity_log_entry(
+                    ctx,
+                    ActivityLogType::TemperatureBreachStockOnHold,
+                    Some(stock_line.stock_line_row.id),
+                    None,
+                    Some(breach.id.clone()),
+                )?;
+            }
+
+            stock_in_breach_location(connection, &breach).map_err(TemperatureBreachStockError::from)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(result)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    temperature_breach_id: &str,
+) -> Result<TemperatureBreachRow, TemperatureBreachStockError> {
+    use TemperatureBreachStockError::*;
+
+    let breach = check_temperature_breach_exists(temperature_breach_id, connection)?
+        .ok_or(TemperatureBreachDoesNotExist)?;
+    if breach.store_id != store_id {
+        return Err(TemperatureBreachDoesNotBelongToCurrentStore);
+    }
+    if breach.location_id.is_none() {
+        return Err(TemperatureBreachHasNoLocation);
+    }
+
+    Ok(breach)
+}
+
+fn stock_in_breach_location(
+    connection: &StorageConnection,
+    breach: &TemperatureBreachRow,
+) -> Result<Vec<StockLine>, RepositoryError> {
+    let Some(location_id) = &breach.location_id else {
+        return Ok(Vec::new());
+    };
+    let end_datetime = breach
+        .end_datetime
+        .unwrap_or_else(|| Utc::now().naive_utc());
+
+    let movements = LocationMovementRepository::new(connection).query(
+        Pagination::all(),
+        Some(
+            LocationMovementFilter::new()
+                .store_id(EqualFilter::equal_to(&breach.store_id))
+                .location_id(EqualFilter::equal_to(location_id)),
+        ),
+        None,
+    )?;
+
+    let mut moved_stock_line_ids = HashSet::new();
+    let mut stock_line_ids = HashSet::new();
+    for movement in movements {
+        let movement = movement.location_movement_row;
+        let entered_before_end = movement
+            .enter_datetime
+            .map_or(true, |enter_datetime| enter_datetime <= end_datetime);
+        let exited_after_start = movement
+            .exit_datetime
+            .map_or(true, |exit_datetime| exit_datetime >= breach.start_datetime);
+        if entered_before_end && exited_after_start {
+            stock_line_ids.insert(movement.stock_line_id.clone());
+        }
+        moved_stock_line_ids.insert(movement.stock_line_id);
+    }
+
+    // Stock lines without location movements for the location (e.g. from before movements were
+    // recorded) are assumed to have been in the location since they were received
+    let stock_line_repo = StockLineRepository::new(connection);
+    let unmoved_stock_line_ids: Vec<String> = stock_line_repo
+        .query_by_filter(
+            StockLineFilter::new()
+                .store_id(EqualFilter::equal_to(&breach.store_id))
+                .location_id(EqualFilter::equal_to(location_id))
+                .has_packs_in_store(true),
+            None,
+        )?
+        .into_iter()
+        .map(|stock_line| stock_line.stock_line_row.id)
+        .filter(|id| !moved_stock_line_ids.contains(id))
+        .collect();
+
+    if !unmoved_stock_line_ids.is_empty() {
+        // Lines received after the breach are excluded, lines without stock movements are kept
+        let received_datetimes = received_datetimes(connection, unmoved_stock_line_ids.clone())?;
+        stock_line_ids.extend(unmoved_stock_line_ids.into_iter().filter(|id| {
+            received_datetimes
+                .get(id)
+                .map_or(true, |received_datetime| *received_datetime <= end_datetime)
+        }));
+    }
+
+    if stock_line_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    stock_line_repo.query_by_filter(
+        StockLineFilter::new()
+            .id(EqualFilter::equal_any(stock_line_ids.into_iter().collect()))
+            .has_packs_in_store(true),
+        None,
+    )
+}
+
+/// Received datetime (the first stock movement into the stock line) by stock line id
+fn received_datetimes(
+    connection: &StorageConnection,
+    stock_line_ids: Vec<String>,
+) -> Result<HashMap<String, NaiveDateTime>, RepositoryError> {
+    let ledger = LedgerRepository::new(connection).query(
+        Pagination::all(),
+        Some(LedgerFilter::new().stock_line_id(EqualFilter::equal_any(stock_line_ids))),
+        None,
+    )?;
+
+    let mut result: HashMap<String, NaiveDateTime> = HashMap::new();
+    for row in ledger.into_iter().filter(|row| row.quantity > 0.0) {
+        let Some(stock_line_id) = row.stock_line_id else {
+            continue;
+        };
+        result
+            .entry(stock_line_id)
+            .and_modify(|datetime| *datetime = (*datetime).min(row.datetime))
+            .or_insert(row.datetime);
+    }
+    Ok(result)
+}
+
+impl From<RepositoryError> for TemperatureBreachStockError {
+    fn from(error: RepositoryError) -> Self {
+        TemperatureBreachStockError::DatabaseError(error)
+    }
+}
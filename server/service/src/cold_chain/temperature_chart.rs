@@ -0,0 +1,208 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{Duration, NaiveDateTime};
+use repository::{
+    location::LocationFilter, DatetimeFilter, EqualFilter, Pagination, RepositoryError,
+    SensorFilter, TemperatureLogAggregateFilter, TemperatureLogAggregateRepository,
+    TemperatureLogFilter, TemperatureLogRepository,
+};
+
+use crate::service_provider::ServiceContext;
+
+use super::temperature_log_retention::hour_start;
+
+pub const MAX_NUMBER_OF_DATA_POINTS: u32 = 500;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct TemperatureChartInput {
+    pub from_datetime: NaiveDateTime,
+    pub to_datetime: NaiveDateTime,
+    pub number_of_data_points: u32,
+    pub sensor_ids: Option<Vec<String>>,
+    pub location_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TemperatureChartError {
+    ToDatetimeBeforeFromDatetime,
+    NumberOfDataPointsOutOfRange,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TemperaturePoint {
+    /// Start of the interval
+    pub datetime: NaiveDateTime,
+    pub minimum_temperature: f64,
+    pub maximum_temperature: f64,
+    pub mean_temperature: f64,
+    pub number_of_logs: i32,
+    /// Breaches of raw logs in the interval (aggregated logs are never part of a breach)
+    pub temperature_breach_ids: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SensorTemperaturePoints {
+    pub sensor_id: String,
+    /// Sorted by datetime, intervals without logs are omitted
+    pub points: Vec<TemperaturePoint>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TemperatureChart {
+    pub interval_seconds: i64,
+    /// Sorted by sensor id
+    pub sensors: Vec<SensorTemperaturePoints>,
+}
+
+#[derive(Default)]
+struct Bucket {
+    minimum_temperature: Option<f64>,
+    maximum_temperature: Option<f64>,
+    sum: f64,
+    number_of_logs: i32,
+    temperature_breach_ids: BTreeSet<String>,
+}
+
+impl Bucket {
+    fn add(&mut self, minimum: f64, maximum: f64, mean: f64, number_of_logs: i32) {
+        self.minimum_temperature =
+            Some(self.minimum_temperature.map_or(minimum, |m| m.min(minimum)));
+        self.maximum_temperature =
+            Some(self.maximum_temperature.map_or(maximum, |m| m.max(maximum)));
+        self.sum += mean * number_of_logs as f64;
+        self.number_of_logs += number_of_logs;
+    }
+}
+
+/// Temperatures of the store's sensors between `from_datetime` and `to_datetime`, split into
+/// `number_of_data_points` intervals.
+///
+/// Raw logs and hourly aggregates (see `temperature_log_retention`) never overlap, so both are
+/// read: within the retention period points come from raw logs, past it from the aggregates, in
+/// which case intervals shorter than an hour only have a point at the start of each hour.
+pub fn get_temperature_chart(
+    ctx: &ServiceContext,
+    input: TemperatureChartInput,
+) -> Result<TemperatureChart, TemperatureChartError> {
+    let TemperatureChartInput {
+        from_datetime,
+        to_datetime,
+        number_of_data_points,
+        sensor_ids,
+        location_ids,
+    } = input;
+
+    if to_datetime <= from_datetime {
+        return Err(TemperatureChartError::ToDatetimeBeforeFromDatetime);
+    }
+    if number_of_data_points == 0 || number_of_data_points > MAX_NUMBER_OF_DATA_POINTS {
+        return Err(TemperatureChartError::NumberOfDataPointsOutOfRange);
+    }
+
+    let interval = Duration::seconds(
+        ((to_datetime - from_datetime).num_seconds() / number_of_data_points as i64).max(1),
+    );
+    let bucket_start = |datetime: NaiveDateTime| {
+        let index = (datetime - from_datetime).num_seconds() / interval.num_seconds();
+        from_datetime + Duration::seconds(index * interval.num_seconds())
+    };
+
+    // Keyed by sensor id then bucket start
+    let mut buckets: BTreeMap<String, BTreeMap<NaiveDateTime, Bucket>> = BTreeMap::new();
+
+    let mut log_filter = TemperatureLogFilter::new()
+        .store_id(EqualFilter::equal_to(&ctx.store_id))
+        .datetime(DatetimeFilter::date_range(from_datetime, to_datetime));
+    if let Some(sensor_ids) = sensor_ids.clone() {
+        log_filter = log_filter.sensor(SensorFilter::new().id(EqualFilter::equal_any(sensor_ids)));
+    }
+    if let Some(location_ids) = location_ids.clone() {
+        log_filter =
+            log_filter.location(LocationFilter::new().id(EqualFilter::equal_any(location_ids)));
+    }
+    let logs = TemperatureLogRepository::new(&ctx.connection).query(
+        Pagination::all(),
+        Some(log_filter),
+        None,
+    )?;
+    for log in logs {
+        let log = log.temperature_log_row;
+        if log.datetime >= to_datetime {
+            continue;
+        }
+        let bucket = buckets
+            .entry(log.sensor_id)
+            .or_default()
+            .entry(bucket_start(log.datetime))
+            .or_default();
+        bucket.add(log.temperature, log.temperature, log.temperature, 1);
+        if let Some(temperature_breach_id) = log.temperature_breach_id {
+            bucket.temperature_breach_ids.insert(temperature_breach_id);
+        }
+    }
+
+    // Aggregates of the hours that overlap the range
+    let mut aggregate_filter = TemperatureLogAggregateFilter::new()
+        .store_id(EqualFilter::equal_to(&ctx.store_id))
+        .datetime(DatetimeFilter::date_range(
+            hour_start(from_datetime),
+            to_datetime,
+        ));
+    if let Some(sensor_ids) = sensor_ids {
+        aggregate_filter = aggregate_filter.sensor_id(EqualFilter::equal_any(sensor_ids));
+    }
+    if let Some(location_ids) = location_ids {
+        aggregate_filter = aggregate_filter.location_id(EqualFilter::equal_any(location_ids));
+    }
+    let aggregates = TemperatureLogAggregateRepository::new(&ctx.connection)
+        .query_by_filter(aggregate_filter)?;
+    for aggregate in aggregates {
+        if aggregate.datetime >= to_datetime {
+            continue;
+        }
+        // An aggregate is placed in the interval its hour starts in (or the first interval if
+        // the hour started before the range)
+        buckets
+            .entry(aggregate.sensor_id)
+            .or_default()
+            .entry(bucket_start(aggregate.datetime.max(from_datetime)))
+            .or_default()
+            .add(
+                aggregate.minimum_temperature,
+                aggregate.maximum_temperature,
+                aggregate.mean_temperature,
+                aggregate.number_of_logs,
+            );
+    }
+
+    let sensors = buckets
+        .into_iter()
+        .map(|(sensor_id, buckets)| SensorTemperaturePoints {
+            sensor_id,
+            points: buckets
+                .into_iter()
+                .filter(|(_, bucket)| bucket.number_of_logs > 0)
+                .map(|(datetime, bucket)| TemperaturePoint {
+                    datetime,
+                    minimum_temperature: bucket.minimum_temperature.unwrap_or_default(),
+                    maximum_temperature: bucket.maximum_temperature.unwrap_or_default(),
+                    mean_temperature: bucket.sum / bucket.number_of_logs as f64,
+                    number_of_logs: bucket.number_of_logs,
+                    temperature_breach_ids: bucket.temperature_breach_ids.into_iter().collect(),
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(TemperatureChart {
+        interval_seconds: interval.num_seconds(),
+        sensors,
+    })
+}
+
+impl From<RepositoryError> for TemperatureChartError {
+    fn from(error: RepositoryError) -> Self {
+        TemperatureChartError::DatabaseError(error)
+    }
+}
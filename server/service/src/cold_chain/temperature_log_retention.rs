@@ -0,0 +1,260 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{Duration, NaiveDateTime, NaiveTime, Timelike};
+use repository::{
+    ChangelogFilter, ChangelogRepository, ChangelogTableName, DatetimeFilter, EqualFilter, KeyType,
+    Pagination, RepositoryError, SensorFilter, SensorRepository, StorageConnection,
+    TemperatureBreachFilter, TemperatureBreachRepository, TemperatureLogAggregateFilter,
+    TemperatureLogAggregateRepository, TemperatureLogAggregateRow,
+    TemperatureLogAggregateRowRepository, TemperatureLogFilter, TemperatureLogRepository,
+    TemperatureLogRowRepository, TemperatureLogSort, TemperatureLogSortField,
+};
+use util::uuid::uuid;
+
+use crate::{cursor_controller::CursorController, sync::ActiveStoresOnSite};
+
+/// Temperature excursions are calculated from the logs of the last 7 days, logs are always kept
+/// for at least this long
+pub const MINIMUM_RETENTION_DAYS: u32 = 7;
+
+/// Logs are aggregated in batches, to bound memory use and the number of ids in a query
+pub(crate) const TEMPERATURE_LOG_BATCH_SIZE: u32 = 500;
+
+/// Start of the hour of `datetime`
+pub(crate) fn hour_start(datetime: NaiveDateTime) -> NaiveDateTime {
+    datetime
+        .date()
+        .and_time(NaiveTime::from_hms_opt(datetime.hour(), 0, 0).unwrap())
+}
+
+/// Downsamples temperature logs older than `retention_days` (before `now`) into hourly
+/// min/max/mean rows (`TemperatureLogAggregateRow`) and deletes the aggregated logs. Logs that
+/// fall within a breach of the sensor are kept.
+///
+/// Only sensors of stores active on this site are processed, and only logs that have already been
+/// pushed to the central server (changelog cursor below the push cursor) are aggregated, since the
+/// aggregates are not synced.
+///
+/// Logs uploaded late (e.g. from a fridge-tag) for an hour that was already aggregated are merged
+/// into the existing aggregate.
+///
+/// Returns the number of logs aggregated
+pub fn aggregate_temperature_logs(
+    connection: &StorageConnection,
+    retention_days: u32,
+    now: NaiveDateTime,
+) -> Result<usize, RepositoryError> {
+    let retention_days = retention_days.max(MINIMUM_RETENTION_DAYS);
+    let cutoff = hour_start(now - Duration::days(retention_days as i64));
+
+    let active_store_ids = match ActiveStoresOnSite::get(connection) {
+        Ok(active_stores) => active_stores.store_ids(),
+        // Site is not initialised
+        Err(_) => return Ok(0),
+    };
+    let sensors = SensorRepository::new(connection)
+        .query_by_filter(SensorFilter::new().store_id(EqualFilter::equal_any(active_store_ids)))?;
+    let push_cursor = CursorController::new(KeyType::RemoteSyncPushCursor).get(connection)?;
+
+    let mut number_of_logs = 0;
+    for sensor in sensors {
+        let sensor_id = sensor.sensor_row.id;
+        let breach_periods = get_breach_periods(connection, &sensor_id)?;
+        let mut offset = 0;
+        loop {
+            let pagination = Pagination {
+                limit: TEMPERATURE_LOG_BATCH_SIZE,
+                offset,
+            };
+            let (number_of_candidates, number_aggregated) = connection
+                .transaction_sync(|connection| {
+                    aggregate_sensor_temperature_logs(
+                        connection,
+                        &sensor_id,
+                        &breach_periods,
+                        cutoff,
+                        push_cursor,
+                        pagination,
+                    )
+                })
+                .map_err(|error| error.to_inner_error())?;
+            number_of_logs += number_aggregated;
+
+            if number_of_candidates < TEMPERATURE_LOG_BATCH_SIZE as usize {
+                break;
+            }
+            // Aggregated logs are deleted, the ones that were kept are skipped in the next batch
+            offset += (number_of_candidates - number_aggregated) as u32;
+        }
+    }
+
+    Ok(number_of_logs)
+}
+
+#[derive(Default)]
+struct Aggregate {
+    minimum_temperature: Option<f64>,
+    maximum_temperature: Option<f64>,
+    sum: f64,
+    number_of_logs: i32,
+}
+
+impl Aggregate {
+    fn add(&mut self, minimum: f64, maximum: f64, mean: f64, number_of_logs: i32) {
+        self.minimum_temperature =
+            Some(self.minimum_temperature.map_or(minimum, |m| m.min(minimum)));
+        self.maximum_temperature =
+            Some(self.maximum_temperature.map_or(maximum, |m| m.max(maximum)));
+        self.sum += mean * number_of_logs as f64;
+        self.number_of_logs += number_of_logs;
+    }
+}
+
+fn get_breach_periods(
+    connection: &StorageConnection,
+    sensor_id: &str,
+) -> Result<Vec<(NaiveDateTime, NaiveDateTime)>, RepositoryError> {
+    Ok(TemperatureBreachRepository::new(connection)
+        .query_by_filter(
+            TemperatureBreachFilter::new()
+                .sensor(SensorFilter::new().id(EqualFilter::equal_to(sensor_id))),
+        )?
+        .into_iter()
+        .map(|breach| {
+            let breach = breach.temperature_breach_row;
+            (
+                breach.start_datetime,
+                // Ongoing breach
+                breach.end_datetime.unwrap_or(NaiveDateTime::MAX),
+            )
+        })
+        .collect())
+}
+
+/// Aggregates one batch of candidate logs of the sensor, sorted by id so that batches are
+/// stable while aggregated logs are deleted.
+///
+/// Returns the number of candidate logs in the batch and the number of logs aggregated
+fn aggregate_sensor_temperature_logs(
+    connection: &StorageConnection,
+    sensor_id: &str,
+    breach_periods: &[(NaiveDateTime, NaiveDateTime)],
+    cutoff: NaiveDateTime,
+    push_cursor: u64,
+    pagination: Pagination,
+) -> Result<(usize, usize), RepositoryError> {
+    let batch = TemperatureLogRepository::new(connection).query(
+        pagination,
+        Some(
+            TemperatureLogFilter::new()
+                .sensor(SensorFilter::new().id(EqualFilter::equal_to(sensor_id)))
+                .datetime(DatetimeFilter::before_or_equal_to(cutoff))
+                .temperature_breach_id(EqualFilter::is_null(true)),
+        ),
+        Some(TemperatureLogSort {
+            key: TemperatureLogSortField::Id,
+            desc: Some(false),
+        }),
+    )?;
+    let number_of_candidates = batch.len();
+
+    let candidates: Vec<_> = batch
+        .into_iter()
+        .map(|log| log.temperature_log_row)
+        .filter(|log| log.datetime < cutoff)
+        .filter(|log| {
+            !breach_periods
+                .iter()
+                .any(|(start, end)| log.datetime >= *start && log.datetime <= *end)
+        })
+        .collect();
+    if candidates.is_empty() {
+        return Ok((number_of_candidates, 0));
+    }
+
+    // Logs with changes at or after the push cursor haven't been pushed yet, deleting them would
+    // lose them from the central server
+    let candidate_ids: Vec<String> = candidates.iter().map(|log| log.id.clone()).collect();
+    let not_pushed: HashSet<String> = ChangelogRepository::new(connection)
+        .changelogs(
+            push_cursor,
+            candidate_ids.len() as u32,
+            Some(
+                ChangelogFilter::new()
+                    .table_name(ChangelogTableName::TemperatureLog.equal_to())
+                    .record_id(EqualFilter::equal_any(candidate_ids)),
+            ),
+        )?
+        .into_iter()
+        .map(|changelog| changelog.record_id)
+        .collect();
+    let logs: Vec<_> = candidates
+        .into_iter()
+        .filter(|log| !not_pushed.contains(&log.id))
+        .collect();
+    if logs.is_empty() {
+        return Ok((number_of_candidates, 0));
+    }
+
+    // Keyed by (store id, location id, hour)
+    let mut aggregates: BTreeMap<(String, Option<String>, NaiveDateTime), Aggregate> =
+        BTreeMap::new();
+    for log in &logs {
+        aggregates
+            .entry((
+                log.store_id.clone(),
+                log.location_id.clone(),
+                hour_start(log.datetime),
+            ))
+            .or_default()
+            .add(log.temperature, log.temperature, log.temperature, 1);
+    }
+
+    let aggregate_repo = TemperatureLogAggregateRepository::new(connection);
+    let aggregate_row_repo = TemperatureLogAggregateRowRepository::new(connection);
+    for ((store_id, location_id, datetime), mut aggregate) in aggregates {
+        let location_filter = match &location_id {
+            Some(location_id) => EqualFilter::equal_to(location_id),
+            None => EqualFilter::is_null(true),
+        };
+        let existing = aggregate_repo
+            .query_by_filter(
+                TemperatureLogAggregateFilter::new()
+                    .sensor_id(EqualFilter::equal_to(sensor_id))
+                    .store_id(EqualFilter::equal_to(&store_id))
+                    .location_id(location_filter)
+                    .datetime(DatetimeFilter::equal_to(datetime)),
+            )?
+            .pop();
+
+        let id = match existing {
+            Some(existing) => {
+                aggregate.add(
+                    existing.minimum_temperature,
+                    existing.maximum_temperature,
+                    existing.mean_temperature,
+                    existing.number_of_logs,
+                );
+                existing.id
+            }
+            None => uuid(),
+        };
+
+        aggregate_row_repo.upsert_one(&TemperatureLogAggregateRow {
+            id,
+            sensor_id: sensor_id.to_string(),
+            location_id,
+            store_id,
+            datetime,
+            minimum_temperature: aggregate.minimum_temperature.unwrap_or_default(),
+            maximum_temperature: aggregate.maximum_temperature.unwrap_or_default(),
+            mean_temperature: aggregate.sum / aggregate.number_of_logs as f64,
+            number_of_logs: aggregate.number_of_logs,
+        })?;
+    }
+
+    let log_ids: Vec<String> = logs.into_iter().map(|log| log.id).collect();
+    TemperatureLogRowRepository::new(connection).delete_many(&log_ids)?;
+
+    Ok((number_of_candidates, log_ids.len()))
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::time::Duration;
+use util::format_error;
+
+use crate::{service_provider::ServiceProvider, settings::Settings, sync::is_initialised};
+
+const TEMPERATURE_LOG_RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub struct TemperatureLogRetentionDriver {
+    retention_days: Option<u32>,
+}
+
+/// Used to 'drive' temperature log retention, once an hour (only when initialised and when
+/// `cold_chain` settings are configured) temperature logs past the retention period are
+/// downsampled into hourly aggregates
+impl TemperatureLogRetentionDriver {
+    pub fn init(settings: &Settings) -> TemperatureLogRetentionDriver {
+        TemperatureLogRetentionDriver {
+            retention_days: settings
+                .cold_chain
+                .as_ref()
+                .map(|cold_chain| cold_chain.temperature_log_retention_days),
+        }
+    }
+
+    /// TemperatureLogRetentionDriver entry point, this method is meant to be run within main
+    /// `select!` macro
+    pub async fn run(self, service_provider: Arc<ServiceProvider>) {
+        loop {
+            tokio::time::sleep(TEMPERATURE_LOG_RETENTION_INTERVAL).await;
+
+            let Some(retention_days) = self.retention_days else {
+                continue;
+            };
+            // Need to check is_initialised from database on every iteration, since it could have been updated
+            if !is_initialised(&service_provider) {
+                continue;
+            }
+
+            let service_provider = service_provider.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let ctx = service_provider.basic_context()?;
+                service_provider
+                    .cold_chain_service
+                    .aggregate_temperature_logs(
+                        &ctx.connection,
+                        retention_days,
+                        Utc::now().naive_utc(),
+                    )
+            })
+            .await;
+
+            match result {
+                Ok(Ok(number_of_logs)) => {
+                    if number_of_logs > 0 {
+                        log::info!("Aggregated {} temperature log(s)", number_of_logs);
+                    }
+                }
+                Ok(Err(error)) => {
+                    log::error!(
+                        "Error aggregating temperature logs: {}",
+                        format_error(&error)
+                    )
+                }
+                Err(error) => log::error!("Temperature log retention task failed: {}", error),
+            }
+        }
+    }
+}
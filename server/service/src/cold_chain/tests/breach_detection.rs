@@ -0,0 +1,281 @@
+#[cfg(test)]
+mod query {
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+    use repository::{
+        mock::{mock_store_a, MockDataInserts},
+        test_db::setup_all,
+        EqualFilter, KeyType, KeyValueStoreRepository, SensorFilter, SensorRow,
+        SensorRowRepository, SensorType, StorageConnection, TemperatureBreachConfigRow,
+        TemperatureBreachConfigRowRepository, TemperatureBreachFilter, TemperatureBreachRepository,
+        TemperatureBreachType, TemperatureLogFilter, TemperatureLogRepository, TemperatureLogRow,
+        TemperatureLogRowRepository,
+    };
+
+    use crate::{
+        cold_chain::breach_detection::TemperatureBreachDetectionConsumer,
+        processors::changelog_consumer::process_changelog_consumer,
+        service_provider::ServiceProvider,
+    };
+
+    fn datetime(minutes: i64) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap()
+            + Duration::minutes(minutes)
+    }
+
+    fn insert_logs(connection: &StorageConnection, logs: &[(i64, f64)]) {
+        let repo = TemperatureLogRowRepository::new(connection);
+        for (minutes, temperature) in logs {
+            repo.upsert_one(&TemperatureLogRow {
+                id: format!("log_{minutes}"),
+                temperature: *temperature,
+                sensor_id: "breach_sensor".to_string(),
+                store_id: mock_store_a().id,
+                datetime: datetime(*minutes),
+                ..Default::default()
+            })
+            .unwrap();
+        }
+    }
+
+    fn breach_id_for_log(connection: &StorageConnection, minutes: i64) -> Option<String> {
+        TemperatureLogRepository::new(connection)
+            .query_by_filter(
+                TemperatureLogFilter::new().id(EqualFilter::equal_to(&format!("log_{minutes}"))),
+            )
+            .unwrap()
+            .pop()
+            .unwrap()
+            .temperature_log_row
+            .temperature_breach_id
+    }
+
+    #[actix_rt::test]
+    async fn temperature_breach_detection() {
+        let (_, connection, connection_manager, _) = setup_all(
+            "temperature_breach_detection",
+            MockDataInserts::none().names().stores(),
+        )
+        .await;
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let context = service_provider.basic_context().unwrap();
+        let service = &service_provider.cold_chain_service;
+
+        SensorRowRepository::new(&connection)
+            .upsert_one(&SensorRow {
+                id: "breach_sensor".to_string(),
+                serial: "breach_sensor".to_string(),
+                store_id: mock_store_a().id,
+                is_active: true,
+                r#type: SensorType::BlueMaestro,
+                ..Default::default()
+            })
+            .unwrap();
+        let config_repo = TemperatureBreachConfigRowRepository::new(&connection);
+        config_repo
+            .upsert_one(&TemperatureBreachConfigRow {
+                id: "hot_consecutive".to_string(),
+                duration_milliseconds: 60 * 60 * 1000,
+                r#type: TemperatureBreachType::HotConsecutive,
+                description: "Consecutive 60 minutes hotter than 8".to_string(),
+                is_active: true,
+                store_id: mock_store_a().id,
+                minimum_temperature: -273.0,
+                maximum_temperature: 8.0,
+            })
+            .unwrap();
+        config_repo
+            .upsert_one(&TemperatureBreachConfigRow {
+                id: "cold_cumulative".to_string(),
+                duration_milliseconds: 30 * 60 * 1000,
+                r#type: TemperatureBreachType::ColdCumulative,
+                description: "Cumulative 30 minutes colder than 2".to_string(),
+                is_active: true,
+                store_id: mock_store_a().id,
+                minimum_temperature: 2.0,
+                maximum_temperature: 100.0,
+            })
+            .unwrap();
+
+        let breaches = || {
+            TemperatureBreachRepository::new(&connection)
+                .query_by_filter(
+                    TemperatureBreachFilter::new()
+                        .sensor(SensorFilter::new().id(EqualFilter::equal_to("breach_sensor"))),
+                )
+                .unwrap()
+                .into_iter()
+                .map(|breach| breach.temperature_breach_row)
+                .collect::<Vec<_>>()
+        };
+
+        // Hot for 40 minutes, not a breach yet
+        insert_logs(
+            &connection,
+            &[
+                (0, 5.0),
+                (10, 9.0),
+                (20, 9.5),
+                (30, 10.0),
+                (40, 9.0),
+                (50, 8.5),
+            ],
+        );
+        let result = service
+            .detect_temperature_breaches(&context, "breach_sensor", datetime(0))
+            .unwrap();
+        assert_eq!(result, vec![]);
+
+        // Hot for 60 minutes, ongoing breach is opened
+        insert_logs(&connection, &[(60, 9.0), (70, 9.0)]);
+        let result = service
+            .detect_temperature_breaches(&context, "breach_sensor", datetime(60))
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        let hot_breach = result[0].clone();
+        assert_eq!(hot_breach.r#type, TemperatureBreachType::HotConsecutive);
+        assert_eq!(hot_breach.start_datetime, datetime(10));
+        assert_eq!(hot_breach.end_datetime, None);
+        assert_eq!(hot_breach.duration_milliseconds, 60 * 60 * 1000);
+        assert_eq!(hot_breach.threshold_maximum, 8.0);
+        assert!(hot_breach.unacknowledged);
+        assert_eq!(breach_id_for_log(&connection, 0), None);
+        assert_eq!(
+            breach_id_for_log(&connection, 10),
+            Some(hot_breach.id.clone())
+        );
+        assert_eq!(
+            breach_id_for_log(&connection, 70),
+            Some(hot_breach.id.clone())
+        );
+
+        // Back in range, breach is closed
+        insert_logs(&connection, &[(80, 5.0)]);
+        let result = service
+            .detect_temperature_breaches(&context, "breach_sensor", datetime(80))
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, hot_breach.id);
+        assert_eq!(result[0].end_datetime, Some(datetime(80)));
+        assert_eq!(result[0].duration_milliseconds, 70 * 60 * 1000);
+        assert_eq!(breach_id_for_log(&connection, 80), None);
+
+        // Cold for 20 + 10 minutes on the same day
+        insert_logs(
+            &connection,
+            &[(120, 1.0), (130, 1.5), (140, 5.0), (180, 0.0), (190, 5.0)],
+        );
+        let result = service
+            .detect_temperature_breaches(&context, "breach_sensor", datetime(120))
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        let cold_breach = result[0].clone();
+        assert_eq!(cold_breach.r#type, TemperatureBreachType::ColdCumulative);
+        assert_eq!(cold_breach.start_datetime, datetime(120));
+        assert_eq!(cold_breach.end_datetime, Some(datetime(190)));
+        assert_eq!(cold_breach.duration_milliseconds, 30 * 60 * 1000);
+        assert_eq!(
+            breach_id_for_log(&connection, 180),
+            Some(cold_breach.id.clone())
+        );
+        assert_eq!(breach_id_for_log(&connection, 140), None);
+
+        // Re-uploading logs (which removes the breach ids) doesn't create new breaches
+        insert_logs(&connection, &[(0, 5.0), (10, 9.0), (180, 0.0)]);
+        let result = service
+            .detect_temperature_breaches(&context, "breach_sensor", datetime(0))
+            .unwrap();
+        assert_eq!(result, vec![]);
+        assert_eq!(breaches().len(), 2);
+        assert_eq!(
+            breach_id_for_log(&connection, 10),
+            Some(hot_breach.id.clone())
+        );
+        assert_eq!(
+            breach_id_for_log(&connection, 180),
+            Some(cold_breach.id.clone())
+        );
+
+        // Berlinger sensors are skipped
+        SensorRowRepository::new(&connection)
+            .upsert_one(&SensorRow {
+                id: "breach_sensor".to_string(),
+                serial: "breach_sensor".to_string(),
+                store_id: mock_store_a().id,
+                is_active: true,
+                r#type: SensorType::Berlinger,
+                ..Default::default()
+            })
+            .unwrap();
+        insert_logs(&connection, &[(300, 20.0), (400, 20.0)]);
+        let result = service
+            .detect_temperature_breaches(&context, "breach_sensor", datetime(300))
+            .unwrap();
+        assert_eq!(result, vec![]);
+    }
+
+    #[actix_rt::test]
+    async fn temperature_breach_detection_consumer() {
+        let (_, connection, connection_manager, _) = setup_all(
+            "temperature_breach_detection_consumer",
+            MockDataInserts::none().names().stores(),
+        )
+        .await;
+        KeyValueStoreRepository::new(&connection)
+            .set_i32(KeyType::SettingsSyncSiteId, Some(mock_store_a().site_id))
+            .unwrap();
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let ctx = service_provider.basic_context().unwrap();
+
+        SensorRowRepository::new(&connection)
+            .upsert_one(&SensorRow {
+                id: "breach_sensor".to_string(),
+                serial: "breach_sensor".to_string(),
+                store_id: mock_store_a().id,
+                is_active: true,
+                r#type: SensorType::BlueMaestro,
+                ..Default::default()
+            })
+            .unwrap();
+        TemperatureBreachConfigRowRepository::new(&connection)
+            .upsert_one(&TemperatureBreachConfigRow {
+                id: "hot_consecutive".to_string(),
+                duration_milliseconds: 60 * 60 * 1000,
+                r#type: TemperatureBreachType::HotConsecutive,
+                description: "Consecutive 60 minutes hotter than 8".to_string(),
+                is_active: true,
+                store_id: mock_store_a().id,
+                minimum_temperature: -273.0,
+                maximum_temperature: 8.0,
+            })
+            .unwrap();
+
+        // All logs of the sensor are handled in one batch
+        insert_logs(
+            &connection,
+            &[(0, 5.0), (10, 9.0), (40, 9.5), (70, 10.0), (80, 5.0)],
+        );
+        process_changelog_consumer(
+            &service_provider,
+            &ctx,
+            &TemperatureBreachDetectionConsumer,
+            datetime(90),
+        )
+        .unwrap();
+
+        let breaches = TemperatureBreachRepository::new(&connection)
+            .query_by_filter(
+                TemperatureBreachFilter::new()
+                    .sensor(SensorFilter::new().id(EqualFilter::equal_to("breach_sensor"))),
+            )
+            .unwrap();
+        assert_eq!(breaches.len(), 1);
+        let breach = &breaches[0].temperature_breach_row;
+        assert_eq!(breach.start_datetime, datetime(10));
+        assert_eq!(breach.end_datetime, Some(datetime(80)));
+        assert_eq!(breach_id_for_log(&connection, 40), Some(breach.id.clone()));
+        assert_eq!(breach_id_for_log(&connection, 80), None);
+    }
+}
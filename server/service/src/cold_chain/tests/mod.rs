@@ -1,4 +1,10 @@
 #[cfg(test)]
+mod breach_detection;
+#[cfg(test)]
 mod query_temperature_log;
 #[cfg(test)]
-mod query_temperature_breach;
\ No newline at end of file
+mod query_temperature_breach;
+#[cfg(test)]
+mod temperature_breach_stock;
+#[cfg(test)]
+mod temperature_log_retention;
@@ -0,0 +1,246 @@
+#[cfg(test)]
+mod query {
+    use chrono::{NaiveDate, NaiveDateTime};
+    use repository::{
+        activity_log::{ActivityLogFilter, ActivityLogRepository},
+        mock::{
+            mock_item_a, mock_location_1, mock_location_2, mock_name_a, mock_sensor_1,
+            mock_store_a, mock_store_b, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        ActivityLogType, EqualFilter, InvoiceLineRow, InvoiceLineType, InvoiceRow, InvoiceType,
+        LocationMovementRow, LocationMovementRowRepository, StockLine, StockLineRow,
+        StockLineRowRepository, TemperatureBreachRow, TemperatureBreachRowRepository,
+        TemperatureBreachType,
+    };
+    use util::inline_init;
+
+    use crate::{
+        cold_chain::temperature_breach_stock::TemperatureBreachStockError as ServiceError,
+        service_provider::ServiceProvider,
+    };
+
+    fn datetime(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn stock_line(id: &str, location_id: Option<String>) -> StockLineRow {
+        inline_init(|r: &mut StockLineRow| {
+            r.id = id.to_string();
+            r.item_link_id = mock_item_a().id;
+            r.store_id = mock_store_a().id;
+            r.location_id = location_id;
+            r.pack_size = 1.0;
+            r.total_number_of_packs = 10.0;
+            r.available_number_of_packs = 10.0;
+        })
+    }
+
+    fn movement(
+        id: &str,
+        stock_line_id: &str,
+        location_id: String,
+        enter_hour: u32,
+        exit_hour: Option<u32>,
+    ) -> LocationMovementRow {
+        LocationMovementRow {
+            id: id.to_string(),
+            store_id: mock_store_a().id,
+            stock_line_id: stock_line_id.to_string(),
+            location_id: Some(location_id),
+            enter_datetime: Some(datetime(enter_hour)),
+            exit_datetime: exit_hour.map(datetime),
+        }
+    }
+
+    fn inbound(id: &str, delivered_hour: u32) -> InvoiceRow {
+        inline_init(|r: &mut InvoiceRow| {
+            r.id = id.to_string();
+            r.store_id = mock_store_a().id;
+            r.name_link_id = mock_name_a().id;
+            r.r#type = InvoiceType::InboundShipment;
+            r.delivered_datetime = Some(datetime(delivered_hour));
+        })
+    }
+
+    fn inbound_line(invoice_id: &str, stock_line_id: &str) -> InvoiceLineRow {
+        inline_init(|r: &mut InvoiceLineRow| {
+            r.id = format!("{invoice_id}_line");
+            r.invoice_id = invoice_id.to_string();
+            r.item_link_id = mock_item_a().id;
+            r.stock_line_id = Some(stock_line_id.to_string());
+            r.r#type = InvoiceLineType::StockIn;
+            r.number_of_packs = 10.0;
+            r.pack_size = 1.0;
+        })
+    }
+
+    fn breach() -> TemperatureBreachRow {
+        TemperatureBreachRow {
+            id: "stock_breach".to_string(),
+            duration_milliseconds: 2 * 60 * 60 * 1000,
+            r#type: TemperatureBreachType::HotConsecutive,
+            sensor_id: mock_sensor_1().id,
+            location_id: Some(mock_location_1().id),
+            store_id: mock_store_a().id,
+            start_datetime: datetime(10),
+            end_datetime: Some(datetime(12)),
+            unacknowledged: true,
+            threshold_minimum: -273.0,
+            threshold_maximum: 8.0,
+            threshold_duration_milliseconds: 60 * 60 * 1000,
+            comment: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn temperature_breach_stock() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "temperature_breach_stock",
+            MockDataInserts::none()
+                .names()
+                .stores()
+                .units()
+                .items()
+                .currencies()
+                .locations()
+                .sensors(),
+            inline_init(|r: &mut MockData| {
+                r.stock_lines = vec![
+                    stock_line("moved_in_during", Some(mock_location_1().id)),
+                    stock_line("moved_out_before", None),
+                    stock_line("moved_out_during", Some(mock_location_2().id)),
+                    stock_line("moved_in_after", Some(mock_location_1().id)),
+                    stock_line("without_movements", Some(mock_location_1().id)),
+                    stock_line("other_location", Some(mock_location_2().id)),
+                    stock_line("received_before", Some(mock_location_1().id)),
+                    stock_line("received_after", Some(mock_location_1().id)),
+                    StockLineRow {
+                        total_number_of_packs: 0.0,
+                        available_number_of_packs: 0.0,
+                        ..stock_line("no_packs", Some(mock_location_1().id))
+                    },
+                ];
+                r.invoices = vec![inbound("inbound_before", 9), inbound("inbound_after", 13)];
+                r.invoice_lines = vec![
+                    inbound_line("inbound_before", "received_before"),
+                    inbound_line("inbound_after", "received_after"),
+                ];
+                r.temperature_breaches = vec![
+                    breach(),
+                    TemperatureBreachRow {
+                        id: "no_location_breach".to_string(),
+                        location_id: None,
+                        ..breach()
+                    },
+                ];
+            }),
+        )
+        .await;
+
+        let movement_repo = LocationMovementRowRepository::new(&connection);
+        for row in [
+            movement("1", "moved_in_during", mock_location_1().id, 11, None),
+            movement("2", "moved_out_before", mock_location_1().id, 8, Some(9)),
+            movement("3", "moved_out_during", mock_location_1().id, 8, Some(11)),
+            movement("4", "moved_out_during", mock_location_2().id, 11, None),
+            movement("5", "moved_in_after", mock_location_1().id, 13, None),
+        ] {
+            movement_repo.upsert_one(&row).unwrap();
+        }
+
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let mut context = service_provider
+            .context(mock_store_a().id, "".to_string())
+            .unwrap();
+        let service = &service_provider.cold_chain_service;
+
+        // Errors
+        assert_eq!(
+            service.get_temperature_breach_stock(&context, "n/a"),
+            Err(ServiceError::TemperatureBreachDoesNotExist)
+        );
+        assert_eq!(
+            service.get_temperature_breach_stock(&context, "no_location_breach"),
+            Err(ServiceError::TemperatureBreachHasNoLocation)
+        );
+        context.store_id = mock_store_b().id;
+        assert_eq!(
+            service.hold_temperature_breach_stock(&context, "stock_breach"),
+            Err(ServiceError::TemperatureBreachDoesNotBelongToCurrentStore)
+        );
+        context.store_id = mock_store_a().id;
+
+        // Stock in the location during the breach, stock received after the breach and stock
+        // no longer in store are excluded
+        let stock_line_ids = |stock_lines: Vec<StockLine>| {
+            let mut ids: Vec<String> = stock_lines
+                .into_iter()
+                .map(|stock_line| stock_line.stock_line_row.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+        let expected = vec![
+            "moved_in_during".to_string(),
+            "moved_out_during".to_string(),
+            "received_before".to_string(),
+            "without_movements".to_string(),
+        ];
+        let result = service
+            .get_temperature_breach_stock(&context, "stock_breach")
+            .unwrap();
+        assert_eq!(stock_line_ids(result), expected);
+
+        // Ongoing breach includes stock moved in since
+        let mut ongoing_breach = breach();
+        ongoing_breach.end_datetime = None;
+        TemperatureBreachRowRepository::new(&connection)
+            .upsert_one(&ongoing_breach)
+            .unwrap();
+        let result = service
+            .get_temperature_breach_stock(&context, "stock_breach")
+            .unwrap();
+        assert_eq!(stock_line_ids(result).len(), 6);
+        TemperatureBreachRowRepository::new(&connection)
+            .upsert_one(&breach())
+            .unwrap();
+
+        // Hold
+        StockLineRowRepository::new(&connection)
+            .upsert_one(&StockLineRow {
+                on_hold: true,
+                ..stock_line("without_movements", Some(mock_location_1().id))
+            })
+            .unwrap();
+        let result = service
+            .hold_temperature_breach_stock(&context, "stock_breach")
+            .unwrap();
+        assert!(result
+            .iter()
+            .all(|stock_line| stock_line.stock_line_row.on_hold));
+        assert_eq!(stock_line_ids(result), expected);
+        assert!(
+            !StockLineRowRepository::new(&connection)
+                .find_one_by_id("other_location")
+                .unwrap()
+                .unwrap()
+                .on_hold
+        );
+
+        let logs = ActivityLogRepository::new(&connection)
+            .query_by_filter(ActivityLogFilter::new().r#type(EqualFilter {
+                equal_to: Some(ActivityLogType::TemperatureBreachStockOnHold),
+                ..Default::default()
+            }))
+            .unwrap();
+        // Stock already on hold is not logged
+        assert_eq!(logs.len(), 3);
+        assert!(logs
+            .iter()
+            .all(|log| log.activity_log_row.changed_to == Some("stock_breach".to_string())));
+    }
+}
@@ -0,0 +1,282 @@
+#[cfg(test)]
+mod query {
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+    use repository::{
+        mock::{
+            mock_sensor_1, mock_sensor_in_another_store, mock_store_a, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        ChangelogRepository, KeyType, KeyValueStoreRepository, TemperatureBreachRow,
+        TemperatureBreachType, TemperatureLogAggregateFilter, TemperatureLogAggregateRepository,
+        TemperatureLogRow, TemperatureLogRowRepository,
+    };
+    use util::inline_init;
+
+    use crate::{
+        cold_chain::{
+            temperature_chart::{TemperatureChartError, TemperatureChartInput},
+            temperature_log_retention::TEMPERATURE_LOG_BATCH_SIZE,
+        },
+        cursor_controller::CursorController,
+        service_provider::ServiceProvider,
+    };
+
+    fn datetime(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn log(id: &str, datetime: NaiveDateTime, temperature: f64) -> TemperatureLogRow {
+        TemperatureLogRow {
+            id: id.to_string(),
+            temperature,
+            sensor_id: mock_sensor_1().id,
+            location_id: None,
+            store_id: mock_store_a().id,
+            datetime,
+            temperature_breach_id: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn temperature_log_retention() {
+        let now = datetime(0, 0) + Duration::days(40);
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "temperature_log_retention",
+            MockDataInserts::none().names().stores().sensors(),
+            inline_init(|r: &mut MockData| {
+                r.temperature_logs = vec![
+                    log("hour_0_a", datetime(0, 10), 4.0),
+                    log("hour_0_b", datetime(0, 40), 6.0),
+                    // Within the breach
+                    log("in_breach", datetime(1, 10), 10.0),
+                    log("hour_1", datetime(1, 50), 3.0),
+                    // Within the retention period
+                    log("recent", now - Duration::days(1), 5.0),
+                    // Store not active on this site
+                    TemperatureLogRow {
+                        sensor_id: mock_sensor_in_another_store().id,
+                        store_id: mock_sensor_in_another_store().store_id,
+                        ..log("remote_store", datetime(0, 10), 4.0)
+                    },
+                ];
+                r.temperature_breaches = vec![TemperatureBreachRow {
+                    id: "retention_breach".to_string(),
+                    duration_milliseconds: 30 * 60 * 1000,
+                    r#type: TemperatureBreachType::HotConsecutive,
+                    sensor_id: mock_sensor_1().id,
+                    location_id: None,
+                    store_id: mock_store_a().id,
+                    start_datetime: datetime(1, 0),
+                    end_datetime: Some(datetime(1, 30)),
+                    unacknowledged: true,
+                    threshold_minimum: -273.0,
+                    threshold_maximum: 8.0,
+                    threshold_duration_milliseconds: 30 * 60 * 1000,
+                    comment: None,
+                }];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let context = service_provider
+            .context(mock_store_a().id, "".to_string())
+            .unwrap();
+        let service = &service_provider.cold_chain_service;
+        let push_cursor = CursorController::new(KeyType::RemoteSyncPushCursor);
+
+        // Site is not initialised
+        assert_eq!(
+            service.aggregate_temperature_logs(&connection, 30, now),
+            Ok(0)
+        );
+        KeyValueStoreRepository::new(&connection)
+            .set_i32(KeyType::SettingsSyncSiteId, Some(mock_store_a().site_id))
+            .unwrap();
+
+        // Logs not yet pushed are kept
+        assert_eq!(
+            service.aggregate_temperature_logs(&connection, 30, now),
+            Ok(0)
+        );
+        let latest_cursor = ChangelogRepository::new(&connection)
+            .latest_cursor()
+            .unwrap();
+        push_cursor.update(&connection, latest_cursor + 1).unwrap();
+
+        // Retention is at least 7 days
+        assert_eq!(
+            service.aggregate_temperature_logs(&connection, 1, datetime(0, 0) + Duration::days(7)),
+            Ok(0)
+        );
+
+        assert_eq!(
+            service.aggregate_temperature_logs(&connection, 30, now),
+            Ok(3)
+        );
+        let log_repo = TemperatureLogRowRepository::new(&connection);
+        assert!(log_repo.find_one_by_id("hour_0_a").unwrap().is_none());
+        assert!(log_repo.find_one_by_id("in_breach").unwrap().is_some());
+        assert!(log_repo.find_one_by_id("recent").unwrap().is_some());
+        assert!(log_repo.find_one_by_id("remote_store").unwrap().is_some());
+
+        let aggregates = TemperatureLogAggregateRepository::new(&connection)
+            .query_by_filter(TemperatureLogAggregateFilter::new())
+            .unwrap();
+        assert_eq!(aggregates.len(), 2);
+        assert_eq!(aggregates[0].datetime, datetime(0, 0));
+        assert_eq!(aggregates[0].number_of_logs, 2);
+        assert_eq!(aggregates[0].mean_temperature, 5.0);
+        assert_eq!(aggregates[1].datetime, datetime(1, 0));
+        assert_eq!(aggregates[1].number_of_logs, 1);
+
+        // Late logs are merged into the existing aggregate
+        log_repo
+            .upsert_one(&log("late", datetime(0, 20), 8.0))
+            .unwrap();
+        assert_eq!(
+            service.aggregate_temperature_logs(&connection, 30, now),
+            Ok(0)
+        );
+        let latest_cursor = ChangelogRepository::new(&connection)
+            .latest_cursor()
+            .unwrap();
+        push_cursor.update(&connection, latest_cursor + 1).unwrap();
+        assert_eq!(
+            service.aggregate_temperature_logs(&connection, 30, now),
+            Ok(1)
+        );
+        let merged = TemperatureLogAggregateRepository::new(&connection)
+            .query_by_filter(TemperatureLogAggregateFilter::new())
+            .unwrap();
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].id, aggregates[0].id);
+        assert_eq!(merged[0].number_of_logs, 3);
+        assert_eq!(merged[0].minimum_temperature, 4.0);
+        assert_eq!(merged[0].maximum_temperature, 8.0);
+        assert_eq!(merged[0].mean_temperature, 6.0);
+
+        // Chart combines raw logs and aggregates
+        let input = TemperatureChartInput {
+            from_datetime: datetime(0, 0),
+            to_datetime: datetime(2, 0),
+            number_of_data_points: 2,
+            sensor_ids: None,
+            location_ids: None,
+        };
+        let chart = service
+            .get_temperature_chart(&context, input.clone())
+            .unwrap();
+        assert_eq!(chart.interval_seconds, 60 * 60);
+        assert_eq!(chart.sensors.len(), 1);
+        let points = &chart.sensors[0].points;
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].datetime, datetime(0, 0));
+        assert_eq!(points[0].number_of_logs, 3);
+        assert_eq!(points[1].datetime, datetime(1, 0));
+        assert_eq!(points[1].number_of_logs, 2);
+        assert_eq!(points[1].minimum_temperature, 3.0);
+        assert_eq!(points[1].maximum_temperature, 10.0);
+        assert_eq!(points[1].mean_temperature, 6.5);
+
+        // Errors
+        assert_eq!(
+            service.get_temperature_chart(
+                &context,
+                TemperatureChartInput {
+                    to_datetime: datetime(0, 0),
+                    ..input.clone()
+                }
+            ),
+            Err(TemperatureChartError::ToDatetimeBeforeFromDatetime)
+        );
+        assert_eq!(
+            service.get_temperature_chart(
+                &context,
+                TemperatureChartInput {
+                    number_of_data_points: 0,
+                    ..input
+                }
+            ),
+            Err(TemperatureChartError::NumberOfDataPointsOutOfRange)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn temperature_log_retention_batches() {
+        let now = datetime(0, 0) + Duration::days(40);
+        let number_of_logs = TEMPERATURE_LOG_BATCH_SIZE as i64 * 2 + 10;
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "temperature_log_retention_batches",
+            MockDataInserts::none().names().stores().sensors(),
+            inline_init(|r: &mut MockData| {
+                // One log a minute, ids sort in datetime order
+                r.temperature_logs = (0..number_of_logs)
+                    .map(|minute| {
+                        log(
+                            &format!("log_{:04}", minute),
+                            datetime(0, 0) + Duration::minutes(minute),
+                            5.0,
+                        )
+                    })
+                    .collect();
+                // Kept logs span more than one batch
+                r.temperature_breaches = vec![TemperatureBreachRow {
+                    id: "retention_breach".to_string(),
+                    duration_milliseconds: 600 * 60 * 1000,
+                    r#type: TemperatureBreachType::HotConsecutive,
+                    sensor_id: mock_sensor_1().id,
+                    location_id: None,
+                    store_id: mock_store_a().id,
+                    start_datetime: datetime(0, 0) + Duration::minutes(100),
+                    end_datetime: Some(datetime(0, 0) + Duration::minutes(700)),
+                    unacknowledged: true,
+                    threshold_minimum: -273.0,
+                    threshold_maximum: 8.0,
+                    threshold_duration_milliseconds: 30 * 60 * 1000,
+                    comment: None,
+                }];
+            }),
+        )
+        .await;
+
+        KeyValueStoreRepository::new(&connection)
+            .set_i32(KeyType::SettingsSyncSiteId, Some(mock_store_a().site_id))
+            .unwrap();
+        let latest_cursor = ChangelogRepository::new(&connection)
+            .latest_cursor()
+            .unwrap();
+        CursorController::new(KeyType::RemoteSyncPushCursor)
+            .update(&connection, latest_cursor + 1)
+            .unwrap();
+
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        assert_eq!(
+            service_provider
+                .cold_chain_service
+                .aggregate_temperature_logs(&connection, 30, now),
+            Ok(number_of_logs as usize - 601)
+        );
+
+        let log_repo = TemperatureLogRowRepository::new(&connection);
+        assert!(log_repo.find_one_by_id("log_0099").unwrap().is_none());
+        assert!(log_repo.find_one_by_id("log_0100").unwrap().is_some());
+        assert!(log_repo.find_one_by_id("log_0700").unwrap().is_some());
+        assert!(log_repo.find_one_by_id("log_0701").unwrap().is_none());
+        let last_log_id = format!("log_{:04}", number_of_logs - 1);
+        assert!(log_repo.find_one_by_id(&last_log_id).unwrap().is_none());
+
+        let aggregated: i32 = TemperatureLogAggregateRepository::new(&connection)
+            .query_by_filter(TemperatureLogAggregateFilter::new())
+            .unwrap()
+            .iter()
+            .map(|aggregate| aggregate.number_of_logs)
+            .sum();
+        assert_eq!(aggregated as i64, number_of_logs - 601);
+    }
+}
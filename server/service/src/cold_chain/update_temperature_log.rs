@@ -40,6 +40,8 @@ pub fn update_temperature_log(
                 .map_err(UpdateTemperatureLogError::from)
         })
         .map_err(|error| error.to_inner_error())?;
+    // Breach detection
+    ctx.processors_trigger.trigger_changelog_consumers();
     Ok(temperature_log)
 }
 
@@ -0,0 +1,44 @@
+use super::{ConsumptionForecaster, ConsumptionHistory};
+
+/// Weight of the most recent month, the remainder is carried over from previous months
+pub const DEFAULT_SMOOTHING_FACTOR: f64 = 0.3;
+/// Smoothing needs a few months of history to settle, regardless of the lookback period
+pub const MINIMUM_SMOOTHING_MONTHS: u32 = 6;
+
+/// Exponentially weighted average of monthly consumption, recent months weigh more but a
+/// single unusual month doesn't swing the forecast as much as with a simple average
+pub struct ExponentialSmoothing {
+    pub smoothing_factor: f64,
+}
+
+impl Default for ExponentialSmoothing {
+    fn default() -> Self {
+        Self {
+            smoothing_factor: DEFAULT_SMOOTHING_FACTOR,
+        }
+    }
+}
+
+impl ConsumptionForecaster for ExponentialSmoothing {
+    fn history_periods(&self, lookback_months: u32) -> u32 {
+        lookback_months.max(MINIMUM_SMOOTHING_MONTHS)
+    }
+
+    fn average_monthly_consumption(
+        &self,
+        history: &ConsumptionHistory,
+        lookback_months: u32,
+    ) -> f64 {
+        let periods = history.periods(0, self.history_periods(lookback_months));
+
+        // Periods are most recent first, smoothing goes from oldest to most recent
+        let mut oldest_first = periods.iter().rev();
+        let Some(oldest) = oldest_first.next() else {
+            return 0.0;
+        };
+
+        oldest_first.fold(oldest.consumption, |level, period| {
+            self.smoothing_factor * period.consumption + (1.0 - self.smoothing_factor) * level
+        })
+    }
+}
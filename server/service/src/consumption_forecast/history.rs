@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use repository::{
+    ConsumptionFilter, ConsumptionRepository, DateFilter, EqualFilter, RepositoryError,
+    StorageConnection,
+};
+use util::{constants::NUMBER_OF_DAYS_IN_A_MONTH, date_with_days_offset};
+
+use crate::stock_out::{get_daily_stock_on_hand, is_out_of_stock};
+
+/// Number of days in one period of consumption history
+pub const DAYS_IN_HISTORY_PERIOD: i64 = NUMBER_OF_DAYS_IN_A_MONTH as i64;
+
+/// Consumption for one 30 day period of history
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct PeriodConsumption {
+    pub consumption: f64,
+    pub days_out_of_stock: u32,
+}
+
+impl PeriodConsumption {
+    pub fn days_in_stock(&self) -> u32 {
+        (DAYS_IN_HISTORY_PERIOD as u32).saturating_sub(self.days_out_of_stock)
+    }
+}
+
+/// Consumption history of an item, periods are counted back from the reference date,
+/// i.e. periods[0] is the 30 days ending on (and including) the reference date
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ConsumptionHistory {
+    pub periods: Vec<PeriodConsumption>,
+}
+
+impl ConsumptionHistory {
+    pub fn new(number_of_periods: u32) -> Self {
+        ConsumptionHistory {
+            periods: vec![PeriodConsumption::default(); number_of_periods as usize],
+        }
+    }
+
+    /// Periods from `offset` (0 being most recent) up to `offset + count`, missing periods are skipped
+    pub fn periods(&self, offset: u32, count: u32) -> &[PeriodConsumption] {
+        let start = (offset as usize).min(self.periods.len());
+        let end = ((offset + count) as usize).min(self.periods.len());
+        &self.periods[start..end]
+    }
+
+    fn period_index(&self, reference_date: &NaiveDate, date: &NaiveDate) -> Option<usize> {
+        let days_ago = (*reference_date - *date).num_days();
+        if days_ago < 0 {
+            return None;
+        }
+        let index = (days_ago / DAYS_IN_HISTORY_PERIOD) as usize;
+        (index < self.periods.len()).then_some(index)
+    }
+}
+
+fn first_history_date(reference_date: &NaiveDate, number_of_periods: u32) -> NaiveDate {
+    date_with_days_offset(
+        reference_date,
+        -((number_of_periods as i64 * DAYS_IN_HISTORY_PERIOD) as i32 - 1),
+    )
+}
+
+/// Builds consumption history for items in a store, grouped by item id
+pub fn get_consumption_history(
+    connection: &StorageConnection,
+    store_id: &str,
+    item_id_filter: Option<EqualFilter<String>>,
+    reference_date: NaiveDate,
+    number_of_periods: u32,
+    include_days_out_of_stock: bool,
+) -> Result<HashMap<String, ConsumptionHistory>, RepositoryError> {
+    let first_date = first_history_date(&reference_date, number_of_periods);
+    let mut result: HashMap<String, ConsumptionHistory> = HashMap::new();
+
+    let filter = ConsumptionFilter {
+        item_id: item_id_filter.clone(),
+        store_id: Some(EqualFilter::equal_to(store_id)),
+        date: Some(DateFilter::date_range(&first_date, &reference_date)),
+    };
+    let consumption_rows = ConsumptionRepository::new(connection).query(Some(filter))?;
+
+    for row in consumption_rows {
+        let history = result
+            .entry(row.item_id.clone())
+            .or_insert_with(|| ConsumptionHistory::new(number_of_periods));
+        if let Some(index) = history.period_index(&reference_date, &row.date) {
+            history.periods[index].consumption += row.quantity;
+        }
+    }
+
+    if !include_days_out_of_stock {
+        return Ok(result);
+    }
+
+    let mut daily_stock_on_hand_by_item = get_daily_stock_on_hand(
+        connection,
+        store_id,
+        item_id_filter,
+        first_date,
+        reference_date,
+    )?;
+
+    for (item_id, history) in result.iter_mut() {
+        let daily_stock_on_hand = daily_stock_on_hand_by_item
+            .remove(item_id)
+            .unwrap_or_default();
+
+        for day in daily_stock_on_hand
+            .iter()
+            .filter(|day| is_out_of_stock(day))
+        {
+            if let Some(index) = history.period_index(&reference_date, &day.date) {
+                history.periods[index].days_out_of_stock += 1;
+            }
+        }
+    }
+
+    Ok(result)
+}
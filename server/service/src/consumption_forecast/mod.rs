@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use repository::{
+    EqualFilter, NameTagFilter, NameTagRepository, ProgramRequisitionSettingsRowRepository,
+    RepositoryError, StorageConnection,
+};
+
+use crate::store_preference::get_store_preferences;
+
+mod exponential_smoothing;
+mod history;
+mod seasonal_moving_average;
+mod simple_average;
+mod stock_out_adjusted;
+
+pub use exponential_smoothing::*;
+pub use history::*;
+pub use repository::ConsumptionForecastMethod;
+pub use seasonal_moving_average::*;
+pub use simple_average::*;
+pub use stock_out_adjusted::*;
+
+/// Calculates average monthly consumption (AMC) of an item from its consumption history
+pub trait ConsumptionForecaster: Sync + Send {
+    /// Number of 30 day periods of history needed to forecast for `lookback_months`
+    fn history_periods(&self, lookback_months: u32) -> u32 {
+        lookback_months
+    }
+
+    /// Should days out of stock be calculated for history periods (requires stock ledger lookup)
+    fn uses_days_out_of_stock(&self) -> bool {
+        false
+    }
+
+    fn average_monthly_consumption(
+        &self,
+        history: &ConsumptionHistory,
+        lookback_months: u32,
+    ) -> f64;
+}
+
+pub fn get_consumption_forecaster(
+    method: &ConsumptionForecastMethod,
+) -> Box<dyn ConsumptionForecaster> {
+    match method {
+        ConsumptionForecastMethod::SimpleAverage => Box::new(SimpleAverage),
+        ConsumptionForecastMethod::StockOutAdjusted => Box::new(StockOutAdjusted),
+        ConsumptionForecastMethod::ExponentialSmoothing => {
+            Box::new(ExponentialSmoothing::default())
+        }
+        ConsumptionForecastMethod::SeasonalMovingAverage => Box::new(SeasonalMovingAverage),
+    }
+}
+
+/// Forecast method configured for a program (in program requisition settings matching store name tags),
+/// falling back to store preference
+pub fn get_consumption_forecast_method(
+    connection: &StorageConnection,
+    store_id: &str,
+    program_id: Option<&str>,
+) -> Result<ConsumptionForecastMethod, RepositoryError> {
+    if let Some(program_id) = program_id {
+        let store_name_tag_ids: Vec<String> = NameTagRepository::new(connection)
+            .query(Some(
+                NameTagFilter::new().store_id(EqualFilter::equal_to(store_id)),
+            ))?
+            .into_iter()
+            .map(|name_tag| name_tag.id)
+            .collect();
+
+        let program_method = ProgramRequisitionSettingsRowRepository::new(connection)
+            .find_many_by_program_id(program_id)?
+            .into_iter()
+            .filter(|settings| store_name_tag_ids.contains(&settings.name_tag_id))
+            .find_map(|settings| settings.consumption_forecast_method);
+
+        if let Some(program_method) = program_method {
+            return Ok(program_method);
+        }
+    }
+
+    Ok(get_store_preferences(connection, store_id)?.consumption_forecast_method)
+}
+
+/// Average monthly consumption for items in a store, using consumption history up to and including
+/// reference_date. Items without consumption in the history are not included in the result
+pub fn forecast_average_monthly_consumption(
+    connection: &StorageConnection,
+    store_id: &str,
+    item_id_filter: Option<EqualFilter<String>>,
+    method: &ConsumptionForecastMethod,
+    lookback_months: u32,
+    reference_date: NaiveDate,
+) -> Result<HashMap<String, f64>, RepositoryError> {
+    let forecaster = get_consumption_forecaster(method);
+
+    let history = get_consumption_history(
+        connection,
+        store_id,
+        item_id_filter,
+        reference_date,
+        forecaster.history_periods(lookback_months),
+        forecaster.uses_days_out_of_stock(),
+    )?;
+
+    Ok(history
+        .into_iter()
+        .map(|(item_id, history)| {
+            let average_monthly_consumption =
+                forecaster.average_monthly_consumption(&history, lookback_months);
+            (item_id, average_monthly_consumption)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn to_history(periods: Vec<(f64, u32)>) -> ConsumptionHistory {
+        ConsumptionHistory {
+            periods: periods
+                .into_iter()
+                .map(|(consumption, days_out_of_stock)| PeriodConsumption {
+                    consumption,
+                    days_out_of_stock,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_simple_average() {
+        let history = to_history(vec![(30.0, 0), (60.0, 0), (90.0, 0), (1000.0, 0)]);
+        assert_eq!(SimpleAverage.average_monthly_consumption(&history, 3), 60.0);
+        assert_eq!(SimpleAverage.average_monthly_consumption(&history, 0), 0.0);
+    }
+
+    #[test]
+    fn test_stock_out_adjusted() {
+        // Out of stock for half of the most recent month
+        let history = to_history(vec![(15.0, 15), (30.0, 0), (30.0, 0)]);
+        assert_eq!(
+            StockOutAdjusted.average_monthly_consumption(&history, 3),
+            75.0 / 75.0 * 30.0
+        );
+        assert_eq!(SimpleAverage.average_monthly_consumption(&history, 3), 25.0);
+
+        // Not enough days in stock, fall back to simple average
+        let history = to_history(vec![(3.0, 30), (0.0, 30), (0.0, 25)]);
+        assert_eq!(
+            StockOutAdjusted.average_monthly_consumption(&history, 3),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_exponential_smoothing() {
+        let forecaster = ExponentialSmoothing {
+            smoothing_factor: 0.5,
+        };
+        assert_eq!(forecaster.history_periods(3), MINIMUM_SMOOTHING_MONTHS);
+        assert_eq!(forecaster.history_periods(12), 12);
+
+        // Most recent first
+        let history = to_history(vec![(40.0, 0), (20.0, 0), (0.0, 0), (0.0, 0), (0.0, 0)]);
+        // Oldest first: 0 -> 0 -> 0 -> 10 -> 25
+        assert_eq!(forecaster.average_monthly_consumption(&history, 3), 25.0);
+
+        assert_eq!(
+            forecaster.average_monthly_consumption(&ConsumptionHistory::default(), 3),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_seasonal_moving_average() {
+        assert_eq!(SeasonalMovingAverage.history_periods(3), 15);
+
+        // No history a year ago, same as simple average
+        let history_without_season = to_history([vec![(10.0, 0); 3], vec![(0.0, 0); 12]].concat());
+        assert_eq!(
+            SeasonalMovingAverage.average_monthly_consumption(&history_without_season, 3),
+            10.0
+        );
+
+        // A year ago consumption doubled in the months following the lookback period
+        let history_with_season = to_history(
+            [
+                vec![(10.0, 0); 3],
+                vec![(0.0, 0); 6],
+                vec![(40.0, 0); 3],
+                vec![(20.0, 0); 3],
+            ]
+            .concat(),
+        );
+        assert_eq!(
+            SeasonalMovingAverage.average_monthly_consumption(&history_with_season, 3),
+            20.0
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use super::{ConsumptionForecaster, ConsumptionHistory, SimpleAverage};
+
+const MONTHS_IN_A_YEAR: u32 = 12;
+
+/// Simple average, scaled by how consumption changed from the same lookback period a year ago
+/// to the months that followed it. E.g. with a 3 month lookback in March, Dec-Feb average is
+/// scaled by the ratio between last year's Mar-May and Dec-Feb averages.
+/// If there is no consumption a year ago, the simple average is used.
+pub struct SeasonalMovingAverage;
+
+impl ConsumptionForecaster for SeasonalMovingAverage {
+    fn history_periods(&self, lookback_months: u32) -> u32 {
+        MONTHS_IN_A_YEAR + lookback_months
+    }
+
+    fn average_monthly_consumption(
+        &self,
+        history: &ConsumptionHistory,
+        lookback_months: u32,
+    ) -> f64 {
+        let recent_average = SimpleAverage.average_monthly_consumption(history, lookback_months);
+
+        let average = |offset: u32| -> f64 {
+            let periods = history.periods(offset, lookback_months);
+            if periods.is_empty() {
+                return 0.0;
+            }
+            periods.iter().map(|period| period.consumption).sum::<f64>() / periods.len() as f64
+        };
+
+        let last_year_recent_average = average(MONTHS_IN_A_YEAR);
+        let last_year_following_average = average(MONTHS_IN_A_YEAR.saturating_sub(lookback_months));
+
+        if last_year_recent_average == 0.0 || last_year_following_average == 0.0 {
+            return recent_average;
+        }
+
+        recent_average * last_year_following_average / last_year_recent_average
+    }
+}
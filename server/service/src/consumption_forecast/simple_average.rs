@@ -0,0 +1,24 @@
+use super::{ConsumptionForecaster, ConsumptionHistory};
+
+/// Total consumption in the lookback period divided by the number of months
+pub struct SimpleAverage;
+
+impl ConsumptionForecaster for SimpleAverage {
+    fn average_monthly_consumption(
+        &self,
+        history: &ConsumptionHistory,
+        lookback_months: u32,
+    ) -> f64 {
+        if lookback_months == 0 {
+            return 0.0;
+        }
+
+        let total_consumption: f64 = history
+            .periods(0, lookback_months)
+            .iter()
+            .map(|period| period.consumption)
+            .sum();
+
+        total_consumption / lookback_months as f64
+    }
+}
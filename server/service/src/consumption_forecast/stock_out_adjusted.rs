@@ -0,0 +1,35 @@
+use util::constants::NUMBER_OF_DAYS_IN_A_MONTH;
+
+use super::{ConsumptionForecaster, ConsumptionHistory, SimpleAverage};
+
+/// If an item was in stock for less than this many days in the lookback period there is not
+/// enough data to extrapolate from, and simple average is used instead
+pub const MINIMUM_DAYS_IN_STOCK: u32 = 7;
+
+/// Consumption in the lookback period divided by the number of days the item was in stock
+/// (rather than the number of days in the lookback period), so that stock outs don't
+/// understate consumption
+pub struct StockOutAdjusted;
+
+impl ConsumptionForecaster for StockOutAdjusted {
+    fn uses_days_out_of_stock(&self) -> bool {
+        true
+    }
+
+    fn average_monthly_consumption(
+        &self,
+        history: &ConsumptionHistory,
+        lookback_months: u32,
+    ) -> f64 {
+        let periods = history.periods(0, lookback_months);
+
+        let total_consumption: f64 = periods.iter().map(|period| period.consumption).sum();
+        let days_in_stock: u32 = periods.iter().map(|period| period.days_in_stock()).sum();
+
+        if days_in_stock < MINIMUM_DAYS_IN_STOCK {
+            return SimpleAverage.average_monthly_consumption(history, lookback_months);
+        }
+
+        total_consumption / days_in_stock as f64 * NUMBER_OF_DAYS_IN_A_MONTH
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use repository::{
+    CycleCountClass, CycleCountItemRow, CycleCountItemRowRepository, CycleCountPlanRow,
+    EqualFilter, RepositoryError, StockLineFilter, StockLineRepository,
+};
+use util::uuid::uuid;
+
+use crate::{item_stats::get_item_stats, service_provider::ServiceContext};
+
+/// Consumption history used for the consumption value of items
+pub const CLASSIFICATION_LOOKBACK_MONTHS: u32 = 12;
+
+struct ItemValue {
+    item_id: String,
+    /// Cost of a year's consumption
+    consumption_value: f64,
+}
+
+/// ABC classification of the items in stock in the store by consumption value (a year of average
+/// monthly consumption at the average cost of the store's stock). Items making up the first
+/// `class_a_percentage` of the store's total consumption value are class A, the next up to
+/// `class_b_percentage` are class B and the rest (including items without consumption) are
+/// class C. Items are returned most valuable first.
+pub fn classify_items(
+    ctx: &ServiceContext,
+    plan: &CycleCountPlanRow,
+) -> Result<Vec<CycleCountItemRow>, RepositoryError> {
+    let store_id = &plan.store_id;
+
+    // Average cost per unit of the items in stock
+    let mut stock: HashMap<String, (f64, f64)> = HashMap::new();
+    for stock_line in StockLineRepository::new(&ctx.connection).query_by_filter(
+        StockLineFilter::new()
+            .store_id(EqualFilter::equal_to(store_id))
+            .has_packs_in_store(true),
+        Some(store_id.to_string()),
+    )? {
+        // Inactive items can't be added to stocktakes
+        if !stock_line.item_row.is_active {
+            continue;
+        }
+        let row = &stock_line.stock_line_row;
+        let (units, cost) = stock.entry(stock_line.item_row.id.clone()).or_default();
+        *units += row.total_number_of_packs * row.pack_size;
+        *cost += row.total_number_of_packs * row.cost_price_per_pack;
+    }
+
+    let average_monthly_consumption: HashMap<String, f64> =
+        get_item_stats(ctx, store_id, Some(CLASSIFICATION_LOOKBACK_MONTHS), None)?
+            .into_iter()
+            .map(|item_stats| (item_stats.item_id, item_stats.average_monthly_consumption))
+            .collect();
+
+    let mut item_values: Vec<ItemValue> = stock
+        .into_iter()
+        .map(|(item_id, (units, cost))| {
+            let unit_cost = if units > 0.0 { cost / units } else { 0.0 };
+            let amc = average_monthly_consumption
+                .get(&item_id)
+                .copied()
+                .unwrap_or_default();
+            ItemValue {
+                consumption_value: amc * 12.0 * unit_cost,
+                item_id,
+            }
+        })
+        .collect();
+    item_values.sort_by(|a, b| {
+        b.consumption_value
+            .total_cmp(&a.consumption_value)
+            .then_with(|| a.item_id.cmp(&b.item_id))
+    });
+
+    let total_value: f64 = item_values.iter().map(|item| item.consumption_value).sum();
+
+    let repo = CycleCountItemRowRepository::new(&ctx.connection);
+    let mut cumulative_value = 0.0;
+    let mut result = Vec::new();
+    for ItemValue {
+        item_id,
+        consumption_value,
+    } in item_values
+    {
+        // Share of the total value of the more valuable items
+        let preceding_percentage = if total_value > 0.0 {
+            cumulative_value / total_value * 100.0
+        } else {
+            100.0
+        };
+        cumulative_value += consumption_value;
+
+        let abc_class = if consumption_value <= 0.0 {
+            CycleCountClass::C
+        } else if preceding_percentage < plan.class_a_percentage {
+            CycleCountClass::A
+        } else if preceding_percentage < plan.class_b_percentage {
+            CycleCountClass::B
+        } else {
+            CycleCountClass::C
+        };
+
+        let existing = repo.find_one_by_store_and_item(store_id, &item_id)?;
+        let row = CycleCountItemRow {
+            id: existing
+                .as_ref()
+                .map(|existing| existing.id.clone())
+                .unwrap_or_else(uuid),
+            store_id: store_id.clone(),
+            item_link_id: item_id,
+            abc_class: Some(abc_class),
+            consumption_value,
+            last_counted_date: existing.and_then(|existing| existing.last_counted_date),
+        };
+        repo.upsert_one(&row)?;
+        result.push(row);
+    }
+
+    Ok(result)
+}
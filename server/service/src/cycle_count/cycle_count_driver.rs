@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::time::Duration;
+use util::format_error;
+
+use crate::{service_provider::ServiceProvider, sync::is_initialised};
+
+const CYCLE_COUNT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub struct CycleCountDriver {}
+
+/// Used to 'drive' cycle counting, once an hour (only when initialised) today's cycle count
+/// stocktakes are generated for stores with an active cycle count plan that haven't had one
+/// generated yet today
+impl CycleCountDriver {
+    pub fn init() -> CycleCountDriver {
+        CycleCountDriver {}
+    }
+
+    /// CycleCountDriver entry point, this method is meant to be run within main `select!` macro
+    pub async fn run(self, service_provider: Arc<ServiceProvider>) {
+        loop {
+            tokio::time::sleep(CYCLE_COUNT_INTERVAL).await;
+
+            // Need to check is_initialised from database on every iteration, since it could have been updated
+            if !is_initialised(&service_provider) {
+                continue;
+            }
+
+            let service_provider = service_provider.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                service_provider
+                    .cycle_count_service
+                    .generate_due_cycle_counts(&service_provider, Utc::now().naive_utc().date())
+            })
+            .await;
+
+            match result {
+                Ok(Ok(stocktakes)) => {
+                    if !stocktakes.is_empty() {
+                        log::info!("Generated {} cycle count stocktake(s)", stocktakes.len());
+                    }
+                }
+                Ok(Err(error)) => {
+                    log::error!("Error generating cycle counts: {}", format_error(&error))
+                }
+                Err(error) => log::error!("Cycle count task failed: {}", error),
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::NaiveDate;
+use repository::{
+    CycleCountClass, CycleCountItemRow, CycleCountPlanRow, CycleCountPlanRowRepository,
+    RepositoryError, Stocktake, StocktakeLineRowRepository,
+};
+use util::uuid::uuid;
+
+use crate::{
+    service_provider::ServiceContext,
+    stocktake::{
+        generate_lines_for_items, insert_stocktake, query::get_stocktake, InsertStocktake,
+        InsertStocktakeError,
+    },
+};
+
+use super::classification::classify_items;
+
+#[derive(Debug, PartialEq)]
+pub enum GenerateCycleCountError {
+    NoCycleCountPlan,
+    PlanIsNotActive,
+    InsertStocktakeError(InsertStocktakeError),
+    CreatedStocktakeDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+fn frequency_days(plan: &CycleCountPlanRow, class: &CycleCountClass) -> i64 {
+    let days = match class {
+        CycleCountClass::A => plan.class_a_frequency_days,
+        CycleCountClass::B => plan.class_b_frequency_days,
+        CycleCountClass::C => plan.class_c_frequency_days,
+    };
+    days as i64
+}
+
+/// Items that have never been counted, or not within the frequency of their class. Ordered by
+/// class, then by the longest time since the last count.
+pub fn get_due_items(
+    plan: &CycleCountPlanRow,
+    items: Vec<CycleCountItemRow>,
+    today: NaiveDate,
+) -> Vec<CycleCountItemRow> {
+    let mut due_items: Vec<CycleCountItemRow> = items
+        .into_iter()
+        .filter(|item| {
+            let Some(abc_class) = &item.abc_class else {
+                return false;
+            };
+            match item.last_counted_date {
+                Some(last_counted_date) => {
+                    (today - last_counted_date).num_days() >= frequency_days(plan, abc_class)
+                }
+                None => true,
+            }
+        })
+        .collect();
+
+    // None (never counted) sorts before any date
+    due_items.sort_by(|a, b| {
+        a.abc_class
+            .cmp(&b.abc_class)
+            .then_with(|| a.last_counted_date.cmp(&b.last_counted_date))
+            .then_with(|| b.consumption_value.total_cmp(&a.consumption_value))
+    });
+
+    if let Some(max_items_per_count) = plan.max_items_per_count {
+        due_items.truncate(max_items_per_count.max(0) as usize);
+    }
+
+    due_items
+}
+
+/// Creates the store's cycle count stocktake for `today` with the items that are due for
+/// counting. Returns `None` if a stocktake was already generated for `today` or no items are due.
+pub fn generate_cycle_count(
+    ctx: &ServiceContext,
+    today: NaiveDate,
+) -> Result<Option<Stocktake>, GenerateCycleCountError> {
+    let stocktake = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let plan_repo = CycleCountPlanRowRepository::new(connection);
+            let plan = plan_repo
+                .find_one_by_store_id(&ctx.store_id)?
+                .ok_or(GenerateCycleCountError::NoCycleCountPlan)?;
+            if !plan.is_active {
+                return Err(GenerateCycleCountError::PlanIsNotActive);
+            }
+            if plan.last_generated_date == Some(today) {
+                return Ok(None);
+            }
+
+            let items = classify_items(ctx, &plan)?;
+            let due_item_ids: Vec<String> = get_due_items(&plan, items, today)
+                .into_iter()
+                .map(|item| item.item_link_id)
+                .collect();
+
+            plan_repo.upsert_one(&CycleCountPlanRow {
+                last_generated_date: Some(today),
+                ..plan
+            })?;
+
+            if due_item_ids.is_empty() {
+                return Ok(None);
+            }
+
+            let stocktake_id = uuid();
+            insert_stocktake(
+                ctx,
+                InsertStocktake {
+                    id: stocktake_id.clone(),
+                    description: Some(format!("Cycle count {}", today)),
+                    stocktake_date: Some(today),
+                    ..Default::default()
+                },
+            )
+            .map_err(GenerateCycleCountError::InsertStocktakeError)?;
+
+            let line_repo = StocktakeLineRowRepository::new(connection);
+            for line in
+                generate_lines_for_items(connection, &ctx.store_id, &stocktake_id, &due_item_ids)?
+            {
+                line_repo.upsert_one(&line)?;
+            }
+
+            get_stocktake(ctx, stocktake_id)?
+                .ok_or(GenerateCycleCountError::CreatedStocktakeDoesNotExist)
+                .map(Some)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(stocktake)
+}
+
+impl From<RepositoryError> for GenerateCycleCountError {
+    fn from(error: RepositoryError) -> Self {
+        GenerateCycleCountError::DatabaseError(error)
+    }
+}
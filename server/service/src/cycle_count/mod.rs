@@ -0,0 +1,114 @@
+use chrono::NaiveDate;
+use repository::{
+    CycleCountItemRow, CycleCountItemRowRepository, CycleCountPlanRow, CycleCountPlanRowRepository,
+    CycleCountVarianceRow, CycleCountVarianceRowRepository, RepositoryError, Stocktake,
+};
+use util::constants::SYSTEM_USER_ID;
+
+use crate::{
+    service_provider::{ServiceContext, ServiceProvider},
+    sync::ActiveStoresOnSite,
+};
+
+use self::{
+    generate::{generate_cycle_count, GenerateCycleCountError},
+    plan::{upsert_cycle_count_plan, UpsertCycleCountPlan, UpsertCycleCountPlanError},
+};
+
+pub mod classification;
+pub mod cycle_count_driver;
+pub mod generate;
+pub mod plan;
+pub mod variance;
+
+#[cfg(test)]
+mod test;
+
+/// Cycle counting: items of a store are classified by consumption value (ABC classification)
+/// and stocktakes of the items due for counting are generated daily, following the store's
+/// cycle count plan
+pub trait CycleCountServiceTrait: Sync + Send {
+    fn get_cycle_count_plan(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<Option<CycleCountPlanRow>, RepositoryError> {
+        CycleCountPlanRowRepository::new(&ctx.connection).find_one_by_store_id(store_id)
+    }
+
+    fn upsert_cycle_count_plan(
+        &self,
+        ctx: &ServiceContext,
+        input: UpsertCycleCountPlan,
+    ) -> Result<CycleCountPlanRow, UpsertCycleCountPlanError> {
+        upsert_cycle_count_plan(ctx, input)
+    }
+
+    /// Classification and last counted date of the store's items
+    fn get_cycle_count_items(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<Vec<CycleCountItemRow>, RepositoryError> {
+        CycleCountItemRowRepository::new(&ctx.connection).find_many_by_store_id(store_id)
+    }
+
+    /// Count history of the store's items (or of one item), most recent first
+    fn get_cycle_count_variances(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        item_id: Option<&str>,
+    ) -> Result<Vec<CycleCountVarianceRow>, RepositoryError> {
+        CycleCountVarianceRowRepository::new(&ctx.connection)
+            .find_many_by_store_id(store_id, item_id)
+    }
+
+    fn generate_cycle_count(
+        &self,
+        ctx: &ServiceContext,
+        today: NaiveDate,
+    ) -> Result<Option<Stocktake>, GenerateCycleCountError> {
+        generate_cycle_count(ctx, today)
+    }
+
+    /// Generates today's cycle count stocktakes of the active stores on this site with an active
+    /// plan. Returns the generated stocktakes, errors of individual stores are logged.
+    fn generate_due_cycle_counts(
+        &self,
+        service_provider: &ServiceProvider,
+        today: NaiveDate,
+    ) -> Result<Vec<Stocktake>, RepositoryError> {
+        let connection = service_provider.connection()?;
+        let active_store_ids = match ActiveStoresOnSite::get(&connection) {
+            Ok(active_stores) => active_stores.store_ids(),
+            // Site is not initialised
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut result = Vec::new();
+        for plan in CycleCountPlanRowRepository::new(&connection).find_active()? {
+            if !active_store_ids.contains(&plan.store_id) || plan.last_generated_date == Some(today)
+            {
+                continue;
+            }
+
+            let ctx =
+                service_provider.context(plan.store_id.clone(), SYSTEM_USER_ID.to_string())?;
+            match generate_cycle_count(&ctx, today) {
+                Ok(Some(stocktake)) => result.push(stocktake),
+                Ok(None) => {}
+                Err(error) => log::error!(
+                    "Error generating cycle count for store {}: {:?}",
+                    plan.store_id,
+                    error
+                ),
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+pub struct CycleCountService;
+impl CycleCountServiceTrait for CycleCountService {}
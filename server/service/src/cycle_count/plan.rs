@@ -0,0 +1,117 @@
+use repository::{CycleCountPlanRow, CycleCountPlanRowRepository, RepositoryError};
+
+use crate::service_provider::ServiceContext;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct UpsertCycleCountPlan {
+    pub id: String,
+    pub class_a_percentage: f64,
+    pub class_b_percentage: f64,
+    pub class_a_frequency_days: i32,
+    pub class_b_frequency_days: i32,
+    pub class_c_frequency_days: i32,
+    pub max_items_per_count: Option<i32>,
+    pub is_active: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum UpsertCycleCountPlanError {
+    NotThisStorePlan,
+    /// A store can only have one cycle count plan
+    PlanAlreadyExistsForStore,
+    /// Percentages must be between 0 and 100 with class B not below class A
+    InvalidClassPercentages,
+    /// Frequencies must be at least one day
+    InvalidFrequency,
+    InvalidMaxItemsPerCount,
+    DatabaseError(RepositoryError),
+}
+
+impl Default for UpsertCycleCountPlan {
+    /// Pareto split of 80/15/5 percent of consumption value, counting class A items monthly,
+    /// class B quarterly and class C twice a year
+    fn default() -> Self {
+        UpsertCycleCountPlan {
+            id: String::new(),
+            class_a_percentage: 80.0,
+            class_b_percentage: 95.0,
+            class_a_frequency_days: 30,
+            class_b_frequency_days: 90,
+            class_c_frequency_days: 180,
+            max_items_per_count: None,
+            is_active: true,
+        }
+    }
+}
+
+pub fn upsert_cycle_count_plan(
+    ctx: &ServiceContext,
+    input: UpsertCycleCountPlan,
+) -> Result<CycleCountPlanRow, UpsertCycleCountPlanError> {
+    let repo = CycleCountPlanRowRepository::new(&ctx.connection);
+    let existing = repo.find_one_by_id(&input.id)?;
+    if let Some(existing) = &existing {
+        if existing.store_id != ctx.store_id {
+            return Err(UpsertCycleCountPlanError::NotThisStorePlan);
+        }
+    }
+    if let Some(store_plan) = repo.find_one_by_store_id(&ctx.store_id)? {
+        if store_plan.id != input.id {
+            return Err(UpsertCycleCountPlanError::PlanAlreadyExistsForStore);
+        }
+    }
+
+    let valid_percentage = |percentage: f64| (0.0..=100.0).contains(&percentage);
+    if !valid_percentage(input.class_a_percentage)
+        || !valid_percentage(input.class_b_percentage)
+        || input.class_b_percentage < input.class_a_percentage
+    {
+        return Err(UpsertCycleCountPlanError::InvalidClassPercentages);
+    }
+    if [
+        input.class_a_frequency_days,
+        input.class_b_frequency_days,
+        input.class_c_frequency_days,
+    ]
+    .iter()
+    .any(|days| *days < 1)
+    {
+        return Err(UpsertCycleCountPlanError::InvalidFrequency);
+    }
+    if input.max_items_per_count.map_or(false, |max| max < 1) {
+        return Err(UpsertCycleCountPlanError::InvalidMaxItemsPerCount);
+    }
+
+    let UpsertCycleCountPlan {
+        id,
+        class_a_percentage,
+        class_b_percentage,
+        class_a_frequency_days,
+        class_b_frequency_days,
+        class_c_frequency_days,
+        max_items_per_count,
+        is_active,
+    } = input;
+
+    let row = CycleCountPlanRow {
+        id,
+        store_id: ctx.store_id.clone(),
+        class_a_percentage,
+        class_b_percentage,
+        class_a_frequency_days,
+        class_b_frequency_days,
+        class_c_frequency_days,
+        max_items_per_count,
+        last_generated_date: existing.and_then(|existing| existing.last_generated_date),
+        is_active,
+    };
+    repo.upsert_one(&row)?;
+
+    Ok(row)
+}
+
+impl From<RepositoryError> for UpsertCycleCountPlanError {
+    fn from(error: RepositoryError) -> Self {
+        UpsertCycleCountPlanError::DatabaseError(error)
+    }
+}
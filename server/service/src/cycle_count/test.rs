@@ -0,0 +1,275 @@
+use chrono::{Duration, Utc};
+use repository::{
+    mock::{mock_name_a, mock_store_a, MockData, MockDataInserts},
+    test_db::setup_all_with_data,
+    CycleCountClass, EqualFilter, InvoiceLineRow, InvoiceLineType, InvoiceRow, InvoiceStatus,
+    InvoiceType, ItemRow, NameRow, StockLineRow, StocktakeLineFilter, StocktakeLineRepository,
+    StoreRow,
+};
+use util::{inline_init, uuid::uuid};
+
+use crate::{
+    cycle_count::{
+        generate::GenerateCycleCountError,
+        plan::{UpsertCycleCountPlan, UpsertCycleCountPlanError},
+    },
+    service_provider::ServiceProvider,
+    stocktake::{UpdateStocktake, UpdateStocktakeStatus},
+    stocktake_line::UpdateStocktakeLine,
+};
+
+fn name() -> NameRow {
+    inline_init(|r: &mut NameRow| {
+        r.id = "cycle_count_name".to_string();
+    })
+}
+
+fn store() -> StoreRow {
+    inline_init(|r: &mut StoreRow| {
+        r.id = "cycle_count_store".to_string();
+        r.name_link_id = name().id;
+        r.code = "cycle_count_store".to_string();
+    })
+}
+
+fn item(id: &str) -> ItemRow {
+    inline_init(|r: &mut ItemRow| {
+        r.id = id.to_string();
+        r.name = id.to_string();
+        r.code = id.to_string();
+    })
+}
+
+/// 100 units of the item in stock and a picked outbound shipment of `consumed_units`
+fn stock_and_consumption(item_id: &str, cost_price_per_pack: f64, consumed_units: f64) -> MockData {
+    let invoice_id = uuid();
+    inline_init(|r: &mut MockData| {
+        r.items = vec![item(item_id)];
+        r.stock_lines = vec![inline_init(|r: &mut StockLineRow| {
+            r.id = format!("{}_stock_line", item_id);
+            r.item_link_id = item_id.to_string();
+            r.store_id = store().id;
+            r.pack_size = 1.0;
+            r.available_number_of_packs = 100.0;
+            r.total_number_of_packs = 100.0;
+            r.cost_price_per_pack = cost_price_per_pack;
+        })];
+        if consumed_units > 0.0 {
+            r.invoices = vec![inline_init(|r: &mut InvoiceRow| {
+                r.id.clone_from(&invoice_id);
+                r.store_id = store().id;
+                r.name_link_id = mock_name_a().id;
+                r.r#type = InvoiceType::OutboundShipment;
+                r.status = InvoiceStatus::Picked;
+                r.picked_datetime = Some(Utc::now().naive_utc() - Duration::days(10));
+            })];
+            r.invoice_lines = vec![inline_init(|r: &mut InvoiceLineRow| {
+                r.id = format!("{}_line", invoice_id);
+                r.invoice_id.clone_from(&invoice_id);
+                r.item_link_id = item_id.to_string();
+                r.r#type = InvoiceLineType::StockOut;
+                r.pack_size = 1.0;
+                r.number_of_packs = consumed_units;
+            })];
+        }
+    })
+}
+
+#[actix_rt::test]
+async fn cycle_count() {
+    let (_, connection, connection_manager, _) = setup_all_with_data(
+        "cycle_count",
+        MockDataInserts::all(),
+        inline_init(|r: &mut MockData| {
+            r.names = vec![name()];
+            r.stores = vec![store()];
+        })
+        // Consumption values of 900 : 10 : 0
+        .join(stock_and_consumption("item_a", 10.0, 90.0))
+        .join(stock_and_consumption("item_b", 1.0, 10.0))
+        .join(stock_and_consumption("item_c", 1.0, 0.0)),
+    )
+    .await;
+
+    let service_provider = ServiceProvider::new(connection_manager, "app_data");
+    let context = service_provider
+        .context(store().id, "".to_string())
+        .unwrap();
+    let service = &service_provider.cycle_count_service;
+    let today = Utc::now().naive_utc().date();
+
+    assert_eq!(
+        service.generate_cycle_count(&context, today),
+        Err(GenerateCycleCountError::NoCycleCountPlan)
+    );
+
+    // Plan validation
+    assert_eq!(
+        service.upsert_cycle_count_plan(
+            &context,
+            UpsertCycleCountPlan {
+                id: "plan".to_string(),
+                class_a_percentage: 90.0,
+                class_b_percentage: 80.0,
+                ..Default::default()
+            }
+        ),
+        Err(UpsertCycleCountPlanError::InvalidClassPercentages)
+    );
+    assert_eq!(
+        service.upsert_cycle_count_plan(
+            &context,
+            UpsertCycleCountPlan {
+                id: "plan".to_string(),
+                class_c_frequency_days: 0,
+                ..Default::default()
+            }
+        ),
+        Err(UpsertCycleCountPlanError::InvalidFrequency)
+    );
+    assert_eq!(
+        service.upsert_cycle_count_plan(
+            &context,
+            UpsertCycleCountPlan {
+                id: "plan".to_string(),
+                max_items_per_count: Some(0),
+                ..Default::default()
+            }
+        ),
+        Err(UpsertCycleCountPlanError::InvalidMaxItemsPerCount)
+    );
+
+    let plan = service
+        .upsert_cycle_count_plan(
+            &context,
+            UpsertCycleCountPlan {
+                id: "plan".to_string(),
+                class_a_percentage: 50.0,
+                class_b_percentage: 99.0,
+                max_items_per_count: Some(2),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        service.get_cycle_count_plan(&context, &store().id),
+        Ok(Some(plan))
+    );
+
+    assert_eq!(
+        service.upsert_cycle_count_plan(
+            &context,
+            UpsertCycleCountPlan {
+                id: "other_plan".to_string(),
+                ..Default::default()
+            }
+        ),
+        Err(UpsertCycleCountPlanError::PlanAlreadyExistsForStore)
+    );
+    let store_a_context = service_provider
+        .context(mock_store_a().id, "".to_string())
+        .unwrap();
+    assert_eq!(
+        service.upsert_cycle_count_plan(
+            &store_a_context,
+            UpsertCycleCountPlan {
+                id: "plan".to_string(),
+                ..Default::default()
+            }
+        ),
+        Err(UpsertCycleCountPlanError::NotThisStorePlan)
+    );
+
+    // Generate: all items are due, limited to the two most valuable
+    let stocktake = service
+        .generate_cycle_count(&context, today)
+        .unwrap()
+        .unwrap();
+    let stocktake_item_ids = |stocktake_id: &str| {
+        let mut item_ids: Vec<String> = StocktakeLineRepository::new(&connection)
+            .query_by_filter(
+                StocktakeLineFilter::new().stocktake_id(EqualFilter::equal_to(stocktake_id)),
+                Some(store().id),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|line| line.item.id)
+            .collect();
+        item_ids.sort();
+        item_ids
+    };
+    assert_eq!(stocktake_item_ids(&stocktake.id), vec!["item_a", "item_b"]);
+
+    let classes: Vec<(String, Option<CycleCountClass>)> = service
+        .get_cycle_count_items(&context, &store().id)
+        .unwrap()
+        .into_iter()
+        .map(|item| (item.item_link_id, item.abc_class))
+        .collect();
+    assert!(classes.contains(&("item_a".to_string(), Some(CycleCountClass::A))));
+    assert!(classes.contains(&("item_b".to_string(), Some(CycleCountClass::B))));
+    assert!(classes.contains(&("item_c".to_string(), Some(CycleCountClass::C))));
+
+    // Only generated once a day
+    assert_eq!(service.generate_cycle_count(&context, today), Ok(None));
+
+    // Finalising records the count of counted items
+    let line = StocktakeLineRepository::new(&connection)
+        .query_by_filter(
+            StocktakeLineFilter::new().stocktake_id(EqualFilter::equal_to(&stocktake.id)),
+            Some(store().id),
+        )
+        .unwrap()
+        .into_iter()
+        .find(|line| line.item.id == "item_a")
+        .unwrap();
+    service_provider
+        .stocktake_line_service
+        .update_stocktake_line(
+            &context,
+            inline_init(|r: &mut UpdateStocktakeLine| {
+                r.id = line.line.id;
+                r.counted_number_of_packs = Some(95.0);
+            }),
+        )
+        .unwrap();
+    service_provider
+        .stocktake_service
+        .update_stocktake(
+            &context,
+            inline_init(|r: &mut UpdateStocktake| {
+                r.id.clone_from(&stocktake.id);
+                r.status = Some(UpdateStocktakeStatus::Finalised);
+            }),
+        )
+        .unwrap();
+
+    let variances = service
+        .get_cycle_count_variances(&context, &store().id, None)
+        .unwrap();
+    assert_eq!(variances.len(), 1);
+    assert_eq!(variances[0].item_link_id, "item_a");
+    assert_eq!(variances[0].stocktake_id, stocktake.id);
+    assert_eq!(variances[0].snapshot_quantity, 100.0);
+    assert_eq!(variances[0].counted_quantity, 95.0);
+
+    let items = service
+        .get_cycle_count_items(&context, &store().id)
+        .unwrap();
+    let last_counted_date = |item_id: &str| {
+        items
+            .iter()
+            .find(|item| item.item_link_id == item_id)
+            .unwrap()
+            .last_counted_date
+    };
+    assert_eq!(last_counted_date("item_a"), Some(today));
+    assert_eq!(last_counted_date("item_b"), None);
+
+    // Next day: class A item was just counted
+    let stocktake = service
+        .generate_cycle_count(&context, today + Duration::days(1))
+        .unwrap()
+        .unwrap();
+    assert_eq!(stocktake_item_ids(&stocktake.id), vec!["item_b", "item_c"]);
+}
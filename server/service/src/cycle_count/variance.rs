@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use repository::{
+    CycleCountItemRow, CycleCountItemRowRepository, CycleCountVarianceRow,
+    CycleCountVarianceRowRepository, EqualFilter, RepositoryError, StocktakeLineFilter,
+    StocktakeLineRepository, StocktakeRow, StorageConnection,
+};
+use util::uuid::uuid;
+
+/// Records the count of each counted item of a finalised stocktake, updating the last counted
+/// date of the items and adding to their count variance history
+pub fn record_stocktake_counts(
+    connection: &StorageConnection,
+    stocktake: &StocktakeRow,
+) -> Result<(), RepositoryError> {
+    let counted_date = stocktake
+        .finalised_datetime
+        .unwrap_or_else(|| Utc::now().naive_utc())
+        .date();
+
+    let lines = StocktakeLineRepository::new(connection).query_by_filter(
+        StocktakeLineFilter::new().stocktake_id(EqualFilter::equal_to(&stocktake.id)),
+        Some(stocktake.store_id.clone()),
+    )?;
+
+    // item id -> (snapshot quantity, counted quantity, is counted)
+    let mut item_counts: BTreeMap<String, (f64, f64, bool)> = BTreeMap::new();
+    for line in lines {
+        let row = &line.line;
+        let pack_size = row.pack_size.unwrap_or(1.0);
+        let (snapshot, counted, is_counted) = item_counts.entry(line.item.id).or_default();
+        *snapshot += row.snapshot_number_of_packs * pack_size;
+        // Uncounted lines are assumed to match the snapshot
+        *counted += row
+            .final_counted_number_of_packs()
+            .unwrap_or(row.snapshot_number_of_packs)
+            * pack_size;
+        *is_counted |= row.counted_number_of_packs.is_some();
+    }
+
+    let item_repo = CycleCountItemRowRepository::new(connection);
+    let variance_repo = CycleCountVarianceRowRepository::new(connection);
+    for (item_id, (snapshot_quantity, counted_quantity, is_counted)) in item_counts {
+        if !is_counted {
+            continue;
+        }
+
+        variance_repo.upsert_one(&CycleCountVarianceRow {
+            id: uuid(),
+            store_id: stocktake.store_id.clone(),
+            item_link_id: item_id.clone(),
+            stocktake_id: stocktake.id.clone(),
+            counted_date,
+            snapshot_quantity,
+            counted_quantity,
+        })?;
+
+        let item = match item_repo.find_one_by_store_and_item(&stocktake.store_id, &item_id)? {
+            Some(item) => CycleCountItemRow {
+                last_counted_date: Some(counted_date),
+                ..item
+            },
+            // Classified when the next cycle count is generated
+            None => CycleCountItemRow {
+                id: uuid(),
+                store_id: stocktake.store_id.clone(),
+                item_link_id: item_id,
+                abc_class: None,
+                consumption_value: 0.0,
+                last_counted_date: Some(counted_date),
+            },
+        };
+        item_repo.upsert_one(&item)?;
+    }
+
+    Ok(())
+}
@@ -14,6 +14,14 @@ pub trait RequisitionCountServiceTrait: Send + Sync {
         RequisitionCountService {}.new_response_requisition_count(ctx, store_id)
     }
 
+    fn new_emergency_response_requisition_count(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<i64, RepositoryError> {
+        RequisitionCountService {}.new_emergency_response_requisition_count(ctx, store_id)
+    }
+
     fn draft_request_requisition_count(
         &self,
         ctx: &ServiceContext,
@@ -36,7 +44,23 @@ impl RequisitionCountServiceTrait for RequisitionCountService {
             RequisitionFilter::new()
                 .store_id(EqualFilter::equal_to(store_id))
                 .r#type(RequisitionType::Response.equal_to())
-                .status(RequisitionStatus::New.equal_to()),
+                .status(RequisitionStatus::New.equal_to())
+                .is_emergency(false),
+        ))
+    }
+
+    fn new_emergency_response_requisition_count(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<i64, RepositoryError> {
+        let repo = RequisitionRepository::new(&ctx.connection);
+        repo.count(Some(
+            RequisitionFilter::new()
+                .store_id(EqualFilter::equal_to(store_id))
+                .r#type(RequisitionType::Response.equal_to())
+                .status(RequisitionStatus::New.equal_to())
+                .is_emergency(true),
         ))
     }
 
@@ -0,0 +1,53 @@
+use chrono::NaiveDate;
+use repository::{Invoice, RepositoryError};
+
+use crate::service_provider::ServiceContext;
+
+use self::{
+    near_expiry::{get_near_expiry_stock, NearExpiryStock, NearExpiryStockFilter},
+    redistribution::{
+        create_redistribution_shipment, get_redistribution_suggestions,
+        CreateRedistributionShipment, CreateRedistributionShipmentError,
+        GetRedistributionSuggestionsError, RedistributionSuggestion,
+    },
+};
+
+pub mod near_expiry;
+pub mod redistribution;
+
+#[cfg(test)]
+mod test;
+
+pub trait ExpiryManagementServiceTrait: Sync + Send {
+    /// Near expiry stock of the store with the value at risk of expiring before use
+    fn get_near_expiry_stock(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        filter: Option<NearExpiryStockFilter>,
+        today: NaiveDate,
+    ) -> Result<Vec<NearExpiryStock>, RepositoryError> {
+        get_near_expiry_stock(ctx, store_id, filter, today)
+    }
+
+    fn get_redistribution_suggestions(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        stock_line_id: &str,
+        today: NaiveDate,
+    ) -> Result<Vec<RedistributionSuggestion>, GetRedistributionSuggestionsError> {
+        get_redistribution_suggestions(ctx, store_id, stock_line_id, today)
+    }
+
+    fn create_redistribution_shipment(
+        &self,
+        ctx: &ServiceContext,
+        input: CreateRedistributionShipment,
+    ) -> Result<Invoice, CreateRedistributionShipmentError> {
+        create_redistribution_shipment(ctx, input)
+    }
+}
+
+pub struct ExpiryManagementService;
+impl ExpiryManagementServiceTrait for ExpiryManagementService {}
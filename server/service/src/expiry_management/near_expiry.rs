@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate};
+use repository::{
+    DateFilter, EqualFilter, Pagination, RepositoryError, StockLine, StockLineFilter,
+    StockLineRepository, StockLineSort, StockLineSortField,
+};
+use util::constants::NUMBER_OF_DAYS_IN_A_MONTH;
+
+use crate::{
+    item_stats::{get_item_stats, ItemStatsFilter},
+    service_provider::ServiceContext,
+};
+
+/// Stock expiring within this number of days is near expiry, if not specified in filter
+pub const DEFAULT_NEAR_EXPIRY_DAYS: u32 = 90;
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct NearExpiryStockFilter {
+    pub item_id: Option<EqualFilter<String>>,
+    pub expiring_within_days: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NearExpiryStock {
+    pub stock_line: StockLine,
+    /// Zero or negative when already expired
+    pub days_until_expiry: i64,
+    /// Average monthly consumption of the item in the store (in units)
+    pub average_monthly_consumption: f64,
+    /// Packs expected to be used before expiry, stock of the item that expires earlier is
+    /// expected to be used first
+    pub expected_consumed_number_of_packs: f64,
+    /// Packs expected to remain unused at expiry
+    pub surplus_number_of_packs: f64,
+    /// Cost of the available packs
+    pub total_value: f64,
+    /// Cost of the surplus packs
+    pub value_at_risk: f64,
+}
+
+impl NearExpiryStockFilter {
+    pub fn new() -> NearExpiryStockFilter {
+        Self::default()
+    }
+
+    pub fn item_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.item_id = Some(filter);
+        self
+    }
+
+    pub fn expiring_within_days(mut self, value: u32) -> Self {
+        self.expiring_within_days = Some(value);
+        self
+    }
+}
+
+/// Available stock of the store expiring within `expiring_within_days` of `today` (including
+/// expired stock), ordered by expiry date
+pub fn get_near_expiry_stock(
+    ctx: &ServiceContext,
+    store_id: &str,
+    filter: Option<NearExpiryStockFilter>,
+    today: NaiveDate,
+) -> Result<Vec<NearExpiryStock>, RepositoryError> {
+    let NearExpiryStockFilter {
+        item_id,
+        expiring_within_days,
+    } = filter.unwrap_or_default();
+    let expiring_within_days = expiring_within_days.unwrap_or(DEFAULT_NEAR_EXPIRY_DAYS);
+
+    let mut stock_line_filter = StockLineFilter::new()
+        .store_id(EqualFilter::equal_to(store_id))
+        .expiry_date(DateFilter::before_or_equal_to(
+            today + Duration::days(expiring_within_days as i64),
+        ))
+        .is_available(true);
+    stock_line_filter.item_id = item_id;
+
+    let stock_lines = StockLineRepository::new(&ctx.connection).query(
+        Pagination::all(),
+        Some(stock_line_filter),
+        Some(StockLineSort {
+            key: StockLineSortField::ExpiryDate,
+            desc: Some(false),
+        }),
+        Some(store_id.to_string()),
+    )?;
+
+    if stock_lines.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut item_ids: Vec<String> = stock_lines
+        .iter()
+        .map(|stock_line| stock_line.item_row.id.clone())
+        .collect();
+    item_ids.sort();
+    item_ids.dedup();
+
+    let average_monthly_consumption: HashMap<String, f64> = get_item_stats(
+        ctx,
+        store_id,
+        None,
+        Some(ItemStatsFilter::new().item_id(EqualFilter::equal_any(item_ids))),
+    )?
+    .into_iter()
+    .map(|item_stats| (item_stats.item_id, item_stats.average_monthly_consumption))
+    .collect();
+
+    // Units of each item expiring before the current stock line, these are expected to be
+    // consumed first
+    let mut units_expiring_earlier: HashMap<String, f64> = HashMap::new();
+
+    let result = stock_lines
+        .into_iter()
+        .map(|stock_line| {
+            let row = &stock_line.stock_line_row;
+            let item_id = stock_line.item_row.id.clone();
+            let days_until_expiry = row
+                .expiry_date
+                .map(|expiry_date| (expiry_date - today).num_days())
+                .unwrap_or_default();
+            let average_monthly_consumption = average_monthly_consumption
+                .get(&item_id)
+                .copied()
+                .unwrap_or_default();
+
+            let available_units = stock_line.available_quantity();
+            let expected_consumed_units = if days_until_expiry > 0 {
+                let units_expiring_earlier = units_expiring_earlier.entry(item_id).or_insert(0.0);
+                let expected_demand = average_monthly_consumption / NUMBER_OF_DAYS_IN_A_MONTH
+                    * days_until_expiry as f64;
+                let consumed = (expected_demand - *units_expiring_earlier)
+                    .max(0.0)
+                    .min(available_units);
+                *units_expiring_earlier += available_units;
+                consumed
+            } else {
+                0.0
+            };
+
+            let expected_consumed_number_of_packs = if row.pack_size > 0.0 {
+                expected_consumed_units / row.pack_size
+            } else {
+                0.0
+            };
+            let surplus_number_of_packs =
+                row.available_number_of_packs - expected_consumed_number_of_packs;
+
+            NearExpiryStock {
+                days_until_expiry,
+                average_monthly_consumption,
+                expected_consumed_number_of_packs,
+                surplus_number_of_packs,
+                total_value: row.available_number_of_packs * row.cost_price_per_pack,
+                value_at_risk: surplus_number_of_packs * row.cost_price_per_pack,
+                stock_line,
+            }
+        })
+        .collect();
+
+    Ok(result)
+}
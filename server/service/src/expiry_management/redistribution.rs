@@ -0,0 +1,259 @@
+use chrono::NaiveDate;
+use repository::{
+    EqualFilter, Invoice, Name, NameFilter, NameRepository, RepositoryError, StockLineFilter,
+    StockLineRepository,
+};
+use util::constants::NUMBER_OF_DAYS_IN_A_MONTH;
+
+use crate::{
+    invoice::{
+        outbound_shipment::insert::{
+            insert_outbound_shipment, InsertOutboundShipment, InsertOutboundShipmentError,
+        },
+        query::get_invoice,
+    },
+    invoice_line::stock_out_line::{
+        insert_stock_out_line, InsertStockOutLine, InsertStockOutLineError, StockOutType,
+    },
+    item_stats::{get_item_stats, ItemStatsFilter},
+    service_provider::ServiceContext,
+    sync::ActiveStoresOnSite,
+};
+
+use super::near_expiry::{get_near_expiry_stock, NearExpiryStockFilter};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RedistributionSuggestion {
+    pub customer: Name,
+    /// Stock on hand and consumption are only known for customer stores active on this site,
+    /// `None` otherwise
+    pub available_stock_on_hand: Option<f64>,
+    pub average_monthly_consumption: Option<f64>,
+    /// Packs the customer is expected to use before the stock expires (limited to the surplus)
+    pub suggested_number_of_packs: Option<f64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GetRedistributionSuggestionsError {
+    StockLineDoesNotExist,
+    NotThisStoreStockLine,
+    StockLineHasNoExpiryDate,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct RedistributionShipmentLine {
+    pub id: String,
+    pub stock_line_id: String,
+    pub number_of_packs: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct CreateRedistributionShipment {
+    pub id: String,
+    /// Customer receiving the stock
+    pub other_party_id: String,
+    pub comment: Option<String>,
+    pub lines: Vec<RedistributionShipmentLine>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CreateRedistributionShipmentError {
+    NoLines,
+    ShipmentError(InsertOutboundShipmentError),
+    LineError {
+        line_id: String,
+        error: InsertStockOutLineError,
+    },
+    CreatedInvoiceDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+/// Customer stores (from the store's name_store_join) that could use the surplus of a near
+/// expiry stock line, customers with the most expected use first. Stock and consumption of
+/// customer stores on other sites are not known (their data on this site is incomplete).
+pub fn get_redistribution_suggestions(
+    ctx: &ServiceContext,
+    store_id: &str,
+    stock_line_id: &str,
+    today: NaiveDate,
+) -> Result<Vec<RedistributionSuggestion>, GetRedistributionSuggestionsError> {
+    use GetRedistributionSuggestionsError as Error;
+
+    let stock_line = StockLineRepository::new(&ctx.connection)
+        .query_by_filter(
+            StockLineFilter::new().id(EqualFilter::equal_to(stock_line_id)),
+            Some(store_id.to_string()),
+        )?
+        .pop()
+        .ok_or(Error::StockLineDoesNotExist)?;
+
+    if stock_line.stock_line_row.store_id != store_id {
+        return Err(Error::NotThisStoreStockLine);
+    }
+
+    let expiry_date = stock_line
+        .stock_line_row
+        .expiry_date
+        .ok_or(Error::StockLineHasNoExpiryDate)?;
+    let days_until_expiry = (expiry_date - today).num_days().max(0);
+    let item_id = stock_line.item_row.id.clone();
+    let pack_size = stock_line.stock_line_row.pack_size;
+
+    let surplus_number_of_packs = get_near_expiry_stock(
+        ctx,
+        store_id,
+        Some(
+            NearExpiryStockFilter::new()
+                .item_id(EqualFilter::equal_to(&item_id))
+                .expiring_within_days(days_until_expiry as u32),
+        ),
+        today,
+    )?
+    .into_iter()
+    .find(|near_expiry| near_expiry.stock_line.stock_line_row.id == stock_line_id)
+    .map(|near_expiry| near_expiry.surplus_number_of_packs)
+    .unwrap_or_default();
+
+    let active_store_ids = match ActiveStoresOnSite::get(&ctx.connection) {
+        Ok(active_stores) => active_stores.store_ids(),
+        // Site is not initialised
+        Err(_) => Vec::new(),
+    };
+    let customers = NameRepository::new(&ctx.connection)
+        .query_by_filter(store_id, NameFilter::new().is_customer(true).is_store(true))?;
+
+    let mut suggestions = Vec::new();
+    for customer in customers {
+        let Some(customer_store_id) = customer.store_id().map(str::to_string) else {
+            continue;
+        };
+        if customer_store_id == store_id {
+            continue;
+        }
+
+        let item_stats = if active_store_ids.contains(&customer_store_id) {
+            get_item_stats(
+                ctx,
+                &customer_store_id,
+                None,
+                Some(ItemStatsFilter::new().item_id(EqualFilter::equal_to(&item_id))),
+            )?
+            .pop()
+        } else {
+            None
+        };
+
+        let suggestion = match item_stats {
+            Some(item_stats) => {
+                let expected_use = item_stats.average_monthly_consumption
+                    / NUMBER_OF_DAYS_IN_A_MONTH
+                    * days_until_expiry as f64;
+                let needed_units = (expected_use - item_stats.available_stock_on_hand).max(0.0);
+                let needed_packs = if pack_size > 0.0 {
+                    (needed_units / pack_size).floor()
+                } else {
+                    0.0
+                };
+
+                RedistributionSuggestion {
+                    customer,
+                    available_stock_on_hand: Some(item_stats.available_stock_on_hand),
+                    average_monthly_consumption: Some(item_stats.average_monthly_consumption),
+                    suggested_number_of_packs: Some(
+                        needed_packs.min(surplus_number_of_packs.max(0.0)),
+                    ),
+                }
+            }
+            None => RedistributionSuggestion {
+                customer,
+                available_stock_on_hand: None,
+                average_monthly_consumption: None,
+                suggested_number_of_packs: None,
+            },
+        };
+        suggestions.push(suggestion);
+    }
+
+    // Most suggested packs first, customers with unknown stock and consumption last
+    suggestions.sort_by(|a, b| {
+        let a_packs = a.suggested_number_of_packs.unwrap_or(-1.0);
+        let b_packs = b.suggested_number_of_packs.unwrap_or(-1.0);
+        b_packs
+            .total_cmp(&a_packs)
+            .then_with(|| a.customer.name_row.name.cmp(&b.customer.name_row.name))
+    });
+
+    Ok(suggestions)
+}
+
+/// Creates a new (draft) outbound shipment to the customer with the near expiry stock lines
+pub fn create_redistribution_shipment(
+    ctx: &ServiceContext,
+    input: CreateRedistributionShipment,
+) -> Result<Invoice, CreateRedistributionShipmentError> {
+    use CreateRedistributionShipmentError as Error;
+
+    if input.lines.is_empty() {
+        return Err(Error::NoLines);
+    }
+
+    let invoice = ctx
+        .connection
+        .transaction_sync(|_| {
+            let CreateRedistributionShipment {
+                id,
+                other_party_id,
+                comment,
+                lines,
+            } = input;
+
+            insert_outbound_shipment(
+                ctx,
+                InsertOutboundShipment {
+                    id: id.clone(),
+                    other_party_id,
+                    comment,
+                    ..Default::default()
+                },
+            )
+            .map_err(Error::ShipmentError)?;
+
+            for RedistributionShipmentLine {
+                id: line_id,
+                stock_line_id,
+                number_of_packs,
+            } in lines
+            {
+                insert_stock_out_line(
+                    ctx,
+                    InsertStockOutLine {
+                        id: line_id.clone(),
+                        r#type: StockOutType::OutboundShipment,
+                        invoice_id: id.clone(),
+                        stock_line_id,
+                        number_of_packs,
+                        ..Default::default()
+                    },
+                )
+                .map_err(|error| Error::LineError { line_id, error })?;
+            }
+
+            get_invoice(ctx, None, &id)?.ok_or(Error::CreatedInvoiceDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(invoice)
+}
+
+impl From<RepositoryError> for GetRedistributionSuggestionsError {
+    fn from(error: RepositoryError) -> Self {
+        GetRedistributionSuggestionsError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for CreateRedistributionShipmentError {
+    fn from(error: RepositoryError) -> Self {
+        CreateRedistributionShipmentError::DatabaseError(error)
+    }
+}
@@ -0,0 +1,272 @@
+use chrono::{Duration, Utc};
+use repository::{
+    mock::{mock_name_a, mock_name_store_a, mock_name_store_b, mock_store_a, mock_store_b},
+    mock::{MockData, MockDataInserts},
+    test_db::setup_all_with_data,
+    EqualFilter, InvoiceLineRow, InvoiceLineType, InvoiceRow, InvoiceRowRepository, InvoiceStatus,
+    InvoiceType, ItemRow, ItemType, KeyType, KeyValueStoreRepository, StockLineRow,
+    StockLineRowRepository, StoreRow, StoreRowRepository,
+};
+use util::{inline_init, uuid::uuid};
+
+use crate::{
+    expiry_management::{
+        near_expiry::NearExpiryStockFilter,
+        redistribution::{
+            CreateRedistributionShipment, CreateRedistributionShipmentError,
+            GetRedistributionSuggestionsError, RedistributionShipmentLine,
+        },
+    },
+    invoice_line::stock_out_line::InsertStockOutLineError,
+    service_provider::ServiceProvider,
+};
+
+fn item() -> ItemRow {
+    inline_init(|r: &mut ItemRow| {
+        r.id = "near_expiry_item".to_string();
+        r.name = "Near expiry item".to_string();
+        r.code = "near_expiry_item".to_string();
+        r.r#type = ItemType::Stock;
+    })
+}
+
+fn stock_line(id: &str, store_id: &str, packs: f64, cost: f64, days: Option<i64>) -> StockLineRow {
+    inline_init(|r: &mut StockLineRow| {
+        r.id = id.to_string();
+        r.item_link_id = item().id;
+        r.store_id = store_id.to_string();
+        r.pack_size = 1.0;
+        r.available_number_of_packs = packs;
+        r.total_number_of_packs = packs;
+        r.cost_price_per_pack = cost;
+        r.expiry_date = days.map(|days| Utc::now().naive_utc().date() + Duration::days(days));
+    })
+}
+
+/// Picked outbound shipment of `units` of the item from the store 10 days ago
+fn consumption(store_id: &str, name_id: &str, units: f64) -> MockData {
+    let invoice_id = uuid();
+    inline_init(|r: &mut MockData| {
+        r.invoices = vec![inline_init(|r: &mut InvoiceRow| {
+            r.id.clone_from(&invoice_id);
+            r.store_id = store_id.to_string();
+            r.name_link_id = name_id.to_string();
+            r.r#type = InvoiceType::OutboundShipment;
+            r.status = InvoiceStatus::Picked;
+            r.picked_datetime = Some(Utc::now().naive_utc() - Duration::days(10));
+        })];
+        r.invoice_lines = vec![inline_init(|r: &mut InvoiceLineRow| {
+            r.id = format!("{}_line", invoice_id);
+            r.invoice_id.clone_from(&invoice_id);
+            r.item_link_id = item().id;
+            r.r#type = InvoiceLineType::StockOut;
+            r.pack_size = 1.0;
+            r.number_of_packs = units;
+        })];
+    })
+}
+
+#[actix_rt::test]
+async fn expiry_management() {
+    let (_, connection, connection_manager, _) = setup_all_with_data(
+        "expiry_management",
+        MockDataInserts::all(),
+        inline_init(|r: &mut MockData| {
+            r.items = vec![item()];
+            r.stock_lines = vec![
+                stock_line("expired", &mock_store_a().id, 5.0, 3.0, Some(-1)),
+                stock_line("expiring_first", &mock_store_a().id, 10.0, 2.0, Some(10)),
+                stock_line("expiring_second", &mock_store_a().id, 100.0, 1.0, Some(60)),
+                stock_line("not_near_expiry", &mock_store_a().id, 100.0, 1.0, Some(200)),
+                stock_line("no_expiry", &mock_store_a().id, 100.0, 1.0, None),
+                stock_line("store_b_stock", &mock_store_b().id, 5.0, 1.0, None),
+            ];
+        })
+        // AMC of 30 units in store a (1 per day) and 20 units in store b
+        .join(consumption(
+            &mock_store_a().id,
+            &mock_name_store_b().id,
+            90.0,
+        ))
+        .join(consumption(&mock_store_b().id, &mock_name_a().id, 60.0)),
+    )
+    .await;
+
+    let service_provider = ServiceProvider::new(connection_manager, "app_data");
+    let context = service_provider
+        .context(mock_store_a().id, "".to_string())
+        .unwrap();
+    let service = service_provider.expiry_management_service;
+    let today = Utc::now().naive_utc().date();
+
+    // Near expiry stock
+    let near_expiry = service
+        .get_near_expiry_stock(
+            &context,
+            &mock_store_a().id,
+            Some(NearExpiryStockFilter::new().item_id(EqualFilter::equal_to(&item().id))),
+            today,
+        )
+        .unwrap();
+
+    assert_eq!(
+        near_expiry
+            .iter()
+            .map(|r| r.stock_line.stock_line_row.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["expired", "expiring_first", "expiring_second"]
+    );
+    // Expired stock is all at risk
+    assert_eq!(near_expiry[0].days_until_expiry, -1);
+    assert_eq!(near_expiry[0].surplus_number_of_packs, 5.0);
+    assert_eq!(near_expiry[0].value_at_risk, 15.0);
+    // Used up before expiry
+    assert_eq!(near_expiry[1].average_monthly_consumption, 30.0);
+    assert_eq!(near_expiry[1].expected_consumed_number_of_packs, 10.0);
+    assert_eq!(near_expiry[1].surplus_number_of_packs, 0.0);
+    assert_eq!(near_expiry[1].value_at_risk, 0.0);
+    // 60 days of consumption, 10 of which come from the line expiring first
+    assert_eq!(near_expiry[2].expected_consumed_number_of_packs, 50.0);
+    assert_eq!(near_expiry[2].surplus_number_of_packs, 50.0);
+    assert_eq!(near_expiry[2].total_value, 100.0);
+    assert_eq!(near_expiry[2].value_at_risk, 50.0);
+
+    let near_expiry = service
+        .get_near_expiry_stock(
+            &context,
+            &mock_store_a().id,
+            Some(
+                NearExpiryStockFilter::new()
+                    .item_id(EqualFilter::equal_to(&item().id))
+                    .expiring_within_days(365),
+            ),
+            today,
+        )
+        .unwrap();
+    assert_eq!(near_expiry.len(), 4);
+
+    // Redistribution suggestions
+    assert_eq!(
+        service.get_redistribution_suggestions(&context, &mock_store_a().id, "invalid", today),
+        Err(GetRedistributionSuggestionsError::StockLineDoesNotExist)
+    );
+    assert_eq!(
+        service.get_redistribution_suggestions(
+            &context,
+            &mock_store_a().id,
+            "store_b_stock",
+            today
+        ),
+        Err(GetRedistributionSuggestionsError::NotThisStoreStockLine)
+    );
+    assert_eq!(
+        service.get_redistribution_suggestions(&context, &mock_store_a().id, "no_expiry", today),
+        Err(GetRedistributionSuggestionsError::StockLineHasNoExpiryDate)
+    );
+
+    // Stock and consumption of customer stores on other sites are not known
+    KeyValueStoreRepository::new(&connection)
+        .set_i32(KeyType::SettingsSyncSiteId, Some(mock_store_a().site_id))
+        .unwrap();
+    let suggestions = service
+        .get_redistribution_suggestions(&context, &mock_store_a().id, "expiring_second", today)
+        .unwrap();
+    let suggestion = suggestions
+        .iter()
+        .find(|s| s.customer.name_row.id == mock_name_store_b().id)
+        .unwrap();
+    assert_eq!(suggestion.available_stock_on_hand, None);
+    assert_eq!(suggestion.average_monthly_consumption, None);
+    assert_eq!(suggestion.suggested_number_of_packs, None);
+
+    StoreRowRepository::new(&connection)
+        .upsert_one(&StoreRow {
+            site_id: mock_store_a().site_id,
+            ..mock_store_b()
+        })
+        .unwrap();
+    let suggestions = service
+        .get_redistribution_suggestions(&context, &mock_store_a().id, "expiring_second", today)
+        .unwrap();
+    // Store b uses 40 units in 60 days and has 5 units on hand
+    let suggestion = &suggestions[0];
+    assert_eq!(suggestion.customer.name_row.id, mock_name_store_b().id);
+    assert_eq!(suggestion.available_stock_on_hand, Some(5.0));
+    assert_eq!(suggestion.average_monthly_consumption, Some(20.0));
+    assert_eq!(suggestion.suggested_number_of_packs, Some(35.0));
+    assert!(suggestions
+        .iter()
+        .all(|s| s.customer.name_row.id != mock_name_store_a().id));
+
+    // Redistribution shipment
+    assert_eq!(
+        service.create_redistribution_shipment(
+            &context,
+            CreateRedistributionShipment {
+                id: "redistribution".to_string(),
+                other_party_id: mock_name_store_b().id,
+                ..Default::default()
+            }
+        ),
+        Err(CreateRedistributionShipmentError::NoLines)
+    );
+
+    assert_eq!(
+        service.create_redistribution_shipment(
+            &context,
+            CreateRedistributionShipment {
+                id: "redistribution".to_string(),
+                other_party_id: mock_name_store_b().id,
+                lines: vec![
+                    RedistributionShipmentLine {
+                        id: "redistribution_line".to_string(),
+                        stock_line_id: "expiring_second".to_string(),
+                        number_of_packs: 35.0,
+                    },
+                    RedistributionShipmentLine {
+                        id: "invalid_line".to_string(),
+                        stock_line_id: "invalid".to_string(),
+                        number_of_packs: 1.0,
+                    }
+                ],
+                ..Default::default()
+            }
+        ),
+        Err(CreateRedistributionShipmentError::LineError {
+            line_id: "invalid_line".to_string(),
+            error: InsertStockOutLineError::StockLineNotFound
+        })
+    );
+    // Nothing created
+    assert!(InvoiceRowRepository::new(&connection)
+        .find_one_by_id("redistribution")
+        .unwrap()
+        .is_none());
+
+    let invoice = service
+        .create_redistribution_shipment(
+            &context,
+            CreateRedistributionShipment {
+                id: "redistribution".to_string(),
+                other_party_id: mock_name_store_b().id,
+                comment: Some("Near expiry".to_string()),
+                lines: vec![RedistributionShipmentLine {
+                    id: "redistribution_line".to_string(),
+                    stock_line_id: "expiring_second".to_string(),
+                    number_of_packs: 35.0,
+                }],
+            },
+        )
+        .unwrap();
+    assert_eq!(invoice.invoice_row.r#type, InvoiceType::OutboundShipment);
+    assert_eq!(invoice.invoice_row.status, InvoiceStatus::New);
+    assert_eq!(invoice.invoice_row.comment, Some("Near expiry".to_string()));
+    assert_eq!(invoice.other_party_name(), mock_name_store_b().name);
+
+    let stock_line = StockLineRowRepository::new(&connection)
+        .find_one_by_id("expiring_second")
+        .unwrap()
+        .unwrap();
+    assert_eq!(stock_line.available_number_of_packs, 65.0);
+    assert_eq!(stock_line.total_number_of_packs, 100.0);
+}
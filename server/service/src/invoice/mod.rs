@@ -165,6 +165,17 @@ pub trait InvoiceServiceTrait: Sync + Send {
         outbound_shipment::add_from_master_list(ctx, input)
     }
 
+    fn allocate_outbound_shipment(
+        &self,
+        ctx: &ServiceContext,
+        invoice_id: &str,
+    ) -> Result<
+        outbound_shipment::AllocateOutboundShipmentResult,
+        outbound_shipment::AllocateOutboundShipmentError,
+    > {
+        outbound_shipment::allocate_outbound_shipment(ctx, invoice_id)
+    }
+
     fn add_to_inbound_shipment_from_master_list(
         &self,
         ctx: &ServiceContext,
@@ -0,0 +1,296 @@
+use repository::{
+    AllocationStrategy, EqualFilter, InvoiceLine, InvoiceLineFilter, InvoiceLineRepository,
+    InvoiceLineType, InvoiceRow, InvoiceStatus, InvoiceType, RepositoryError, StockLine,
+    StorageConnection,
+};
+
+use crate::{
+    invoice::check_invoice_exists,
+    invoice_line::outbound_shipment_unallocated_line::{
+        allocate_outbound_shipment_unallocated_line, AllocateLineResult,
+        AllocateOutboundShipmentUnallocatedLineError,
+    },
+    service_provider::ServiceContext,
+    store_preference::get_store_preferences,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum AllocateOutboundShipmentError {
+    ShipmentDoesNotExist,
+    NotThisStoreShipment,
+    CannotEditShipment,
+    NotAnOutboundShipment,
+    // Internal
+    AllocateLine {
+        line_id: String,
+        error: AllocateOutboundShipmentUnallocatedLineError,
+    },
+    DatabaseError(RepositoryError),
+}
+
+type OutError = AllocateOutboundShipmentError;
+
+impl From<RepositoryError> for AllocateOutboundShipmentError {
+    fn from(error: RepositoryError) -> Self {
+        AllocateOutboundShipmentError::DatabaseError(error)
+    }
+}
+
+/// Quantity of an item that could not be allocated, left on the unallocated line
+#[derive(Debug, PartialEq)]
+pub struct AllocationShortfall {
+    pub item_id: String,
+    pub item_name: String,
+    pub requested_quantity: f64,
+    pub remaining_quantity: f64,
+}
+
+/// Allocation results of all unallocated lines in the shipment
+#[derive(Default, Debug, PartialEq)]
+pub struct AllocateOutboundShipmentResult {
+    pub inserts: Vec<InvoiceLine>,
+    pub deletes: Vec<String>,
+    pub updates: Vec<InvoiceLine>,
+    pub skipped_expired_stock_lines: Vec<StockLine>,
+    pub skipped_on_hold_stock_lines: Vec<StockLine>,
+    pub issued_expiring_soon_stock_lines: Vec<StockLine>,
+    pub shortfalls: Vec<AllocationShortfall>,
+    pub allocation_strategy: AllocationStrategy,
+}
+
+pub fn allocate_outbound_shipment(
+    ctx: &ServiceContext,
+    invoice_id: &str,
+) -> Result<AllocateOutboundShipmentResult, OutError> {
+    let result = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(connection, &ctx.store_id, invoice_id)?;
+            let unallocated_lines = get_unallocated_lines(connection, invoice_id)?;
+
+            let mut result = AllocateOutboundShipmentResult {
+                allocation_strategy: get_store_preferences(connection, &ctx.store_id)?
+                    .allocation_strategy,
+                ..Default::default()
+            };
+
+            for line in unallocated_lines {
+                let line_id = line.invoice_line_row.id.clone();
+                let line_result = allocate_outbound_shipment_unallocated_line(ctx, line_id.clone())
+                    .map_err(|error| OutError::AllocateLine { line_id, error })?;
+
+                add_line_result(&mut result, line, line_result);
+            }
+
+            Ok(result) as Result<AllocateOutboundShipmentResult, OutError>
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(result)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    invoice_id: &str,
+) -> Result<InvoiceRow, OutError> {
+    let invoice_row =
+        check_invoice_exists(invoice_id, connection)?.ok_or(OutError::ShipmentDoesNotExist)?;
+
+    if invoice_row.store_id != store_id {
+        return Err(OutError::NotThisStoreShipment);
+    }
+    if invoice_row.r#type != InvoiceType::OutboundShipment {
+        return Err(OutError::NotAnOutboundShipment);
+    }
+    if invoice_row.status == InvoiceStatus::Shipped
+        || invoice_row.status == InvoiceStatus::Delivered
+        || invoice_row.status == InvoiceStatus::Verified
+    {
+        return Err(OutError::CannotEditShipment);
+    }
+
+    Ok(invoice_row)
+}
+
+fn get_unallocated_lines(
+    connection: &StorageConnection,
+    invoice_id: &str,
+) -> Result<Vec<InvoiceLine>, RepositoryError> {
+    InvoiceLineRepository::new(connection).query_by_filter(
+        InvoiceLineFilter::new()
+            .invoice_id(EqualFilter::equal_to(invoice_id))
+            .r#type(InvoiceLineType::UnallocatedStock.equal_to()),
+    )
+}
+
+fn add_line_result(
+    result: &mut AllocateOutboundShipmentResult,
+    unallocated_line: InvoiceLine,
+    AllocateLineResult {
+        mut inserts,
+        mut deletes,
+        mut updates,
+        mut skipped_expired_stock_lines,
+        mut skipped_on_hold_stock_lines,
+        mut issued_expiring_soon_stock_lines,
+        allocation_strategy: _,
+    }: AllocateLineResult,
+) {
+    // Unallocated line is updated rather than deleted when it could not be fully allocated
+    let remaining_line = updates.iter().find(|line| {
+        line.invoice_line_row.id == unallocated_line.invoice_line_row.id
+            && line.invoice_line_row.r#type == InvoiceLineType::UnallocatedStock
+    });
+    if let Some(remaining_line) = remaining_line {
+        result.shortfalls.push(AllocationShortfall {
+            item_id: unallocated_line.item_row.id.clone(),
+            item_name: unallocated_line.item_row.name.clone(),
+            requested_quantity: unallocated_line.invoice_line_row.number_of_packs,
+            remaining_quantity: remaining_line.invoice_line_row.number_of_packs,
+        });
+    }
+
+    result.inserts.append(&mut inserts);
+    result.deletes.append(&mut deletes);
+    result.updates.append(&mut updates);
+    result
+        .skipped_expired_stock_lines
+        .append(&mut skipped_expired_stock_lines);
+    result
+        .skipped_on_hold_stock_lines
+        .append(&mut skipped_on_hold_stock_lines);
+    result
+        .issued_expiring_soon_stock_lines
+        .append(&mut issued_expiring_soon_stock_lines);
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::{
+            mock_item_a, mock_item_b, mock_name_a, mock_store_a, mock_store_b, MockData,
+            MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineType, InvoiceRow, InvoiceStatus, InvoiceType, StockLineRow,
+    };
+    use util::inline_init;
+
+    use crate::{
+        invoice::outbound_shipment::{
+            AllocateOutboundShipmentError as ServiceError, AllocationShortfall,
+        },
+        service_provider::ServiceProvider,
+    };
+
+    #[actix_rt::test]
+    async fn allocate_outbound_shipment() {
+        fn invoice() -> InvoiceRow {
+            inline_init(|r: &mut InvoiceRow| {
+                r.id = "invoice".to_string();
+                r.store_id = mock_store_a().id;
+                r.name_link_id = mock_name_a().id;
+                r.r#type = InvoiceType::OutboundShipment;
+                r.status = InvoiceStatus::New;
+            })
+        }
+
+        fn inbound_shipment() -> InvoiceRow {
+            inline_init(|r: &mut InvoiceRow| {
+                r.id = "inbound_shipment".to_string();
+                r.store_id = mock_store_a().id;
+                r.name_link_id = mock_name_a().id;
+                r.r#type = InvoiceType::InboundShipment;
+            })
+        }
+
+        fn unallocated_line(id: &str, item_id: &str, number_of_packs: f64) -> InvoiceLineRow {
+            inline_init(|r: &mut InvoiceLineRow| {
+                r.id = id.to_string();
+                r.invoice_id = invoice().id;
+                r.item_link_id = item_id.to_string();
+                r.r#type = InvoiceLineType::UnallocatedStock;
+                r.number_of_packs = number_of_packs;
+                r.pack_size = 1.0;
+            })
+        }
+
+        fn stock_line(id: &str, item_id: &str, on_hold: bool) -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = id.to_string();
+                r.store_id = mock_store_a().id;
+                r.item_link_id = item_id.to_string();
+                r.pack_size = 1.0;
+                r.available_number_of_packs = 10.0;
+                r.on_hold = on_hold;
+                r.expiry_date = Some(NaiveDate::from_ymd_opt(3021, 1, 1).unwrap());
+            })
+        }
+
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "allocate_outbound_shipment",
+            MockDataInserts::none()
+                .stores()
+                .items()
+                .names()
+                .units()
+                .currencies(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![invoice(), inbound_shipment()];
+                r.invoice_lines = vec![
+                    unallocated_line("line_a", &mock_item_a().id, 5.0),
+                    unallocated_line("line_b", &mock_item_b().id, 15.0),
+                ];
+                r.stock_lines = vec![
+                    stock_line("stock_line_a", &mock_item_a().id, false),
+                    stock_line("stock_line_b", &mock_item_b().id, false),
+                    stock_line("stock_line_b_on_hold", &mock_item_b().id, true),
+                ];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let mut context = service_provider
+            .context(mock_store_a().id, "".to_string())
+            .unwrap();
+        let service = service_provider.invoice_service;
+
+        // ShipmentDoesNotExist
+        assert_eq!(
+            service.allocate_outbound_shipment(&context, "invalid"),
+            Err(ServiceError::ShipmentDoesNotExist)
+        );
+        // NotAnOutboundShipment
+        assert_eq!(
+            service.allocate_outbound_shipment(&context, &inbound_shipment().id),
+            Err(ServiceError::NotAnOutboundShipment)
+        );
+        // NotThisStoreShipment
+        context.store_id = mock_store_b().id;
+        assert_eq!(
+            service.allocate_outbound_shipment(&context, &invoice().id),
+            Err(ServiceError::NotThisStoreShipment)
+        );
+        context.store_id = mock_store_a().id;
+
+        // Success
+        let result = service
+            .allocate_outbound_shipment(&context, &invoice().id)
+            .unwrap();
+
+        assert_eq!(result.inserts.len(), 2);
+        assert_eq!(result.deletes, vec!["line_a".to_string()]);
+        assert_eq!(result.skipped_on_hold_stock_lines.len(), 1);
+        assert_eq!(
+            result.shortfalls,
+            vec![AllocationShortfall {
+                item_id: mock_item_b().id,
+                item_name: mock_item_b().name,
+                requested_quantity: 15.0,
+                remaining_quantity: 5.0,
+            }]
+        );
+    }
+}
@@ -13,3 +13,6 @@ pub use self::add_from_master_list::*;
 
 pub mod update_name;
 pub use self::update_name::*;
+
+mod allocate;
+pub use self::allocate::*;
@@ -1,22 +1,27 @@
 use std::cmp::Ordering;
 
 use repository::{
-    EqualFilter, InvoiceLine, InvoiceLineFilter, InvoiceLineRepository, InvoiceLineType,
-    Pagination, RepositoryError, StockLine, StockLineFilter, StockLineRepository, StockLineSort,
-    StockLineSortField, StorageConnection,
+    AllocationStrategy, EqualFilter, InvoiceLine, InvoiceLineFilter, InvoiceLineRepository,
+    InvoiceLineType, Pagination, RepositoryError, StockLine, StockLineFilter, StockLineRepository,
+    StockLineSort, StockLineSortField, StorageConnection,
 };
 use util::{
     constants::stock_line_expiring_soon_offset, date_now, date_now_with_offset,
     fraction_is_integer, uuid,
 };
 
-use crate::invoice_line::{
-    outbound_shipment_unallocated_line::{
-        DeleteOutboundShipmentUnallocatedLine, UpdateOutboundShipmentUnallocatedLine,
+use crate::{
+    invoice_line::{
+        outbound_shipment_unallocated_line::{
+            DeleteOutboundShipmentUnallocatedLine, UpdateOutboundShipmentUnallocatedLine,
+        },
+        stock_out_line::{InsertStockOutLine, StockOutType, UpdateStockOutLine},
     },
-    stock_out_line::{InsertStockOutLine, StockOutType, UpdateStockOutLine},
+    store_preference::get_store_preferences,
 };
 
+use super::strategy::{get_stock_line_sorter, ByRemainingHeatStability, StockLineSorter};
+
 #[derive(Default)]
 pub struct GenerateOutput {
     pub update_lines: Vec<UpdateStockOutLine>,
@@ -26,6 +31,7 @@ pub struct GenerateOutput {
     pub skipped_expired_stock_lines: Vec<StockLine>,
     pub skipped_on_hold_stock_lines: Vec<StockLine>,
     pub issued_expiring_soon_stock_lines: Vec<StockLine>,
+    pub allocation_strategy: AllocationStrategy,
 }
 
 pub fn generate(
@@ -33,7 +39,11 @@ pub fn generate(
     store_id: &str,
     unallocated_line: InvoiceLine,
 ) -> Result<GenerateOutput, RepositoryError> {
-    let mut result = GenerateOutput::default();
+    let allocation_strategy = get_store_preferences(connection, store_id)?.allocation_strategy;
+    let mut result = GenerateOutput {
+        allocation_strategy,
+        ..Default::default()
+    };
     let allocated_lines = get_allocated_lines(connection, &unallocated_line)?;
     // Assume pack_size 1 for unallocated line
     let mut remaining_to_allocate = unallocated_line.invoice_line_row.number_of_packs;
@@ -47,7 +57,13 @@ pub fn generate(
     // Asc, by expiry date, nulls last
     let sorted_available_stock_lines =
         get_sorted_available_stock_lines(connection, store_id, &unallocated_line)?;
-    // Use FEFO to allocate
+    // Reorder as per store allocation strategy (FEFO by default)
+    let sorted_available_stock_lines = get_stock_line_sorter(&allocation_strategy)
+        .sort(connection, sorted_available_stock_lines)?;
+    // Vaccines closest to their heat stability limit are used first
+    let sorted_available_stock_lines =
+        ByRemainingHeatStability.sort(connection, sorted_available_stock_lines)?;
+
     for stock_line in sorted_available_stock_lines {
         let can_use = get_stock_line_eligibility(&stock_line)
             .map(|eligibility| match eligibility {
@@ -120,8 +136,9 @@ enum StockLineAlert {
 fn get_stock_line_eligibility(stock_line: &StockLine) -> Option<StockLineAlert> {
     use StockLineAlert::*;
     let stock_line_row = &stock_line.stock_line_row;
-    // Expired
-    if stock_line_row.on_hold {
+    // On hold stock line or location
+    let location_on_hold = matches!(&stock_line.location_row, Some(location) if location.on_hold);
+    if stock_line_row.on_hold || location_on_hold {
         return Some(OnHold);
     }
 
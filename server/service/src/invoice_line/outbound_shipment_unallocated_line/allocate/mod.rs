@@ -8,7 +8,9 @@ use crate::{
     },
     service_provider::ServiceContext,
 };
-use repository::{InvoiceLine, InvoiceLineType, RepositoryError, StockLine, StorageConnection};
+use repository::{
+    AllocationStrategy, InvoiceLine, InvoiceLineType, RepositoryError, StockLine, StorageConnection,
+};
 
 use super::{
     delete_outbound_shipment_unallocated_line, update_outbound_shipment_unallocated_line,
@@ -17,6 +19,7 @@ use super::{
 };
 
 mod generate;
+pub mod strategy;
 mod test;
 use generate::{generate, GenerateOutput};
 
@@ -63,6 +66,8 @@ pub struct AllocateLineResult {
     pub skipped_expired_stock_lines: Vec<StockLine>,
     pub skipped_on_hold_stock_lines: Vec<StockLine>,
     pub issued_expiring_soon_stock_lines: Vec<StockLine>,
+    /// Strategy used to choose stock lines to allocate
+    pub allocation_strategy: AllocationStrategy,
 }
 
 type ServiceResult = AllocateLineResult;
@@ -83,6 +88,7 @@ pub fn allocate_outbound_shipment_unallocated_line(
                 skipped_expired_stock_lines,
                 skipped_on_hold_stock_lines,
                 issued_expiring_soon_stock_lines,
+                allocation_strategy,
             } = generate(connection, &ctx.store_id, unallocated_line)?;
 
             let mut result = ServiceResult {
@@ -92,6 +98,7 @@ pub fn allocate_outbound_shipment_unallocated_line(
                 skipped_expired_stock_lines,
                 skipped_on_hold_stock_lines,
                 issued_expiring_soon_stock_lines,
+                allocation_strategy,
             };
 
             for input in update_lines.into_iter() {
@@ -0,0 +1,182 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{NaiveDateTime, Utc};
+use repository::{
+    AllocationStrategy, EqualFilter, LedgerFilter, LedgerRepository, PackVariantFilter,
+    PackVariantRepository, Pagination, RepositoryError, StockLine, StorageConnection,
+};
+
+use crate::cold_chain::heat_exposure::get_heat_exposures;
+
+/// Decides the order in which available stock lines are allocated.
+/// Stock lines are passed in FEFO order (by expiry date, nulls last), sorting should be stable
+/// so that FEFO is kept as a tie breaker
+pub trait StockLineSorter: Sync + Send {
+    fn sort(
+        &self,
+        connection: &StorageConnection,
+        stock_lines: Vec<StockLine>,
+    ) -> Result<Vec<StockLine>, RepositoryError>;
+}
+
+pub fn get_stock_line_sorter(strategy: &AllocationStrategy) -> Box<dyn StockLineSorter> {
+    match strategy {
+        AllocationStrategy::Fefo => Box::new(Fefo),
+        AllocationStrategy::Fifo => Box::new(Fifo),
+        AllocationStrategy::Location => Box::new(ByLocation),
+        AllocationStrategy::PackVariant => Box::new(ByPackVariant),
+    }
+}
+
+pub struct Fefo;
+
+impl StockLineSorter for Fefo {
+    fn sort(
+        &self,
+        _: &StorageConnection,
+        stock_lines: Vec<StockLine>,
+    ) -> Result<Vec<StockLine>, RepositoryError> {
+        Ok(stock_lines)
+    }
+}
+
+/// FIFO for non expiring stock: stock lines with expiry date keep FEFO order and are allocated
+/// first, stock lines without expiry date are then ordered by received date (the date of the first
+/// stock movement into the stock line), stock lines without received date are allocated last
+pub struct Fifo;
+
+impl StockLineSorter for Fifo {
+    fn sort(
+        &self,
+        connection: &StorageConnection,
+        mut stock_lines: Vec<StockLine>,
+    ) -> Result<Vec<StockLine>, RepositoryError> {
+        let stock_line_ids: Vec<String> = stock_lines
+            .iter()
+            .filter(|line| line.stock_line_row.expiry_date.is_none())
+            .map(|line| line.stock_line_row.id.clone())
+            .collect();
+        if stock_line_ids.is_empty() {
+            return Ok(stock_lines);
+        }
+
+        let ledger = LedgerRepository::new(connection).query(
+            Pagination::all(),
+            Some(LedgerFilter::new().stock_line_id(EqualFilter::equal_any(stock_line_ids))),
+            None,
+        )?;
+
+        let mut received_datetimes: HashMap<String, NaiveDateTime> = HashMap::new();
+        for row in ledger.into_iter().filter(|row| row.quantity > 0.0) {
+            let Some(stock_line_id) = row.stock_line_id else {
+                continue;
+            };
+            received_datetimes
+                .entry(stock_line_id)
+                .and_modify(|datetime| *datetime = (*datetime).min(row.datetime))
+                .or_insert(row.datetime);
+        }
+
+        stock_lines.sort_by_key(|line| {
+            if line.stock_line_row.expiry_date.is_some() {
+                return (false, false, None);
+            }
+            let received = received_datetimes.get(&line.stock_line_row.id);
+            (true, received.is_none(), received.copied())
+        });
+
+        Ok(stock_lines)
+    }
+}
+
+/// By location code, stock lines without location are allocated last
+pub struct ByLocation;
+
+impl StockLineSorter for ByLocation {
+    fn sort(
+        &self,
+        _: &StorageConnection,
+        mut stock_lines: Vec<StockLine>,
+    ) -> Result<Vec<StockLine>, RepositoryError> {
+        stock_lines.sort_by_key(|line| {
+            let code = line
+                .location_row
+                .as_ref()
+                .map(|location| location.code.to_lowercase());
+            (code.is_none(), code)
+        });
+
+        Ok(stock_lines)
+    }
+}
+
+/// Stock lines with pack size matching an active pack variant of the item are allocated first,
+/// largest pack size first
+pub struct ByPackVariant;
+
+impl StockLineSorter for ByPackVariant {
+    fn sort(
+        &self,
+        connection: &StorageConnection,
+        mut stock_lines: Vec<StockLine>,
+    ) -> Result<Vec<StockLine>, RepositoryError> {
+        let item_ids: HashSet<String> = stock_lines
+            .iter()
+            .map(|line| line.item_row.id.clone())
+            .collect();
+        let pack_variants = PackVariantRepository::new(connection).query_by_filter(
+            PackVariantFilter::new()
+                .item_id(EqualFilter::equal_any(item_ids.into_iter().collect()))
+                .is_active(true),
+        )?;
+
+        let matches_pack_variant = |line: &StockLine| {
+            pack_variants.iter().any(|variant| {
+                variant.item_id == line.item_row.id
+                    && variant.pack_size == line.stock_line_row.pack_size
+            })
+        };
+
+        // Stable sort, only reorders matching lines by pack size
+        stock_lines.sort_by(
+            |a, b| match (matches_pack_variant(a), matches_pack_variant(b)) {
+                (true, true) => b
+                    .stock_line_row
+                    .pack_size
+                    .total_cmp(&a.stock_line_row.pack_size),
+                (true, false) => std::cmp::Ordering::Less,
+                (false, true) => std::cmp::Ordering::Greater,
+                (false, false) => std::cmp::Ordering::Equal,
+            },
+        );
+
+        Ok(stock_lines)
+    }
+}
+
+/// Vaccine stock lines with the least remaining heat stability budget are allocated first
+/// (use-first), applied on top of the store allocation strategy
+pub struct ByRemainingHeatStability;
+
+impl StockLineSorter for ByRemainingHeatStability {
+    fn sort(
+        &self,
+        connection: &StorageConnection,
+        mut stock_lines: Vec<StockLine>,
+    ) -> Result<Vec<StockLine>, RepositoryError> {
+        if !stock_lines.iter().any(|line| line.item_row.is_vaccine) {
+            return Ok(stock_lines);
+        }
+        let heat_exposures = get_heat_exposures(connection, &stock_lines, Utc::now().naive_utc())?;
+
+        stock_lines.sort_by_key(|line| {
+            let remaining = heat_exposures
+                .get(&line.stock_line_row.id)
+                .filter(|_| line.item_row.is_vaccine)
+                .map(|heat_exposure| heat_exposure.remaining_budget_minutes);
+            (remaining.is_none(), remaining)
+        });
+
+        Ok(stock_lines)
+    }
+}
@@ -1,15 +1,17 @@
 #[cfg(test)]
 mod test {
 
-    use chrono::{Duration, NaiveDate};
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
     use repository::{
         mock::{
             mock_item_a, mock_item_b, mock_name_a, mock_outbound_shipment_a_invoice_lines,
             mock_store_a, mock_store_b, MockData, MockDataInserts,
         },
         test_db::{setup_all, setup_all_with_data},
-        InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineType, InvoiceRow, InvoiceType,
-        StockLine, StockLineRow,
+        AllocationStrategy, InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineType, InvoiceRow,
+        InvoiceType, ItemRow, LocationMovementRow, LocationMovementRowRepository, LocationRow,
+        SensorRow, StockLine, StockLineRow, StorePreferenceRow, StorePreferenceRowRepository,
+        TemperatureLogRow,
     };
     use util::{
         constants::stock_line_expiring_soon_offset, date_now, date_now_with_offset, inline_edit,
@@ -17,6 +19,7 @@ mod test {
     };
 
     use crate::{
+        cold_chain::heat_exposure::HeatExposure,
         invoice_line::AllocateOutboundShipmentUnallocatedLineError as ServiceError,
         service_provider::ServiceProvider,
     };
@@ -665,4 +668,401 @@ mod test {
             })
         );
     }
+
+    #[actix_rt::test]
+    async fn allocate_unallocated_line_location_strategy() {
+        fn invoice() -> InvoiceRow {
+            inline_init(|r: &mut InvoiceRow| {
+                r.id = "invoice".to_string();
+                r.store_id = mock_store_a().id;
+                r.name_link_id = mock_name_a().id;
+                r.r#type = InvoiceType::OutboundShipment;
+            })
+        }
+
+        fn line() -> InvoiceLineRow {
+            inline_init(|r: &mut InvoiceLineRow| {
+                r.id = "line".to_string();
+                r.invoice_id = invoice().id;
+                r.item_link_id = mock_item_a().id;
+                r.r#type = InvoiceLineType::UnallocatedStock;
+                r.number_of_packs = 15.0;
+                r.pack_size = 1.0;
+            })
+        }
+
+        fn location(id: &str, code: &str, on_hold: bool) -> LocationRow {
+            inline_init(|r: &mut LocationRow| {
+                r.id = id.to_string();
+                r.code = code.to_string();
+                r.store_id = mock_store_a().id;
+                r.on_hold = on_hold;
+            })
+        }
+
+        fn stock_line(id: &str, location_id: &str, expiry_month: u32) -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = id.to_string();
+                r.store_id = mock_store_a().id;
+                r.item_link_id = mock_item_a().id;
+                r.location_id = Some(location_id.to_string());
+                r.pack_size = 1.0;
+                r.available_number_of_packs = 10.0;
+                r.expiry_date = Some(NaiveDate::from_ymd_opt(3021, expiry_month, 1).unwrap());
+            })
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "allocate_unallocated_line_location_strategy",
+            MockDataInserts::none()
+                .stores()
+                .items()
+                .names()
+                .units()
+                .currencies(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![invoice()];
+                r.invoice_lines = vec![line()];
+                r.locations = vec![
+                    location("location_a", "A", false),
+                    location("location_b", "B", false),
+                    location("location_on_hold", "0", true),
+                ];
+                r.stock_lines = vec![
+                    stock_line("on_hold_location", "location_on_hold", 1),
+                    stock_line("location_b_line", "location_b", 2),
+                    stock_line("location_a_line", "location_a", 3),
+                ];
+            }),
+        )
+        .await;
+
+        StorePreferenceRowRepository::new(&connection)
+            .upsert_one(&inline_init(|r: &mut StorePreferenceRow| {
+                r.id = mock_store_a().id;
+                r.allocation_strategy = AllocationStrategy::Location;
+            }))
+            .unwrap();
+
+        let service_provider = ServiceProvider::new(connection_manager.clone(), "app_data");
+        let context = service_provider
+            .context(mock_store_a().id, "".to_string())
+            .unwrap();
+        let service = service_provider.invoice_line_service;
+
+        let result = service
+            .allocate_outbound_shipment_unallocated_line(&context, line().id.clone())
+            .unwrap();
+
+        assert_eq!(result.allocation_strategy, AllocationStrategy::Location);
+        assert_eq!(result.inserts.len(), 2);
+        assert_eq!(result.deletes.len(), 1);
+        // Location on hold, even though stock line itself is not
+        assert_eq!(result.skipped_on_hold_stock_lines.len(), 1);
+        assert_eq!(
+            result.skipped_on_hold_stock_lines[0].stock_line_row.id,
+            "on_hold_location"
+        );
+        // By location code rather than expiry
+        assert_eq!(
+            result.inserts[0].invoice_line_row.stock_line_id,
+            Some("location_a_line".to_string())
+        );
+        assert_eq!(result.inserts[0].invoice_line_row.number_of_packs, 10.0);
+        assert_eq!(
+            result.inserts[1].invoice_line_row.stock_line_id,
+            Some("location_b_line".to_string())
+        );
+        assert_eq!(result.inserts[1].invoice_line_row.number_of_packs, 5.0);
+    }
+
+    #[actix_rt::test]
+    async fn allocate_unallocated_line_fifo_strategy() {
+        fn invoice() -> InvoiceRow {
+            inline_init(|r: &mut InvoiceRow| {
+                r.id = "invoice".to_string();
+                r.store_id = mock_store_a().id;
+                r.name_link_id = mock_name_a().id;
+                r.r#type = InvoiceType::OutboundShipment;
+            })
+        }
+
+        fn line() -> InvoiceLineRow {
+            inline_init(|r: &mut InvoiceLineRow| {
+                r.id = "line".to_string();
+                r.invoice_id = invoice().id;
+                r.item_link_id = mock_item_a().id;
+                r.r#type = InvoiceLineType::UnallocatedStock;
+                r.number_of_packs = 25.0;
+                r.pack_size = 1.0;
+            })
+        }
+
+        fn inbound(id: &str, month: u32) -> InvoiceRow {
+            inline_init(|r: &mut InvoiceRow| {
+                r.id = id.to_string();
+                r.store_id = mock_store_a().id;
+                r.name_link_id = mock_name_a().id;
+                r.r#type = InvoiceType::InboundShipment;
+                r.delivered_datetime = NaiveDate::from_ymd_opt(2024, month, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0);
+            })
+        }
+
+        fn inbound_line(invoice_id: &str, stock_line_id: &str) -> InvoiceLineRow {
+            inline_init(|r: &mut InvoiceLineRow| {
+                r.id = format!("{invoice_id}_line");
+                r.invoice_id = invoice_id.to_string();
+                r.item_link_id = mock_item_a().id;
+                r.stock_line_id = Some(stock_line_id.to_string());
+                r.r#type = InvoiceLineType::StockIn;
+                r.number_of_packs = 10.0;
+                r.pack_size = 1.0;
+            })
+        }
+
+        fn stock_line(id: &str, expiry_date: Option<NaiveDate>) -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = id.to_string();
+                r.store_id = mock_store_a().id;
+                r.item_link_id = mock_item_a().id;
+                r.pack_size = 1.0;
+                r.available_number_of_packs = 10.0;
+                r.expiry_date = expiry_date;
+            })
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "allocate_unallocated_line_fifo_strategy",
+            MockDataInserts::none()
+                .stores()
+                .items()
+                .names()
+                .units()
+                .currencies(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![
+                    invoice(),
+                    inbound("inbound_january", 1),
+                    inbound("inbound_february", 2),
+                ];
+                r.stock_lines = vec![
+                    stock_line("a_received_february", None),
+                    stock_line("b_received_january", None),
+                    stock_line(
+                        "c_expiring",
+                        Some(NaiveDate::from_ymd_opt(3021, 1, 1).unwrap()),
+                    ),
+                ];
+                r.invoice_lines = vec![
+                    line(),
+                    inbound_line("inbound_january", "b_received_january"),
+                    inbound_line("inbound_february", "a_received_february"),
+                ];
+            }),
+        )
+        .await;
+
+        StorePreferenceRowRepository::new(&connection)
+            .upsert_one(&inline_init(|r: &mut StorePreferenceRow| {
+                r.id = mock_store_a().id;
+                r.allocation_strategy = AllocationStrategy::Fifo;
+            }))
+            .unwrap();
+
+        let service_provider = ServiceProvider::new(connection_manager.clone(), "app_data");
+        let context = service_provider
+            .context(mock_store_a().id, "".to_string())
+            .unwrap();
+        let service = service_provider.invoice_line_service;
+
+        let result = service
+            .allocate_outbound_shipment_unallocated_line(&context, line().id.clone())
+            .unwrap();
+
+        assert_eq!(result.allocation_strategy, AllocationStrategy::Fifo);
+        let allocated: Vec<(Option<String>, f64)> = result
+            .inserts
+            .iter()
+            .map(|line| {
+                (
+                    line.invoice_line_row.stock_line_id.clone(),
+                    line.invoice_line_row.number_of_packs,
+                )
+            })
+            .collect();
+        // Expiring stock keeps FEFO, non expiring stock is allocated by received date
+        assert_eq!(
+            allocated,
+            vec![
+                (Some("c_expiring".to_string()), 10.0),
+                (Some("b_received_january".to_string()), 10.0),
+                (Some("a_received_february".to_string()), 5.0),
+            ]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn allocate_unallocated_line_vaccine_heat_stability() {
+        fn datetime(hour: u32, minute: u32) -> NaiveDateTime {
+            NaiveDate::from_ymd_opt(2024, 1, 1)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+                .unwrap()
+        }
+
+        fn item() -> ItemRow {
+            inline_init(|r: &mut ItemRow| {
+                r.id = "vaccine_item".to_string();
+                r.name = "Vaccine".to_string();
+                r.code = "vaccine_item".to_string();
+                r.is_vaccine = true;
+                r.heat_stability_threshold = Some(8.0);
+                r.heat_stability_budget_minutes = Some(120);
+            })
+        }
+
+        fn invoice() -> InvoiceRow {
+            inline_init(|r: &mut InvoiceRow| {
+                r.id = "invoice".to_string();
+                r.store_id = mock_store_a().id;
+                r.name_link_id = mock_name_a().id;
+                r.r#type = InvoiceType::OutboundShipment;
+            })
+        }
+
+        fn line() -> InvoiceLineRow {
+            inline_init(|r: &mut InvoiceLineRow| {
+                r.id = "line".to_string();
+                r.invoice_id = invoice().id;
+                r.item_link_id = item().id;
+                r.r#type = InvoiceLineType::UnallocatedStock;
+                r.number_of_packs = 5.0;
+                r.pack_size = 1.0;
+            })
+        }
+
+        fn location(id: &str) -> LocationRow {
+            inline_init(|r: &mut LocationRow| {
+                r.id = id.to_string();
+                r.code = id.to_string();
+                r.store_id = mock_store_a().id;
+            })
+        }
+
+        fn stock_line(id: &str, location_id: &str, expiry_month: u32) -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = id.to_string();
+                r.store_id = mock_store_a().id;
+                r.item_link_id = item().id;
+                r.location_id = Some(location_id.to_string());
+                r.pack_size = 1.0;
+                r.available_number_of_packs = 10.0;
+                r.expiry_date = Some(NaiveDate::from_ymd_opt(3021, expiry_month, 1).unwrap());
+            })
+        }
+
+        fn log(id: &str, location_id: &str, temperature: f64, minute: u32) -> TemperatureLogRow {
+            TemperatureLogRow {
+                id: id.to_string(),
+                temperature,
+                sensor_id: "sensor".to_string(),
+                location_id: Some(location_id.to_string()),
+                store_id: mock_store_a().id,
+                datetime: datetime(10, minute),
+                temperature_breach_id: None,
+            }
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "allocate_unallocated_line_vaccine_heat_stability",
+            MockDataInserts::none()
+                .stores()
+                .items()
+                .names()
+                .units()
+                .currencies(),
+            inline_init(|r: &mut MockData| {
+                r.items = vec![item()];
+                r.invoices = vec![invoice()];
+                r.invoice_lines = vec![line()];
+                r.locations = vec![location("location_warm"), location("location_cold")];
+                r.sensors = vec![inline_init(|r: &mut SensorRow| {
+                    r.id = "sensor".to_string();
+                    r.serial = "sensor".to_string();
+                    r.store_id = mock_store_a().id;
+                })];
+                // Warm location is above the threshold from 10:00 to 11:00
+                r.temperature_logs = vec![
+                    log("warm_1", "location_warm", 10.0, 0),
+                    log("warm_2", "location_warm", 10.0, 30),
+                    log("warm_3", "location_warm", 5.0, 60),
+                    log("cold_1", "location_cold", 5.0, 0),
+                ];
+                r.stock_lines = vec![
+                    stock_line("cold_line", "location_cold", 1),
+                    stock_line("warm_line", "location_warm", 2),
+                ];
+            }),
+        )
+        .await;
+
+        let movement_repo = LocationMovementRowRepository::new(&connection);
+        for (stock_line_id, location_id) in [
+            ("cold_line", "location_cold"),
+            ("warm_line", "location_warm"),
+        ] {
+            movement_repo
+                .upsert_one(&LocationMovementRow {
+                    id: stock_line_id.to_string(),
+                    store_id: mock_store_a().id,
+                    stock_line_id: stock_line_id.to_string(),
+                    location_id: Some(location_id.to_string()),
+                    enter_datetime: Some(datetime(0, 0)),
+                    exit_datetime: None,
+                })
+                .unwrap();
+        }
+
+        let service_provider = ServiceProvider::new(connection_manager.clone(), "app_data");
+        let context = service_provider
+            .context(mock_store_a().id, "".to_string())
+            .unwrap();
+
+        let heat_exposures = service_provider
+            .cold_chain_service
+            .get_stock_line_heat_exposures(
+                &context,
+                vec!["cold_line".to_string(), "warm_line".to_string()],
+            )
+            .unwrap();
+        assert_eq!(
+            heat_exposures.get("warm_line"),
+            Some(&HeatExposure {
+                exposure_minutes: 60,
+                remaining_budget_minutes: 60
+            })
+        );
+        assert_eq!(
+            heat_exposures.get("cold_line"),
+            Some(&HeatExposure {
+                exposure_minutes: 0,
+                remaining_budget_minutes: 120
+            })
+        );
+
+        let result = service_provider
+            .invoice_line_service
+            .allocate_outbound_shipment_unallocated_line(&context, line().id.clone())
+            .unwrap();
+
+        // Least remaining heat stability rather than expiry
+        assert_eq!(result.inserts.len(), 1);
+        assert_eq!(
+            result.inserts[0].invoice_line_row.stock_line_id,
+            Some("warm_line".to_string())
+        );
+        assert_eq!(result.inserts[0].invoice_line_row.number_of_packs, 5.0);
+    }
 }
@@ -1,8 +1,10 @@
 use repository::{
-    EqualFilter, Item, ItemFilter, ItemRepository, ItemSort, PaginationOption, RepositoryError,
-    StorageConnection, StorageConnectionManager,
+    EqualFilter, Item, ItemFilter, ItemOmsFieldsRow, ItemRepository, ItemRowRepository, ItemSort,
+    PaginationOption, RepositoryError, StorageConnection, StorageConnectionManager,
 };
 
+use crate::service_provider::ServiceContext;
+
 use super::{get_default_pagination, i64_to_u32, ListError, ListResult};
 
 pub const MAX_LIMIT: u32 = 5000;
@@ -36,3 +38,144 @@ pub fn check_item_exists(
     )?;
     Ok(count > 0)
 }
+
+#[derive(PartialEq, Debug)]
+pub enum UpdateItemHeatStabilityError {
+    ItemDoesNotExist,
+    /// Threshold and budget have to be set (or cleared) together
+    ThresholdAndBudgetRequired,
+    BudgetMustBePositive,
+    UpdatedRecordNotFound,
+    DatabaseError(RepositoryError),
+}
+
+pub struct UpdateItemHeatStability {
+    pub item_id: String,
+    pub heat_stability_threshold: Option<f64>,
+    pub heat_stability_budget_minutes: Option<i32>,
+}
+
+/// Sets the heat stability of an item, stored in the open mSupply central managed item fields
+/// (item_oms_fields) which are synced to remote sites
+pub fn update_item_heat_stability(
+    ctx: &ServiceContext,
+    input: UpdateItemHeatStability,
+) -> Result<Item, UpdateItemHeatStabilityError> {
+    let item = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(connection, &input)?;
+            ItemRowRepository::new(connection).update_oms_fields(&ItemOmsFieldsRow {
+                id: input.item_id.clone(),
+                heat_stability_threshold: input.heat_stability_threshold,
+                heat_stability_budget_minutes: input.heat_stability_budget_minutes,
+            })?;
+
+            ItemRepository::new(connection)
+                .query_one(
+                    None,
+                    ItemFilter::new().id(EqualFilter::equal_to(&input.item_id)),
+                )?
+                .ok_or(UpdateItemHeatStabilityError::UpdatedRecordNotFound)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(item)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    input: &UpdateItemHeatStability,
+) -> Result<(), UpdateItemHeatStabilityError> {
+    use UpdateItemHeatStabilityError::*;
+
+    ItemRowRepository::new(connection)
+        .find_active_by_id(&input.item_id)?
+        .ok_or(ItemDoesNotExist)?;
+
+    match (
+        input.heat_stability_threshold,
+        input.heat_stability_budget_minutes,
+    ) {
+        (Some(_), Some(budget_minutes)) if budget_minutes <= 0 => Err(BudgetMustBePositive),
+        (Some(_), Some(_)) | (None, None) => Ok(()),
+        _ => Err(ThresholdAndBudgetRequired),
+    }
+}
+
+impl From<RepositoryError> for UpdateItemHeatStabilityError {
+    fn from(error: RepositoryError) -> Self {
+        UpdateItemHeatStabilityError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_item_a, MockDataInserts},
+        ItemRowRepository,
+    };
+
+    use crate::test_helpers::{setup_all_and_service_provider, ServiceTestContext};
+
+    use super::{
+        update_item_heat_stability, UpdateItemHeatStability, UpdateItemHeatStabilityError,
+    };
+
+    #[actix_rt::test]
+    async fn test_update_item_heat_stability() {
+        let ServiceTestContext {
+            connection,
+            service_context,
+            ..
+        } = setup_all_and_service_provider(
+            "test_update_item_heat_stability",
+            MockDataInserts::none().units().items(),
+        )
+        .await;
+
+        let input = |item_id: &str, threshold: Option<f64>, budget_minutes: Option<i32>| {
+            UpdateItemHeatStability {
+                item_id: item_id.to_string(),
+                heat_stability_threshold: threshold,
+                heat_stability_budget_minutes: budget_minutes,
+            }
+        };
+
+        // Errors
+        assert_eq!(
+            update_item_heat_stability(&service_context, input("n/a", None, None)),
+            Err(UpdateItemHeatStabilityError::ItemDoesNotExist)
+        );
+        assert_eq!(
+            update_item_heat_stability(&service_context, input(&mock_item_a().id, Some(8.0), None)),
+            Err(UpdateItemHeatStabilityError::ThresholdAndBudgetRequired)
+        );
+        assert_eq!(
+            update_item_heat_stability(
+                &service_context,
+                input(&mock_item_a().id, Some(8.0), Some(0))
+            ),
+            Err(UpdateItemHeatStabilityError::BudgetMustBePositive)
+        );
+
+        // Success
+        let item = update_item_heat_stability(
+            &service_context,
+            input(&mock_item_a().id, Some(8.0), Some(120)),
+        )
+        .unwrap();
+        assert_eq!(item.item_row.heat_stability_threshold, Some(8.0));
+        assert_eq!(item.item_row.heat_stability_budget_minutes, Some(120));
+        // Other item fields are not changed
+        assert_eq!(item.item_row.name, mock_item_a().name);
+
+        // Clearing
+        update_item_heat_stability(&service_context, input(&mock_item_a().id, None, None)).unwrap();
+        let oms_fields = ItemRowRepository::new(&connection)
+            .find_one_oms_fields_by_id(&mock_item_a().id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(oms_fields.heat_stability_threshold, None);
+        assert_eq!(oms_fields.heat_stability_budget_minutes, None);
+    }
+}
@@ -1,6 +1,10 @@
 use std::{collections::HashMap, ops::Neg};
 
-use crate::service_provider::ServiceContext;
+use crate::{
+    consumption_forecast::{forecast_average_monthly_consumption, ConsumptionForecastMethod},
+    service_provider::ServiceContext,
+    store_preference::get_store_preferences,
+};
 use chrono::Duration;
 use repository::{
     ConsumptionFilter, ConsumptionRepository, ConsumptionRow, DateFilter, EqualFilter,
@@ -9,7 +13,7 @@ use repository::{
 };
 use util::{
     constants::{DEFAULT_AMC_LOOKBACK_MONTHS, NUMBER_OF_DAYS_IN_A_MONTH},
-    date_now_with_offset,
+    date_now, date_now_with_offset,
 };
 
 #[derive(Clone, Debug, PartialEq, Default)]
@@ -40,11 +44,31 @@ pub trait ItemStatsServiceTrait: Sync + Send {
 pub struct ItemStatsService {}
 impl ItemStatsServiceTrait for ItemStatsService {}
 
+/// Item stats with AMC calculated using store preference consumption forecast method
 pub fn get_item_stats(
     ctx: &ServiceContext,
     store_id: &str,
     amc_lookback_months: Option<u32>,
     filter: Option<ItemStatsFilter>,
+) -> Result<Vec<ItemStats>, RepositoryError> {
+    let consumption_forecast_method =
+        get_store_preferences(&ctx.connection, store_id)?.consumption_forecast_method;
+
+    get_item_stats_with_forecast_method(
+        ctx,
+        store_id,
+        amc_lookback_months,
+        filter,
+        &consumption_forecast_method,
+    )
+}
+
+pub fn get_item_stats_with_forecast_method(
+    ctx: &ServiceContext,
+    store_id: &str,
+    amc_lookback_months: Option<u32>,
+    filter: Option<ItemStatsFilter>,
+    consumption_forecast_method: &ConsumptionForecastMethod,
 ) -> Result<Vec<ItemStats>, RepositoryError> {
     let ItemStatsFilter {
         item_id: item_id_filter,
@@ -52,18 +76,50 @@ pub fn get_item_stats(
 
     let amc_lookback_months = amc_lookback_months.unwrap_or(DEFAULT_AMC_LOOKBACK_MONTHS);
 
-    Ok(ItemStats::new_vec(
-        get_consumption_rows(
+    let average_monthly_consumption = match consumption_forecast_method {
+        ConsumptionForecastMethod::SimpleAverage => simple_average_monthly_consumption(
+            get_consumption_rows(
+                &ctx.connection,
+                store_id,
+                item_id_filter.clone(),
+                amc_lookback_months,
+            )?,
+            amc_lookback_months,
+        ),
+        method => forecast_average_monthly_consumption(
             &ctx.connection,
             store_id,
             item_id_filter.clone(),
+            method,
             amc_lookback_months,
+            date_now(),
         )?,
+    };
+
+    Ok(ItemStats::new_vec(
+        average_monthly_consumption,
         get_stock_on_hand_rows(&ctx.connection, store_id, item_id_filter)?,
-        amc_lookback_months,
     ))
 }
 
+fn simple_average_monthly_consumption(
+    consumption_rows: Vec<ConsumptionRow>,
+    amc_lookback_months: u32,
+) -> HashMap<String, f64> {
+    let mut consumption_map = HashMap::new();
+    for consumption_row in consumption_rows.into_iter() {
+        let item_total_consumption = consumption_map
+            .entry(consumption_row.item_id.clone())
+            .or_insert(0.0);
+        *item_total_consumption += consumption_row.quantity;
+    }
+
+    consumption_map
+        .into_iter()
+        .map(|(item_id, consumption)| (item_id, consumption / amc_lookback_months as f64))
+        .collect()
+}
+
 pub fn get_consumption_rows(
     connection: &StorageConnection,
     store_id: &str,
@@ -98,27 +154,18 @@ pub fn get_stock_on_hand_rows(
 
 impl ItemStats {
     fn new_vec(
-        consumption_rows: Vec<ConsumptionRow>,
+        average_monthly_consumption_map: HashMap<String, f64>,
         stock_on_hand_rows: Vec<StockOnHandRow>,
-        amc_lookback_months: u32,
     ) -> Vec<Self> {
-        let mut consumption_map = HashMap::new();
-        for consumption_row in consumption_rows.into_iter() {
-            let item_total_consumption = consumption_map
-                .entry(consumption_row.item_id.clone())
-                .or_insert(0.0);
-            *item_total_consumption += consumption_row.quantity;
-        }
-
         stock_on_hand_rows
             .into_iter()
             .map(|stock_on_hand| ItemStats {
                 available_stock_on_hand: stock_on_hand.available_stock_on_hand,
                 item_id: stock_on_hand.item_id.clone(),
                 item_name: stock_on_hand.item_name.clone(),
-                average_monthly_consumption: consumption_map
+                average_monthly_consumption: average_monthly_consumption_map
                     .get(&stock_on_hand.item_id)
-                    .map(|consumption| *consumption / amc_lookback_months as f64)
+                    .copied()
                     .unwrap_or_default(),
             })
             .collect()
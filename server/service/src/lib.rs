@@ -11,6 +11,7 @@ pub mod apis;
 pub mod app_data;
 
 pub mod asset;
+pub mod audit_log;
 pub mod auth;
 pub mod auth_data;
 pub mod barcode;
@@ -18,12 +19,15 @@ pub mod catalogue;
 pub mod clinician;
 pub mod cold_chain;
 mod common_stock;
+pub mod consumption_forecast;
 pub mod currency;
 pub mod cursor_controller;
+pub mod cycle_count;
 pub mod dashboard;
 pub mod demographic;
 pub mod display_settings_service;
 pub mod document;
+pub mod expiry_management;
 pub mod inventory_adjustment_reason;
 pub mod invoice;
 pub mod invoice_line;
@@ -38,6 +42,7 @@ pub mod master_list;
 pub mod missing_program;
 pub mod name;
 pub mod name_property;
+pub mod notification;
 pub mod number;
 pub mod pack_variant;
 pub mod permission;
@@ -58,6 +63,7 @@ pub mod settings;
 pub mod settings_service;
 pub mod static_files;
 pub mod stock_line;
+pub mod stock_out;
 pub mod stocktake;
 pub mod stocktake_line;
 pub mod store;
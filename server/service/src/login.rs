@@ -389,11 +389,13 @@ fn permissions_to_domain(permissions: Vec<Permissions>) -> HashSet<PermissionTyp
                 output.insert(PermissionType::StocktakeMutate);
             }
             // inventory adjustments
-            Permissions::EnterInventoryAdjustments
-            | Permissions::EditInventoryAdjustments
-            | Permissions::FinaliseInventoryAdjustments => {
+            Permissions::EnterInventoryAdjustments | Permissions::EditInventoryAdjustments => {
                 output.insert(PermissionType::InventoryAdjustmentMutate);
             }
+            Permissions::FinaliseInventoryAdjustments => {
+                output.insert(PermissionType::InventoryAdjustmentMutate);
+                output.insert(PermissionType::StocktakeVarianceApprove);
+            }
             // customer invoices
             Permissions::ViewCustomerInvoices => {
                 output.insert(PermissionType::OutboundShipmentQuery);
@@ -436,10 +438,19 @@ fn permissions_to_domain(permissions: Vec<Permissions>) -> HashSet<PermissionTyp
             Permissions::ConfirmInternalOrderSent => {
                 output.insert(PermissionType::RequisitionSend);
             }
+            Permissions::AuthoriseCustomerInvoices => {
+                output.insert(PermissionType::RequisitionApprove);
+            }
+            Permissions::CanEditAuthorisers => {
+                output.insert(PermissionType::RequisitionApprovalChainMutate);
+            }
             // reports
             Permissions::ViewReports => {
                 output.insert(PermissionType::Report);
             }
+            Permissions::ManageReports => {
+                output.insert(PermissionType::ReportScheduleMutate);
+            }
             // log
             Permissions::ViewLog => {
                 output.insert(PermissionType::LogQuery);
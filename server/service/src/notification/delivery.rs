@@ -0,0 +1,224 @@
+use std::{sync::Arc, time::Duration as StdDuration};
+
+use chrono::{Duration, NaiveDateTime};
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use repository::{
+    NotificationChannel, NotificationOutboxRow, NotificationOutboxRowRepository,
+    NotificationOutboxStatus, RepositoryError,
+};
+use serde_json::json;
+use thiserror::Error;
+
+use crate::{
+    service_provider::ServiceProvider,
+    settings::{NotificationSettings, SmtpSettings},
+};
+
+use super::template::event_type_key;
+
+/// Number of attempts before a notification is marked as failed
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 5;
+/// Delay before the first retry, doubled for every following attempt
+const RETRY_BASE_DELAY_SECONDS: i64 = 60;
+const RETRY_MAX_DELAY_SECONDS: i64 = 60 * 60;
+/// Maximum number of notifications delivered in one run
+const DELIVERY_BATCH_SIZE: i64 = 50;
+const WEBHOOK_TIMEOUT: StdDuration = StdDuration::from_secs(30);
+
+#[derive(Error, Debug)]
+pub enum NotificationDeliveryError {
+    #[error("SMTP is not configured (notifications.smtp setting)")]
+    SmtpNotConfigured,
+    #[error("Invalid email address: {0}")]
+    InvalidAddress(String),
+    #[error("Failed to send email: {0}")]
+    Email(String),
+    #[error("Failed to call webhook: {0}")]
+    Webhook(String),
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SendNotificationsError {
+    #[error("Database error while delivering notifications")]
+    DatabaseError(#[from] RepositoryError),
+    #[error("Notification outbox task failed: {0}")]
+    TaskFailed(String),
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct DeliveryResult {
+    pub sent: usize,
+    pub failed: usize,
+}
+
+/// Delivers pending notifications that are due at `now`.
+///
+/// A notification that fails is retried on a later run with the delay doubling after every
+/// attempt (1 minute, 2 minutes, ... up to 1 hour), after `MAX_DELIVERY_ATTEMPTS` it's marked as
+/// failed. `failed` in the result counts failed attempts.
+///
+/// The outbox is read and updated in blocking tasks, no database connection is held while emails
+/// and webhooks are sent.
+pub async fn send_due_notifications(
+    service_provider: Arc<ServiceProvider>,
+    settings: Option<&NotificationSettings>,
+    now: NaiveDateTime,
+) -> Result<DeliveryResult, SendNotificationsError> {
+    let provider = service_provider.clone();
+    let due = tokio::task::spawn_blocking(move || {
+        let connection = provider.connection()?;
+        NotificationOutboxRowRepository::new(&connection).find_due(now, DELIVERY_BATCH_SIZE)
+    })
+    .await
+    .map_err(|error| SendNotificationsError::TaskFailed(error.to_string()))??;
+
+    let mut result = DeliveryResult::default();
+    let mut rows = Vec::new();
+    for notification in due {
+        let row = match deliver_notification(settings, &notification).await {
+            Ok(()) => {
+                result.sent += 1;
+                NotificationOutboxRow {
+                    status: NotificationOutboxStatus::Sent,
+                    next_attempt_datetime: None,
+                    sent_datetime: Some(now),
+                    ..notification
+                }
+            }
+            Err(error) => {
+                result.failed += 1;
+                log::error!(
+                    "Failed to deliver notification {} to {}: {}",
+                    notification.id,
+                    notification.recipient,
+                    error
+                );
+                let attempts = notification.attempts + 1;
+                let is_final = attempts >= MAX_DELIVERY_ATTEMPTS;
+                NotificationOutboxRow {
+                    status: if is_final {
+                        NotificationOutboxStatus::Failed
+                    } else {
+                        NotificationOutboxStatus::Pending
+                    },
+                    attempts,
+                    next_attempt_datetime: (!is_final).then(|| now + retry_delay(attempts)),
+                    last_error: Some(error.to_string()),
+                    ..notification
+                }
+            }
+        };
+        rows.push(row);
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let connection = service_provider.connection()?;
+        let repo = NotificationOutboxRowRepository::new(&connection);
+        for row in rows {
+            repo.upsert_one(&row)?;
+        }
+        Ok::<(), RepositoryError>(())
+    })
+    .await
+    .map_err(|error| SendNotificationsError::TaskFailed(error.to_string()))??;
+
+    Ok(result)
+}
+
+pub async fn deliver_notification(
+    settings: Option<&NotificationSettings>,
+    notification: &NotificationOutboxRow,
+) -> Result<(), NotificationDeliveryError> {
+    match notification.channel {
+        NotificationChannel::Email => {
+            let smtp = settings
+                .and_then(|settings| settings.smtp.as_ref())
+                .ok_or(NotificationDeliveryError::SmtpNotConfigured)?;
+            send_email(smtp, notification).await
+        }
+        NotificationChannel::Webhook => send_webhook(notification).await,
+    }
+}
+
+async fn send_email(
+    smtp: &SmtpSettings,
+    notification: &NotificationOutboxRow,
+) -> Result<(), NotificationDeliveryError> {
+    use NotificationDeliveryError as Error;
+
+    let parse_mailbox = |address: &str| {
+        address
+            .parse::<Mailbox>()
+            .map_err(|_| Error::InvalidAddress(address.to_string()))
+    };
+    let message = Message::builder()
+        .from(parse_mailbox(&smtp.from)?)
+        .to(parse_mailbox(&notification.recipient)?)
+        .subject(&notification.subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(notification.body.clone())
+        .map_err(|error| Error::Email(error.to_string()))?;
+
+    let builder = if smtp.danger_allow_insecure {
+        AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&smtp.host)
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host)
+            .map_err(|error| Error::Email(error.to_string()))?
+    };
+    let builder = match (&smtp.username, &smtp.password) {
+        (Some(username), Some(password)) => {
+            builder.credentials(Credentials::new(username.clone(), password.clone()))
+        }
+        _ => builder,
+    };
+
+    builder
+        .port(smtp.port)
+        .build()
+        .send(message)
+        .await
+        .map_err(|error| Error::Email(error.to_string()))?;
+    Ok(())
+}
+
+/// Posts the notification as json, any 2xx response is treated as delivered
+async fn send_webhook(
+    notification: &NotificationOutboxRow,
+) -> Result<(), NotificationDeliveryError> {
+    use NotificationDeliveryError as Error;
+
+    let client = reqwest::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()
+        .map_err(|error| Error::Webhook(error.to_string()))?;
+    let response = client
+        .post(&notification.recipient)
+        .json(&json!({
+            "id": notification.id,
+            "event_type": event_type_key(notification.event_type),
+            "store_id": notification.store_id,
+            "record_id": notification.record_id,
+            "subject": notification.subject,
+            "body": notification.body,
+        }))
+        .send()
+        .await
+        .map_err(|error| Error::Webhook(error.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(Error::Webhook(format!("Response status {}", status)));
+    }
+    Ok(())
+}
+
+/// Backoff before the next attempt, after `attempts` failed attempts
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = (attempts - 1).clamp(0, 16) as u32;
+    let seconds = RETRY_BASE_DELAY_SECONDS.saturating_mul(2_i64.pow(exponent));
+    Duration::seconds(seconds.min(RETRY_MAX_DELAY_SECONDS))
+}
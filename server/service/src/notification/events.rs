@@ -0,0 +1,350 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use repository::{
+    ChangelogRow, ChangelogTableName, DateFilter, EqualFilter, InvoiceFilter, InvoiceRepository,
+    InvoiceType, LocationRowRepository, NotificationEventType, NotificationOutboxRow,
+    NotificationOutboxRowRepository, NotificationOutboxStatus, NotificationSubscriptionRow,
+    NotificationSubscriptionRowRepository, RepositoryError, RequisitionFilter,
+    RequisitionRepository, RequisitionType, SensorRowRepository, StockLineFilter,
+    StockLineRepository, StorageConnection, StoreFilter, StoreRepository,
+    TemperatureBreachRowRepository, TemperatureBreachType,
+};
+use serde_json::{json, Value};
+use util::uuid::uuid;
+
+use crate::{
+    processors::changelog_consumer::{ChangelogConsumer, ChangelogConsumerError},
+    service_provider::{ServiceContext, ServiceProvider},
+    sync::ActiveStoresOnSite,
+};
+
+use super::template::{
+    default_body_template, default_subject_template, event_type_key, render_template,
+};
+
+pub const DEFAULT_STOCK_EXPIRY_DAYS: u32 = 30;
+
+/// Something that happened in a store that subscriptions can be notified of
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NotificationEvent {
+    pub store_id: String,
+    pub event_type: NotificationEventType,
+    /// Each subscription is only notified once per record
+    pub record_id: String,
+    /// Subscriptions created after the event are not notified
+    pub datetime: NaiveDateTime,
+    /// Template context of the record
+    pub record: Value,
+}
+
+/// Renders the notification of the event for each matching active subscription into the outbox.
+///
+/// A notification that can't be rendered (e.g. a template uses an unknown variable) is added as
+/// failed with the render error, so it shows up in the outbox. Returns the number of
+/// notifications added.
+pub(crate) fn enqueue_notifications(
+    connection: &StorageConnection,
+    event: &NotificationEvent,
+    now: NaiveDateTime,
+) -> Result<usize, RepositoryError> {
+    let subscriptions = NotificationSubscriptionRowRepository::new(connection)
+        .find_active(&event.store_id, event.event_type)?;
+    let subscriptions: Vec<NotificationSubscriptionRow> = subscriptions
+        .into_iter()
+        .filter(|subscription| subscription.created_datetime <= event.datetime)
+        .collect();
+    if subscriptions.is_empty() {
+        return Ok(0);
+    }
+
+    let store_name = StoreRepository::new(connection)
+        .query_one(StoreFilter::new().id(EqualFilter::equal_to(&event.store_id)))?
+        .map(|store| store.name_row.name)
+        .unwrap_or_default();
+    let context = json!({
+        "event_type": event_type_key(event.event_type),
+        "store": { "id": event.store_id, "name": store_name },
+        "record": event.record,
+    });
+
+    let outbox_repo = NotificationOutboxRowRepository::new(connection);
+    let mut count = 0;
+    for subscription in subscriptions {
+        if outbox_repo
+            .find_one_by_subscription_and_record(&subscription.id, &event.record_id)?
+            .is_some()
+        {
+            continue;
+        }
+
+        let subject_template = subscription
+            .subject_template
+            .as_deref()
+            .unwrap_or(default_subject_template(event.event_type));
+        let body_template = subscription
+            .body_template
+            .as_deref()
+            .unwrap_or(default_body_template(event.event_type));
+        let rendered = render_template(subject_template, &context).and_then(|subject| {
+            render_template(body_template, &context).map(|body| (subject, body))
+        });
+
+        let row = NotificationOutboxRow {
+            id: uuid(),
+            subscription_id: subscription.id,
+            store_id: event.store_id.clone(),
+            event_type: event.event_type,
+            record_id: event.record_id.clone(),
+            channel: subscription.channel,
+            recipient: subscription.recipient,
+            created_datetime: now,
+            ..Default::default()
+        };
+        let row = match rendered {
+            Ok((subject, body)) => NotificationOutboxRow {
+                subject: subject.trim().to_string(),
+                body,
+                status: NotificationOutboxStatus::Pending,
+                ..row
+            },
+            Err(error) => NotificationOutboxRow {
+                status: NotificationOutboxStatus::Failed,
+                last_error: Some(format!("Failed to render notification: {}", error)),
+                ..row
+            },
+        };
+        outbox_repo.upsert_one(&row)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Notifies `StockExpiring` subscriptions of stock lines (with stock in store) that expire
+/// within `stock_expiry_days`, in stores active on this site. Each stock line is only notified
+/// once per subscription.
+pub(crate) fn enqueue_stock_expiring_notifications(
+    connection: &StorageConnection,
+    stock_expiry_days: u32,
+    now: NaiveDateTime,
+) -> Result<usize, RepositoryError> {
+    let mut store_ids: Vec<String> = NotificationSubscriptionRowRepository::new(connection)
+        .find_active_by_event_type(NotificationEventType::StockExpiring)?
+        .into_iter()
+        .map(|subscription| subscription.store_id)
+        .collect();
+    store_ids.sort();
+    store_ids.dedup();
+    if store_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let active_store_ids = match ActiveStoresOnSite::get(connection) {
+        Ok(active_stores) => active_stores.store_ids(),
+        // Site is not initialised
+        Err(_) => return Ok(0),
+    };
+
+    let today = now.date();
+    let expiry_limit = today + Duration::days(stock_expiry_days as i64);
+    let mut count = 0;
+    for store_id in store_ids {
+        if !active_store_ids.contains(&store_id) {
+            continue;
+        }
+
+        let stock_lines = StockLineRepository::new(connection).query_by_filter(
+            StockLineFilter::new()
+                .store_id(EqualFilter::equal_to(&store_id))
+                .has_packs_in_store(true)
+                .expiry_date(DateFilter::date_range(&today, &expiry_limit)),
+            Some(store_id.clone()),
+        )?;
+
+        for stock_line in stock_lines {
+            let row = stock_line.stock_line_row;
+            let record = json!({
+                "id": row.id,
+                "item_code": stock_line.item_row.code,
+                "item_name": stock_line.item_row.name,
+                "batch": row.batch,
+                "expiry_date": row.expiry_date,
+                "number_of_packs": row.total_number_of_packs,
+                "pack_size": row.pack_size,
+            });
+            count += enqueue_notifications(
+                connection,
+                &NotificationEvent {
+                    store_id: store_id.clone(),
+                    event_type: NotificationEventType::StockExpiring,
+                    record_id: row.id,
+                    datetime: now,
+                    record,
+                },
+                now,
+            )?;
+        }
+    }
+
+    Ok(count)
+}
+
+fn temperature_breach_event(
+    connection: &StorageConnection,
+    id: &str,
+) -> Result<Option<NotificationEvent>, RepositoryError> {
+    let Some(breach) = TemperatureBreachRowRepository::new(connection).find_one_by_id(id)? else {
+        return Ok(None);
+    };
+
+    let sensor_name = SensorRowRepository::new(connection)
+        .find_one_by_id(&breach.sensor_id)?
+        .map(|sensor| sensor.name);
+    let location_name = match &breach.location_id {
+        Some(location_id) => LocationRowRepository::new(connection)
+            .find_one_by_id(location_id)?
+            .map(|location| location.name),
+        None => None,
+    };
+
+    Ok(Some(NotificationEvent {
+        store_id: breach.store_id.clone(),
+        event_type: NotificationEventType::TemperatureBreach,
+        record_id: breach.id.clone(),
+        datetime: breach.start_datetime,
+        record: json!({
+            "id": breach.id,
+            "type": breach_type_description(&breach.r#type),
+            "start_datetime": breach.start_datetime,
+            "end_datetime": breach.end_datetime,
+            "threshold_minimum": breach.threshold_minimum,
+            "threshold_maximum": breach.threshold_maximum,
+            "sensor_name": sensor_name,
+            "location_name": location_name,
+        }),
+    }))
+}
+
+/// Response requisitions created by the requisition transfer processor
+fn requisition_received_event(
+    connection: &StorageConnection,
+    id: &str,
+) -> Result<Option<NotificationEvent>, RepositoryError> {
+    let Some(requisition) = RequisitionRepository::new(connection)
+        .query_one(RequisitionFilter::new().id(EqualFilter::equal_to(id)))?
+    else {
+        return Ok(None);
+    };
+    let row = requisition.requisition_row;
+    if row.r#type != RequisitionType::Response || row.linked_requisition_id.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(NotificationEvent {
+        store_id: row.store_id,
+        event_type: NotificationEventType::RequisitionReceived,
+        record_id: row.id.clone(),
+        datetime: row.created_datetime,
+        record: json!({
+            "id": row.id,
+            "requisition_number": row.requisition_number,
+            "customer_name": requisition.name_row.name,
+            "their_reference": row.their_reference,
+            "comment": row.comment,
+            "created_datetime": row.created_datetime,
+        }),
+    }))
+}
+
+/// Inbound shipments once they are delivered
+fn shipment_arrived_event(
+    connection: &StorageConnection,
+    id: &str,
+) -> Result<Option<NotificationEvent>, RepositoryError> {
+    let Some(invoice) = InvoiceRepository::new(connection)
+        .query_one(InvoiceFilter::new().id(EqualFilter::equal_to(id)))?
+    else {
+        return Ok(None);
+    };
+    let row = invoice.invoice_row;
+    let Some(delivered_datetime) = row.delivered_datetime else {
+        return Ok(None);
+    };
+    if row.r#type != InvoiceType::InboundShipment {
+        return Ok(None);
+    }
+
+    Ok(Some(NotificationEvent {
+        store_id: row.store_id,
+        event_type: NotificationEventType::ShipmentArrived,
+        record_id: row.id.clone(),
+        datetime: delivered_datetime,
+        record: json!({
+            "id": row.id,
+            "invoice_number": row.invoice_number,
+            "supplier_name": invoice.name_row.name,
+            "their_reference": row.their_reference,
+            "delivered_datetime": delivered_datetime,
+        }),
+    }))
+}
+
+fn breach_type_description(r#type: &TemperatureBreachType) -> &'static str {
+    match r#type {
+        TemperatureBreachType::ColdConsecutive => "cold consecutive",
+        TemperatureBreachType::ColdCumulative => "cold cumulative",
+        TemperatureBreachType::HotConsecutive => "hot consecutive",
+        TemperatureBreachType::HotCumulative => "hot cumulative",
+        TemperatureBreachType::Excursion => "excursion",
+    }
+}
+
+/// Adds notifications of temperature breaches, received requisitions and arrived shipments to the
+/// outbox (see `enqueue_notifications`), delivery is done by the `NotificationDriver`
+pub(crate) struct NotificationEventConsumer;
+
+impl ChangelogConsumer for NotificationEventConsumer {
+    fn name(&self) -> &'static str {
+        "notification_events"
+    }
+
+    fn table_names(&self) -> Vec<ChangelogTableName> {
+        vec![
+            ChangelogTableName::TemperatureBreach,
+            ChangelogTableName::Requisition,
+            ChangelogTableName::Invoice,
+        ]
+    }
+
+    fn handle(
+        &self,
+        _: &ServiceProvider,
+        ctx: &ServiceContext,
+        changelog: &ChangelogRow,
+    ) -> Result<(), ChangelogConsumerError> {
+        let connection = &ctx.connection;
+        let event = match changelog.table_name {
+            ChangelogTableName::TemperatureBreach => {
+                temperature_breach_event(connection, &changelog.record_id)?
+            }
+            ChangelogTableName::Requisition => {
+                requisition_received_event(connection, &changelog.record_id)?
+            }
+            ChangelogTableName::Invoice => {
+                shipment_arrived_event(connection, &changelog.record_id)?
+            }
+            _ => None,
+        };
+        let Some(event) = event else {
+            return Ok(());
+        };
+
+        let active_stores = ActiveStoresOnSite::get(connection)
+            .map_err(|error| ChangelogConsumerError::Retryable(error.to_string()))?;
+        if !active_stores.store_ids().contains(&event.store_id) {
+            return Ok(());
+        }
+
+        enqueue_notifications(connection, &event, Utc::now().naive_utc())?;
+        Ok(())
+    }
+}
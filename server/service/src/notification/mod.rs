@@ -0,0 +1,84 @@
+use chrono::NaiveDateTime;
+use repository::{
+    NotificationOutboxFilter, NotificationOutboxRepository, NotificationOutboxRow,
+    NotificationOutboxSort, NotificationSubscriptionRow, NotificationSubscriptionRowRepository,
+    PaginationOption, RepositoryError, StorageConnection,
+};
+
+use crate::{
+    get_default_pagination, i64_to_u32, service_provider::ServiceContext, ListError, ListResult,
+};
+
+use self::subscription::{
+    delete_notification_subscription, upsert_notification_subscription,
+    DeleteNotificationSubscriptionError, UpsertNotificationSubscription,
+    UpsertNotificationSubscriptionError,
+};
+
+pub mod delivery;
+pub mod events;
+pub mod notification_driver;
+pub mod subscription;
+pub mod template;
+
+#[cfg(test)]
+mod test;
+
+pub const MAX_LIMIT: u32 = 1000;
+pub const MIN_LIMIT: u32 = 1;
+
+/// Notifications of store events (see README.md in this folder)
+pub trait NotificationServiceTrait: Sync + Send {
+    fn get_notification_subscriptions(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<Vec<NotificationSubscriptionRow>, RepositoryError> {
+        NotificationSubscriptionRowRepository::new(&ctx.connection).find_many_by_store_id(store_id)
+    }
+
+    fn upsert_notification_subscription(
+        &self,
+        ctx: &ServiceContext,
+        input: UpsertNotificationSubscription,
+    ) -> Result<NotificationSubscriptionRow, UpsertNotificationSubscriptionError> {
+        upsert_notification_subscription(ctx, input)
+    }
+
+    fn delete_notification_subscription(
+        &self,
+        ctx: &ServiceContext,
+        id: &str,
+    ) -> Result<String, DeleteNotificationSubscriptionError> {
+        delete_notification_subscription(ctx, id)
+    }
+
+    fn get_notification_outbox(
+        &self,
+        ctx: &ServiceContext,
+        pagination: Option<PaginationOption>,
+        filter: Option<NotificationOutboxFilter>,
+        sort: Option<NotificationOutboxSort>,
+    ) -> Result<ListResult<NotificationOutboxRow>, ListError> {
+        let pagination = get_default_pagination(pagination, MAX_LIMIT, MIN_LIMIT)?;
+        let repository = NotificationOutboxRepository::new(&ctx.connection);
+        Ok(ListResult {
+            rows: repository.query(pagination, filter.clone(), sort)?,
+            count: i64_to_u32(repository.count(filter)?),
+        })
+    }
+
+    /// Adds notifications for stock expiring within `stock_expiry_days` to the outbox, returns
+    /// the number of notifications added
+    fn enqueue_stock_expiring_notifications(
+        &self,
+        connection: &StorageConnection,
+        stock_expiry_days: u32,
+        now: NaiveDateTime,
+    ) -> Result<usize, RepositoryError> {
+        events::enqueue_stock_expiring_notifications(connection, stock_expiry_days, now)
+    }
+}
+
+pub struct NotificationService;
+impl NotificationServiceTrait for NotificationService {}
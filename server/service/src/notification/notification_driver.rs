@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::time::{Duration, Instant};
+use util::format_error;
+
+use crate::{
+    service_provider::ServiceProvider,
+    settings::{NotificationSettings, Settings},
+    sync::is_initialised,
+};
+
+use super::{delivery::send_due_notifications, events::DEFAULT_STOCK_EXPIRY_DAYS};
+
+const NOTIFICATION_DELIVERY_INTERVAL: Duration = Duration::from_secs(60);
+const STOCK_EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub struct NotificationDriver {
+    settings: Option<NotificationSettings>,
+}
+
+/// Used to 'drive' notifications, every minute (only when initialised) it delivers due
+/// notifications from the outbox and once an hour it checks for expiring stock. Notifications of
+/// other events are added to the outbox by the `NotificationEventConsumer` changelog consumer.
+impl NotificationDriver {
+    pub fn init(settings: &Settings) -> NotificationDriver {
+        NotificationDriver {
+            settings: settings.notifications.clone(),
+        }
+    }
+
+    /// NotificationDriver entry point, this method is meant to be run within main `select!` macro
+    pub async fn run(self, service_provider: Arc<ServiceProvider>) {
+        let mut last_stock_expiry_check: Option<Instant> = None;
+        loop {
+            tokio::time::sleep(NOTIFICATION_DELIVERY_INTERVAL).await;
+
+            // Need to check is_initialised from database on every iteration, since it could have been updated
+            if !is_initialised(&service_provider) {
+                continue;
+            }
+
+            let now = Utc::now().naive_utc();
+
+            if last_stock_expiry_check.map_or(true, |checked| {
+                checked.elapsed() >= STOCK_EXPIRY_CHECK_INTERVAL
+            }) {
+                last_stock_expiry_check = Some(Instant::now());
+                let stock_expiry_days = self
+                    .settings
+                    .as_ref()
+                    .and_then(|settings| settings.stock_expiry_days)
+                    .unwrap_or(DEFAULT_STOCK_EXPIRY_DAYS);
+                let service_provider = service_provider.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    let ctx = service_provider.basic_context()?;
+                    service_provider
+                        .notification_service
+                        .enqueue_stock_expiring_notifications(
+                            &ctx.connection,
+                            stock_expiry_days,
+                            now,
+                        )
+                })
+                .await;
+
+                match result {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(error)) => log::error!(
+                        "Error checking for expiring stock notifications: {}",
+                        format_error(&error)
+                    ),
+                    Err(error) => log::error!("Stock expiry check task failed: {}", error),
+                }
+            }
+
+            match send_due_notifications(service_provider.clone(), self.settings.as_ref(), now)
+                .await
+            {
+                Ok(result) => {
+                    if result.sent > 0 {
+                        log::info!("Delivered {} notification(s)", result.sent);
+                    }
+                }
+                Err(error) => {
+                    log::error!("Error delivering notifications: {}", format_error(&error))
+                }
+            }
+        }
+    }
+}
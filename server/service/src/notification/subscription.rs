@@ -0,0 +1,115 @@
+use chrono::Utc;
+use repository::{
+    NotificationChannel, NotificationEventType, NotificationSubscriptionRow,
+    NotificationSubscriptionRowRepository, RepositoryError,
+};
+
+use crate::service_provider::ServiceContext;
+
+use super::template::validate_template;
+
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct UpsertNotificationSubscription {
+    pub id: String,
+    pub event_type: NotificationEventType,
+    pub channel: NotificationChannel,
+    /// Email address or webhook (http or https) url, depending on the channel
+    pub recipient: String,
+    pub subject_template: Option<String>,
+    pub body_template: Option<String>,
+    pub is_active: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum UpsertNotificationSubscriptionError {
+    NotThisStoreSubscription,
+    InvalidRecipient,
+    InvalidTemplate(String),
+    DatabaseError(RepositoryError),
+}
+
+#[derive(PartialEq, Debug)]
+pub enum DeleteNotificationSubscriptionError {
+    SubscriptionDoesNotExist,
+    NotThisStoreSubscription,
+    DatabaseError(RepositoryError),
+}
+
+pub(crate) fn upsert_notification_subscription(
+    ctx: &ServiceContext,
+    input: UpsertNotificationSubscription,
+) -> Result<NotificationSubscriptionRow, UpsertNotificationSubscriptionError> {
+    let repo = NotificationSubscriptionRowRepository::new(&ctx.connection);
+    let existing = repo.find_one_by_id(&input.id)?;
+    if let Some(existing) = &existing {
+        if existing.store_id != ctx.store_id {
+            return Err(UpsertNotificationSubscriptionError::NotThisStoreSubscription);
+        }
+    }
+
+    if !is_valid_recipient(input.channel, &input.recipient) {
+        return Err(UpsertNotificationSubscriptionError::InvalidRecipient);
+    }
+    for template in [&input.subject_template, &input.body_template]
+        .into_iter()
+        .flatten()
+    {
+        validate_template(template)
+            .map_err(UpsertNotificationSubscriptionError::InvalidTemplate)?;
+    }
+
+    let row = NotificationSubscriptionRow {
+        id: input.id,
+        store_id: ctx.store_id.clone(),
+        event_type: input.event_type,
+        channel: input.channel,
+        recipient: input.recipient.trim().to_string(),
+        subject_template: input.subject_template,
+        body_template: input.body_template,
+        is_active: input.is_active,
+        created_datetime: existing
+            .map(|existing| existing.created_datetime)
+            .unwrap_or_else(|| Utc::now().naive_utc()),
+    };
+    repo.upsert_one(&row)?;
+    Ok(row)
+}
+
+/// Deletes the subscription, notifications already in the outbox are still delivered
+pub(crate) fn delete_notification_subscription(
+    ctx: &ServiceContext,
+    id: &str,
+) -> Result<String, DeleteNotificationSubscriptionError> {
+    let repo = NotificationSubscriptionRowRepository::new(&ctx.connection);
+    let existing = repo
+        .find_one_by_id(id)?
+        .ok_or(DeleteNotificationSubscriptionError::SubscriptionDoesNotExist)?;
+    if existing.store_id != ctx.store_id {
+        return Err(DeleteNotificationSubscriptionError::NotThisStoreSubscription);
+    }
+
+    repo.delete(id)?;
+    Ok(id.to_string())
+}
+
+fn is_valid_recipient(channel: NotificationChannel, recipient: &str) -> bool {
+    let recipient = recipient.trim();
+    match channel {
+        NotificationChannel::Email => recipient.parse::<lettre::Address>().is_ok(),
+        NotificationChannel::Webhook => url::Url::parse(recipient)
+            .map(|url| matches!(url.scheme(), "http" | "https"))
+            .unwrap_or(false),
+    }
+}
+
+impl From<RepositoryError> for UpsertNotificationSubscriptionError {
+    fn from(error: RepositoryError) -> Self {
+        UpsertNotificationSubscriptionError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for DeleteNotificationSubscriptionError {
+    fn from(error: RepositoryError) -> Self {
+        DeleteNotificationSubscriptionError::DatabaseError(error)
+    }
+}
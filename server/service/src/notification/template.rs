@@ -0,0 +1,78 @@
+use repository::NotificationEventType;
+use serde_json::Value;
+
+/// Event type as used in template contexts and webhook payloads
+pub fn event_type_key(event_type: NotificationEventType) -> &'static str {
+    match event_type {
+        NotificationEventType::TemperatureBreach => "TEMPERATURE_BREACH",
+        NotificationEventType::RequisitionReceived => "REQUISITION_RECEIVED",
+        NotificationEventType::ShipmentArrived => "SHIPMENT_ARRIVED",
+        NotificationEventType::StockExpiring => "STOCK_EXPIRING",
+    }
+}
+
+pub fn default_subject_template(event_type: NotificationEventType) -> &'static str {
+    match event_type {
+        NotificationEventType::TemperatureBreach => "Temperature breach at {{ store.name }}",
+        NotificationEventType::RequisitionReceived => {
+            "Requisition {{ record.requisition_number }} received from {{ record.customer_name }}"
+        }
+        NotificationEventType::ShipmentArrived => {
+            "Shipment {{ record.invoice_number }} from {{ record.supplier_name }} arrived"
+        }
+        NotificationEventType::StockExpiring => {
+            "{{ record.item_name }} expires on {{ record.expiry_date }}"
+        }
+    }
+}
+
+pub fn default_body_template(event_type: NotificationEventType) -> &'static str {
+    match event_type {
+        NotificationEventType::TemperatureBreach => {
+            "A {{ record.type }} temperature breach was detected by sensor {{ record.sensor_name }}\
+            {% if record.location_name %} in {{ record.location_name }}{% endif %} at \
+            {{ store.name }}, starting {{ record.start_datetime }} (UTC). The allowed range is \
+            {{ record.threshold_minimum }} to {{ record.threshold_maximum }}°C."
+        }
+        NotificationEventType::RequisitionReceived => {
+            "{{ store.name }} received requisition {{ record.requisition_number }} from \
+            {{ record.customer_name }}{% if record.their_reference %} (reference \
+            {{ record.their_reference }}){% endif %}."
+        }
+        NotificationEventType::ShipmentArrived => {
+            "Inbound shipment {{ record.invoice_number }} from {{ record.supplier_name }} was \
+            delivered to {{ store.name }} on {{ record.delivered_datetime }} (UTC)."
+        }
+        NotificationEventType::StockExpiring => {
+            "{{ record.number_of_packs }} pack(s) of {{ record.item_name }} \
+            ({{ record.item_code }}){% if record.batch %}, batch {{ record.batch }}{% endif %}, \
+            in {{ store.name }} expire on {{ record.expiry_date }}."
+        }
+    }
+}
+
+/// Checks that the template can be parsed, variables are only checked when rendering
+pub fn validate_template(template: &str) -> Result<(), String> {
+    tera::Tera::default()
+        .add_raw_template("notification", template)
+        .map_err(|error| format_tera_error(&error))
+}
+
+/// Renders a (Tera) notification template, the context has `event_type`, `store` (id and name)
+/// and `record` (fields of the notified record, see `events`)
+pub fn render_template(template: &str, context: &Value) -> Result<String, String> {
+    let context =
+        tera::Context::from_value(context.clone()).map_err(|error| format_tera_error(&error))?;
+    tera::Tera::one_off(template, &context, false).map_err(|error| format_tera_error(&error))
+}
+
+/// Tera errors only describe the cause in their source
+fn format_tera_error(error: &tera::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(error) = source {
+        message = format!("{}: {}", message, error);
+        source = error.source();
+    }
+    message
+}
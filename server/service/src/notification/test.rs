@@ -0,0 +1,307 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{mpsc, Arc},
+};
+
+use chrono::{Duration, Utc};
+use httpmock::{Method::POST, MockServer};
+use repository::{
+    mock::{mock_sensor_1, mock_store_a, MockDataInserts},
+    test_db::setup_all,
+    EqualFilter, KeyType, KeyValueStoreRepository, NotificationChannel, NotificationEventType,
+    NotificationOutboxFilter, NotificationOutboxRow, NotificationOutboxStatus,
+    TemperatureBreachRow, TemperatureBreachRowRepository,
+};
+
+use crate::{
+    notification::{
+        delivery::{send_due_notifications, DeliveryResult},
+        events::NotificationEventConsumer,
+        subscription::{UpsertNotificationSubscription, UpsertNotificationSubscriptionError},
+    },
+    processors::changelog_consumer::process_changelog_consumer,
+    service_provider::{ServiceContext, ServiceProvider},
+    settings::{NotificationSettings, SmtpSettings},
+};
+
+/// Minimal SMTP server that accepts a single connection, returns its port and a receiver for
+/// the received message
+fn smtp_stand_in() -> (u16, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (sender, receiver) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let mut reply = |response: &str| {
+            writer
+                .write_all(format!("{}\r\n", response).as_bytes())
+                .unwrap()
+        };
+        reply("220 localhost ESMTP");
+
+        let mut message = String::new();
+        let mut in_data = false;
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if in_data {
+                if line == "." {
+                    in_data = false;
+                    reply("250 OK");
+                } else {
+                    message.push_str(&line);
+                    message.push('\n');
+                }
+                continue;
+            }
+            match line
+                .get(..4)
+                .unwrap_or_default()
+                .to_ascii_uppercase()
+                .as_str()
+            {
+                "DATA" => {
+                    in_data = true;
+                    reply("354 Start mail input");
+                }
+                "QUIT" => {
+                    reply("221 Bye");
+                    break;
+                }
+                _ => reply("250 OK"),
+            }
+        }
+        sender.send(message).unwrap();
+    });
+
+    (port, receiver)
+}
+
+fn outbox_rows(
+    service_provider: &ServiceProvider,
+    ctx: &ServiceContext,
+    subscription_id: &str,
+) -> Vec<NotificationOutboxRow> {
+    service_provider
+        .notification_service
+        .get_notification_outbox(
+            ctx,
+            None,
+            Some(
+                NotificationOutboxFilter::new()
+                    .subscription_id(EqualFilter::equal_to(subscription_id)),
+            ),
+            None,
+        )
+        .unwrap()
+        .rows
+}
+
+#[actix_rt::test]
+async fn notifications() {
+    let (_, connection, connection_manager, _) = setup_all(
+        "notifications",
+        MockDataInserts::none().names().stores().sensors(),
+    )
+    .await;
+    KeyValueStoreRepository::new(&connection)
+        .set_i32(KeyType::SettingsSyncSiteId, Some(mock_store_a().site_id))
+        .unwrap();
+    let service_provider = Arc::new(ServiceProvider::new(connection_manager, "app_data"));
+    let ctx = service_provider
+        .context(mock_store_a().id, "".to_string())
+        .unwrap();
+    let service = &service_provider.notification_service;
+
+    let mock_server = MockServer::start();
+    let webhook = mock_server.mock(|when, then| {
+        when.method(POST)
+            .path("/breach")
+            .body_contains(r#""event_type":"TEMPERATURE_BREACH""#);
+        then.status(200);
+    });
+    let failing_webhook = mock_server.mock(|when, then| {
+        when.method(POST).path("/failing");
+        then.status(500);
+    });
+
+    let subscription =
+        |id: &str, channel: NotificationChannel, recipient: &str| UpsertNotificationSubscription {
+            id: id.to_string(),
+            event_type: NotificationEventType::TemperatureBreach,
+            channel,
+            recipient: recipient.to_string(),
+            is_active: true,
+            ..Default::default()
+        };
+
+    // Validation
+    assert_eq!(
+        service.upsert_notification_subscription(
+            &ctx,
+            subscription("invalid", NotificationChannel::Email, "not an email"),
+        ),
+        Err(UpsertNotificationSubscriptionError::InvalidRecipient)
+    );
+    assert_eq!(
+        service.upsert_notification_subscription(
+            &ctx,
+            subscription("invalid", NotificationChannel::Webhook, "ftp://example.org"),
+        ),
+        Err(UpsertNotificationSubscriptionError::InvalidRecipient)
+    );
+    assert!(matches!(
+        service.upsert_notification_subscription(
+            &ctx,
+            UpsertNotificationSubscription {
+                body_template: Some("{{ store.name".to_string()),
+                ..subscription("invalid", NotificationChannel::Email, "a@example.org")
+            },
+        ),
+        Err(UpsertNotificationSubscriptionError::InvalidTemplate(_))
+    ));
+
+    service
+        .upsert_notification_subscription(
+            &ctx,
+            subscription(
+                "email",
+                NotificationChannel::Email,
+                "cold.chain@example.org",
+            ),
+        )
+        .unwrap();
+    service
+        .upsert_notification_subscription(
+            &ctx,
+            UpsertNotificationSubscription {
+                subject_template: Some("Breach: {{ record.sensor_name }}".to_string()),
+                ..subscription(
+                    "webhook",
+                    NotificationChannel::Webhook,
+                    &mock_server.url("/breach"),
+                )
+            },
+        )
+        .unwrap();
+    service
+        .upsert_notification_subscription(
+            &ctx,
+            subscription(
+                "failing",
+                NotificationChannel::Webhook,
+                &mock_server.url("/failing"),
+            ),
+        )
+        .unwrap();
+
+    // Breaches that started before the subscriptions were created are not notified
+    let now = Utc::now().naive_utc();
+    let breach_repo = TemperatureBreachRowRepository::new(&connection);
+    let breach = |id: &str, start_datetime| TemperatureBreachRow {
+        id: id.to_string(),
+        sensor_id: mock_sensor_1().id,
+        store_id: mock_store_a().id,
+        start_datetime,
+        threshold_maximum: 8.0,
+        ..Default::default()
+    };
+    breach_repo
+        .upsert_one(&breach("old_breach", now - Duration::days(1)))
+        .unwrap();
+    breach_repo.upsert_one(&breach("new_breach", now)).unwrap();
+
+    let basic_ctx = service_provider.basic_context().unwrap();
+    process_changelog_consumer(
+        &service_provider,
+        &basic_ctx,
+        &NotificationEventConsumer,
+        now,
+    )
+    .unwrap();
+
+    let email = outbox_rows(&service_provider, &ctx, "email");
+    assert_eq!(email.len(), 1);
+    assert_eq!(email[0].record_id, "new_breach");
+    assert_eq!(email[0].status, NotificationOutboxStatus::Pending);
+    assert!(email[0].subject.starts_with("Temperature breach at"));
+    assert!(email[0].body.contains("hot consecutive"));
+    let webhook_row = outbox_rows(&service_provider, &ctx, "webhook");
+    assert_eq!(webhook_row.len(), 1);
+    assert_eq!(webhook_row[0].subject, "Breach: name_sensor_1");
+
+    // Each subscription is only notified once per record
+    breach_repo
+        .upsert_one(&TemperatureBreachRow {
+            end_datetime: Some(now),
+            ..breach("new_breach", now)
+        })
+        .unwrap();
+    process_changelog_consumer(
+        &service_provider,
+        &basic_ctx,
+        &NotificationEventConsumer,
+        now,
+    )
+    .unwrap();
+    assert_eq!(outbox_rows(&service_provider, &ctx, "email").len(), 1);
+
+    // Delivery
+    let (port, received_email) = smtp_stand_in();
+    let settings = NotificationSettings {
+        smtp: Some(SmtpSettings {
+            host: "127.0.0.1".to_string(),
+            port,
+            username: None,
+            password: None,
+            from: "oMSupply <noreply@example.org>".to_string(),
+            danger_allow_insecure: true,
+        }),
+        stock_expiry_days: None,
+    };
+    assert_eq!(
+        send_due_notifications(service_provider.clone(), Some(&settings), now).await,
+        Ok(DeliveryResult { sent: 2, failed: 1 })
+    );
+    webhook.assert();
+    let received_email = received_email
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .unwrap();
+    assert!(received_email.contains("cold.chain@example.org"));
+    assert!(received_email.contains("Temperature breach at"));
+    let email = outbox_rows(&service_provider, &ctx, "email");
+    assert_eq!(email[0].status, NotificationOutboxStatus::Sent);
+    assert_eq!(email[0].sent_datetime, Some(now));
+
+    // Failed deliveries are retried with backoff
+    let failing = outbox_rows(&service_provider, &ctx, "failing");
+    assert_eq!(failing[0].status, NotificationOutboxStatus::Pending);
+    assert_eq!(failing[0].attempts, 1);
+    assert_eq!(
+        failing[0].next_attempt_datetime,
+        Some(now + Duration::minutes(1))
+    );
+    assert_eq!(
+        send_due_notifications(service_provider.clone(), Some(&settings), now).await,
+        Ok(DeliveryResult::default())
+    );
+
+    for hour in 1..=4 {
+        send_due_notifications(
+            service_provider.clone(),
+            Some(&settings),
+            now + Duration::hours(hour),
+        )
+        .await
+        .unwrap();
+    }
+    failing_webhook.assert_hits(5);
+    let failing = outbox_rows(&service_provider, &ctx, "failing");
+    assert_eq!(failing[0].status, NotificationOutboxStatus::Failed);
+    assert_eq!(failing[0].attempts, 5);
+    assert_eq!(failing[0].next_attempt_datetime, None);
+}
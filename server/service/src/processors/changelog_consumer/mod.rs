@@ -0,0 +1,303 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use repository::{
+    ChangelogConsumerDeadLetterRow, ChangelogConsumerDeadLetterRowRepository, ChangelogConsumerRow,
+    ChangelogConsumerRowRepository, ChangelogFilter, ChangelogRepository, ChangelogRow,
+    ChangelogTableName, EqualFilter, RepositoryError,
+};
+use thiserror::Error;
+use util::uuid::uuid;
+
+use crate::{
+    cold_chain::breach_detection::TemperatureBreachDetectionConsumer,
+    notification::events::NotificationEventConsumer,
+    service_provider::{ServiceContext, ServiceProvider},
+    sync::is_initialised,
+};
+
+pub mod status;
+#[cfg(test)]
+mod test;
+
+const CHANGELOG_BATCH_SIZE: u32 = 20;
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+/// Delay before the first retry, doubled for every following attempt
+const RETRY_BASE_DELAY_SECONDS: i64 = 30;
+const RETRY_MAX_DELAY_SECONDS: i64 = 60 * 60;
+
+#[derive(Error, Debug)]
+pub enum ChangelogConsumerError {
+    /// The record will be retried with backoff until the consumer's max_attempts is reached
+    #[error("{0}")]
+    Retryable(String),
+    /// The record is moved to the dead letters straight away
+    #[error("{0}")]
+    Permanent(String),
+    #[error("{0:?}")]
+    DatabaseError(RepositoryError),
+}
+
+impl From<RepositoryError> for ChangelogConsumerError {
+    fn from(error: RepositoryError) -> Self {
+        ChangelogConsumerError::DatabaseError(error)
+    }
+}
+
+/// A background job that processes changelog records of the tables it's interested in.
+///
+/// The changelog cursor, retries and dead letters are managed by `process_changelog_consumer`,
+/// see README.md in this folder.
+pub trait ChangelogConsumer: Sync + Send {
+    /// Unique name of the consumer, used to persist its cursor and to identify its dead letters
+    fn name(&self) -> &'static str;
+
+    /// Changelog tables the consumer is interested in
+    fn table_names(&self) -> Vec<ChangelogTableName>;
+
+    /// Handles a single changelog record. Database changes made by the handler are committed in
+    /// the same transaction as the consumer cursor, and rolled back if an error is returned.
+    fn handle(
+        &self,
+        service_provider: &ServiceProvider,
+        ctx: &ServiceContext,
+        changelog: &ChangelogRow,
+    ) -> Result<(), ChangelogConsumerError>;
+
+    /// Handles all records of a batch at once, e.g. to process several changes of the same entity
+    /// only once. Returns false if the consumer handles records one by one with `handle` (default).
+    ///
+    /// Database changes are committed in the same transaction as the consumer cursor. If an error
+    /// is returned the batch is rolled back and its records are handled one by one, so the failing
+    /// record is retried or moved to the dead letters.
+    fn handle_batch(
+        &self,
+        _service_provider: &ServiceProvider,
+        _ctx: &ServiceContext,
+        _changelogs: &[ChangelogRow],
+    ) -> Result<bool, ChangelogConsumerError> {
+        Ok(false)
+    }
+
+    /// Number of attempts before a failing record is moved to the dead letters
+    fn max_attempts(&self) -> u32 {
+        DEFAULT_MAX_ATTEMPTS
+    }
+}
+
+/// All consumers run by the processors, add new consumers here
+pub(crate) fn changelog_consumers() -> Vec<Box<dyn ChangelogConsumer>> {
+    vec![
+        Box::new(TemperatureBreachDetectionConsumer),
+        Box::new(NotificationEventConsumer),
+    ]
+}
+
+#[derive(Error, Debug)]
+pub(crate) enum ProcessChangelogConsumersError {
+    #[error("Error in changelog consumer {0} ({1:?})")]
+    DatabaseError(&'static str, RepositoryError),
+}
+
+pub(crate) fn process_changelog_consumers(
+    service_provider: &ServiceProvider,
+) -> Result<(), ProcessChangelogConsumersError> {
+    // Records received during initialisation would otherwise be processed while the site is
+    // still being initialised
+    if !is_initialised(service_provider) {
+        return Ok(());
+    }
+
+    let now = Utc::now().naive_utc();
+    for consumer in changelog_consumers() {
+        // A database error in one consumer shouldn't stop the other consumers from running
+        let result = service_provider.basic_context().and_then(|ctx| {
+            process_changelog_consumer(service_provider, &ctx, consumer.as_ref(), now)
+        });
+        if let Err(e) = result {
+            log::error!(
+                "{}",
+                ProcessChangelogConsumersError::DatabaseError(consumer.name(), e)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Processes all outstanding changelog records for the consumer.
+///
+/// Records are processed in changelog order. When a record fails the consumer stops and the
+/// record is retried, with exponential backoff, on a later run (runs before `next_retry_datetime`
+/// are skipped). Once the record has failed `max_attempts` times, or straight away for
+/// `ChangelogConsumerError::Permanent`, it's recorded as a dead letter and the consumer moves on.
+///
+/// Returns the number of successfully handled records
+pub(crate) fn process_changelog_consumer(
+    service_provider: &ServiceProvider,
+    ctx: &ServiceContext,
+    consumer: &dyn ChangelogConsumer,
+    now: NaiveDateTime,
+) -> Result<u32, RepositoryError> {
+    let connection = &ctx.connection;
+    let consumer_repo = ChangelogConsumerRowRepository::new(connection);
+    let changelog_repo = ChangelogRepository::new(connection);
+
+    let mut state = consumer_repo
+        .find_one_by_id(consumer.name())?
+        .unwrap_or_else(|| ChangelogConsumerRow {
+            id: consumer.name().to_string(),
+            ..Default::default()
+        });
+
+    if let Some(next_retry_datetime) = state.next_retry_datetime {
+        if now < next_retry_datetime {
+            return Ok(0);
+        }
+    }
+
+    let filter = ChangelogFilter::new().table_name(EqualFilter {
+        equal_any: Some(consumer.table_names()),
+        ..Default::default()
+    });
+
+    let mut handled = 0;
+    loop {
+        let logs = changelog_repo.changelogs(
+            state.cursor as u64,
+            CHANGELOG_BATCH_SIZE,
+            Some(filter.clone()),
+        )?;
+
+        let Some(last_log) = logs.last() else {
+            break;
+        };
+
+        // Records that are being retried are handled one by one to keep counting their attempts
+        if state.retry_cursor.is_none() {
+            let processed_state = ChangelogConsumerRow {
+                cursor: last_log.cursor + 1,
+                retry_cursor: None,
+                retry_count: 0,
+                next_retry_datetime: None,
+                last_processed_datetime: Some(now),
+                ..state.clone()
+            };
+
+            let result = connection
+                .transaction_sync(|connection| -> Result<bool, ChangelogConsumerError> {
+                    if !consumer.handle_batch(service_provider, ctx, &logs)? {
+                        return Ok(false);
+                    }
+                    ChangelogConsumerRowRepository::new(connection).upsert_one(&processed_state)?;
+                    Ok(true)
+                })
+                .map_err(|error| error.to_inner_error());
+
+            match result {
+                Ok(true) => {
+                    state = processed_state;
+                    handled += logs.len() as u32;
+                    continue;
+                }
+                Ok(false) => {}
+                Err(error) => log::warn!(
+                    "Changelog consumer {} failed to process batch, handling records one by one: {}",
+                    consumer.name(),
+                    error
+                ),
+            }
+        }
+
+        for log in logs {
+            // A retried record could have been superseded by a newer change of the same record
+            // (changelog is deduped), in that case we start counting attempts again
+            let retry_count = match state.retry_cursor {
+                Some(retry_cursor) if retry_cursor == log.cursor => state.retry_count,
+                _ => 0,
+            };
+
+            let processed_state = ChangelogConsumerRow {
+                cursor: log.cursor + 1,
+                retry_cursor: None,
+                retry_count: 0,
+                next_retry_datetime: None,
+                last_processed_datetime: Some(now),
+                ..state.clone()
+            };
+
+            let result = connection
+                .transaction_sync(|connection| -> Result<(), ChangelogConsumerError> {
+                    consumer.handle(service_provider, ctx, &log)?;
+                    ChangelogConsumerRowRepository::new(connection).upsert_one(&processed_state)?;
+                    Ok(())
+                })
+                .map_err(|error| error.to_inner_error());
+
+            let error = match result {
+                Ok(()) => {
+                    state = processed_state;
+                    handled += 1;
+                    continue;
+                }
+                Err(error) => error,
+            };
+
+            let attempts = retry_count + 1;
+            let is_permanent = matches!(error, ChangelogConsumerError::Permanent(_));
+            let error = error.to_string();
+            log::error!(
+                "Changelog consumer {} failed to process {:?} {} (attempt {}): {}",
+                consumer.name(),
+                log.table_name,
+                log.record_id,
+                attempts,
+                error
+            );
+
+            if !is_permanent && (attempts as u32) < consumer.max_attempts() {
+                state = ChangelogConsumerRow {
+                    retry_cursor: Some(log.cursor),
+                    retry_count: attempts,
+                    next_retry_datetime: Some(now + retry_delay(attempts)),
+                    last_error: Some(error),
+                    ..state
+                };
+                consumer_repo.upsert_one(&state)?;
+                return Ok(handled);
+            }
+
+            state = ChangelogConsumerRow {
+                cursor: log.cursor + 1,
+                retry_cursor: None,
+                retry_count: 0,
+                next_retry_datetime: None,
+                last_error: Some(error.clone()),
+                ..state
+            };
+            let dead_letter = ChangelogConsumerDeadLetterRow {
+                id: uuid(),
+                consumer_id: consumer.name().to_string(),
+                changelog_cursor: log.cursor,
+                table_name: log.table_name.clone(),
+                record_id: log.record_id.clone(),
+                error,
+                attempts,
+                created_datetime: now,
+            };
+            connection
+                .transaction_sync(|connection| {
+                    ChangelogConsumerDeadLetterRowRepository::new(connection)
+                        .upsert_one(&dead_letter)?;
+                    ChangelogConsumerRowRepository::new(connection).upsert_one(&state)
+                })
+                .map_err(|error| error.to_inner_error())?;
+        }
+    }
+
+    Ok(handled)
+}
+
+/// Backoff before the next attempt, after `attempts` failed attempts
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = (attempts - 1).clamp(0, 16) as u32;
+    let seconds = RETRY_BASE_DELAY_SECONDS.saturating_mul(2_i64.pow(exponent));
+    Duration::seconds(seconds.min(RETRY_MAX_DELAY_SECONDS))
+}
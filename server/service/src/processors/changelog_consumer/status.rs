@@ -0,0 +1,92 @@
+use chrono::NaiveDateTime;
+use repository::{
+    ChangelogConsumerDeadLetterFilter, ChangelogConsumerDeadLetterRepository,
+    ChangelogConsumerDeadLetterRow, ChangelogConsumerRowRepository, ChangelogFilter,
+    ChangelogRepository, ChangelogTableName, EqualFilter, PaginationOption, RepositoryError,
+    StorageConnection,
+};
+
+use crate::{get_default_pagination, i64_to_u32, service_provider::ServiceContext};
+use crate::{ListError, ListResult};
+
+use super::{changelog_consumers, ChangelogConsumer};
+
+pub const MAX_LIMIT: u32 = 1000;
+pub const MIN_LIMIT: u32 = 1;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ChangelogConsumerStatus {
+    pub name: String,
+    pub table_names: Vec<ChangelogTableName>,
+    /// Next changelog cursor to be processed
+    pub cursor: u64,
+    /// Number of changelog records that are still to be processed by the consumer
+    pub pending_count: u64,
+    /// Failed attempts of the record that is currently waiting to be retried
+    pub retry_count: u32,
+    pub next_retry_datetime: Option<NaiveDateTime>,
+    pub last_error: Option<String>,
+    pub last_processed_datetime: Option<NaiveDateTime>,
+    pub dead_letter_count: u32,
+}
+
+pub trait ChangelogConsumerServiceTrait: Sync + Send {
+    /// Status of all registered changelog consumers
+    fn get_changelog_consumer_statuses(
+        &self,
+        ctx: &ServiceContext,
+    ) -> Result<Vec<ChangelogConsumerStatus>, RepositoryError> {
+        changelog_consumers()
+            .iter()
+            .map(|consumer| get_changelog_consumer_status(&ctx.connection, consumer.as_ref()))
+            .collect()
+    }
+
+    fn get_changelog_consumer_dead_letters(
+        &self,
+        ctx: &ServiceContext,
+        pagination: Option<PaginationOption>,
+        filter: Option<ChangelogConsumerDeadLetterFilter>,
+    ) -> Result<ListResult<ChangelogConsumerDeadLetterRow>, ListError> {
+        let pagination = get_default_pagination(pagination, MAX_LIMIT, MIN_LIMIT)?;
+        let repository = ChangelogConsumerDeadLetterRepository::new(&ctx.connection);
+        Ok(ListResult {
+            rows: repository.query(pagination, filter.clone())?,
+            count: i64_to_u32(repository.count(filter)?),
+        })
+    }
+}
+
+pub struct ChangelogConsumerService;
+impl ChangelogConsumerServiceTrait for ChangelogConsumerService {}
+
+pub(crate) fn get_changelog_consumer_status(
+    connection: &StorageConnection,
+    consumer: &dyn ChangelogConsumer,
+) -> Result<ChangelogConsumerStatus, RepositoryError> {
+    let state = ChangelogConsumerRowRepository::new(connection)
+        .find_one_by_id(consumer.name())?
+        .unwrap_or_default();
+    let filter = ChangelogFilter::new().table_name(EqualFilter {
+        equal_any: Some(consumer.table_names()),
+        ..Default::default()
+    });
+    let pending_count =
+        ChangelogRepository::new(connection).count(state.cursor as u64, Some(filter))?;
+    let dead_letter_count = ChangelogConsumerDeadLetterRepository::new(connection).count(Some(
+        ChangelogConsumerDeadLetterFilter::new()
+            .consumer_id(EqualFilter::equal_to(consumer.name())),
+    ))?;
+
+    Ok(ChangelogConsumerStatus {
+        name: consumer.name().to_string(),
+        table_names: consumer.table_names(),
+        cursor: state.cursor as u64,
+        pending_count,
+        retry_count: state.retry_count as u32,
+        next_retry_datetime: state.next_retry_datetime,
+        last_error: state.last_error,
+        last_processed_datetime: state.last_processed_datetime,
+        dead_letter_count: i64_to_u32(dead_letter_count),
+    })
+}
@@ -0,0 +1,181 @@
+use std::sync::Mutex;
+
+use chrono::{Duration, NaiveDate};
+use repository::{
+    mock::MockDataInserts, test_db::setup_all, ChangeLogInsertRow,
+    ChangelogConsumerDeadLetterFilter, ChangelogConsumerDeadLetterRepository, ChangelogRepository,
+    ChangelogRow, ChangelogTableName, RowActionType,
+};
+
+use crate::service_provider::{ServiceContext, ServiceProvider};
+
+use super::{
+    process_changelog_consumer, status::get_changelog_consumer_status, ChangelogConsumer,
+    ChangelogConsumerError,
+};
+
+struct TestConsumer {
+    handled: Mutex<Vec<String>>,
+}
+
+impl ChangelogConsumer for TestConsumer {
+    fn name(&self) -> &'static str {
+        "test_consumer"
+    }
+
+    fn table_names(&self) -> Vec<ChangelogTableName> {
+        vec![ChangelogTableName::Location, ChangelogTableName::Sensor]
+    }
+
+    fn handle(
+        &self,
+        _: &ServiceProvider,
+        _: &ServiceContext,
+        changelog: &ChangelogRow,
+    ) -> Result<(), ChangelogConsumerError> {
+        match changelog.record_id.as_str() {
+            "failing" => Err(ChangelogConsumerError::Retryable("failing".to_string())),
+            "permanent" => Err(ChangelogConsumerError::Permanent("permanent".to_string())),
+            record_id => {
+                self.handled.lock().unwrap().push(record_id.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    fn max_attempts(&self) -> u32 {
+        3
+    }
+}
+
+#[actix_rt::test]
+async fn changelog_consumer_retry_and_dead_letter() {
+    let (_, connection, connection_manager, _) = setup_all(
+        "changelog_consumer_retry_and_dead_letter",
+        MockDataInserts::none(),
+    )
+    .await;
+    let service_provider = ServiceProvider::new(connection_manager, "app_data");
+    let ctx = service_provider.basic_context().unwrap();
+
+    let changelog_repo = ChangelogRepository::new(&connection);
+    for (table_name, record_id) in [
+        (ChangelogTableName::Location, "a"),
+        (ChangelogTableName::Sensor, "failing"),
+        (ChangelogTableName::Invoice, "not_consumed"),
+        (ChangelogTableName::Location, "b"),
+        (ChangelogTableName::Location, "permanent"),
+    ] {
+        changelog_repo
+            .insert(&ChangeLogInsertRow {
+                table_name,
+                record_id: record_id.to_string(),
+                row_action: RowActionType::Upsert,
+                ..Default::default()
+            })
+            .unwrap();
+    }
+
+    let consumer = TestConsumer {
+        handled: Mutex::new(Vec::new()),
+    };
+    let now = NaiveDate::from_ymd_opt(2024, 1, 1)
+        .unwrap()
+        .and_hms_opt(10, 0, 0)
+        .unwrap();
+
+    // First attempt, stops at the failing record
+    assert_eq!(
+        process_changelog_consumer(&service_provider, &ctx, &consumer, now),
+        Ok(1)
+    );
+    assert_eq!(*consumer.handled.lock().unwrap(), vec!["a".to_string()]);
+    let status = get_changelog_consumer_status(&connection, &consumer).unwrap();
+    assert_eq!(status.retry_count, 1);
+    assert_eq!(status.pending_count, 3);
+    assert_eq!(status.last_error, Some("failing".to_string()));
+    assert_eq!(
+        status.next_retry_datetime,
+        Some(now + Duration::seconds(30))
+    );
+
+    // Backing off, nothing is processed
+    assert_eq!(
+        process_changelog_consumer(
+            &service_provider,
+            &ctx,
+            &consumer,
+            now + Duration::seconds(10)
+        ),
+        Ok(0)
+    );
+    assert_eq!(
+        get_changelog_consumer_status(&connection, &consumer)
+            .unwrap()
+            .retry_count,
+        1
+    );
+
+    // Second attempt, backoff is doubled
+    let now = now + Duration::seconds(30);
+    assert_eq!(
+        process_changelog_consumer(&service_provider, &ctx, &consumer, now),
+        Ok(0)
+    );
+    let status = get_changelog_consumer_status(&connection, &consumer).unwrap();
+    assert_eq!(status.retry_count, 2);
+    assert_eq!(
+        status.next_retry_datetime,
+        Some(now + Duration::seconds(60))
+    );
+
+    // Last attempt, failing and permanently failing records are moved to dead letters
+    let now = now + Duration::seconds(60);
+    assert_eq!(
+        process_changelog_consumer(&service_provider, &ctx, &consumer, now),
+        Ok(1)
+    );
+    assert_eq!(
+        *consumer.handled.lock().unwrap(),
+        vec!["a".to_string(), "b".to_string()]
+    );
+    let status = get_changelog_consumer_status(&connection, &consumer).unwrap();
+    assert_eq!(status.retry_count, 0);
+    assert_eq!(status.next_retry_datetime, None);
+    assert_eq!(status.pending_count, 0);
+    assert_eq!(status.dead_letter_count, 2);
+
+    let dead_letters = ChangelogConsumerDeadLetterRepository::new(&connection)
+        .query_by_filter(ChangelogConsumerDeadLetterFilter::new())
+        .unwrap();
+    let mut dead_letters: Vec<(String, ChangelogTableName, i32)> = dead_letters
+        .into_iter()
+        .map(|row| (row.record_id, row.table_name, row.attempts))
+        .collect();
+    dead_letters.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        dead_letters,
+        vec![
+            ("failing".to_string(), ChangelogTableName::Sensor, 3),
+            ("permanent".to_string(), ChangelogTableName::Location, 1),
+        ]
+    );
+
+    // New records are processed from the persisted cursor
+    changelog_repo
+        .insert(&ChangeLogInsertRow {
+            table_name: ChangelogTableName::Location,
+            record_id: "c".to_string(),
+            row_action: RowActionType::Upsert,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(
+        process_changelog_consumer(&service_provider, &ctx, &consumer, now),
+        Ok(1)
+    );
+    assert_eq!(
+        *consumer.handled.lock().unwrap(),
+        vec!["a".to_string(), "b".to_string(), "c".to_string()]
+    );
+}
@@ -1,33 +1,40 @@
 use std::sync::Arc;
 use thiserror::Error;
-use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
+use tokio::time::{self, Duration, Instant, MissedTickBehavior};
 
 use crate::service_provider::ServiceProvider;
 
+use self::changelog_consumer::{process_changelog_consumers, ProcessChangelogConsumersError};
 use self::transfer::invoice::ProcessInvoiceTransfersError;
 use self::transfer::requisition::ProcessRequisitionTransfersError;
 use self::transfer::{
     invoice::process_invoice_transfers, requisition::process_requisition_transfers,
 };
 
+pub mod changelog_consumer;
 #[cfg(test)]
 mod test_helpers;
 pub(crate) mod transfer;
 
 const CHANNEL_BUFFER_SIZE: usize = 30;
+/// Changelog consumers are also run periodically, to pick up local changes and to retry failed records
+const CHANGELOG_CONSUMER_INTERVAL: Duration = Duration::from_secs(30);
 
 #[derive(Clone)]
 pub struct ProcessorsTrigger {
     requisition_transfer: Sender<()>,
     invoice_transfer: Sender<()>,
+    changelog_consumer: Sender<()>,
     await_process_queue: Sender<oneshot::Sender<()>>,
 }
 
 pub struct Processors {
     requisition_transfer: Receiver<()>,
     invoice_transfer: Receiver<()>,
+    changelog_consumer: Receiver<()>,
     await_process_queue: Receiver<oneshot::Sender<()>>,
 }
 
@@ -37,6 +44,8 @@ enum ProcessorsError {
     InvoiceTransfer(ProcessInvoiceTransfersError),
     #[error("Error in requisition transfer processor ({0})")]
     RequisitionTransfer(ProcessRequisitionTransfersError),
+    #[error("{0}")]
+    ChangelogConsumer(ProcessChangelogConsumersError),
     #[error("Error when waiting for the process queue to be processed")]
     AwaitProcessQueue(()),
 }
@@ -49,17 +58,22 @@ impl Processors {
         let (invoice_transfer_sender, invoice_transfer_receiver) =
             mpsc::channel(CHANNEL_BUFFER_SIZE);
 
+        let (changelog_consumer_sender, changelog_consumer_receiver) =
+            mpsc::channel(CHANNEL_BUFFER_SIZE);
+
         let (request_check_sender, request_check_receiver) = mpsc::channel(CHANNEL_BUFFER_SIZE);
 
         (
             ProcessorsTrigger {
                 requisition_transfer: requisition_transfer_sender,
                 invoice_transfer: invoice_transfer_sender,
+                changelog_consumer: changelog_consumer_sender,
                 await_process_queue: request_check_sender,
             },
             Processors {
                 requisition_transfer: requisition_transfer_receiver,
                 invoice_transfer: invoice_transfer_receiver,
+                changelog_consumer: changelog_consumer_receiver,
                 await_process_queue: request_check_receiver,
             },
         )
@@ -69,10 +83,17 @@ impl Processors {
         let Processors {
             mut requisition_transfer,
             mut invoice_transfer,
+            mut changelog_consumer,
             mut await_process_queue,
         } = self;
 
         tokio::spawn(async move {
+            let mut changelog_consumer_interval = time::interval_at(
+                Instant::now() + CHANGELOG_CONSUMER_INTERVAL,
+                CHANGELOG_CONSUMER_INTERVAL,
+            );
+            changelog_consumer_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
             loop {
                 // See test below for reasoning behind biased, even though there is no foreseen use case where
                 // requisition must be processed before shipment, it easy to reason about future use cases if
@@ -86,6 +107,12 @@ impl Processors {
                     Some(_) = invoice_transfer.recv() => {
                         process_invoice_transfers(&service_provider).map_err(ProcessorsError::InvoiceTransfer)
                     },
+                    Some(_) = changelog_consumer.recv() => {
+                        process_changelog_consumers(&service_provider).map_err(ProcessorsError::ChangelogConsumer)
+                    },
+                    _ = changelog_consumer_interval.tick() => {
+                        process_changelog_consumers(&service_provider).map_err(ProcessorsError::ChangelogConsumer)
+                    },
                     Some(sender) = await_process_queue.recv() => {
                         sender.send(()).map_err(ProcessorsError::AwaitProcessQueue)
                     },
@@ -117,6 +144,14 @@ impl ProcessorsTrigger {
         }
     }
 
+    pub(crate) fn trigger_changelog_consumers(&self) {
+        match self.changelog_consumer.try_send(()) {
+            // Consumers process all outstanding changelogs, a full queue means they will run anyway
+            Ok(()) | Err(TrySendError::Full(_)) => {}
+            Err(error) => log::error!("Problem triggering changelog consumers {:#?}", error),
+        }
+    }
+
     /// Waits till all current events in the processor queue are handled.
     /// Its guaranteed that all queued processor events that where in the queue before calling
     /// this method are handled when this method returns.
@@ -143,6 +178,7 @@ impl ProcessorsTrigger {
         ProcessorsTrigger {
             requisition_transfer: mpsc::channel(1).0,
             invoice_transfer: mpsc::channel(1).0,
+            changelog_consumer: mpsc::channel(1).0,
             await_process_queue: mpsc::channel(1).0,
         }
     }
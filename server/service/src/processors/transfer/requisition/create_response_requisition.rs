@@ -1,6 +1,10 @@
 use crate::{
-    activity_log::system_activity_log_entry, number::next_number,
-    requisition::common::get_lines_for_requisition, store_preference::get_store_preferences,
+    activity_log::system_activity_log_entry,
+    number::next_number,
+    requisition::{
+        approval::{get_response_requisition_approval, ResponseRequisitionApproval},
+        common::get_lines_for_requisition,
+    },
 };
 
 use super::{RequisitionTransferProcessor, RequisitionTransferProcessorRecord};
@@ -56,17 +60,18 @@ impl RequisitionTransferProcessor for CreateResponseRequisitionProcessor {
 
         // Execute
 
-        // Check if approval status needs to be set
+        // Check if approval status needs to be set, remote authorisation takes precedence over
+        // approval chains of the supplying store
         // TODO link to documentation of how remote authorisation works
-        let store_preference =
-            get_store_preferences(connection, &record_for_processing.other_party_store_id)?;
-        // TODO Rework once plugin functionality has been implemented
-        let approval_status = if store_preference.response_requisition_requires_authorisation
-            && request_requisition.requisition_row.program_id.is_some()
-        {
-            Some(ApprovalStatusType::Pending)
-        } else {
-            None
+        let approval = get_response_requisition_approval(
+            connection,
+            &record_for_processing.other_party_store_id,
+            request_requisition.requisition_row.program_id.as_deref(),
+        )?;
+        let approval_status = match approval {
+            ResponseRequisitionApproval::NotRequired => None,
+            ResponseRequisitionApproval::ApprovalChain
+            | ResponseRequisitionApproval::RemoteAuthorisation => Some(ApprovalStatusType::Pending),
         };
 
         let new_response_requisition = RequisitionRow {
@@ -74,11 +79,17 @@ impl RequisitionTransferProcessor for CreateResponseRequisitionProcessor {
             ..generate_response_requisition(connection, request_requisition, record_for_processing)?
         };
 
-        let new_requisition_lines = generate_response_requisition_lines(
+        let mut new_requisition_lines = generate_response_requisition_lines(
             connection,
             &new_response_requisition.id,
             &request_requisition.requisition_row,
         )?;
+        // Approvers reduce approved quantities from the requested quantities
+        if approval == ResponseRequisitionApproval::ApprovalChain {
+            for line in new_requisition_lines.iter_mut() {
+                line.approved_quantity = line.requested_quantity;
+            }
+        }
 
         RequisitionRowRepository::new(connection).upsert_one(&new_response_requisition)?;
 
@@ -165,6 +176,7 @@ fn generate_response_requisition(
         program_id: request_requisition_row.program_id.clone(),
         period_id: request_requisition_row.period_id.clone(),
         order_type: request_requisition_row.order_type.clone(),
+        is_emergency: request_requisition_row.is_emergency,
         // Default
         user_id: None,
         approval_status: None,
@@ -202,6 +214,12 @@ fn generate_response_requisition_lines(
                          snapshot_datetime,
                          comment,
                          item_name,
+                         opening_balance,
+                         quantity_received,
+                         quantity_dispensed,
+                         losses_and_adjustments,
+                         closing_balance,
+                         days_out_of_stock,
                      },
                  item_row: ItemRow { id: item_id, .. },
                  requisition_row: _,
@@ -216,6 +234,12 @@ fn generate_response_requisition_lines(
                 snapshot_datetime,
                 comment: comment.clone(),
                 item_name,
+                opening_balance,
+                quantity_received,
+                quantity_dispensed,
+                losses_and_adjustments,
+                closing_balance,
+                days_out_of_stock,
                 // Default
                 supply_quantity: 0.0,
                 approved_quantity: 0.0,
@@ -226,6 +226,7 @@ impl RequisitionTransferTester {
             r.comment = Some("some comment".to_string());
             r.max_months_of_stock = 10.0;
             r.min_months_of_stock = 5.0;
+            r.is_emergency = true;
         });
 
         let request_requisition_line1 = inline_init(|r: &mut RequisitionLineRow| {
@@ -348,6 +349,7 @@ impl RequisitionTransferTester {
             response_requisition.expected_delivery_date,
             self.request_requisition.expected_delivery_date
         );
+        assert!(response_requisition.is_emergency);
 
         assert_eq!(
             RequisitionLineRepository::new(connection)
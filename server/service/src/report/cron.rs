@@ -0,0 +1,238 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+/// A parsed cron expression with the five standard fields:
+/// `minute hour day-of-month month day-of-week`
+///
+/// Each field supports `*`, single values, ranges (`1-5`), lists (`1,15`) and steps (`*/15`,
+/// `0-30/10`). Day of week is 0-7 where both 0 and 7 are Sunday.
+/// The aliases `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` are supported as well.
+///
+/// Like in standard cron, if both day of month and day of week are restricted a day matches if
+/// either of them matches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u32,
+    days_of_month: u32,
+    months: u16,
+    days_of_week: u8,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+/// Give up searching for the next matching time after this number of years, e.g. for schedules
+/// like `0 0 30 2 *` that never match
+const MAX_SEARCH_YEARS: i32 = 5;
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<CronSchedule, String> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            expression => expression,
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields.as_slice() else {
+            return Err(format!(
+                "Expected 5 fields (minute hour day-of-month month day-of-week) but got {}",
+                fields.len()
+            ));
+        };
+
+        let mut days_of_week = parse_field(day_of_week, 0, 7)?;
+        // 7 is an alias for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+            days_of_week &= !(1 << 7);
+        }
+
+        Ok(CronSchedule {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)? as u32,
+            days_of_month: parse_field(day_of_month, 1, 31)? as u32,
+            months: parse_field(month, 1, 12)? as u16,
+            days_of_week: days_of_week as u8,
+            day_of_month_restricted: !day_of_month.starts_with('*'),
+            day_of_week_restricted: !day_of_week.starts_with('*'),
+        })
+    }
+
+    /// Returns the first matching time strictly after the provided datetime
+    pub fn next_after(&self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        let max_year = datetime.year() + MAX_SEARCH_YEARS;
+        // start at the next full minute
+        let mut next = datetime.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+
+        while next.year() <= max_year {
+            if self.months & (1 << next.month()) == 0 {
+                let (year, month) = match next.month() {
+                    12 => (next.year() + 1, 1),
+                    month => (next.year(), month + 1),
+                };
+                next = NaiveDate::from_ymd_opt(year, month, 1)?.and_time(NaiveTime::MIN);
+                continue;
+            }
+            if !self.matches_day(next.date()) {
+                next = next.date().succ_opt()?.and_time(NaiveTime::MIN);
+                continue;
+            }
+            if self.hours & (1 << next.hour()) == 0 {
+                next = next.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if self.minutes & (1 << next.minute()) == 0 {
+                next += Duration::minutes(1);
+                continue;
+            }
+            return Some(next);
+        }
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = self.days_of_month & (1 << date.day()) != 0;
+        let day_of_week = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            (true, false) => day_of_month,
+            (false, _) => day_of_week,
+        }
+    }
+}
+
+/// Parses a single cron field into a bit set of matching values
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut result = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("Invalid step in cron field: {}", part))?;
+                if step == 0 {
+                    return Err(format!("Invalid step in cron field: {}", part));
+                }
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (parse_value(start, min, max)?, parse_value(end, min, max)?),
+                None => {
+                    let start = parse_value(range, min, max)?;
+                    // e.g. "5/10" means every 10th value starting from 5
+                    (start, if step.is_some() { max } else { start })
+                }
+            },
+        };
+        if start > end {
+            return Err(format!("Invalid range in cron field: {}", part));
+        }
+
+        for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            result |= 1 << value;
+        }
+    }
+    Ok(result)
+}
+
+fn parse_value(value: &str, min: u32, max: u32) -> Result<u32, String> {
+    let parsed: u32 = value
+        .parse()
+        .map_err(|_| format!("Invalid value in cron field: {}", value))?;
+    if parsed < min || parsed > max {
+        return Err(format!(
+            "Value {} out of range in cron field, expected {}-{}",
+            parsed, min, max
+        ));
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    use super::CronSchedule;
+
+    fn datetime(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn cron_next_after() {
+        // monthly on the 1st at 6:30
+        let schedule = CronSchedule::parse("30 6 1 * *").unwrap();
+        assert_eq!(
+            schedule.next_after(datetime(2024, 1, 15, 10, 0)),
+            Some(datetime(2024, 2, 1, 6, 30))
+        );
+        // strictly after
+        assert_eq!(
+            schedule.next_after(datetime(2024, 2, 1, 6, 30)),
+            Some(datetime(2024, 3, 1, 6, 30))
+        );
+        assert_eq!(
+            schedule.next_after(datetime(2024, 12, 2, 0, 0)),
+            Some(datetime(2025, 1, 1, 6, 30))
+        );
+
+        // aliases
+        let schedule = CronSchedule::parse("@monthly").unwrap();
+        assert_eq!(
+            schedule.next_after(datetime(2024, 1, 1, 0, 0)),
+            Some(datetime(2024, 2, 1, 0, 0))
+        );
+        // 2024-01-03 is a Wednesday
+        let schedule = CronSchedule::parse("@weekly").unwrap();
+        assert_eq!(
+            schedule.next_after(datetime(2024, 1, 3, 12, 0)),
+            Some(datetime(2024, 1, 7, 0, 0))
+        );
+
+        // steps, ranges and lists: every 15 min during working hours on weekdays
+        let schedule = CronSchedule::parse("*/15 8-17 * * 1-5").unwrap();
+        assert_eq!(
+            schedule.next_after(datetime(2024, 1, 5, 17, 50)),
+            Some(datetime(2024, 1, 8, 8, 0))
+        );
+        assert_eq!(
+            schedule.next_after(datetime(2024, 1, 8, 8, 1)),
+            Some(datetime(2024, 1, 8, 8, 15))
+        );
+
+        // day of month OR day of week: 15th or Sundays (7 is an alias for Sunday)
+        let schedule = CronSchedule::parse("0 0 15 * 7").unwrap();
+        assert_eq!(
+            schedule.next_after(datetime(2024, 1, 8, 0, 0)),
+            Some(datetime(2024, 1, 14, 0, 0))
+        );
+        assert_eq!(
+            schedule.next_after(datetime(2024, 1, 14, 0, 0)),
+            Some(datetime(2024, 1, 15, 0, 0))
+        );
+
+        // never matches
+        let schedule = CronSchedule::parse("0 0 30 2 *").unwrap();
+        assert_eq!(schedule.next_after(datetime(2024, 1, 1, 0, 0)), None);
+
+        // invalid
+        assert!(CronSchedule::parse("0 0 1 *").is_err());
+        assert!(CronSchedule::parse("60 0 1 * *").is_err());
+        assert!(CronSchedule::parse("0 0 0 * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("0 5-1 * * *").is_err());
+        assert!(CronSchedule::parse("a * * * *").is_err());
+    }
+}
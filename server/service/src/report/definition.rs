@@ -53,13 +53,127 @@ pub struct TeraTemplate {
 /// The output format that is produced by a report
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub enum ReportOutputType {
+    /// HTML, printed to PDF using headless Chrome
     Html,
+    /// HTML, printed to PDF using the built-in renderer (no browser required).
+    /// Only supports a subset of HTML, i.e. text, headings and tables without css styling.
+    NativePdf,
+    /// HTML tables, exported as an Excel workbook with one worksheet per table
+    Xlsx,
+    /// HTML tables, exported as CSV (tables are separated by an empty line)
+    Csv,
+}
+
+/// Maps query results directly to spreadsheet columns, i.e. no Tera template is needed.
+/// Can be used as the main template of a report.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct SpreadsheetTemplate {
+    /// Must be Xlsx or Csv
+    pub output: ReportOutputType,
+    pub sheets: Vec<SheetMapping>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct SheetMapping {
+    pub name: String,
+    /// Dot separated path to the list of rows in the report data, e.g. "invoice.lines.nodes" or
+    /// the name of a SQL query
+    pub rows: String,
+    /// Columns of the sheet, if empty all fields of the first row are used
+    #[serde(default)]
+    pub columns: Vec<ColumnMapping>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct ColumnMapping {
+    pub header: String,
+    /// Dot separated path to the value in the row, e.g. "item.name"
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub enum PageSize {
+    #[default]
+    A4,
+    A3,
+    A5,
+    Letter,
+    Legal,
+    Custom {
+        width_mm: f64,
+        height_mm: f64,
+    },
+}
+
+impl PageSize {
+    /// (width, height) in mm, in portrait orientation
+    pub fn dimensions_mm(&self) -> (f64, f64) {
+        match self {
+            PageSize::A4 => (210.0, 297.0),
+            PageSize::A3 => (297.0, 420.0),
+            PageSize::A5 => (148.0, 210.0),
+            PageSize::Letter => (215.9, 279.4),
+            PageSize::Legal => (215.9, 355.6),
+            PageSize::Custom {
+                width_mm,
+                height_mm,
+            } => (*width_mm, *height_mm),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub enum PageOrientation {
+    #[default]
+    Portrait,
+    Landscape,
+}
+
+/// Page margins in mm
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct PageMargins {
+    pub top: f64,
+    pub bottom: f64,
+    pub left: f64,
+    pub right: f64,
+}
+
+impl Default for PageMargins {
+    fn default() -> Self {
+        PageMargins {
+            top: 10.0,
+            bottom: 10.0,
+            left: 10.0,
+            right: 10.0,
+        }
+    }
+}
+
+/// Page layout used when printing a report to PDF
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(default)]
+pub struct PageSettings {
+    pub size: PageSize,
+    pub orientation: PageOrientation,
+    pub margins: PageMargins,
+}
+
+impl PageSettings {
+    /// (width, height) in mm, taking orientation into account
+    pub fn page_dimensions_mm(&self) -> (f64, f64) {
+        let (width, height) = self.size.dimensions_mm();
+        match self.orientation {
+            PageOrientation::Portrait => (width, height),
+            PageOrientation::Landscape => (height, width),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[serde(tag = "type", content = "data")]
 pub enum ReportDefinitionEntry {
     TeraTemplate(TeraTemplate),
+    SpreadsheetTemplate(SpreadsheetTemplate),
     /// Custom http query
     GraphGLQuery(GraphQlQuery),
     /// Use default predefined query
@@ -78,6 +192,10 @@ pub struct ReportDefinitionIndex {
     pub footer: Option<String>,
     #[serde(deserialize_with = "string_or_vec")]
     pub query: Vec<String>,
+    /// Page layout for PDF output. If not set, the css page size and margins of the template are
+    /// used (native PDF output defaults to A4 portrait)
+    #[serde(default)]
+    pub page: Option<PageSettings>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -93,8 +211,8 @@ mod report_dsl_test {
     use serde_json::json;
 
     use crate::report::definition::{
-        DefaultQuery, ReportDefinition, ReportDefinitionEntry, ReportDefinitionIndex,
-        ReportOutputType, ReportRef, TeraTemplate,
+        DefaultQuery, PageMargins, PageOrientation, PageSettings, PageSize, ReportDefinition,
+        ReportDefinitionEntry, ReportDefinitionIndex, ReportOutputType, ReportRef, TeraTemplate,
     };
 
     #[test]
@@ -107,7 +225,11 @@ mod report_dsl_test {
             "index": {
                 "template": "template.html",
                 "footer": "local_footer.html",
-                "query": "query"
+                "query": "query",
+                "page": {
+                    "size": "Letter",
+                    "orientation": "Landscape"
+                }
             },
             "entries": {
               "template.html": {
@@ -149,6 +271,11 @@ mod report_dsl_test {
                     header: None,
                     footer: Some("local_footer.html".to_string()),
                     query: vec!["query".to_string()],
+                    page: Some(PageSettings {
+                        size: PageSize::Letter,
+                        orientation: PageOrientation::Landscape,
+                        margins: PageMargins::default(),
+                    }),
                 },
                 entries: HashMap::from([
                     (
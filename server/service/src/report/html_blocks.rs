@@ -0,0 +1,404 @@
+//! Parser for the subset of HTML supported by the native report outputs, i.e. the native PDF
+//! renderer and the spreadsheet outputs.
+//! Only text, headings, paragraphs, line breaks, horizontal rules and tables are recognised,
+//! styling (css) is ignored and nested tables are flattened into the cell text.
+
+pub(super) const FONT_SIZE: f64 = 10.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct TextStyle {
+    pub(super) bold: bool,
+    pub(super) font_size: f64,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        TextStyle {
+            bold: false,
+            font_size: FONT_SIZE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(super) struct TableCell {
+    pub(super) text: String,
+    pub(super) bold: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(super) struct TableRow {
+    pub(super) cells: Vec<TableCell>,
+    pub(super) is_header: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Block {
+    Paragraph { text: String, style: TextStyle },
+    Table { rows: Vec<TableRow> },
+    Rule,
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Start(String),
+    End(String),
+    Text(String),
+}
+
+const VOID_TAGS: [&str; 7] = ["br", "hr", "img", "meta", "link", "input", "col"];
+const BLOCK_TAGS: [&str; 16] = [
+    "p", "div", "h1", "h2", "h3", "h4", "h5", "h6", "li", "ul", "ol", "section", "header",
+    "footer", "body", "article",
+];
+
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    let mut skip_until: Option<String> = None;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            if skip_until.is_none() {
+                tokens.push(Token::Text(decode_entities(rest)));
+            }
+            break;
+        };
+        if tag_start > 0 && skip_until.is_none() {
+            tokens.push(Token::Text(decode_entities(&rest[..tag_start])));
+        }
+        rest = &rest[tag_start..];
+
+        // Comments and doctype
+        if rest.starts_with("<!--") {
+            rest = rest
+                .find("-->")
+                .map(move |end| &rest[end + 3..])
+                .unwrap_or("");
+            continue;
+        }
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..tag_end];
+        rest = &rest[tag_end + 1..];
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        let is_end = tag.starts_with('/');
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+
+        if let Some(skip_tag) = &skip_until {
+            if is_end && &name == skip_tag {
+                skip_until = None;
+            }
+            continue;
+        }
+        if is_end {
+            tokens.push(Token::End(name));
+            continue;
+        }
+        // Content of these is not rendered
+        if (name == "style" || name == "script" || name == "head") && !tag.ends_with('/') {
+            skip_until = Some(name);
+            continue;
+        }
+        let self_closing = tag.ends_with('/') || VOID_TAGS.contains(&name.as_str());
+        tokens.push(Token::Start(name.clone()));
+        if self_closing {
+            tokens.push(Token::End(name));
+        }
+    }
+
+    tokens
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn heading_font_size(tag: &str) -> Option<f64> {
+    match tag {
+        "h1" => Some(18.0),
+        "h2" => Some(15.0),
+        "h3" => Some(13.0),
+        "h4" | "h5" | "h6" => Some(11.0),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct TableBuilder {
+    rows: Vec<TableRow>,
+    in_head: bool,
+}
+
+#[derive(Default)]
+struct BlockBuilder {
+    blocks: Vec<Block>,
+    text: String,
+    style: TextStyle,
+    bold_depth: u32,
+    tables: Vec<TableBuilder>,
+}
+
+impl BlockBuilder {
+    fn push_text(&mut self, text: &str) {
+        let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if collapsed.is_empty() {
+            if !text.is_empty() && !self.current_text().ends_with([' ', '\n']) {
+                self.current_text_mut().push(' ');
+            }
+            return;
+        }
+        let starts_with_space = text.starts_with(char::is_whitespace);
+        let ends_with_space = text.ends_with(char::is_whitespace);
+        let current = self.current_text_mut();
+        if starts_with_space && !current.is_empty() && !current.ends_with([' ', '\n']) {
+            current.push(' ');
+        }
+        current.push_str(&collapsed);
+        if ends_with_space {
+            current.push(' ');
+        }
+    }
+
+    /// Text of the current table cell (outermost table only) or paragraph
+    fn current_text(&self) -> &str {
+        match self.tables.first().and_then(|table| table.rows.last()) {
+            Some(row) if self.tables.len() == 1 => row
+                .cells
+                .last()
+                .map(|cell| cell.text.as_str())
+                .unwrap_or(""),
+            _ => &self.text,
+        }
+    }
+
+    fn current_text_mut(&mut self) -> &mut String {
+        let in_cell = self.tables.len() == 1
+            && matches!(self.tables[0].rows.last(), Some(row) if !row.cells.is_empty());
+        if !in_cell {
+            return &mut self.text;
+        }
+        let row = self.tables[0].rows.last_mut().unwrap();
+        &mut row.cells.last_mut().unwrap().text
+    }
+
+    fn line_break(&mut self) {
+        let text = self.current_text_mut();
+        let trimmed_length = text.trim_end_matches(' ').len();
+        text.truncate(trimmed_length);
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+    }
+
+    fn flush_paragraph(&mut self) {
+        if !self.tables.is_empty() {
+            self.line_break();
+            return;
+        }
+        let text = std::mem::take(&mut self.text);
+        let text = text.trim();
+        if !text.is_empty() {
+            self.blocks.push(Block::Paragraph {
+                text: text.to_string(),
+                style: TextStyle {
+                    bold: self.style.bold || self.bold_depth > 0,
+                    font_size: self.style.font_size,
+                },
+            });
+        }
+    }
+
+    fn start(&mut self, tag: &str) {
+        match tag {
+            "b" | "strong" => self.bold_depth += 1,
+            "br" => self.line_break(),
+            "hr" => {
+                self.flush_paragraph();
+                if self.tables.is_empty() {
+                    self.blocks.push(Block::Rule);
+                }
+            }
+            "table" => {
+                self.flush_paragraph();
+                self.tables.push(TableBuilder::default());
+            }
+            "thead" => {
+                if let Some(table) = self.tables.last_mut() {
+                    table.in_head = true;
+                }
+            }
+            "tr" => match self.tables.len() {
+                0 => {}
+                1 => {
+                    let table = &mut self.tables[0];
+                    let is_header = table.in_head;
+                    table.rows.push(TableRow {
+                        cells: Vec::new(),
+                        is_header,
+                    });
+                }
+                // Nested table rows are printed as lines in the outer cell
+                _ => self.line_break(),
+            },
+            "td" | "th" => match self.tables.len() {
+                0 => {}
+                1 => {
+                    let table = &mut self.tables[0];
+                    if table.rows.is_empty() {
+                        table.rows.push(TableRow::default());
+                    }
+                    let row = table.rows.last_mut().unwrap();
+                    row.cells.push(TableCell {
+                        text: String::new(),
+                        bold: tag == "th",
+                    });
+                }
+                _ => {
+                    if !self.current_text().is_empty() && !self.current_text().ends_with('\n') {
+                        self.current_text_mut().push(' ');
+                    }
+                }
+            },
+            _ => {
+                if let Some(font_size) = heading_font_size(tag) {
+                    self.flush_paragraph();
+                    self.style = TextStyle {
+                        bold: true,
+                        font_size,
+                    };
+                } else if BLOCK_TAGS.contains(&tag) {
+                    self.flush_paragraph();
+                }
+            }
+        }
+    }
+
+    fn end(&mut self, tag: &str) {
+        match tag {
+            "b" | "strong" => self.bold_depth = self.bold_depth.saturating_sub(1),
+            "table" => {
+                if let Some(table) = self.tables.pop() {
+                    if self.tables.is_empty() {
+                        let rows = table
+                            .rows
+                            .into_iter()
+                            .filter(|row| !row.cells.is_empty())
+                            .map(|mut row| {
+                                for cell in row.cells.iter_mut() {
+                                    cell.text = cell.text.trim().to_string();
+                                }
+                                // Rows of only th cells are also treated as header rows
+                                row.is_header =
+                                    row.is_header || row.cells.iter().all(|cell| cell.bold);
+                                row
+                            })
+                            .collect::<Vec<_>>();
+                        if !rows.is_empty() {
+                            self.blocks.push(Block::Table { rows });
+                        }
+                    } else {
+                        self.line_break();
+                    }
+                }
+            }
+            "thead" => {
+                if let Some(table) = self.tables.last_mut() {
+                    table.in_head = false;
+                }
+            }
+            _ => {
+                if heading_font_size(tag).is_some() {
+                    self.flush_paragraph();
+                    self.style = TextStyle::default();
+                } else if BLOCK_TAGS.contains(&tag) {
+                    self.flush_paragraph();
+                }
+            }
+        }
+    }
+}
+
+/// Parses the supported subset of HTML into a flat list of blocks
+pub(super) fn parse_html(html: &str) -> Vec<Block> {
+    let mut builder = BlockBuilder::default();
+    for token in tokenize(html) {
+        match token {
+            Token::Start(tag) => builder.start(&tag),
+            Token::End(tag) => builder.end(&tag),
+            Token::Text(text) => builder.push_text(&text),
+        }
+    }
+    // Unclosed tables
+    while !builder.tables.is_empty() {
+        builder.end("table");
+    }
+    builder.flush_paragraph();
+    builder.blocks
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_html, Block, TextStyle};
+
+    #[test]
+    fn parse_html_blocks() {
+        let blocks = parse_html(
+            r#"<html><head><style>p { color: red; }</style></head><body>
+                <h1>Title</h1>
+                <p>Some   <b>bold</b>
+                   text &amp; more</p>
+                <table>
+                    <thead><tr><th>Item</th><th>Quantity</th></tr></thead>
+                    <tbody>
+                        <tr><td>Item A</td><td>10</td></tr>
+                        <tr><td>Item B<br/>second line</td><td>5</td></tr>
+                    </tbody>
+                </table>
+                <hr>
+            </body></html>"#,
+        );
+
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(
+            blocks[0],
+            Block::Paragraph {
+                text: "Title".to_string(),
+                style: TextStyle {
+                    bold: true,
+                    font_size: 18.0
+                }
+            }
+        );
+        assert_eq!(
+            blocks[1],
+            Block::Paragraph {
+                text: "Some bold text & more".to_string(),
+                style: TextStyle::default()
+            }
+        );
+        let Block::Table { rows } = &blocks[2] else {
+            panic!("Expected table");
+        };
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].is_header);
+        assert!(!rows[1].is_header);
+        assert_eq!(rows[2].cells[0].text, "Item B\nsecond line");
+        assert_eq!(blocks[3], Block::Rule);
+    }
+}
@@ -2,29 +2,37 @@ use std::{fs, path::PathBuf, str::FromStr};
 
 use headless_chrome::{types::PrintToPdfOptions, Browser, LaunchOptionsBuilder};
 
+use super::definition::{PageOrientation, PageSettings};
+
+const MM_PER_INCH: f64 = 25.4;
+
 pub fn html_to_pdf(
     temp_dir: &Option<String>,
     document: &str,
     document_id: &str,
+    page: Option<&PageSettings>,
 ) -> Result<Vec<u8>, anyhow::Error> {
-    let pdf_options = Some(PrintToPdfOptions {
-        display_header_footer: Some(false),
-        prefer_css_page_size: None,
-        landscape: None,
-        print_background: None,
-        scale: None,
-        // Assuming 96 DPI (dots per inch)
-        paper_width: None,
-        paper_height: None,
-        margin_top: None,
-        margin_bottom: None,
-        margin_left: None,
-        margin_right: None,
-        page_ranges: None,
-        ignore_invalid_page_ranges: None,
-        header_template: None,
-        footer_template: None,
-        transfer_mode: None,
+    let pdf_options = Some(match page {
+        Some(page) => page_print_options(page),
+        // Css @page rules of the report decide the layout
+        None => PrintToPdfOptions {
+            display_header_footer: Some(false),
+            prefer_css_page_size: None,
+            landscape: None,
+            print_background: None,
+            scale: None,
+            paper_width: None,
+            paper_height: None,
+            margin_top: None,
+            margin_bottom: None,
+            margin_left: None,
+            margin_right: None,
+            page_ranges: None,
+            ignore_invalid_page_ranges: None,
+            header_template: None,
+            footer_template: None,
+            transfer_mode: None,
+        },
     });
 
     let temp_dir = match temp_dir {
@@ -45,14 +53,42 @@ pub fn html_to_pdf(
     fs::write(&temp_html_doc_path, document)?;
 
     // create a new browser and a tab in that browser using headless-chrome
-    let launch_options = LaunchOptionsBuilder::default().headless(true).build()?;
-    let local_pdf = Browser::new(launch_options)?
-        .new_tab()?
-        .navigate_to(&format!("file:{}", temp_html_doc_path.to_string_lossy()))?
-        .wait_until_navigated()?
-        .print_to_pdf(pdf_options)?;
-
-    // clean up
-    fs::remove_file(temp_html_doc_path)?;
-    Ok(local_pdf)
+    let print = || -> Result<Vec<u8>, anyhow::Error> {
+        let launch_options = LaunchOptionsBuilder::default().headless(true).build()?;
+        let pdf = Browser::new(launch_options)?
+            .new_tab()?
+            .navigate_to(&format!("file:{}", temp_html_doc_path.to_string_lossy()))?
+            .wait_until_navigated()?
+            .print_to_pdf(pdf_options)?;
+        Ok(pdf)
+    };
+    let result = print();
+
+    // clean up, also if printing failed
+    fs::remove_file(&temp_html_doc_path)?;
+    result
+}
+
+fn page_print_options(page: &PageSettings) -> PrintToPdfOptions {
+    // Chrome applies the orientation to the paper size, i.e. paper size is in portrait
+    let (paper_width, paper_height) = page.size.dimensions_mm();
+    let margins = &page.margins;
+    PrintToPdfOptions {
+        display_header_footer: Some(false),
+        prefer_css_page_size: Some(false),
+        landscape: Some(page.orientation == PageOrientation::Landscape),
+        print_background: None,
+        scale: None,
+        paper_width: Some(paper_width / MM_PER_INCH),
+        paper_height: Some(paper_height / MM_PER_INCH),
+        margin_top: Some(margins.top / MM_PER_INCH),
+        margin_bottom: Some(margins.bottom / MM_PER_INCH),
+        margin_left: Some(margins.left / MM_PER_INCH),
+        margin_right: Some(margins.right / MM_PER_INCH),
+        page_ranges: None,
+        ignore_invalid_page_ranges: None,
+        header_template: None,
+        footer_template: None,
+        transfer_mode: None,
+    }
 }
@@ -1,5 +1,11 @@
+mod cron;
 pub mod default_queries;
 pub mod definition;
+mod html_blocks;
 mod html_printing;
+mod native_pdf;
+pub mod report_schedule;
+pub mod report_schedule_driver;
 pub mod report_service;
+mod spreadsheet;
 mod string_or_vec;
@@ -0,0 +1,651 @@
+//! Minimal in-process HTML to PDF renderer.
+//!
+//! Only a small subset of HTML is supported: text, headings, paragraphs, line breaks, horizontal
+//! rules and tables. Styling (css) is ignored. Header and footer are printed on every page and the
+//! header row of a table is repeated when the table continues on the next page.
+//! Text is rendered with the standard PDF Helvetica fonts, reports with characters outside of the
+//! WinAnsi (Latin-1) range can't be rendered and are rejected, they have to use the html output
+//! (browser rendered pdf).
+
+use std::io::Write;
+
+use flate2::{write::ZlibEncoder, Compression};
+
+use super::{
+    definition::PageSettings,
+    html_blocks::{parse_html, Block, TableCell, TableRow, TextStyle, FONT_SIZE},
+    report_service::GeneratedReport,
+};
+
+const POINTS_PER_MM: f64 = 72.0 / 25.4;
+const LINE_HEIGHT_FACTOR: f64 = 1.3;
+const CELL_PADDING: f64 = 3.0;
+const BLOCK_SPACING: f64 = 4.0;
+
+pub fn html_to_native_pdf(
+    document: &GeneratedReport,
+    page: &PageSettings,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let header = document
+        .header
+        .as_deref()
+        .map(parse_html)
+        .unwrap_or_default();
+    let body = parse_html(&document.document);
+    let footer = document
+        .footer
+        .as_deref()
+        .map(parse_html)
+        .unwrap_or_default();
+
+    let pages = layout_pages(&header, &body, &footer, page);
+    write_pdf(&pages, page)
+}
+
+// Text measuring
+
+/// Helvetica glyph widths (1/1000 em) for characters 32 to 126
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+fn text_width(text: &str, style: &TextStyle) -> f64 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c as u32 {
+            code @ 32..=126 => HELVETICA_WIDTHS[(code - 32) as usize] as u32,
+            _ => 556,
+        })
+        .sum();
+    // Bold glyphs are slightly wider, over estimate rather than overflow
+    let bold_factor = if style.bold { 1.1 } else { 1.0 };
+    units as f64 / 1000.0 * style.font_size * bold_factor
+}
+
+fn line_height(style: &TextStyle) -> f64 {
+    style.font_size * LINE_HEIGHT_FACTOR
+}
+
+/// Splits text into lines fitting into max_width, explicit new lines are kept
+fn wrap_text(text: &str, style: &TextStyle, max_width: f64) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if text_width(&candidate, style) <= max_width {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            // Break words that are longer than the line
+            for c in word.chars() {
+                line.push(c);
+                if text_width(&line, style) > max_width && line.chars().count() > 1 {
+                    let last = line.pop().unwrap();
+                    lines.push(std::mem::replace(&mut line, last.to_string()));
+                }
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+// Layout
+
+#[derive(Debug, PartialEq)]
+enum DrawOp {
+    Text {
+        x: f64,
+        y: f64,
+        text: String,
+        style: TextStyle,
+    },
+    Line {
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+    },
+    /// Filled grey rectangle, used as table header background
+    Fill {
+        x: f64,
+        y: f64,
+        w: f64,
+        h: f64,
+    },
+}
+
+/// Content box in PDF coordinates (origin is bottom left)
+#[derive(Clone, Copy)]
+struct Frame {
+    left: f64,
+    width: f64,
+    top: f64,
+    bottom: f64,
+}
+
+struct PageLayout<'a> {
+    header: &'a [Block],
+    footer: &'a [Block],
+    /// Full page content area
+    content: Frame,
+    header_height: f64,
+    footer_height: f64,
+    pages: Vec<Vec<DrawOp>>,
+    /// Current y position of the body on the current page
+    y: f64,
+}
+
+impl PageLayout<'_> {
+    fn body_top(&self) -> f64 {
+        self.content.top - self.header_height
+    }
+
+    fn body_bottom(&self) -> f64 {
+        self.content.bottom + self.footer_height
+    }
+
+    fn new_page(&mut self) {
+        let mut ops = Vec::new();
+        let header_frame = Frame {
+            bottom: f64::MIN,
+            ..self.content
+        };
+        draw_blocks(self.header, header_frame, &mut ops);
+        let footer_frame = Frame {
+            top: self.content.bottom + self.footer_height,
+            bottom: f64::MIN,
+            ..self.content
+        };
+        draw_blocks(self.footer, footer_frame, &mut ops);
+        self.pages.push(ops);
+        self.y = self.body_top();
+    }
+
+    fn ops(&mut self) -> &mut Vec<DrawOp> {
+        self.pages.last_mut().unwrap()
+    }
+
+    /// Moves to the next page if height doesn't fit on the current page.
+    /// Content higher than a whole page is placed anyway
+    fn ensure_space(&mut self, height: f64) {
+        let fits = self.y - height >= self.body_bottom();
+        let page_is_empty = self.y >= self.body_top();
+        if !fits && !page_is_empty {
+            self.new_page();
+        }
+    }
+
+    fn add_paragraph(&mut self, text: &str, style: &TextStyle) {
+        let line_height = line_height(style);
+        for line in wrap_text(text, style, self.content.width) {
+            self.ensure_space(line_height);
+            let y = self.y - style.font_size;
+            let x = self.content.left;
+            self.ops().push(DrawOp::Text {
+                x,
+                y,
+                text: line,
+                style: style.clone(),
+            });
+            self.y -= line_height;
+        }
+        self.y -= BLOCK_SPACING;
+    }
+
+    fn add_rule(&mut self) {
+        self.ensure_space(BLOCK_SPACING * 2.0);
+        let y = self.y - BLOCK_SPACING;
+        let (x1, x2) = (self.content.left, self.content.left + self.content.width);
+        self.ops().push(DrawOp::Line {
+            x1,
+            y1: y,
+            x2,
+            y2: y,
+        });
+        self.y -= BLOCK_SPACING * 2.0;
+    }
+
+    fn add_table(&mut self, rows: &[TableRow]) {
+        let column_widths = column_widths(rows, self.content.width);
+        let header_rows: Vec<&TableRow> = rows.iter().take_while(|row| row.is_header).collect();
+
+        let mut is_first_row = true;
+        for row in rows.iter().skip(header_rows.len()) {
+            let height = row_height(row, &column_widths);
+            let header_height: f64 = header_rows
+                .iter()
+                .map(|row| row_height(row, &column_widths))
+                .sum();
+
+            let page_count = self.pages.len();
+            self.ensure_space(height + if is_first_row { header_height } else { 0.0 });
+            // Repeat header rows at the start of the table and on every new page
+            if is_first_row || self.pages.len() != page_count {
+                for header_row in &header_rows {
+                    let header_row_height = row_height(header_row, &column_widths);
+                    self.draw_row(header_row, &column_widths, header_row_height);
+                }
+            }
+            self.draw_row(row, &column_widths, height);
+            is_first_row = false;
+        }
+        // Table with only header rows
+        if is_first_row {
+            for header_row in &header_rows {
+                let height = row_height(header_row, &column_widths);
+                self.ensure_space(height);
+                self.draw_row(header_row, &column_widths, height);
+            }
+        }
+        self.y -= BLOCK_SPACING;
+    }
+
+    fn draw_row(&mut self, row: &TableRow, column_widths: &[f64], height: f64) {
+        let top = self.y;
+        let bottom = top - height;
+        let left = self.content.left;
+        let right = left + column_widths.iter().sum::<f64>();
+        let ops = self.ops();
+
+        if row.is_header {
+            ops.push(DrawOp::Fill {
+                x: left,
+                y: bottom,
+                w: right - left,
+                h: height,
+            });
+        }
+
+        let mut x = left;
+        for (index, width) in column_widths.iter().enumerate() {
+            if let Some(cell) = row.cells.get(index) {
+                let style = cell_style(cell, row);
+                let mut y = top - CELL_PADDING;
+                for line in wrap_text(&cell.text, &style, width - CELL_PADDING * 2.0) {
+                    ops.push(DrawOp::Text {
+                        x: x + CELL_PADDING,
+                        y: y - style.font_size,
+                        text: line,
+                        style: style.clone(),
+                    });
+                    y -= line_height(&style);
+                }
+            }
+            // Cell left border
+            ops.push(DrawOp::Line {
+                x1: x,
+                y1: top,
+                x2: x,
+                y2: bottom,
+            });
+            x += width;
+        }
+        ops.push(DrawOp::Line {
+            x1: right,
+            y1: top,
+            x2: right,
+            y2: bottom,
+        });
+        ops.push(DrawOp::Line {
+            x1: left,
+            y1: top,
+            x2: right,
+            y2: top,
+        });
+        ops.push(DrawOp::Line {
+            x1: left,
+            y1: bottom,
+            x2: right,
+            y2: bottom,
+        });
+        self.y = bottom;
+    }
+}
+
+fn cell_style(cell: &TableCell, row: &TableRow) -> TextStyle {
+    TextStyle {
+        bold: cell.bold || row.is_header,
+        font_size: FONT_SIZE,
+    }
+}
+
+fn row_height(row: &TableRow, column_widths: &[f64]) -> f64 {
+    let lines = row
+        .cells
+        .iter()
+        .zip(column_widths)
+        .map(|(cell, width)| {
+            let style = cell_style(cell, row);
+            wrap_text(&cell.text, &style, width - CELL_PADDING * 2.0).len() as f64
+                * line_height(&style)
+        })
+        .fold(line_height(&TextStyle::default()), f64::max);
+    lines + CELL_PADDING * 2.0
+}
+
+/// Column widths proportional to the content width, fitted to the available width
+fn column_widths(rows: &[TableRow], available_width: f64) -> Vec<f64> {
+    let column_count = rows.iter().map(|row| row.cells.len()).max().unwrap_or(0);
+    if column_count == 0 {
+        return Vec::new();
+    }
+    let minimum_width = available_width / column_count as f64 / 3.0;
+    let natural_widths: Vec<f64> = (0..column_count)
+        .map(|index| {
+            rows.iter()
+                .filter_map(|row| row.cells.get(index).map(|cell| (cell, row)))
+                .map(|(cell, row)| {
+                    cell.text
+                        .split('\n')
+                        .map(|line| text_width(line, &cell_style(cell, row)))
+                        .fold(0.0, f64::max)
+                })
+                .fold(0.0, f64::max)
+                + CELL_PADDING * 2.0
+        })
+        .map(|width| width.max(minimum_width))
+        .collect();
+
+    let total: f64 = natural_widths.iter().sum();
+    natural_widths
+        .iter()
+        .map(|width| width / total * available_width)
+        .collect()
+}
+
+/// Draws blocks from the top of the frame without page breaks, returns used height
+fn draw_blocks(blocks: &[Block], frame: Frame, ops: &mut Vec<DrawOp>) -> f64 {
+    let mut layout = PageLayout {
+        header: &[],
+        footer: &[],
+        content: frame,
+        header_height: 0.0,
+        footer_height: 0.0,
+        pages: vec![Vec::new()],
+        y: frame.top,
+    };
+    for block in blocks {
+        layout.add_block(block);
+    }
+    ops.append(&mut layout.pages.remove(0));
+    frame.top - layout.y
+}
+
+impl PageLayout<'_> {
+    fn add_block(&mut self, block: &Block) {
+        match block {
+            Block::Paragraph { text, style } => self.add_paragraph(text, style),
+            Block::Table { rows } => self.add_table(rows),
+            Block::Rule => self.add_rule(),
+        }
+    }
+}
+
+fn layout_pages(
+    header: &[Block],
+    body: &[Block],
+    footer: &[Block],
+    page: &PageSettings,
+) -> Vec<Vec<DrawOp>> {
+    let (width, height) = page.page_dimensions_mm();
+    let margins = &page.margins;
+    let content = Frame {
+        left: margins.left * POINTS_PER_MM,
+        width: (width - margins.left - margins.right) * POINTS_PER_MM,
+        top: (height - margins.top) * POINTS_PER_MM,
+        bottom: margins.bottom * POINTS_PER_MM,
+    };
+    let unbounded = Frame {
+        bottom: f64::MIN,
+        ..content
+    };
+
+    let mut layout = PageLayout {
+        header,
+        footer,
+        content,
+        header_height: draw_blocks(header, unbounded, &mut Vec::new()),
+        footer_height: draw_blocks(footer, unbounded, &mut Vec::new()),
+        pages: Vec::new(),
+        y: 0.0,
+    };
+    layout.new_page();
+    for block in body {
+        layout.add_block(block);
+    }
+    layout.pages
+}
+
+// PDF writing
+
+fn encode_pdf_text(text: &str) -> Result<Vec<u8>, anyhow::Error> {
+    let mut result = Vec::new();
+    for c in text.chars() {
+        let byte = match c as u32 {
+            code @ 32..=126 => code as u8,
+            // Latin-1 supplement, same code points in WinAnsiEncoding
+            code @ 160..=255 => code as u8,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Character '{}' is not supported by the native pdf renderer (Latin-1 only), \
+                    use the html output for this report",
+                    c
+                ))
+            }
+        };
+        if matches!(byte, b'(' | b')' | b'\\') {
+            result.push(b'\\');
+        }
+        result.push(byte);
+    }
+    Ok(result)
+}
+
+fn content_stream(ops: &[DrawOp]) -> Result<Vec<u8>, anyhow::Error> {
+    let mut stream: Vec<u8> = Vec::new();
+    stream.extend_from_slice(b"0.5 w\n");
+    for op in ops {
+        match op {
+            DrawOp::Fill { x, y, w, h } => {
+                stream.extend_from_slice(
+                    format!("0.9 g {:.2} {:.2} {:.2} {:.2} re f 0 g\n", x, y, w, h).as_bytes(),
+                );
+            }
+            DrawOp::Line { x1, y1, x2, y2 } => {
+                stream.extend_from_slice(
+                    format!("{:.2} {:.2} m {:.2} {:.2} l S\n", x1, y1, x2, y2).as_bytes(),
+                );
+            }
+            DrawOp::Text { x, y, text, style } => {
+                let font = if style.bold { "F2" } else { "F1" };
+                stream.extend_from_slice(
+                    format!(
+                        "BT /{} {:.1} Tf {:.2} {:.2} Td (",
+                        font, style.font_size, x, y
+                    )
+                    .as_bytes(),
+                );
+                stream.extend_from_slice(&encode_pdf_text(text)?);
+                stream.extend_from_slice(b") Tj ET\n");
+            }
+        }
+    }
+    Ok(stream)
+}
+
+fn write_pdf(pages: &[Vec<DrawOp>], page: &PageSettings) -> Result<Vec<u8>, anyhow::Error> {
+    let (width, height) = page.page_dimensions_mm();
+    let (width, height) = (width * POINTS_PER_MM, height * POINTS_PER_MM);
+
+    // Object ids: 1 catalog, 2 pages, 3 regular font, 4 bold font, then page and content pairs
+    let page_ids: Vec<usize> = (0..pages.len()).map(|index| 5 + index * 2).collect();
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+    ];
+
+    for (ops, page_id) in pages.iter().zip(&page_ids) {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] \
+                /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                width,
+                height,
+                page_id + 1
+            )
+            .into_bytes(),
+        );
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&content_stream(ops)?)?;
+        let compressed = encoder.finish()?;
+        let mut content = format!(
+            "<< /Length {} /Filter /FlateDecode >>\nstream\n",
+            compressed.len()
+        )
+        .into_bytes();
+        content.extend_from_slice(&compressed);
+        content.extend_from_slice(b"\nendstream");
+        objects.push(content);
+    }
+
+    let mut pdf: Vec<u8> = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref_offset = pdf.len();
+    pdf.extend_from_slice(format!("xref\n0 {}\n", objects.len() + 1).as_bytes());
+    pdf.extend_from_slice(b"0000000000 65535 f \n");
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+
+    Ok(pdf)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::report::{
+        definition::{PageOrientation, PageSettings, PageSize},
+        report_service::GeneratedReport,
+    };
+
+    use crate::report::html_blocks::{parse_html, Block, TableRow};
+
+    use super::{html_to_native_pdf, layout_pages};
+
+    #[test]
+    fn table_header_repeated_on_new_page() {
+        let header = parse_html("<p>Header</p>");
+        let footer = parse_html("<p>Footer</p>");
+        let body = parse_html(&format!(
+            "<table><tr><th>Item</th></tr>{}</table>",
+            "<tr><td>Line</td></tr>".repeat(200)
+        ));
+        let Block::Table { rows } = &body[0] else {
+            panic!("Expected table");
+        };
+        assert_eq!(
+            rows.iter().filter(|row: &&TableRow| row.is_header).count(),
+            1
+        );
+
+        let pages = layout_pages(&header, &body, &footer, &PageSettings::default());
+        assert!(pages.len() > 1);
+        for page in pages {
+            let texts: Vec<_> = page
+                .iter()
+                .filter_map(|op| match op {
+                    super::DrawOp::Text { text, .. } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            assert!(texts.contains(&"Header"));
+            assert!(texts.contains(&"Footer"));
+            assert!(texts.contains(&"Item"));
+        }
+    }
+
+    #[test]
+    fn generate_pdf() {
+        let page = PageSettings {
+            size: PageSize::A5,
+            orientation: PageOrientation::Landscape,
+            ..Default::default()
+        };
+        let pdf = html_to_native_pdf(
+            &GeneratedReport {
+                document: "<p>Hello (world)</p>".to_string(),
+                header: None,
+                footer: None,
+            },
+            &page,
+        )
+        .unwrap();
+        let pdf = String::from_utf8_lossy(&pdf);
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        // A5 landscape
+        assert!(pdf.contains("/MediaBox [0 0 595.28 419.53]"));
+
+        // Latin-1
+        assert!(html_to_native_pdf(
+            &GeneratedReport {
+                document: "<p>Café, 25 °C</p>".to_string(),
+                header: None,
+                footer: None,
+            },
+            &page,
+        )
+        .is_ok());
+        // Not WinAnsi, e.g. Russian or Arabic translations
+        assert!(html_to_native_pdf(
+            &GeneratedReport {
+                document: "<p>Склад</p>".to_string(),
+                header: None,
+                footer: None,
+            },
+            &page,
+        )
+        .is_err());
+    }
+}
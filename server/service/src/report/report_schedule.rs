@@ -0,0 +1,713 @@
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday,
+};
+use repository::{
+    PaginationOption, ReportRowRepository, ReportScheduleRow, ReportScheduleRowRepository,
+    ReportScheduleRunFilter, ReportScheduleRunRepository, ReportScheduleRunRow,
+    ReportScheduleRunRowRepository, RepositoryError,
+};
+use util::uuid::uuid;
+
+use crate::{
+    get_default_pagination, i64_to_u32,
+    service_provider::{ServiceContext, ServiceProvider},
+    settings::Settings,
+    static_files::StaticFileCategory,
+    ListError, ListResult,
+};
+
+use super::{
+    cron::CronSchedule,
+    definition::SQLQuery,
+    report_service::{
+        print_report_to_file, ReportError, ReportService, ReportServiceTrait,
+        ResolvedReportDefinition, ResolvedReportQuery,
+    },
+};
+
+pub const MAX_LIMIT: u32 = 1000;
+pub const MIN_LIMIT: u32 = 1;
+
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct UpsertReportSchedule {
+    pub id: String,
+    pub report_id: String,
+    pub name: String,
+    /// Cron expression, e.g. "0 6 1 * *" or "@monthly"
+    pub schedule: String,
+    /// Report arguments, string values can be relative dates, e.g. "$previousMonthStart"
+    pub arguments: Option<serde_json::Value>,
+    pub is_active: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum UpsertReportScheduleError {
+    NotThisStoreReportSchedule,
+    ReportDoesNotExist,
+    /// Report definition can't be resolved
+    InvalidReport(String),
+    /// Reports using GraphQL queries can only be printed by a logged in user (the query is run
+    /// through the GraphQL API with the user's auth token), scheduled reports are limited to
+    /// reports that only use SQL queries
+    ReportNotSchedulable,
+    InvalidSchedule(String),
+    /// Arguments must be a json object
+    InvalidArguments,
+    /// Arguments don't match the argument schema of the report
+    ArgumentsDoNotMatchSchema(Vec<String>),
+    DatabaseError(RepositoryError),
+}
+
+#[derive(PartialEq, Debug)]
+pub enum DeleteReportScheduleError {
+    ReportScheduleDoesNotExist,
+    NotThisStoreReportSchedule,
+    DatabaseError(RepositoryError),
+}
+
+pub trait ReportScheduleServiceTrait: Sync + Send {
+    fn get_report_schedules(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<Vec<ReportScheduleRow>, RepositoryError> {
+        ReportScheduleRowRepository::new(&ctx.connection).find_many_by_store_id(store_id)
+    }
+
+    fn upsert_report_schedule(
+        &self,
+        ctx: &ServiceContext,
+        input: UpsertReportSchedule,
+    ) -> Result<ReportScheduleRow, UpsertReportScheduleError> {
+        upsert_report_schedule(ctx, input)
+    }
+
+    /// Deletes the schedule and its run history, generated files are kept
+    fn delete_report_schedule(
+        &self,
+        ctx: &ServiceContext,
+        id: &str,
+    ) -> Result<String, DeleteReportScheduleError> {
+        delete_report_schedule(ctx, id)
+    }
+
+    fn get_report_schedule_runs(
+        &self,
+        ctx: &ServiceContext,
+        pagination: Option<PaginationOption>,
+        filter: Option<ReportScheduleRunFilter>,
+    ) -> Result<ListResult<ReportScheduleRunRow>, ListError> {
+        let pagination = get_default_pagination(pagination, MAX_LIMIT, MIN_LIMIT)?;
+        let repository = ReportScheduleRunRepository::new(&ctx.connection);
+        Ok(ListResult {
+            rows: repository.query(pagination, filter.clone())?,
+            count: i64_to_u32(repository.count(filter)?),
+        })
+    }
+
+    /// Generates all reports that are due at `now` (UTC) and stores them as
+    /// `StaticFileCategory::ScheduledReport` files. Returns the created run records.
+    fn run_due_report_schedules(
+        &self,
+        service_provider: &ServiceProvider,
+        settings: &Settings,
+        now: NaiveDateTime,
+    ) -> Result<Vec<ReportScheduleRunRow>, RepositoryError> {
+        run_due_report_schedules(service_provider, settings, now)
+    }
+}
+
+pub struct ReportScheduleService;
+impl ReportScheduleServiceTrait for ReportScheduleService {}
+
+fn upsert_report_schedule(
+    ctx: &ServiceContext,
+    input: UpsertReportSchedule,
+) -> Result<ReportScheduleRow, UpsertReportScheduleError> {
+    let repo = ReportScheduleRowRepository::new(&ctx.connection);
+    let existing = repo.find_one_by_id(&input.id)?;
+    if let Some(existing) = &existing {
+        if existing.store_id != ctx.store_id {
+            return Err(UpsertReportScheduleError::NotThisStoreReportSchedule);
+        }
+    }
+
+    let schedule =
+        CronSchedule::parse(&input.schedule).map_err(UpsertReportScheduleError::InvalidSchedule)?;
+    if !matches!(input.arguments, None | Some(serde_json::Value::Object(_))) {
+        return Err(UpsertReportScheduleError::InvalidArguments);
+    }
+    if ReportRowRepository::new(&ctx.connection)
+        .find_one_by_id(&input.report_id)?
+        .is_none()
+    {
+        return Err(UpsertReportScheduleError::ReportDoesNotExist);
+    }
+    let report = ReportService
+        .resolve_report(ctx, &input.report_id)
+        .map_err(|err| UpsertReportScheduleError::InvalidReport(format!("{:?}", err)))?;
+    if report
+        .queries
+        .iter()
+        .any(|query| matches!(query, ResolvedReportQuery::GraphQlQuery(_)))
+    {
+        return Err(UpsertReportScheduleError::ReportNotSchedulable);
+    }
+
+    let now = Utc::now().naive_utc();
+    // relative dates are resolved when the report is generated, use today's date to validate them
+    let arguments = input
+        .arguments
+        .clone()
+        .map(|arguments| resolve_relative_dates(arguments, local_date(now)));
+    match ReportService.validate_report_arguments(&report, &arguments) {
+        Ok(()) => {}
+        Err(ReportError::InvalidArguments(errors)) => {
+            return Err(UpsertReportScheduleError::ArgumentsDoNotMatchSchema(errors))
+        }
+        Err(err) => {
+            return Err(UpsertReportScheduleError::InvalidReport(format!(
+                "{:?}",
+                err
+            )))
+        }
+    };
+
+    let row = ReportScheduleRow {
+        id: input.id,
+        report_id: input.report_id,
+        store_id: ctx.store_id.clone(),
+        user_id: ctx.user_id.clone(),
+        name: input.name,
+        schedule: input.schedule,
+        arguments: input.arguments.map(|arguments| arguments.to_string()),
+        is_active: input.is_active,
+        created_datetime: existing
+            .as_ref()
+            .map(|existing| existing.created_datetime)
+            .unwrap_or(now),
+        last_run_datetime: existing.and_then(|existing| existing.last_run_datetime),
+        next_run_datetime: next_run_datetime(&schedule, now),
+    };
+    repo.upsert_one(&row)?;
+    Ok(row)
+}
+
+fn delete_report_schedule(
+    ctx: &ServiceContext,
+    id: &str,
+) -> Result<String, DeleteReportScheduleError> {
+    ctx.connection
+        .transaction_sync(|connection| {
+            let repo = ReportScheduleRowRepository::new(connection);
+            let existing = repo
+                .find_one_by_id(id)?
+                .ok_or(DeleteReportScheduleError::ReportScheduleDoesNotExist)?;
+            if existing.store_id != ctx.store_id {
+                return Err(DeleteReportScheduleError::NotThisStoreReportSchedule);
+            }
+            ReportScheduleRunRowRepository::new(connection).delete_by_report_schedule_id(id)?;
+            repo.delete(id)?;
+            Ok(id.to_string())
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+fn run_due_report_schedules(
+    service_provider: &ServiceProvider,
+    settings: &Settings,
+    now: NaiveDateTime,
+) -> Result<Vec<ReportScheduleRunRow>, RepositoryError> {
+    let ctx = service_provider.basic_context()?;
+    let due = ReportScheduleRowRepository::new(&ctx.connection).find_due(now)?;
+
+    let mut runs = Vec::new();
+    for schedule in due {
+        let ctx = service_provider.context(schedule.store_id.clone(), schedule.user_id.clone())?;
+        // Use the time the run was scheduled for to resolve relative dates, in case the run was
+        // delayed, e.g. because the server was offline
+        let scheduled_for = schedule.next_run_datetime.unwrap_or(now);
+
+        let (file_id, file_name, error) =
+            match run_report_schedule(&ctx, settings, &schedule, scheduled_for) {
+                Ok((file_id, file_name)) => (Some(file_id), Some(file_name), None),
+                Err(error) => {
+                    log::error!("Failed to run report schedule {}: {}", schedule.id, error);
+                    (None, None, Some(error))
+                }
+            };
+        let run = ReportScheduleRunRow {
+            id: uuid(),
+            report_schedule_id: schedule.id.clone(),
+            report_id: schedule.report_id.clone(),
+            store_id: schedule.store_id.clone(),
+            run_datetime: now,
+            file_id,
+            file_name,
+            error,
+        };
+        ReportScheduleRunRowRepository::new(&ctx.connection).upsert_one(&run)?;
+
+        // Missed runs are skipped, i.e. the next run is always in the future
+        let next_run_datetime = CronSchedule::parse(&schedule.schedule)
+            .ok()
+            .and_then(|cron| next_run_datetime(&cron, now));
+        ReportScheduleRowRepository::new(&ctx.connection).upsert_one(&ReportScheduleRow {
+            last_run_datetime: Some(now),
+            next_run_datetime,
+            ..schedule
+        })?;
+        runs.push(run);
+    }
+    Ok(runs)
+}
+
+/// Generates the report and returns the file id and file name
+fn run_report_schedule(
+    ctx: &ServiceContext,
+    settings: &Settings,
+    schedule: &ReportScheduleRow,
+    scheduled_for: NaiveDateTime,
+) -> Result<(String, String), String> {
+    let report = ReportService
+        .resolve_report(ctx, &schedule.report_id)
+        .map_err(|err| format!("{:?}", err))?;
+
+    let arguments = schedule
+        .arguments
+        .as_ref()
+        .map(|arguments| serde_json::from_str::<serde_json::Value>(arguments))
+        .transpose()
+        .map_err(|err| format!("Invalid arguments: {}", err))?
+        .map(|arguments| resolve_relative_dates(arguments, local_date(scheduled_for)));
+
+    ReportService
+        .validate_report_arguments(&report, &arguments)
+        .map_err(|err| format!("{:?}", err))?;
+    let report_data = fetch_sql_data(ctx, settings, &report, &arguments)?;
+    let file = print_report_to_file(
+        &settings.server.base_dir,
+        &report,
+        report_data,
+        arguments,
+        None,
+        StaticFileCategory::ScheduledReport,
+    )
+    .map_err(|err| format!("{:?}", err))?;
+    Ok((file.id, file.name))
+}
+
+fn fetch_sql_data(
+    ctx: &ServiceContext,
+    settings: &Settings,
+    report: &ResolvedReportDefinition,
+    arguments: &Option<serde_json::Value>,
+) -> Result<serde_json::Value, String> {
+    let mut variables = match arguments {
+        Some(serde_json::Value::Object(arguments)) => arguments.clone(),
+        _ => serde_json::Map::new(),
+    };
+    variables.insert(
+        "storeId".to_string(),
+        serde_json::Value::String(ctx.store_id.clone()),
+    );
+    variables.insert(
+        "now".to_string(),
+        serde_json::Value::String(Utc::now().to_rfc3339()),
+    );
+
+    let mut data = serde_json::Map::new();
+    for query in &report.queries {
+        let ResolvedReportQuery::SQLQuery(query) = query else {
+            return Err("Only SQL queries are supported in scheduled reports".to_string());
+        };
+        let result = query_json(ctx, settings, query, &variables)
+            .map_err(|err| format!("Failed to query {}: {:?}", query.name, err))?;
+        data.insert(query.name.clone(), serde_json::Value::Array(result));
+    }
+    Ok(serde_json::Value::Object(data))
+}
+
+#[cfg(not(feature = "postgres"))]
+fn query_json(
+    _: &ServiceContext,
+    settings: &Settings,
+    query: &SQLQuery,
+    variables: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Vec<serde_json::Value>, RepositoryError> {
+    repository::query_json(&settings.database, &query.query_sqlite, variables)
+}
+
+#[cfg(feature = "postgres")]
+fn query_json(
+    ctx: &ServiceContext,
+    _: &Settings,
+    query: &SQLQuery,
+    variables: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Vec<serde_json::Value>, RepositoryError> {
+    repository::query_json(&ctx.connection, &query.query_postgres, variables)
+}
+
+/// Schedules are evaluated in the local time of the server, returns the next run in UTC
+fn next_run_datetime(schedule: &CronSchedule, now: NaiveDateTime) -> Option<NaiveDateTime> {
+    let local_now = Local.from_utc_datetime(&now).naive_local();
+    let next = schedule.next_after(local_now)?;
+    let next = Local
+        .from_local_datetime(&next)
+        .earliest()
+        // local time doesn't exist (DST gap), run an hour later
+        .or_else(|| {
+            Local
+                .from_local_datetime(&(next + Duration::hours(1)))
+                .earliest()
+        })?;
+    Some(next.with_timezone(&Utc).naive_utc())
+}
+
+fn local_date(datetime: NaiveDateTime) -> NaiveDate {
+    let datetime: DateTime<Local> = Local.from_utc_datetime(&datetime);
+    datetime.date_naive()
+}
+
+/// Replaces relative date placeholders in the top level string arguments with dates
+/// (YYYY-MM-DD) relative to `today`, e.g. `"$previousMonthStart"`
+fn resolve_relative_dates(arguments: serde_json::Value, today: NaiveDate) -> serde_json::Value {
+    let serde_json::Value::Object(arguments) = arguments else {
+        return arguments;
+    };
+    let arguments = arguments
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::String(value) => match relative_date(&value, today) {
+                    Some(date) => serde_json::Value::String(date.format("%Y-%m-%d").to_string()),
+                    None => serde_json::Value::String(value),
+                },
+                value => value,
+            };
+            (key, value)
+        })
+        .collect();
+    serde_json::Value::Object(arguments)
+}
+
+fn relative_date(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let current_month_start = today.with_day(1)?;
+    let previous_month_start = (current_month_start - Duration::days(1)).with_day(1)?;
+    let current_week_start = today.week(Weekday::Mon).first_day();
+
+    let date = match value {
+        "$today" => today,
+        "$yesterday" => today - Duration::days(1),
+        "$currentMonthStart" => current_month_start,
+        "$previousMonthStart" => previous_month_start,
+        "$previousMonthEnd" => current_month_start - Duration::days(1),
+        "$previousWeekStart" => current_week_start - Duration::days(7),
+        "$previousWeekEnd" => current_week_start - Duration::days(1),
+        "$previousYearStart" => NaiveDate::from_ymd_opt(today.year() - 1, 1, 1)?,
+        "$previousYearEnd" => NaiveDate::from_ymd_opt(today.year() - 1, 12, 31)?,
+        _ => return None,
+    };
+    Some(date)
+}
+
+impl From<RepositoryError> for UpsertReportScheduleError {
+    fn from(error: RepositoryError) -> Self {
+        UpsertReportScheduleError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for DeleteReportScheduleError {
+    fn from(error: RepositoryError) -> Self {
+        DeleteReportScheduleError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use chrono::{Duration, NaiveDate, Utc};
+    use repository::{
+        mock::{mock_store_a, mock_store_b, MockDataInserts},
+        test_db::setup_all,
+        ContextType, EqualFilter, ReportRow, ReportRowRepository, ReportScheduleRowRepository,
+        ReportScheduleRunFilter, ReportType,
+    };
+    use serde_json::json;
+
+    use crate::{
+        report::definition::{
+            DefaultQuery, ReportDefinition, ReportDefinitionEntry, ReportDefinitionIndex,
+            ReportOutputType, SQLQuery, SheetMapping, SpreadsheetTemplate,
+        },
+        service_provider::ServiceProvider,
+        settings::{ServerSettings, Settings},
+        static_files::{StaticFileCategory, StaticFileService},
+    };
+
+    use super::{
+        resolve_relative_dates, DeleteReportScheduleError, UpsertReportSchedule,
+        UpsertReportScheduleError,
+    };
+
+    fn report_row(id: &str, query: ReportDefinitionEntry) -> ReportRow {
+        let definition = ReportDefinition {
+            index: ReportDefinitionIndex {
+                template: Some("sheet".to_string()),
+                header: None,
+                footer: None,
+                query: vec!["stores".to_string()],
+                page: Default::default(),
+            },
+            entries: HashMap::from([
+                (
+                    "sheet".to_string(),
+                    ReportDefinitionEntry::SpreadsheetTemplate(SpreadsheetTemplate {
+                        output: ReportOutputType::Csv,
+                        sheets: vec![SheetMapping {
+                            name: "Stores".to_string(),
+                            rows: "stores".to_string(),
+                            columns: vec![],
+                        }],
+                    }),
+                ),
+                ("stores".to_string(), query),
+            ]),
+        };
+        ReportRow {
+            id: id.to_string(),
+            name: id.to_string(),
+            r#type: ReportType::OmSupply,
+            template: serde_json::to_string(&definition).unwrap(),
+            context: ContextType::Stocktake,
+            comment: None,
+            sub_context: None,
+            argument_schema_id: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn report_schedule_service() {
+        let (_, connection, connection_manager, db_settings) = setup_all(
+            "report_schedule_service",
+            MockDataInserts::none().names().stores(),
+        )
+        .await;
+        let report_repo = ReportRowRepository::new(&connection);
+        report_repo
+            .upsert_one(&report_row(
+                "sql_report",
+                ReportDefinitionEntry::SQLQuery(SQLQuery {
+                    name: "stores".to_string(),
+                    query_sqlite:
+                        "SELECT id, $fromDate AS from_date FROM store WHERE id = $storeId"
+                            .to_string(),
+                    query_postgres:
+                        "SELECT id, $fromDate AS from_date FROM store WHERE id = $storeId"
+                            .to_string(),
+                }),
+            ))
+            .unwrap();
+        report_repo
+            .upsert_one(&report_row(
+                "graphql_report",
+                ReportDefinitionEntry::DefaultQuery(DefaultQuery::Invoice),
+            ))
+            .unwrap();
+
+        let base_dir = tempfile::tempdir().unwrap();
+        let base_dir = Some(base_dir.path().to_string_lossy().to_string());
+        let settings = Settings {
+            server: ServerSettings {
+                port: 0,
+                danger_allow_http: false,
+                debug_no_access_control: false,
+                cors_origins: vec![],
+                base_dir: base_dir.clone(),
+                machine_uid: None,
+            },
+            database: db_settings,
+            sync: None,
+            logging: None,
+            cold_chain: None,
+            notifications: None,
+        };
+
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let context = service_provider
+            .context(mock_store_a().id, "user".to_string())
+            .unwrap();
+        let service = &service_provider.report_schedule_service;
+
+        let input = UpsertReportSchedule {
+            id: "schedule_a".to_string(),
+            report_id: "sql_report".to_string(),
+            name: "Monthly stores".to_string(),
+            schedule: "@monthly".to_string(),
+            arguments: Some(json!({ "fromDate": "$previousMonthStart" })),
+            is_active: true,
+        };
+
+        // ReportDoesNotExist
+        assert_eq!(
+            service.upsert_report_schedule(
+                &context,
+                UpsertReportSchedule {
+                    report_id: "invalid".to_string(),
+                    ..input.clone()
+                }
+            ),
+            Err(UpsertReportScheduleError::ReportDoesNotExist)
+        );
+        // ReportNotSchedulable
+        assert_eq!(
+            service.upsert_report_schedule(
+                &context,
+                UpsertReportSchedule {
+                    report_id: "graphql_report".to_string(),
+                    ..input.clone()
+                }
+            ),
+            Err(UpsertReportScheduleError::ReportNotSchedulable)
+        );
+        // InvalidSchedule
+        assert!(matches!(
+            service.upsert_report_schedule(
+                &context,
+                UpsertReportSchedule {
+                    schedule: "0 0 32 * *".to_string(),
+                    ..input.clone()
+                }
+            ),
+            Err(UpsertReportScheduleError::InvalidSchedule(_))
+        ));
+        // InvalidArguments
+        assert_eq!(
+            service.upsert_report_schedule(
+                &context,
+                UpsertReportSchedule {
+                    arguments: Some(json!(["fromDate"])),
+                    ..input.clone()
+                }
+            ),
+            Err(UpsertReportScheduleError::InvalidArguments)
+        );
+
+        // success
+        let schedule = service
+            .upsert_report_schedule(&context, input.clone())
+            .unwrap();
+        let next_run = schedule.next_run_datetime.unwrap();
+        assert!(next_run > Utc::now().naive_utc());
+
+        // NotThisStoreReportSchedule
+        let context_b = service_provider
+            .context(mock_store_b().id, "user".to_string())
+            .unwrap();
+        assert_eq!(
+            service.upsert_report_schedule(&context_b, input.clone()),
+            Err(UpsertReportScheduleError::NotThisStoreReportSchedule)
+        );
+        assert_eq!(
+            service.delete_report_schedule(&context_b, "schedule_a"),
+            Err(DeleteReportScheduleError::NotThisStoreReportSchedule)
+        );
+
+        // Nothing due yet
+        assert_eq!(
+            service
+                .run_due_report_schedules(&service_provider, &settings, Utc::now().naive_utc())
+                .unwrap(),
+            vec![]
+        );
+
+        // Run when due
+        let run_time = next_run + Duration::minutes(1);
+        let runs = service
+            .run_due_report_schedules(&service_provider, &settings, run_time)
+            .unwrap();
+        assert_eq!(runs.len(), 1);
+        let run = &runs[0];
+        assert_eq!(run.error, None);
+
+        let schedule = ReportScheduleRowRepository::new(&connection)
+            .find_one_by_id("schedule_a")
+            .unwrap()
+            .unwrap();
+        assert_eq!(schedule.last_run_datetime, Some(run_time));
+        assert!(schedule.next_run_datetime.unwrap() > run_time);
+
+        let file = StaticFileService::new(&base_dir)
+            .unwrap()
+            .find_file(
+                run.file_id.as_ref().unwrap(),
+                StaticFileCategory::ScheduledReport,
+            )
+            .unwrap()
+            .unwrap();
+        let content = std::fs::read_to_string(file.path).unwrap();
+        assert!(content.contains(&mock_store_a().id));
+
+        let runs = service
+            .get_report_schedule_runs(
+                &context,
+                None,
+                Some(
+                    ReportScheduleRunFilter::new()
+                        .store_id(EqualFilter::equal_to(&mock_store_a().id))
+                        .report_schedule_id(EqualFilter::equal_to("schedule_a")),
+                ),
+            )
+            .unwrap();
+        assert_eq!(runs.count, 1);
+
+        // delete
+        assert_eq!(
+            service.delete_report_schedule(&context, "invalid"),
+            Err(DeleteReportScheduleError::ReportScheduleDoesNotExist)
+        );
+        service
+            .delete_report_schedule(&context, "schedule_a")
+            .unwrap();
+        assert_eq!(
+            service.get_report_schedules(&context, &mock_store_a().id),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn report_schedule_relative_dates() {
+        // Wednesday
+        let today = NaiveDate::from_ymd_opt(2024, 3, 13).unwrap();
+        let arguments = json!({
+            "today": "$today",
+            "yesterday": "$yesterday",
+            "currentMonthStart": "$currentMonthStart",
+            "previousMonthStart": "$previousMonthStart",
+            "previousMonthEnd": "$previousMonthEnd",
+            "previousWeekStart": "$previousWeekStart",
+            "previousWeekEnd": "$previousWeekEnd",
+            "previousYearStart": "$previousYearStart",
+            "previousYearEnd": "$previousYearEnd",
+            "other": "value",
+            "number": 1
+        });
+        assert_eq!(
+            resolve_relative_dates(arguments, today),
+            json!({
+                "today": "2024-03-13",
+                "yesterday": "2024-03-12",
+                "currentMonthStart": "2024-03-01",
+                "previousMonthStart": "2024-02-01",
+                "previousMonthEnd": "2024-02-29",
+                "previousWeekStart": "2024-03-04",
+                "previousWeekEnd": "2024-03-10",
+                "previousYearStart": "2023-01-01",
+                "previousYearEnd": "2023-12-31",
+                "other": "value",
+                "number": 1
+            })
+        );
+    }
+}
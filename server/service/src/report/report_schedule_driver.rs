@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::time::Duration;
+use util::format_error;
+
+use crate::{service_provider::ServiceProvider, settings::Settings, sync::is_initialised};
+
+const REPORT_SCHEDULE_INTERVAL: Duration = Duration::from_secs(60);
+
+pub struct ReportScheduleDriver {
+    settings: Settings,
+}
+
+/// Used to 'drive' scheduled reports, it checks for due report schedules every minute (only when
+/// initialised) and generates the reports locally, i.e. no external service is required
+impl ReportScheduleDriver {
+    pub fn init(settings: &Settings) -> ReportScheduleDriver {
+        ReportScheduleDriver {
+            settings: settings.clone(),
+        }
+    }
+
+    /// ReportScheduleDriver entry point, this method is meant to be run within main `select!` macro
+    pub async fn run(self, service_provider: Arc<ServiceProvider>) {
+        loop {
+            tokio::time::sleep(REPORT_SCHEDULE_INTERVAL).await;
+
+            // Need to check is_initialised from database on every iteration, since it could have been updated
+            if !is_initialised(&service_provider) {
+                continue;
+            }
+
+            let service_provider = service_provider.clone();
+            let settings = self.settings.clone();
+            // Report generation is blocking (DB queries and file IO)
+            let result = tokio::task::spawn_blocking(move || {
+                service_provider
+                    .report_schedule_service
+                    .run_due_report_schedules(&service_provider, &settings, Utc::now().naive_utc())
+            })
+            .await;
+
+            match result {
+                Ok(Ok(runs)) => {
+                    if !runs.is_empty() {
+                        log::info!("Generated {} scheduled report(s)", runs.len());
+                    }
+                }
+                Ok(Err(error)) => {
+                    log::error!("Error running report schedules: {}", format_error(&error))
+                }
+                Err(error) => log::error!("Report schedule task failed: {}", error),
+            }
+        }
+    }
+}
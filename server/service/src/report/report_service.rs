@@ -1,7 +1,8 @@
 use chrono::{DateTime, Utc};
+use jsonschema::JSONSchema;
 use repository::{
-    PaginationOption, Report, ReportFilter, ReportRepository, ReportRowRepository, ReportSort,
-    ReportType, RepositoryError,
+    FormSchema, FormSchemaRowRepository, PaginationOption, Report, ReportFilter, ReportRepository,
+    ReportRow, ReportRowRepository, ReportSort, ReportType, RepositoryError,
 };
 use std::{collections::HashMap, time::SystemTime};
 use util::uuid::uuid;
@@ -9,21 +10,26 @@ use util::uuid::uuid;
 use crate::{
     get_default_pagination,
     service_provider::ServiceContext,
-    static_files::{StaticFileCategory, StaticFileService},
+    static_files::{StaticFile, StaticFileCategory, StaticFileService},
     ListError,
 };
 
 use super::{
     default_queries::get_default_gql_query,
     definition::{
-        GraphQlQuery, ReportDefinition, ReportDefinitionEntry, ReportRef, SQLQuery, TeraTemplate,
+        GraphQlQuery, PageSettings, ReportDefinition, ReportDefinitionEntry, ReportOutputType,
+        ReportRef, SQLQuery, SpreadsheetTemplate, TeraTemplate,
     },
     html_printing::html_to_pdf,
+    native_pdf::html_to_native_pdf,
+    spreadsheet::{sheets_from_data, sheets_from_html, to_csv, to_xlsx, Sheet},
 };
 
 pub enum PrintFormat {
     Pdf,
     Html,
+    Xlsx,
+    Csv,
 }
 
 #[derive(Debug)]
@@ -37,6 +43,10 @@ pub enum ReportError {
     QueryError(String),
     DocGenerationError(String),
     HTMLToPDFError(String),
+    /// The argument schema of the report is not a valid json schema
+    InvalidArgumentSchema(String),
+    /// The arguments don't match the argument schema of the report
+    InvalidArguments(Vec<String>),
 }
 
 #[derive(Debug, Clone)]
@@ -52,6 +62,11 @@ pub struct ResolvedReportDefinition {
     pub name: String,
     /// Reference to the main template in the templates map
     pub template: String,
+    /// Output type of the main template, decides how the report is printed by default
+    pub output: ReportOutputType,
+    pub page: Option<PageSettings>,
+    /// Set if the main template maps the report data directly to spreadsheet columns
+    pub spreadsheet: Option<SpreadsheetTemplate>,
     /// Reference to the header entry in the templates map
     pub header: Option<String>,
     /// Reference to the footer entry in the templates map
@@ -60,6 +75,9 @@ pub struct ResolvedReportDefinition {
     pub templates: HashMap<String, TeraTemplate>,
     pub queries: Vec<ResolvedReportQuery>,
     pub resources: HashMap<String, serde_json::Value>,
+    /// Form schema linked to the report (argument_schema_id), report arguments are validated
+    /// against its json schema
+    pub argument_schema: Option<FormSchema>,
 }
 
 pub struct GeneratedReport {
@@ -98,7 +116,18 @@ pub trait ReportServiceTrait: Sync + Send {
         resolve_report_definition(ctx, name, report_definition)
     }
 
-    /// Converts a HTML report to a file for the target PrintFormat and returns file id
+    /// Validates the arguments against the argument schema of the report (if any).
+    /// Must be called before the report queries are executed.
+    fn validate_report_arguments(
+        &self,
+        report: &ResolvedReportDefinition,
+        arguments: &Option<serde_json::Value>,
+    ) -> Result<(), ReportError> {
+        validate_report_arguments(report, arguments)
+    }
+
+    /// Converts a HTML report to a file for the target PrintFormat and returns file id.
+    /// If no format is specified the format is derived from the output type of the report.
     fn print_html_report(
         &self,
         base_dir: &Option<String>,
@@ -107,60 +136,109 @@ pub trait ReportServiceTrait: Sync + Send {
         arguments: Option<serde_json::Value>,
         format: Option<PrintFormat>,
     ) -> Result<String, ReportError> {
-        let document = generate_report(report, report_data, arguments)?;
-
-        match format {
-            Some(PrintFormat::Html) => {
-                print_html_report_to_html(base_dir, document, report.name.clone())
-            }
-            Some(PrintFormat::Pdf) | None => {
-                print_html_report_to_pdf(base_dir, document, report.name.clone())
-            }
-        }
+        let file = print_report_to_file(
+            base_dir,
+            report,
+            report_data,
+            arguments,
+            format,
+            StaticFileCategory::Temporary,
+        )?;
+        Ok(file.id)
     }
 }
 
-/// Converts a HTML report to a pdf file and returns the file id
-fn print_html_report_to_pdf(
+/// Prints the report and stores it as static file in the given category
+pub fn print_report_to_file(
     base_dir: &Option<String>,
-    document: GeneratedReport,
-    report_name: String,
-) -> Result<String, ReportError> {
-    let id = uuid();
-    // TODO use a proper tmp dir here instead of base_dir?
-    let pdf = html_to_pdf(base_dir, &format_html_document(document), &id)
-        .map_err(|err| ReportError::HTMLToPDFError(format!("{}", err)))?;
+    report: &ResolvedReportDefinition,
+    report_data: serde_json::Value,
+    arguments: Option<serde_json::Value>,
+    format: Option<PrintFormat>,
+    category: StaticFileCategory,
+) -> Result<StaticFile, ReportError> {
+    let format = format.unwrap_or(match report.output {
+        ReportOutputType::Html | ReportOutputType::NativePdf => PrintFormat::Pdf,
+        ReportOutputType::Xlsx => PrintFormat::Xlsx,
+        ReportOutputType::Csv => PrintFormat::Csv,
+    });
+
+    let (content, extension) = match &report.spreadsheet {
+        // Reports without Tera template can only be printed as spreadsheet
+        Some(spreadsheet) => {
+            let sheets = sheets_from_data(spreadsheet, &report_data)
+                .map_err(ReportError::DocGenerationError)?;
+            print_spreadsheet(&sheets, format)?
+        }
+        None => {
+            let document = generate_report(report, report_data, arguments)?;
+            match format {
+                PrintFormat::Html => (format_html_document(document).into_bytes(), "html"),
+                PrintFormat::Pdf => (print_html_report_to_pdf(base_dir, document, report)?, "pdf"),
+                PrintFormat::Xlsx | PrintFormat::Csv => {
+                    print_spreadsheet(&sheets_from_html(&document), format)?
+                }
+            }
+        }
+    };
 
     let file_service = StaticFileService::new(base_dir)
         .map_err(|err| ReportError::DocGenerationError(format!("{}", err)))?;
     let now: DateTime<Utc> = SystemTime::now().into();
     let file = file_service
         .store_file(
-            &format!("{}_{}.pdf", now.format("%Y%m%d_%H%M%S"), report_name),
-            StaticFileCategory::Temporary,
-            &pdf,
+            &format!(
+                "{}_{}.{}",
+                now.format("%Y%m%d_%H%M%S"),
+                report.name,
+                extension
+            ),
+            category,
+            &content,
         )
         .map_err(|err| ReportError::DocGenerationError(format!("{}", err)))?;
-    Ok(file.id)
+    Ok(file)
 }
 
-/// Converts the report to a HTML file and returns the file id
-fn print_html_report_to_html(
+/// Converts a HTML report to pdf
+fn print_html_report_to_pdf(
     base_dir: &Option<String>,
     document: GeneratedReport,
-    report_name: String,
-) -> Result<String, ReportError> {
-    let file_service = StaticFileService::new(base_dir)
-        .map_err(|err| ReportError::DocGenerationError(format!("{}", err)))?;
-    let now: DateTime<Utc> = SystemTime::now().into();
-    let file = file_service
-        .store_file(
-            &format!("{}_{}.html", now.format("%Y%m%d_%H%M%S"), report_name),
-            StaticFileCategory::Temporary,
-            format_html_document(document).as_bytes(),
-        )
-        .map_err(|err| ReportError::DocGenerationError(format!("{}", err)))?;
-    Ok(file.id)
+    report: &ResolvedReportDefinition,
+) -> Result<Vec<u8>, ReportError> {
+    match report.output {
+        ReportOutputType::NativePdf => {
+            html_to_native_pdf(&document, &report.page.clone().unwrap_or_default())
+        }
+        ReportOutputType::Html | ReportOutputType::Xlsx | ReportOutputType::Csv => {
+            let id = uuid();
+            // TODO use a proper tmp dir here instead of base_dir?
+            html_to_pdf(
+                base_dir,
+                &format_html_document(document),
+                &id,
+                report.page.as_ref(),
+            )
+        }
+    }
+    .map_err(|err| ReportError::HTMLToPDFError(format!("{}", err)))
+}
+
+/// Converts sheets to xlsx or csv, returns the content and the file extension
+fn print_spreadsheet(
+    sheets: &[Sheet],
+    format: PrintFormat,
+) -> Result<(Vec<u8>, &'static str), ReportError> {
+    match format {
+        PrintFormat::Xlsx => Ok((
+            to_xlsx(sheets).map_err(|err| ReportError::DocGenerationError(format!("{}", err)))?,
+            "xlsx",
+        )),
+        PrintFormat::Csv => Ok((to_csv(sheets).into_bytes(), "csv")),
+        PrintFormat::Pdf | PrintFormat::Html => Err(ReportError::DocGenerationError(
+            "Spreadsheet reports can only be printed as xlsx or csv".to_string(),
+        )),
+    }
 }
 
 /// Puts the document content, header and footer into a <html> template.
@@ -222,8 +300,13 @@ fn resolve_report(
 ) -> Result<ResolvedReportDefinition, ReportError> {
     let repo = ReportRowRepository::new(&ctx.connection);
 
-    let (report_name, main) = load_report_definition(&repo, report_id)?;
-    resolve_report_definition(ctx, report_name, main)
+    let (row, main) = load_report_definition(&repo, report_id)?;
+    let mut report = resolve_report_definition(ctx, row.name, main)?;
+    if let Some(schema_id) = row.argument_schema_id {
+        report.argument_schema =
+            FormSchemaRowRepository::new(&ctx.connection).find_one_by_id(&schema_id)?;
+    }
+    Ok(report)
 }
 
 fn resolve_report_definition(
@@ -246,10 +329,24 @@ fn resolve_report_definition(
             .ok_or(ReportError::InvalidReportDefinition(
                 "Template reference missing".to_string(),
             ))?;
-    if !templates.contains_key(&template) {
+    let spreadsheet = match fully_loaded_report.entries.get(&template) {
+        Some(ReportDefinitionEntry::SpreadsheetTemplate(spreadsheet)) => Some(spreadsheet.clone()),
+        _ => None,
+    };
+    let output = match (&spreadsheet, templates.get(&template)) {
+        (Some(spreadsheet), _) => spreadsheet.output.clone(),
+        (None, Some(template)) => template.output.clone(),
+        (None, None) => {
+            return Err(ReportError::InvalidReportDefinition(format!(
+                "Invalid template reference: {}",
+                template
+            )))
+        }
+    };
+    if spreadsheet.is_some() && !matches!(output, ReportOutputType::Xlsx | ReportOutputType::Csv) {
         return Err(ReportError::InvalidReportDefinition(format!(
-            "Invalid template reference: {}",
-            template
+            "Invalid spreadsheet output type: {:?}",
+            output
         )));
     }
     if let Some(header) = fully_loaded_report.index.header.clone() {
@@ -288,11 +385,15 @@ fn resolve_report_definition(
     Ok(ResolvedReportDefinition {
         name,
         template,
+        output,
+        page: fully_loaded_report.index.page.clone(),
+        spreadsheet,
         header: fully_loaded_report.index.header.clone(),
         footer: fully_loaded_report.index.footer.clone(),
         templates,
         queries,
         resources,
+        argument_schema: None,
     })
 }
 
@@ -421,7 +522,7 @@ fn resources_from_resolved_template(
 fn load_report_definition(
     repo: &ReportRowRepository,
     report_id: &str,
-) -> Result<(String, ReportDefinition), ReportError> {
+) -> Result<(ReportRow, ReportDefinition), ReportError> {
     let row = match repo.find_one_by_id(report_id)? {
         Some(row) => row,
         None => {
@@ -434,7 +535,24 @@ fn load_report_definition(
     let def = serde_json::from_str::<ReportDefinition>(&row.template).map_err(|err| {
         ReportError::InvalidReportDefinition(format!("Can't parse report: {}", err))
     })?;
-    Ok((row.name, def))
+    Ok((row, def))
+}
+
+fn validate_report_arguments(
+    report: &ResolvedReportDefinition,
+    arguments: &Option<serde_json::Value>,
+) -> Result<(), ReportError> {
+    let Some(schema) = &report.argument_schema else {
+        return Ok(());
+    };
+    let validator = JSONSchema::compile(&schema.json_schema)
+        .map_err(|err| ReportError::InvalidArgumentSchema(format!("{}", err)))?;
+    // no arguments are validated as empty object, e.g. to detect missing required arguments
+    let empty = serde_json::Value::Object(serde_json::Map::new());
+    let arguments = arguments.as_ref().unwrap_or(&empty);
+    validator.validate(arguments).map_err(|errors| {
+        ReportError::InvalidArguments(errors.map(|err| format!("{}", err)).collect())
+    })
 }
 
 fn load_template_references(
@@ -493,9 +611,11 @@ mod report_service_test {
     use std::collections::HashMap;
 
     use repository::{
-        mock::MockDataInserts, test_db::setup_all, ContextType, ReportRow, ReportRowRepository,
+        mock::MockDataInserts, test_db::setup_all, ContextType, FormSchema,
+        FormSchemaRowRepository, ReportRow, ReportRowRepository, ReportSort, ReportSortField,
         ReportType,
     };
+    use serde_json::json;
 
     use crate::{
         report::{
@@ -503,7 +623,7 @@ mod report_service_test {
                 DefaultQuery, ReportDefinition, ReportDefinitionEntry, ReportDefinitionIndex,
                 ReportOutputType, ReportRef, TeraTemplate,
             },
-            report_service::generate_report,
+            report_service::{generate_report, ReportError},
         },
         service_provider::ServiceProvider,
     };
@@ -516,6 +636,7 @@ mod report_service_test {
                 header: None,
                 footer: Some("footer.html".to_string()),
                 query: vec!["query".to_string()],
+                page: Default::default(),
             },
             entries: HashMap::from([
                 (
@@ -545,6 +666,7 @@ mod report_service_test {
                 header: None,
                 footer: Some("footer.html".to_string()),
                 query: vec![],
+                page: Default::default(),
             },
             entries: HashMap::from([(
                 "footer.html".to_string(),
@@ -598,4 +720,119 @@ mod report_service_test {
         .unwrap();
         assert_eq!(doc.document, "Template: Hello Footer");
     }
+
+    #[actix_rt::test]
+    async fn report_argument_schema() {
+        let json_schema = json!({
+            "type": "object",
+            "properties": {
+                "fromDate": { "type": "string" }
+            },
+            "required": ["fromDate"]
+        });
+        let definition = ReportDefinition {
+            index: ReportDefinitionIndex {
+                template: Some("template.html".to_string()),
+                header: None,
+                footer: None,
+                query: vec![],
+                page: Default::default(),
+            },
+            entries: HashMap::from([(
+                "template.html".to_string(),
+                ReportDefinitionEntry::TeraTemplate(TeraTemplate {
+                    output: ReportOutputType::Html,
+                    template: "{{arguments.fromDate}}".to_string(),
+                }),
+            )]),
+        };
+
+        let (_, connection, connection_manager, _) =
+            setup_all("report_argument_schema", MockDataInserts::none()).await;
+        FormSchemaRowRepository::new(&connection)
+            .upsert_one(&FormSchema {
+                id: "form_schema".to_string(),
+                r#type: "JsonForms".to_string(),
+                json_schema: json_schema.clone(),
+                ui_schema: json!({}),
+            })
+            .unwrap();
+        let repo = ReportRowRepository::new(&connection);
+        repo.upsert_one(&ReportRow {
+            id: "no_schema".to_string(),
+            name: "No schema".to_string(),
+            r#type: ReportType::OmSupply,
+            template: serde_json::to_string(&definition).unwrap(),
+            context: ContextType::Stocktake,
+            comment: None,
+            sub_context: None,
+            argument_schema_id: None,
+        })
+        .unwrap();
+        repo.upsert_one(&ReportRow {
+            id: "linked_schema".to_string(),
+            name: "Linked schema".to_string(),
+            r#type: ReportType::OmSupply,
+            template: serde_json::to_string(&definition).unwrap(),
+            context: ContextType::Stocktake,
+            comment: None,
+            sub_context: None,
+            argument_schema_id: Some("form_schema".to_string()),
+        })
+        .unwrap();
+
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let context = service_provider
+            .context("store_id".to_string(), "".to_string())
+            .unwrap();
+        let service = &service_provider.report_service;
+
+        let report = service.resolve_report(&context, "linked_schema").unwrap();
+        assert_eq!(
+            report.argument_schema.as_ref().map(|it| &it.json_schema),
+            Some(&json_schema)
+        );
+        assert!(matches!(
+            service.validate_report_arguments(&report, &None),
+            Err(ReportError::InvalidArguments(_))
+        ));
+        assert!(matches!(
+            service.validate_report_arguments(&report, &Some(json!({ "fromDate": 5 }))),
+            Err(ReportError::InvalidArguments(_))
+        ));
+        assert!(service
+            .validate_report_arguments(&report, &Some(json!({ "fromDate": "2024-01-01" })))
+            .is_ok());
+
+        // reports without a linked schema accept any arguments
+        let report = service.resolve_report(&context, "no_schema").unwrap();
+        assert!(report.argument_schema.is_none());
+        assert!(service.validate_report_arguments(&report, &None).is_ok());
+
+        // schema is returned with the reports
+        let reports = service
+            .query_reports(
+                &context,
+                None,
+                None,
+                Some(ReportSort {
+                    key: ReportSortField::Id,
+                    desc: None,
+                }),
+            )
+            .unwrap();
+        let schema_ids: Vec<_> = reports
+            .iter()
+            .map(|report| {
+                (
+                    report.report_row.id.as_str(),
+                    report.argument_schema.as_ref().map(|it| it.id.as_str()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            schema_ids,
+            vec![("linked_schema", Some("form_schema")), ("no_schema", None)]
+        );
+    }
 }
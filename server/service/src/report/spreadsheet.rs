@@ -0,0 +1,419 @@
+//! Tabular (XLSX and CSV) report outputs.
+//!
+//! Sheets are either created from the tables of a rendered HTML report or by mapping the report
+//! data directly to columns, see [SpreadsheetTemplate].
+
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+use serde_json::Value;
+
+use super::{
+    definition::{SheetMapping, SpreadsheetTemplate},
+    html_blocks::{parse_html, Block},
+    report_service::GeneratedReport,
+};
+
+/// Excel doesn't allow longer worksheet names
+const MAX_SHEET_NAME_LENGTH: usize = 31;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum CellValue {
+    Text(String),
+    Number(f64),
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(super) struct Sheet {
+    pub(super) name: String,
+    pub(super) header_rows: Vec<Vec<String>>,
+    pub(super) rows: Vec<Vec<CellValue>>,
+}
+
+/// One sheet per table in the report document, header and footer are ignored
+pub(super) fn sheets_from_html(document: &GeneratedReport) -> Vec<Sheet> {
+    parse_html(&document.document)
+        .into_iter()
+        .filter_map(|block| match block {
+            Block::Table { rows } => Some(rows),
+            _ => None,
+        })
+        .enumerate()
+        .map(|(index, rows)| {
+            let (header_rows, rows): (Vec<_>, Vec<_>) =
+                rows.into_iter().partition(|row| row.is_header);
+            Sheet {
+                name: format!("Sheet{}", index + 1),
+                header_rows: header_rows
+                    .into_iter()
+                    .map(|row| row.cells.into_iter().map(|cell| cell.text).collect())
+                    .collect(),
+                rows: rows
+                    .into_iter()
+                    .map(|row| {
+                        row.cells
+                            .iter()
+                            .map(|cell| parse_cell_value(&cell.text))
+                            .collect()
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Cell text that looks like a plain number is stored as number, e.g. so that columns can be
+/// summed up. Codes with leading zeros are kept as text.
+fn parse_cell_value(text: &str) -> CellValue {
+    let trimmed = text.trim();
+    let is_plain_number = !trimmed.is_empty()
+        && trimmed
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == '-')
+        && !(trimmed.len() > 1 && trimmed.starts_with('0') && !trimmed.starts_with("0."));
+    match trimmed.parse::<f64>() {
+        Ok(number) if is_plain_number => CellValue::Number(number),
+        _ => CellValue::Text(text.to_string()),
+    }
+}
+
+pub(super) fn sheets_from_data(
+    template: &SpreadsheetTemplate,
+    data: &Value,
+) -> Result<Vec<Sheet>, String> {
+    template
+        .sheets
+        .iter()
+        .map(|mapping| sheet_from_data(mapping, data))
+        .collect()
+}
+
+fn sheet_from_data(mapping: &SheetMapping, data: &Value) -> Result<Sheet, String> {
+    let rows = match lookup(data, &mapping.rows) {
+        Some(Value::Array(rows)) => rows,
+        // GraphQL connection, e.g. "invoice.lines"
+        Some(Value::Object(object)) => match object.get("nodes") {
+            Some(Value::Array(rows)) => rows,
+            _ => return Err(format!("Rows of sheet {} is not a list", mapping.name)),
+        },
+        // No data
+        Some(Value::Null) | None => return Ok(empty_sheet(mapping)),
+        Some(_) => return Err(format!("Rows of sheet {} is not a list", mapping.name)),
+    };
+
+    let (headers, paths): (Vec<String>, Vec<String>) = if mapping.columns.is_empty() {
+        match rows.first() {
+            Some(Value::Object(row)) => row.keys().map(|key| (key.clone(), key.clone())).unzip(),
+            _ => (vec![], vec![]),
+        }
+    } else {
+        mapping
+            .columns
+            .iter()
+            .map(|column| (column.header.clone(), column.value.clone()))
+            .unzip()
+    };
+
+    Ok(Sheet {
+        name: mapping.name.clone(),
+        header_rows: vec![headers],
+        rows: rows
+            .iter()
+            .map(|row| {
+                paths
+                    .iter()
+                    .map(|path| to_cell_value(lookup(row, path)))
+                    .collect()
+            })
+            .collect(),
+    })
+}
+
+fn empty_sheet(mapping: &SheetMapping) -> Sheet {
+    Sheet {
+        name: mapping.name.clone(),
+        header_rows: vec![mapping
+            .columns
+            .iter()
+            .map(|column| column.header.clone())
+            .collect()],
+        rows: vec![],
+    }
+}
+
+/// Looks up a dot separated path, e.g. "lines.nodes.0.item.name"
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |value, segment| match value {
+            Value::Object(object) => object.get(segment),
+            Value::Array(array) => segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| array.get(index)),
+            _ => None,
+        })
+}
+
+fn to_cell_value(value: Option<&Value>) -> CellValue {
+    match value {
+        None | Some(Value::Null) => CellValue::Text(String::new()),
+        Some(Value::Number(number)) => match number.as_f64() {
+            Some(number) => CellValue::Number(number),
+            None => CellValue::Text(number.to_string()),
+        },
+        Some(Value::String(text)) => CellValue::Text(text.clone()),
+        Some(Value::Bool(value)) => CellValue::Text(value.to_string()),
+        Some(value) => CellValue::Text(value.to_string()),
+    }
+}
+
+/// Worksheet names must be unique and can't contain []:*?/\
+fn worksheet_names(sheets: &[Sheet]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for (index, sheet) in sheets.iter().enumerate() {
+        let name: String = sheet
+            .name
+            .chars()
+            .map(|c| match c {
+                '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+                c => c,
+            })
+            .take(MAX_SHEET_NAME_LENGTH)
+            .collect();
+        let name = name.trim().trim_matches('\'').to_string();
+        let is_duplicate = names
+            .iter()
+            .any(|existing| existing.to_lowercase() == name.to_lowercase());
+        let name = if name.is_empty() || is_duplicate {
+            let suffix = format!(" {}", index + 1);
+            let prefix: String = name
+                .chars()
+                .take(MAX_SHEET_NAME_LENGTH - suffix.len())
+                .collect();
+            format!("{}{}", prefix, suffix).trim().to_string()
+        } else {
+            name
+        };
+        names.push(name);
+    }
+    names
+}
+
+pub(super) fn to_xlsx(sheets: &[Sheet]) -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let header_format = Format::new().set_bold();
+
+    for (sheet, name) in sheets.iter().zip(worksheet_names(sheets)) {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(name)?;
+
+        for (row_index, row) in sheet.header_rows.iter().enumerate() {
+            for (column_index, text) in row.iter().enumerate() {
+                worksheet.write_string_with_format(
+                    row_index as u32,
+                    column_index as u16,
+                    text,
+                    &header_format,
+                )?;
+            }
+        }
+        let header_row_count = sheet.header_rows.len();
+        if header_row_count > 0 {
+            worksheet.set_freeze_panes(header_row_count as u32, 0)?;
+        }
+
+        for (row_index, row) in sheet.rows.iter().enumerate() {
+            let row_index = (header_row_count + row_index) as u32;
+            for (column_index, value) in row.iter().enumerate() {
+                match value {
+                    CellValue::Number(number) => {
+                        worksheet.write_number(row_index, column_index as u16, *number)?
+                    }
+                    CellValue::Text(text) => {
+                        worksheet.write_string(row_index, column_index as u16, text)?
+                    }
+                };
+            }
+        }
+    }
+    // Excel can't open a workbook without worksheet
+    if sheets.is_empty() {
+        workbook.add_worksheet();
+    }
+
+    workbook.save_to_buffer()
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// All sheets in one CSV file, separated by an empty line.
+/// Starts with a UTF-8 byte order mark so that Excel detects the encoding
+pub(super) fn to_csv(sheets: &[Sheet]) -> String {
+    let header_rows = |sheet: &Sheet| -> Vec<String> {
+        sheet
+            .header_rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|text| escape_csv_field(text))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect()
+    };
+    let rows = |sheet: &Sheet| -> Vec<String> {
+        sheet
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|value| match value {
+                        CellValue::Number(number) => number.to_string(),
+                        CellValue::Text(text) => escape_csv_field(text),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect()
+    };
+
+    let sheets: Vec<String> = sheets
+        .iter()
+        .map(|sheet| {
+            let mut lines = header_rows(sheet);
+            lines.append(&mut rows(sheet));
+            lines.join("\r\n")
+        })
+        .collect();
+    format!("\u{feff}{}\r\n", sheets.join("\r\n\r\n"))
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::report::{
+        definition::{ColumnMapping, ReportOutputType, SheetMapping, SpreadsheetTemplate},
+        report_service::GeneratedReport,
+    };
+
+    use super::{sheets_from_data, sheets_from_html, to_csv, to_xlsx, CellValue, Sheet};
+
+    #[test]
+    fn spreadsheet_from_html() {
+        let sheets = sheets_from_html(&GeneratedReport {
+            document: r#"<h1>Stock</h1>
+                <table>
+                    <tr><th>Code</th><th>Name</th><th>Quantity</th></tr>
+                    <tr><td>007</td><td>Item, "A"</td><td>10.5</td></tr>
+                </table>"#
+                .to_string(),
+            header: None,
+            footer: None,
+        });
+
+        assert_eq!(
+            sheets,
+            vec![Sheet {
+                name: "Sheet1".to_string(),
+                header_rows: vec![vec![
+                    "Code".to_string(),
+                    "Name".to_string(),
+                    "Quantity".to_string()
+                ]],
+                rows: vec![vec![
+                    CellValue::Text("007".to_string()),
+                    CellValue::Text("Item, \"A\"".to_string()),
+                    CellValue::Number(10.5)
+                ]]
+            }]
+        );
+        assert_eq!(
+            to_csv(&sheets),
+            "\u{feff}Code,Name,Quantity\r\n007,\"Item, \"\"A\"\"\",10.5\r\n"
+        );
+        assert!(to_xlsx(&sheets).unwrap().starts_with(b"PK"));
+    }
+
+    #[test]
+    fn spreadsheet_from_data() {
+        let template = SpreadsheetTemplate {
+            output: ReportOutputType::Xlsx,
+            sheets: vec![
+                SheetMapping {
+                    name: "Lines".to_string(),
+                    rows: "invoice.lines".to_string(),
+                    columns: vec![
+                        ColumnMapping {
+                            header: "Item".to_string(),
+                            value: "item.name".to_string(),
+                        },
+                        ColumnMapping {
+                            header: "Packs".to_string(),
+                            value: "numberOfPacks".to_string(),
+                        },
+                    ],
+                },
+                SheetMapping {
+                    name: "Stock".to_string(),
+                    rows: "stock".to_string(),
+                    columns: vec![],
+                },
+            ],
+        };
+        let data = json!({
+            "invoice": {
+                "lines": {
+                    "nodes": [
+                        { "item": { "name": "Item A" }, "numberOfPacks": 2 },
+                        { "item": { "name": "Item B" }, "numberOfPacks": null }
+                    ]
+                }
+            },
+            "stock": [{ "code": "A", "soh": 5 }]
+        });
+
+        let sheets = sheets_from_data(&template, &data).unwrap();
+        assert_eq!(sheets.len(), 2);
+        assert_eq!(
+            sheets[0].rows,
+            vec![
+                vec![
+                    CellValue::Text("Item A".to_string()),
+                    CellValue::Number(2.0)
+                ],
+                vec![
+                    CellValue::Text("Item B".to_string()),
+                    CellValue::Text("".to_string())
+                ]
+            ]
+        );
+        assert_eq!(
+            sheets[1].header_rows,
+            vec![vec!["code".to_string(), "soh".to_string()]]
+        );
+        assert_eq!(
+            sheets[1].rows,
+            vec![vec![
+                CellValue::Text("A".to_string()),
+                CellValue::Number(5.0)
+            ]]
+        );
+
+        // Rows is not a list
+        let template = SpreadsheetTemplate {
+            output: ReportOutputType::Csv,
+            sheets: vec![SheetMapping {
+                name: "Invoice".to_string(),
+                rows: "invoice.lines.nodes.0".to_string(),
+                columns: vec![],
+            }],
+        };
+        assert!(sheets_from_data(&template, &data).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::Utc;
+use repository::{
+    requisition_row::{RequisitionRow, RequisitionStatus, RequisitionType},
+    ApprovalStatusType, RepositoryError, Requisition, RequisitionApprovalRow,
+    RequisitionApprovalRowRepository, RequisitionRowRepository, StorageConnection,
+};
+use util::{inline_edit, uuid::uuid};
+
+use crate::{
+    requisition::{common::check_requisition_row_exists, query::get_requisition},
+    service_provider::ServiceContext,
+};
+
+use super::{get_pending_approval, PendingApproval};
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub enum ApproveRequisitionStatus {
+    #[default]
+    Approved,
+    Denied,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ApproveRequisition {
+    pub id: String,
+    pub status: ApproveRequisitionStatus,
+    /// Required when denying
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ApproveRequisitionError {
+    RequisitionDoesNotExist,
+    NotThisStoreRequisition,
+    NotAResponseRequisition,
+    CannotEditRequisition,
+    /// Not pending approval through an approval chain
+    NotPendingApproval,
+    /// User is not the approver of the level, or already approved a previous level
+    UserCannotApproveLevel,
+    CommentRequiredToDeny,
+    UpdatedRequisitionDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = ApproveRequisitionError;
+
+/// Approve or deny the current approval chain level of a response requisition. The requisition
+/// is approved when the last level is approved, and denied when any level is denied
+pub fn approve_requisition(
+    ctx: &ServiceContext,
+    input: ApproveRequisition,
+) -> Result<Requisition, OutError> {
+    let requisition = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let (requisition_row, pending_approval) =
+                validate(connection, &ctx.store_id, &ctx.user_id, &input)?;
+            let (updated_requisition_row, approval) =
+                generate(&ctx.user_id, requisition_row, pending_approval, input);
+
+            RequisitionRowRepository::new(connection).upsert_one(&updated_requisition_row)?;
+            RequisitionApprovalRowRepository::new(connection).upsert_one(&approval)?;
+
+            get_requisition(ctx, None, &updated_requisition_row.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::UpdatedRequisitionDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(requisition)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    user_id: &str,
+    input: &ApproveRequisition,
+) -> Result<(RequisitionRow, PendingApproval), OutError> {
+    let requisition_row = check_requisition_row_exists(connection, &input.id)?
+        .ok_or(OutError::RequisitionDoesNotExist)?;
+
+    if requisition_row.store_id != store_id {
+        return Err(OutError::NotThisStoreRequisition);
+    }
+
+    if requisition_row.r#type != RequisitionType::Response {
+        return Err(OutError::NotAResponseRequisition);
+    }
+
+    if requisition_row.status != RequisitionStatus::New {
+        return Err(OutError::CannotEditRequisition);
+    }
+
+    let pending_approval =
+        get_pending_approval(connection, &requisition_row)?.ok_or(OutError::NotPendingApproval)?;
+
+    if !pending_approval.can_be_approved_by(user_id) {
+        return Err(OutError::UserCannotApproveLevel);
+    }
+
+    let has_comment = input
+        .comment
+        .as_ref()
+        .map_or(false, |comment| !comment.trim().is_empty());
+    if input.status == ApproveRequisitionStatus::Denied && !has_comment {
+        return Err(OutError::CommentRequiredToDeny);
+    }
+
+    Ok((requisition_row, pending_approval))
+}
+
+fn generate(
+    user_id: &str,
+    existing: RequisitionRow,
+    PendingApproval {
+        level,
+        is_last_level,
+        approvals: _,
+    }: PendingApproval,
+    ApproveRequisition {
+        id: _,
+        status,
+        comment,
+    }: ApproveRequisition,
+) -> (RequisitionRow, RequisitionApprovalRow) {
+    let status = match status {
+        ApproveRequisitionStatus::Approved => ApprovalStatusType::Approved,
+        ApproveRequisitionStatus::Denied => ApprovalStatusType::Denied,
+    };
+
+    let updated_requisition_row = inline_edit(&existing, |mut u| {
+        u.user_id = Some(user_id.to_string());
+        if status == ApprovalStatusType::Denied || is_last_level {
+            u.approval_status = Some(status.clone());
+        }
+        u
+    });
+
+    let approval = RequisitionApprovalRow {
+        id: uuid(),
+        requisition_id: existing.id,
+        level: level.level,
+        user_id: user_id.to_string(),
+        status,
+        comment,
+        created_datetime: Utc::now().naive_utc(),
+    };
+
+    (updated_requisition_row, approval)
+}
+
+pub fn get_requisition_approvals(
+    ctx: &ServiceContext,
+    requisition_id: &str,
+) -> Result<Vec<RequisitionApprovalRow>, RepositoryError> {
+    RequisitionApprovalRowRepository::new(&ctx.connection)
+        .find_many_by_requisition_id(requisition_id)
+}
+
+impl From<RepositoryError> for ApproveRequisitionError {
+    fn from(error: RepositoryError) -> Self {
+        ApproveRequisitionError::DatabaseError(error)
+    }
+}
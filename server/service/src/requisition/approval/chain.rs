@@ -0,0 +1,171 @@
+use repository::{
+    ApprovalStatusType, EqualFilter, ProgramRowRepository, RepositoryError,
+    RequisitionApprovalLevelRow, RequisitionApprovalLevelRowRepository, RequisitionFilter,
+    RequisitionRepository, RequisitionStatus, RequisitionType, UserAccountRowRepository,
+};
+
+use crate::service_provider::ServiceContext;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct UpsertRequisitionApprovalLevel {
+    pub id: String,
+    pub program_id: Option<String>,
+    pub level: i32,
+    pub name: String,
+    pub approver_user_id: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpsertRequisitionApprovalLevelError {
+    NotThisStoreApprovalLevel,
+    LevelMustBePositive,
+    LevelAlreadyExists,
+    ProgramDoesNotExist,
+    ApproverDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteRequisitionApprovalLevelError {
+    ApprovalLevelDoesNotExist,
+    NotThisStoreApprovalLevel,
+    /// Response requisitions are pending approval through the level's chain
+    RequisitionsPendingApproval,
+    DatabaseError(RepositoryError),
+}
+
+pub fn get_requisition_approval_levels(
+    ctx: &ServiceContext,
+    store_id: &str,
+) -> Result<Vec<RequisitionApprovalLevelRow>, RepositoryError> {
+    RequisitionApprovalLevelRowRepository::new(&ctx.connection).find_many_by_store_id(store_id)
+}
+
+pub fn upsert_requisition_approval_level(
+    ctx: &ServiceContext,
+    input: UpsertRequisitionApprovalLevel,
+) -> Result<RequisitionApprovalLevelRow, UpsertRequisitionApprovalLevelError> {
+    let level = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate_upsert(ctx, &input)?;
+            let UpsertRequisitionApprovalLevel {
+                id,
+                program_id,
+                level,
+                name,
+                approver_user_id,
+            } = input;
+
+            let level = RequisitionApprovalLevelRow {
+                id,
+                store_id: ctx.store_id.clone(),
+                program_id,
+                level,
+                name,
+                approver_user_id,
+            };
+            RequisitionApprovalLevelRowRepository::new(connection).upsert_one(&level)?;
+
+            Ok(level)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(level)
+}
+
+fn validate_upsert(
+    ctx: &ServiceContext,
+    input: &UpsertRequisitionApprovalLevel,
+) -> Result<(), UpsertRequisitionApprovalLevelError> {
+    use UpsertRequisitionApprovalLevelError as Error;
+    let connection = &ctx.connection;
+    let repo = RequisitionApprovalLevelRowRepository::new(connection);
+
+    if let Some(existing) = repo.find_one_by_id(&input.id)? {
+        if existing.store_id != ctx.store_id {
+            return Err(Error::NotThisStoreApprovalLevel);
+        }
+    }
+
+    if input.level < 1 {
+        return Err(Error::LevelMustBePositive);
+    }
+
+    let level_exists = repo
+        .find_chain(&ctx.store_id, input.program_id.as_deref())?
+        .iter()
+        .any(|level| level.level == input.level && level.id != input.id);
+    if level_exists {
+        return Err(Error::LevelAlreadyExists);
+    }
+
+    if let Some(program_id) = &input.program_id {
+        if ProgramRowRepository::new(connection)
+            .find_one_by_id(program_id)?
+            .is_none()
+        {
+            return Err(Error::ProgramDoesNotExist);
+        }
+    }
+
+    if let Some(approver_user_id) = &input.approver_user_id {
+        if UserAccountRowRepository::new(connection)
+            .find_one_by_id(approver_user_id)?
+            .is_none()
+        {
+            return Err(Error::ApproverDoesNotExist);
+        }
+    }
+
+    Ok(())
+}
+
+/// Levels can't be deleted while response requisitions are pending approval through their chain,
+/// these would otherwise skip the level (or, for the last level, remain pending)
+pub fn delete_requisition_approval_level(
+    ctx: &ServiceContext,
+    id: &str,
+) -> Result<String, DeleteRequisitionApprovalLevelError> {
+    use DeleteRequisitionApprovalLevelError as Error;
+
+    let repo = RequisitionApprovalLevelRowRepository::new(&ctx.connection);
+    let level = repo
+        .find_one_by_id(id)?
+        .ok_or(Error::ApprovalLevelDoesNotExist)?;
+    if level.store_id != ctx.store_id {
+        return Err(Error::NotThisStoreApprovalLevel);
+    }
+
+    let has_pending_requisitions = RequisitionRepository::new(&ctx.connection)
+        .query_by_filter(
+            RequisitionFilter::new()
+                .store_id(EqualFilter::equal_to(&ctx.store_id))
+                .r#type(RequisitionType::Response.equal_to())
+                .status(RequisitionStatus::New.equal_to()),
+        )?
+        .into_iter()
+        .any(|requisition| {
+            let row = requisition.requisition_row;
+            row.approval_status == Some(ApprovalStatusType::Pending)
+                && row.program_id == level.program_id
+        });
+    if has_pending_requisitions {
+        return Err(Error::RequisitionsPendingApproval);
+    }
+
+    repo.delete(id)?;
+    Ok(level.id)
+}
+
+impl From<RepositoryError> for UpsertRequisitionApprovalLevelError {
+    fn from(error: RepositoryError) -> Self {
+        UpsertRequisitionApprovalLevelError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for DeleteRequisitionApprovalLevelError {
+    fn from(error: RepositoryError) -> Self {
+        DeleteRequisitionApprovalLevelError::DatabaseError(error)
+    }
+}
@@ -0,0 +1,115 @@
+use repository::{
+    ApprovalStatusType, RepositoryError, RequisitionApprovalLevelRow,
+    RequisitionApprovalLevelRowRepository, RequisitionApprovalRow,
+    RequisitionApprovalRowRepository, RequisitionRow, StorageConnection,
+};
+
+use crate::store_preference::get_store_preferences;
+
+pub mod approve;
+pub mod chain;
+#[cfg(test)]
+mod test;
+
+/// Next approval chain level to be approved for a response requisition
+#[derive(Debug, PartialEq)]
+pub struct PendingApproval {
+    pub level: RequisitionApprovalLevelRow,
+    pub is_last_level: bool,
+    pub approvals: Vec<RequisitionApprovalRow>,
+}
+
+impl PendingApproval {
+    /// Only the level's approver (if set) can approve it, and a user can only approve one level
+    pub fn can_be_approved_by(&self, user_id: &str) -> bool {
+        let is_level_approver = self
+            .level
+            .approver_user_id
+            .as_ref()
+            .map_or(true, |approver_user_id| approver_user_id == user_id);
+        let approved_another_level = self.approvals.iter().any(|approval| {
+            approval.user_id == user_id && approval.status == ApprovalStatusType::Approved
+        });
+
+        is_level_approver && !approved_another_level
+    }
+}
+
+/// How response requisitions of a store are approved
+#[derive(Debug, PartialEq)]
+pub enum ResponseRequisitionApproval {
+    NotRequired,
+    ApprovalChain,
+    RemoteAuthorisation,
+}
+
+/// Remote authorisation takes precedence over the store's approval chain: program requisitions
+/// of a store requiring response requisition authorisation are authorised remotely, which sets
+/// the approval status and approved quantities, even if the store has an approval chain for the
+/// program
+pub fn get_response_requisition_approval(
+    connection: &StorageConnection,
+    store_id: &str,
+    program_id: Option<&str>,
+) -> Result<ResponseRequisitionApproval, RepositoryError> {
+    // TODO Rework once plugin functionality has been implemented
+    if program_id.is_some()
+        && get_store_preferences(connection, store_id)?.response_requisition_requires_authorisation
+    {
+        return Ok(ResponseRequisitionApproval::RemoteAuthorisation);
+    }
+
+    let has_approval_chain = !RequisitionApprovalLevelRowRepository::new(connection)
+        .find_chain(store_id, program_id)?
+        .is_empty();
+    Ok(if has_approval_chain {
+        ResponseRequisitionApproval::ApprovalChain
+    } else {
+        ResponseRequisitionApproval::NotRequired
+    })
+}
+
+/// None if the requisition is not pending approval through an approval chain, e.g. it is
+/// pending remote authorisation instead
+pub fn get_pending_approval(
+    connection: &StorageConnection,
+    requisition_row: &RequisitionRow,
+) -> Result<Option<PendingApproval>, RepositoryError> {
+    if requisition_row.approval_status != Some(ApprovalStatusType::Pending) {
+        return Ok(None);
+    }
+    let approval = get_response_requisition_approval(
+        connection,
+        &requisition_row.store_id,
+        requisition_row.program_id.as_deref(),
+    )?;
+    if approval != ResponseRequisitionApproval::ApprovalChain {
+        return Ok(None);
+    }
+
+    let chain = RequisitionApprovalLevelRowRepository::new(connection).find_chain(
+        &requisition_row.store_id,
+        requisition_row.program_id.as_deref(),
+    )?;
+    let approvals = RequisitionApprovalRowRepository::new(connection)
+        .find_many_by_requisition_id(&requisition_row.id)?;
+
+    let mut remaining_levels: Vec<RequisitionApprovalLevelRow> = chain
+        .into_iter()
+        .filter(|level| {
+            !approvals.iter().any(|approval| {
+                approval.level == level.level && approval.status == ApprovalStatusType::Approved
+            })
+        })
+        .collect();
+
+    if remaining_levels.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(PendingApproval {
+        is_last_level: remaining_levels.len() == 1,
+        level: remaining_levels.remove(0),
+        approvals,
+    }))
+}
@@ -0,0 +1,410 @@
+use repository::{
+    mock::{
+        mock_item_a, mock_name_a, mock_program_a, mock_store_a, mock_store_b, mock_user_account_a,
+        mock_user_account_b, MockData, MockDataInserts,
+    },
+    requisition_row::{RequisitionRow, RequisitionStatus, RequisitionType},
+    test_db::setup_all_with_data,
+    ApprovalStatusType, RequisitionLineRow, RequisitionLineRowRepository, StorePreferenceRow,
+    StorePreferenceRowRepository,
+};
+use util::inline_init;
+
+use crate::{
+    requisition::{
+        approval::{
+            approve::{ApproveRequisition, ApproveRequisitionError, ApproveRequisitionStatus},
+            chain::{
+                DeleteRequisitionApprovalLevelError, UpsertRequisitionApprovalLevel,
+                UpsertRequisitionApprovalLevelError,
+            },
+            get_pending_approval, get_response_requisition_approval, ResponseRequisitionApproval,
+        },
+        common::check_approval_status,
+        response_requisition::{
+            UpdateResponseRequisition, UpdateResponseRequisitionError,
+            UpdateResponseRequisitionStatus,
+        },
+    },
+    requisition_line::response_requisition_line::{
+        UpdateResponseRequisitionLineApproval, UpdateResponseRequisitionLineApprovalError,
+    },
+    service_provider::ServiceProvider,
+};
+
+fn requisition() -> RequisitionRow {
+    inline_init(|r: &mut RequisitionRow| {
+        r.id = "approval_requisition".to_string();
+        r.name_link_id = mock_name_a().id;
+        r.store_id = mock_store_a().id;
+        r.r#type = RequisitionType::Response;
+        r.status = RequisitionStatus::New;
+        r.approval_status = Some(ApprovalStatusType::Pending);
+    })
+}
+
+fn requisition_line() -> RequisitionLineRow {
+    inline_init(|r: &mut RequisitionLineRow| {
+        r.id = "approval_requisition_line".to_string();
+        r.requisition_id = requisition().id;
+        r.item_link_id = mock_item_a().id;
+        r.requested_quantity = 10.0;
+        r.approved_quantity = 10.0;
+    })
+}
+
+#[actix_rt::test]
+async fn requisition_approval_chain() {
+    let (_, connection, connection_manager, _) = setup_all_with_data(
+        "requisition_approval_chain",
+        MockDataInserts::all(),
+        inline_init(|r: &mut MockData| {
+            r.requisitions = vec![requisition()];
+            r.requisition_lines = vec![requisition_line()];
+        }),
+    )
+    .await;
+
+    let service_provider = ServiceProvider::new(connection_manager, "app_data");
+    let context_a = service_provider
+        .context(mock_store_a().id, mock_user_account_a().id)
+        .unwrap();
+    let context_b = service_provider
+        .context(mock_store_a().id, mock_user_account_b().id)
+        .unwrap();
+    let service = &service_provider.requisition_service;
+    let line_service = &service_provider.requisition_line_service;
+
+    // Chain validation
+    assert_eq!(
+        service.upsert_requisition_approval_level(
+            &context_a,
+            UpsertRequisitionApprovalLevel {
+                id: "level_1".to_string(),
+                level: 0,
+                ..Default::default()
+            }
+        ),
+        Err(UpsertRequisitionApprovalLevelError::LevelMustBePositive)
+    );
+    assert_eq!(
+        service.upsert_requisition_approval_level(
+            &context_a,
+            UpsertRequisitionApprovalLevel {
+                id: "level_1".to_string(),
+                level: 1,
+                program_id: Some("invalid".to_string()),
+                ..Default::default()
+            }
+        ),
+        Err(UpsertRequisitionApprovalLevelError::ProgramDoesNotExist)
+    );
+    assert_eq!(
+        service.upsert_requisition_approval_level(
+            &context_a,
+            UpsertRequisitionApprovalLevel {
+                id: "level_1".to_string(),
+                level: 1,
+                approver_user_id: Some("invalid".to_string()),
+                ..Default::default()
+            }
+        ),
+        Err(UpsertRequisitionApprovalLevelError::ApproverDoesNotExist)
+    );
+
+    // Level 1 approved by user a, level 2 by any approver
+    service
+        .upsert_requisition_approval_level(
+            &context_a,
+            UpsertRequisitionApprovalLevel {
+                id: "level_1".to_string(),
+                level: 1,
+                name: "Pharmacist".to_string(),
+                approver_user_id: Some(mock_user_account_a().id),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    service
+        .upsert_requisition_approval_level(
+            &context_a,
+            UpsertRequisitionApprovalLevel {
+                id: "level_2".to_string(),
+                level: 2,
+                name: "Manager".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        service.upsert_requisition_approval_level(
+            &context_a,
+            UpsertRequisitionApprovalLevel {
+                id: "level_3".to_string(),
+                level: 2,
+                ..Default::default()
+            }
+        ),
+        Err(UpsertRequisitionApprovalLevelError::LevelAlreadyExists)
+    );
+
+    // Can't be supplied while pending approval
+    assert_eq!(
+        service.update_response_requisition(
+            &context_a,
+            UpdateResponseRequisition {
+                id: requisition().id,
+                status: Some(UpdateResponseRequisitionStatus::Finalised),
+                ..Default::default()
+            }
+        ),
+        Err(UpdateResponseRequisitionError::CannotEditRequisition)
+    );
+    // Pending approval without an approval chain is only possible for program requisitions
+    // (remote authorisation)
+    let other_store_requisition = RequisitionRow {
+        store_id: mock_store_b().id,
+        ..requisition()
+    };
+    assert_eq!(
+        check_approval_status(&connection, &other_store_requisition),
+        Ok(false)
+    );
+    assert_eq!(
+        check_approval_status(
+            &connection,
+            &RequisitionRow {
+                program_id: Some("program".to_string()),
+                ..other_store_requisition
+            }
+        ),
+        Ok(true)
+    );
+
+    // Levels can't be deleted while requisitions are pending approval
+    assert_eq!(
+        service.delete_requisition_approval_level(&context_a, "level_2"),
+        Err(DeleteRequisitionApprovalLevelError::RequisitionsPendingApproval)
+    );
+
+    // Level 1
+    assert_eq!(
+        service.approve_requisition(
+            &context_b,
+            ApproveRequisition {
+                id: requisition().id,
+                ..Default::default()
+            }
+        ),
+        Err(ApproveRequisitionError::UserCannotApproveLevel)
+    );
+    assert_eq!(
+        line_service.update_response_requisition_line_approval(
+            &context_a,
+            UpdateResponseRequisitionLineApproval {
+                id: requisition_line().id,
+                approved_quantity: 5.0,
+                approval_comment: None,
+            }
+        ),
+        Err(UpdateResponseRequisitionLineApprovalError::CommentRequiredForReduction)
+    );
+    line_service
+        .update_response_requisition_line_approval(
+            &context_a,
+            UpdateResponseRequisitionLineApproval {
+                id: requisition_line().id,
+                approved_quantity: 5.0,
+                approval_comment: Some("Overstocked".to_string()),
+            },
+        )
+        .unwrap();
+    let requisition_result = service
+        .approve_requisition(
+            &context_a,
+            ApproveRequisition {
+                id: requisition().id,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        requisition_result.requisition_row.approval_status,
+        Some(ApprovalStatusType::Pending)
+    );
+
+    // Level 2, can't be approved by the level 1 approver
+    assert_eq!(
+        service.approve_requisition(
+            &context_a,
+            ApproveRequisition {
+                id: requisition().id,
+                ..Default::default()
+            }
+        ),
+        Err(ApproveRequisitionError::UserCannotApproveLevel)
+    );
+    assert_eq!(
+        service.approve_requisition(
+            &context_b,
+            ApproveRequisition {
+                id: requisition().id,
+                status: ApproveRequisitionStatus::Denied,
+                comment: None,
+            }
+        ),
+        Err(ApproveRequisitionError::CommentRequiredToDeny)
+    );
+    let requisition_result = service
+        .approve_requisition(
+            &context_b,
+            ApproveRequisition {
+                id: requisition().id,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        requisition_result.requisition_row.approval_status,
+        Some(ApprovalStatusType::Approved)
+    );
+
+    assert_eq!(
+        service.approve_requisition(
+            &context_b,
+            ApproveRequisition {
+                id: requisition().id,
+                ..Default::default()
+            }
+        ),
+        Err(ApproveRequisitionError::NotPendingApproval)
+    );
+
+    // History
+    let approvals: Vec<(i32, String, ApprovalStatusType)> = service
+        .get_requisition_approvals(&context_a, &requisition().id)
+        .unwrap()
+        .into_iter()
+        .map(|approval| (approval.level, approval.user_id, approval.status))
+        .collect();
+    assert_eq!(
+        approvals,
+        vec![
+            (1, mock_user_account_a().id, ApprovalStatusType::Approved),
+            (2, mock_user_account_b().id, ApprovalStatusType::Approved)
+        ]
+    );
+
+    let line = RequisitionLineRowRepository::new(&connection)
+        .find_one_by_id(&requisition_line().id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(line.approved_quantity, 5.0);
+    assert_eq!(line.approval_comment, Some("Overstocked".to_string()));
+
+    assert_eq!(
+        service.delete_requisition_approval_level(&context_a, "level_2"),
+        Ok("level_2".to_string())
+    );
+
+    // Approved requisition can be supplied
+    assert!(service
+        .update_response_requisition(
+            &context_a,
+            UpdateResponseRequisition {
+                id: requisition().id,
+                status: Some(UpdateResponseRequisitionStatus::Finalised),
+                ..Default::default()
+            }
+        )
+        .is_ok());
+}
+
+#[actix_rt::test]
+async fn requisition_remote_authorisation_precedence() {
+    let program_requisition = RequisitionRow {
+        id: "program_approval_requisition".to_string(),
+        program_id: Some(mock_program_a().id),
+        ..requisition()
+    };
+    let (_, connection, connection_manager, _) = setup_all_with_data(
+        "requisition_remote_authorisation_precedence",
+        MockDataInserts::all(),
+        inline_init(|r: &mut MockData| {
+            r.requisitions = vec![program_requisition.clone()];
+        }),
+    )
+    .await;
+
+    let service_provider = ServiceProvider::new(connection_manager, "app_data");
+    let context = service_provider
+        .context(mock_store_a().id, mock_user_account_a().id)
+        .unwrap();
+    let service = &service_provider.requisition_service;
+
+    service
+        .upsert_requisition_approval_level(
+            &context,
+            UpsertRequisitionApprovalLevel {
+                id: "program_level_1".to_string(),
+                level: 1,
+                name: "Program manager".to_string(),
+                program_id: Some(mock_program_a().id),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        get_response_requisition_approval(
+            &connection,
+            &mock_store_a().id,
+            Some(&mock_program_a().id)
+        ),
+        Ok(ResponseRequisitionApproval::ApprovalChain)
+    );
+    assert!(get_pending_approval(&connection, &program_requisition)
+        .unwrap()
+        .is_some());
+
+    // Store also requires remote authorisation of program requisitions
+    StorePreferenceRowRepository::new(&connection)
+        .upsert_one(&StorePreferenceRow {
+            id: mock_store_a().id,
+            response_requisition_requires_authorisation: true,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(
+        get_response_requisition_approval(
+            &connection,
+            &mock_store_a().id,
+            Some(&mock_program_a().id)
+        ),
+        Ok(ResponseRequisitionApproval::RemoteAuthorisation)
+    );
+    // Requisitions without a program are not authorised remotely
+    assert_eq!(
+        get_response_requisition_approval(&connection, &mock_store_a().id, None),
+        Ok(ResponseRequisitionApproval::NotRequired)
+    );
+
+    // Requisition is pending remote authorisation and can't be approved through the chain
+    assert_eq!(
+        get_pending_approval(&connection, &program_requisition),
+        Ok(None)
+    );
+    assert_eq!(
+        service.approve_requisition(
+            &context,
+            ApproveRequisition {
+                id: program_requisition.id.clone(),
+                ..Default::default()
+            }
+        ),
+        Err(ApproveRequisitionError::NotPendingApproval)
+    );
+    assert_eq!(
+        check_approval_status(&connection, &program_requisition),
+        Ok(true)
+    );
+}
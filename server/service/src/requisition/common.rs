@@ -3,7 +3,8 @@ use repository::{
     RequisitionLineRepository, RequisitionRowRepository, StorageConnection,
 };
 use repository::{
-    ApprovalStatusType, EqualFilter, Requisition, RequisitionFilter, RequisitionRepository,
+    ApprovalStatusType, EqualFilter, Requisition, RequisitionApprovalLevelRowRepository,
+    RequisitionFilter, RequisitionRepository,
 };
 use util::inline_edit;
 
@@ -46,13 +47,30 @@ pub fn generate_requisition_user_id_update(
     })
 }
 
-pub fn check_approval_status(requisition_row: &RequisitionRow) -> bool {
+/// Response requisitions pending approval, or with denied approval, can't be edited or supplied.
+/// Approval is pending either through an approval chain or remote authorisation (program
+/// requisitions only)
+pub fn check_approval_status(
+    connection: &StorageConnection,
+    requisition_row: &RequisitionRow,
+) -> Result<bool, RepositoryError> {
     // TODO Rework once plugins are implemented
-    if let Some(approval_status) = &requisition_row.approval_status {
-        return requisition_row.program_id.is_some()
-            && (*approval_status == ApprovalStatusType::Pending
-                || *approval_status == ApprovalStatusType::Denied
-                || *approval_status == ApprovalStatusType::DeniedByAnother);
+    let Some(approval_status) = &requisition_row.approval_status else {
+        return Ok(false);
+    };
+    if !(*approval_status == ApprovalStatusType::Pending
+        || *approval_status == ApprovalStatusType::Denied
+        || *approval_status == ApprovalStatusType::DeniedByAnother)
+    {
+        return Ok(false);
+    }
+    if requisition_row.program_id.is_some() {
+        return Ok(true);
     }
-    false
+
+    // Non program requisitions are only approved through an approval chain
+    let has_approval_chain = !RequisitionApprovalLevelRowRepository::new(connection)
+        .find_chain(&requisition_row.store_id, None)?
+        .is_empty();
+    Ok(has_approval_chain)
 }
@@ -1,4 +1,15 @@
 use self::{
+    approval::{
+        approve::{
+            approve_requisition, get_requisition_approvals, ApproveRequisition,
+            ApproveRequisitionError,
+        },
+        chain::{
+            delete_requisition_approval_level, get_requisition_approval_levels,
+            upsert_requisition_approval_level, DeleteRequisitionApprovalLevelError,
+            UpsertRequisitionApprovalLevel, UpsertRequisitionApprovalLevelError,
+        },
+    },
     program_settings::{get_program_requisition_settings, ProgramSettings},
     query::{get_requisition, get_requisition_by_number, get_requisitions},
     request_requisition::{
@@ -22,10 +33,12 @@ use super::{ListError, ListResult};
 use crate::service_provider::ServiceContext;
 use repository::PaginationOption;
 use repository::{
-    requisition_row::RequisitionType, Invoice, RepositoryError, Requisition, RequisitionFilter,
-    RequisitionLine, RequisitionSort,
+    requisition_row::RequisitionType, Invoice, RepositoryError, Requisition,
+    RequisitionApprovalLevelRow, RequisitionApprovalRow, RequisitionFilter, RequisitionLine,
+    RequisitionSort,
 };
 
+pub mod approval;
 pub mod common;
 pub mod program_settings;
 pub mod query;
@@ -159,6 +172,46 @@ pub trait RequisitionServiceTrait: Sync + Send {
     ) -> Result<Vec<ProgramSettings>, RepositoryError> {
         get_program_requisition_settings(ctx, store_id)
     }
+
+    fn get_requisition_approval_levels(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<Vec<RequisitionApprovalLevelRow>, RepositoryError> {
+        get_requisition_approval_levels(ctx, store_id)
+    }
+
+    fn upsert_requisition_approval_level(
+        &self,
+        ctx: &ServiceContext,
+        input: UpsertRequisitionApprovalLevel,
+    ) -> Result<RequisitionApprovalLevelRow, UpsertRequisitionApprovalLevelError> {
+        upsert_requisition_approval_level(ctx, input)
+    }
+
+    fn delete_requisition_approval_level(
+        &self,
+        ctx: &ServiceContext,
+        id: &str,
+    ) -> Result<String, DeleteRequisitionApprovalLevelError> {
+        delete_requisition_approval_level(ctx, id)
+    }
+
+    fn approve_requisition(
+        &self,
+        ctx: &ServiceContext,
+        input: ApproveRequisition,
+    ) -> Result<Requisition, ApproveRequisitionError> {
+        approve_requisition(ctx, input)
+    }
+
+    fn get_requisition_approvals(
+        &self,
+        ctx: &ServiceContext,
+        requisition_id: &str,
+    ) -> Result<Vec<RequisitionApprovalRow>, RepositoryError> {
+        get_requisition_approvals(ctx, requisition_id)
+    }
 }
 
 pub struct RequisitionService {}
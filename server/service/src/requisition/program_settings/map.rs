@@ -74,6 +74,8 @@ pub(super) fn map(
 /// matching period_schedule_id and number of requisition that exists for this
 /// order_type and program_id is within order_type.max_order_per_period
 /// note: lowercase match for order type
+/// Emergency order types are only available for the current period, and are counted
+/// separately to other order types as they have their own name
 fn period_is_available(
     period: &PeriodRow,
     setting: &ProgramRequisitionSettings,
@@ -84,6 +86,11 @@ fn period_is_available(
         return false;
     }
 
+    let today = date_now();
+    if order_type.is_emergency && (period.start_date > today || period.end_date < today) {
+        return false;
+    }
+
     // requisitions_in_period already has a count of how many requisitions are in a period
     // there should only be one requistions_in_period entry for one program period, see
     // requisitions_in_period view
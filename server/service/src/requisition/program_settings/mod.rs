@@ -161,6 +161,7 @@ mod test {
             program_id: program1.id.clone(),
             name_tag_id: name_tag1.id.clone(),
             period_schedule_id: period_schedule1.id.clone(),
+            consumption_forecast_method: None,
         };
         let order_type1 = ProgramRequisitionOrderTypeRow {
             id: "order_type1".to_string(),
@@ -174,6 +175,7 @@ mod test {
             program_id: program2.id.clone(),
             name_tag_id: name_tag2.id.clone(),
             period_schedule_id: period_schedule2.id.clone(),
+            consumption_forecast_method: None,
         };
         let order_type2 = ProgramRequisitionOrderTypeRow {
             id: "order_type2".to_string(),
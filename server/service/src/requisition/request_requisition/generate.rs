@@ -2,7 +2,8 @@ use chrono::Utc;
 use repository::{EqualFilter, RepositoryError, RequisitionLineRow, RequisitionRow};
 use util::uuid::uuid;
 
-use crate::item_stats::{get_item_stats, ItemStatsFilter};
+use crate::consumption_forecast::get_consumption_forecast_method;
+use crate::item_stats::{get_item_stats_with_forecast_method, ItemStatsFilter};
 use crate::service_provider::ServiceContext;
 
 pub struct GenerateSuggestedQuantity {
@@ -44,11 +45,18 @@ pub fn generate_requisition_lines(
     requisition_row: &RequisitionRow,
     item_ids: Vec<String>,
 ) -> Result<Vec<RequisitionLineRow>, RepositoryError> {
-    let item_stats_rows = get_item_stats(
+    let consumption_forecast_method = get_consumption_forecast_method(
+        &ctx.connection,
+        store_id,
+        requisition_row.program_id.as_deref(),
+    )?;
+
+    let item_stats_rows = get_item_stats_with_forecast_method(
         ctx,
         store_id,
         None,
         Some(ItemStatsFilter::new().item_id(EqualFilter::equal_any(item_ids))),
+        &consumption_forecast_method,
     )?;
 
     let result = item_stats_rows
@@ -78,6 +86,12 @@ pub fn generate_requisition_lines(
                 requested_quantity: 0.0,
                 approved_quantity: 0.0,
                 approval_comment: None,
+                opening_balance: None,
+                quantity_received: None,
+                quantity_dispensed: None,
+                losses_and_adjustments: None,
+                closing_balance: None,
+                days_out_of_stock: None,
             }
         })
         .collect();
@@ -139,6 +139,7 @@ fn generate(
         program_id: None,
         period_id: None,
         order_type: None,
+        is_emergency: false,
     };
 
     Ok(result)
@@ -11,11 +11,11 @@ use chrono::{NaiveDate, Utc};
 use repository::{
     requisition_row::{RequisitionRow, RequisitionStatus, RequisitionType},
     ActivityLogType, EqualFilter, MasterListLineFilter, MasterListLineRepository, NumberRowType,
-    ProgramRequisitionOrderTypeRow, ProgramRow, RepositoryError, Requisition, RequisitionLineRow,
-    RequisitionLineRowRepository, RequisitionRowRepository,
+    PeriodRowRepository, ProgramRequisitionOrderTypeRow, ProgramRow, RepositoryError, Requisition,
+    RequisitionLineRow, RequisitionLineRowRepository, RequisitionRowRepository,
 };
 
-use super::generate_requisition_lines;
+use super::{generate_lmis, generate_requisition_lines};
 
 #[derive(Debug, PartialEq)]
 pub enum InsertProgramRequestRequisitionError {
@@ -160,6 +160,7 @@ fn generate(
         program_id: Some(program.id),
         period_id: Some(period_id),
         order_type: Some(order_type.name),
+        is_emergency: order_type.is_emergency,
         // Default
         sent_datetime: None,
         approval_status: None,
@@ -177,12 +178,53 @@ fn generate(
         .map(|line| line.item_id)
         .collect();
 
-    let requisition_line_rows =
+    let mut requisition_line_rows =
         generate_requisition_lines(ctx, &ctx.store_id, &requisition, program_item_ids)?;
+    if order_type.is_emergency {
+        requisition_line_rows = restrict_emergency_order_lines(
+            requisition_line_rows,
+            order_type.max_items_in_emergency_order,
+        );
+    }
+
+    let period = PeriodRowRepository::new(connection)
+        .find_one_by_id(requisition.period_id.as_deref().unwrap_or_default())?;
+    if let Some(period) = period {
+        let item_ids = requisition_line_rows
+            .iter()
+            .map(|line| line.item_link_id.clone())
+            .collect();
+        let lmis_by_item = generate_lmis(connection, &ctx.store_id, item_ids, &period)?;
+        for line in requisition_line_rows.iter_mut() {
+            if let Some(lmis) = lmis_by_item.get(&line.item_link_id) {
+                lmis.apply_to(line);
+            }
+        }
+    }
 
     Ok((requisition, requisition_line_rows))
 }
 
+/// Emergency orders only include items below the order type threshold, lowest months of stock first,
+/// limited to max_items_in_emergency_order (0 for no limit)
+fn restrict_emergency_order_lines(
+    mut lines: Vec<RequisitionLineRow>,
+    max_items_in_emergency_order: i32,
+) -> Vec<RequisitionLineRow> {
+    // Suggested quantity is only above zero for items with consumption, below the threshold
+    lines.retain(|line| line.suggested_quantity > 0.0);
+
+    let months_of_stock =
+        |line: &RequisitionLineRow| line.available_stock_on_hand / line.average_monthly_consumption;
+    lines.sort_by(|a, b| months_of_stock(a).total_cmp(&months_of_stock(b)));
+
+    if max_items_in_emergency_order > 0 {
+        lines.truncate(max_items_in_emergency_order as usize);
+    }
+
+    lines
+}
+
 impl From<RepositoryError> for InsertProgramRequestRequisitionError {
     fn from(error: RepositoryError) -> Self {
         InsertProgramRequestRequisitionError::DatabaseError(error)
@@ -199,15 +241,16 @@ mod test_insert {
     };
     use repository::{
         mock::{
-            mock_name_store_b, mock_period, mock_program_a, mock_program_order_types_a,
+            mock_name_store_b, mock_period, mock_period_schedule_1, mock_program_a,
+            mock_program_order_types_a, mock_program_requisition_setting_a,
             mock_request_draft_requisition, mock_user_account_a, program_master_list_store,
             MockData, MockDataInserts,
         },
         test_db::{setup_all, setup_all_with_data},
-        EqualFilter, NameRow, RequisitionLineFilter, RequisitionLineRepository,
-        RequisitionRowRepository,
+        EqualFilter, NameRow, PeriodRow, ProgramRequisitionOrderTypeRow, RequisitionLineFilter,
+        RequisitionLineRepository, RequisitionLineRow, RequisitionRowRepository,
     };
-    use util::inline_init;
+    use util::{date_now, date_with_days_offset, inline_init};
 
     #[actix_rt::test]
     async fn insert_program_request_requisition_errors() {
@@ -314,6 +357,11 @@ mod test_insert {
         assert_eq!(new_row.order_type, Some(mock_program_order_types_a().id));
         assert_eq!(new_row.program_id, Some(mock_program_a().id));
         assert_eq!(requisition_lines.len(), 1);
+        // R&R values are generated for the period
+        let line = &requisition_lines[0].requisition_line_row;
+        assert!(line.opening_balance.is_some());
+        assert!(line.closing_balance.is_some());
+        assert!(line.days_out_of_stock.is_some());
 
         // Error: MaxOrdersReachedForPeriod
         assert_eq!(
@@ -329,4 +377,144 @@ mod test_insert {
             Err(ServiceError::MaxOrdersReachedForPeriod)
         );
     }
+
+    #[actix_rt::test]
+    async fn insert_program_emergency_request_requisition() {
+        fn emergency_order_type() -> ProgramRequisitionOrderTypeRow {
+            inline_init(|r: &mut ProgramRequisitionOrderTypeRow| {
+                r.id = "emergency_order_type".to_string();
+                r.program_requisition_settings_id = mock_program_requisition_setting_a().id;
+                r.name = "Emergency".to_string();
+                r.threshold_mos = 1.0;
+                r.max_mos = 2.0;
+                r.max_order_per_period = 1;
+                r.is_emergency = true;
+                r.max_items_in_emergency_order = 5;
+            })
+        }
+
+        fn current_period() -> PeriodRow {
+            inline_init(|r: &mut PeriodRow| {
+                r.id = "current_period".to_string();
+                r.name = "Current period".to_string();
+                r.period_schedule_id = mock_period_schedule_1().id;
+                r.start_date = date_with_days_offset(&date_now(), -5);
+                r.end_date = date_with_days_offset(&date_now(), 5);
+            })
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "insert_program_emergency_request_requisition",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.program_order_types = vec![emergency_order_type()];
+                r.periods = vec![current_period()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let context = service_provider
+            .context(program_master_list_store().id, mock_user_account_a().id)
+            .unwrap();
+        let service = service_provider.requisition_service;
+
+        // Emergency order types are only available for the current period
+        let settings = service
+            .get_program_requisition_settings(&context, &program_master_list_store().id)
+            .unwrap();
+        let emergency = settings
+            .iter()
+            .flat_map(|setting| setting.order_types.iter())
+            .find(|order_type| order_type.order_type.id == emergency_order_type().id)
+            .unwrap();
+        assert_eq!(emergency.available_periods, vec![current_period()]);
+
+        let result = service
+            .insert_program_request_requisition(
+                &context,
+                inline_init(|r: &mut InsertProgramRequestRequisition| {
+                    r.id = "emergency_requisition".to_string();
+                    r.other_party_id.clone_from(&mock_name_store_b().id);
+                    r.program_order_type_id = emergency_order_type().id;
+                    r.period_id = current_period().id;
+                }),
+            )
+            .unwrap();
+        assert!(result.requisition_row.is_emergency);
+        assert_eq!(
+            result.requisition_row.order_type,
+            Some("Emergency".to_string())
+        );
+
+        // Items without consumption are not below the threshold
+        assert_eq!(
+            RequisitionLineRepository::new(&connection)
+                .count(Some(RequisitionLineFilter::new().requisition_id(
+                    EqualFilter::equal_to("emergency_requisition")
+                )))
+                .unwrap(),
+            0
+        );
+
+        // Emergency orders per period are limited separately to the regular order type
+        assert_eq!(
+            service.insert_program_request_requisition(
+                &context,
+                inline_init(|r: &mut InsertProgramRequestRequisition| {
+                    r.id = "second_emergency_requisition".to_string();
+                    r.other_party_id.clone_from(&mock_name_store_b().id);
+                    r.program_order_type_id = emergency_order_type().id;
+                    r.period_id = current_period().id;
+                }),
+            ),
+            Err(ServiceError::MaxOrdersReachedForPeriod)
+        );
+        assert!(service
+            .insert_program_request_requisition(
+                &context,
+                inline_init(|r: &mut InsertProgramRequestRequisition| {
+                    r.id = "regular_requisition".to_string();
+                    r.other_party_id.clone_from(&mock_name_store_b().id);
+                    r.program_order_type_id = mock_program_order_types_a().id;
+                    r.period_id = mock_period().id;
+                }),
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn restrict_emergency_order_lines() {
+        fn line(
+            id: &str,
+            available_stock_on_hand: f64,
+            suggested_quantity: f64,
+        ) -> RequisitionLineRow {
+            inline_init(|r: &mut RequisitionLineRow| {
+                r.id = id.to_string();
+                r.average_monthly_consumption = 10.0;
+                r.available_stock_on_hand = available_stock_on_hand;
+                r.suggested_quantity = suggested_quantity;
+            })
+        }
+
+        let lines = vec![
+            line("above_threshold", 50.0, 0.0),
+            line("low", 5.0, 15.0),
+            line("stock_out", 0.0, 20.0),
+            line("lower", 2.0, 18.0),
+        ];
+
+        let ids = |lines: Vec<RequisitionLineRow>| -> Vec<String> {
+            lines.into_iter().map(|line| line.id).collect()
+        };
+        assert_eq!(
+            ids(super::restrict_emergency_order_lines(lines.clone(), 0)),
+            vec!["stock_out", "lower", "low"]
+        );
+        assert_eq!(
+            ids(super::restrict_emergency_order_lines(lines, 2)),
+            vec!["stock_out", "lower"]
+        );
+    }
 }
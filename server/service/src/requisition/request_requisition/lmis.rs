@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use repository::{
+    DatetimeFilter, EqualFilter, InvoiceType, LedgerFilter, LedgerRepository, Pagination,
+    PeriodRow, RepositoryError, RequisitionLineRow, StorageConnection,
+};
+use util::date_with_days_offset;
+
+use crate::stock_out::{get_daily_stock_on_hand, is_out_of_stock};
+
+/// Report and requisition (R&R) values of an item in a store for a period, in units
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ItemLmis {
+    pub opening_balance: f64,
+    pub quantity_received: f64,
+    pub quantity_dispensed: f64,
+    pub losses_and_adjustments: f64,
+    pub closing_balance: f64,
+    pub days_out_of_stock: i32,
+}
+
+impl ItemLmis {
+    pub fn apply_to(&self, line: &mut RequisitionLineRow) {
+        line.opening_balance = Some(self.opening_balance);
+        line.quantity_received = Some(self.quantity_received);
+        line.quantity_dispensed = Some(self.quantity_dispensed);
+        line.losses_and_adjustments = Some(self.losses_and_adjustments);
+        line.closing_balance = Some(self.closing_balance);
+        line.days_out_of_stock = Some(self.days_out_of_stock);
+    }
+}
+
+/// Calculates R&R values from the ledger, for the period start date to end date (inclusive).
+/// Inbound shipments are received, outbound shipments and prescriptions are dispensed
+/// and all other movements (inventory adjustments, repacks and returns) are losses and adjustments
+pub fn generate_lmis(
+    connection: &StorageConnection,
+    store_id: &str,
+    item_ids: Vec<String>,
+    period: &PeriodRow,
+) -> Result<HashMap<String, ItemLmis>, RepositoryError> {
+    let start_of_period = period.start_date.and_hms_opt(0, 0, 0).unwrap();
+    let end_of_period = date_with_days_offset(&period.end_date, 1)
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+
+    let ledger_rows = LedgerRepository::new(connection).query(
+        Pagination::all(),
+        Some(
+            LedgerFilter::new()
+                .item_id(EqualFilter::equal_any(item_ids.clone()))
+                .store_id(EqualFilter::equal_to(store_id))
+                .datetime(DatetimeFilter::before_or_equal_to(end_of_period)),
+        ),
+        None,
+    )?;
+
+    let mut result: HashMap<String, ItemLmis> = item_ids
+        .iter()
+        .map(|item_id| (item_id.clone(), ItemLmis::default()))
+        .collect();
+
+    for row in ledger_rows
+        .into_iter()
+        .filter(|row| row.datetime < end_of_period)
+    {
+        let Some(lmis) = result.get_mut(&row.item_id) else {
+            continue;
+        };
+
+        lmis.closing_balance += row.quantity;
+        if row.datetime < start_of_period {
+            lmis.opening_balance += row.quantity;
+            continue;
+        }
+
+        match row.invoice_type {
+            InvoiceType::InboundShipment => lmis.quantity_received += row.quantity,
+            InvoiceType::OutboundShipment | InvoiceType::Prescription => {
+                lmis.quantity_dispensed -= row.quantity
+            }
+            InvoiceType::InventoryAddition
+            | InvoiceType::InventoryReduction
+            | InvoiceType::Repack
+            | InvoiceType::InboundReturn
+            | InvoiceType::OutboundReturn => lmis.losses_and_adjustments += row.quantity,
+        }
+    }
+
+    let daily_stock_on_hand = get_daily_stock_on_hand(
+        connection,
+        store_id,
+        Some(EqualFilter::equal_any(item_ids)),
+        period.start_date,
+        period.end_date,
+    )?;
+    let days_in_period = (period.end_date - period.start_date).num_days() as i32 + 1;
+
+    for (item_id, lmis) in result.iter_mut() {
+        // Items without stock movements were never in stock
+        lmis.days_out_of_stock = match daily_stock_on_hand.get(item_id) {
+            Some(days) => days.iter().filter(|day| is_out_of_stock(day)).count() as i32,
+            None => days_in_period,
+        };
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::{mock_item_a, mock_item_b, mock_store_a, MockData, MockDataInserts},
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineType, InvoiceRow, InvoiceStatus, InvoiceType, PeriodRow,
+    };
+    use util::inline_init;
+
+    use super::{generate_lmis, ItemLmis};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    fn invoice(id: &str, r#type: InvoiceType, day: u32, number_of_packs: f64) -> MockData {
+        let line_type = match r#type {
+            InvoiceType::InboundShipment | InvoiceType::InventoryAddition => {
+                InvoiceLineType::StockIn
+            }
+            _ => InvoiceLineType::StockOut,
+        };
+        inline_init(|r: &mut MockData| {
+            r.invoices = vec![inline_init(|r: &mut InvoiceRow| {
+                r.id = id.to_string();
+                r.store_id = mock_store_a().id;
+                r.name_link_id = "name_store_b".to_string();
+                r.r#type = r#type;
+                r.status = InvoiceStatus::Verified;
+                r.picked_datetime = Some(date(day).and_hms_opt(12, 0, 0).unwrap());
+                r.delivered_datetime = Some(date(day).and_hms_opt(12, 0, 0).unwrap());
+                r.verified_datetime = Some(date(day).and_hms_opt(12, 0, 0).unwrap());
+            })];
+            r.invoice_lines = vec![inline_init(|r: &mut InvoiceLineRow| {
+                r.id = format!("{}_line", id);
+                r.invoice_id = id.to_string();
+                r.item_link_id = mock_item_a().id;
+                r.r#type = line_type;
+                r.pack_size = 1.0;
+                r.number_of_packs = number_of_packs;
+            })];
+        })
+    }
+
+    #[actix_rt::test]
+    async fn generate_lmis_from_ledger() {
+        let (_, connection, _, _) = setup_all_with_data(
+            "generate_lmis_from_ledger",
+            MockDataInserts::none().names().stores().units().items(),
+            invoice("before_period", InvoiceType::InboundShipment, 1, 20.0)
+                .join(invoice("received", InvoiceType::InboundShipment, 10, 30.0))
+                .join(invoice(
+                    "dispensed",
+                    InvoiceType::OutboundShipment,
+                    12,
+                    40.0,
+                ))
+                .join(invoice(
+                    "reduced",
+                    InvoiceType::InventoryReduction,
+                    15,
+                    10.0,
+                ))
+                .join(invoice("added", InvoiceType::InventoryAddition, 20, 5.0))
+                .join(invoice(
+                    "after_period",
+                    InvoiceType::OutboundShipment,
+                    25,
+                    5.0,
+                )),
+        )
+        .await;
+
+        let period = inline_init(|r: &mut PeriodRow| {
+            r.start_date = date(5);
+            r.end_date = date(21);
+        });
+
+        let result = generate_lmis(
+            &connection,
+            &mock_store_a().id,
+            vec![mock_item_a().id, mock_item_b().id],
+            &period,
+        )
+        .unwrap();
+
+        assert_eq!(
+            result.get(&mock_item_a().id),
+            Some(&ItemLmis {
+                opening_balance: 20.0,
+                quantity_received: 30.0,
+                quantity_dispensed: 40.0,
+                losses_and_adjustments: -5.0,
+                closing_balance: 5.0,
+                // 10 units left on the 12th, stock out from the 15th to the 19th
+                days_out_of_stock: 5,
+            })
+        );
+        // No stock movements
+        assert_eq!(
+            result.get(&mock_item_b().id),
+            Some(&ItemLmis {
+                days_out_of_stock: 17,
+                ..Default::default()
+            })
+        );
+    }
+}
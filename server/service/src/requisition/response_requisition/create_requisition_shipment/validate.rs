@@ -5,7 +5,8 @@ use repository::{
 
 use crate::requisition::requisition_supply_status::RequisitionLineSupplyStatus;
 use crate::requisition::{
-    common::check_requisition_exists, requisition_supply_status::get_requisitions_supply_statuses,
+    common::{check_approval_status, check_requisition_exists},
+    requisition_supply_status::get_requisitions_supply_statuses,
 };
 
 use super::{CreateRequisitionShipment, OutError};
@@ -30,6 +31,10 @@ pub fn validate(
         return Err(OutError::CannotEditRequisition);
     }
 
+    if check_approval_status(connection, requisition_row)? {
+        return Err(OutError::CannotEditRequisition);
+    }
+
     let supply_statuses =
         get_requisitions_supply_statuses(connection, vec![requisition_row.id.clone()])?;
 
@@ -87,7 +87,7 @@ fn validate(
         return Err(OutError::CannotEditRequisition);
     }
 
-    if check_approval_status(&requisition_row) {
+    if check_approval_status(connection, &requisition_row)? {
         return Err(OutError::CannotEditRequisition);
     }
 
@@ -81,7 +81,7 @@ pub fn validate(
     let requisition_row = check_requisition_row_exists(connection, &input.id)?
         .ok_or(OutError::RequisitionDoesNotExist)?;
 
-    if check_approval_status(&requisition_row) {
+    if check_approval_status(connection, &requisition_row)? {
         return Err(OutError::CannotEditRequisition);
     }
 
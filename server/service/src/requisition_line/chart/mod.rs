@@ -1,7 +1,7 @@
 use chrono::NaiveDate;
 use repository::{
-    requisition_row::RequisitionType, RepositoryError, RequisitionLine, RequisitionLineRow,
-    StorageConnection,
+    requisition_row::RequisitionType, ConsumptionForecastMethod, RepositoryError, RequisitionLine,
+    RequisitionLineRow, StorageConnection,
 };
 use util::{date_with_months_offset, last_day_of_the_month};
 mod historic_consumption;
@@ -10,7 +10,9 @@ pub use historic_consumption::*;
 mod stock_evolution;
 pub use stock_evolution::*;
 
-use crate::service_provider::ServiceContext;
+use crate::{
+    consumption_forecast::get_consumption_forecast_method, service_provider::ServiceContext,
+};
 
 use super::common::check_requisition_line_exists;
 
@@ -32,6 +34,8 @@ pub struct SuggestedQuantityCalculation {
     pub minimum_stock_on_hand: f64,
     pub maximum_stock_on_hand: f64,
     pub suggested: u32,
+    /// Method used to forecast average_monthly_consumption for the requisition
+    pub consumption_forecast_method: ConsumptionForecastMethod,
 }
 
 #[derive(Debug, PartialEq, Default)]
@@ -51,8 +55,13 @@ pub fn get_requisition_line_chart(
     // Validate
     let requisition_line = validate(&ctx.connection, &ctx.store_id, requisition_line_id)?;
 
-    let suggested_quantity_calculation =
+    let mut suggested_quantity_calculation =
         SuggestedQuantityCalculation::from_requisition_line(&requisition_line);
+    suggested_quantity_calculation.consumption_forecast_method = get_consumption_forecast_method(
+        &ctx.connection,
+        &ctx.store_id,
+        requisition_line.requisition_row.program_id.as_deref(),
+    )?;
 
     let (expected_delivery_date, requisition_line_datetime) = match (
         &requisition_line.requisition_row.expected_delivery_date,
@@ -161,6 +170,7 @@ impl SuggestedQuantityCalculation {
             maximum_stock_on_hand: from.requisition_line_row.average_monthly_consumption
                 * from.requisition_row.max_months_of_stock,
             suggested: from.requisition_line_row.suggested_quantity as u32,
+            consumption_forecast_method: ConsumptionForecastMethod::default(),
         }
     }
 }
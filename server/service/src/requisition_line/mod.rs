@@ -16,8 +16,9 @@ use self::{
         ResponseRequisitionStatsError,
     },
     response_requisition_line::{
-        update_response_requisition_line, UpdateResponseRequisitionLine,
-        UpdateResponseRequisitionLineError,
+        update_response_requisition_line, update_response_requisition_line_approval,
+        UpdateResponseRequisitionLine, UpdateResponseRequisitionLineApproval,
+        UpdateResponseRequisitionLineApprovalError, UpdateResponseRequisitionLineError,
     },
 };
 
@@ -74,6 +75,14 @@ pub trait RequisitionLineServiceTrait: Sync + Send {
         update_response_requisition_line(ctx, input)
     }
 
+    fn update_response_requisition_line_approval(
+        &self,
+        ctx: &ServiceContext,
+        input: UpdateResponseRequisitionLineApproval,
+    ) -> Result<RequisitionLine, UpdateResponseRequisitionLineApprovalError> {
+        update_response_requisition_line_approval(ctx, input)
+    }
+
     fn get_requisition_line_chart(
         &self,
         ctx: &ServiceContext,
@@ -5,7 +5,7 @@ use crate::{
 };
 
 use repository::{
-    requisition_row::{RequisitionStatus, RequisitionType},
+    requisition_row::{RequisitionRow, RequisitionStatus, RequisitionType},
     RepositoryError, RequisitionLine, RequisitionLineRow, RequisitionLineRowRepository,
     StorageConnection,
 };
@@ -16,6 +16,12 @@ pub struct UpdateRequestRequisitionLine {
     pub id: String,
     pub requested_quantity: Option<f64>,
     pub comment: Option<String>,
+    pub opening_balance: Option<f64>,
+    pub quantity_received: Option<f64>,
+    pub quantity_dispensed: Option<f64>,
+    pub losses_and_adjustments: Option<f64>,
+    pub closing_balance: Option<f64>,
+    pub days_out_of_stock: Option<i32>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -25,6 +31,11 @@ pub enum UpdateRequestRequisitionLineError {
     NotThisStoreRequisition,
     CannotEditRequisition,
     NotARequestRequisition,
+    /// opening balance + received - dispensed + losses and adjustments must equal closing balance
+    LmisBalanceMismatch,
+    DaysOutOfStockNegative,
+    /// Emergency orders can't request more than needed to reach the maximum months of stock
+    RequestedQuantityAboveEmergencyMaximum,
     UpdatedRequisitionLineDoesNotExist,
     RequisitionDoesNotExist,
     DatabaseError(RepositoryError),
@@ -39,8 +50,11 @@ pub fn update_request_requisition_line(
     let requisition_line = ctx
         .connection
         .transaction_sync(|connection| {
-            let requisition_row = validate(connection, &ctx.store_id, &input)?;
-            let updated_requisition_line_row = generate(requisition_row, input);
+            let (requisition_row, requisition_line_row) =
+                validate(connection, &ctx.store_id, &input)?;
+            let updated_requisition_line_row = generate(requisition_line_row, input.clone());
+            validate_lmis(&input, &updated_requisition_line_row)?;
+            validate_emergency_quantity(&requisition_row, &updated_requisition_line_row)?;
 
             RequisitionLineRowRepository::new(connection)
                 .upsert_one(&updated_requisition_line_row)?;
@@ -57,7 +71,7 @@ fn validate(
     connection: &StorageConnection,
     store_id: &str,
     input: &UpdateRequestRequisitionLine,
-) -> Result<RequisitionLineRow, OutError> {
+) -> Result<(RequisitionRow, RequisitionLineRow), OutError> {
     let requisition_line_row = check_requisition_line_exists(connection, &input.id)?
         .ok_or(OutError::RequisitionLineDoesNotExist)?
         .requisition_line_row;
@@ -78,7 +92,54 @@ fn validate(
         return Err(OutError::NotARequestRequisition);
     }
 
-    Ok(requisition_line_row)
+    Ok((requisition_row, requisition_line_row))
+}
+
+fn validate_lmis(
+    input: &UpdateRequestRequisitionLine,
+    updated: &RequisitionLineRow,
+) -> Result<(), OutError> {
+    if input.days_out_of_stock.map_or(false, |days| days < 0) {
+        return Err(OutError::DaysOutOfStockNegative);
+    }
+
+    let balance_updated = input.opening_balance.is_some()
+        || input.quantity_received.is_some()
+        || input.quantity_dispensed.is_some()
+        || input.losses_and_adjustments.is_some()
+        || input.closing_balance.is_some();
+    if !balance_updated {
+        return Ok(());
+    }
+
+    let calculated_closing_balance = updated.opening_balance.unwrap_or_default()
+        + updated.quantity_received.unwrap_or_default()
+        - updated.quantity_dispensed.unwrap_or_default()
+        + updated.losses_and_adjustments.unwrap_or_default();
+    if (calculated_closing_balance - updated.closing_balance.unwrap_or_default()).abs() > 0.0001 {
+        return Err(OutError::LmisBalanceMismatch);
+    }
+
+    Ok(())
+}
+
+fn validate_emergency_quantity(
+    requisition_row: &RequisitionRow,
+    updated: &RequisitionLineRow,
+) -> Result<(), OutError> {
+    if !requisition_row.is_emergency {
+        return Ok(());
+    }
+
+    let maximum_quantity = (updated.average_monthly_consumption
+        * requisition_row.max_months_of_stock
+        - updated.available_stock_on_hand)
+        .max(0.0);
+    if updated.requested_quantity > maximum_quantity {
+        return Err(OutError::RequestedQuantityAboveEmergencyMaximum);
+    }
+
+    Ok(())
 }
 
 fn generate(
@@ -87,11 +148,23 @@ fn generate(
         id: _,
         requested_quantity: updated_requested_quantity,
         comment: updated_comment,
+        opening_balance,
+        quantity_received,
+        quantity_dispensed,
+        losses_and_adjustments,
+        closing_balance,
+        days_out_of_stock,
     }: UpdateRequestRequisitionLine,
 ) -> RequisitionLineRow {
     inline_edit(&existing, |mut u| {
         u.requested_quantity = updated_requested_quantity.unwrap_or(u.requested_quantity);
         u.comment = updated_comment.or(u.comment);
+        u.opening_balance = opening_balance.or(u.opening_balance);
+        u.quantity_received = quantity_received.or(u.quantity_received);
+        u.quantity_dispensed = quantity_dispensed.or(u.quantity_dispensed);
+        u.losses_and_adjustments = losses_and_adjustments.or(u.losses_and_adjustments);
+        u.closing_balance = closing_balance.or(u.closing_balance);
+        u.days_out_of_stock = days_out_of_stock.or(u.days_out_of_stock);
         u
     })
 }
@@ -106,12 +179,13 @@ impl From<RepositoryError> for UpdateRequestRequisitionLineError {
 mod test {
     use repository::{
         mock::{
-            mock_full_draft_response_requisition_for_update_test,
+            mock_full_draft_response_requisition_for_update_test, mock_item_a, mock_name_b,
             mock_request_draft_requisition_calculation_test, mock_sent_request_requisition_line,
-            mock_store_a, mock_store_b, MockDataInserts,
+            mock_store_a, mock_store_b, MockData, MockDataInserts,
         },
-        test_db::setup_all,
-        RequisitionLineRowRepository,
+        requisition_row::{RequisitionRow, RequisitionStatus, RequisitionType},
+        test_db::{setup_all, setup_all_with_data},
+        RequisitionLineRow, RequisitionLineRowRepository,
     };
     use util::{inline_edit, inline_init};
 
@@ -171,6 +245,32 @@ mod test {
             Err(ServiceError::NotARequestRequisition)
         );
 
+        // LmisBalanceMismatch
+        assert_eq!(
+            service.update_request_requisition_line(
+                &context,
+                inline_init(|r: &mut UpdateRequestRequisitionLine| {
+                    r.id.clone_from(&mock_request_draft_requisition_calculation_test().lines[0].id);
+                    r.opening_balance = Some(10.0);
+                    r.quantity_received = Some(5.0);
+                    r.closing_balance = Some(20.0);
+                }),
+            ),
+            Err(ServiceError::LmisBalanceMismatch)
+        );
+
+        // DaysOutOfStockNegative
+        assert_eq!(
+            service.update_request_requisition_line(
+                &context,
+                inline_init(|r: &mut UpdateRequestRequisitionLine| {
+                    r.id.clone_from(&mock_request_draft_requisition_calculation_test().lines[0].id);
+                    r.days_out_of_stock = Some(-1);
+                }),
+            ),
+            Err(ServiceError::DaysOutOfStockNegative)
+        );
+
         // NotThisStoreRequisition
         context.store_id = mock_store_b().id;
         assert_eq!(
@@ -207,6 +307,12 @@ mod test {
                     id: test_line.id.clone(),
                     requested_quantity: Some(99.0),
                     comment: Some("comment".to_string()),
+                    opening_balance: Some(10.0),
+                    quantity_received: Some(20.0),
+                    quantity_dispensed: Some(15.0),
+                    losses_and_adjustments: Some(-5.0),
+                    closing_balance: Some(10.0),
+                    days_out_of_stock: Some(3),
                 },
             )
             .unwrap();
@@ -221,8 +327,80 @@ mod test {
             inline_edit(&test_line, |mut u| {
                 u.requested_quantity = 99.0;
                 u.comment = Some("comment".to_string());
+                u.opening_balance = Some(10.0);
+                u.quantity_received = Some(20.0);
+                u.quantity_dispensed = Some(15.0);
+                u.losses_and_adjustments = Some(-5.0);
+                u.closing_balance = Some(10.0);
+                u.days_out_of_stock = Some(3);
                 u
             })
         );
     }
+
+    #[actix_rt::test]
+    async fn update_emergency_request_requisition_line() {
+        fn emergency_requisition() -> RequisitionRow {
+            inline_init(|r: &mut RequisitionRow| {
+                r.id = "emergency_requisition".to_string();
+                r.name_link_id = mock_name_b().id;
+                r.store_id = mock_store_a().id;
+                r.r#type = RequisitionType::Request;
+                r.status = RequisitionStatus::Draft;
+                r.max_months_of_stock = 2.0;
+                r.is_emergency = true;
+            })
+        }
+
+        fn emergency_requisition_line() -> RequisitionLineRow {
+            inline_init(|r: &mut RequisitionLineRow| {
+                r.id = "emergency_requisition_line".to_string();
+                r.requisition_id = emergency_requisition().id;
+                r.item_link_id = mock_item_a().id;
+                r.average_monthly_consumption = 10.0;
+                r.available_stock_on_hand = 5.0;
+            })
+        }
+
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "update_emergency_request_requisition_line",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.requisitions = vec![emergency_requisition()];
+                r.requisition_lines = vec![emergency_requisition_line()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let context = service_provider
+            .context(mock_store_a().id, "".to_string())
+            .unwrap();
+        let service = service_provider.requisition_line_service;
+
+        // 10 AMC * 2 max months of stock - 5 available stock on hand = 15
+        assert_eq!(
+            service.update_request_requisition_line(
+                &context,
+                UpdateRequestRequisitionLine {
+                    id: emergency_requisition_line().id,
+                    requested_quantity: Some(16.0),
+                    ..Default::default()
+                },
+            ),
+            Err(ServiceError::RequestedQuantityAboveEmergencyMaximum)
+        );
+
+        let result = service
+            .update_request_requisition_line(
+                &context,
+                UpdateRequestRequisitionLine {
+                    id: emergency_requisition_line().id,
+                    requested_quantity: Some(15.0),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(result.requisition_line_row.requested_quantity, 15.0);
+    }
 }
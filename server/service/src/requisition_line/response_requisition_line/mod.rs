@@ -1,2 +1,5 @@
 mod update;
 pub use update::*;
+
+mod update_approval;
+pub use update_approval::*;
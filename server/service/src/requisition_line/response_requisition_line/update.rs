@@ -74,7 +74,7 @@ fn validate(
         check_requisition_row_exists(connection, &requisition_line_row.requisition_id)?
             .ok_or(OutError::RequisitionDoesNotExist)?;
 
-    if check_approval_status(&requisition_row) {
+    if check_approval_status(connection, &requisition_row)? {
         return Err(OutError::CannotEditRequisition);
     }
 
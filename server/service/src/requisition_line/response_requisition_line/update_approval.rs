@@ -0,0 +1,128 @@
+use crate::{
+    requisition::{approval::get_pending_approval, common::check_requisition_row_exists},
+    requisition_line::{common::check_requisition_line_exists, query::get_requisition_line},
+    service_provider::ServiceContext,
+};
+
+use repository::{
+    requisition_row::{RequisitionStatus, RequisitionType},
+    RepositoryError, RequisitionLine, RequisitionLineRow, RequisitionLineRowRepository,
+    StorageConnection,
+};
+use util::inline_edit;
+
+#[derive(Debug, PartialEq, Default)]
+pub struct UpdateResponseRequisitionLineApproval {
+    pub id: String,
+    pub approved_quantity: f64,
+    /// Required when the approved quantity is less than the requested quantity
+    pub approval_comment: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpdateResponseRequisitionLineApprovalError {
+    RequisitionLineDoesNotExist,
+    RequisitionDoesNotExist,
+    NotThisStoreRequisition,
+    NotAResponseRequisition,
+    CannotEditRequisition,
+    /// Not pending approval through an approval chain
+    NotPendingApproval,
+    /// User is not the approver of the current level, or already approved a previous level
+    UserCannotApproveLevel,
+    ApprovedQuantityBelowZero,
+    CommentRequiredForReduction,
+    UpdatedRequisitionLineDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = UpdateResponseRequisitionLineApprovalError;
+
+pub fn update_response_requisition_line_approval(
+    ctx: &ServiceContext,
+    input: UpdateResponseRequisitionLineApproval,
+) -> Result<RequisitionLine, OutError> {
+    let requisition_line = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let requisition_line_row = validate(connection, ctx, &input)?;
+            let updated_requisition_line_row = generate(requisition_line_row, input);
+
+            RequisitionLineRowRepository::new(connection)
+                .upsert_one(&updated_requisition_line_row)?;
+
+            get_requisition_line(ctx, &updated_requisition_line_row.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::UpdatedRequisitionLineDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(requisition_line)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    ctx: &ServiceContext,
+    input: &UpdateResponseRequisitionLineApproval,
+) -> Result<RequisitionLineRow, OutError> {
+    let requisition_line_row = check_requisition_line_exists(connection, &input.id)?
+        .ok_or(OutError::RequisitionLineDoesNotExist)?
+        .requisition_line_row;
+
+    let requisition_row =
+        check_requisition_row_exists(connection, &requisition_line_row.requisition_id)?
+            .ok_or(OutError::RequisitionDoesNotExist)?;
+
+    if requisition_row.store_id != ctx.store_id {
+        return Err(OutError::NotThisStoreRequisition);
+    }
+
+    if requisition_row.r#type != RequisitionType::Response {
+        return Err(OutError::NotAResponseRequisition);
+    }
+
+    if requisition_row.status != RequisitionStatus::New {
+        return Err(OutError::CannotEditRequisition);
+    }
+
+    let pending_approval =
+        get_pending_approval(connection, &requisition_row)?.ok_or(OutError::NotPendingApproval)?;
+
+    if !pending_approval.can_be_approved_by(&ctx.user_id) {
+        return Err(OutError::UserCannotApproveLevel);
+    }
+
+    if input.approved_quantity < 0.0 {
+        return Err(OutError::ApprovedQuantityBelowZero);
+    }
+
+    let has_comment = input
+        .approval_comment
+        .as_ref()
+        .map_or(false, |comment| !comment.trim().is_empty());
+    if input.approved_quantity < requisition_line_row.requested_quantity && !has_comment {
+        return Err(OutError::CommentRequiredForReduction);
+    }
+
+    Ok(requisition_line_row)
+}
+
+fn generate(
+    existing: RequisitionLineRow,
+    UpdateResponseRequisitionLineApproval {
+        id: _,
+        approved_quantity,
+        approval_comment,
+    }: UpdateResponseRequisitionLineApproval,
+) -> RequisitionLineRow {
+    inline_edit(&existing, |mut u| {
+        u.approved_quantity = approved_quantity;
+        u.approval_comment = approval_comment;
+        u
+    })
+}
+
+impl From<RepositoryError> for UpdateResponseRequisitionLineApprovalError {
+    fn from(error: RepositoryError) -> Self {
+        UpdateResponseRequisitionLineApprovalError::DatabaseError(error)
+    }
+}
@@ -0,0 +1,447 @@
+use super::import::{ReadSensorError, SensorFile, SensorFileImporter};
+use chrono::{Duration, Local, LocalResult, NaiveDateTime, TimeZone};
+use repository::SensorType;
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    fn to_celsius(&self, temperature: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => temperature,
+            TemperatureUnit::Fahrenheit => (temperature - 32.0) * 5.0 / 9.0,
+        }
+    }
+}
+
+/// Column mapping and format of a logger CSV export.
+///
+/// Columns are matched by the start of the header (case insensitive), e.g. `Temperature` matches
+/// `Temperature (°C)`. The header row is the first line containing both the datetime and
+/// temperature columns, any lines before it (logger details) are skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvImportConfig {
+    /// Type of the sensor created for a new serial
+    pub sensor_type: SensorType,
+    pub delimiter: char,
+    /// Serial of the sensor, required if the file doesn't include the serial (see `serial_label`)
+    pub serial: Option<String>,
+    /// Label of the serial in the lines before the header row, e.g. `Serial Number` for a
+    /// `Serial Number,1234567` line
+    pub serial_label: Option<String>,
+    pub datetime_column: String,
+    /// For files with the time in a separate column to the date
+    pub time_column: Option<String>,
+    /// Format of the datetime (date and time are joined with a space when in separate columns)
+    pub datetime_format: String,
+    pub temperature_column: String,
+    pub temperature_unit: TemperatureUnit,
+    /// Offset of the logger clock from UTC, the logger clock is assumed to be in the local time
+    /// of the server if not set (same as fridge-tags)
+    pub utc_offset_minutes: Option<i32>,
+}
+
+impl Default for CsvImportConfig {
+    /// Generic `Timestamp,Temperature` CSV, e.g. `2024-01-01 10:00:00,5.2`
+    fn default() -> Self {
+        Self {
+            sensor_type: SensorType::Csv,
+            delimiter: ',',
+            serial: None,
+            serial_label: None,
+            datetime_column: "Timestamp".to_string(),
+            time_column: None,
+            datetime_format: "%Y-%m-%d %H:%M:%S".to_string(),
+            temperature_column: "Temperature".to_string(),
+            temperature_unit: TemperatureUnit::Celsius,
+            utc_offset_minutes: None,
+        }
+    }
+}
+
+impl CsvImportConfig {
+    /// LogTag Analyzer CSV export, logger details followed by `Date,Time,Temperature` rows
+    pub fn log_tag() -> Self {
+        Self {
+            sensor_type: SensorType::LogTag,
+            serial_label: Some("Serial Number".to_string()),
+            datetime_column: "Date".to_string(),
+            time_column: Some("Time".to_string()),
+            datetime_format: "%d/%m/%Y %H:%M:%S".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Preset with the fields set in `overrides` replaced
+    pub fn with_overrides(self, overrides: CsvImportConfigOverrides) -> Self {
+        let CsvImportConfigOverrides {
+            delimiter,
+            serial,
+            serial_label,
+            datetime_column,
+            time_column,
+            datetime_format,
+            temperature_column,
+            temperature_unit,
+            utc_offset_minutes,
+        } = overrides;
+
+        Self {
+            sensor_type: self.sensor_type,
+            delimiter: delimiter.unwrap_or(self.delimiter),
+            serial: serial.or(self.serial),
+            serial_label: serial_label.or(self.serial_label),
+            datetime_column: datetime_column.unwrap_or(self.datetime_column),
+            time_column: time_column.or(self.time_column),
+            datetime_format: datetime_format.unwrap_or(self.datetime_format),
+            temperature_column: temperature_column.unwrap_or(self.temperature_column),
+            temperature_unit: temperature_unit.unwrap_or(self.temperature_unit),
+            utc_offset_minutes: utc_offset_minutes.or(self.utc_offset_minutes),
+        }
+    }
+}
+
+/// Fields of a `CsvImportConfig` preset that can be changed for an upload, see
+/// `CsvImportConfig::with_overrides`. The sensor type always comes from the preset.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CsvImportConfigOverrides {
+    pub delimiter: Option<char>,
+    pub serial: Option<String>,
+    pub serial_label: Option<String>,
+    pub datetime_column: Option<String>,
+    pub time_column: Option<String>,
+    pub datetime_format: Option<String>,
+    pub temperature_column: Option<String>,
+    pub temperature_unit: Option<TemperatureUnit>,
+    pub utc_offset_minutes: Option<i32>,
+}
+
+/// Temperature logs from a CSV file, breaches are detected from the logs once integrated
+pub struct CsvImporter {
+    pub config: CsvImportConfig,
+}
+
+impl SensorFileImporter for CsvImporter {
+    fn sensor_type(&self) -> SensorType {
+        self.config.sensor_type.clone()
+    }
+
+    fn read_file(&self, file: &Path) -> Result<SensorFile, ReadSensorError> {
+        let content = fs::read_to_string(file)
+            .map_err(|error| ReadSensorError::StringError(error.to_string()))?;
+
+        self.parse(&content)
+    }
+}
+
+struct Columns {
+    datetime: usize,
+    time: Option<usize>,
+    temperature: usize,
+}
+
+impl CsvImporter {
+    fn parse(&self, content: &str) -> Result<SensorFile, ReadSensorError> {
+        let config = &self.config;
+        let mut lines = content.trim_start_matches('\u{feff}').lines().enumerate();
+        let mut serial = config.serial.clone();
+
+        let columns = loop {
+            let Some((_, line)) = lines.next() else {
+                return Err(error(format!(
+                    "Header with {} and {} columns not found",
+                    config.datetime_column, config.temperature_column
+                )));
+            };
+            let cells = split_line(line, config.delimiter);
+
+            if let (None, Some(serial_label)) = (&serial, &config.serial_label) {
+                if cells
+                    .first()
+                    .is_some_and(|cell| matches_column(cell, serial_label))
+                {
+                    serial = cells.get(1).filter(|cell| !cell.is_empty()).cloned();
+                    continue;
+                }
+            }
+
+            let find = |column: &str| cells.iter().position(|cell| matches_column(cell, column));
+            let (Some(datetime), Some(temperature)) = (
+                find(&config.datetime_column),
+                find(&config.temperature_column),
+            ) else {
+                continue;
+            };
+            let time = match &config.time_column {
+                Some(time_column) => Some(
+                    find(time_column)
+                        .ok_or_else(|| error(format!("Column {time_column} not found")))?,
+                ),
+                None => None,
+            };
+            break Columns {
+                datetime,
+                time,
+                temperature,
+            };
+        };
+
+        let mut logs = Vec::new();
+        for (index, line) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line_number = index + 1;
+            let cells = split_line(line, config.delimiter);
+            let cell = |column: usize| cells.get(column).map(String::as_str).unwrap_or_default();
+
+            let datetime = match columns.time {
+                Some(time) => format!("{} {}", cell(columns.datetime), cell(time)),
+                None => cell(columns.datetime).to_string(),
+            };
+            let datetime = NaiveDateTime::parse_from_str(&datetime, &config.datetime_format)
+                .map_err(|_| error(format!("Invalid datetime {datetime} on line {line_number}")))?;
+
+            let temperature = cell(columns.temperature);
+            let temperature: f64 = temperature
+                .trim_end_matches(|c: char| c.is_alphabetic() || c == '°')
+                .trim()
+                .parse()
+                .map_err(|_| {
+                    error(format!(
+                        "Invalid temperature {temperature} on line {line_number}"
+                    ))
+                })?;
+
+            logs.push(temperature_sensor::TemperatureLog {
+                timestamp: self.to_utc(datetime)?,
+                temperature: config.temperature_unit.to_celsius(temperature),
+            });
+        }
+
+        if logs.is_empty() {
+            return Err(error("No temperature logs found".to_string()));
+        }
+        logs.sort_by_key(|log| log.timestamp);
+
+        // Logs can't be matched to a sensor without its serial
+        let Some(serial) = serial else {
+            let location = match &config.serial_label {
+                Some(serial_label) => format!(" ({serial_label} line)"),
+                None => String::new(),
+            };
+            return Err(error(format!(
+                "Sensor serial not found{location}, please provide the serial of the sensor"
+            )));
+        };
+        Ok(SensorFile {
+            name: serial.clone(),
+            serial,
+            log_interval: logs
+                .get(1)
+                .map(|second_log| second_log.timestamp - logs[0].timestamp),
+            last_connected_timestamp: logs.last().map(|log| log.timestamp),
+            configs: Vec::new(),
+            breaches: Vec::new(),
+            logs,
+        })
+    }
+
+    fn to_utc(&self, datetime: NaiveDateTime) -> Result<NaiveDateTime, ReadSensorError> {
+        if let Some(offset_minutes) = self.config.utc_offset_minutes {
+            return Ok(datetime - Duration::minutes(offset_minutes as i64));
+        }
+
+        match Local.from_local_datetime(&datetime) {
+            LocalResult::None => Err(anyhow::anyhow!("Cannot convert to local timestamp").into()),
+            LocalResult::Single(r) => Ok(r.naive_utc()),
+            LocalResult::Ambiguous(r, _) => Ok(r.naive_utc()),
+        }
+    }
+}
+
+fn error(message: String) -> ReadSensorError {
+    ReadSensorError::StringError(message)
+}
+
+fn matches_column(cell: &str, column: &str) -> bool {
+    cell.to_lowercase().starts_with(&column.to_lowercase())
+}
+
+/// Splits a CSV line into trimmed cells, delimiters in quoted cells are ignored
+fn split_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => {
+                cells.push(cell.trim().to_string());
+                cell.clear();
+            }
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+
+    cells
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CsvImportConfig, CsvImportConfigOverrides, CsvImporter, TemperatureUnit};
+    use crate::{
+        sensor::import::import_sensor_file,
+        test_helpers::{setup_all_and_service_provider, ServiceTestContext},
+    };
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+    use repository::{
+        mock::{mock_store_a, MockDataInserts},
+        EqualFilter, SensorFilter, SensorRepository, SensorType, TemperatureLogFilter,
+        TemperatureLogRepository,
+    };
+    use std::fs;
+
+    fn datetime(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn csv_import_config_overrides() {
+        let overrides: CsvImportConfigOverrides =
+            serde_json::from_str(r#"{"serial": "A1234", "temperatureUnit": "fahrenheit"}"#)
+                .unwrap();
+        assert_eq!(
+            CsvImportConfig::log_tag().with_overrides(overrides),
+            CsvImportConfig {
+                serial: Some("A1234".to_string()),
+                temperature_unit: TemperatureUnit::Fahrenheit,
+                ..CsvImportConfig::log_tag()
+            }
+        );
+
+        // Sensor type is set by the format
+        assert!(
+            serde_json::from_str::<CsvImportConfigOverrides>(r#"{"sensorType": "CSV"}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn parse_log_tag_csv() {
+        let importer = CsvImporter {
+            config: CsvImportConfig {
+                temperature_unit: TemperatureUnit::Fahrenheit,
+                utc_offset_minutes: Some(60),
+                ..CsvImportConfig::log_tag()
+            },
+        };
+        let content = "\u{feff}Product,LogTag TRIX-8\n\
+                       Serial Number,\"A1234\"\n\
+                       \n\
+                       Date,Time,Temperature (°F)\n\
+                       01/01/2024,10:10:00,50.0\n\
+                       01/01/2024,10:00:00,41.0°F\n";
+
+        let result = importer.parse(content).unwrap();
+
+        assert_eq!(result.serial, "A1234");
+        assert_eq!(result.log_interval, Some(Duration::minutes(10)));
+        assert_eq!(result.last_connected_timestamp, Some(datetime(9, 10)));
+        let logs: Vec<(NaiveDateTime, f64)> = result
+            .logs
+            .iter()
+            .map(|log| (log.timestamp, log.temperature))
+            .collect();
+        assert_eq!(logs, vec![(datetime(9, 0), 5.0), (datetime(9, 10), 10.0)]);
+
+        // Errors
+        assert!(importer.parse("Date,Temperature\n").is_err());
+        assert!(importer
+            .parse("Date,Time,Temperature\n01/01/2024,10:00:00,warm\n")
+            .is_err());
+        assert!(importer.parse("Date,Time,Temperature\n").is_err());
+        // Serial is not in the file or config
+        assert!(importer
+            .parse("Date,Time,Temperature\n01/01/2024,10:00:00,41.0\n")
+            .is_err());
+    }
+
+    #[actix_rt::test]
+    async fn import_csv_sensor_file() {
+        let ServiceTestContext {
+            connection,
+            service_context,
+            ..
+        } = setup_all_and_service_provider(
+            "import_csv_sensor_file",
+            MockDataInserts::none().names().stores(),
+        )
+        .await;
+        let importer = CsvImporter {
+            config: CsvImportConfig {
+                delimiter: ';',
+                serial: Some("csv_sensor".to_string()),
+                utc_offset_minutes: Some(0),
+                ..Default::default()
+            },
+        };
+        let directory = tempfile::tempdir().unwrap();
+        let file = directory.path().join("upload_id_csv_sensor.csv");
+
+        fs::write(
+            &file,
+            "Timestamp;Temperature\n\
+             2024-01-01 10:00:00;5.0\n\
+             2024-01-01 10:15:00;9.5\n",
+        )
+        .unwrap();
+        import_sensor_file(&service_context, &mock_store_a().id, &importer, &file).unwrap();
+
+        let sensor = SensorRepository::new(&connection)
+            .query_by_filter(SensorFilter::new().serial(EqualFilter::equal_to("csv_sensor")))
+            .unwrap()
+            .pop()
+            .unwrap()
+            .sensor_row;
+        assert_eq!(sensor.r#type, SensorType::Csv);
+        assert_eq!(sensor.store_id, mock_store_a().id);
+        assert_eq!(sensor.log_interval, Some(15 * 60));
+        assert_eq!(sensor.last_connection_datetime, Some(datetime(10, 15)));
+
+        // Logs already uploaded are not added again
+        fs::write(
+            &file,
+            "Timestamp;Temperature\n\
+             2024-01-01 10:00:00;5.0\n\
+             2024-01-01 10:15:00;9.5\n\
+             2024-01-01 10:30:00;4.0\n",
+        )
+        .unwrap();
+        import_sensor_file(&service_context, &mock_store_a().id, &importer, &file).unwrap();
+
+        let logs = TemperatureLogRepository::new(&connection)
+            .query_by_filter(
+                TemperatureLogFilter::new()
+                    .sensor(SensorFilter::new().id(EqualFilter::equal_to(&sensor.id))),
+            )
+            .unwrap();
+        assert_eq!(logs.len(), 3);
+    }
+}
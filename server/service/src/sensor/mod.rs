@@ -10,6 +10,8 @@ use crate::{service_provider::ServiceContext, SingleRecordError};
 use repository::{PaginationOption, Sensor, SensorFilter, SensorSort};
 
 pub mod berlinger;
+pub mod csv;
+pub mod import;
 pub mod insert;
 pub mod query;
 pub mod update;
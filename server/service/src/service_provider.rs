@@ -7,6 +7,7 @@ use crate::{
     clinician::{ClinicianService, ClinicianServiceTrait},
     cold_chain::{ColdChainService, ColdChainServiceTrait},
     currency::{CurrencyService, CurrencyServiceTrait},
+    cycle_count::{CycleCountService, CycleCountServiceTrait},
     dashboard::{
         invoice_count::{InvoiceCountService, InvoiceCountServiceTrait},
         item_count::{ItemCountServiceTrait, ItemServiceCount},
@@ -20,6 +21,7 @@ use crate::{
         document_service::{DocumentService, DocumentServiceTrait},
         form_schema_service::{FormSchemaService, FormSchemaServiceTrait},
     },
+    expiry_management::{ExpiryManagementService, ExpiryManagementServiceTrait},
     invoice::{InvoiceService, InvoiceServiceTrait},
     invoice_line::{InvoiceLineService, InvoiceLineServiceTrait},
     item_stats::{ItemStatsService, ItemStatsServiceTrait},
@@ -29,9 +31,13 @@ use crate::{
     master_list::{MasterListService, MasterListServiceTrait},
     missing_program::create_missing_master_list_and_program,
     name::{NameService, NameServiceTrait},
+    notification::{NotificationService, NotificationServiceTrait},
     pack_variant::PackVariantServiceTrait,
     plugin_data::{PluginDataService, PluginDataServiceTrait},
-    processors::ProcessorsTrigger,
+    processors::{
+        changelog_consumer::status::{ChangelogConsumerService, ChangelogConsumerServiceTrait},
+        ProcessorsTrigger,
+    },
     program::ProgramServiceTrait,
     programs::{
         contact_trace::{ContactTraceService, ContactTraceServiceTrait},
@@ -41,17 +47,22 @@ use crate::{
         program_event::{ProgramEventService, ProgramEventServiceTrait},
     },
     repack::{RepackService, RepackServiceTrait},
-    report::report_service::{ReportService, ReportServiceTrait},
+    report::{
+        report_schedule::{ReportScheduleService, ReportScheduleServiceTrait},
+        report_service::{ReportService, ReportServiceTrait},
+    },
     requisition::{RequisitionService, RequisitionServiceTrait},
     requisition_line::{RequisitionLineService, RequisitionLineServiceTrait},
     sensor::{SensorService, SensorServiceTrait},
     settings_service::{SettingsService, SettingsServiceTrait},
     stock_line::{StockLineService, StockLineServiceTrait},
+    stock_out::{StockOutService, StockOutServiceTrait},
     stocktake::{StocktakeService, StocktakeServiceTrait},
     stocktake_line::{StocktakeLineService, StocktakeLineServiceTrait},
     store::{get_store, get_stores},
     sync::{
         site_info::{SiteInfoService, SiteInfoTrait},
+        sync_buffer_error::{SyncBufferErrorService, SyncBufferErrorServiceTrait},
         sync_status::status::{SyncStatusService, SyncStatusTrait},
         synchroniser_driver::{SiteIsInitialisedTrigger, SyncTrigger},
     },
@@ -61,8 +72,8 @@ use crate::{
     ListError, ListResult,
 };
 use repository::{
-    PaginationOption, RepositoryError, StorageConnection, StorageConnectionManager, Store,
-    StoreFilter, StoreSort,
+    AuditContext, PaginationOption, RepositoryError, StorageConnection, StorageConnectionManager,
+    Store, StoreFilter, StoreSort,
 };
 
 pub struct ServiceProvider {
@@ -81,6 +92,7 @@ pub struct ServiceProvider {
     pub master_list_service: Box<dyn MasterListServiceTrait>,
     pub stocktake_service: Box<dyn StocktakeServiceTrait>,
     pub stocktake_line_service: Box<dyn StocktakeLineServiceTrait>,
+    pub cycle_count_service: Box<dyn CycleCountServiceTrait>,
     pub invoice_line_service: Box<dyn InvoiceLineServiceTrait>,
     pub requisition_service: Box<dyn RequisitionServiceTrait>,
     pub requisition_line_service: Box<dyn RequisitionLineServiceTrait>,
@@ -91,13 +103,18 @@ pub struct ServiceProvider {
     pub stock_expiry_count_service: Box<dyn StockExpiryCountServiceTrait>,
     pub item_count_service: Box<dyn ItemCountServiceTrait>,
     pub requisition_count_service: Box<dyn RequisitionCountServiceTrait>,
+    pub expiry_management_service: Box<dyn ExpiryManagementServiceTrait>,
     // Stock stats
     pub item_stats_service: Box<dyn ItemStatsServiceTrait>,
+    pub stock_out_service: Box<dyn StockOutServiceTrait>,
     // Stock
     pub stock_line_service: Box<dyn StockLineServiceTrait>,
     pub repack_service: Box<dyn RepackServiceTrait>,
     // Reports
     pub report_service: Box<dyn ReportServiceTrait>,
+    pub report_schedule_service: Box<dyn ReportScheduleServiceTrait>,
+    // Notifications
+    pub notification_service: Box<dyn NotificationServiceTrait>,
 
     // Document
     pub document_service: Box<dyn DocumentServiceTrait>,
@@ -116,6 +133,9 @@ pub struct ServiceProvider {
     // Sync
     pub site_info_service: Box<dyn SiteInfoTrait>,
     pub sync_status_service: Box<dyn SyncStatusTrait>,
+    // Processors
+    pub changelog_consumer_service: Box<dyn ChangelogConsumerServiceTrait>,
+    pub sync_buffer_error_service: Box<dyn SyncBufferErrorServiceTrait>,
     // Triggers
     processors_trigger: ProcessorsTrigger,
     pub sync_trigger: SyncTrigger,
@@ -183,14 +203,19 @@ impl ServiceProvider {
             requisition_count_service: Box::new(RequisitionCountService {}),
             invoice_service: Box::new(InvoiceService {}),
             stock_expiry_count_service: Box::new(StockExpiryServiceCount {}),
+            expiry_management_service: Box::new(ExpiryManagementService),
             stocktake_service: Box::new(StocktakeService {}),
             stocktake_line_service: Box::new(StocktakeLineService {}),
+            cycle_count_service: Box::new(CycleCountService),
             requisition_service: Box::new(RequisitionService {}),
             requisition_line_service: Box::new(RequisitionLineService {}),
             item_stats_service: Box::new(ItemStatsService {}),
+            stock_out_service: Box::new(StockOutService {}),
             clinician_service: Box::new(ClinicianService {}),
             general_service: Box::new(GeneralService {}),
             report_service: Box::new(ReportService {}),
+            report_schedule_service: Box::new(ReportScheduleService {}),
+            notification_service: Box::new(NotificationService),
             settings: Box::new(SettingsService),
             document_service: Box::new(DocumentService {}),
             document_registry_service: Box::new(DocumentRegistryService {}),
@@ -203,6 +228,8 @@ impl ServiceProvider {
             app_data_service: Box::new(AppDataService::new(app_data_folder)),
             site_info_service: Box::new(SiteInfoService),
             sync_status_service: Box::new(SyncStatusService),
+            changelog_consumer_service: Box::new(ChangelogConsumerService),
+            sync_buffer_error_service: Box::new(SyncBufferErrorService),
             processors_trigger,
             sync_trigger,
             site_is_initialised_trigger,
@@ -238,13 +265,21 @@ impl ServiceProvider {
         })
     }
 
+    /// Changes made with a store context are recorded in the audit log
     pub fn context(
         &self,
         store_id: String,
         user_id: String,
     ) -> Result<ServiceContext, RepositoryError> {
+        let mut connection = self.connection()?;
+        if !store_id.is_empty() {
+            connection.set_audit_context(AuditContext {
+                store_id: store_id.clone(),
+                user_id: (!user_id.is_empty()).then(|| user_id.clone()),
+            });
+        }
         Ok(ServiceContext {
-            connection: self.connection()?,
+            connection,
             processors_trigger: self.processors_trigger.clone(),
             user_id,
             store_id,
@@ -10,6 +10,8 @@ pub struct Settings {
     pub database: DatabaseSettings,
     pub sync: Option<SyncSettings>,
     pub logging: Option<LoggingSettings>,
+    pub cold_chain: Option<ColdChainSettings>,
+    pub notifications: Option<NotificationSettings>,
 }
 
 #[derive(serde::Deserialize, Clone)]
@@ -40,6 +42,33 @@ impl ServerSettings {
     }
 }
 
+#[derive(serde::Deserialize, Clone)]
+pub struct ColdChainSettings {
+    /// Temperature logs older than this are aggregated into hourly rows, logs in breaches are kept
+    pub temperature_log_retention_days: u32,
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct NotificationSettings {
+    /// Required for email notifications
+    pub smtp: Option<SmtpSettings>,
+    /// Stock expiring within this number of days is notified, defaults to 30
+    pub stock_expiry_days: Option<u32>,
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct SmtpSettings {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Sender of notification emails, e.g. "oMSupply <noreply@example.org>"
+    pub from: String,
+    /// Connect without STARTTLS, only meant for local test servers
+    #[serde(default)]
+    pub danger_allow_insecure: bool,
+}
+
 pub fn is_develop() -> bool {
     // debug_assertions is the recommended way to check if we are in 'dev' mode
     cfg!(debug_assertions)
@@ -23,6 +23,7 @@ const STATIC_FILE_DIR: &str = "static_files";
 pub enum StaticFileCategory {
     Temporary,
     SyncFile(String, String), // Files to be synced (Table Name, Record Id)
+    ScheduledReport,          // Reports generated by a report schedule, kept permanently
 }
 
 impl StaticFileCategory {
@@ -32,6 +33,7 @@ impl StaticFileCategory {
             StaticFileCategory::SyncFile(table_name, record_id) => {
                 PathBuf::from("sync_files").join(table_name).join(record_id)
             }
+            StaticFileCategory::ScheduledReport => PathBuf::from("scheduled_reports"),
         }
     }
 }
@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use repository::{
+    ConsumptionFilter, ConsumptionRepository, DateFilter, DatetimeFilter, EqualFilter,
+    RepositoryError, StockMovementFilter, StockMovementRepository, StorageConnection,
+};
+use util::date_with_days_offset;
+
+use crate::service_provider::ServiceContext;
+
+/// Longest date range (in days) stock outs can be calculated for, daily stock on hand is
+/// calculated for every item and day
+pub const MAX_DATE_RANGE_DAYS: i64 = 366;
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct StockOutFilter {
+    pub item_id: Option<EqualFilter<String>>,
+}
+
+impl StockOutFilter {
+    pub fn new() -> StockOutFilter {
+        Self::default()
+    }
+
+    pub fn item_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.item_id = Some(filter);
+        self
+    }
+}
+
+/// Stock on hand at the end of the day
+#[derive(Clone, Debug, PartialEq)]
+pub struct DailyStockOnHand {
+    pub date: NaiveDate,
+    pub stock_on_hand: f64,
+}
+
+/// Consecutive days with no stock on hand, `to` is inclusive
+#[derive(Clone, Debug, PartialEq)]
+pub struct StockOutPeriod {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemStockOut {
+    pub item_id: String,
+    pub store_id: String,
+    pub total_days: u32,
+    pub days_out_of_stock: u32,
+    pub stock_out_periods: Vec<StockOutPeriod>,
+    pub daily_stock_on_hand: Vec<DailyStockOnHand>,
+    /// Consumption recorded in the date range
+    pub consumption: f64,
+    /// Consumption extrapolated to the whole date range from the days the item was in stock,
+    /// same as consumption if the item was never in stock
+    pub adjusted_consumption: f64,
+}
+
+impl ItemStockOut {
+    pub fn days_in_stock(&self) -> u32 {
+        self.total_days.saturating_sub(self.days_out_of_stock)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum StockOutError {
+    /// From date is after to date
+    InvalidDateRange,
+    /// Date range is longer than `MAX_DATE_RANGE_DAYS`
+    DateRangeTooLong,
+    DatabaseError(RepositoryError),
+}
+
+impl From<RepositoryError> for StockOutError {
+    fn from(error: RepositoryError) -> Self {
+        StockOutError::DatabaseError(error)
+    }
+}
+
+pub trait StockOutServiceTrait: Sync + Send {
+    /// Days out of stock for items in a store, between `from_date` and `to_date` (inclusive, at
+    /// most `MAX_DATE_RANGE_DAYS`). Only items that had stock movements up to `to_date` are
+    /// included
+    fn get_stock_outs(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        from_date: NaiveDate,
+        to_date: NaiveDate,
+        filter: Option<StockOutFilter>,
+    ) -> Result<Vec<ItemStockOut>, StockOutError> {
+        get_stock_outs(ctx, store_id, from_date, to_date, filter)
+    }
+}
+
+pub struct StockOutService {}
+impl StockOutServiceTrait for StockOutService {}
+
+pub fn get_stock_outs(
+    ctx: &ServiceContext,
+    store_id: &str,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+    filter: Option<StockOutFilter>,
+) -> Result<Vec<ItemStockOut>, StockOutError> {
+    if from_date > to_date {
+        return Err(StockOutError::InvalidDateRange);
+    }
+    if (to_date - from_date).num_days() + 1 > MAX_DATE_RANGE_DAYS {
+        return Err(StockOutError::DateRangeTooLong);
+    }
+    let item_id_filter = filter.and_then(|f| f.item_id);
+
+    let daily_stock_on_hand = get_daily_stock_on_hand(
+        &ctx.connection,
+        store_id,
+        item_id_filter.clone(),
+        from_date,
+        to_date,
+    )?;
+
+    let consumption_filter = ConsumptionFilter {
+        item_id: item_id_filter,
+        store_id: Some(EqualFilter::equal_to(store_id)),
+        date: Some(DateFilter::date_range(&from_date, &to_date)),
+    };
+    let mut consumption_by_item: HashMap<String, f64> = HashMap::new();
+    for row in ConsumptionRepository::new(&ctx.connection).query(Some(consumption_filter))? {
+        *consumption_by_item.entry(row.item_id).or_insert(0.0) += row.quantity;
+    }
+
+    let mut result: Vec<ItemStockOut> = daily_stock_on_hand
+        .into_iter()
+        .map(|(item_id, daily_stock_on_hand)| {
+            let consumption = consumption_by_item.get(&item_id).copied().unwrap_or(0.0);
+            generate_item_stock_out(store_id, item_id, consumption, daily_stock_on_hand)
+        })
+        .collect();
+    result.sort_by(|a, b| a.item_id.cmp(&b.item_id));
+
+    Ok(result)
+}
+
+fn generate_item_stock_out(
+    store_id: &str,
+    item_id: String,
+    consumption: f64,
+    daily_stock_on_hand: Vec<DailyStockOnHand>,
+) -> ItemStockOut {
+    let mut stock_out_periods: Vec<StockOutPeriod> = Vec::new();
+    let mut days_out_of_stock = 0;
+
+    for day in daily_stock_on_hand
+        .iter()
+        .filter(|day| is_out_of_stock(day))
+    {
+        days_out_of_stock += 1;
+        match stock_out_periods.last_mut() {
+            Some(period) if date_with_days_offset(&period.to, 1) == day.date => {
+                period.to = day.date
+            }
+            _ => stock_out_periods.push(StockOutPeriod {
+                from: day.date,
+                to: day.date,
+            }),
+        }
+    }
+
+    let total_days = daily_stock_on_hand.len() as u32;
+    let days_in_stock = total_days - days_out_of_stock;
+    let adjusted_consumption = if days_in_stock == 0 {
+        consumption
+    } else {
+        consumption * total_days as f64 / days_in_stock as f64
+    };
+
+    ItemStockOut {
+        item_id,
+        store_id: store_id.to_string(),
+        total_days,
+        days_out_of_stock,
+        stock_out_periods,
+        daily_stock_on_hand,
+        consumption,
+        adjusted_consumption,
+    }
+}
+
+pub fn is_out_of_stock(day: &DailyStockOnHand) -> bool {
+    day.stock_on_hand <= 0.0
+}
+
+/// Reconstructs end of day stock on hand from stock movements, for every day between
+/// `from_date` and `to_date` (inclusive), grouped by item id.
+/// Only items with stock movements up to `to_date` are returned
+pub fn get_daily_stock_on_hand(
+    connection: &StorageConnection,
+    store_id: &str,
+    item_id_filter: Option<EqualFilter<String>>,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+) -> Result<HashMap<String, Vec<DailyStockOnHand>>, RepositoryError> {
+    let start_of_next_day = date_with_days_offset(&to_date, 1)
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let filter = StockMovementFilter {
+        item_id: item_id_filter,
+        store_id: Some(EqualFilter::equal_to(store_id)),
+        datetime: Some(DatetimeFilter::before_or_equal_to(start_of_next_day)),
+    };
+
+    let mut daily_movements_by_item: HashMap<String, HashMap<NaiveDate, f64>> = HashMap::new();
+    for row in StockMovementRepository::new(connection).query(Some(filter))? {
+        *daily_movements_by_item
+            .entry(row.item_id)
+            .or_default()
+            .entry(row.datetime.date())
+            .or_insert(0.0) += row.quantity;
+    }
+
+    let result = daily_movements_by_item
+        .into_iter()
+        .map(|(item_id, daily_movements)| {
+            let mut stock_on_hand = daily_movements
+                .iter()
+                .filter(|(date, _)| **date < from_date)
+                .fold(0.0, |sum, (_, quantity)| sum + quantity);
+
+            let mut daily_stock_on_hand = Vec::new();
+            let mut date = from_date;
+            while date <= to_date {
+                stock_on_hand += daily_movements.get(&date).copied().unwrap_or_default();
+                daily_stock_on_hand.push(DailyStockOnHand {
+                    date,
+                    stock_on_hand,
+                });
+                date = date_with_days_offset(&date, 1);
+            }
+
+            (item_id, daily_stock_on_hand)
+        })
+        .collect();
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::{mock_item_a, mock_store_a, MockData, MockDataInserts},
+        test_db::setup_all_with_data,
+        EqualFilter, InvoiceLineRow, InvoiceLineType, InvoiceRow, InvoiceStatus, InvoiceType,
+    };
+    use util::inline_init;
+
+    use crate::{service_provider::ServiceProvider, stock_out::StockOutError};
+
+    use super::{StockOutFilter, StockOutPeriod, MAX_DATE_RANGE_DAYS};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    fn invoice(id: &str, r#type: InvoiceType, day: u32) -> InvoiceRow {
+        inline_init(|r: &mut InvoiceRow| {
+            r.id = id.to_string();
+            r.store_id = mock_store_a().id;
+            r.name_link_id = "name_store_b".to_string();
+            r.r#type = r#type;
+            r.status = InvoiceStatus::Verified;
+            r.picked_datetime = Some(date(day).and_hms_opt(12, 0, 0).unwrap());
+            r.delivered_datetime = Some(date(day).and_hms_opt(12, 0, 0).unwrap());
+            r.verified_datetime = Some(date(day).and_hms_opt(12, 0, 0).unwrap());
+        })
+    }
+
+    fn invoice_line(
+        invoice_id: &str,
+        r#type: InvoiceLineType,
+        number_of_packs: f64,
+    ) -> InvoiceLineRow {
+        inline_init(|r: &mut InvoiceLineRow| {
+            r.id = format!("{}_line", invoice_id);
+            r.invoice_id = invoice_id.to_string();
+            r.item_link_id = mock_item_a().id;
+            r.r#type = r#type;
+            r.pack_size = 1.0;
+            r.number_of_packs = number_of_packs;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn stock_outs() {
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "stock_outs",
+            MockDataInserts::none().names().stores().units().items(),
+            MockData {
+                invoices: vec![
+                    // Received 10 before the date range
+                    invoice("inbound_1", InvoiceType::InboundShipment, 1),
+                    // Issued 10 on the 3rd, out of stock from 3rd to 5th
+                    invoice("outbound_1", InvoiceType::OutboundShipment, 3),
+                    // Received 10 on the 6th
+                    invoice("inbound_2", InvoiceType::InboundShipment, 6),
+                    // Issued 10 on the 9th, out of stock from 9th to 10th
+                    invoice("outbound_2", InvoiceType::OutboundShipment, 9),
+                ],
+                invoice_lines: vec![
+                    invoice_line("inbound_1", InvoiceLineType::StockIn, 10.0),
+                    invoice_line("outbound_1", InvoiceLineType::StockOut, 10.0),
+                    invoice_line("inbound_2", InvoiceLineType::StockIn, 10.0),
+                    invoice_line("outbound_2", InvoiceLineType::StockOut, 10.0),
+                ],
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let context = service_provider
+            .context(mock_store_a().id, "".to_string())
+            .unwrap();
+        let service = service_provider.stock_out_service;
+
+        assert_eq!(
+            service.get_stock_outs(&context, &mock_store_a().id, date(10), date(2), None),
+            Err(StockOutError::InvalidDateRange)
+        );
+        let too_long_to_date = date(1) + chrono::Duration::days(MAX_DATE_RANGE_DAYS);
+        assert_eq!(
+            service.get_stock_outs(
+                &context,
+                &mock_store_a().id,
+                date(1),
+                too_long_to_date,
+                None
+            ),
+            Err(StockOutError::DateRangeTooLong)
+        );
+
+        let result = service
+            .get_stock_outs(
+                &context,
+                &mock_store_a().id,
+                date(2),
+                date(10),
+                Some(StockOutFilter::new().item_id(EqualFilter::equal_to(&mock_item_a().id))),
+            )
+            .unwrap();
+
+        assert_eq!(result.len(), 1);
+        let stock_out = &result[0];
+        assert_eq!(stock_out.total_days, 9);
+        assert_eq!(stock_out.days_out_of_stock, 5);
+        assert_eq!(stock_out.days_in_stock(), 4);
+        assert_eq!(
+            stock_out.stock_out_periods,
+            vec![
+                StockOutPeriod {
+                    from: date(3),
+                    to: date(5)
+                },
+                StockOutPeriod {
+                    from: date(9),
+                    to: date(10)
+                }
+            ]
+        );
+        assert_eq!(stock_out.consumption, 20.0);
+        assert_eq!(stock_out.adjusted_consumption, 20.0 * 9.0 / 4.0);
+    }
+}
@@ -0,0 +1,371 @@
+use chrono::Utc;
+use repository::{
+    EqualFilter, RepositoryError, Stocktake, StocktakeLine, StocktakeLineFilter,
+    StocktakeLineRepository, StocktakeLineRow, StocktakeRow, StocktakeRowRepository,
+    StorageConnection,
+};
+
+use crate::{
+    service_provider::ServiceContext, stocktake::query::get_stocktake,
+    validate::check_store_id_matches,
+};
+
+use super::validate::{
+    check_count_not_submitted, check_stocktake_exist, check_stocktake_not_finalised,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum SubmitStocktakeCountError {
+    DatabaseError(RepositoryError),
+    InternalError(String),
+    InvalidStore,
+    StocktakeDoesNotExist,
+    NotABlindCount,
+    CannotEditFinalised,
+    CountAlreadySubmitted,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ApproveStocktakeVarianceError {
+    DatabaseError(RepositoryError),
+    InternalError(String),
+    InvalidStore,
+    StocktakeDoesNotExist,
+    NotABlindCount,
+    CannotEditFinalised,
+    CountNotSubmitted,
+    /// Holds the lines still waiting for a second count
+    SecondCountsMissing(Vec<StocktakeLine>),
+    VarianceAlreadyApproved,
+}
+
+/// A submitted blind count line needs a second count when its variance from the snapshot, in
+/// percent of the snapshot, exceeds the stocktake's threshold
+pub fn requires_second_count(stocktake: &StocktakeRow, line: &StocktakeLineRow) -> bool {
+    if !stocktake.is_blind_count || stocktake.count_submitted_datetime.is_none() {
+        return false;
+    }
+    let (Some(threshold), Some(counted_number_of_packs)) = (
+        stocktake.variance_threshold_percentage,
+        line.counted_number_of_packs,
+    ) else {
+        return false;
+    };
+
+    let variance = (counted_number_of_packs - line.snapshot_number_of_packs).abs();
+    if line.snapshot_number_of_packs == 0.0 {
+        return variance != 0.0;
+    }
+    variance / line.snapshot_number_of_packs.abs() * 100.0 > threshold
+}
+
+fn validate_submit(
+    connection: &StorageConnection,
+    store_id: &str,
+    stocktake_id: &str,
+) -> Result<StocktakeRow, SubmitStocktakeCountError> {
+    use SubmitStocktakeCountError::*;
+
+    let stocktake = match check_stocktake_exist(connection, stocktake_id)? {
+        Some(stocktake) => stocktake,
+        None => return Err(StocktakeDoesNotExist),
+    };
+    if !check_store_id_matches(store_id, &stocktake.store_id) {
+        return Err(InvalidStore);
+    }
+    if !stocktake.is_blind_count {
+        return Err(NotABlindCount);
+    }
+    if !check_stocktake_not_finalised(&stocktake.status) {
+        return Err(CannotEditFinalised);
+    }
+    if !check_count_not_submitted(&stocktake) {
+        return Err(CountAlreadySubmitted);
+    }
+
+    Ok(stocktake)
+}
+
+fn validate_approve(
+    connection: &StorageConnection,
+    store_id: &str,
+    stocktake_id: &str,
+) -> Result<StocktakeRow, ApproveStocktakeVarianceError> {
+    use ApproveStocktakeVarianceError::*;
+
+    let stocktake = match check_stocktake_exist(connection, stocktake_id)? {
+        Some(stocktake) => stocktake,
+        None => return Err(StocktakeDoesNotExist),
+    };
+    if !check_store_id_matches(store_id, &stocktake.store_id) {
+        return Err(InvalidStore);
+    }
+    if !stocktake.is_blind_count {
+        return Err(NotABlindCount);
+    }
+    if !check_stocktake_not_finalised(&stocktake.status) {
+        return Err(CannotEditFinalised);
+    }
+    if check_count_not_submitted(&stocktake) {
+        return Err(CountNotSubmitted);
+    }
+    if stocktake.variance_approved_datetime.is_some() {
+        return Err(VarianceAlreadyApproved);
+    }
+
+    let missing_second_counts: Vec<StocktakeLine> = StocktakeLineRepository::new(connection)
+        .query_by_filter(
+            StocktakeLineFilter::new().stocktake_id(EqualFilter::equal_to(&stocktake.id)),
+            Some(store_id.to_string()),
+        )?
+        .into_iter()
+        .filter(|line| {
+            requires_second_count(&stocktake, &line.line)
+                && line.line.second_counted_number_of_packs.is_none()
+        })
+        .collect();
+    if !missing_second_counts.is_empty() {
+        return Err(SecondCountsMissing(missing_second_counts));
+    }
+
+    Ok(stocktake)
+}
+
+/// Ends the counting of a blind count, revealing the snapshot quantities
+pub fn submit_stocktake_count(
+    ctx: &ServiceContext,
+    stocktake_id: String,
+) -> Result<Stocktake, SubmitStocktakeCountError> {
+    let result = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let stocktake = validate_submit(connection, &ctx.store_id, &stocktake_id)?;
+
+            StocktakeRowRepository::new(connection).upsert_one(&StocktakeRow {
+                count_submitted_datetime: Some(Utc::now().naive_utc()),
+                ..stocktake
+            })?;
+
+            get_stocktake(ctx, stocktake_id)?.ok_or(SubmitStocktakeCountError::InternalError(
+                "Failed to read the just submitted stocktake!".to_string(),
+            ))
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(result)
+}
+
+/// Approves the counted variance of a submitted blind count, allowing it to be finalised
+pub fn approve_stocktake_variance(
+    ctx: &ServiceContext,
+    stocktake_id: String,
+) -> Result<Stocktake, ApproveStocktakeVarianceError> {
+    let result = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let stocktake = validate_approve(connection, &ctx.store_id, &stocktake_id)?;
+
+            StocktakeRowRepository::new(connection).upsert_one(&StocktakeRow {
+                variance_approved_datetime: Some(Utc::now().naive_utc()),
+                variance_approved_by_user_id: Some(ctx.user_id.clone()),
+                ..stocktake
+            })?;
+
+            get_stocktake(ctx, stocktake_id)?.ok_or(ApproveStocktakeVarianceError::InternalError(
+                "Failed to read the just approved stocktake!".to_string(),
+            ))
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(result)
+}
+
+impl From<RepositoryError> for SubmitStocktakeCountError {
+    fn from(error: RepositoryError) -> Self {
+        SubmitStocktakeCountError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for ApproveStocktakeVarianceError {
+    fn from(error: RepositoryError) -> Self {
+        ApproveStocktakeVarianceError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_stock_line_a, mock_stock_line_b, mock_stocktake_a, mock_store_a,
+            mock_user_account_a, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        StockLineRowRepository, StocktakeLineRow, StocktakeRow,
+    };
+    use util::inline_init;
+
+    use crate::{
+        service_provider::ServiceProvider,
+        stocktake::{
+            ApproveStocktakeVarianceError, SubmitStocktakeCountError, UpdateStocktake,
+            UpdateStocktakeError, UpdateStocktakeStatus,
+        },
+        stocktake_line::{UpdateStocktakeLine, UpdateStocktakeLineError},
+    };
+
+    fn blind_stocktake() -> StocktakeRow {
+        inline_init(|r: &mut StocktakeRow| {
+            r.id = "blind_stocktake".to_string();
+            r.store_id = mock_store_a().id;
+            r.stocktake_number = 100;
+            r.is_blind_count = true;
+            r.variance_threshold_percentage = Some(10.0);
+        })
+    }
+
+    fn line_a() -> StocktakeLineRow {
+        inline_init(|r: &mut StocktakeLineRow| {
+            r.id = "blind_stocktake_line_a".to_string();
+            r.stocktake_id = blind_stocktake().id;
+            r.stock_line_id = Some(mock_stock_line_a().id);
+            r.item_link_id = mock_stock_line_a().item_link_id;
+            r.snapshot_number_of_packs = mock_stock_line_a().total_number_of_packs;
+        })
+    }
+
+    fn line_b() -> StocktakeLineRow {
+        inline_init(|r: &mut StocktakeLineRow| {
+            r.id = "blind_stocktake_line_b".to_string();
+            r.stocktake_id = blind_stocktake().id;
+            r.stock_line_id = Some(mock_stock_line_b().id);
+            r.item_link_id = mock_stock_line_b().item_link_id;
+            r.snapshot_number_of_packs = mock_stock_line_b().total_number_of_packs;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn blind_count() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "blind_count",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.stocktakes = vec![blind_stocktake()];
+                r.stocktake_lines = vec![line_a(), line_b()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let context = service_provider
+            .context(mock_store_a().id, mock_user_account_a().id)
+            .unwrap();
+        let service = &service_provider.stocktake_service;
+        let line_service = &service_provider.stocktake_line_service;
+        let count = |line_id: String, counted: Option<f64>, second_counted: Option<f64>| {
+            line_service.update_stocktake_line(
+                &context,
+                inline_init(|r: &mut UpdateStocktakeLine| {
+                    r.id = line_id;
+                    r.counted_number_of_packs = counted;
+                    r.second_counted_number_of_packs = second_counted;
+                }),
+            )
+        };
+        let finalise = || {
+            service.update_stocktake(
+                &context,
+                inline_init(|r: &mut UpdateStocktake| {
+                    r.id = blind_stocktake().id;
+                    r.status = Some(UpdateStocktakeStatus::Finalised);
+                }),
+            )
+        };
+
+        // error: NotABlindCount
+        assert_eq!(
+            service.submit_stocktake_count(&context, mock_stocktake_a().id),
+            Err(SubmitStocktakeCountError::NotABlindCount)
+        );
+
+        // 5% and 33% variance
+        count(line_a().id, Some(38.0), None).unwrap();
+        count(line_b().id, Some(20.0), None).unwrap();
+
+        // Counting is not submitted
+        assert_eq!(
+            count(line_b().id, None, Some(28.0)).map(|_| ()),
+            Err(UpdateStocktakeLineError::SecondCountNotRequired)
+        );
+        assert_eq!(
+            service.approve_stocktake_variance(&context, blind_stocktake().id),
+            Err(ApproveStocktakeVarianceError::CountNotSubmitted)
+        );
+        assert_eq!(
+            finalise().map(|_| ()),
+            Err(UpdateStocktakeError::BlindCountNotSubmitted)
+        );
+
+        let stocktake = service
+            .submit_stocktake_count(&context, blind_stocktake().id)
+            .unwrap();
+        assert!(stocktake.count_submitted_datetime.is_some());
+        assert_eq!(
+            service.submit_stocktake_count(&context, blind_stocktake().id),
+            Err(SubmitStocktakeCountError::CountAlreadySubmitted)
+        );
+        assert_eq!(
+            count(line_b().id, Some(25.0), None).map(|_| ()),
+            Err(UpdateStocktakeLineError::CountAlreadySubmitted)
+        );
+
+        // Line b exceeds the threshold
+        match service.approve_stocktake_variance(&context, blind_stocktake().id) {
+            Err(ApproveStocktakeVarianceError::SecondCountsMissing(lines)) => {
+                assert_eq!(
+                    lines
+                        .into_iter()
+                        .map(|line| line.line.id)
+                        .collect::<Vec<_>>(),
+                    vec![line_b().id]
+                )
+            }
+            result => panic!("Expected SecondCountsMissing, got {:?}", result),
+        }
+        assert_eq!(
+            count(line_a().id, None, Some(39.0)).map(|_| ()),
+            Err(UpdateStocktakeLineError::SecondCountNotRequired)
+        );
+        let line = count(line_b().id, None, Some(28.0)).unwrap();
+        assert_eq!(line.line.counted_number_of_packs, Some(20.0));
+        assert_eq!(line.line.second_counted_number_of_packs, Some(28.0));
+
+        assert_eq!(
+            finalise().map(|_| ()),
+            Err(UpdateStocktakeError::VarianceNotApproved)
+        );
+
+        let stocktake = service
+            .approve_stocktake_variance(&context, blind_stocktake().id)
+            .unwrap();
+        assert!(stocktake.variance_approved_datetime.is_some());
+        assert_eq!(
+            stocktake.variance_approved_by_user_id,
+            Some(mock_user_account_a().id)
+        );
+        assert_eq!(
+            count(line_b().id, None, Some(29.0)).map(|_| ()),
+            Err(UpdateStocktakeLineError::VarianceAlreadyApproved)
+        );
+
+        // Second count replaces the first one
+        finalise().unwrap();
+        let stock_line_repo = StockLineRowRepository::new(&connection);
+        let total_number_of_packs = |id: &str| {
+            stock_line_repo
+                .find_one_by_id(id)
+                .unwrap()
+                .unwrap()
+                .total_number_of_packs
+        };
+        assert_eq!(total_number_of_packs(&mock_stock_line_a().id), 38.0);
+        assert_eq!(total_number_of_packs(&mock_stock_line_b().id), 28.0);
+    }
+}
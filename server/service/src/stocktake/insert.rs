@@ -26,6 +26,9 @@ pub struct InsertStocktake {
     pub location: Option<NullableUpdate<String>>,
     pub items_have_stock: Option<bool>,
     pub expires_before: Option<NaiveDate>,
+    pub is_blind_count: Option<bool>,
+    /// Only used for blind counts
+    pub variance_threshold_percentage: Option<f64>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -84,6 +87,12 @@ fn validate(
     if !check_location_exists(connection, store_id, &stocktake.location)? {
         return Err(InsertStocktakeError::InvalidLocation);
     }
+    if stocktake
+        .variance_threshold_percentage
+        .map_or(false, |threshold| threshold < 0.0)
+    {
+        return Err(InsertStocktakeError::InvalidArguments);
+    }
 
     Ok(())
 }
@@ -102,6 +111,8 @@ fn generate(
         master_list_id,
         items_have_stock,
         expires_before,
+        is_blind_count,
+        variance_threshold_percentage,
     }: InsertStocktake,
 ) -> Result<(StocktakeRow, Vec<StocktakeLineRow>), RepositoryError> {
     let stocktake_number = next_number(connection, &NumberRowType::Stocktake, store_id)?;
@@ -155,10 +166,15 @@ fn generate(
             user_id: user_id.to_string(),
             store_id: store_id.to_string(),
             is_locked: is_locked.unwrap_or(false),
+            is_blind_count: is_blind_count.unwrap_or(false),
+            variance_threshold_percentage,
             // Default
             finalised_datetime: None,
             inventory_addition_id: None,
             inventory_reduction_id: None,
+            count_submitted_datetime: None,
+            variance_approved_datetime: None,
+            variance_approved_by_user_id: None,
         },
         lines,
     ))
@@ -180,6 +196,16 @@ fn generate_lines_from_master_list(
         .map(|r| r.item_id)
         .collect();
 
+    generate_lines_for_items(connection, store_id, stocktake_id, &item_ids)
+}
+
+/// Stocktake lines for the store's stock of the items, items without stock get an empty line
+pub fn generate_lines_for_items(
+    connection: &StorageConnection,
+    store_id: &str,
+    stocktake_id: &str,
+    item_ids: &[String],
+) -> Result<Vec<StocktakeLineRow>, RepositoryError> {
     let mut result = Vec::<StocktakeLineRow>::new();
 
     item_ids.iter().for_each(|item_id| {
@@ -216,6 +242,7 @@ fn generate_lines_from_master_list(
                 comment: None,
                 counted_number_of_packs: None,
                 inventory_adjustment_reason_id: None,
+                second_counted_number_of_packs: None,
             });
         } else {
             stock_lines.into_iter().for_each(|line| {
@@ -254,6 +281,7 @@ fn generate_lines_from_master_list(
                     comment: None,
                     counted_number_of_packs: None,
                     inventory_adjustment_reason_id: None,
+                    second_counted_number_of_packs: None,
                 });
             });
         }
@@ -314,6 +342,7 @@ fn generate_lines_from_location(
                 comment: None,
                 counted_number_of_packs: None,
                 inventory_adjustment_reason_id: None,
+                second_counted_number_of_packs: None,
             }
         })
         .collect();
@@ -370,6 +399,7 @@ pub fn generate_lines_with_stock(
                 comment: None,
                 counted_number_of_packs: None,
                 inventory_adjustment_reason_id: None,
+                second_counted_number_of_packs: None,
             }
         })
         .collect();
@@ -426,6 +456,7 @@ fn generate_lines_expiring_before(
                 comment: None,
                 counted_number_of_packs: None,
                 inventory_adjustment_reason_id: None,
+                second_counted_number_of_packs: None,
                 item_name: line.item_row.name,
             }
         })
@@ -545,6 +576,8 @@ mod test {
                     master_list_id: None,
                     items_have_stock: None,
                     expires_before: None,
+                    is_blind_count: None,
+                    variance_threshold_percentage: None,
                 },
             )
             .unwrap();
@@ -601,6 +634,8 @@ mod test {
                 master_list_id: Some("invalid".to_string()),
                 items_have_stock: None,
                 expires_before: None,
+                is_blind_count: None,
+                variance_threshold_percentage: None,
             },
         );
         assert!(invalid_result.is_err());
@@ -627,6 +662,8 @@ mod test {
                     master_list_id: Some(master_list_id.clone()),
                     items_have_stock: None,
                     expires_before: None,
+                    is_blind_count: None,
+                    variance_threshold_percentage: None,
                 },
             )
             .unwrap();
@@ -677,6 +714,8 @@ mod test {
                     master_list_id: Some(master_list_id.clone()),
                     items_have_stock: None,
                     expires_before: None,
+                    is_blind_count: None,
+                    variance_threshold_percentage: None,
                 },
             )
             .unwrap();
@@ -728,6 +767,8 @@ mod test {
                     master_list_id: None,
                     items_have_stock: None,
                     expires_before: None,
+                    is_blind_count: None,
+                    variance_threshold_percentage: None,
                 },
             )
             .unwrap();
@@ -769,6 +810,8 @@ mod test {
                     master_list_id: None,
                     items_have_stock: None,
                     expires_before: None,
+                    is_blind_count: None,
+                    variance_threshold_percentage: None,
                 },
             )
             .unwrap();
@@ -858,6 +901,8 @@ mod test {
                     master_list_id: None,
                     items_have_stock: None,
                     expires_before: None,
+                    is_blind_count: None,
+                    variance_threshold_percentage: None,
                 },
             )
             .unwrap();
@@ -884,6 +929,8 @@ mod test {
                     master_list_id: None,
                     items_have_stock: Some(true),
                     expires_before: None,
+                    is_blind_count: None,
+                    variance_threshold_percentage: None,
                 },
             )
             .unwrap();
@@ -922,6 +969,8 @@ mod test {
                     master_list_id: None,
                     items_have_stock: None,
                     expires_before: Some(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()),
+                    is_blind_count: None,
+                    variance_threshold_percentage: None,
                 },
             )
             .unwrap();
@@ -951,6 +1000,8 @@ mod test {
                     master_list_id: None,
                     items_have_stock: None,
                     expires_before: Some(NaiveDate::from_ymd_opt(2020, 4, 22).unwrap()),
+                    is_blind_count: None,
+                    variance_threshold_percentage: None,
                 },
             )
             .unwrap();
@@ -18,6 +18,9 @@ pub use self::update::*;
 mod batch;
 pub use self::batch::*;
 
+mod blind_count;
+pub use self::blind_count::*;
+
 pub trait StocktakeServiceTrait: Sync + Send {
     fn get_stocktakes(
         &self,
@@ -67,6 +70,24 @@ pub trait StocktakeServiceTrait: Sync + Send {
         update_stocktake(ctx, input)
     }
 
+    /// Ends the counting of a blind count stocktake
+    fn submit_stocktake_count(
+        &self,
+        ctx: &ServiceContext,
+        stocktake_id: String,
+    ) -> Result<Stocktake, SubmitStocktakeCountError> {
+        submit_stocktake_count(ctx, stocktake_id)
+    }
+
+    /// Approves the variance of a submitted blind count stocktake, required before finalising
+    fn approve_stocktake_variance(
+        &self,
+        ctx: &ServiceContext,
+        stocktake_id: String,
+    ) -> Result<Stocktake, ApproveStocktakeVarianceError> {
+        approve_stocktake_variance(ctx, stocktake_id)
+    }
+
     fn batch_stocktake(
         &self,
         ctx: &ServiceContext,
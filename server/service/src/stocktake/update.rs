@@ -13,6 +13,7 @@ use util::{constants::INVENTORY_ADJUSTMENT_NAME_CODE, inline_edit, uuid::uuid};
 
 use crate::{
     activity_log::activity_log_entry,
+    cycle_count::variance::record_stocktake_counts,
     invoice::inventory_adjustment::UpdateInventoryAdjustmentReason,
     invoice_line::{
         stock_in_line::{
@@ -73,6 +74,10 @@ pub enum UpdateStocktakeError {
     /// Holds list of affected stock lines
     SnapshotCountCurrentCountMismatch(Vec<StocktakeLine>),
     StockLinesReducedBelowZero(Vec<StockLine>),
+    /// Blind count stocktakes can only be finalised once the count is submitted
+    BlindCountNotSubmitted,
+    /// Blind count stocktakes can only be finalised once the variance is approved
+    VarianceNotApproved,
 }
 
 fn check_snapshot_matches_current_count(
@@ -105,7 +110,7 @@ fn check_stock_lines_reduced_to_zero(
             Some(stock_line) => stock_line,
             None => continue,
         };
-        if let Some(counted_number_of_packs) = line.line.counted_number_of_packs {
+        if let Some(counted_number_of_packs) = line.line.final_counted_number_of_packs() {
             let adjustment = stock_line_row.total_number_of_packs - counted_number_of_packs;
 
             if adjustment > 0.0
@@ -170,6 +175,15 @@ fn validate(
             return Err(UpdateStocktakeError::NoLines);
         }
 
+        if existing.is_blind_count {
+            if existing.count_submitted_datetime.is_none() {
+                return Err(UpdateStocktakeError::BlindCountNotSubmitted);
+            }
+            if existing.variance_approved_datetime.is_none() {
+                return Err(UpdateStocktakeError::VarianceNotApproved);
+            }
+        }
+
         if let Some(stock_reduced_to_zero) =
             check_stock_lines_reduced_to_zero(connection, &stocktake_lines)?
         {
@@ -252,7 +266,7 @@ fn generate_stock_in_out_or_update(
     let row = stocktake_line.line.to_owned();
 
     let counted_number_of_packs = row
-        .counted_number_of_packs
+        .final_counted_number_of_packs()
         .unwrap_or(stocktake_line.line.snapshot_number_of_packs);
     let delta = counted_number_of_packs - row.snapshot_number_of_packs;
 
@@ -361,7 +375,10 @@ fn generate_new_stock_line(
     let item_id = stocktake_line.item.id.to_owned();
     let stock_line_id = uuid();
 
-    let counted_number_of_packs = stocktake_line.line.counted_number_of_packs.unwrap_or(0.0);
+    let counted_number_of_packs = stocktake_line
+        .line
+        .final_counted_number_of_packs()
+        .unwrap_or(0.0);
 
     // If no counted packs, we shouldn't create a stock line
     if counted_number_of_packs == 0.0 {
@@ -784,6 +801,7 @@ pub fn update_stocktake(
                     None,
                     None,
                 )?;
+                record_stocktake_counts(connection, &result.stocktake)?;
             }
 
             // return the updated stocktake
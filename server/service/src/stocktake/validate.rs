@@ -15,6 +15,11 @@ pub fn check_stocktake_not_finalised(status: &StocktakeStatus) -> bool {
     *status != StocktakeStatus::Finalised
 }
 
+/// Lines of a blind count can't be changed once the count is submitted
+pub fn check_count_not_submitted(stocktake: &StocktakeRow) -> bool {
+    stocktake.count_submitted_datetime.is_none()
+}
+
 pub fn check_no_stocktake_lines_exist(
     connection: &StorageConnection,
     stocktake_line_id: &str,
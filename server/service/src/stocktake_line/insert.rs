@@ -10,7 +10,9 @@ use crate::validate::check_store_id_matches;
 use crate::{check_location_exists, NullableUpdate};
 use crate::{
     service_provider::ServiceContext,
-    stocktake::validate::{check_stocktake_exist, check_stocktake_not_finalised},
+    stocktake::validate::{
+        check_count_not_submitted, check_stocktake_exist, check_stocktake_not_finalised,
+    },
     stocktake_line::query::get_stocktake_line,
 };
 
@@ -54,6 +56,8 @@ pub enum InsertStocktakeLineError {
     AdjustmentReasonNotProvided,
     AdjustmentReasonNotValid,
     StockLineReducedBelowZero(StockLine),
+    /// Blind count has already been submitted
+    CountAlreadySubmitted,
 }
 
 fn check_stocktake_line_does_not_exist(
@@ -167,6 +171,10 @@ fn validate(
         return Err(StocktakeIsLocked);
     }
 
+    if !check_count_not_submitted(&stocktake) {
+        return Err(CountAlreadySubmitted);
+    }
+
     let stock_line = if let Some(stock_line_id) = &input.stock_line_id {
         Some(
             check_stock_line_exists(connection, store_id, stock_line_id).map_err(
@@ -283,6 +291,7 @@ fn generate(
         sell_price_per_pack,
         note,
         inventory_adjustment_reason_id,
+        second_counted_number_of_packs: None,
     }
 }
 
@@ -8,7 +8,12 @@ use crate::{
     check_location_exists,
     common_stock::{check_stock_line_exists, CommonStockLineError},
     service_provider::ServiceContext,
-    stocktake::validate::{check_stocktake_exist, check_stocktake_not_finalised},
+    stocktake::{
+        requires_second_count,
+        validate::{
+            check_count_not_submitted, check_stocktake_exist, check_stocktake_not_finalised,
+        },
+    },
     stocktake_line::{query::get_stocktake_line, validate::check_stocktake_line_exist},
     validate::check_store_id_matches,
     NullableUpdate,
@@ -26,6 +31,8 @@ pub struct UpdateStocktakeLine {
     pub comment: Option<String>,
     pub snapshot_number_of_packs: Option<f64>,
     pub counted_number_of_packs: Option<f64>,
+    /// Recount of a submitted blind count line with a variance above the threshold
+    pub second_counted_number_of_packs: Option<f64>,
     pub batch: Option<String>,
     pub expiry_date: Option<NaiveDate>,
     pub pack_size: Option<f64>,
@@ -48,6 +55,11 @@ pub enum UpdateStocktakeLineError {
     AdjustmentReasonNotProvided,
     AdjustmentReasonNotValid,
     StockLineReducedBelowZero(StockLine),
+    /// Counted and snapshot quantities of a blind count can't change once it's submitted
+    CountAlreadySubmitted,
+    /// Second counts are only entered for lines with a variance above the threshold
+    SecondCountNotRequired,
+    VarianceAlreadyApproved,
 }
 
 fn validate(
@@ -82,8 +94,28 @@ fn validate(
         return Err(LocationDoesNotExist);
     }
 
+    if !check_count_not_submitted(&stocktake)
+        && (input.counted_number_of_packs.is_some() || input.snapshot_number_of_packs.is_some())
+    {
+        return Err(CountAlreadySubmitted);
+    }
+
+    if input.second_counted_number_of_packs.is_some() {
+        if stocktake.variance_approved_datetime.is_some() {
+            return Err(VarianceAlreadyApproved);
+        }
+        if !requires_second_count(&stocktake, stocktake_line_row) {
+            return Err(SecondCountNotRequired);
+        }
+    }
+
+    // The second count replaces the first one
+    let counted_number_of_packs = input
+        .second_counted_number_of_packs
+        .or(input.counted_number_of_packs);
+
     let stocktake_reduction_amount =
-        stocktake_reduction_amount(&input.counted_number_of_packs, stocktake_line_row);
+        stocktake_reduction_amount(&counted_number_of_packs, stocktake_line_row);
     if check_active_adjustment_reasons(connection, stocktake_reduction_amount)?.is_some()
         && input.inventory_adjustment_reason_id.is_none()
         && stocktake_reduction_amount != 0.0
@@ -101,10 +133,9 @@ fn validate(
         return Err(AdjustmentReasonNotValid);
     }
 
-    if let (Some(counted_number_of_packs), Some(stock_line_id)) = (
-        input.counted_number_of_packs,
-        &stocktake_line_row.stock_line_id,
-    ) {
+    if let (Some(counted_number_of_packs), Some(stock_line_id)) =
+        (counted_number_of_packs, &stocktake_line_row.stock_line_id)
+    {
         let stock_line = check_stock_line_exists(connection, store_id, stock_line_id).map_err(
             |err| match err {
                 CommonStockLineError::DatabaseError(RepositoryError::NotFound) => {
@@ -132,6 +163,7 @@ fn generate(
         comment,
         snapshot_number_of_packs,
         counted_number_of_packs,
+        second_counted_number_of_packs,
         batch,
         expiry_date,
         pack_size,
@@ -154,6 +186,8 @@ fn generate(
         snapshot_number_of_packs: snapshot_number_of_packs
             .unwrap_or(existing_line.snapshot_number_of_packs),
         counted_number_of_packs: counted_number_of_packs.or(existing_line.counted_number_of_packs),
+        second_counted_number_of_packs: second_counted_number_of_packs
+            .or(existing_line.second_counted_number_of_packs),
 
         item_link_id: existing.item.id,
         item_name: existing_line.item_name,
@@ -485,6 +519,7 @@ mod stocktake_line_test {
                 pack_size: None,
                 note: None,
                 inventory_adjustment_reason_id: None,
+                second_counted_number_of_packs: None,
             }
         );
 
@@ -12,6 +12,8 @@ pub(crate) mod remote_data_synchroniser;
 pub mod settings;
 pub mod site_info;
 mod sync_buffer;
+pub mod sync_buffer_error;
+pub mod sync_bundle;
 pub mod sync_on_central;
 pub(crate) mod sync_serde;
 pub mod sync_status;
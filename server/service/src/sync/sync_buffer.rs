@@ -8,6 +8,7 @@ use util::inline_edit;
 pub(crate) struct SyncBuffer<'a> {
     query_repository: SyncBufferRepository<'a>,
     row_repository: SyncBufferRowRepository<'a>,
+    record_ids: Option<Vec<String>>,
 }
 
 impl<'a> SyncBuffer<'a> {
@@ -15,9 +16,16 @@ impl<'a> SyncBuffer<'a> {
         SyncBuffer {
             query_repository: SyncBufferRepository::new(connection),
             row_repository: SyncBufferRowRepository::new(connection),
+            record_ids: None,
         }
     }
 
+    /// Only get records with these ids
+    pub(crate) fn record_ids(mut self, record_ids: Vec<String>) -> Self {
+        self.record_ids = Some(record_ids);
+        self
+    }
+
     pub(crate) fn record_successful_integration(
         &self,
         row: &SyncBufferRow,
@@ -59,16 +67,18 @@ impl<'a> SyncBuffer<'a> {
         let mut result = Vec::new();
 
         for legacy_table_name in order {
-            let mut rows = self.query_repository.query_by_filter(
-                SyncBufferFilter::new()
-                    .table_name(EqualFilter::equal_to(legacy_table_name))
-                    .action(action.equal_to())
-                    .integration_datetime(DatetimeFilter::is_null(true))
-                    .source_site_id(match source_site_id {
-                        Some(site_id) => EqualFilter::equal_to_i32(site_id),
-                        None => EqualFilter::i32_is_null(true),
-                    }),
-            )?;
+            let mut filter = SyncBufferFilter::new()
+                .table_name(EqualFilter::equal_to(legacy_table_name))
+                .action(action.equal_to())
+                .integration_datetime(DatetimeFilter::is_null(true))
+                .source_site_id(match source_site_id {
+                    Some(site_id) => EqualFilter::equal_to_i32(site_id),
+                    None => EqualFilter::i32_is_null(true),
+                });
+            if let Some(record_ids) = &self.record_ids {
+                filter = filter.record_id(EqualFilter::equal_any(record_ids.clone()));
+            }
+            let mut rows = self.query_repository.query_by_filter(filter)?;
             result.append(&mut rows);
         }
 
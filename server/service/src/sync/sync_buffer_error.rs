@@ -0,0 +1,477 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use repository::{
+    ActivityLogType, EqualFilter, PaginationOption, RepositoryError, StorageConnection,
+    SyncBufferErrorGroup, SyncBufferFilter, SyncBufferRepository, SyncBufferRow,
+    SyncBufferRowRepository,
+};
+
+use crate::{
+    activity_log::activity_log_entry,
+    get_default_pagination, i64_to_u32,
+    service_provider::ServiceContext,
+    sync::{
+        sync_on_central::{is_integrating, set_integrating},
+        sync_status::status::{SyncStatusService, SyncStatusTrait},
+        synchroniser::integrate_and_translate_sync_buffer_records,
+    },
+    ListError, ListResult,
+};
+
+pub const MAX_LIMIT: u32 = 1000;
+pub const MIN_LIMIT: u32 = 1;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ReintegrateSyncBufferRows {
+    /// Failed rows with these record ids
+    pub record_ids: Option<Vec<String>>,
+    /// All failed rows of these tables
+    pub table_names: Option<Vec<String>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ReintegrateSyncBufferRowsResult {
+    pub integrated_count: u32,
+    /// Rows that failed integration again
+    pub failed_count: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ReintegrateSyncBufferRowsError {
+    NoRowsSelected,
+    /// This site is syncing, sync integrates pending rows
+    SyncInProgress,
+    /// Records pushed by the site are being integrated on central server
+    IntegrationInProgress(i32),
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct PurgeSyncBufferRows {
+    pub record_ids: Vec<String>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PurgeSyncBufferRowsError {
+    NoRowsSelected,
+    RowDoesNotExist(String),
+    /// Only rows that failed integration can be purged
+    RowHasNotFailed(String),
+    DatabaseError(RepositoryError),
+}
+
+pub trait SyncBufferErrorServiceTrait: Sync + Send {
+    fn get_sync_buffer_error_groups(
+        &self,
+        ctx: &ServiceContext,
+    ) -> Result<Vec<SyncBufferErrorGroup>, RepositoryError> {
+        SyncBufferRepository::new(&ctx.connection).error_groups()
+    }
+
+    /// Rows that failed integration, most recently received first
+    fn get_failed_sync_buffer_rows(
+        &self,
+        ctx: &ServiceContext,
+        pagination: Option<PaginationOption>,
+        filter: Option<SyncBufferFilter>,
+    ) -> Result<ListResult<SyncBufferRow>, ListError> {
+        let pagination = get_default_pagination(pagination, MAX_LIMIT, MIN_LIMIT)?;
+        let mut filter = filter.unwrap_or_default();
+        if filter.integration_error.is_none() {
+            filter.integration_error = Some(EqualFilter::is_null(false));
+        }
+
+        let repository = SyncBufferRepository::new(&ctx.connection);
+        Ok(ListResult {
+            rows: repository.query_paginated(pagination, Some(filter.clone()))?,
+            count: i64_to_u32(repository.count(Some(filter))?),
+        })
+    }
+
+    /// Re-run integration of failed rows, i.e. after the cause of the error was fixed
+    fn reintegrate_sync_buffer_rows(
+        &self,
+        ctx: &ServiceContext,
+        input: ReintegrateSyncBufferRows,
+    ) -> Result<ReintegrateSyncBufferRowsResult, ReintegrateSyncBufferRowsError> {
+        reintegrate_sync_buffer_rows(ctx, input)
+    }
+
+    /// Delete rows that can never be integrated, purged rows (and the reason) are recorded in the
+    /// activity log
+    fn purge_sync_buffer_rows(
+        &self,
+        ctx: &ServiceContext,
+        input: PurgeSyncBufferRows,
+    ) -> Result<Vec<SyncBufferRow>, PurgeSyncBufferRowsError> {
+        purge_sync_buffer_rows(ctx, input)
+    }
+}
+
+pub struct SyncBufferErrorService;
+impl SyncBufferErrorServiceTrait for SyncBufferErrorService {}
+
+fn reintegrate_sync_buffer_rows(
+    ctx: &ServiceContext,
+    ReintegrateSyncBufferRows {
+        record_ids,
+        table_names,
+    }: ReintegrateSyncBufferRows,
+) -> Result<ReintegrateSyncBufferRowsResult, ReintegrateSyncBufferRowsError> {
+    let record_ids = record_ids.unwrap_or_default();
+    let table_names = table_names.unwrap_or_default();
+    if record_ids.is_empty() && table_names.is_empty() {
+        return Err(ReintegrateSyncBufferRowsError::NoRowsSelected);
+    }
+
+    let is_syncing = SyncStatusService
+        .get_latest_sync_status(ctx)?
+        .is_some_and(|status| status.is_syncing);
+    if is_syncing {
+        return Err(ReintegrateSyncBufferRowsError::SyncInProgress);
+    }
+
+    let repository = SyncBufferRepository::new(&ctx.connection);
+    let failed = || SyncBufferFilter::new().integration_error(EqualFilter::is_null(false));
+    let mut rows = BTreeMap::new();
+    for filter in [
+        (!record_ids.is_empty()).then(|| failed().record_id(EqualFilter::equal_any(record_ids))),
+        (!table_names.is_empty()).then(|| failed().table_name(EqualFilter::equal_any(table_names))),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        for row in repository.query_by_filter(filter)? {
+            rows.insert(row.record_id.clone(), row);
+        }
+    }
+
+    // Integration is done separately for rows received from each site (on central server)
+    let source_site_ids: BTreeSet<Option<i32>> =
+        rows.values().map(|row| row.source_site_id).collect();
+    if let Some(site_id) = source_site_ids
+        .iter()
+        .flatten()
+        .find(|site_id| is_integrating(**site_id))
+    {
+        return Err(ReintegrateSyncBufferRowsError::IntegrationInProgress(
+            *site_id,
+        ));
+    }
+
+    reintegrate_rows(
+        &ctx.connection,
+        &rows,
+        integrate_and_translate_sync_buffer_records,
+    )?;
+
+    let selected_count = rows.len();
+    let failed_count = repository
+        .query_by_filter(failed().record_id(EqualFilter::equal_any(rows.into_keys().collect())))?
+        .len();
+
+    Ok(ReintegrateSyncBufferRowsResult {
+        integrated_count: (selected_count - failed_count) as u32,
+        failed_count: failed_count as u32,
+    })
+}
+
+/// Marks `rows` as pending and integrates them. Done in a transaction so that rows keep their
+/// integration error if integration fails.
+fn reintegrate_rows<T>(
+    connection: &StorageConnection,
+    rows: &BTreeMap<String, SyncBufferRow>,
+    integrate: impl Fn(&StorageConnection, Option<i32>, Vec<String>) -> Result<T, RepositoryError>,
+) -> Result<(), RepositoryError> {
+    let source_site_ids: BTreeSet<Option<i32>> =
+        rows.values().map(|row| row.source_site_id).collect();
+
+    connection
+        .transaction_sync(|connection| {
+            let row_repository = SyncBufferRowRepository::new(connection);
+            for row in rows.values() {
+                row_repository.upsert_one(&SyncBufferRow {
+                    integration_datetime: None,
+                    integration_error: None,
+                    ..row.clone()
+                })?;
+            }
+
+            for source_site_id in source_site_ids {
+                // Other pending rows of the site are not integrated here
+                let record_ids = rows
+                    .values()
+                    .filter(|row| row.source_site_id == source_site_id)
+                    .map(|row| row.record_id.clone())
+                    .collect();
+                if let Some(site_id) = source_site_id {
+                    set_integrating(site_id, true);
+                }
+                let result = integrate(connection, source_site_id, record_ids);
+                if let Some(site_id) = source_site_id {
+                    set_integrating(site_id, false);
+                }
+                result?;
+            }
+
+            Ok(())
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+fn purge_sync_buffer_rows(
+    ctx: &ServiceContext,
+    PurgeSyncBufferRows { record_ids, reason }: PurgeSyncBufferRows,
+) -> Result<Vec<SyncBufferRow>, PurgeSyncBufferRowsError> {
+    if record_ids.is_empty() {
+        return Err(PurgeSyncBufferRowsError::NoRowsSelected);
+    }
+
+    ctx.connection
+        .transaction_sync(|connection| {
+            let repository = SyncBufferRowRepository::new(connection);
+            let mut purged = Vec::new();
+
+            for record_id in record_ids {
+                let row = repository
+                    .find_one_by_record_id(&record_id)?
+                    .ok_or(PurgeSyncBufferRowsError::RowDoesNotExist(record_id.clone()))?;
+                if row.integration_error.is_none() {
+                    return Err(PurgeSyncBufferRowsError::RowHasNotFailed(record_id));
+                }
+
+                // Keep the payload and error of the purged row, the reason is recorded as the
+                // changed to value
+                activity_log_entry(
+                    ctx,
+                    ActivityLogType::SyncBufferRowPurged,
+                    Some(record_id.clone()),
+                    serde_json::to_string(&row).ok(),
+                    reason.clone(),
+                )?;
+                repository.delete(&record_id)?;
+                purged.push(row);
+            }
+
+            Ok(purged)
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+impl From<RepositoryError> for ReintegrateSyncBufferRowsError {
+    fn from(error: RepositoryError) -> Self {
+        ReintegrateSyncBufferRowsError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for PurgeSyncBufferRowsError {
+    fn from(error: RepositoryError) -> Self {
+        PurgeSyncBufferRowsError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_user_account_a, MockData, MockDataInserts},
+        test_db::setup_all_with_data,
+        ActivityLogRowRepository, ActivityLogType, RepositoryError, SyncBufferRow,
+        SyncBufferRowRepository, SyncLogRow, SyncLogRowRepository, UnitRowRepository,
+    };
+    use std::collections::BTreeMap;
+    use util::{inline_init, Defaults};
+
+    use crate::{service_provider::ServiceProvider, ListResult};
+
+    use super::{
+        reintegrate_rows, PurgeSyncBufferRows, PurgeSyncBufferRowsError, ReintegrateSyncBufferRows,
+        ReintegrateSyncBufferRowsError, ReintegrateSyncBufferRowsResult,
+    };
+
+    fn unit_row(record_id: &str, data: &str, integration_error: Option<&str>) -> SyncBufferRow {
+        inline_init(|r: &mut SyncBufferRow| {
+            r.record_id = record_id.to_string();
+            r.table_name = "unit".to_string();
+            r.data = data.to_string();
+            r.received_datetime = Defaults::naive_date_time();
+            r.integration_datetime = integration_error.map(|_| Defaults::naive_date_time());
+            r.integration_error = integration_error.map(str::to_string);
+        })
+    }
+
+    fn fixed_row() -> SyncBufferRow {
+        unit_row(
+            "fixed_unit",
+            r#"{"ID": "fixed_unit", "units": "Tab", "comment": "", "order_number": 1}"#,
+            Some("error"),
+        )
+    }
+
+    fn broken_row() -> SyncBufferRow {
+        unit_row("broken_unit", r#"{"ID": "broken_unit"}"#, Some("error"))
+    }
+
+    fn pending_row() -> SyncBufferRow {
+        unit_row(
+            "pending_unit",
+            r#"{"ID": "pending_unit", "units": "Vial", "comment": "", "order_number": 2}"#,
+            None,
+        )
+    }
+
+    #[actix_rt::test]
+    async fn sync_buffer_errors() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "sync_buffer_errors",
+            MockDataInserts::none().user_accounts(),
+            inline_init(|r: &mut MockData| {
+                r.sync_buffer_rows = vec![fixed_row(), broken_row(), pending_row()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let context = service_provider
+            .context("".to_string(), mock_user_account_a().id)
+            .unwrap();
+        let service = &service_provider.sync_buffer_error_service;
+
+        let groups = service.get_sync_buffer_error_groups(&context).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            (groups[0].table_name.as_str(), groups[0].count),
+            ("unit", 2)
+        );
+
+        let ListResult { count, rows } = service
+            .get_failed_sync_buffer_rows(&context, None, None)
+            .unwrap();
+        assert_eq!(count, 2);
+        assert!(rows.iter().all(|row| row.integration_error.is_some()));
+
+        // Reintegrate
+        assert_eq!(
+            service.reintegrate_sync_buffer_rows(&context, ReintegrateSyncBufferRows::default()),
+            Err(ReintegrateSyncBufferRowsError::NoRowsSelected)
+        );
+        let reintegrate_units = || ReintegrateSyncBufferRows {
+            table_names: Some(vec!["unit".to_string()]),
+            ..Default::default()
+        };
+
+        let sync_log_repo = SyncLogRowRepository::new(&connection);
+        let sync_log = SyncLogRow {
+            id: "sync_log".to_string(),
+            ..Default::default()
+        };
+        sync_log_repo._upsert_one(&sync_log).unwrap();
+        assert_eq!(
+            service.reintegrate_sync_buffer_rows(&context, reintegrate_units()),
+            Err(ReintegrateSyncBufferRowsError::SyncInProgress)
+        );
+        sync_log_repo
+            ._upsert_one(&SyncLogRow {
+                finished_datetime: Some(Defaults::naive_date_time()),
+                ..sync_log
+            })
+            .unwrap();
+
+        assert_eq!(
+            service.reintegrate_sync_buffer_rows(&context, reintegrate_units()),
+            Ok(ReintegrateSyncBufferRowsResult {
+                integrated_count: 1,
+                failed_count: 1
+            })
+        );
+        let unit_repo = UnitRowRepository::new(&connection);
+        assert!(unit_repo.find_one_by_id("fixed_unit").unwrap().is_some());
+        // Pending rows are left for sync
+        assert!(unit_repo.find_one_by_id("pending_unit").unwrap().is_none());
+
+        // Purge
+        assert_eq!(
+            service.purge_sync_buffer_rows(
+                &context,
+                PurgeSyncBufferRows {
+                    record_ids: vec!["invalid".to_string()],
+                    reason: None,
+                }
+            ),
+            Err(PurgeSyncBufferRowsError::RowDoesNotExist(
+                "invalid".to_string()
+            ))
+        );
+        assert_eq!(
+            service.purge_sync_buffer_rows(
+                &context,
+                PurgeSyncBufferRows {
+                    record_ids: vec![fixed_row().record_id],
+                    reason: None,
+                }
+            ),
+            Err(PurgeSyncBufferRowsError::RowHasNotFailed(
+                fixed_row().record_id
+            ))
+        );
+
+        let purged = service
+            .purge_sync_buffer_rows(
+                &context,
+                PurgeSyncBufferRows {
+                    record_ids: vec![broken_row().record_id],
+                    reason: Some("Unit was deleted on central".to_string()),
+                },
+            )
+            .unwrap();
+        assert_eq!(purged.len(), 1);
+        assert_eq!(
+            SyncBufferRowRepository::new(&connection)
+                .find_one_by_record_id(&broken_row().record_id)
+                .unwrap(),
+            None
+        );
+
+        let logs = ActivityLogRowRepository::new(&connection)
+            .find_many_by_record_id(&broken_row().record_id)
+            .unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].r#type, ActivityLogType::SyncBufferRowPurged);
+        assert_eq!(logs[0].user_id, Some(mock_user_account_a().id));
+        assert_eq!(
+            logs[0].changed_to,
+            Some("Unit was deleted on central".to_string())
+        );
+        assert!(logs[0]
+            .changed_from
+            .as_ref()
+            .unwrap()
+            .contains("broken_unit"));
+    }
+
+    #[actix_rt::test]
+    async fn reintegrate_sync_buffer_rows_failure() {
+        let (_, connection, _, _) = setup_all_with_data(
+            "reintegrate_sync_buffer_rows_failure",
+            MockDataInserts::none(),
+            inline_init(|r: &mut MockData| {
+                r.sync_buffer_rows = vec![fixed_row()];
+            }),
+        )
+        .await;
+
+        let rows = BTreeMap::from([(fixed_row().record_id, fixed_row())]);
+        let result = reintegrate_rows(&connection, &rows, |_, _, _| -> Result<(), _> {
+            Err(RepositoryError::as_db_error("Integration failed", ""))
+        });
+        assert!(result.is_err());
+
+        // Row is left failed with its error
+        assert_eq!(
+            SyncBufferRowRepository::new(&connection)
+                .find_one_by_record_id(&fixed_row().record_id)
+                .unwrap(),
+            Some(fixed_row())
+        );
+    }
+}
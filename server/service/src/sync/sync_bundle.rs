@@ -0,0 +1,1111 @@
+//! Offline sync with open-mSupply central server, for sites without a network connection.
+//!
+//! Records are carried between remote site and central server as sync bundle files (e.g. on a USB stick):
+//! 1) Remote site exports a push bundle with pending changelogs (push cursors are not moved)
+//! 2) Central server imports the push bundle, integrates the records through the sync buffer,
+//! forwards the legacy records to mSupply central server (with the site's credentials) and
+//! replies with a pull bundle, containing central and legacy records for the site and
+//! acknowledging the push.
+//! Central server keeps the end cursor of the last integrated push bundle of each site and rejects
+//! bundles that are not newer (replayed bundles)
+//! 3) Remote site imports the pull bundle, records are integrated through the sync buffer and the
+//! pull and push cursors are advanced
+//!
+//! Bundles are gzipped JSON, signed with HMAC-SHA256 keyed by the site password hash.
+//! Both records synced with open-mSupply central server (sync v6) and legacy records synced with
+//! mSupply central server (sync v5) are included.
+//!
+//! Legacy records queued for the site are acknowledged on mSupply central server when they are
+//! pulled by central server, they are kept in `sync_bundle_site` and sent again with every reply
+//! until the remote site imported one of them (known by its pull cursor in the next push bundle).
+use std::io::Read;
+
+use chrono::{NaiveDateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use hmac::{Hmac, Mac};
+use repository::{
+    ChangelogRepository, ChangelogRow, KeyType, RepositoryError, StorageConnection,
+    SyncBufferRowRepository, SyncBundleSiteRow, SyncBundleSiteRowRepository,
+};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+use util::{format_error, hash::sha256};
+
+use crate::{
+    cursor_controller::CursorController,
+    service_provider::{ServiceContext, ServiceProvider},
+    sync::{
+        api::{
+            CentralSyncBatchV5, CommonSyncRecord, RemoteSyncRecordV5, SyncApiError,
+            SyncApiSettings, SyncApiV5, SyncApiV5CreatingError,
+        },
+        api_v6::SyncRecordV6,
+        get_sync_push_changelogs_filter,
+        remote_data_synchroniser::RemoteDataSynchroniser,
+        settings::{BatchSize, SyncSettings, SYNC_V5_VERSION},
+        sync_on_central::{is_integrating, set_integrating},
+        synchroniser::{
+            integrate_and_translate_sync_buffer, INTEGRATION_POLL_PERIOD_SECONDS,
+            INTEGRATION_TIMEOUT_SECONDS,
+        },
+        translations::{
+            translate_changelogs_to_sync_records, PushSyncRecord, ToSyncRecordTranslationType,
+        },
+        GetActiveStoresOnSiteError,
+    },
+};
+
+const SYNC_BUNDLE_VERSION: u32 = 1;
+const CHANGELOG_BATCH_SIZE: u32 = 1000;
+/// Changelogs exported in one push bundle, the remaining changelogs are exported in the next bundle
+const PUSH_BUNDLE_MAX_CHANGELOGS: usize = 10000;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncBundleType {
+    Push,
+    Pull,
+}
+
+/// Site details needed by central server to check site credentials with mSupply central server
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SyncBundleSite {
+    username: String,
+    site_uuid: String,
+    app_version: String,
+    app_name: String,
+    sync_version: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SyncBundle {
+    version: u32,
+    r#type: SyncBundleType,
+    site_id: i32,
+    created_datetime: NaiveDateTime,
+    /// Pull cursor of the remote site when push bundle was exported,
+    /// pull bundle contains central records from this cursor
+    pull_cursor: u64,
+    /// Push: cursor of the last changelog in the bundle (records and legacy records)
+    /// Pull: cursor the remote pull cursor is advanced past
+    end_cursor: Option<u64>,
+    /// Push bundle only
+    site: Option<SyncBundleSite>,
+    /// Pull bundle only, end cursor of the push bundle that was integrated on central server
+    acknowledged_push_cursor: Option<u64>,
+    records: Vec<SyncRecordV6>,
+    /// Push: remote site records for legacy mSupply central server
+    /// Pull: records legacy mSupply central server queued for the site
+    legacy_records: Vec<RemoteSyncRecordV5>,
+    /// Push: legacy central data pull cursor of the remote site
+    /// Pull: cursor the remote legacy central data pull cursor is advanced to
+    legacy_central_pull_cursor: u64,
+    /// Pull bundle only, legacy central data from the remote legacy central data pull cursor
+    legacy_central_records: Vec<CommonSyncRecord>,
+}
+
+/// Sync bundle file content, signature is for the exact bundle string
+#[derive(Serialize, Deserialize)]
+struct SignedSyncBundle {
+    signature: String,
+    bundle: String,
+}
+
+#[derive(Error, Debug)]
+pub enum SyncBundleError {
+    #[error("Sync settings are not set")]
+    SyncSettingsNotSet,
+    #[error("Site id is not set")]
+    SiteIdNotSet,
+    #[error("Site is not initialised")]
+    NotInitialised,
+    #[error("Not a central server")]
+    NotACentralServer,
+    #[error("Cannot read sync bundle: {0}")]
+    InvalidBundle(String),
+    #[error("Sync bundle version {0} is not supported")]
+    UnsupportedVersion(u32),
+    #[error("Sync bundle signature does not match")]
+    InvalidSignature,
+    #[error("Site password is required to import a push bundle")]
+    SitePasswordRequired,
+    #[error("Sync bundle is for site {bundle_site_id} but site is {site_id}")]
+    WrongSite { bundle_site_id: i32, site_id: i32 },
+    #[error("Pull bundle starts at cursor {bundle_cursor} but site pull cursor is {cursor}, export a new push bundle")]
+    PullCursorMismatch { bundle_cursor: u64, cursor: u64 },
+    #[error("Push bundle up to cursor {end_cursor} was already imported for this site (acknowledged up to cursor {acknowledged_push_cursor})")]
+    PushAlreadyImported {
+        end_cursor: u64,
+        acknowledged_push_cursor: u64,
+    },
+    #[error("Integration in progress for site {0}")]
+    IntegrationInProgress(i32),
+    #[error("mSupply central server did not start integration of legacy records")]
+    LegacyIntegrationNotStarted,
+    #[error(transparent)]
+    SyncApiError(#[from] SyncApiError),
+    #[error(transparent)]
+    SyncApiV5CreatingError(#[from] SyncApiV5CreatingError),
+    #[error("Database error")]
+    DatabaseError(#[from] RepositoryError),
+    #[error("{0}")]
+    Other(String),
+}
+
+pub struct SyncBundleExport {
+    /// Bundle file content
+    pub bundle: Vec<u8>,
+    pub number_of_records: u64,
+    /// More changelogs are pending than fit in one bundle, the next push bundle can be exported
+    /// once the reply to this one was imported
+    pub has_more: bool,
+}
+
+pub struct SyncBundleImport {
+    pub r#type: SyncBundleType,
+    pub number_of_records: u64,
+    /// Pull bundle file content to be carried back to the remote site, when importing a push bundle
+    pub reply: Option<Vec<u8>>,
+}
+
+/// Export pending push changelogs of a remote site as a push bundle.
+/// Push cursor is only advanced when the pull bundle acknowledging this push is imported
+pub fn export_push_bundle(
+    service_provider: &ServiceProvider,
+) -> Result<SyncBundleExport, SyncBundleError> {
+    let ctx = service_provider.basic_context()?;
+    let connection = &ctx.connection;
+
+    if !service_provider.sync_status_service.is_initialised(&ctx)? {
+        return Err(SyncBundleError::NotInitialised);
+    }
+    let sync_settings = get_sync_settings(service_provider, &ctx)?;
+    let site_id = service_provider
+        .site_info_service
+        .get_site_id(&ctx)?
+        .ok_or(SyncBundleError::SiteIdNotSet)?;
+    let SyncApiSettings {
+        username,
+        site_uuid,
+        app_version,
+        app_name,
+        sync_version,
+        ..
+    } = SyncApiV5::new_settings(&sync_settings, service_provider, SYNC_V5_VERSION)?;
+
+    let push_cursor = CursorController::new(KeyType::SyncPushCursorV6).get(connection)?;
+    let pull_cursor = CursorController::new(KeyType::SyncPullCursorV6).get(connection)?;
+    let legacy_push_cursor =
+        CursorController::new(KeyType::RemoteSyncPushCursor).get(connection)?;
+    let legacy_central_pull_cursor =
+        CursorController::new(KeyType::CentralSyncPullCursor).get(connection)?;
+
+    let filter = get_sync_push_changelogs_filter(connection).map_err(|error| match error {
+        GetActiveStoresOnSiteError::DatabaseError(error) => SyncBundleError::DatabaseError(error),
+        GetActiveStoresOnSiteError::SiteIdNotSet => SyncBundleError::SiteIdNotSet,
+    })?;
+    let changelog_repo = ChangelogRepository::new(connection);
+    let (pending_changelogs, has_more) = collect_changelogs(
+        push_cursor.min(legacy_push_cursor),
+        Some(PUSH_BUNDLE_MAX_CHANGELOGS),
+        |cursor| changelog_repo.changelogs(cursor, CHANGELOG_BATCH_SIZE, filter.clone()),
+    )?;
+    // Both push cursors are advanced to the end cursor once acknowledged, all pending changelogs
+    // of either kind up to the end cursor are in the bundle
+    let end_cursor = pending_changelogs.last().map(|log| log.cursor as u64);
+    let (changelogs, legacy_changelogs): (Vec<_>, Vec<_>) = (
+        pending_changelogs
+            .iter()
+            .filter(|log| log.cursor as u64 >= push_cursor)
+            .cloned()
+            .collect(),
+        pending_changelogs
+            .into_iter()
+            .filter(|log| log.cursor as u64 >= legacy_push_cursor)
+            .collect(),
+    );
+
+    let records: Vec<SyncRecordV6> = translate(
+        connection,
+        changelogs,
+        ToSyncRecordTranslationType::PushToOmSupplyCentral,
+    )?;
+    let legacy_records: Vec<RemoteSyncRecordV5> = translate(
+        connection,
+        legacy_changelogs,
+        ToSyncRecordTranslationType::PushToLegacyCentral,
+    )?;
+    let number_of_records = (records.len() + legacy_records.len()) as u64;
+
+    log::info!(
+        "Exporting {} records in push bundle (more pending: {})",
+        number_of_records,
+        has_more
+    );
+
+    let bundle = SyncBundle {
+        version: SYNC_BUNDLE_VERSION,
+        r#type: SyncBundleType::Push,
+        site_id,
+        created_datetime: Utc::now().naive_utc(),
+        pull_cursor,
+        end_cursor,
+        site: Some(SyncBundleSite {
+            username,
+            site_uuid,
+            app_version,
+            app_name,
+            sync_version,
+        }),
+        acknowledged_push_cursor: None,
+        records,
+        legacy_records,
+        legacy_central_pull_cursor,
+        legacy_central_records: Vec::new(),
+    };
+
+    Ok(SyncBundleExport {
+        bundle: encode_bundle(&bundle, &sync_settings.password_sha256)?,
+        number_of_records,
+        has_more,
+    })
+}
+
+/// Import a sync bundle, push bundles are imported on central server (site password is required)
+/// and pull bundles on the remote site they were created for
+pub async fn import_sync_bundle(
+    service_provider: &ServiceProvider,
+    bundle: &[u8],
+    site_password: Option<String>,
+) -> Result<SyncBundleImport, SyncBundleError> {
+    let signed_bundle = decode_signed_bundle(bundle)?;
+
+    match peek_bundle_type(&signed_bundle)? {
+        SyncBundleType::Push => {
+            let site_password = site_password.ok_or(SyncBundleError::SitePasswordRequired)?;
+            import_push_bundle(service_provider, signed_bundle, &sha256(&site_password)).await
+        }
+        SyncBundleType::Pull => import_pull_bundle(service_provider, signed_bundle),
+    }
+}
+
+/// Central server: integrate push bundle records and create the pull bundle reply
+async fn import_push_bundle(
+    service_provider: &ServiceProvider,
+    signed_bundle: SignedSyncBundle,
+    password_sha256: &str,
+) -> Result<SyncBundleImport, SyncBundleError> {
+    let ctx = service_provider.basic_context()?;
+    let bundle = verify_bundle(signed_bundle, password_sha256)?;
+    let site = bundle.site.ok_or(SyncBundleError::InvalidBundle(
+        "Push bundle is missing site details".to_string(),
+    ))?;
+
+    // Check this is a central server and site credentials against mSupply central server
+    let sync_settings = get_sync_settings(service_provider, &ctx)?;
+    let batch_size = sync_settings.batch_size.clone();
+    let central_site_info = SyncApiV5::new(SyncApiV5::new_settings(
+        &sync_settings,
+        service_provider,
+        SYNC_V5_VERSION,
+    )?)?
+    .get_site_info()
+    .await?;
+    if !central_site_info.is_central_server {
+        return Err(SyncBundleError::NotACentralServer);
+    }
+
+    let site_api = SyncApiV5::new(SyncApiSettings {
+        server_url: sync_settings.url,
+        username: site.username,
+        password_sha256: password_sha256.to_string(),
+        site_uuid: site.site_uuid,
+        app_version: site.app_version,
+        app_name: site.app_name,
+        sync_version: site.sync_version,
+    })?;
+    let site_info = site_api.get_site_info().await?;
+    let site_id = site_info.site_id;
+    if site_id != bundle.site_id {
+        return Err(SyncBundleError::WrongSite {
+            bundle_site_id: bundle.site_id,
+            site_id,
+        });
+    }
+
+    check_push_not_imported(&ctx.connection, site_id, bundle.end_cursor)?;
+    let site_repo = SyncBundleSiteRowRepository::new(&ctx.connection);
+    let mut site_row = site_repo
+        .find_one_by_site_id(site_id)?
+        .unwrap_or(SyncBundleSiteRow {
+            site_id,
+            ..Default::default()
+        });
+
+    if is_integrating(site_id) {
+        return Err(SyncBundleError::IntegrationInProgress(site_id));
+    }
+    set_integrating(site_id, true);
+    let result = integrate_push_records(&ctx.connection, site_id, bundle.records);
+    set_integrating(site_id, false);
+    let number_of_records = result?;
+    trigger_processors(&ctx);
+
+    let legacy_sync = RemoteDataSynchroniser {
+        sync_api_v5: site_api,
+    };
+    let number_of_legacy_records = bundle.legacy_records.len() as u64;
+    forward_legacy_records(&legacy_sync, &batch_size, bundle.legacy_records).await?;
+
+    let changelog_repo = ChangelogRepository::new(&ctx.connection);
+    let max_cursor = changelog_repo.latest_cursor()?;
+    let (changelogs, _) = collect_changelogs(bundle.pull_cursor, None, |cursor| {
+        changelog_repo.outgoing_sync_records_from_central(
+            cursor,
+            CHANGELOG_BATCH_SIZE,
+            site_id,
+            true,
+        )
+    })?;
+    let end_cursor = changelogs
+        .last()
+        .map(|log| log.cursor as u64)
+        .unwrap_or(max_cursor);
+
+    let records: Vec<SyncRecordV6> = translate(
+        &ctx.connection,
+        changelogs,
+        ToSyncRecordTranslationType::PullFromOmSupplyCentral,
+    )?;
+
+    let (legacy_central_records, legacy_central_pull_cursor) = pull_legacy_central_records(
+        &legacy_sync.sync_api_v5,
+        batch_size.central_pull,
+        bundle.legacy_central_pull_cursor,
+    )
+    .await?;
+
+    // Records of the last reply are only sent again if the remote site didn't import it
+    if site_row.reply_pull_cursor == Some(bundle.pull_cursor as i64) {
+        site_row.pending_legacy_records = None;
+    }
+    let mut legacy_records: Vec<RemoteSyncRecordV5> = match &site_row.pending_legacy_records {
+        Some(pending) => {
+            serde_json::from_str(pending).map_err(|e| SyncBundleError::Other(format_error(&e)))?
+        }
+        None => Vec::new(),
+    };
+    loop {
+        let batch = legacy_sync
+            .sync_api_v5
+            .get_queued_records(batch_size.remote_pull)
+            .await?;
+        if batch.data.is_empty() {
+            break;
+        }
+        let sync_ids = batch.extract_sync_ids();
+        legacy_records.extend(batch.data);
+        // Kept before acknowledging, in case the import fails from here
+        site_row.pending_legacy_records = Some(to_json(&legacy_records)?);
+        site_repo.upsert_one(&site_row)?;
+        legacy_sync
+            .sync_api_v5
+            .post_acknowledged_records(sync_ids)
+            .await?;
+    }
+
+    // A reply that doesn't move the remote pull cursor can't be told apart from the remote site
+    // not importing it, pending legacy records are sent again in that case
+    let reply_pull_cursor = end_cursor + 1;
+    site_row.reply_pull_cursor =
+        (reply_pull_cursor != bundle.pull_cursor).then_some(reply_pull_cursor as i64);
+    site_row.pending_legacy_records = if legacy_records.is_empty() {
+        None
+    } else {
+        Some(to_json(&legacy_records)?)
+    };
+    if let Some(end_cursor) = bundle.end_cursor {
+        site_row.acknowledged_push_cursor = Some(end_cursor as i64);
+    }
+    site_repo.upsert_one(&site_row)?;
+
+    let number_of_records = number_of_records + number_of_legacy_records;
+    log::info!(
+        "Imported {} records from site {} push bundle, replying with {} records",
+        number_of_records,
+        site_id,
+        records.len() + legacy_records.len() + legacy_central_records.len()
+    );
+
+    let reply = SyncBundle {
+        version: SYNC_BUNDLE_VERSION,
+        r#type: SyncBundleType::Pull,
+        site_id,
+        created_datetime: Utc::now().naive_utc(),
+        pull_cursor: bundle.pull_cursor,
+        end_cursor: Some(end_cursor),
+        site: None,
+        acknowledged_push_cursor: bundle.end_cursor,
+        records,
+        legacy_records,
+        legacy_central_pull_cursor,
+        legacy_central_records,
+    };
+
+    Ok(SyncBundleImport {
+        r#type: SyncBundleType::Push,
+        number_of_records,
+        reply: Some(encode_bundle(&reply, password_sha256)?),
+    })
+}
+
+/// Push bundles (with records) up to the last acknowledged push cursor of the site were already
+/// imported, e.g. a copy of an old bundle
+fn check_push_not_imported(
+    connection: &StorageConnection,
+    site_id: i32,
+    end_cursor: Option<u64>,
+) -> Result<(), SyncBundleError> {
+    let acknowledged_push_cursor = SyncBundleSiteRowRepository::new(connection)
+        .find_one_by_site_id(site_id)?
+        .and_then(|row| row.acknowledged_push_cursor)
+        .map(|cursor| cursor as u64);
+
+    match (end_cursor, acknowledged_push_cursor) {
+        (Some(end_cursor), Some(acknowledged_push_cursor))
+            if end_cursor <= acknowledged_push_cursor =>
+        {
+            Err(SyncBundleError::PushAlreadyImported {
+                end_cursor,
+                acknowledged_push_cursor,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn integrate_push_records(
+    connection: &StorageConnection,
+    site_id: i32,
+    records: Vec<SyncRecordV6>,
+) -> Result<u64, SyncBundleError> {
+    let repo = SyncBufferRowRepository::new(connection);
+    let number_of_records = records.len() as u64;
+    for SyncRecordV6 { record, .. } in records {
+        let buffer_row = record
+            .to_buffer_row(Some(site_id))
+            .map_err(|e| SyncBundleError::InvalidBundle(format_error(&e)))?;
+        repo.upsert_one(&buffer_row)?;
+    }
+
+    integrate_and_translate_sync_buffer(connection, None, Some(site_id))?;
+
+    Ok(number_of_records)
+}
+
+/// Push legacy records to mSupply central server and wait for them to be integrated, as per
+/// `RemoteDataSynchroniser::push`
+async fn forward_legacy_records(
+    legacy_sync: &RemoteDataSynchroniser,
+    batch_size: &BatchSize,
+    records: Vec<RemoteSyncRecordV5>,
+) -> Result<(), SyncBundleError> {
+    let sync_api = &legacy_sync.sync_api_v5;
+
+    let mut remaining = records.len() as u64;
+    let mut records = records.into_iter();
+    while remaining > 0 {
+        let batch: Vec<RemoteSyncRecordV5> = records
+            .by_ref()
+            .take(batch_size.remote_push as usize)
+            .collect();
+        let batch_length = batch.len() as u64;
+        sync_api.post_queued_records(remaining, batch).await?;
+        remaining -= batch_length;
+    }
+
+    // Empty batch starts the integration
+    let response = sync_api.post_queued_records(0, Vec::new()).await?;
+    if !response.integration_started {
+        return Err(SyncBundleError::LegacyIntegrationNotStarted);
+    }
+    legacy_sync
+        .wait_for_sync_operation(INTEGRATION_POLL_PERIOD_SECONDS, INTEGRATION_TIMEOUT_SECONDS)
+        .await
+        .map_err(|e| SyncBundleError::Other(format_error(&e)))?;
+
+    Ok(())
+}
+
+/// Legacy central data from `cursor`, as per `CentralDataSynchroniser::pull`. Returns the records
+/// and the cursor to continue from
+async fn pull_legacy_central_records(
+    sync_api: &SyncApiV5,
+    batch_size: u32,
+    cursor: u64,
+) -> Result<(Vec<CommonSyncRecord>, u64), SyncBundleError> {
+    let mut records = Vec::new();
+    let mut cursor = cursor;
+    loop {
+        let CentralSyncBatchV5 { max_cursor, data } =
+            sync_api.get_central_records(cursor, batch_size).await?;
+        let batch_length = data.len();
+
+        for sync_record in data {
+            cursor = sync_record.cursor;
+            records.push(sync_record.record);
+        }
+
+        match (batch_length, cursor < max_cursor) {
+            (0, false) => break,
+            (0, true) => cursor += 1,
+            _ => continue,
+        }
+    }
+
+    Ok((records, cursor))
+}
+
+/// Remote site: integrate central records and advance cursors
+fn import_pull_bundle(
+    service_provider: &ServiceProvider,
+    signed_bundle: SignedSyncBundle,
+) -> Result<SyncBundleImport, SyncBundleError> {
+    let ctx = service_provider.basic_context()?;
+    let connection = &ctx.connection;
+
+    let sync_settings = get_sync_settings(service_provider, &ctx)?;
+    let bundle = verify_bundle(signed_bundle, &sync_settings.password_sha256)?;
+
+    let site_id = service_provider
+        .site_info_service
+        .get_site_id(&ctx)?
+        .ok_or(SyncBundleError::SiteIdNotSet)?;
+    if site_id != bundle.site_id {
+        return Err(SyncBundleError::WrongSite {
+            bundle_site_id: bundle.site_id,
+            site_id,
+        });
+    }
+
+    let pull_cursor_controller = CursorController::new(KeyType::SyncPullCursorV6);
+    let push_cursor_controllers = [
+        CursorController::new(KeyType::SyncPushCursorV6),
+        CursorController::new(KeyType::RemoteSyncPushCursor),
+    ];
+    let legacy_central_pull_cursor_controller =
+        CursorController::new(KeyType::CentralSyncPullCursor);
+    // Bundle was already imported or site synced since push bundle was exported
+    let pull_cursor = pull_cursor_controller.get(connection)?;
+    if pull_cursor != bundle.pull_cursor {
+        return Err(SyncBundleError::PullCursorMismatch {
+            bundle_cursor: bundle.pull_cursor,
+            cursor: pull_cursor,
+        });
+    }
+
+    let number_of_records = (bundle.legacy_central_records.len()
+        + bundle.legacy_records.len()
+        + bundle.records.len()) as u64;
+    // Same order as online sync: legacy central data, legacy remote data, then v6 records
+    let buffer_rows = bundle
+        .legacy_central_records
+        .into_iter()
+        .chain(
+            bundle
+                .legacy_records
+                .into_iter()
+                .map(|RemoteSyncRecordV5 { record, .. }| record),
+        )
+        .chain(
+            bundle
+                .records
+                .into_iter()
+                .map(|SyncRecordV6 { record, .. }| record),
+        )
+        .map(|record| record.to_buffer_row(None))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| SyncBundleError::InvalidBundle(format_error(&e)))?;
+
+    connection
+        .transaction_sync(|con| {
+            SyncBufferRowRepository::new(con).upsert_many(&buffer_rows)?;
+
+            if let Some(end_cursor) = bundle.end_cursor {
+                pull_cursor_controller.update(con, end_cursor + 1)?;
+            }
+            legacy_central_pull_cursor_controller.update(con, bundle.legacy_central_pull_cursor)?;
+            // Central server integrated (and forwarded) pushed records, they don't need to be
+            // pushed again
+            if let Some(acknowledged_push_cursor) = bundle.acknowledged_push_cursor {
+                for push_cursor_controller in &push_cursor_controllers {
+                    let push_cursor = push_cursor_controller.get(con)?;
+                    push_cursor_controller
+                        .update(con, push_cursor.max(acknowledged_push_cursor + 1))?;
+                }
+            }
+            Ok(())
+        })
+        .map_err(|e| e.to_inner_error())?;
+
+    integrate_and_translate_sync_buffer(connection, None, None)?;
+    trigger_processors(&ctx);
+
+    log::info!("Imported {} records from pull bundle", number_of_records);
+
+    Ok(SyncBundleImport {
+        r#type: SyncBundleType::Pull,
+        number_of_records,
+        reply: None,
+    })
+}
+
+fn get_sync_settings(
+    service_provider: &ServiceProvider,
+    ctx: &ServiceContext,
+) -> Result<SyncSettings, SyncBundleError> {
+    service_provider
+        .settings
+        .sync_settings(ctx)?
+        .ok_or(SyncBundleError::SyncSettingsNotSet)
+}
+
+fn trigger_processors(ctx: &ServiceContext) {
+    ctx.processors_trigger
+        .trigger_requisition_transfer_processors();
+    ctx.processors_trigger.trigger_invoice_transfer_processors();
+    ctx.processors_trigger.trigger_changelog_consumers();
+}
+
+/// Collects changelogs from cursor in batches, until there are no more changelogs or `limit` is
+/// reached. Returns the changelogs and if there are more changelogs after them
+fn collect_changelogs<F>(
+    cursor: u64,
+    limit: Option<usize>,
+    next_batch: F,
+) -> Result<(Vec<ChangelogRow>, bool), RepositoryError>
+where
+    F: Fn(u64) -> Result<Vec<ChangelogRow>, RepositoryError>,
+{
+    let mut result = Vec::new();
+    let mut cursor = cursor;
+    loop {
+        let changelogs = next_batch(cursor)?;
+        let Some(last) = changelogs.last() else {
+            break;
+        };
+        cursor = last.cursor as u64 + 1;
+        let is_last_batch = changelogs.len() < CHANGELOG_BATCH_SIZE as usize;
+        result.extend(changelogs);
+
+        if let Some(limit) = limit {
+            if result.len() > limit {
+                result.truncate(limit);
+                return Ok((result, true));
+            }
+        }
+        if is_last_batch {
+            break;
+        }
+    }
+    Ok((result, false))
+}
+
+fn translate<T: From<PushSyncRecord>>(
+    connection: &StorageConnection,
+    changelogs: Vec<ChangelogRow>,
+    translation_type: ToSyncRecordTranslationType,
+) -> Result<Vec<T>, SyncBundleError> {
+    Ok(
+        translate_changelogs_to_sync_records(connection, changelogs, translation_type)
+            .map_err(|e| SyncBundleError::Other(format_error(&e)))?
+            .into_iter()
+            .map(T::from)
+            .collect(),
+    )
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, SyncBundleError> {
+    serde_json::to_string(value).map_err(|e| SyncBundleError::Other(format_error(&e)))
+}
+
+fn encode_bundle(bundle: &SyncBundle, password_sha256: &str) -> Result<Vec<u8>, SyncBundleError> {
+    let bundle =
+        serde_json::to_string(bundle).map_err(|e| SyncBundleError::Other(format_error(&e)))?;
+    let signed_bundle = SignedSyncBundle {
+        signature: hex::encode(hmac_sha256(password_sha256.as_bytes(), bundle.as_bytes())),
+        bundle,
+    };
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    serde_json::to_writer(&mut encoder, &signed_bundle)
+        .map_err(|e| SyncBundleError::Other(format_error(&e)))?;
+    encoder
+        .finish()
+        .map_err(|e| SyncBundleError::Other(format_error(&e)))
+}
+
+fn decode_signed_bundle(content: &[u8]) -> Result<SignedSyncBundle, SyncBundleError> {
+    let mut json = String::new();
+    GzDecoder::new(content)
+        .read_to_string(&mut json)
+        .map_err(|e| SyncBundleError::InvalidBundle(format_error(&e)))?;
+
+    serde_json::from_str(&json).map_err(|e| SyncBundleError::InvalidBundle(format_error(&e)))
+}
+
+/// Bundle type is needed to know the signing key, it's not trusted until signature is verified
+fn peek_bundle_type(signed_bundle: &SignedSyncBundle) -> Result<SyncBundleType, SyncBundleError> {
+    #[derive(Deserialize)]
+    struct BundleType {
+        r#type: SyncBundleType,
+    }
+
+    serde_json::from_str::<BundleType>(&signed_bundle.bundle)
+        .map(|bundle| bundle.r#type)
+        .map_err(|e| SyncBundleError::InvalidBundle(format_error(&e)))
+}
+
+fn verify_bundle(
+    SignedSyncBundle { signature, bundle }: SignedSyncBundle,
+    password_sha256: &str,
+) -> Result<SyncBundle, SyncBundleError> {
+    let signature = hex::decode(signature).map_err(|_| SyncBundleError::InvalidSignature)?;
+    hmac_sha256_mac(password_sha256.as_bytes(), bundle.as_bytes())
+        .verify_slice(&signature)
+        .map_err(|_| SyncBundleError::InvalidSignature)?;
+
+    let bundle: SyncBundle = serde_json::from_str(&bundle)
+        .map_err(|e| SyncBundleError::InvalidBundle(format_error(&e)))?;
+    if bundle.version != SYNC_BUNDLE_VERSION {
+        return Err(SyncBundleError::UnsupportedVersion(bundle.version));
+    }
+
+    Ok(bundle)
+}
+
+fn hmac_sha256_mac(key: &[u8], message: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(message);
+    mac
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    hmac_sha256_mac(key, message)
+        .finalize()
+        .into_bytes()
+        .to_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::{mock_store_a, MockDataInserts},
+        test_db::setup_all,
+        ChangelogTableName, KeyValueStoreRepository, LocationRow, LocationRowRepository,
+        RowActionType, SyncLogRow, SyncLogRowRepository, UnitRowRepository,
+    };
+    use serde_json::json;
+    use util::inline_init;
+
+    use crate::sync::{
+        api::{CommonSyncRecord, RemoteSyncRecordV5, SyncAction},
+        api_v6::SyncRecordV6,
+    };
+
+    use super::*;
+
+    fn bundle() -> SyncBundle {
+        SyncBundle {
+            version: SYNC_BUNDLE_VERSION,
+            r#type: SyncBundleType::Pull,
+            site_id: 2,
+            created_datetime: NaiveDate::from_ymd_opt(2024, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+            pull_cursor: 10,
+            end_cursor: Some(20),
+            site: None,
+            acknowledged_push_cursor: Some(5),
+            records: vec![SyncRecordV6 {
+                cursor: 20,
+                record: CommonSyncRecord {
+                    table_name: "location".to_string(),
+                    record_id: "location_a".to_string(),
+                    action: SyncAction::Update,
+                    record_data: json!({ "id": "location_a" }),
+                },
+            }],
+            legacy_records: vec![RemoteSyncRecordV5 {
+                sync_id: "1".to_string(),
+                record: CommonSyncRecord {
+                    table_name: "item".to_string(),
+                    record_id: "item_a".to_string(),
+                    action: SyncAction::Update,
+                    record_data: json!({ "ID": "item_a" }),
+                },
+            }],
+            legacy_central_pull_cursor: 30,
+            legacy_central_records: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn hmac_sha256_rfc_4231() {
+        // Test case 2
+        assert_eq!(
+            hex::encode(hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Test case 6, key larger than block size
+        assert_eq!(
+            hex::encode(hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn sync_bundle_signature() {
+        let password_sha256 = sha256("password");
+        let content = encode_bundle(&bundle(), &password_sha256).unwrap();
+
+        let signed_bundle = decode_signed_bundle(&content).unwrap();
+        assert_eq!(
+            peek_bundle_type(&signed_bundle).unwrap(),
+            SyncBundleType::Pull
+        );
+        let decoded = verify_bundle(signed_bundle, &password_sha256).unwrap();
+        assert_eq!(decoded.site_id, 2);
+        assert_eq!(decoded.acknowledged_push_cursor, Some(5));
+        assert_eq!(decoded.records[0].record, bundle().records[0].record);
+        assert_eq!(decoded.legacy_records, bundle().legacy_records);
+        assert_eq!(decoded.legacy_central_pull_cursor, 30);
+
+        // Wrong key
+        let signed_bundle = decode_signed_bundle(&content).unwrap();
+        assert!(matches!(
+            verify_bundle(signed_bundle, &sha256("wrong")),
+            Err(SyncBundleError::InvalidSignature)
+        ));
+
+        // Tampered bundle
+        let mut signed_bundle = decode_signed_bundle(&content).unwrap();
+        signed_bundle.bundle = signed_bundle.bundle.replace("\"siteId\":2", "\"siteId\":3");
+        assert!(matches!(
+            verify_bundle(signed_bundle, &password_sha256),
+            Err(SyncBundleError::InvalidSignature)
+        ));
+
+        // Not a bundle
+        assert!(matches!(
+            decode_signed_bundle(b"not a bundle"),
+            Err(SyncBundleError::InvalidBundle(_))
+        ));
+    }
+
+    #[test]
+    fn collect_changelogs_limit() {
+        let changelogs = |cursor: u64| -> Result<Vec<ChangelogRow>, RepositoryError> {
+            // 2500 changelogs from cursor 1
+            Ok((cursor.max(1)..=2500)
+                .take(CHANGELOG_BATCH_SIZE as usize)
+                .map(|cursor| ChangelogRow {
+                    cursor: cursor as i64,
+                    table_name: ChangelogTableName::Location,
+                    record_id: cursor.to_string(),
+                    row_action: RowActionType::Upsert,
+                    name_id: None,
+                    store_id: None,
+                    is_sync_update: false,
+                    source_site_id: None,
+                })
+                .collect())
+        };
+
+        let (result, has_more) = collect_changelogs(0, None, changelogs).unwrap();
+        assert_eq!((result.len(), has_more), (2500, false));
+
+        let (result, has_more) = collect_changelogs(0, Some(1500), changelogs).unwrap();
+        assert_eq!((result.len(), has_more), (1500, true));
+        assert_eq!(result.last().unwrap().cursor, 1500);
+
+        let (result, has_more) = collect_changelogs(1001, Some(1500), changelogs).unwrap();
+        assert_eq!((result.len(), has_more), (1500, false));
+    }
+
+    #[actix_rt::test]
+    async fn sync_bundle_round_trip() {
+        let (_, connection, connection_manager, _) = setup_all(
+            "sync_bundle_round_trip",
+            MockDataInserts::none().names().stores(),
+        )
+        .await;
+        let service_provider = ServiceProvider::new(connection_manager, "app_data");
+        let ctx = service_provider.basic_context().unwrap();
+        let password_sha256 = sha256("password");
+
+        // Initialised remote site
+        service_provider
+            .settings
+            .update_sync_settings(
+                &ctx,
+                &SyncSettings {
+                    url: "http://test.com".to_string(),
+                    username: "site_a".to_string(),
+                    password_sha256: password_sha256.clone(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        KeyValueStoreRepository::new(&connection)
+            .set_i32(KeyType::SettingsSyncSiteId, Some(mock_store_a().site_id))
+            .unwrap();
+        SyncLogRowRepository::new(&connection)
+            ._upsert_one(&SyncLogRow {
+                id: "sync_log".to_string(),
+                finished_datetime: Some(bundle().created_datetime),
+                ..Default::default()
+            })
+            .unwrap();
+        LocationRowRepository::new(&connection)
+            .upsert_one(&inline_init(|r: &mut LocationRow| {
+                r.id = "bundle_location".to_string();
+                r.store_id = mock_store_a().id;
+            }))
+            .unwrap();
+
+        // Export
+        let export = export_push_bundle(&service_provider).unwrap();
+        assert!(!export.has_more);
+        let push = verify_bundle(
+            decode_signed_bundle(&export.bundle).unwrap(),
+            &password_sha256,
+        )
+        .unwrap();
+        assert_eq!(push.r#type, SyncBundleType::Push);
+        assert_eq!(push.site_id, mock_store_a().site_id);
+        assert_eq!(push.pull_cursor, 0);
+        assert!(push
+            .legacy_records
+            .iter()
+            .any(|record| record.record.record_id == "bundle_location"));
+        let end_cursor = push.end_cursor.unwrap();
+        assert_eq!(
+            end_cursor,
+            ChangelogRepository::new(&connection)
+                .latest_cursor()
+                .unwrap()
+        );
+        // Push cursors are not moved until the push is acknowledged
+        assert_eq!(
+            CursorController::new(KeyType::SyncPushCursorV6)
+                .get(&connection)
+                .unwrap(),
+            0
+        );
+
+        // Reply from central server
+        let reply = SyncBundle {
+            r#type: SyncBundleType::Pull,
+            site_id: push.site_id,
+            pull_cursor: push.pull_cursor,
+            end_cursor: Some(20),
+            acknowledged_push_cursor: push.end_cursor,
+            records: Vec::new(),
+            legacy_records: Vec::new(),
+            legacy_central_records: vec![CommonSyncRecord {
+                table_name: "unit".to_string(),
+                record_id: "bundle_unit".to_string(),
+                action: SyncAction::Update,
+                record_data: json!({
+                    "ID": "bundle_unit",
+                    "units": "Tab",
+                    "comment": "",
+                    "order_number": 1
+                }),
+            }],
+            ..bundle()
+        };
+        let reply = encode_bundle(&reply, &password_sha256).unwrap();
+
+        let import = import_sync_bundle(&service_provider, &reply, None)
+            .await
+            .unwrap();
+        assert_eq!(import.r#type, SyncBundleType::Pull);
+        assert_eq!(import.number_of_records, 1);
+        assert!(UnitRowRepository::new(&connection)
+            .find_one_by_id("bundle_unit")
+            .unwrap()
+            .is_some());
+
+        // Cursors are advanced
+        let cursor = |key_type: KeyType| CursorController::new(key_type).get(&connection).unwrap();
+        assert_eq!(cursor(KeyType::SyncPullCursorV6), 21);
+        assert_eq!(cursor(KeyType::SyncPushCursorV6), end_cursor + 1);
+        assert_eq!(cursor(KeyType::RemoteSyncPushCursor), end_cursor + 1);
+        assert_eq!(cursor(KeyType::CentralSyncPullCursor), 30);
+
+        // Pushed records are not exported again
+        let export = export_push_bundle(&service_provider).unwrap();
+        let push = verify_bundle(
+            decode_signed_bundle(&export.bundle).unwrap(),
+            &password_sha256,
+        )
+        .unwrap();
+        assert!(!push
+            .legacy_records
+            .iter()
+            .any(|record| record.record.record_id == "bundle_location"));
+        assert_eq!(push.pull_cursor, 21);
+
+        // Same reply imported again
+        assert!(matches!(
+            import_sync_bundle(&service_provider, &reply, None).await,
+            Err(SyncBundleError::PullCursorMismatch {
+                bundle_cursor: 0,
+                cursor: 21
+            })
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn sync_bundle_replay() {
+        let (_, connection, _, _) = setup_all("sync_bundle_replay", MockDataInserts::none()).await;
+
+        assert!(check_push_not_imported(&connection, 2, Some(10)).is_ok());
+        SyncBundleSiteRowRepository::new(&connection)
+            .upsert_one(&SyncBundleSiteRow {
+                site_id: 2,
+                acknowledged_push_cursor: Some(10),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert!(matches!(
+            check_push_not_imported(&connection, 2, Some(10)),
+            Err(SyncBundleError::PushAlreadyImported {
+                end_cursor: 10,
+                acknowledged_push_cursor: 10
+            })
+        ));
+        assert!(matches!(
+            check_push_not_imported(&connection, 2, Some(5)),
+            Err(SyncBundleError::PushAlreadyImported { .. })
+        ));
+        assert!(check_push_not_imported(&connection, 2, Some(11)).is_ok());
+        // Nothing pushed
+        assert!(check_push_not_imported(&connection, 2, None).is_ok());
+        // Other site
+        assert!(check_push_not_imported(&connection, 3, Some(5)).is_ok());
+    }
+}
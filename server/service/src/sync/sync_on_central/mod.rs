@@ -359,12 +359,12 @@ pub async fn upload_file(
 
 static SITES_BEING_INTEGRATED: RwLock<Vec<i32>> = RwLock::new(vec![]);
 
-fn is_integrating(site_id: i32) -> bool {
+pub(crate) fn is_integrating(site_id: i32) -> bool {
     let sites_being_integrated = SITES_BEING_INTEGRATED.read().unwrap();
     sites_being_integrated.contains(&site_id)
 }
 
-fn set_integrating(site_id: i32, is_integrating: bool) {
+pub(crate) fn set_integrating(site_id: i32, is_integrating: bool) {
     let mut sites_being_integrated = SITES_BEING_INTEGRATED.write().unwrap();
 
     if is_integrating {
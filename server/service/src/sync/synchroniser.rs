@@ -27,8 +27,8 @@ use super::{
     translations::{all_translators, pull_integration_order},
 };
 
-const INTEGRATION_POLL_PERIOD_SECONDS: u64 = 1;
-const INTEGRATION_TIMEOUT_SECONDS: u64 = 30;
+pub(crate) const INTEGRATION_POLL_PERIOD_SECONDS: u64 = 1;
+pub(crate) const INTEGRATION_TIMEOUT_SECONDS: u64 = 30;
 pub struct Synchroniser {
     settings: SyncSettings,
     service_provider: Arc<ServiceProvider>,
@@ -284,6 +284,7 @@ impl Synchroniser {
         ctx.processors_trigger
             .trigger_requisition_transfer_processors();
         ctx.processors_trigger.trigger_invoice_transfer_processors();
+        ctx.processors_trigger.trigger_changelog_consumers();
 
         Ok(())
     }
@@ -301,6 +302,39 @@ pub fn integrate_and_translate_sync_buffer(
         TranslationAndIntegrationResults,
     ),
     RepositoryError,
+> {
+    integrate_and_translate(connection, logger, source_site_id, None)
+}
+
+/// Translation And Integration of the given sync buffer records only, other pending records
+/// are left for the next sync
+pub(crate) fn integrate_and_translate_sync_buffer_records(
+    connection: &StorageConnection,
+    source_site_id: Option<i32>,
+    record_ids: Vec<String>,
+) -> Result<
+    (
+        TranslationAndIntegrationResults,
+        TranslationAndIntegrationResults,
+        TranslationAndIntegrationResults,
+    ),
+    RepositoryError,
+> {
+    integrate_and_translate(connection, None, source_site_id, Some(record_ids))
+}
+
+fn integrate_and_translate(
+    connection: &StorageConnection,
+    logger: Option<&mut SyncLogger<'_>>,
+    source_site_id: Option<i32>,
+    record_ids: Option<Vec<String>>,
+) -> Result<
+    (
+        TranslationAndIntegrationResults,
+        TranslationAndIntegrationResults,
+        TranslationAndIntegrationResults,
+    ),
+    RepositoryError,
 > {
     // Integration is done inside a transaction, to make sure all records are available at the same time
     // and maintain logical data integrity. During initialisation nested transactions cause significant
@@ -322,7 +356,10 @@ pub fn integrate_and_translate_sync_buffer(
         let translators = all_translators();
         let table_order = pull_integration_order(&translators);
 
-        let sync_buffer = SyncBuffer::new(connection);
+        let sync_buffer = match &record_ids {
+            Some(record_ids) => SyncBuffer::new(connection).record_ids(record_ids.clone()),
+            None => SyncBuffer::new(connection),
+        };
         let translation_and_integration = TranslationAndIntegration::new(connection, &sync_buffer);
 
         // Translate and integrate upserts (ordered by referential database constraints)
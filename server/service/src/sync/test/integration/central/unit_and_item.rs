@@ -56,6 +56,8 @@ impl SyncRecordTester for UnitAndItemTester {
             default_pack_size: 1.0,
             is_active: true,
             is_vaccine: false,
+            heat_stability_threshold: None,
+            heat_stability_budget_minutes: None,
         };
         let item_json1 = extend_base(json!({
             "ID": item_row1.id,
@@ -76,6 +78,8 @@ impl SyncRecordTester for UnitAndItemTester {
             default_pack_size: 1.0,
             is_active: true,
             is_vaccine: false,
+            heat_stability_threshold: None,
+            heat_stability_budget_minutes: None,
         };
         let item_json2 = extend_base(json!({
             "ID": item_row2.id,
@@ -96,6 +100,8 @@ impl SyncRecordTester for UnitAndItemTester {
             default_pack_size: 1.0,
             is_active: true,
             is_vaccine: false,
+            heat_stability_threshold: None,
+            heat_stability_budget_minutes: None,
         };
         let item_json3 = extend_base(json!({
             "ID": item_row3.id,
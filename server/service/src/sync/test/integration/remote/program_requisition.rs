@@ -136,6 +136,7 @@ impl SyncRecordTester for ProgramRequisitionTester {
             name_tag_id: name_tag1.id.clone(),
             program_id: master_list_row.id.clone(),
             period_schedule_id: period_schedule1.id.clone(),
+            consumption_forecast_method: None,
         };
 
         let program_requisition_settings2 = ProgramRequisitionSettingsRow {
@@ -143,6 +144,7 @@ impl SyncRecordTester for ProgramRequisitionTester {
             name_tag_id: name_tag2.id.clone(),
             program_id: master_list_row.id.clone(),
             period_schedule_id: period_schedule2.id.clone(),
+            consumption_forecast_method: None,
         };
 
         let order_type1 = ProgramRequisitionOrderTypeRow {
@@ -152,6 +154,8 @@ impl SyncRecordTester for ProgramRequisitionTester {
             threshold_mos: 3.0,
             max_mos: 3.0,
             max_order_per_period: 1,
+            is_emergency: false,
+            max_items_in_emergency_order: 0,
         };
 
         let order_type2 = ProgramRequisitionOrderTypeRow {
@@ -161,6 +165,8 @@ impl SyncRecordTester for ProgramRequisitionTester {
             threshold_mos: 3.0,
             max_mos: 3.0,
             max_order_per_period: 1,
+            is_emergency: false,
+            max_items_in_emergency_order: 0,
         };
 
         let order_type3 = ProgramRequisitionOrderTypeRow {
@@ -170,6 +176,8 @@ impl SyncRecordTester for ProgramRequisitionTester {
             threshold_mos: 4.0,
             max_mos: 4.0,
             max_order_per_period: 1,
+            is_emergency: false,
+            max_items_in_emergency_order: 0,
         };
 
         let master_list_row2 = MasterListRow {
@@ -221,6 +229,7 @@ impl SyncRecordTester for ProgramRequisitionTester {
             name_tag_id: name_tag1.id.clone(),
             program_id: master_list_row2.id.clone(),
             period_schedule_id: period_schedule1.id.clone(),
+            consumption_forecast_method: None,
         };
 
         result.push(TestStepData {
@@ -291,6 +300,7 @@ impl SyncRecordTester for ProgramRequisitionTester {
             name_tag_id: upsert_name_tag.id.clone(),
             program_id: master_list_row.id.clone(),
             period_schedule_id: period_schedule1.id.clone(),
+            consumption_forecast_method: None,
         };
 
         let upsert_order_type = ProgramRequisitionOrderTypeRow {
@@ -300,6 +310,8 @@ impl SyncRecordTester for ProgramRequisitionTester {
             threshold_mos: 3.0,
             max_mos: 6.0,
             max_order_per_period: 1,
+            is_emergency: false,
+            max_items_in_emergency_order: 0,
         };
 
         result.push(TestStepData {
@@ -44,6 +44,7 @@ impl SyncRecordTester for RequisitionRecordTester {
             program_id: None,
             period_id: None,
             order_type: None,
+            is_emergency: false,
         };
         let requisition_row_1 = base_requisition_row.clone();
         let requisition_line_row_1 = RequisitionLineRow {
@@ -60,6 +61,12 @@ impl SyncRecordTester for RequisitionRecordTester {
             snapshot_datetime: None,
             approved_quantity: 0.0,
             approval_comment: None,
+            opening_balance: None,
+            quantity_received: None,
+            quantity_dispensed: None,
+            losses_and_adjustments: None,
+            closing_balance: None,
+            days_out_of_stock: None,
         };
 
         let requisition_row_2 = inline_edit(&base_requisition_row, |mut d| {
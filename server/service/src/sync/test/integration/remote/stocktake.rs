@@ -52,6 +52,11 @@ impl SyncRecordTester for StocktakeRecordTester {
             inventory_addition_id: None,
             inventory_reduction_id: None,
             is_locked: true,
+            is_blind_count: true,
+            variance_threshold_percentage: Some(10.0),
+            count_submitted_datetime: None,
+            variance_approved_datetime: None,
+            variance_approved_by_user_id: None,
         };
         let stocktake_line_row = StocktakeLineRow {
             id: uuid(),
@@ -70,6 +75,7 @@ impl SyncRecordTester for StocktakeRecordTester {
             sell_price_per_pack: Some(0.0),
             note: None,
             inventory_adjustment_reason_id: None,
+            second_counted_number_of_packs: Some(98.0),
         };
         result.push(TestStepData {
             central_upsert: json!({"item": [{
@@ -0,0 +1,48 @@
+use repository::{AuditLogRecordType, AuditLogRow};
+use serde_json::json;
+use util::Defaults;
+
+use super::{TestSyncIncomingRecord, TestSyncOutgoingRecord};
+
+const TABLE_NAME: &str = "audit_log";
+
+const AUDIT_LOG1: (&str, &str) = (
+    "5b9c7d7e-6d2b-4f3a-9a1e-2c6f0f4f7a10",
+    r#"{
+        "id": "5b9c7d7e-6d2b-4f3a-9a1e-2c6f0f4f7a10",
+        "record_type": "STOCK_LINE",
+        "record_id": "stock_line_a",
+        "user_id": "user_account_a",
+        "store_id": "store_a",
+        "datetime": "2020-01-22T15:16:00",
+        "changes": "{\"batch\":{\"from\":null,\"to\":\"B1\"}}"
+    }"#,
+);
+
+fn audit_log1() -> AuditLogRow {
+    AuditLogRow {
+        id: AUDIT_LOG1.0.to_string(),
+        record_type: AuditLogRecordType::StockLine,
+        record_id: "stock_line_a".to_string(),
+        user_id: Some("user_account_a".to_string()),
+        store_id: "store_a".to_string(),
+        datetime: Defaults::naive_date_time(),
+        changes: r#"{"batch":{"from":null,"to":"B1"}}"#.to_string(),
+    }
+}
+
+pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
+    vec![TestSyncIncomingRecord::new_pull_upsert(
+        TABLE_NAME,
+        AUDIT_LOG1,
+        audit_log1(),
+    )]
+}
+
+pub(crate) fn test_v6_records() -> Vec<TestSyncOutgoingRecord> {
+    vec![TestSyncOutgoingRecord {
+        table_name: TABLE_NAME.to_string(),
+        record_id: AUDIT_LOG1.0.to_string(),
+        push_data: json!(audit_log1()),
+    }]
+}
@@ -258,6 +258,8 @@ pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
                 default_pack_size: 1.0,
                 is_active: true,
                 is_vaccine: false,
+                heat_stability_threshold: None,
+                heat_stability_budget_minutes: None,
             },
         ),
         TestSyncIncomingRecord::new_pull_upsert(
@@ -273,6 +275,8 @@ pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
                 default_pack_size: 2.0,
                 is_active: true,
                 is_vaccine: false,
+                heat_stability_threshold: None,
+                heat_stability_budget_minutes: None,
             },
         ),
         TestSyncIncomingRecord::new_pull_upsert(
@@ -291,6 +295,8 @@ pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
                 default_pack_size: 1.0,
                 is_active: true,
                 is_vaccine: true,
+                heat_stability_threshold: None,
+                heat_stability_budget_minutes: None,
             },
         ),
     ]
@@ -11,11 +11,13 @@ pub(crate) mod asset_log;
 pub(crate) mod asset_log_reason;
 pub(crate) mod asset_property;
 pub(crate) mod asset_type;
+pub(crate) mod audit_log;
 pub(crate) mod barcode;
 pub(crate) mod currency;
 pub(crate) mod invoice;
 pub(crate) mod invoice_line;
 pub(crate) mod item;
+pub(crate) mod item_oms_fields;
 pub(crate) mod location;
 pub(crate) mod location_movement;
 pub(crate) mod master_list;
@@ -35,6 +37,8 @@ pub(crate) mod property;
 pub(crate) mod reason;
 pub(crate) mod report;
 pub(crate) mod requisition;
+pub(crate) mod requisition_approval;
+pub(crate) mod requisition_approval_level;
 pub(crate) mod requisition_line;
 pub(crate) mod sensor;
 pub(crate) mod special;
@@ -74,6 +78,7 @@ pub(crate) fn get_all_pull_upsert_central_test_records() -> Vec<TestSyncIncoming
     // Open mSupply Central
     test_records.append(&mut pack_variant::test_pull_upsert_records());
     test_records.append(&mut name_oms_fields::test_pull_upsert_records());
+    test_records.append(&mut item_oms_fields::test_pull_upsert_records());
     test_records.append(&mut asset_class::test_pull_upsert_records());
     test_records.append(&mut asset_category::test_pull_upsert_records());
     test_records.append(&mut asset_type::test_pull_upsert_records());
@@ -85,6 +90,9 @@ pub(crate) fn get_all_pull_upsert_central_test_records() -> Vec<TestSyncIncoming
     test_records.append(&mut asset_property::test_pull_upsert_records());
     test_records.append(&mut property::test_pull_upsert_records());
     test_records.append(&mut name_property::test_pull_upsert_records());
+    test_records.append(&mut audit_log::test_pull_upsert_records());
+    test_records.append(&mut requisition_approval_level::test_pull_upsert_records());
+    test_records.append(&mut requisition_approval::test_pull_upsert_records());
     test_records
 }
 
@@ -133,6 +141,7 @@ pub(crate) fn get_all_pull_delete_remote_test_records() -> Vec<TestSyncIncomingR
     test_records.append(&mut invoice::test_pull_delete_records());
     test_records.append(&mut invoice_line::test_pull_delete_records());
     test_records.append(&mut name_tag_join::test_pull_delete_records());
+    test_records.append(&mut requisition_approval_level::test_pull_delete_records());
 
     test_records
 }
@@ -173,8 +182,12 @@ pub(crate) fn get_all_sync_v6_records() -> Vec<TestSyncOutgoingRecord> {
     test_records.append(&mut sync_file_reference::test_v6_records());
     test_records.append(&mut asset_property::test_v6_central_push_records());
     test_records.append(&mut name_oms_fields::test_v6_central_push_records());
+    test_records.append(&mut item_oms_fields::test_v6_central_push_records());
     test_records.append(&mut property::test_v6_central_push_records());
     test_records.append(&mut name_property::test_v6_central_push_records());
+    test_records.append(&mut audit_log::test_v6_records());
+    test_records.append(&mut requisition_approval_level::test_v6_records());
+    test_records.append(&mut requisition_approval::test_v6_records());
 
     test_records
 }
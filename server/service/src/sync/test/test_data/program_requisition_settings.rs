@@ -138,6 +138,7 @@ pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
                     name_tag_id: mock_name_tag_1().id,
                     program_id: MASTER_LIST_WITH_PROGRAM_1.0.to_owned(),
                     period_schedule_id: mock_period_schedule_1().id,
+                    consumption_forecast_method: None,
                 }),
                 IntegrationOperation::upsert(ProgramRequisitionOrderTypeRow {
                     id: MASTER_LIST_WITH_PROGRAM_1.0.to_owned()
@@ -149,6 +150,8 @@ pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
                     threshold_mos: 3.0,
                     max_mos: 3.0,
                     max_order_per_period: 1,
+                    is_emergency: false,
+                    max_items_in_emergency_order: 0,
                 }),
                 IntegrationOperation::upsert(ProgramRequisitionOrderTypeRow {
                     id: MASTER_LIST_WITH_PROGRAM_1.0.to_owned()
@@ -160,12 +163,15 @@ pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
                     threshold_mos: 3.0,
                     max_mos: 3.0,
                     max_order_per_period: 1,
+                    is_emergency: false,
+                    max_items_in_emergency_order: 0,
                 }),
                 IntegrationOperation::upsert(ProgramRequisitionSettingsRow {
                     id: MASTER_LIST_WITH_PROGRAM_1.0.to_owned() + &mock_name_tag_2().id,
                     name_tag_id: mock_name_tag_2().id,
                     program_id: MASTER_LIST_WITH_PROGRAM_1.0.to_owned(),
                     period_schedule_id: mock_period_schedule_1().id,
+                    consumption_forecast_method: None,
                 }),
                 IntegrationOperation::upsert(ProgramRequisitionOrderTypeRow {
                     id: MASTER_LIST_WITH_PROGRAM_1.0.to_owned()
@@ -177,12 +183,15 @@ pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
                     threshold_mos: 4.0,
                     max_mos: 4.0,
                     max_order_per_period: 1,
+                    is_emergency: false,
+                    max_items_in_emergency_order: 0,
                 }),
                 IntegrationOperation::upsert(ProgramRequisitionSettingsRow {
                     id: MASTER_LIST_WITH_PROGRAM_1.0.to_owned() + &mock_name_tag_3().id,
                     name_tag_id: mock_name_tag_3().id,
                     program_id: MASTER_LIST_WITH_PROGRAM_1.0.to_owned(),
                     period_schedule_id: mock_period_schedule_2().id,
+                    consumption_forecast_method: None,
                 }),
                 IntegrationOperation::upsert(ProgramRequisitionOrderTypeRow {
                     id: MASTER_LIST_WITH_PROGRAM_1.0.to_owned()
@@ -194,6 +203,8 @@ pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
                     threshold_mos: 2.0,
                     max_mos: 2.0,
                     max_order_per_period: 3,
+                    is_emergency: false,
+                    max_items_in_emergency_order: 0,
                 }),
             ]),
             sync_buffer_row: SyncBufferRow {
@@ -224,6 +235,7 @@ pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
                     name_tag_id: mock_name_tag_1().id,
                     program_id: MASTER_LIST_WITH_PROGRAM_2.0.to_owned(),
                     period_schedule_id: mock_period_schedule_1().id,
+                    consumption_forecast_method: None,
                 }),
             ]),
             sync_buffer_row: SyncBufferRow {
@@ -0,0 +1,48 @@
+use repository::{ApprovalStatusType, RequisitionApprovalRow};
+use serde_json::json;
+use util::Defaults;
+
+use super::{TestSyncIncomingRecord, TestSyncOutgoingRecord};
+
+const TABLE_NAME: &str = "requisition_approval";
+
+const REQUISITION_APPROVAL1: (&str, &str) = (
+    "7c1d3b5e-2a4f-4e6b-9c8d-0e1f2a3b4c51",
+    r#"{
+        "id": "7c1d3b5e-2a4f-4e6b-9c8d-0e1f2a3b4c51",
+        "requisition_id": "mock_new_response_requisition",
+        "level": 1,
+        "user_id": "user_account_a",
+        "status": "APPROVED",
+        "comment": "Quantities checked",
+        "created_datetime": "2020-01-22T15:16:00"
+    }"#,
+);
+
+fn requisition_approval1() -> RequisitionApprovalRow {
+    RequisitionApprovalRow {
+        id: REQUISITION_APPROVAL1.0.to_string(),
+        requisition_id: "mock_new_response_requisition".to_string(),
+        level: 1,
+        user_id: "user_account_a".to_string(),
+        status: ApprovalStatusType::Approved,
+        comment: Some("Quantities checked".to_string()),
+        created_datetime: Defaults::naive_date_time(),
+    }
+}
+
+pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
+    vec![TestSyncIncomingRecord::new_pull_upsert(
+        TABLE_NAME,
+        REQUISITION_APPROVAL1,
+        requisition_approval1(),
+    )]
+}
+
+pub(crate) fn test_v6_records() -> Vec<TestSyncOutgoingRecord> {
+    vec![TestSyncOutgoingRecord {
+        table_name: TABLE_NAME.to_string(),
+        record_id: REQUISITION_APPROVAL1.0.to_string(),
+        push_data: json!(requisition_approval1()),
+    }]
+}
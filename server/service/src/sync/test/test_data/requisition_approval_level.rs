@@ -0,0 +1,90 @@
+use repository::{RequisitionApprovalLevelRow, RequisitionApprovalLevelRowDelete};
+use serde_json::json;
+
+use super::{TestSyncIncomingRecord, TestSyncOutgoingRecord};
+
+const TABLE_NAME: &str = "requisition_approval_level";
+
+const REQUISITION_APPROVAL_LEVEL1: (&str, &str) = (
+    "0f3a0c8e-9b5e-4d1c-8f0a-1c7d2e4b6a01",
+    r#"{
+        "id": "0f3a0c8e-9b5e-4d1c-8f0a-1c7d2e4b6a01",
+        "store_id": "store_a",
+        "program_id": null,
+        "level": 1,
+        "name": "Supervisor",
+        "approver_user_id": "user_account_a"
+    }"#,
+);
+
+fn requisition_approval_level1() -> RequisitionApprovalLevelRow {
+    RequisitionApprovalLevelRow {
+        id: REQUISITION_APPROVAL_LEVEL1.0.to_string(),
+        store_id: "store_a".to_string(),
+        program_id: None,
+        level: 1,
+        name: "Supervisor".to_string(),
+        approver_user_id: Some("user_account_a".to_string()),
+    }
+}
+
+const REQUISITION_APPROVAL_LEVEL2: (&str, &str) = (
+    "0f3a0c8e-9b5e-4d1c-8f0a-1c7d2e4b6a02",
+    r#"{
+        "id": "0f3a0c8e-9b5e-4d1c-8f0a-1c7d2e4b6a02",
+        "store_id": "store_a",
+        "program_id": null,
+        "level": 2,
+        "name": "Manager",
+        "approver_user_id": null
+    }"#,
+);
+
+fn requisition_approval_level2() -> RequisitionApprovalLevelRow {
+    RequisitionApprovalLevelRow {
+        id: REQUISITION_APPROVAL_LEVEL2.0.to_string(),
+        store_id: "store_a".to_string(),
+        program_id: None,
+        level: 2,
+        name: "Manager".to_string(),
+        approver_user_id: None,
+    }
+}
+
+pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
+    vec![
+        TestSyncIncomingRecord::new_pull_upsert(
+            TABLE_NAME,
+            REQUISITION_APPROVAL_LEVEL1,
+            requisition_approval_level1(),
+        ),
+        TestSyncIncomingRecord::new_pull_upsert(
+            TABLE_NAME,
+            REQUISITION_APPROVAL_LEVEL2,
+            requisition_approval_level2(),
+        ),
+    ]
+}
+
+pub(crate) fn test_pull_delete_records() -> Vec<TestSyncIncomingRecord> {
+    vec![TestSyncIncomingRecord::new_pull_delete(
+        TABLE_NAME,
+        REQUISITION_APPROVAL_LEVEL2.0,
+        RequisitionApprovalLevelRowDelete(REQUISITION_APPROVAL_LEVEL2.0.to_string()),
+    )]
+}
+
+pub(crate) fn test_v6_records() -> Vec<TestSyncOutgoingRecord> {
+    vec![
+        TestSyncOutgoingRecord {
+            table_name: TABLE_NAME.to_string(),
+            record_id: REQUISITION_APPROVAL_LEVEL1.0.to_string(),
+            push_data: json!(requisition_approval_level1()),
+        },
+        TestSyncOutgoingRecord {
+            table_name: TABLE_NAME.to_string(),
+            record_id: REQUISITION_APPROVAL_LEVEL2.0.to_string(),
+            push_data: json!(requisition_approval_level2()),
+        },
+    ]
+}
@@ -62,6 +62,12 @@ fn requisition_line_request_pull_record() -> TestSyncIncomingRecord {
             approved_quantity: 0.0,
             approval_comment: None,
             item_name: "Ibuprofen 200mg tablets".to_string(),
+            opening_balance: None,
+            quantity_received: None,
+            quantity_dispensed: None,
+            losses_and_adjustments: None,
+            closing_balance: None,
+            days_out_of_stock: None,
         },
     )
 }
@@ -82,7 +88,13 @@ fn requisition_line_request_push_record() -> TestSyncOutgoingRecord {
             snapshot_datetime: None,
             approved_quantity: 0.0,
             approval_comment: None,
-            item_name: "Ibuprofen 200mg tablets".to_string()
+            item_name: "Ibuprofen 200mg tablets".to_string(),
+            opening_balance: None,
+            quantity_received: None,
+            quantity_dispensed: None,
+            losses_and_adjustments: None,
+            closing_balance: None,
+            days_out_of_stock: None,
         }),
     }
 }
@@ -120,7 +132,13 @@ const REQUISITION_LINE_OM_FIELD: (&str, &str) = (
         "requestedPackSize": 0,
         "approved_quantity": 0,
         "authoriser_comment": "approval comment",
-        "om_snapshot_datetime": "2022-04-04T14:48:11"
+        "om_snapshot_datetime": "2022-04-04T14:48:11",
+        "om_opening_balance": 20,
+        "om_quantity_received": 30,
+        "om_quantity_dispensed": 35,
+        "om_losses_and_adjustments": -5,
+        "om_closing_balance": 10,
+        "om_days_out_of_stock": 2
     }"#,
 );
 fn requisition_line_om_fields_pull_record() -> TestSyncIncomingRecord {
@@ -146,6 +164,12 @@ fn requisition_line_om_fields_pull_record() -> TestSyncIncomingRecord {
                     .unwrap(),
             ),
             item_name: "Ibuprofen 200mg tablets".to_string(),
+            opening_balance: Some(20.0),
+            quantity_received: Some(30.0),
+            quantity_dispensed: Some(35.0),
+            losses_and_adjustments: Some(-5.0),
+            closing_balance: Some(10.0),
+            days_out_of_stock: Some(2),
         },
     )
 }
@@ -172,6 +196,12 @@ fn requisition_line_om_fields_push_record() -> TestSyncOutgoingRecord {
                     .and_hms_opt(14, 48, 11)
                     .unwrap()
             ),
+            opening_balance: Some(20.0),
+            quantity_received: Some(30.0),
+            quantity_dispensed: Some(35.0),
+            losses_and_adjustments: Some(-5.0),
+            closing_balance: Some(10.0),
+            days_out_of_stock: Some(2),
         }),
     }
 }
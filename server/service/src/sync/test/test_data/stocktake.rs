@@ -52,6 +52,11 @@ fn stocktake_pull_record() -> TestSyncIncomingRecord {
             inventory_reduction_id: Some("inbound_shipment_b".to_string()),
             is_locked: false,
             stocktake_date: Some(NaiveDate::from_ymd_opt(2021, 7, 30).unwrap()),
+            is_blind_count: false,
+            variance_threshold_percentage: None,
+            count_submitted_datetime: None,
+            variance_approved_datetime: None,
+            variance_approved_by_user_id: None,
         },
     )
 }
@@ -79,6 +84,11 @@ fn stocktake_push_record() -> TestSyncOutgoingRecord {
                     .and_time(NaiveTime::from_num_seconds_from_midnight_opt(47061, 0).unwrap())
             ),
             finalised_datetime: None,
+            is_blind_count: false,
+            variance_threshold_percentage: None,
+            count_submitted_datetime: None,
+            variance_approved_datetime: None,
+            variance_approved_by_user_id: None,
         }),
     }
 }
@@ -132,6 +142,11 @@ fn stocktake_om_field_pull_record() -> TestSyncIncomingRecord {
             inventory_reduction_id: None,
             is_locked: false,
             stocktake_date: Some(NaiveDate::from_ymd_opt(2021, 7, 30).unwrap()),
+            is_blind_count: false,
+            variance_threshold_percentage: None,
+            count_submitted_datetime: None,
+            variance_approved_datetime: None,
+            variance_approved_by_user_id: None,
         },
     )
 }
@@ -165,6 +180,11 @@ fn stocktake_om_field_push_record() -> TestSyncOutgoingRecord {
                     .and_hms_opt(15, 15, 15)
                     .unwrap()
             ),
+            is_blind_count: false,
+            variance_threshold_percentage: None,
+            count_submitted_datetime: None,
+            variance_approved_datetime: None,
+            variance_approved_by_user_id: None,
         }),
     }
 }
@@ -54,6 +54,7 @@ fn stocktake_line_pull_record() -> TestSyncIncomingRecord {
             sell_price_per_pack: Some(15.0),
             note: None,
             inventory_adjustment_reason_id: None,
+            second_counted_number_of_packs: None,
         },
     )
 }
@@ -79,6 +80,7 @@ fn stocktake_line_push_record() -> TestSyncOutgoingRecord {
             sell_price: 15.0,
             note: None,
             inventory_adjustment_reason_id: None,
+            second_counted_number_of_packs: None,
         }),
     }
 }
@@ -131,6 +133,7 @@ fn stocktake_line_om_field_pull_record() -> TestSyncIncomingRecord {
             sell_price_per_pack: Some(15.0),
             note: Some("om note".to_string()),
             inventory_adjustment_reason_id: None,
+            second_counted_number_of_packs: None,
         },
     )
 }
@@ -156,6 +159,7 @@ fn stocktake_line_om_field_push_record() -> TestSyncOutgoingRecord {
             sell_price: 15.0,
             note: Some("om note".to_string()),
             inventory_adjustment_reason_id: None,
+            second_counted_number_of_packs: None,
         }),
     }
 }
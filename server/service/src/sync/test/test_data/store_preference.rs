@@ -1,5 +1,7 @@
 use crate::sync::test::TestSyncIncomingRecord;
-use repository::{StorePreferenceRow, StorePreferenceType};
+use repository::{
+    AllocationStrategy, ConsumptionForecastMethod, StorePreferenceRow, StorePreferenceType,
+};
 
 const TABLE_NAME: &str = "pref";
 
@@ -64,7 +66,9 @@ const STORE_PREFERENCE_1: (&str, &str) = (
         "monthsItemsExpire": 2,
         "boxPrefix": "",
         "boxPercentageSpace": 0,
-        "omSupplyUsesProgramModule": true
+        "omSupplyUsesProgramModule": true,
+        "omConsumptionForecastMethod": "stock_out_adjusted",
+        "omAllocationStrategy": "location"
     }
 }"#,
 );
@@ -148,6 +152,8 @@ pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
                 om_program_module: true,
                 vaccine_module: false,
                 issue_in_foreign_currency: true,
+                consumption_forecast_method: ConsumptionForecastMethod::StockOutAdjusted,
+                allocation_strategy: AllocationStrategy::Location,
             },
         ),
         TestSyncIncomingRecord::new_pull_upsert(
@@ -163,6 +169,9 @@ pub(crate) fn test_pull_upsert_records() -> Vec<TestSyncIncomingRecord> {
                 om_program_module: false,
                 vaccine_module: true,
                 issue_in_foreign_currency: false,
+                // Missing, should default to simple average
+                consumption_forecast_method: ConsumptionForecastMethod::SimpleAverage,
+                allocation_strategy: AllocationStrategy::Fefo,
             },
         ),
     ]
@@ -1,4 +1,6 @@
-use repository::{ItemRow, ItemRowDelete, ItemType, StorageConnection, SyncBufferRow};
+use repository::{
+    ItemRow, ItemRowDelete, ItemRowRepository, ItemType, StorageConnection, SyncBufferRow,
+};
 use serde::Deserialize;
 
 use crate::sync::{sync_serde::empty_str_as_option_string, translations::unit::UnitTranslation};
@@ -57,10 +59,14 @@ impl SyncTranslation for ItemTranslation {
 
     fn try_translate_from_upsert_sync_record(
         &self,
-        _: &StorageConnection,
+        connection: &StorageConnection,
         sync_record: &SyncBufferRow,
     ) -> Result<PullTranslateResult, anyhow::Error> {
         let data = serde_json::from_str::<LegacyItemRow>(&sync_record.data)?;
+        // Fields set on open mSupply central (item_oms_fields) are kept
+        let oms_fields = ItemRowRepository::new(connection)
+            .find_one_oms_fields_by_id(&data.ID)?
+            .unwrap_or_default();
 
         let result = ItemRow {
             id: data.ID,
@@ -72,6 +78,8 @@ impl SyncTranslation for ItemTranslation {
             default_pack_size: data.default_pack_size,
             is_active: true,
             is_vaccine: data.is_vaccine,
+            heat_stability_threshold: oms_fields.heat_stability_threshold,
+            heat_stability_budget_minutes: oms_fields.heat_stability_budget_minutes,
         };
 
         Ok(PullTranslateResult::upsert(result))
@@ -7,6 +7,7 @@ pub(crate) mod asset_log;
 pub(crate) mod asset_log_reason;
 pub(crate) mod asset_property;
 pub(crate) mod asset_type;
+pub(crate) mod audit_log;
 pub(crate) mod barcode;
 pub(crate) mod clinician;
 pub(crate) mod clinician_store_join;
@@ -17,6 +18,7 @@ pub(crate) mod form_schema;
 pub(crate) mod invoice;
 pub(crate) mod invoice_line;
 pub(crate) mod item;
+pub(crate) mod item_oms_fields;
 pub(crate) mod location;
 pub(crate) mod location_movement;
 pub(crate) mod master_list;
@@ -36,6 +38,8 @@ pub(crate) mod property;
 pub(crate) mod reason;
 pub(crate) mod report;
 pub(crate) mod requisition;
+pub(crate) mod requisition_approval;
+pub(crate) mod requisition_approval_level;
 pub(crate) mod requisition_line;
 pub(crate) mod sensor;
 pub(crate) mod special;
@@ -108,6 +112,7 @@ pub(crate) fn all_translators() -> SyncTranslators {
         pack_variant::boxed(),
         // Special translations
         name_oms_fields::boxed(),
+        item_oms_fields::boxed(),
         special::name_to_name_store_join::boxed(),
         // Merge
         special::name_merge::boxed(),
@@ -122,6 +127,10 @@ pub(crate) fn all_translators() -> SyncTranslators {
         asset_log::boxed(),
         asset_log_reason::boxed(),
         asset_property::boxed(),
+        audit_log::boxed(),
+        // Requisition approval chains
+        requisition_approval_level::boxed(),
+        requisition_approval::boxed(),
         //Sync file reference
         sync_file_reference::boxed(),
     ]
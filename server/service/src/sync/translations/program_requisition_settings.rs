@@ -13,7 +13,8 @@ use crate::sync::translations::{
 };
 
 use super::{
-    master_list::MasterListTranslation, IntegrationOperation, PullTranslateResult, SyncTranslation,
+    master_list::MasterListTranslation, store_preference::LegacyConsumptionForecastMethod,
+    IntegrationOperation, PullTranslateResult, SyncTranslation,
 };
 
 #[allow(non_snake_case)]
@@ -39,6 +40,8 @@ struct LegacyProgramSettings {
 struct LegacyProgramSettingsStoreTag {
     order_types: Option<Vec<LegacyOrderType>>,
     period_schedule_name: String,
+    #[serde(default)]
+    consumption_forecast_method: Option<LegacyConsumptionForecastMethod>,
 }
 
 #[derive(Deserialize, Clone)]
@@ -50,6 +53,32 @@ struct LegacyOrderType {
     max_mos: f64,
     #[serde(rename = "maxOrdersPerPeriod")]
     max_order_per_period: i32,
+    #[serde(rename = "isEmergency")]
+    #[serde(default)]
+    is_emergency: bool,
+    /// Maximum number of items in an emergency order, mSupply sends an empty string when not set
+    #[serde(rename = "maxEmergencyOrders")]
+    #[serde(default)]
+    max_items_in_emergency_order: LegacyMaxEmergencyOrders,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(untagged)]
+enum LegacyMaxEmergencyOrders {
+    Number(i32),
+    Text(String),
+    #[default]
+    None,
+}
+
+impl LegacyMaxEmergencyOrders {
+    fn to_domain(&self) -> i32 {
+        match self {
+            LegacyMaxEmergencyOrders::Number(number) => *number,
+            LegacyMaxEmergencyOrders::Text(text) => text.parse().unwrap_or(0),
+            LegacyMaxEmergencyOrders::None => 0,
+        }
+    }
 }
 // Needs to be added to all_translators()
 #[deny(dead_code)]
@@ -210,6 +239,10 @@ fn generate_requisition_program(
                 name_tag_id: name_tag.id.clone(),
                 program_id: master_list.id.clone(),
                 period_schedule_id: period_schedule.id.clone(),
+                consumption_forecast_method: settings
+                    .consumption_forecast_method
+                    .clone()
+                    .map(LegacyConsumptionForecastMethod::to_domain),
             };
 
             program_requisition_settings_rows.push(program_requisition_settings_row.clone());
@@ -230,6 +263,10 @@ fn generate_requisition_program(
                         threshold_mos: order_type.threshold_mos,
                         max_mos: order_type.max_mos,
                         max_order_per_period: order_type.max_order_per_period,
+                        is_emergency: order_type.is_emergency,
+                        max_items_in_emergency_order: order_type
+                            .max_items_in_emergency_order
+                            .to_domain(),
                     };
 
                     program_requisition_order_type_rows.push(program_requisition_order_type_row);
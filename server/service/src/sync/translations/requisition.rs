@@ -159,6 +159,8 @@ pub struct LegacyRequisitionRow {
     pub periodID: Option<String>,
     #[serde(deserialize_with = "empty_str_as_option_string")]
     pub programID: Option<String>,
+    #[serde(default)]
+    pub is_emergency: bool,
 }
 // Needs to be added to all_translators()
 #[deny(dead_code)]
@@ -266,6 +268,7 @@ impl SyncTranslation for RequisitionTranslation {
             program_id,
             period_id: data.periodID,
             order_type: data.orderType,
+            is_emergency: data.is_emergency,
         };
 
         Ok(PullTranslateResult::upsert(result))
@@ -311,6 +314,7 @@ impl SyncTranslation for RequisitionTranslation {
                     program_id,
                     period_id,
                     order_type,
+                    is_emergency,
                 },
             name_row,
             ..
@@ -363,6 +367,7 @@ impl SyncTranslation for RequisitionTranslation {
             programID: program_id,
             periodID: period_id,
             orderType: order_type,
+            is_emergency,
         };
 
         Ok(PushTranslateResult::upsert(
@@ -44,6 +44,25 @@ pub struct LegacyRequisitionLineRow {
 
     #[serde(rename = "itemName")]
     pub item_name: String,
+
+    #[serde(rename = "om_opening_balance")]
+    #[serde(default)]
+    pub opening_balance: Option<f64>,
+    #[serde(rename = "om_quantity_received")]
+    #[serde(default)]
+    pub quantity_received: Option<f64>,
+    #[serde(rename = "om_quantity_dispensed")]
+    #[serde(default)]
+    pub quantity_dispensed: Option<f64>,
+    #[serde(rename = "om_losses_and_adjustments")]
+    #[serde(default)]
+    pub losses_and_adjustments: Option<f64>,
+    #[serde(rename = "om_closing_balance")]
+    #[serde(default)]
+    pub closing_balance: Option<f64>,
+    #[serde(rename = "om_days_out_of_stock")]
+    #[serde(default)]
+    pub days_out_of_stock: Option<i32>,
 }
 // Needs to be added to all_translators()
 #[deny(dead_code)]
@@ -89,6 +108,12 @@ impl SyncTranslation for RequisitionLineTranslation {
             approved_quantity: data.approved_quantity,
             approval_comment: data.approval_comment,
             item_name: data.item_name,
+            opening_balance: data.opening_balance,
+            quantity_received: data.quantity_received,
+            quantity_dispensed: data.quantity_dispensed,
+            losses_and_adjustments: data.losses_and_adjustments,
+            closing_balance: data.closing_balance,
+            days_out_of_stock: data.days_out_of_stock,
         };
 
         Ok(PullTranslateResult::upsert(result))
@@ -124,6 +149,12 @@ impl SyncTranslation for RequisitionLineTranslation {
             approved_quantity,
             approval_comment,
             item_name,
+            opening_balance,
+            quantity_received,
+            quantity_dispensed,
+            losses_and_adjustments,
+            closing_balance,
+            days_out_of_stock,
         } = RequisitionLineRowRepository::new(connection)
             .find_one_by_id(&changelog.record_id)?
             .ok_or(anyhow::Error::msg(format!(
@@ -153,6 +184,12 @@ impl SyncTranslation for RequisitionLineTranslation {
             approved_quantity,
             approval_comment,
             item_name,
+            opening_balance,
+            quantity_received,
+            quantity_dispensed,
+            losses_and_adjustments,
+            closing_balance,
+            days_out_of_stock,
         };
 
         Ok(PushTranslateResult::upsert(
@@ -144,6 +144,8 @@ impl SyncTranslation for SensorTranslation {
             SensorType::BlueMaestro => "BLUE_MAESTRO",
             SensorType::Laird => "LAIRD",
             SensorType::Berlinger => "BERLINGER",
+            SensorType::LogTag => "LOG_TAG",
+            SensorType::Csv => "CSV",
         }
         .to_string();
 
@@ -70,6 +70,29 @@ pub struct LegacyStocktakeRow {
     #[serde(deserialize_with = "empty_str_as_option")]
     #[serde(default)]
     pub finalised_datetime: Option<NaiveDateTime>,
+
+    #[serde(rename = "om_is_blind_count")]
+    #[serde(default)]
+    pub is_blind_count: bool,
+
+    #[serde(rename = "om_variance_threshold_percentage")]
+    #[serde(default)]
+    pub variance_threshold_percentage: Option<f64>,
+
+    #[serde(rename = "om_count_submitted_datetime")]
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    pub count_submitted_datetime: Option<NaiveDateTime>,
+
+    #[serde(rename = "om_variance_approved_datetime")]
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    pub variance_approved_datetime: Option<NaiveDateTime>,
+
+    #[serde(rename = "om_variance_approved_by_user_id")]
+    #[serde(deserialize_with = "empty_str_as_option_string")]
+    #[serde(default)]
+    pub variance_approved_by_user_id: Option<String>,
 }
 
 // Needs to be added to all_translators()
@@ -136,6 +159,11 @@ impl SyncTranslation for StocktakeTranslation {
             inventory_reduction_id: data.inventory_reduction_id,
             stocktake_date: data.stocktake_date,
             is_locked: data.is_locked,
+            is_blind_count: data.is_blind_count,
+            variance_threshold_percentage: data.variance_threshold_percentage,
+            count_submitted_datetime: data.count_submitted_datetime,
+            variance_approved_datetime: data.variance_approved_datetime,
+            variance_approved_by_user_id: data.variance_approved_by_user_id,
         };
 
         Ok(PullTranslateResult::upsert(result))
@@ -160,6 +188,11 @@ impl SyncTranslation for StocktakeTranslation {
             stocktake_date,
             inventory_addition_id,
             inventory_reduction_id,
+            is_blind_count,
+            variance_threshold_percentage,
+            count_submitted_datetime,
+            variance_approved_datetime,
+            variance_approved_by_user_id,
         } = StocktakeRowRepository::new(connection)
             .find_one_by_id(&changelog.record_id)?
             .ok_or(anyhow::Error::msg("Stocktake row not found"))?;
@@ -180,6 +213,11 @@ impl SyncTranslation for StocktakeTranslation {
             stock_take_time: created_datetime.time(),
             created_datetime: Some(created_datetime),
             finalised_datetime,
+            is_blind_count,
+            variance_threshold_percentage,
+            count_submitted_datetime,
+            variance_approved_datetime,
+            variance_approved_by_user_id,
         };
 
         Ok(PushTranslateResult::upsert(
@@ -47,6 +47,9 @@ pub struct LegacyStocktakeLineRow {
     #[serde(rename = "optionID")]
     #[serde(deserialize_with = "empty_str_as_option_string")]
     pub inventory_adjustment_reason_id: Option<String>,
+    #[serde(rename = "om_second_counted_number_of_packs")]
+    #[serde(default)]
+    pub second_counted_number_of_packs: Option<f64>,
 }
 // Needs to be added to all_translators()
 #[deny(dead_code)]
@@ -97,6 +100,7 @@ impl SyncTranslation for StocktakeLineTranslation {
             sell_price,
             note,
             inventory_adjustment_reason_id,
+            second_counted_number_of_packs,
         } = serde_json::from_str::<LegacyStocktakeLineRow>(&sync_record.data)?;
 
         // TODO is this correct?
@@ -124,6 +128,7 @@ impl SyncTranslation for StocktakeLineTranslation {
             sell_price_per_pack: Some(sell_price),
             note,
             inventory_adjustment_reason_id,
+            second_counted_number_of_packs,
         };
 
         Ok(PullTranslateResult::upsert(result))
@@ -163,6 +168,7 @@ impl SyncTranslation for StocktakeLineTranslation {
                     sell_price_per_pack,
                     note,
                     inventory_adjustment_reason_id,
+                    second_counted_number_of_packs,
                 },
             item,
             stock_line,
@@ -188,6 +194,7 @@ impl SyncTranslation for StocktakeLineTranslation {
             sell_price: sell_price_per_pack.unwrap_or(0.0),
             note,
             inventory_adjustment_reason_id,
+            second_counted_number_of_packs,
         };
 
         Ok(PushTranslateResult::upsert(
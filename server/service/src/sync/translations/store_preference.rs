@@ -1,4 +1,7 @@
-use repository::{StorageConnection, StorePreferenceRow, StorePreferenceType, SyncBufferRow};
+use repository::{
+    AllocationStrategy, ConsumptionForecastMethod, StorageConnection, StorePreferenceRow,
+    StorePreferenceType, SyncBufferRow,
+};
 use serde::{Deserialize, Serialize};
 
 use super::{PullTranslateResult, SyncTranslation};
@@ -39,6 +42,56 @@ pub struct LegacyPrefData {
     #[serde(default)]
     #[serde(rename = "can_issue_in_foreign_currency")]
     pub issue_in_foreign_currency: bool,
+    #[serde(default)]
+    #[serde(rename = "omConsumptionForecastMethod")]
+    pub consumption_forecast_method: LegacyConsumptionForecastMethod,
+    #[serde(default)]
+    #[serde(rename = "omAllocationStrategy")]
+    pub allocation_strategy: LegacyAllocationStrategy,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LegacyConsumptionForecastMethod {
+    #[default]
+    #[serde(other)]
+    SimpleAverage,
+    StockOutAdjusted,
+    ExponentialSmoothing,
+    SeasonalMovingAverage,
+}
+
+impl LegacyConsumptionForecastMethod {
+    pub(crate) fn to_domain(self) -> ConsumptionForecastMethod {
+        match self {
+            Self::SimpleAverage => ConsumptionForecastMethod::SimpleAverage,
+            Self::StockOutAdjusted => ConsumptionForecastMethod::StockOutAdjusted,
+            Self::ExponentialSmoothing => ConsumptionForecastMethod::ExponentialSmoothing,
+            Self::SeasonalMovingAverage => ConsumptionForecastMethod::SeasonalMovingAverage,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LegacyAllocationStrategy {
+    #[default]
+    #[serde(other)]
+    Fefo,
+    Fifo,
+    Location,
+    PackVariant,
+}
+
+impl LegacyAllocationStrategy {
+    fn to_domain(self) -> AllocationStrategy {
+        match self {
+            Self::Fefo => AllocationStrategy::Fefo,
+            Self::Fifo => AllocationStrategy::Fifo,
+            Self::Location => AllocationStrategy::Location,
+            Self::PackVariant => AllocationStrategy::PackVariant,
+        }
+    }
 }
 
 // Needs to be added to all_translators()
@@ -82,6 +135,8 @@ impl SyncTranslation for StorePreferenceTranslation {
             om_program_module,
             vaccine_module,
             issue_in_foreign_currency,
+            consumption_forecast_method,
+            allocation_strategy,
         } = data;
 
         let result = StorePreferenceRow {
@@ -93,6 +148,8 @@ impl SyncTranslation for StorePreferenceTranslation {
             om_program_module,
             vaccine_module,
             issue_in_foreign_currency,
+            consumption_forecast_method: consumption_forecast_method.to_domain(),
+            allocation_strategy: allocation_strategy.to_domain(),
         };
 
         Ok(PullTranslateResult::upsert(result))